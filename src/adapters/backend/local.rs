@@ -94,6 +94,11 @@ impl Backend for LocalBackend {
         fs::create_dir_all(path.as_std_path())
     }
 
+    fn remove_dir(&self, loc: &Location) -> io::Result<()> {
+        let path = local_path(loc)?;
+        fs::remove_dir(path.as_std_path())
+    }
+
     fn read_to_string(&self, loc: &Location) -> io::Result<String> {
         let path = local_path(loc)?;
         // sidecar.rs 唯一消费者：XMP/Takeout JSON 实测 < 10 KiB。不受信媒体目录
@@ -122,6 +127,30 @@ impl Backend for LocalBackend {
         fs::copy(src.as_std_path(), dst.as_std_path())
     }
 
+    fn hard_link(&self, original: &Location, link: &Location) -> io::Result<()> {
+        fs::hard_link(local_path(original)?, local_path(link)?)
+    }
+
+    fn reflink(&self, original: &Location, link: &Location) -> io::Result<()> {
+        reflink_copy::reflink(local_path(original)?, local_path(link)?)
+    }
+
+    /// Unix 上比较 `(dev, ino)`；其它平台无廉价判定手段，一律返回 `false`。
+    fn same_file(&self, a: &Location, b: &Location) -> io::Result<bool> {
+        let a = fs::metadata(local_path(a)?)?;
+        let b = fs::metadata(local_path(b)?)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            Ok(a.dev() == b.dev() && a.ino() == b.ino())
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = (a, b);
+            Ok(false)
+        }
+    }
+
     /// `std::fs::rename` 在同一文件系统时是原子操作。跨设备（`ErrorKind::CrossesDevices`）
     /// 时 std 返回 Err，fallback 走 trait default 的 `copy_file` + `remove_file` 两步。
     fn rename(&self, from: &Location, to: &Location, mkparents: bool) -> io::Result<()> {
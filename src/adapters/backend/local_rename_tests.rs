@@ -138,3 +138,50 @@ fn rename_propagates_non_crossdevice_error_without_fallback_copy() {
         "no fallback copy may leave a partial dst on non-crossdevice failure"
     );
 }
+
+#[test]
+fn hard_link_shares_content_and_refuses_existing_link() {
+    let dir = tempfile::tempdir().unwrap();
+    let original = dir.path().join("a.bin");
+    let link = dir.path().join("b.bin");
+    fs::write(&original, b"linked").unwrap();
+    let backend = LocalBackend::new();
+    backend.hard_link(&local(&original), &local(&link)).unwrap();
+    assert_eq!(fs::read(&link).unwrap(), b"linked");
+
+    let err = backend
+        .hard_link(&local(&original), &local(&link))
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+}
+
+#[cfg(unix)]
+#[test]
+fn same_file_compares_inodes_not_content() {
+    let dir = tempfile::tempdir().unwrap();
+    let original = dir.path().join("a.bin");
+    let link = dir.path().join("b.bin");
+    let copy = dir.path().join("c.bin");
+    fs::write(&original, b"linked").unwrap();
+    fs::write(&copy, b"linked").unwrap();
+    let backend = LocalBackend::new();
+    backend.hard_link(&local(&original), &local(&link)).unwrap();
+    assert!(backend.same_file(&local(&original), &local(&link)).unwrap());
+    assert!(!backend.same_file(&local(&original), &local(&copy)).unwrap());
+    assert!(
+        backend
+            .same_file(&local(&original), &local(dir.path().join("missing")))
+            .is_err()
+    );
+}
+
+#[test]
+fn hard_link_and_reflink_reject_non_local_scheme() {
+    let dir = tempfile::tempdir().unwrap();
+    let backend = LocalBackend::new();
+    let link = local(dir.path().join("x"));
+    let err = backend.hard_link(&smb_uri(), &link).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    let err = backend.reflink(&smb_uri(), &link).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
@@ -248,6 +248,30 @@ fn mkdir_p_rejects_non_local_scheme() {
     assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
 }
 
+#[test]
+fn remove_dir_only_removes_empty_dirs() {
+    let dir = tempdir().unwrap();
+    let full = dir.path().join("full");
+    fs::create_dir(&full).unwrap();
+    fs::write(full.join("x"), b"x").unwrap();
+    let empty = dir.path().join("empty");
+    fs::create_dir(&empty).unwrap();
+    let backend = LocalBackend::new();
+    backend.remove_dir(&local(&empty)).unwrap();
+    assert!(!empty.exists());
+    assert!(backend.remove_dir(&local(&full)).is_err());
+    assert!(full.join("x").exists());
+    let err = backend.remove_dir(&smb_uri()).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn remove_dir_defaults_to_unsupported() {
+    let backend = crate::adapters::backend::fake::FakeBackend::new("local");
+    let err = backend.remove_dir(&smb_uri()).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+}
+
 #[test]
 fn read_to_string_ok_and_missing() {
     let dir = tempdir().unwrap();
@@ -65,6 +65,20 @@ fn fake_basic_add_and_metadata() {
     assert_eq!(m.kind, EntryKind::File);
 }
 
+// 远端 / fake 不覆写 hard_link / reflink：default 实现一律 Unsupported。
+#[test]
+fn default_hard_link_and_reflink_are_unsupported() {
+    let b = FakeBackend::new("smb");
+    b.add_file(smb("a.jpg"), b"x".to_vec());
+    for err in [
+        b.hard_link(&smb("a.jpg"), &smb("b.jpg")).unwrap_err(),
+        b.reflink(&smb("a.jpg"), &smb("b.jpg")).unwrap_err(),
+    ] {
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+        assert!(err.to_string().contains("only supported on local"), "{err}");
+    }
+}
+
 #[test]
 fn fake_metadata_missing_returns_not_found() {
     let b = FakeBackend::new("smb");
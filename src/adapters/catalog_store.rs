@@ -0,0 +1,27 @@
+//! catalog 持久化 Gateway：[`Catalog`] 与 JSON 文件互转，读写走
+//! [`super::json_store`]（原子写盘，损坏不当空）。
+
+use crate::entities::common;
+use crate::usecases::catalog::Catalog;
+
+use super::json_store::{VersionPolicy, load_versioned_json, save_json};
+
+/// 读取 catalog；文件不存在视为空 catalog（首次使用）。
+///
+/// # Errors
+///
+/// 读盘失败或内容不是合法 catalog 时返回 `Err`——损坏的 catalog 绝不静默当空处理，
+/// 否则随后的保存会把历史记录整体覆盖掉。
+pub fn load_catalog(path: &str) -> common::Result<Catalog> {
+    load_versioned_json(path, "catalog", VersionPolicy::Unversioned)
+}
+
+/// 原子写回 catalog。
+///
+/// # Errors
+///
+/// 临时文件创建、写入或 rename 失败时返回 `Err`。
+pub fn save_catalog(path: &str, catalog: &Catalog) -> common::Result<()> {
+    // Catalog 只含 String / u64 字段，序列化不可能失败。
+    save_json(path, catalog)
+}
@@ -11,6 +11,9 @@ use crate::entities::common::Error;
 use crate::entities::common::Result;
 use crate::entities::uri::Location;
 use crate::usecases::config::config;
+use crate::usecases::cull::GroupBy;
+use crate::usecases::find_apply::ApplyAction;
+use crate::usecases::survivor::SurvivorPolicy;
 
 pub(crate) const FEATURE_CLI: &str = "cli";
 
@@ -54,10 +57,42 @@ pub enum Commands {
         #[arg(short, long)]
         output: Location,
 
-        /// Archive directory template; placeholders: `{year}` `{month}` `{day}` `{make}` `{model}` `{valuable_name}`
+        /// Archive directory template. Placeholders: `{year}` `{month}` `{day}` `{hour}` `{week}` (ISO week) `{week_year}` (ISO week-numbering year) `{quarter}` `{date:%Y-%m-%d}` `{make}` `{model}` `{lens}` `{country}` (XMP `photoshop:Country`, not derived from GPS) `{media_kind}` `{mime_type}` `{ext}` `{original_name}` `{source_priority}` `{valuable_name}` `{rating}` `{label}` `{hash8}` `{event}` `{person}` (named person with the largest face, from `faces name`; needs the face database) `{album}` (Apple Photos album). For Apple Photos library sources `{original_name}` is the file name before import. Append `:upper` / `:lower` / `:title` to change case and `|TEXT` for a fallback, e.g. `{make:lower|unknown}`
         #[arg(long)]
         archive_template: Option<String>,
 
+        /// File name template, rendered with the same placeholders as `--archive-template` plus `{hash8}` (first 8 hex chars of the SHA-512), e.g. `{date:%Y%m%d_%H%M%S}_{make}_{hash8}.{ext}`. Name clashes get a `_N` suffix; `.xmp` / `.json` sidecars are renamed alongside. Defaults to the original file name
+        #[arg(long)]
+        name_template: Option<String>,
+
+        /// Only include files whose XMP star rating (embedded or `.xmp` sidecar) is at least N (0-5). Unrated files count as 0; rejected (-1) files never pass
+        #[arg(long, value_parser = clap::value_parser!(i8).range(0..=5))]
+        min_rating: Option<i8>,
+
+        /// Only include files whose XMP color label (embedded or `.xmp` sidecar) equals LABEL, case-insensitive (e.g. `Red`)
+        #[arg(long)]
+        label: Option<String>,
+
+        /// Only include photos marked as favourites in an Apple Photos library source; files outside a library never pass
+        #[arg(long)]
+        favorite: bool,
+
+        /// Content-hash catalog file (SHA-512 + size + last known location) recording every file archived by `copy` / `move`; overrides `copy.catalog_path`
+        #[arg(long)]
+        catalog: Option<String>,
+
+        /// Skip source files whose content is already in the catalog, even if the archive has since been reorganised or is offline. Known files are never deleted by `move`
+        #[arg(long)]
+        skip_known: bool,
+
+        /// Only include images in which the face database has a face named NAME (case-insensitive; see `faces name`). Other files are left in place, even by `move`
+        #[arg(long, value_name = "NAME")]
+        person: Option<String>,
+
+        /// Face database built by `faces scan`, used by `--person` and `{person}`; overrides `backend.face.db_path`
+        #[arg(long)]
+        faces_db: Option<String>,
+
         /// Write a JSON operation report to this path
         #[arg(long)]
         report: Option<String>,
@@ -77,6 +112,42 @@ pub enum Commands {
         #[arg(short, long)]
         output: Option<Location>,
 
+        /// Survivor policy deciding which copy of each group is kept; repeatable, applied in order as tie-breakers and falling back to path order: `prefix:<PATH>`, `oldest` (mtime), `shortest` (path), `metadata` (most EXIF/XMP fields), `scheme:<local|smb|mtp|adb>`, `date-dir` (a parent directory named like a date). Without `--output`, giving a policy uncomments the deletions of non-survivors
+        #[arg(long, value_name = "POLICY")]
+        keep: Vec<SurvivorPolicy>,
+
+        /// Resolve duplicates through the storage backend instead of printing a script: `delete`, `quarantine` (move into `--quarantine`), `hardlink` / `reflink` (local only: replace the copy with a link to the survivor). SHA-512 of both files is re-verified right before each step; survivors and copies under `--output` are never touched
+        #[arg(long, value_name = "ACTION")]
+        apply: Option<ApplyAction>,
+
+        /// Quarantine directory for `--apply quarantine`; each duplicate keeps its full original path below it
+        #[arg(long)]
+        quarantine: Option<Location>,
+
+        /// Find near-duplicate images (resized, re-encoded or screenshotted copies) by perceptual hash instead of identical bytes. Each group lists the Hamming distance, resolution, size and EXIF presence of every member, highest-quality candidate first; every deletion in the script stays commented out
+        #[arg(
+            long,
+            group = "near_duplicate",
+            conflicts_with_all = ["secure", "output", "keep", "apply", "quarantine"]
+        )]
+        similar: bool,
+
+        /// Find near-duplicate MJPEG videos (old cameras, dash cams: MOV/MP4/AVI re-muxed, resized or re-compressed) by the perceptual hashes of evenly sampled frames plus duration; other MP4s are compared by their cover art only. H.264/HEVC video is not decoded, so re-encoded phone or messaging-app clips without cover art are NOT matched and are only counted as skipped. Every deletion in the script stays commented out
+        #[arg(
+            long,
+            group = "near_duplicate",
+            conflicts_with_all = ["secure", "output", "keep", "apply", "quarantine"]
+        )]
+        similar_video: bool,
+
+        /// Maximum pHash Hamming distance for `--similar` / `--similar-video` (mean per frame for videos; overrides `backend.face.phash_hamming_max`)
+        #[arg(
+            long,
+            requires = "near_duplicate",
+            value_parser = clap::value_parser!(u8).range(1..=64)
+        )]
+        phash_max: Option<u8>,
+
         /// Write a JSON operation report to this path
         #[arg(long)]
         report: Option<String>,
@@ -101,27 +172,62 @@ pub enum Commands {
         report: Option<String>,
     },
 
-    /// Cull similar/burst photos: keep the best one in source and move lower-quality copies to `output/<relative-path>/group-NNN/`, with a `BEST_<basename>` copy of the best photo placed alongside for side-by-side review. Uses perceptual hashing for grouping plus 4 ONNX models (`SCRFD`/`MobileFaceNet`/`FaceMesh`/`EyeState`) configured under `backend.face.*` for face quality scoring.
+    /// Cull similar/burst photos: keep the best one in source and move lower-quality copies to `output/<relative-path>/group-NNN/`, with a `BEST_<basename>` copy of the best photo placed alongside for side-by-side review. Groups bursts by capture time, EXIF burst IDs and perceptual hashing, then uses 4 ONNX models (`SCRFD`/`MobileFaceNet`/`FaceMesh`/`EyeState`) configured under `backend.face.*` for face quality scoring.
     Cull {
         /// Dry run, do not move files or create output directories
         #[arg(short, long)]
         dry_run: bool,
 
         /// The source directories or files (URI or local path)
-        #[arg(required = true)]
+        #[arg(required_unless_present_any = ["apply_decisions", "restore"])]
         sources: Vec<Location>,
 
         /// The output directory (URI or local path)
-        #[arg(short, long)]
-        output: Location,
+        #[arg(short, long, required_unless_present = "apply_decisions")]
+        output: Option<Location>,
 
         /// Maximum pHash Hamming distance for grouping similar photos (overrides `backend.face.phash_hamming_max`)
         #[arg(long)]
         phash_max: Option<u8>,
 
+        /// How to link photos into burst groups (default `phash`): `time` (capture time within `backend.face.burst_window_secs`, or a shared EXIF burst ID / adjacent sequence number), `phash` (perceptual hash only), `both` (either, except that pHash matches further apart in time than `backend.face.phash_max_gap_secs` are ignored, and time-window matches must still be within twice the pHash threshold). `time` links every pair inside the window, so steady shooting can chain into one large group. The reasons behind each group are recorded in the report
+        #[arg(long, value_name = "MODE")]
+        group_by: Option<GroupBy>,
+
+        /// Only include files whose XMP star rating (embedded or `.xmp` sidecar) is at least N (0-5). Unrated files count as 0; rejected (-1) files never pass
+        #[arg(long, value_parser = clap::value_parser!(i8).range(0..=5))]
+        min_rating: Option<i8>,
+
+        /// Only include files whose XMP color label (embedded or `.xmp` sidecar) equals LABEL, case-insensitive (e.g. `Red`)
+        #[arg(long)]
+        label: Option<String>,
+
         /// Write a JSON operation report to this path
         #[arg(long)]
         report: Option<String>,
+
+        /// Write a self-contained HTML review page to this path: per-group thumbnails with face boxes (green eyes open, red blink), score breakdowns, and "best" / "keep all" toggles that export a `cull-decisions.json`
+        #[arg(long, value_name = "PATH")]
+        review: Option<String>,
+
+        /// Keep per-photo analysis (pHash, sharpness, face detections, embeddings, eye aspect ratios and eye-state probabilities) in this JSON cache, keyed by content SHA-512 (overrides `backend.face.cull_cache_path`). Re-running with different weights or thresholds then skips decoding and model inference for cached photos; cached inference is discarded when model paths or detection settings change. Written even with `--dry-run`
+        #[arg(long, value_name = "PATH")]
+        cache: Option<String>,
+
+        /// Apply a `cull-decisions.json` exported from the review page instead of culling: frames chosen as best (or whole groups marked keep-all) move back to their source, newly culled frames move into the group directory, and `BEST_` copies follow the new choice. Safe to run again; honours `--dry-run`
+        #[arg(
+            long,
+            value_name = "FILE",
+            conflicts_with_all = ["sources", "output", "phash_max", "group_by", "min_rating", "label", "review", "cache", "restore"]
+        )]
+        apply_decisions: Option<String>,
+
+        /// Undo earlier cull runs into `--output`: every `MANIFEST.json` under it is read, each culled copy is checked against its recorded content hash and moved back to its original source path (never overwriting an existing file), then `BEST_` copies, manifests and emptied group directories are removed. Honours `--dry-run`
+        #[arg(
+            long,
+            conflicts_with_all = ["sources", "phash_max", "group_by", "min_rating", "label", "review", "cache"]
+        )]
+        restore: bool,
     },
 
     /// Move non-duplicate media files from sources into the output directory. Sources that duplicate something already in output are physically deleted; duplicate detection uses SHA-512. Pass --include-non-media to also move everything else.
@@ -142,14 +248,161 @@ pub enum Commands {
         #[arg(short, long)]
         output: Location,
 
-        /// Archive directory template; placeholders: `{year}` `{month}` `{day}` `{make}` `{model}` `{valuable_name}`
+        /// Archive directory template. Placeholders: `{year}` `{month}` `{day}` `{hour}` `{week}` (ISO week) `{week_year}` (ISO week-numbering year) `{quarter}` `{date:%Y-%m-%d}` `{make}` `{model}` `{lens}` `{country}` (XMP `photoshop:Country`, not derived from GPS) `{media_kind}` `{mime_type}` `{ext}` `{original_name}` `{source_priority}` `{valuable_name}` `{rating}` `{label}` `{hash8}` `{event}` `{person}` (named person with the largest face, from `faces name`; needs the face database) `{album}` (Apple Photos album). For Apple Photos library sources `{original_name}` is the file name before import. Append `:upper` / `:lower` / `:title` to change case and `|TEXT` for a fallback, e.g. `{make:lower|unknown}`
         #[arg(long)]
         archive_template: Option<String>,
 
+        /// File name template, rendered with the same placeholders as `--archive-template` plus `{hash8}` (first 8 hex chars of the SHA-512), e.g. `{date:%Y%m%d_%H%M%S}_{make}_{hash8}.{ext}`. Name clashes get a `_N` suffix; `.xmp` / `.json` sidecars are renamed alongside. Defaults to the original file name
+        #[arg(long)]
+        name_template: Option<String>,
+
+        /// Only include files whose XMP star rating (embedded or `.xmp` sidecar) is at least N (0-5). Unrated files count as 0; rejected (-1) files never pass
+        #[arg(long, value_parser = clap::value_parser!(i8).range(0..=5))]
+        min_rating: Option<i8>,
+
+        /// Only include files whose XMP color label (embedded or `.xmp` sidecar) equals LABEL, case-insensitive (e.g. `Red`)
+        #[arg(long)]
+        label: Option<String>,
+
+        /// Only include photos marked as favourites in an Apple Photos library source; files outside a library never pass
+        #[arg(long)]
+        favorite: bool,
+
+        /// Content-hash catalog file (SHA-512 + size + last known location) recording every file archived by `copy` / `move`; overrides `copy.catalog_path`
+        #[arg(long)]
+        catalog: Option<String>,
+
+        /// Skip source files whose content is already in the catalog, even if the archive has since been reorganised or is offline. Known files are never deleted by `move`
+        #[arg(long)]
+        skip_known: bool,
+
+        /// Only include images in which the face database has a face named NAME (case-insensitive; see `faces name`). Other files are left in place, even by `move`
+        #[arg(long, value_name = "NAME")]
+        person: Option<String>,
+
+        /// Face database built by `faces scan`, used by `--person` and `{person}`; overrides `backend.face.db_path`
+        #[arg(long)]
+        faces_db: Option<String>,
+
         /// Write a JSON operation report to this path
         #[arg(long)]
         report: Option<String>,
     },
+
+    /// Rename media files in place (within their own directory) using `--name-template`, rendered from the same media-time decision and placeholders as `copy`. Name clashes get a `_N` suffix; `.xmp` / `.json` sidecars are renamed alongside. Non-media files are left alone.
+    Rename {
+        /// Dry run, do not rename files
+        #[arg(short, long)]
+        dry_run: bool,
+
+        /// The source directories or files (URI or local path)
+        #[arg(required = true)]
+        sources: Vec<Location>,
+
+        /// File name template, e.g. `{date:%Y%m%d_%H%M%S}_{make}_{hash8}.{ext}`. Supports every `--archive-template` placeholder plus `{hash8}`
+        #[arg(long)]
+        name_template: String,
+
+        /// Write a Python script that restores the original names to this path
+        #[arg(long)]
+        undo_log: Option<String>,
+
+        /// Write a JSON operation report to this path
+        #[arg(long)]
+        report: Option<String>,
+    },
+
+    /// Summarise media files by year, month, camera, MIME type, time source priority and confidence, and list empty months between the first and last one. Read-only; prints tables to stdout.
+    Stats {
+        /// The source directories or files (URI or local path)
+        #[arg(required = true)]
+        sources: Vec<Location>,
+
+        /// Write the statistics as JSON to this path
+        #[arg(long)]
+        report: Option<String>,
+    },
+
+    /// Audit the resolved media-time timeline: flag months where most files are dated only by mtime (lost EXIF), files whose metadata claims a future date or a camera reset default (1970/1980/2000-01-01), and long gaps. Read-only; prints findings with paths to stdout.
+    Audit {
+        /// The source directories or files (URI or local path)
+        #[arg(required = true)]
+        sources: Vec<Location>,
+
+        /// Minimum number of mtime-only files in a month before it is reported as a lost-metadata cluster
+        #[arg(long, default_value_t = 20)]
+        min_cluster: usize,
+
+        /// Minimum number of consecutive empty months reported as a gap
+        #[arg(long, default_value_t = 12, value_parser = clap::value_parser!(u32).range(1..))]
+        gap_months: u32,
+
+        /// Write the findings as JSON to this path
+        #[arg(long)]
+        report: Option<String>,
+    },
+
+    /// Maintain the face database behind the `{person}` archive placeholder and `--person`: detect and embed faces across the library, cluster them into identities and name the clusters.
+    Faces {
+        #[command(subcommand)]
+        action: FacesAction,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum FacesAction {
+    /// Detect faces in every image under the sources (SCRFD + MobileFaceNet, same models as `cull`) and add their embeddings to the database, clustering them into identities across the whole library. Images already in the database (by SHA-512) are not re-analysed. Prints the cluster table to stdout.
+    Scan {
+        /// The source directories or files (URI or local path)
+        #[arg(required = true)]
+        sources: Vec<Location>,
+
+        /// Face database file; overrides `backend.face.db_path`
+        #[arg(long)]
+        db: Option<String>,
+
+        /// Write a JSON operation report to this path
+        #[arg(long)]
+        report: Option<String>,
+    },
+
+    /// Print every identity cluster with its name, face and image counts and a sample image.
+    List {
+        /// Face database file; overrides `backend.face.db_path`
+        #[arg(long)]
+        db: Option<String>,
+
+        /// Write the clusters as JSON to this path
+        #[arg(long)]
+        report: Option<String>,
+    },
+
+    /// Name an identity cluster; an empty NAME removes the name. Several clusters may share a name when one person was split into more than one.
+    Name {
+        /// Cluster ID as printed by `faces list`
+        id: u32,
+
+        /// Person name used by `{person}` and `--person`
+        name: String,
+
+        /// Face database file; overrides `backend.face.db_path`
+        #[arg(long)]
+        db: Option<String>,
+    },
+
+    /// Split an identity cluster that mixes several people: its faces are re-clustered among themselves, the largest part keeps the ID and name, and every other part becomes a new unnamed cluster. Fails without changes when the faces stay together; retry with a higher --cosine-min.
+    Split {
+        /// Cluster ID as printed by `faces list`
+        id: u32,
+
+        /// Minimum cosine similarity for two faces to stay in one part (-1 to 1); defaults to `backend.face.face_cosine_min`
+        #[arg(long, value_name = "SIM", allow_negative_numbers = true)]
+        cosine_min: Option<f32>,
+
+        /// Face database file; overrides `backend.face.db_path`
+        #[arg(long)]
+        db: Option<String>,
+    },
 }
 
 /// 解析命令行参数并执行对应子命令。
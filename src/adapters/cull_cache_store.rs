@@ -0,0 +1,35 @@
+//! `cull` 分析缓存持久化 Gateway：[`CullCache`] 与 JSON 文件互转，读写走
+//! [`super::json_store`]。
+
+use crate::entities::common;
+use crate::usecases::cull::{CULL_CACHE_VERSION, CullCache};
+
+use super::json_store::{VersionPolicy, load_versioned_json, save_json};
+
+/// 读取分析缓存；文件不存在视为空缓存（首次运行），其他版本的缓存丢弃重建。
+///
+/// # Errors
+///
+/// 读盘失败或内容根本不是缓存文件时返回 `Err`——缓存本身可重算，但路径指错时（如误指向
+/// catalog）随后的保存会把那个文件覆盖掉。
+pub fn load_cull_cache(path: &str) -> common::Result<CullCache> {
+    load_versioned_json(
+        path,
+        "cull cache",
+        VersionPolicy::Rebuild(CULL_CACHE_VERSION),
+    )
+}
+
+/// 原子写回分析缓存。
+///
+/// # Errors
+///
+/// 临时文件创建、写入或 rename 失败时返回 `Err`。
+pub fn save_cull_cache(path: &str, cache: &CullCache) -> common::Result<()> {
+    // 非有限值不入缓存（见 `CullCache::record_*`），序列化不可能失败。
+    save_json(path, cache)
+}
+
+#[cfg(test)]
+#[path = "cull_cache_store_tests.rs"]
+mod tests;
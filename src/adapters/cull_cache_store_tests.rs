@@ -0,0 +1,14 @@
+use tempfile::tempdir;
+
+use super::*;
+
+/// 其他版本的缓存只是过期数据：整体重建，不必能解析其余字段。
+#[test]
+fn other_version_starts_empty() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("cull-cache.json");
+    std::fs::write(&path, br#"{"version":99,"entries":"new layout"}"#).unwrap();
+    let cache = load_cull_cache(path.to_str().unwrap()).unwrap();
+    assert_eq!(cache.version, CULL_CACHE_VERSION);
+    assert_eq!(cache.image_count(), 0);
+}
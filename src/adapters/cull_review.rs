@@ -0,0 +1,286 @@
+//! cull 复核页 Gateway：把 [`CullReport`] 渲染成单文件静态 HTML（缩略图 base64 内嵌），
+//! 逐组展示各帧缩略图 + 人脸框（绿 = 睁眼、红 = 闭眼）+ 评分明细。
+//!
+//! 页内每组一组「best」单选与「keep all」勾选，点导出按钮把选择填进内嵌的
+//! [`CullDecisions`] JSON 并下载为 `cull-decisions.json`，交 `cull --apply-decisions` 落实。
+//! 页面不依赖任何外部资源，可直接本地打开或随报告归档。
+
+use std::fmt::Write as _;
+use std::io::{self, Read};
+
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD;
+
+use super::report_sink::write_atomic;
+use crate::entities::backend::factory::BackendFactory;
+use crate::entities::common;
+use crate::entities::uri::Location;
+use crate::usecases::config::config;
+use crate::usecases::cull::{CullDecisions, CullReport, FaceMark, ScoreBreakdown};
+
+/// 缩略图长边像素。
+const THUMB_MAX: u32 = 320;
+const THUMB_JPEG_QUALITY: u8 = 80;
+
+/// 内嵌缩略图：data URI + 原图尺寸（人脸框按原图像素坐标换算百分比）。
+pub(crate) struct Thumbnail {
+    pub data_uri: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// 渲染复核页并原子写到 `path`。缩略图经 `factory` 读原图生成：dry-run 报告读源路径，
+/// 真跑报告的劣质帧读 group 目录里的落点。取不到的缩略图显示占位块，不算失败。
+///
+/// # Errors
+///
+/// 复核页写盘失败时返回 `Err`（错误信息带 `path`）；`path` 处原有文件保持不变。
+pub fn write_review_page(
+    path: &str,
+    report: &CullReport,
+    factory: &dyn BackendFactory,
+) -> io::Result<()> {
+    let html = render_review_page(report, &|p: &str| load_thumbnail(factory, p));
+    write_atomic(path, html.as_bytes()).map_err(|common::Error::Io(e)| {
+        io::Error::new(
+            e.kind(),
+            format!("cannot write cull review page {path}: {e}"),
+        )
+    })
+}
+
+/// 纯渲染：缩略图经 `thumbnail` 注入，单测不碰文件系统。取不到时显示占位块。
+pub(crate) fn render_review_page(
+    report: &CullReport,
+    thumbnail: &dyn Fn(&str) -> Option<Thumbnail>,
+) -> String {
+    let decisions = CullDecisions::from_report(report);
+    let mut html = String::with_capacity(4096);
+    html.push_str(PAGE_HEAD);
+    let _ = write!(
+        html,
+        "<h1>cull review</h1>\n<p>{} groups, {} culled{}. \
+         <button id=\"export\">Export decisions</button></p>\n",
+        report.groups.len(),
+        report.culled_count,
+        if report.dry_run { " (dry run)" } else { "" },
+    );
+    for (i, group) in report.groups.iter().enumerate() {
+        let _ = write!(
+            html,
+            "<section class=\"group\">\n<h2>group {:03} \
+             <label><input type=\"checkbox\" id=\"keep-all-{i}\"> keep all</label></h2>\n\
+             <div class=\"frames\">\n",
+            group.group_id
+        );
+        let best = Frame {
+            read_path: &group.best_source,
+            label: &group.best_source,
+            breakdown: &group.score_breakdown,
+            faces: &group.best_faces,
+        };
+        render_frame(&mut html, i, 0, &best, thumbnail);
+        for (pos, culled) in group.culled.iter().enumerate() {
+            let frame = Frame {
+                read_path: if report.dry_run {
+                    &culled.source_path
+                } else {
+                    &culled.dest_path
+                },
+                label: &culled.source_path,
+                breakdown: &culled.score_breakdown,
+                faces: &culled.faces,
+            };
+            render_frame(&mut html, i, pos + 1, &frame, thumbnail);
+        }
+        html.push_str("</div>\n</section>\n");
+    }
+    // 决定 JSON 只含 String / bool / 整数，序列化不可能失败；`</` 转义防提前闭合 script。
+    let json = serde_json::to_string(&decisions)
+        .expect("internal error: serializing cull decisions must not fail")
+        .replace("</", "<\\/");
+    let _ = write!(
+        html,
+        "<script type=\"application/json\" id=\"cull-decisions\">{json}</script>\n"
+    );
+    html.push_str(PAGE_TAIL);
+    html
+}
+
+struct Frame<'a> {
+    /// 缩略图读取路径。
+    read_path: &'a str,
+    /// 页面展示与决定引用的源路径。
+    label: &'a str,
+    breakdown: &'a ScoreBreakdown,
+    faces: &'a [FaceMark],
+}
+
+fn render_frame(
+    html: &mut String,
+    group_idx: usize,
+    frame_idx: usize,
+    frame: &Frame<'_>,
+    thumbnail: &dyn Fn(&str) -> Option<Thumbnail>,
+) {
+    html.push_str("<figure class=\"frame\">\n<div class=\"thumb\">");
+    match thumbnail(frame.read_path) {
+        Some(thumb) => {
+            let _ = write!(html, "<img src=\"{}\" alt=\"\">", thumb.data_uri);
+            for face in frame.faces {
+                render_face(html, face, thumb.width, thumb.height);
+            }
+        }
+        None => html.push_str("<div class=\"missing\">no preview</div>"),
+    }
+    let checked = if frame_idx == 0 { " checked" } else { "" };
+    let _ = write!(
+        html,
+        "</div>\n<figcaption><label><input type=\"radio\" name=\"best-{group_idx}\" \
+         value=\"{frame_idx}\"{checked}> best</label> <span class=\"path\">{}</span></figcaption>\n\
+         <table>\n",
+        escape_html(frame.label)
+    );
+    let b = frame.breakdown;
+    for (name, value) in [
+        ("sharpness", b.sharpness),
+        ("blink penalty", b.blink_penalty),
+        ("smile bonus", b.smile_bonus),
+        ("exposure penalty", b.exposure_penalty),
+        ("noise penalty", b.noise_penalty),
+        ("motion blur penalty", b.motion_blur_penalty),
+        ("motion blur angle", b.motion_blur_angle),
+        ("tilt penalty", b.tilt_penalty),
+        ("subject sharpness bonus", b.subject_sharpness_bonus),
+        ("quality bonus", b.quality_bonus),
+        ("total", b.total),
+    ] {
+        let _ = writeln!(html, "<tr><th>{name}</th><td>{value:.2}</td></tr>");
+    }
+    html.push_str("</table>\n</figure>\n");
+}
+
+/// 人脸框按原图尺寸折算百分比绝对定位，缩略图缩放后仍对齐。
+fn render_face(html: &mut String, face: &FaceMark, width: u32, height: u32) {
+    if width == 0 || height == 0 {
+        return;
+    }
+    let (w, h) = (f64::from(width), f64::from(height));
+    let pct = |v: f32, dim: f64| (f64::from(v) / dim * 100.0).clamp(0.0, 100.0);
+    let [x1, y1, x2, y2] = face.bbox;
+    let (class, flag) = if face.eyes_open {
+        ("open", "eyes open")
+    } else {
+        ("blink", "blink")
+    };
+    let _ = write!(
+        html,
+        "<span class=\"face {class}\" style=\"left:{:.2}%;top:{:.2}%;width:{:.2}%;height:{:.2}%\">{flag}</span>",
+        pct(x1, w),
+        pct(y1, h),
+        pct(x2 - x1, w),
+        pct(y2 - y1, h),
+    );
+}
+
+/// 经 backend 读原图（按 `backend.face.max_image_bytes` 封顶）并缩成内嵌 JPEG；
+/// 任一步失败返 None 由页面显示占位。
+fn load_thumbnail(factory: &dyn BackendFactory, path: &str) -> Option<Thumbnail> {
+    let loc = Location::parse(path).ok()?;
+    let backend = factory.for_location(&loc).ok()?;
+    let limit = config().backend.face.max_image_bytes;
+    let mut bytes = Vec::new();
+    backend
+        .open_read(&loc)
+        .ok()?
+        .take(limit.saturating_add(1))
+        .read_to_end(&mut bytes)
+        .ok()?;
+    if bytes.len() as u64 > limit {
+        return None;
+    }
+    thumbnail_from_bytes(&bytes)
+}
+
+pub(crate) fn thumbnail_from_bytes(bytes: &[u8]) -> Option<Thumbnail> {
+    let image = image::load_from_memory(bytes).ok()?;
+    let (width, height) = (image.width(), image.height());
+    let thumb = image.thumbnail(THUMB_MAX, THUMB_MAX).to_rgb8();
+    let mut jpeg = Vec::new();
+    thumb
+        .write_with_encoder(image::codecs::jpeg::JpegEncoder::new_with_quality(
+            &mut jpeg,
+            THUMB_JPEG_QUALITY,
+        ))
+        .ok()?;
+    Some(Thumbnail {
+        data_uri: format!("data:image/jpeg;base64,{}", STANDARD.encode(&jpeg)),
+        width,
+        height,
+    })
+}
+
+pub(crate) fn escape_html(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+const PAGE_HEAD: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>cull review</title>
+<style>
+body { font-family: sans-serif; margin: 1.5em; background: #fafafa; }
+.group { border-top: 1px solid #ccc; padding: .5em 0 1em; }
+.frames { display: flex; flex-wrap: wrap; gap: 1em; }
+.frame { margin: 0; width: 320px; }
+.thumb { position: relative; display: inline-block; line-height: 0; }
+.thumb img { max-width: 320px; max-height: 320px; }
+.missing { width: 320px; height: 200px; background: #ddd; line-height: 200px; text-align: center; color: #666; }
+.face { position: absolute; box-sizing: border-box; border: 2px solid; font-size: 10px; line-height: 1.2; color: #fff; }
+.face.open { border-color: #2e7d32; }
+.face.blink { border-color: #c62828; background: rgba(198, 40, 40, .15); }
+.path { font-size: 12px; word-break: break-all; }
+table { font-size: 12px; border-collapse: collapse; }
+th { text-align: left; font-weight: normal; padding-right: 1em; color: #555; }
+td { text-align: right; }
+</style>
+</head>
+<body>
+"#;
+
+const PAGE_TAIL: &str = r#"<script>
+document.getElementById('export').addEventListener('click', () => {
+  const decisions = JSON.parse(document.getElementById('cull-decisions').textContent);
+  decisions.groups.forEach((group, i) => {
+    const pick = document.querySelector(`input[name="best-${i}"]:checked`);
+    group.best = group.frames[Number(pick.value)].source_path;
+    group.keep_all = document.getElementById(`keep-all-${i}`).checked;
+  });
+  const blob = new Blob([JSON.stringify(decisions, null, 2)], { type: 'application/json' });
+  const link = document.createElement('a');
+  link.href = URL.createObjectURL(blob);
+  link.download = 'cull-decisions.json';
+  link.click();
+  // click() 只是排队下载：同步 revoke 会让部分浏览器取消下载，延后释放。
+  setTimeout(() => URL.revokeObjectURL(link.href), 1000);
+});
+</script>
+</body>
+</html>
+"#;
+
+#[cfg(test)]
+#[path = "cull_review_tests.rs"]
+mod tests;
@@ -0,0 +1,152 @@
+use image::ImageEncoder;
+use tempfile::tempdir;
+
+use super::*;
+use crate::adapters::backend::factory::DefaultBackendFactory;
+use crate::usecases::cull::{CulledEntry, GroupReport, PickStrategy};
+
+fn report(dry_run: bool) -> CullReport {
+    CullReport {
+        dry_run,
+        grouped: 1,
+        culled_count: 1,
+        groups: vec![GroupReport {
+            group_id: 7,
+            best_source: "/src/a.jpg".to_string(),
+            best_dest: "/out/group-007/BEST_a.jpg".to_string(),
+            culled: vec![CulledEntry {
+                source_path: "/src/<b>.jpg".to_string(),
+                dest_path: "/out/group-007/<b>.jpg".to_string(),
+                score: 12.5,
+                score_breakdown: ScoreBreakdown {
+                    total: 12.5,
+                    ..ScoreBreakdown::default()
+                },
+                faces: vec![FaceMark {
+                    bbox: [10.0, 20.0, 60.0, 70.0],
+                    eyes_open: false,
+                }],
+                content_hash: String::new(),
+            }],
+            score_breakdown: ScoreBreakdown {
+                sharpness: 300.0,
+                total: 300.0,
+                ..ScoreBreakdown::default()
+            },
+            best_faces: vec![FaceMark {
+                bbox: [0.0, 0.0, 50.0, 100.0],
+                eyes_open: true,
+            }],
+            strategy: PickStrategy::AllEyesOpen,
+            reasons: Vec::new(),
+            identities: Vec::new(),
+        }],
+        ..CullReport::default()
+    }
+}
+
+fn fake_thumb(_: &str) -> Option<Thumbnail> {
+    Some(Thumbnail {
+        data_uri: "data:image/jpeg;base64,AAAA".to_string(),
+        width: 200,
+        height: 100,
+    })
+}
+
+fn png_bytes(width: u32, height: u32) -> Vec<u8> {
+    let pixels = vec![128_u8; (width * height * 3) as usize];
+    let mut buf = Vec::new();
+    image::codecs::png::PngEncoder::new(&mut buf)
+        .write_image(&pixels, width, height, image::ExtendedColorType::Rgb8)
+        .unwrap();
+    buf
+}
+
+#[test]
+fn escape_html_escapes_markup_characters() {
+    assert_eq!(
+        escape_html(r#"<a href="x">&'</a>"#),
+        "&lt;a href=&quot;x&quot;&gt;&amp;&#39;&lt;/a&gt;"
+    );
+}
+
+#[test]
+fn render_draws_faces_breakdown_and_toggles() {
+    let html = render_review_page(&report(false), &fake_thumb);
+    assert!(html.contains("group 007"), "got: {html}");
+    assert!(html.contains(r#"id="keep-all-0""#));
+    assert!(html.contains(r#"name="best-0" value="0" checked"#));
+    assert!(html.contains(r#"name="best-0" value="1">"#));
+    // 200×100 原图：bbox [0,0,50,100] → 宽 25%、高 100%
+    assert!(
+        html.contains(
+            r#"class="face open" style="left:0.00%;top:0.00%;width:25.00%;height:100.00%">eyes open"#
+        ),
+        "got: {html}"
+    );
+    assert!(html.contains(r#"class="face blink""#));
+    assert!(html.contains("<tr><th>total</th><td>12.50</td></tr>"));
+    assert!(html.contains("/src/&lt;b&gt;.jpg"), "源路径需 HTML 转义");
+    assert!(html.contains(r#"<script type="application/json" id="cull-decisions">"#));
+    assert!(html.contains(r#""best":"/src/a.jpg""#));
+}
+
+#[test]
+fn embedded_decisions_cannot_close_script_early() {
+    let mut r = report(false);
+    r.groups[0].best_source = "/src/</script>.jpg".to_string();
+    let html = render_review_page(&r, &fake_thumb);
+    let start = html.find(r#"id="cull-decisions">"#).unwrap();
+    let json_end = html[start..].find("</script>").unwrap();
+    let json = &html[start + r#"id="cull-decisions">"#.len()..start + json_end];
+    let decisions: CullDecisions = serde_json::from_str(json).unwrap();
+    assert_eq!(decisions.groups[0].best, "/src/</script>.jpg");
+}
+
+#[test]
+fn render_reads_culled_frames_from_dest_unless_dry_run() {
+    let seen = std::cell::RefCell::new(Vec::new());
+    let record = |p: &str| -> Option<Thumbnail> {
+        seen.borrow_mut().push(p.to_string());
+        None
+    };
+    let html = render_review_page(&report(false), &record);
+    assert!(html.contains("no preview"));
+    assert_eq!(*seen.borrow(), ["/src/a.jpg", "/out/group-007/<b>.jpg"]);
+    seen.borrow_mut().clear();
+    render_review_page(&report(true), &record);
+    assert_eq!(*seen.borrow(), ["/src/a.jpg", "/src/<b>.jpg"]);
+}
+
+#[test]
+fn thumbnail_keeps_original_dimensions_and_embeds_jpeg() {
+    let thumb = thumbnail_from_bytes(&png_bytes(640, 480)).unwrap();
+    assert_eq!((thumb.width, thumb.height), (640, 480));
+    assert!(thumb.data_uri.starts_with("data:image/jpeg;base64,/9j/"));
+    assert!(thumbnail_from_bytes(b"not an image").is_none());
+}
+
+#[test]
+fn write_review_page_embeds_local_thumbnails() {
+    let dir = tempdir().unwrap();
+    let img = dir.path().join("a.png");
+    std::fs::write(&img, png_bytes(8, 8)).unwrap();
+    let mut r = report(true);
+    r.groups[0].best_source = img.to_str().unwrap().to_string();
+    let out = dir.path().join("review.html");
+    write_review_page(out.to_str().unwrap(), &r, &DefaultBackendFactory).unwrap();
+    let html = std::fs::read_to_string(&out).unwrap();
+    assert_eq!(html.matches("data:image/jpeg;base64,").count(), 1);
+    assert!(html.contains("no preview"), "缺失的劣质帧显示占位");
+}
+
+/// 写不出复核页（父目录不存在）时返回带路径的 Err，不再只 warn。
+#[test]
+fn write_review_page_reports_unwritable_path() {
+    let dir = tempdir().unwrap();
+    let out = dir.path().join("missing").join("review.html");
+    let err = write_review_page(out.to_str().unwrap(), &report(true), &DefaultBackendFactory)
+        .unwrap_err();
+    assert!(err.to_string().contains("review.html"), "{err}");
+    assert!(!out.exists());
+}
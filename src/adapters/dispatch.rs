@@ -1,16 +1,35 @@
+use std::sync::Arc;
+
 use crate::adapters::backend::factory::DefaultBackendFactory;
-use crate::adapters::cli::Commands;
+use crate::adapters::catalog_store::{load_catalog, save_catalog};
+use crate::adapters::cli::{Commands, FacesAction};
+use crate::adapters::cull_cache_store::{load_cull_cache, save_cull_cache};
+use crate::adapters::face_db_store::{load_face_db, save_face_db};
 use crate::adapters::report_sink::JsonFileReportSink;
+use crate::entities::backend::Backend;
 use crate::entities::backend::factory::BackendFactory;
 use crate::entities::common::{Error, Result};
+use crate::entities::media_time::Candidate;
+use crate::entities::tags::Tags;
 use crate::entities::uri::Location;
-use crate::usecases::config::validate_archive_template;
-use crate::usecases::cull::CullReport;
+use crate::usecases::CopyRequest;
+use crate::usecases::audit::{AuditOpts, AuditReport};
+use crate::usecases::catalog::CatalogOpts;
+use crate::usecases::config::{validate_archive_template, validate_name_template};
+use crate::usecases::cull::{CullDecisions, CullReport, GroupBy};
+use crate::usecases::faces::{FacesReport, PersonOpts, uses_person};
+use crate::usecases::find_apply::{ApplyAction, Resolution};
 use crate::usecases::move_text_shot::MoveTextShotReport;
+use crate::usecases::rename::{RenameReport, UndoLog};
 use crate::usecases::report::{CopyReport, FindReport, Report, ReportSink};
+use crate::usecases::stats::StatsReport;
+use crate::usecases::survivor::SurvivorPolicy;
+use crate::usecases::tag_filter::TagFilter;
 
 /// 子命令执行结果：Copy/Move 返回 [`CopyReport`]，Find 返回 [`FindReport`]，
-/// `MoveTextShot` 返回 [`MoveTextShotReport`]，`Cull` 返回 [`CullReport`]。
+/// `MoveTextShot` 返回 [`MoveTextShotReport`]，`Cull` 返回 [`CullReport`]，
+/// `Rename` 返回 [`RenameReport`]，`Stats` 返回 [`StatsReport`]，`Audit` 返回 [`AuditReport`]，
+/// `Faces` 返回 [`FacesReport`]。
 /// `tidy_with` 单一入口同时服务 CLI（丢弃返回）与 Android/mobile（消费 report）。
 #[derive(Debug)]
 pub enum CommandResult {
@@ -18,6 +37,10 @@ pub enum CommandResult {
     Find(FindReport),
     MoveTextShot(MoveTextShotReport),
     Cull(CullReport),
+    Rename(RenameReport),
+    Stats(StatsReport),
+    Audit(AuditReport),
+    Faces(FacesReport),
 }
 
 /// 用默认 backend factory 跑命令；旧入口，等价于 `tidy_with(&DefaultBackendFactory, ...)`。
@@ -54,10 +77,33 @@ pub fn tidy(command: Commands) -> Result<()> {
                 report.failed, report.moved, report.culled_count, report.grouped
             ))))
         }
+        CommandResult::Find(report) if !report.errors.is_empty() => {
+            Err(Error::Io(std::io::Error::other(format!(
+                "find --apply partial failure: {} failed, {} resolved",
+                report.errors.len(),
+                report.resolutions.len()
+            ))))
+        }
+        CommandResult::Rename(report) if report.failed > 0 => {
+            Err(Error::Io(std::io::Error::other(format!(
+                "rename partial failure: {} failed, {} renamed, {} unchanged",
+                report.failed, report.renamed, report.unchanged
+            ))))
+        }
+        CommandResult::Faces(report) if report.failed > 0 => {
+            Err(Error::Io(std::io::Error::other(format!(
+                "faces scan partial failure: {} failed, {} analyzed, {} known",
+                report.failed, report.analyzed, report.known
+            ))))
+        }
         CommandResult::Copy(_)
         | CommandResult::Find(_)
         | CommandResult::MoveTextShot(_)
-        | CommandResult::Cull(_) => Ok(()),
+        | CommandResult::Cull(_)
+        | CommandResult::Rename(_)
+        | CommandResult::Stats(_)
+        | CommandResult::Audit(_)
+        | CommandResult::Faces(_) => Ok(()),
     }
 }
 
@@ -68,6 +114,8 @@ pub fn tidy(command: Commands) -> Result<()> {
 ///
 /// 当 backend 构造失败、IO 操作出错或业务逻辑出错时返回 `Err`。
 pub fn tidy_with(factory: &dyn BackendFactory, command: Commands) -> Result<CommandResult> {
+    // Copy / Move 字段一致，唯一区别是 move 删源：共用一个 arm。
+    let remove = matches!(command, Commands::Move { .. });
     match command {
         Commands::Copy {
             dry_run,
@@ -75,40 +123,90 @@ pub fn tidy_with(factory: &dyn BackendFactory, command: Commands) -> Result<Comm
             sources,
             output,
             archive_template,
+            name_template,
+            min_rating,
+            label,
+            favorite,
+            catalog,
+            skip_known,
+            person,
+            faces_db,
             report,
-        } => dispatch_copy_or_move(
-            factory,
-            sources,
-            output,
-            dry_run,
-            /* remove = */ false,
-            include_non_media,
-            archive_template.as_deref(),
-            report.as_deref(),
-        ),
-        Commands::Move {
+        }
+        | Commands::Move {
             dry_run,
             include_non_media,
             sources,
             output,
             archive_template,
+            name_template,
+            min_rating,
+            label,
+            favorite,
+            catalog,
+            skip_known,
+            person,
+            faces_db,
             report,
         } => dispatch_copy_or_move(
             factory,
             sources,
             output,
-            dry_run,
-            /* remove = */ true,
-            include_non_media,
-            archive_template.as_deref(),
-            report.as_deref(),
+            CopyFlags {
+                dry_run,
+                remove,
+                include_non_media,
+                archive_template: archive_template.as_deref(),
+                name_template: name_template.as_deref(),
+                filter: TagFilter {
+                    min_rating,
+                    label,
+                    favorite,
+                },
+                catalog: catalog.as_deref(),
+                skip_known,
+                person: person.as_deref(),
+                faces_db: faces_db.as_deref(),
+                report: report.as_deref(),
+            },
         ),
+        // clap 已拒绝 `--similar` / `--similar-video` 互相同用及与 secure / output / keep /
+        // apply / quarantine 同用。
+        Commands::Find {
+            similar: true,
+            sources,
+            phash_max,
+            report,
+            ..
+        } => dispatch_find_similar(factory, sources, phash_max, false, report.as_deref()),
+        Commands::Find {
+            similar_video: true,
+            sources,
+            phash_max,
+            report,
+            ..
+        } => dispatch_find_similar(factory, sources, phash_max, true, report.as_deref()),
         Commands::Find {
             secure,
             sources,
             output,
+            keep,
+            apply,
+            quarantine,
+            similar: false,
+            similar_video: false,
+            phash_max: _,
             report,
-        } => dispatch_find(factory, sources, output, secure, report.as_deref()),
+        } => dispatch_find(
+            factory,
+            sources,
+            output,
+            secure,
+            &keep,
+            apply,
+            quarantine,
+            report.as_deref(),
+        ),
         Commands::MoveTextShot {
             dry_run,
             sources,
@@ -120,61 +218,235 @@ pub fn tidy_with(factory: &dyn BackendFactory, command: Commands) -> Result<Comm
             sources,
             output,
             phash_max,
+            group_by,
+            min_rating,
+            label,
             report,
-        } => dispatch_cull(
+            review,
+            cache,
+            apply_decisions,
+            restore,
+        } => match apply_decisions {
+            Some(decisions) => {
+                dispatch_cull_apply_decisions(factory, &decisions, dry_run, report.as_deref())
+            }
+            None if restore => dispatch_cull_restore(
+                factory,
+                &output
+                    .ok_or_else(|| invalid_input("cull --restore requires --output".to_string()))?,
+                dry_run,
+                report.as_deref(),
+            ),
+            None => dispatch_cull(
+                factory,
+                sources,
+                output.ok_or_else(|| invalid_input("cull requires --output".to_string()))?,
+                dry_run,
+                phash_max,
+                group_by.unwrap_or_default(),
+                &TagFilter {
+                    min_rating,
+                    label,
+                    favorite: false,
+                },
+                report.as_deref(),
+                review.as_deref(),
+                cache.as_deref(),
+            ),
+        },
+        Commands::Rename {
+            dry_run,
+            sources,
+            name_template,
+            undo_log,
+            report,
+        } => dispatch_rename(
             factory,
             sources,
-            output,
+            &name_template,
             dry_run,
-            phash_max,
+            undo_log.as_deref(),
+            report.as_deref(),
+        ),
+        Commands::Stats { sources, report } => dispatch_stats(factory, sources, report.as_deref()),
+        Commands::Audit {
+            sources,
+            min_cluster,
+            gap_months,
+            report,
+        } => dispatch_audit(
+            factory,
+            sources,
+            AuditOpts {
+                min_cluster,
+                gap_months,
+            },
             report.as_deref(),
         ),
+        Commands::Faces { action } => dispatch_faces(factory, action),
     }
 }
 
-// Copy / Move 唯一区别是 `remove` 布尔；提到此处避免两个 arm 18 行同体重复。
-#[expect(
-    clippy::too_many_arguments,
-    reason = "dispatch 单点接 6 个 CLI flag + factory + sources/output；折成结构体会让两个调用点也要先 Build 结构体"
-)]
+/// copy / move 的 CLI flag（move 即 `remove`）。
+struct CopyFlags<'a> {
+    dry_run: bool,
+    remove: bool,
+    include_non_media: bool,
+    archive_template: Option<&'a str>,
+    name_template: Option<&'a str>,
+    filter: TagFilter,
+    catalog: Option<&'a str>,
+    skip_known: bool,
+    person: Option<&'a str>,
+    faces_db: Option<&'a str>,
+    report: Option<&'a str>,
+}
+
 fn dispatch_copy_or_move(
     factory: &dyn BackendFactory,
     sources: Vec<Location>,
     output: Location,
-    dry_run: bool,
-    remove: bool,
-    include_non_media: bool,
-    archive_template: Option<&str>,
-    report: Option<&str>,
+    flags: CopyFlags<'_>,
 ) -> Result<CommandResult> {
-    validate_template_arg(archive_template)?;
-    let src_pairs = build_sources(factory, sources)?;
-    let out_pair = build_source(factory, output)?;
-    let sink = report.map(JsonFileReportSink::new);
-    let copy_report = crate::usecases::copy_with_sidecar(
-        &src_pairs,
-        out_pair,
+    let CopyFlags {
         dry_run,
         remove,
         include_non_media,
         archive_template,
-        sink.as_ref().map(|s| s as &dyn ReportSink),
-        // P3 sidecar 发现的依赖倒置注入点：adapters 协议解析进 usecases 流程。
-        Some(crate::adapters::sidecar::discover_with_backend),
+        name_template,
+        filter,
+        catalog,
+        skip_known,
+        person,
+        faces_db,
+        report,
+    } = flags;
+    if remove {
+        reject_photos_libraries(&sources, "move")?;
+    }
+    validate_template_arg(
+        archive_template,
+        "--archive-template",
+        validate_archive_template,
     )?;
-    Ok(CommandResult::Copy(copy_report))
+    validate_template_arg(name_template, "--name-template", validate_name_template)?;
+    // `--catalog` 优先于 `copy.catalog_path`；扫描前加载，损坏的 catalog 不应等复制完才报错。
+    let catalog_path = catalog.map(str::to_string).or_else(|| {
+        Some(crate::usecases::config::config().copy.catalog_path.clone()).filter(|p| !p.is_empty())
+    });
+    if skip_known && catalog_path.is_none() {
+        return Err(invalid_input(
+            "--skip-known requires --catalog or copy.catalog_path".into(),
+        ));
+    }
+    let mut known = catalog_path.as_deref().map(load_catalog).transpose()?;
+    // 人脸库只在 `--person` 或模板引用 `{person}` 时加载；同样扫描前加载、早报错。
+    let template =
+        archive_template.unwrap_or(&crate::usecases::config::config().copy.archive_template);
+    let faces =
+        if person.is_some() || uses_person(template) || name_template.is_some_and(uses_person) {
+            let path = resolve_face_db(faces_db, "--person / {person} requires --faces-db")?;
+            Some(load_face_db(&path)?)
+        } else {
+            None
+        };
+    if let (Some(name), Some(db)) = (person, &faces)
+        && !db.is_named(name)
+    {
+        return Err(invalid_input(format!(
+            "--person {name:?} matches no named face cluster (see `faces list` / `faces name`)"
+        )));
+    }
+    let src_pairs = build_sources(factory, sources)?;
+    let out_pair = build_source(factory, output)?;
+    let sink = report.map(JsonFileReportSink::new);
+    let copy_result = crate::usecases::copy_with_sidecar(
+        &src_pairs,
+        out_pair,
+        CopyRequest {
+            dry_run,
+            remove,
+            include_non_media,
+            archive_template,
+            name_template,
+            report_sink: sink.as_ref().map(|s| s as &dyn ReportSink),
+            // 外部时间候选（P3 sidecar + P0 Photos 图库）的依赖倒置注入点：
+            // adapters 协议解析进 usecases 流程。
+            sidecar: Some(discover_candidates),
+            // 星级 / 色标（`.xmp` sidecar）与收藏 / 相册 / 原名（Photos 图库）；仅在过滤
+            // 启用或模板引用时被调用。
+            tags: Some(discover_tags),
+            // 改名时随媒体搬运的 `.xmp` / `.json`；仅在 --name-template 生效时被调用。
+            companions: Some(crate::adapters::sidecar::companions_with_backend),
+            filter,
+            catalog: known.as_mut().map(|catalog| CatalogOpts {
+                catalog,
+                skip_known,
+            }),
+            persons: faces.as_ref().map(|db| PersonOpts { db, person }),
+        },
+    );
+    // 目录先于 copy 的 Err 落盘：中途失败前已归档的文件照样登记，下次 --skip-known 不重拷。
+    // 两者都失败时返回 copy 的 Err，目录写入错误只记日志。
+    if let (Some(path), Some(catalog)) = (catalog_path, known.filter(|_| !dry_run)) {
+        let saved = save_catalog(&path, &catalog);
+        if copy_result.is_ok() {
+            saved?;
+        } else if let Err(e) = saved {
+            tracing::error!(
+                feature = "copy",
+                operation = "save_catalog",
+                result = "error",
+                path = %path,
+                error = %e,
+                "catalog save failed after copy error"
+            );
+        }
+    }
+    Ok(CommandResult::Copy(copy_result?))
 }
 
+#[expect(
+    clippy::too_many_arguments,
+    reason = "dispatch 单点接 find 的 6 个 CLI flag + factory + report，逐个只在本函数消费"
+)]
 fn dispatch_find(
     factory: &dyn BackendFactory,
     sources: Vec<Location>,
     output: Option<Location>,
     secure: bool,
+    keep: &[SurvivorPolicy],
+    apply: Option<ApplyAction>,
+    quarantine: Option<Location>,
     report: Option<&str>,
 ) -> Result<CommandResult> {
+    if apply.is_some() {
+        reject_photos_libraries(&sources, "find --apply")?;
+    }
+    // 参数组合在扫描前校验：错配的 `--quarantine` 不应等全量哈希后才报错。
+    let resolution = match (apply, quarantine) {
+        (Some(action), quarantine) => {
+            let quarantine = quarantine
+                .map(|loc| build_source(factory, loc))
+                .transpose()?;
+            Some(Resolution::new(action, quarantine).map_err(invalid_input)?)
+        }
+        (None, Some(_)) => {
+            return Err(invalid_input(
+                "--quarantine requires --apply quarantine".to_string(),
+            ));
+        }
+        (None, None) => None,
+    };
     let src_pairs = build_sources(factory, sources)?;
     let out_pair = output.map(|loc| build_source(factory, loc)).transpose()?;
-    let find_report = crate::usecases::find_duplicates(secure, src_pairs, out_pair.as_ref())?;
+    let find_report = crate::usecases::find_duplicates(
+        secure,
+        src_pairs,
+        out_pair.as_ref(),
+        keep,
+        resolution.as_ref(),
+    )?;
     // Find use case 当前不接 sink（report 由 dispatch 层捕获最终结构后落盘），
     // 与 Copy/Move 把 sink 当参数传给 use case 的形态不对称——find_duplicates
     // 无 progress 回调需求，单点写盘已够；若未来需要流式输出再改为同 Copy 形态。
@@ -185,6 +457,28 @@ fn dispatch_find(
     Ok(CommandResult::Find(find_report))
 }
 
+fn dispatch_find_similar(
+    factory: &dyn BackendFactory,
+    sources: Vec<Location>,
+    phash_max: Option<u8>,
+    video: bool,
+    report: Option<&str>,
+) -> Result<CommandResult> {
+    let face_cfg = &crate::usecases::config::config().backend.face;
+    let src_pairs = build_sources(factory, sources)?;
+    let max_hamming = phash_max.unwrap_or(face_cfg.phash_hamming_max);
+    let find_report = if video {
+        crate::usecases::find_similar_videos(&src_pairs, max_hamming)
+    } else {
+        crate::usecases::find_similar(&src_pairs, max_hamming)
+    };
+    if let Some(path) = report {
+        let sink = JsonFileReportSink::new(path);
+        sink.write(&Report::Find(&find_report));
+    }
+    Ok(CommandResult::Find(find_report))
+}
+
 #[expect(
     clippy::needless_pass_by_value,
     reason = "由 Commands::MoveTextShot enum 解构 by-value 而来；usecase 接 &[]/& 借用"
@@ -196,6 +490,7 @@ fn dispatch_move_text_shot(
     dry_run: bool,
     report_path: Option<&str>,
 ) -> Result<CommandResult> {
+    reject_photos_libraries(&sources, "move-text-shot")?;
     let ocr_cfg = &crate::usecases::config::config().backend.ocr;
     let detector = crate::adapters::ocr::build_detector(ocr_cfg)?;
     let move_report =
@@ -207,6 +502,10 @@ fn dispatch_move_text_shot(
     Ok(CommandResult::MoveTextShot(move_report))
 }
 
+#[expect(
+    clippy::too_many_arguments,
+    reason = "dispatch 单点接 Cull 全部 CLI flag + factory；折成结构体只是把解构挪个位置"
+)]
 #[expect(
     clippy::needless_pass_by_value,
     reason = "由 Commands::Cull enum 解构 by-value 而来；usecase 接 &[]/& 借用"
@@ -217,24 +516,94 @@ fn dispatch_cull(
     output: Location,
     dry_run: bool,
     phash_max: Option<u8>,
+    group_by: GroupBy,
+    filter: &TagFilter,
     report_path: Option<&str>,
+    review_path: Option<&str>,
+    cache: Option<&str>,
 ) -> Result<CommandResult> {
+    reject_photos_libraries(&sources, "cull")?;
     let face_cfg = &crate::usecases::config::config().backend.face;
+    // `--cache` 优先于 `backend.face.cull_cache_path`；加载模型前读，路径指错尽早报错。
+    let cache_path = cache
+        .map(str::to_string)
+        .or_else(|| Some(face_cfg.cull_cache_path.clone()).filter(|p| !p.is_empty()));
+    let mut cull_cache = cache_path.as_deref().map(load_cull_cache).transpose()?;
     let scrfd = crate::adapters::face::build_scrfd_detector(face_cfg)?;
     let facenet = crate::adapters::face::build_facenet_embedder(face_cfg)?;
     let facemesh = crate::adapters::face::build_facemesh(face_cfg)?;
     let eyestate = crate::adapters::face::build_eyestate_classifier(face_cfg)?;
+    let quality_model = crate::adapters::face::build_quality_scorer(face_cfg)?;
     let cull_report = crate::usecases::cull(
         scrfd.as_ref(),
         facenet.as_ref(),
         facemesh.as_ref(),
         eyestate.as_ref(),
+        quality_model.as_deref(),
         factory,
         &sources,
         &output,
         dry_run,
         phash_max.unwrap_or(face_cfg.phash_hamming_max),
+        group_by,
+        filter,
+        Some(discover_tags),
+        cull_cache.as_mut(),
     )?;
+    // dry-run 同样落盘：缓存记录的是分析结果而非归档动作，调权重正是靠反复 dry-run。
+    if let (Some(path), Some(cull_cache)) = (cache_path, cull_cache) {
+        save_cull_cache(&path, &cull_cache)?;
+    }
+    if let Some(path) = report_path {
+        let sink = JsonFileReportSink::new(path);
+        sink.write(&Report::Cull(&cull_report));
+    }
+    // 复核页在报告落盘之后写：写不出来时报告已在，只需换个路径重跑。
+    if let Some(path) = review_path {
+        crate::adapters::cull_review::write_review_page(path, &cull_report, factory)?;
+    }
+    Ok(CommandResult::Cull(cull_report))
+}
+
+fn dispatch_cull_restore(
+    factory: &dyn BackendFactory,
+    output: &Location,
+    dry_run: bool,
+    report_path: Option<&str>,
+) -> Result<CommandResult> {
+    reject_photos_libraries(std::slice::from_ref(output), "cull --restore")?;
+    let cull_report = crate::usecases::cull::restore(factory, output, dry_run)?;
+    if let Some(path) = report_path {
+        let sink = JsonFileReportSink::new(path);
+        sink.write(&Report::Cull(&cull_report));
+    }
+    Ok(CommandResult::Cull(cull_report))
+}
+
+fn dispatch_cull_apply_decisions(
+    factory: &dyn BackendFactory,
+    decisions_path: &str,
+    dry_run: bool,
+    report_path: Option<&str>,
+) -> Result<CommandResult> {
+    let bytes = std::fs::read(decisions_path)?;
+    let decisions: CullDecisions = serde_json::from_slice(&bytes).map_err(|e| {
+        invalid_input(format!(
+            "--apply-decisions {decisions_path} is not a valid decisions file: {e}"
+        ))
+    })?;
+    // 决定文件可手改：源路径与 group 目录都可能落在图库内。解析失败的留给 usecase 报错。
+    let touched: Vec<Location> = decisions
+        .groups
+        .iter()
+        .flat_map(|g| {
+            std::iter::once(g.best_dest.as_str())
+                .chain(g.frames.iter().map(|f| f.source_path.as_str()))
+        })
+        .filter_map(|s| Location::parse(s).ok())
+        .collect();
+    reject_photos_libraries(&touched, "cull --apply-decisions")?;
+    let cull_report = crate::usecases::cull::apply_decisions(factory, &decisions, dry_run)?;
     if let Some(path) = report_path {
         let sink = JsonFileReportSink::new(path);
         sink.write(&Report::Cull(&cull_report));
@@ -242,29 +611,220 @@ fn dispatch_cull(
     Ok(CommandResult::Cull(cull_report))
 }
 
-// None 表示未传，跳过校验；Some(s) 时校验模板合法性。
-fn validate_template_arg(template: Option<&str>) -> Result<()> {
+fn dispatch_rename(
+    factory: &dyn BackendFactory,
+    sources: Vec<Location>,
+    name_template: &str,
+    dry_run: bool,
+    undo_log: Option<&str>,
+    report_path: Option<&str>,
+) -> Result<CommandResult> {
+    reject_photos_libraries(&sources, "rename")?;
+    validate_template_arg(
+        Some(name_template),
+        "--name-template",
+        validate_name_template,
+    )?;
+    let src_pairs = build_sources(factory, sources)?;
+    // 还原脚本在改名前创建：路径不可写时直接失败，而不是改完才发现无法还原。
+    // 此后每条改名成功即追加 + flush（见 `UndoLog`），中途中断也不丢还原记录。
+    let mut undo_file = match undo_log {
+        Some(path) if !dry_run => Some(std::fs::File::create(path)?),
+        _ => None,
+    };
+    let mut undo = undo_file
+        .as_mut()
+        .map(|file| UndoLog::new(file))
+        .transpose()?;
+    let rename_report = crate::usecases::rename(
+        &src_pairs,
+        name_template,
+        dry_run,
+        Some(discover_candidates),
+        Some(discover_tags),
+        Some(crate::adapters::sidecar::companions_with_backend),
+        undo.as_mut(),
+    )?;
+    if let Some(path) = report_path {
+        let sink = JsonFileReportSink::new(path);
+        sink.write(&Report::Rename(&rename_report));
+    }
+    Ok(CommandResult::Rename(rename_report))
+}
+
+fn dispatch_stats(
+    factory: &dyn BackendFactory,
+    sources: Vec<Location>,
+    report_path: Option<&str>,
+) -> Result<CommandResult> {
+    let src_pairs = build_sources(factory, sources)?;
+    let stats_report = crate::usecases::stats(&src_pairs, Some(discover_candidates));
+    if let Some(path) = report_path {
+        let sink = JsonFileReportSink::new(path);
+        sink.write(&Report::Stats(&stats_report));
+    }
+    Ok(CommandResult::Stats(stats_report))
+}
+
+fn dispatch_audit(
+    factory: &dyn BackendFactory,
+    sources: Vec<Location>,
+    opts: AuditOpts,
+    report_path: Option<&str>,
+) -> Result<CommandResult> {
+    let src_pairs = build_sources(factory, sources)?;
+    let audit_report = crate::usecases::audit(&src_pairs, opts, Some(discover_candidates));
+    if let Some(path) = report_path {
+        let sink = JsonFileReportSink::new(path);
+        sink.write(&Report::Audit(&audit_report));
+    }
+    Ok(CommandResult::Audit(audit_report))
+}
+
+fn dispatch_faces(factory: &dyn BackendFactory, action: FacesAction) -> Result<CommandResult> {
+    let (faces_report, report_path) = match action {
+        FacesAction::Scan {
+            sources,
+            db,
+            report,
+        } => {
+            let path = resolve_face_db(db.as_deref(), "faces scan requires --db")?;
+            let mut face_db = load_face_db(&path)?;
+            let src_pairs = build_sources(factory, sources)?;
+            let face_cfg = &crate::usecases::config::config().backend.face;
+            let scrfd = crate::adapters::face::build_scrfd_detector(face_cfg)?;
+            let facenet = crate::adapters::face::build_facenet_embedder(face_cfg)?;
+            let faces_report = crate::usecases::faces::scan_faces(
+                scrfd.as_ref(),
+                facenet.as_ref(),
+                &src_pairs,
+                &mut face_db,
+            );
+            // 部分失败也落盘：成功分析的图不必下次重跑推理。
+            save_face_db(&path, &face_db)?;
+            (faces_report, report)
+        }
+        FacesAction::List { db, report } => {
+            let path = resolve_face_db(db.as_deref(), "faces list requires --db")?;
+            (
+                crate::usecases::faces::list_faces(&load_face_db(&path)?),
+                report,
+            )
+        }
+        FacesAction::Name { id, name, db } => {
+            let path = resolve_face_db(db.as_deref(), "faces name requires --db")?;
+            let mut face_db = load_face_db(&path)?;
+            let faces_report = crate::usecases::faces::name_face_cluster(&mut face_db, id, &name)?;
+            save_face_db(&path, &face_db)?;
+            (faces_report, None)
+        }
+        FacesAction::Split { id, cosine_min, db } => {
+            let path = resolve_face_db(db.as_deref(), "faces split requires --db")?;
+            let mut face_db = load_face_db(&path)?;
+            let threshold = cosine_min.unwrap_or(
+                crate::usecases::config::config()
+                    .backend
+                    .face
+                    .face_cosine_min,
+            );
+            let faces_report =
+                crate::usecases::faces::split_face_cluster(&mut face_db, id, threshold)?;
+            save_face_db(&path, &face_db)?;
+            (faces_report, None)
+        }
+    };
+    if let Some(path) = report_path {
+        let sink = JsonFileReportSink::new(path);
+        sink.write(&Report::Faces(&faces_report));
+    }
+    Ok(CommandResult::Faces(faces_report))
+}
+
+// 命令行给的人脸库路径优先于 `backend.face.db_path`；两者皆空时以 `missing` 开头报错。
+fn resolve_face_db(flag: Option<&str>, missing: &str) -> Result<String> {
+    flag.map(str::to_string)
+        .or_else(|| {
+            Some(
+                crate::usecases::config::config()
+                    .backend
+                    .face
+                    .db_path
+                    .clone(),
+            )
+            .filter(|p| !p.is_empty())
+        })
+        .ok_or_else(|| invalid_input(format!("{missing} or backend.face.db_path")))
+}
+
+// CandidateProvider 是 fn 指针，多个 Gateway 在此串联而非在 usecases 里接 Vec。
+fn discover_candidates(loc: &Location, backend: &Arc<dyn Backend>) -> Vec<Candidate> {
+    let mut out = crate::adapters::sidecar::discover_with_backend(loc, backend);
+    out.extend(crate::adapters::photos_library::discover_with_backend(
+        loc, backend,
+    ));
+    out
+}
+
+// TagProvider 同为 fn 指针：`.xmp` sidecar 逐字段优先于 Photos 图库。
+fn discover_tags(loc: &Location, backend: &Arc<dyn Backend>) -> Option<Tags> {
+    let sidecar = crate::adapters::sidecar::discover_tags_with_backend(loc, backend);
+    let photos = crate::adapters::photos_library::discover_tags_with_backend(loc, backend);
+    match (sidecar, photos) {
+        (Some(sidecar), Some(photos)) => Some(sidecar.or(photos)),
+        (sidecar, photos) => sidecar.or(photos),
+    }
+}
+
+// None 表示未传，跳过校验；Some(s) 时按 `validate` 校验模板合法性，错误带上 flag 名。
+fn validate_template_arg(
+    template: Option<&str>,
+    flag: &str,
+    validate: fn(&str) -> std::result::Result<(), String>,
+) -> Result<()> {
     let Some(t) = template else {
         return Ok(());
     };
-    validate_archive_template(t).map_err(|msg| {
+    validate(t).map_err(|msg| {
         Error::Io(std::io::Error::new(
             std::io::ErrorKind::InvalidInput,
-            format!("invalid --archive-template: {msg}"),
+            format!("invalid {flag}: {msg}"),
         ))
     })
 }
 
+// Photos 数据库按 `ZFILENAME` 引用包内原图：删 / 改名 / 搬走任一文件都会损坏图库。
+// 源本身或其任一祖先是 `.photoslibrary` 即拒绝，提示先 copy 出来。
+fn reject_photos_libraries(sources: &[Location], command: &str) -> Result<()> {
+    for loc in sources {
+        if let Location::Local(path) = loc
+            && let Some(root) = crate::adapters::photos_library::library_root(path)
+        {
+            return Err(invalid_input(format!(
+                "{command} would modify files inside Photos library {root}; \
+                 export them with `copy` first"
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn invalid_input(msg: String) -> Error {
+    Error::Io(std::io::Error::new(std::io::ErrorKind::InvalidInput, msg))
+}
+
 fn build_source(factory: &dyn BackendFactory, loc: Location) -> Result<crate::usecases::Source> {
     let backend = factory.for_location(&loc)?;
     Ok((loc, backend))
 }
 
+// 只读命令（copy / find 无 --apply / stats / audit / faces scan）的源：`.photoslibrary`
+// 包根改写为 originals/，跳过缩略图与数据库。会删 / 改名 / 搬源文件的命令先过
+// `reject_photos_libraries`。
 fn build_sources(
     factory: &dyn BackendFactory,
     locs: Vec<Location>,
 ) -> Result<Vec<crate::usecases::Source>> {
     locs.into_iter()
-        .map(|loc| build_source(factory, loc))
+        .map(|loc| build_source(factory, crate::adapters::photos_library::source_root(loc)))
         .collect()
 }
@@ -1,4 +1,4 @@
-//! 测试用 4 个 face Gateway 实现：路径查表 + Err 注入。与 `FakeTextDetector` 同套路。
+//! 测试用 4 个 face Gateway + `QualityScorer` 实现：路径查表 + Err 注入。与 `FakeTextDetector` 同套路。
 
 use std::collections::{HashMap, HashSet};
 use std::io;
@@ -7,7 +7,7 @@ use camino::{Utf8Path, Utf8PathBuf};
 use parking_lot::Mutex;
 
 use crate::usecases::face::{
-    EyeStateClassifier, FaceDetection, FaceDetector, FaceEmbedder, FaceMeshDetector,
+    EyeStateClassifier, FaceDetection, FaceDetector, FaceEmbedder, FaceMeshDetector, QualityScorer,
 };
 
 // ───────────────────── FakeFaceDetector ─────────────────────
@@ -247,6 +247,64 @@ impl EyeStateClassifier for FakeEyeStateClassifier {
     }
 }
 
+// ───────────────────── FakeQualityScorer ─────────────────────
+
+/// 路径查表整图质量模型：质量分默认 `default`，可路径级覆盖 + Err 注入。
+pub struct FakeQualityScorer {
+    results: Mutex<HashMap<Utf8PathBuf, f32>>,
+    errors: Mutex<HashSet<Utf8PathBuf>>,
+    default: f32,
+}
+
+impl FakeQualityScorer {
+    #[must_use]
+    pub fn new(default: f32) -> Self {
+        Self {
+            results: Mutex::new(HashMap::new()),
+            errors: Mutex::new(HashSet::new()),
+            default,
+        }
+    }
+
+    #[must_use]
+    pub fn with_result(self, path: impl Into<Utf8PathBuf>, score: f32) -> Self {
+        self.results.lock().insert(path.into(), score);
+        self
+    }
+
+    #[must_use]
+    pub fn with_error(self, path: impl Into<Utf8PathBuf>) -> Self {
+        self.errors.lock().insert(path.into());
+        self
+    }
+}
+
+impl std::fmt::Debug for FakeQualityScorer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FakeQualityScorer")
+            .field("default", &self.default)
+            .field("results_count", &self.results.lock().len())
+            .field("errors_count", &self.errors.lock().len())
+            .finish()
+    }
+}
+
+impl QualityScorer for FakeQualityScorer {
+    fn score_quality(&self, path: &Utf8Path, _image: &image::RgbImage) -> io::Result<f32> {
+        if self.errors.lock().contains(path) {
+            return Err(io::Error::other(format!(
+                "FakeQualityScorer: injected error for {path}"
+            )));
+        }
+        Ok(self
+            .results
+            .lock()
+            .get(path)
+            .copied()
+            .unwrap_or(self.default))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -374,4 +432,23 @@ mod tests {
         assert!(s.contains("results_count: 1"), "got: {s}");
         assert!(s.contains("errors_count: 1"), "got: {s}");
     }
+
+    #[test]
+    fn fake_quality_scorer_path_resolution_and_error_precedence() {
+        let d = FakeQualityScorer::new(5.0)
+            .with_result("/good", 7.5)
+            .with_error("/e");
+        let img = tiny_rgb();
+        assert!(
+            (d.score_quality(Utf8Path::new("/miss"), &img).unwrap() - 5.0).abs() < f32::EPSILON
+        );
+        assert!(
+            (d.score_quality(Utf8Path::new("/good"), &img).unwrap() - 7.5).abs() < f32::EPSILON
+        );
+        let err = d.score_quality(Utf8Path::new("/e"), &img).unwrap_err();
+        assert!(err.to_string().contains("injected error"));
+        let s = format!("{d:?}");
+        assert!(s.contains("results_count: 1"), "got: {s}");
+        assert!(s.contains("errors_count: 1"), "got: {s}");
+    }
 }
@@ -1,8 +1,9 @@
-//! 人脸推理 Adapter：4 个 Port trait 实现 + Fake。
+//! 人脸推理 Adapter：4 个 Port trait + 可选整图质量模型（NIMA）实现 + Fake。
 //!
-//! Port 定义见 `crate::usecases::face`（4 个 trait + `FaceDetection` DTO）。
+//! Port 定义见 `crate::usecases::face`（5 个 trait + `FaceDetection` DTO）。
 //! 真实实现走 tract-onnx，每 trait 拆 `tract_xxx.rs`（算法主体）+ `tract_xxx_real.rs`
 //! （`_real` 走 `--ignore-filename-regex` 排除整文件：CI 无 ONNX 模型不可触发）。
+//! 推理实例池与批量切块共用 `adapters::model_pool`，按 `backend.face.inference_*` 配置。
 
 pub mod fake;
 
@@ -12,10 +13,13 @@ pub mod tract_facemesh;
 pub mod tract_facemesh_real;
 pub mod tract_mobilefacenet;
 pub mod tract_mobilefacenet_real;
+pub mod tract_nima;
+pub mod tract_nima_real;
 pub mod tract_scrfd;
 pub mod tract_scrfd_real;
 
 pub use tract_eyestate::build_eyestate_classifier;
 pub use tract_facemesh::build_facemesh;
 pub use tract_mobilefacenet::build_facenet_embedder;
+pub use tract_nima::build_quality_scorer;
 pub use tract_scrfd::build_scrfd_detector;
@@ -14,13 +14,13 @@
 
 use std::io;
 use std::path::Path;
-use std::sync::{Arc, OnceLock};
+use std::sync::Arc;
 
 use camino::Utf8Path;
-use parking_lot::Mutex;
 use tract_onnx::prelude::*;
 
 use super::tract_eyestate_real::load_runnable;
+use crate::adapters::model_pool::{BatchPolicy, ModelPool, SharedModel};
 use crate::usecases::config::FaceConfig;
 use crate::usecases::face::EyeStateClassifier;
 
@@ -33,7 +33,7 @@ const OUTPUT_CHANNELS: usize = BOX_DIMS + NUM_CLASSES;
 const CLOSED_CLASS_IDX: usize = 1;
 
 pub(crate) trait RawEyeState: Send + Sync {
-    /// 接 NCHW `[1, 3, 640, 640]` f32；返 `[1, 6, anchors]` f32 `YOLOv8` 检测头输出。
+    /// 接 NCHW `[N, 3, 640, 640]` f32；返 `[N, 6, anchors]` f32 `YOLOv8` 检测头输出。
     ///
     /// # Errors
     ///
@@ -62,61 +62,67 @@ impl RawEyeState for TractRawEyeState {
 
 pub struct TractEyeStateClassifier {
     cfg: FaceConfig,
-    // OnceLock 让 lazy init 后 inference 无锁并发（同 SCRFD：旧 Mutex 串行化所有 worker）。
-    raw: OnceLock<Box<dyn RawEyeState>>,
-    // load 阶段互斥避免 N worker 重复 load model（详见 tract_scrfd.rs 同字段注释）。
-    load_lock: Mutex<()>,
+    model: SharedModel<EyeStateModel>,
+    // 每个 worker 一份推理实例，槽数 = `inference_workers`（见 `adapters::model_pool`）。
+    pool: ModelPool<dyn RawEyeState>,
+    batch: BatchPolicy,
 }
 
 impl std::fmt::Debug for TractEyeStateClassifier {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("TractEyeStateClassifier")
             .field("eyestate_model_path", &self.cfg.eyestate_model_path)
-            .field("loaded", &self.raw.get().is_some())
-            .field("load_lock", &self.load_lock)
+            .field("workers", &self.pool.workers())
+            .field("loaded", &(self.pool.loaded() > 0))
+            .field("batch", &self.batch)
             .finish()
     }
 }
 
 impl TractEyeStateClassifier {
-    #[cfg(test)]
-    pub(crate) fn with_raw(cfg: FaceConfig, raw: Box<dyn RawEyeState>) -> Self {
-        let cell = OnceLock::new();
-        let _ = cell.set(raw);
+    fn new(cfg: &FaceConfig, pool: ModelPool<dyn RawEyeState>) -> Self {
         Self {
-            cfg,
-            raw: cell,
-            load_lock: Mutex::new(()),
+            cfg: cfg.clone(),
+            model: SharedModel::default(),
+            pool,
+            batch: BatchPolicy::new(cfg.inference_batch_size),
         }
     }
 
+    #[cfg(test)]
+    pub(crate) fn with_raw(cfg: FaceConfig, raw: Box<dyn RawEyeState>) -> Self {
+        Self::new(&cfg, ModelPool::preloaded(raw))
+    }
+
     #[cfg_attr(coverage_nightly, coverage(off))]
-    fn ensure_raw(&self) -> io::Result<&dyn RawEyeState> {
-        if let Some(r) = self.raw.get() {
-            return Ok(r.as_ref());
-        }
-        let _guard = self.load_lock.lock();
-        if let Some(r) = self.raw.get() {
-            return Ok(r.as_ref());
-        }
-        let model = load_runnable(Path::new(&self.cfg.eyestate_model_path))?;
-        let boxed: Box<dyn RawEyeState> = Box::new(TractRawEyeState { model });
-        let _ = self.raw.set(boxed);
-        Ok(self
-            .raw
-            .get()
-            .expect("OnceLock set by self under load_lock")
-            .as_ref())
+    fn load_instance(&self) -> io::Result<Box<dyn RawEyeState>> {
+        let model = self
+            .model
+            .get_or_load(|| load_runnable(Path::new(&self.cfg.eyestate_model_path)))?;
+        Ok(Box::new(TractRawEyeState { model }))
+    }
+
+    fn run(&self, input: Tensor) -> io::Result<Tensor> {
+        self.pool.run(|| self.load_instance(), |raw| raw.run(input))
     }
 }
 
 impl EyeStateClassifier for TractEyeStateClassifier {
     fn classify_eye(&self, _path: &Utf8Path, eye_crop: &image::RgbImage) -> io::Result<f32> {
-        let raw = self.ensure_raw()?;
-        let input = preprocess(eye_crop)?;
-        let output = raw.run(input)?;
+        let output = self.run(preprocess(eye_crop)?)?;
         decode(&output)
     }
+
+    fn classify_eyes(
+        &self,
+        _path: &Utf8Path,
+        eye_crops: &[image::RgbImage],
+    ) -> io::Result<Vec<f32>> {
+        self.batch.run("EyeState", eye_crops, |chunk| {
+            let output = self.run(preprocess_batch(chunk)?)?;
+            decode_batch(&output, chunk.len())
+        })
+    }
 }
 
 /// `eyestate_model_path` 为空时报 `InvalidInput`。
@@ -131,28 +137,53 @@ pub fn build_eyestate_classifier(cfg: &FaceConfig) -> io::Result<Box<dyn EyeStat
             "backend.face.eyestate_model_path is empty; set TIDYMEDIA_FACE_EYESTATE_MODEL or config.yaml",
         ));
     }
-    Ok(Box::new(TractEyeStateClassifier {
-        cfg: cfg.clone(),
-        raw: OnceLock::new(),
-        load_lock: Mutex::new(()),
-    }))
+    Ok(Box::new(TractEyeStateClassifier::new(
+        cfg,
+        ModelPool::new(cfg.inference_workers),
+    )))
 }
 
 /// 任意 RGB → 640×640 letterbox（灰底 114）→ `[0, 1]` 归一化 NCHW `[1, 3, 640, 640]`。
 ///
+/// # Errors
+///
+/// 同 [`preprocess_batch`]。
+pub(crate) fn preprocess(img: &image::RgbImage) -> io::Result<Tensor> {
+    preprocess_batch(std::slice::from_ref(img))
+}
+
+/// N 个眼部 crop 各自 letterbox 后叠成 NCHW `[N, 3, 640, 640]`。
+///
 /// letterbox：保持长边按比例 resize 到 640，短边居中 padding 114（`YOLOv8` 默认填充值）。
 /// 0 尺寸入参降级为全 padding 画布。
 ///
 /// # Errors
 ///
 /// `Array4::from_shape_vec` 形状失配返 Err（const 形状下数学上不可达，? 兼容未来动态 shape）。
-pub(crate) fn preprocess(img: &image::RgbImage) -> io::Result<Tensor> {
-    let (src_w, src_h) = (img.width(), img.height());
+pub(crate) fn preprocess_batch(imgs: &[image::RgbImage]) -> io::Result<Tensor> {
     let side = INPUT_SIDE as usize;
     let plane = side * side;
+    let mut nchw = vec![0.0_f32; imgs.len() * 3 * plane];
+    for (img, chw) in imgs.iter().zip(nchw.chunks_exact_mut(3 * plane)) {
+        let canvas = letterbox(img);
+        for (idx, px) in canvas.pixels().enumerate() {
+            let y = idx / side;
+            let x = idx % side;
+            for ch in 0..3 {
+                chw[ch * plane + y * side + x] = f32::from(px.0[ch]) / 255.0;
+            }
+        }
+    }
+    // 同 mobilefacenet.preprocess：const 形状下 Err arm 不可达，map_err 让 caller 经 ? 传播。
+    tract_ndarray::Array4::from_shape_vec((imgs.len(), 3, side, side), nchw)
+        .map_err(|e| io::Error::other(format!("eyestate preprocess shape: {e}")))
+        .map(IntoTensor::into_tensor)
+}
+
+fn letterbox(img: &image::RgbImage) -> image::RgbImage {
+    let (src_w, src_h) = (img.width(), img.height());
     let mut canvas =
         image::RgbImage::from_pixel(INPUT_SIDE, INPUT_SIDE, image::Rgb([114, 114, 114]));
-
     if src_w > 0 && src_h > 0 {
         // INPUT_SIDE 是编译期 const 640，f32 字面量直替 try_from 运行时不可达分支。
         let side_f: f32 = 640.0;
@@ -185,19 +216,7 @@ pub(crate) fn preprocess(img: &image::RgbImage) -> io::Result<Tensor> {
         let pad_y = (INPUT_SIDE - new_h.min(INPUT_SIDE)) / 2;
         image::imageops::overlay(&mut canvas, &resized, i64::from(pad_x), i64::from(pad_y));
     }
-
-    let mut chw = vec![0.0_f32; 3 * plane];
-    for (idx, px) in canvas.pixels().enumerate() {
-        let y = idx / side;
-        let x = idx % side;
-        for ch in 0..3 {
-            chw[ch * plane + y * side + x] = f32::from(px.0[ch]) / 255.0;
-        }
-    }
-    // 同 mobilefacenet.preprocess：const 形状下 Err arm 不可达，map_err 让 caller 经 ? 传播。
-    tract_ndarray::Array4::from_shape_vec((1, 3, side, side), chw)
-        .map_err(|e| io::Error::other(format!("eyestate preprocess shape: {e}")))
-        .map(IntoTensor::into_tensor)
+    canvas
 }
 
 /// 取宽高较大者并转 f32（letterbox scale 计算用）。维度 ≤ 65535 时 f32 精度够。
@@ -212,17 +231,26 @@ fn orig_max_dim(w: u32, h: u32) -> f32 {
 
 /// `YOLOv8` 检测头 `[1, 6, anchors]` → 取 closed 类（index 1）在所有 anchor 中的最大 conf。
 ///
+/// # Errors
+///
+/// 同 [`decode_batch`]。
+pub(crate) fn decode(output: &Tensor) -> io::Result<f32> {
+    decode_batch(output, 1).map(|probs| probs[0])
+}
+
+/// `[N, 6, anchors]` → 每个 batch 项的闭眼概率。
+///
 /// `output` 内存布局假设 `[batch, channels, anchors]` 连续：channel 0..4 = box `(cx,cy,w,h)`，
 /// channel 4 = open conf，channel 5 = closed conf。
-pub(crate) fn decode(output: &Tensor) -> io::Result<f32> {
+pub(crate) fn decode_batch(output: &Tensor, n: usize) -> io::Result<Vec<f32>> {
     let cast = output
         .cast_to::<f32>()
         .map_err(|e| io::Error::other(format!("eyestate output not f32-castable: {e}")))?;
     let view = cast.view();
     let shape = view.shape();
-    if shape.len() != 3 || shape[0] != 1 || shape[1] != OUTPUT_CHANNELS {
+    if shape.len() != 3 || shape[0] != n || shape[1] != OUTPUT_CHANNELS {
         return Err(io::Error::other(format!(
-            "eyestate output shape {shape:?} != [1, {OUTPUT_CHANNELS}, anchors]"
+            "eyestate output shape {shape:?} != [{n}, {OUTPUT_CHANNELS}, anchors]"
         )));
     }
     let anchors = shape[2];
@@ -232,15 +260,17 @@ pub(crate) fn decode(output: &Tensor) -> io::Result<f32> {
     let slice = view
         .as_slice::<f32>()
         .map_err(|e| io::Error::other(format!("eyestate output slice: {e}")))?;
-    let closed_offset = CLOSED_CLASS_IDX + BOX_DIMS;
-    let closed_start = closed_offset * anchors;
-    let closed_end = closed_start + anchors;
-    let closed_conf = &slice[closed_start..closed_end];
-    let max = closed_conf
-        .iter()
-        .copied()
-        .fold(f32::NEG_INFINITY, f32::max);
-    Ok(max.clamp(0.0, 1.0))
+    let closed_start = (CLOSED_CLASS_IDX + BOX_DIMS) * anchors;
+    Ok(slice
+        .chunks_exact(OUTPUT_CHANNELS * anchors)
+        .map(|item| {
+            item[closed_start..closed_start + anchors]
+                .iter()
+                .copied()
+                .fold(f32::NEG_INFINITY, f32::max)
+                .clamp(0.0, 1.0)
+        })
+        .collect())
 }
 
 #[cfg(test)]
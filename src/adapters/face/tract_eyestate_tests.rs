@@ -28,6 +28,28 @@ impl RawEyeState for ConstRaw {
     }
 }
 
+/// 按输入 batch 维返 `[N, 6, anchors]`：第 i 项 closed conf 为 `0.1 * (i + 1)`。
+struct RampRaw;
+
+impl RawEyeState for RampRaw {
+    fn run(&self, input: Tensor) -> io::Result<Tensor> {
+        let n = input.shape()[0];
+        let anchors = 4;
+        let mut data = vec![0.0_f32; n * OUTPUT_CHANNELS * anchors];
+        let closed_offset = (BOX_DIMS + CLOSED_CLASS_IDX) * anchors;
+        for (i, item) in data.chunks_exact_mut(OUTPUT_CHANNELS * anchors).enumerate() {
+            #[expect(clippy::cast_precision_loss, reason = "测试 batch 很小")]
+            let conf = 0.1 * (i + 1) as f32;
+            item[closed_offset] = conf;
+        }
+        Ok(
+            tract_ndarray::Array3::from_shape_vec((n, OUTPUT_CHANNELS, anchors), data)
+                .expect("stub yolo output shape")
+                .into_tensor(),
+        )
+    }
+}
+
 struct FailRaw;
 impl RawEyeState for FailRaw {
     fn run(&self, _input: Tensor) -> io::Result<Tensor> {
@@ -115,6 +137,29 @@ fn classify_eye_propagates_raw_error() {
     assert!(e.to_string().contains("stub eyestate failed"), "got: {e}");
 }
 
+#[test]
+fn classify_eyes_batches_in_order() {
+    let det = TractEyeStateClassifier::with_raw(
+        FaceConfig {
+            inference_batch_size: 4,
+            ..cfg()
+        },
+        Box::new(RampRaw),
+    );
+    let crops = vec![tiny_eye(); 3];
+    let probs = det.classify_eyes(Utf8Path::new("/x.jpg"), &crops).unwrap();
+    assert_eq!(probs.len(), 3);
+    for (i, (got, want)) in probs.iter().zip([0.1, 0.2, 0.3]).enumerate() {
+        assert!((got - want).abs() < 1e-6, "item {i}: {got}");
+    }
+}
+
+#[test]
+fn decode_batch_rejects_batch_mismatch() {
+    let e = decode_batch(&build_yolo_output(4, 0.5), 2).unwrap_err();
+    assert!(e.to_string().contains("!= [2, 6, anchors]"), "got: {e}");
+}
+
 #[test]
 fn decode_rejects_wrong_rank() {
     let t = tract_ndarray::Array2::from_shape_vec((1, 6), vec![0.0_f32; 6])
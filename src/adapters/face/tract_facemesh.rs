@@ -5,13 +5,13 @@
 
 use std::io;
 use std::path::Path;
-use std::sync::{Arc, OnceLock};
+use std::sync::Arc;
 
 use camino::Utf8Path;
-use parking_lot::Mutex;
 use tract_onnx::prelude::*;
 
 use super::tract_facemesh_real::load_runnable;
+use crate::adapters::model_pool::{ModelPool, SharedModel};
 use crate::usecases::config::FaceConfig;
 use crate::usecases::face::FaceMeshDetector;
 
@@ -51,51 +51,41 @@ impl RawFaceMesh for TractRawFaceMesh {
 
 pub struct TractFaceMeshDetector {
     cfg: FaceConfig,
-    // OnceLock 让 lazy init 后 inference 无锁并发（同 SCRFD：旧 Mutex 串行化所有 worker）。
-    raw: OnceLock<Box<dyn RawFaceMesh>>,
-    // load 阶段互斥避免 N worker 重复 load model（详见 tract_scrfd.rs 同字段注释）。
-    load_lock: Mutex<()>,
+    model: SharedModel<FaceMeshModel>,
+    // 每个 worker 一份推理实例，槽数 = `inference_workers`（见 `adapters::model_pool`）。
+    pool: ModelPool<dyn RawFaceMesh>,
 }
 
 impl std::fmt::Debug for TractFaceMeshDetector {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("TractFaceMeshDetector")
             .field("facemesh_model_path", &self.cfg.facemesh_model_path)
-            .field("loaded", &self.raw.get().is_some())
-            .field("load_lock", &self.load_lock)
+            .field("workers", &self.pool.workers())
+            .field("loaded", &(self.pool.loaded() > 0))
             .finish()
     }
 }
 
 impl TractFaceMeshDetector {
-    #[cfg(test)]
-    pub(crate) fn with_raw(cfg: FaceConfig, raw: Box<dyn RawFaceMesh>) -> Self {
-        let cell = OnceLock::new();
-        let _ = cell.set(raw);
+    fn new(cfg: &FaceConfig, pool: ModelPool<dyn RawFaceMesh>) -> Self {
         Self {
-            cfg,
-            raw: cell,
-            load_lock: Mutex::new(()),
+            cfg: cfg.clone(),
+            model: SharedModel::default(),
+            pool,
         }
     }
 
+    #[cfg(test)]
+    pub(crate) fn with_raw(cfg: FaceConfig, raw: Box<dyn RawFaceMesh>) -> Self {
+        Self::new(&cfg, ModelPool::preloaded(raw))
+    }
+
     #[cfg_attr(coverage_nightly, coverage(off))]
-    fn ensure_raw(&self) -> io::Result<&dyn RawFaceMesh> {
-        if let Some(r) = self.raw.get() {
-            return Ok(r.as_ref());
-        }
-        let _guard = self.load_lock.lock();
-        if let Some(r) = self.raw.get() {
-            return Ok(r.as_ref());
-        }
-        let model = load_runnable(Path::new(&self.cfg.facemesh_model_path))?;
-        let boxed: Box<dyn RawFaceMesh> = Box::new(TractRawFaceMesh { model });
-        let _ = self.raw.set(boxed);
-        Ok(self
-            .raw
-            .get()
-            .expect("OnceLock set by self under load_lock")
-            .as_ref())
+    fn load_instance(&self) -> io::Result<Box<dyn RawFaceMesh>> {
+        let model = self
+            .model
+            .get_or_load(|| load_runnable(Path::new(&self.cfg.facemesh_model_path)))?;
+        Ok(Box::new(TractRawFaceMesh { model }))
     }
 }
 
@@ -105,9 +95,10 @@ impl FaceMeshDetector for TractFaceMeshDetector {
         _path: &Utf8Path,
         face_crop: &image::RgbImage,
     ) -> io::Result<Vec<[f32; 3]>> {
-        let raw = self.ensure_raw()?;
         let input = preprocess(face_crop)?;
-        let output = raw.run(input)?;
+        let output = self
+            .pool
+            .run(|| self.load_instance(), |raw| raw.run(input))?;
         decode(&output)
     }
 }
@@ -124,11 +115,10 @@ pub fn build_facemesh(cfg: &FaceConfig) -> io::Result<Box<dyn FaceMeshDetector>>
             "backend.face.facemesh_model_path is empty; set TIDYMEDIA_FACE_FACEMESH_MODEL or config.yaml",
         ));
     }
-    Ok(Box::new(TractFaceMeshDetector {
-        cfg: cfg.clone(),
-        raw: OnceLock::new(),
-        load_lock: Mutex::new(()),
-    }))
+    Ok(Box::new(TractFaceMeshDetector::new(
+        cfg,
+        ModelPool::new(cfg.inference_workers),
+    )))
 }
 
 /// 输入 RGB → 192×192 → `[0, 1]` 归一化 NCHW `[1, 3, 192, 192]`。
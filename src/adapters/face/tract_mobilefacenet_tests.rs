@@ -18,6 +18,28 @@ impl RawFacenet for ConstRaw {
     }
 }
 
+/// 按输入 batch 维返 `[N, 128]`：第 i 行只有第 i 维非零，可验逐行顺序。
+/// `max_batch` 模拟导出时固定 batch 的模型。
+struct RowRaw {
+    max_batch: usize,
+}
+
+impl RawFacenet for RowRaw {
+    fn run(&self, input: Tensor) -> io::Result<Tensor> {
+        let n = input.shape()[0];
+        if n > self.max_batch {
+            return Err(io::Error::other("stub facenet batch dim is fixed"));
+        }
+        let mut v = vec![0.0_f32; n * 128];
+        for i in 0..n {
+            v[i * 128 + i] = 2.0;
+        }
+        Ok(tract_ndarray::Array2::from_shape_vec((n, 128), v)
+            .expect("stub facenet shape")
+            .into_tensor())
+    }
+}
+
 struct FailRaw;
 impl RawFacenet for FailRaw {
     fn run(&self, _input: Tensor) -> io::Result<Tensor> {
@@ -103,6 +125,50 @@ fn embed_face_propagates_raw_error() {
     assert!(e.to_string().contains("stub facenet failed"), "got: {e}");
 }
 
+#[test]
+fn embed_faces_batches_in_order() {
+    let det = TractFacenetEmbedder::with_raw(
+        FaceConfig {
+            inference_batch_size: 3,
+            ..cfg()
+        },
+        Box::new(RowRaw { max_batch: 3 }),
+    );
+    let faces = vec![tiny_face(); 3];
+    let embs = det.embed_faces(Utf8Path::new("/x.jpg"), &faces).unwrap();
+    assert_eq!(embs.len(), 3);
+    for (i, emb) in embs.iter().enumerate() {
+        assert!(
+            (emb[i] - 1.0).abs() < 1e-5,
+            "row {i} not normalized in order"
+        );
+    }
+}
+
+/// 固定 batch=1 的模型：整批失败后逐张重跑，结果不丢。
+#[test]
+fn embed_faces_falls_back_when_model_rejects_batch() {
+    let det = TractFacenetEmbedder::with_raw(cfg(), Box::new(RowRaw { max_batch: 1 }));
+    let faces = vec![tiny_face(); 2];
+    let embs = det.embed_faces(Utf8Path::new("/x.jpg"), &faces).unwrap();
+    assert_eq!(embs.len(), 2);
+    assert!(embs.iter().all(|e| (e[0] - 1.0).abs() < 1e-5));
+    assert!(format!("{det:?}").contains("unbatched: true"));
+}
+
+#[test]
+fn preprocess_batch_stacks_faces() {
+    let faces = [
+        image::RgbImage::from_pixel(112, 112, image::Rgb([0, 0, 0])),
+        image::RgbImage::from_pixel(64, 64, image::Rgb([255, 255, 255])),
+    ];
+    let t = preprocess_batch(&faces).unwrap();
+    assert_eq!(t.shape(), [2, 3, 112, 112]);
+    let view = t.to_array_view::<f32>().unwrap();
+    assert!((view[[0, 0, 0, 0]] + 1.0).abs() < 1e-6);
+    assert!((view[[1, 2, 5, 5]] - 1.0).abs() < 1e-6);
+}
+
 #[test]
 fn decode_rejects_short_output() {
     let t = tract_ndarray::Array2::from_shape_vec((1, 64), vec![0.0_f32; 64])
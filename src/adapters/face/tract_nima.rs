@@ -0,0 +1,204 @@
+//! tract-onnx 实现 `QualityScorer`：跑 NIMA 类整图质量模型输出质量分。
+//!
+//! 模型形态：`idealo/image-quality-assessment` 的 `MobileNet` NIMA（aesthetic / technical
+//! 两套权重同结构），导出 ONNX 后输入 NCHW `[1, 3, 224, 224]`、`[-1, 1]` 归一化。
+//! 输出两种都接：
+//! - `[1, 10]` softmax 分布（1~10 分各档概率）→ 取期望 `Σ (i+1)·p_i / Σ p_i`
+//! - `[1, 1]` 标量回归头 → 原样返回
+//!
+//! 设计与 `tract_mobilefacenet` 同构：内部 `RawNima` trait 隔离真实 `model.run`，
+//! 单测注入 `ConstRaw` 验前/后处理，真实加载在 `_real.rs` 走 ignore-regex 排除。
+
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+use camino::Utf8Path;
+use tract_onnx::prelude::*;
+
+use super::tract_nima_real::load_runnable;
+use crate::adapters::model_pool::{ModelPool, SharedModel};
+use crate::usecases::config::FaceConfig;
+use crate::usecases::face::QualityScorer;
+
+/// 已优化的 NIMA 推理图。
+pub(crate) type NimaModel = Arc<TypedRunnableModel>;
+
+const INPUT_SIDE: u32 = 224;
+const SCORE_BINS: usize = 10;
+
+/// 把模型加载与单张推理拆开注入，让前/后处理可独立单测。
+pub(crate) trait RawNima: Send + Sync {
+    /// 接预处理 NCHW `[1, 3, 224, 224]` f32；返 `[1, 10]` 分布或 `[1, 1]` 标量。
+    ///
+    /// # Errors
+    ///
+    /// 模型推理失败时返回 `Err`。
+    fn run(&self, input: Tensor) -> io::Result<Tensor>;
+}
+
+struct TractRawNima {
+    model: NimaModel,
+}
+
+impl RawNima for TractRawNima {
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn run(&self, input: Tensor) -> io::Result<Tensor> {
+        let outputs = self
+            .model
+            .run(tvec!(input.into_tvalue()))
+            .map_err(|e| io::Error::other(format!("tract NIMA run failed: {e}")))?;
+        let first = outputs
+            .into_iter()
+            .next()
+            .ok_or_else(|| io::Error::other("tract NIMA returned no output tensor"))?;
+        Ok(first.into_tensor())
+    }
+}
+
+pub struct TractNimaScorer {
+    cfg: FaceConfig,
+    model: SharedModel<NimaModel>,
+    // 每个 worker 一份推理实例，槽数 = `inference_workers`（见 `adapters::model_pool`）。
+    pool: ModelPool<dyn RawNima>,
+}
+
+impl std::fmt::Debug for TractNimaScorer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TractNimaScorer")
+            .field("quality_model_path", &self.cfg.quality_model_path)
+            .field("workers", &self.pool.workers())
+            .field("loaded", &(self.pool.loaded() > 0))
+            .finish()
+    }
+}
+
+impl TractNimaScorer {
+    fn new(cfg: &FaceConfig, pool: ModelPool<dyn RawNima>) -> Self {
+        Self {
+            cfg: cfg.clone(),
+            model: SharedModel::default(),
+            pool,
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn with_raw(cfg: FaceConfig, raw: Box<dyn RawNima>) -> Self {
+        Self::new(&cfg, ModelPool::preloaded(raw))
+    }
+
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn load_instance(&self) -> io::Result<Box<dyn RawNima>> {
+        let model = self
+            .model
+            .get_or_load(|| load_runnable(Path::new(&self.cfg.quality_model_path)))?;
+        Ok(Box::new(TractRawNima { model }))
+    }
+}
+
+impl QualityScorer for TractNimaScorer {
+    fn score_quality(&self, _path: &Utf8Path, image: &image::RgbImage) -> io::Result<f32> {
+        let input = preprocess(image)?;
+        let output = self
+            .pool
+            .run(|| self.load_instance(), |raw| raw.run(input))?;
+        decode(&output)
+    }
+}
+
+/// 可选模型槽：`quality_model_path` 为空（或全空白）返 None，`cull` 评分不含质量模型项。
+/// 模型仍 lazy load，但路径先查一次是否为文件：可选槽的推理失败只退化为无质量分，
+/// 路径配错若拖到推理时才暴露，整次 cull 会悄悄失去质量项。
+///
+/// # Errors
+///
+/// 路径非空但不是已存在的文件返 `NotFound`。
+pub fn build_quality_scorer(cfg: &FaceConfig) -> io::Result<Option<Box<dyn QualityScorer>>> {
+    if cfg.quality_model_path.trim().is_empty() {
+        return Ok(None);
+    }
+    if !Path::new(&cfg.quality_model_path).is_file() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!(
+                "backend.face.quality_model_path {} is not a file; set TIDYMEDIA_FACE_QUALITY_MODEL or leave it empty",
+                cfg.quality_model_path
+            ),
+        ));
+    }
+    Ok(Some(Box::new(TractNimaScorer::new(
+        cfg,
+        ModelPool::new(cfg.inference_workers),
+    ))))
+}
+
+/// 任意 RGB → Triangle resize 224×224 → `[-1, 1]` 归一化 NCHW `[1, 3, 224, 224]` f32
+/// （`MobileNet` `preprocess_input` 口径）。
+///
+/// # Errors
+///
+/// `Array4::from_shape_vec` 形状失配返 Err（const 形状下数学上不可达，? 兼容未来动态 shape）。
+pub(crate) fn preprocess(img: &image::RgbImage) -> io::Result<Tensor> {
+    let resized = image::imageops::resize(
+        img,
+        INPUT_SIDE,
+        INPUT_SIDE,
+        image::imageops::FilterType::Triangle,
+    );
+    let side = INPUT_SIDE as usize;
+    let plane = side * side;
+    let mut chw = vec![0.0_f32; 3 * plane];
+    for (idx, px) in resized.pixels().enumerate() {
+        let y = idx / side;
+        let x = idx % side;
+        for ch in 0..3 {
+            chw[ch * plane + y * side + x] = (f32::from(px.0[ch]) - 127.5) / 127.5;
+        }
+    }
+    tract_ndarray::Array4::from_shape_vec((1, 3, side, side), chw)
+        .map_err(|e| io::Error::other(format!("nima preprocess shape: {e}")))
+        .map(IntoTensor::into_tensor)
+}
+
+/// `[1, 10]` 分布取 1~10 分期望；`[1, 1]` 标量原样返回；其余长度报错（错配模型）。
+pub(crate) fn decode(output: &Tensor) -> io::Result<f32> {
+    let cast = output
+        .cast_to::<f32>()
+        .map_err(|e| io::Error::other(format!("nima output not f32-castable: {e}")))?;
+    let view = cast.view();
+    let slice = view
+        .as_slice::<f32>()
+        .map_err(|e| io::Error::other(format!("nima output slice: {e}")))?;
+    match slice.len() {
+        1 if slice[0].is_finite() => Ok(slice[0]),
+        1 => Err(io::Error::other(format!(
+            "nima scalar output is not finite ({})",
+            slice[0]
+        ))),
+        SCORE_BINS => {
+            let mut mass = 0.0_f32;
+            let mut weighted = 0.0_f32;
+            let mut bin = 0.0_f32;
+            for &p in slice {
+                bin += 1.0;
+                mass += p;
+                weighted += bin * p;
+            }
+            if mass.is_finite() && mass > f32::EPSILON {
+                Ok(weighted / mass)
+            } else {
+                Err(io::Error::other(format!(
+                    "nima output distribution has no mass (sum={mass})"
+                )))
+            }
+        }
+        n => Err(io::Error::other(format!(
+            "nima output len {n} is neither a {SCORE_BINS}-bin distribution nor a scalar \
+             (check backend.face.quality_model_path)"
+        ))),
+    }
+}
+
+#[cfg(test)]
+#[path = "tract_nima_tests.rs"]
+mod tests;
@@ -0,0 +1,130 @@
+//! `tract_nima` 单元测试。
+
+use super::*;
+use camino::Utf8Path;
+
+struct ConstRaw {
+    values: Vec<f32>,
+}
+
+impl RawNima for ConstRaw {
+    fn run(&self, _input: Tensor) -> io::Result<Tensor> {
+        let t = tract_ndarray::Array2::from_shape_vec((1, self.values.len()), self.values.clone())
+            .expect("stub nima shape")
+            .into_tensor();
+        Ok(t)
+    }
+}
+
+struct FailRaw;
+impl RawNima for FailRaw {
+    fn run(&self, _input: Tensor) -> io::Result<Tensor> {
+        Err(io::Error::other("stub nima failed"))
+    }
+}
+
+fn cfg() -> FaceConfig {
+    FaceConfig {
+        quality_model_path: "ignored-by-stub".into(),
+        ..FaceConfig::default()
+    }
+}
+
+fn tiny_image() -> image::RgbImage {
+    image::RgbImage::from_pixel(8, 6, image::Rgb([128, 64, 200]))
+}
+
+fn tensor(values: &[f32]) -> Tensor {
+    tract_ndarray::Array2::from_shape_vec((1, values.len()), values.to_vec())
+        .unwrap()
+        .into_tensor()
+}
+
+#[test]
+fn build_quality_scorer_is_disabled_for_empty_or_blank_path() {
+    assert!(build_quality_scorer(&FaceConfig::default()).unwrap().is_none());
+    let blank = FaceConfig {
+        quality_model_path: "   ".into(),
+        ..FaceConfig::default()
+    };
+    assert!(build_quality_scorer(&blank).unwrap().is_none());
+    let model = tempfile::NamedTempFile::new().unwrap();
+    let present = FaceConfig {
+        quality_model_path: model.path().to_str().unwrap().into(),
+        ..FaceConfig::default()
+    };
+    let scorer = build_quality_scorer(&present)
+        .unwrap()
+        .expect("configured path enables the slot");
+    assert!(format!("{scorer:?}").contains("loaded: false"));
+}
+
+#[test]
+fn build_quality_scorer_rejects_missing_model_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let missing = FaceConfig {
+        quality_model_path: dir.path().join("nima.onnx").to_str().unwrap().into(),
+        ..FaceConfig::default()
+    };
+    let e = build_quality_scorer(&missing).err().expect("missing file is an error");
+    assert_eq!(e.kind(), io::ErrorKind::NotFound);
+    assert!(e.to_string().contains("quality_model_path"), "{e}");
+}
+
+#[test]
+fn score_quality_returns_distribution_mean() {
+    // 全部概率压在第 7 档 → 期望 7.0
+    let mut bins = vec![0.0; 10];
+    bins[6] = 1.0;
+    let scorer = TractNimaScorer::with_raw(cfg(), Box::new(ConstRaw { values: bins }));
+    let s = scorer
+        .score_quality(Utf8Path::new("/x.jpg"), &tiny_image())
+        .unwrap();
+    assert!((s - 7.0).abs() < 1e-5, "got: {s}");
+}
+
+#[test]
+fn decode_normalizes_unnormalized_distribution() {
+    // 均匀分布（未归一）→ 期望 5.5
+    let s = decode(&tensor(&[2.0; 10])).unwrap();
+    assert!((s - 5.5).abs() < 1e-5, "got: {s}");
+}
+
+#[test]
+fn decode_passes_scalar_regressor_through() {
+    let s = decode(&tensor(&[0.73])).unwrap();
+    assert!((s - 0.73).abs() < f32::EPSILON);
+}
+
+#[test]
+fn decode_rejects_empty_distribution_and_other_lengths() {
+    let e = decode(&tensor(&[0.0; 10])).unwrap_err();
+    assert!(e.to_string().contains("no mass"), "got: {e}");
+    let e = decode(&tensor(&[f32::NAN])).unwrap_err();
+    assert!(e.to_string().contains("not finite"), "got: {e}");
+    let e = decode(&tensor(&[0.5; 5])).unwrap_err();
+    assert!(e.to_string().contains("len 5"), "got: {e}");
+}
+
+#[test]
+fn score_quality_propagates_raw_error() {
+    let scorer = TractNimaScorer::with_raw(cfg(), Box::new(FailRaw));
+    let e = scorer
+        .score_quality(Utf8Path::new("/x.jpg"), &tiny_image())
+        .unwrap_err();
+    assert!(e.to_string().contains("stub nima failed"), "got: {e}");
+}
+
+#[test]
+fn preprocess_resizes_to_224_in_unit_range() {
+    let t = preprocess(&image::RgbImage::from_pixel(
+        300,
+        200,
+        image::Rgb([255, 0, 128]),
+    ))
+    .unwrap();
+    assert_eq!(t.shape(), [1, 3, 224, 224]);
+    let view = t.to_array_view::<f32>().unwrap();
+    assert!((view[[0, 0, 0, 0]] - 1.0).abs() < 1e-5);
+    assert!((view[[0, 1, 0, 0]] + 1.0).abs() < 1e-5);
+}
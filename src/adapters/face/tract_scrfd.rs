@@ -9,13 +9,13 @@
 
 use std::io;
 use std::path::Path;
-use std::sync::{Arc, OnceLock};
+use std::sync::Arc;
 
 use camino::Utf8Path;
-use parking_lot::Mutex;
 use tract_onnx::prelude::*;
 
 use super::tract_scrfd_real::{ScaleMeta, TractRawScrfd, load_runnable};
+use crate::adapters::model_pool::{ModelPool, SharedModel};
 use crate::usecases::config::FaceConfig;
 use crate::usecases::face::{FaceDetection, FaceDetector};
 
@@ -39,69 +39,53 @@ pub(crate) trait RawScrfd: Send + Sync {
 
 pub struct TractScrfdDetector {
     cfg: FaceConfig,
-    // OnceLock 让 lazy init 后 inference 无锁并发：旧 Mutex<Option<...>> 让每次
-    // detect_faces 都加锁，rayon 并行评分时所有 worker 串行化在同一把锁上。
-    raw: OnceLock<Box<dyn RawScrfd>>,
-    // 仅 load 阶段互斥：35 worker 同时进 ensure_raw 时若都各自调 load_runnable，
-    // 250 MB SCRFD model parse + tract optimize 会被并行执行 N 次（OnceLock::set
-    // race 只有 1 个生效，其它 N-1 个 worker 已浪费了 load 时间）。double-checked
-    // locking 让 load 只跑 1 次；inference 仍走 OnceLock::get 无锁。
-    load_lock: Mutex<()>,
+    model: SharedModel<ScrfdModel>,
+    // 每个 worker 一份推理实例，槽数 = `inference_workers`（见 `adapters::model_pool`）。
+    pool: ModelPool<dyn RawScrfd>,
 }
 
 impl std::fmt::Debug for TractScrfdDetector {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("TractScrfdDetector")
             .field("scrfd_model_path", &self.cfg.scrfd_model_path)
-            .field("loaded", &self.raw.get().is_some())
-            .field("load_lock", &self.load_lock)
+            .field("workers", &self.pool.workers())
+            .field("loaded", &(self.pool.loaded() > 0))
             .finish()
     }
 }
 
 impl TractScrfdDetector {
-    #[cfg(test)]
-    pub(crate) fn with_raw(cfg: FaceConfig, raw: Box<dyn RawScrfd>) -> Self {
-        let cell = OnceLock::new();
-        let _ = cell.set(raw);
+    fn new(cfg: &FaceConfig, pool: ModelPool<dyn RawScrfd>) -> Self {
         Self {
-            cfg,
-            raw: cell,
-            load_lock: Mutex::new(()),
+            cfg: cfg.clone(),
+            model: SharedModel::default(),
+            pool,
         }
     }
 
+    #[cfg(test)]
+    pub(crate) fn with_raw(cfg: FaceConfig, raw: Box<dyn RawScrfd>) -> Self {
+        Self::new(&cfg, ModelPool::preloaded(raw))
+    }
+
     #[cfg_attr(coverage_nightly, coverage(off))]
-    fn ensure_raw(&self) -> io::Result<&dyn RawScrfd> {
-        // 快路径：已 load 直接 OnceLock::get 无锁返。
-        if let Some(r) = self.raw.get() {
-            return Ok(r.as_ref());
-        }
-        // 慢路径：拿 load_lock 串行 load；双查避免 N worker 都 load 一次。
-        let _guard = self.load_lock.lock();
-        if let Some(r) = self.raw.get() {
-            return Ok(r.as_ref());
-        }
-        let model = load_runnable(Path::new(&self.cfg.scrfd_model_path))?;
-        let boxed: Box<dyn RawScrfd> = Box::new(TractRawScrfd {
+    fn load_instance(&self) -> io::Result<Box<dyn RawScrfd>> {
+        let model = self
+            .model
+            .get_or_load(|| load_runnable(Path::new(&self.cfg.scrfd_model_path)))?;
+        Ok(Box::new(TractRawScrfd {
             model,
             score_threshold: self.cfg.scrfd_score_threshold,
             nms_iou: self.cfg.scrfd_nms_iou,
-        });
-        let _ = self.raw.set(boxed);
-        Ok(self
-            .raw
-            .get()
-            .expect("OnceLock set by self under load_lock")
-            .as_ref())
+        }))
     }
 }
 
 impl FaceDetector for TractScrfdDetector {
     fn detect_faces(&self, _path: &Utf8Path, image_bytes: &[u8]) -> io::Result<Vec<FaceDetection>> {
-        let raw = self.ensure_raw()?;
         let (input, meta) = preprocess(image_bytes)?;
-        raw.run(input, meta)
+        self.pool
+            .run(|| self.load_instance(), |raw| raw.run(input, meta))
     }
 }
 
@@ -117,11 +101,10 @@ pub fn build_scrfd_detector(cfg: &FaceConfig) -> io::Result<Box<dyn FaceDetector
             "backend.face.scrfd_model_path is empty; set TIDYMEDIA_FACE_SCRFD_MODEL or config.yaml",
         ));
     }
-    Ok(Box::new(TractScrfdDetector {
-        cfg: cfg.clone(),
-        raw: OnceLock::new(),
-        load_lock: Mutex::new(()),
-    }))
+    Ok(Box::new(TractScrfdDetector::new(
+        cfg,
+        ModelPool::new(cfg.inference_workers),
+    )))
 }
 
 /// Letterbox 把图像 resize 到 640×640（保持长宽比，灰色 128 padding），同时记录
@@ -0,0 +1,30 @@
+//! 人脸库持久化 Gateway：[`FaceDb`] 与 JSON 文件互转，读写走 [`super::json_store`]。
+
+use crate::entities::common;
+use crate::usecases::faces::{FACE_DB_VERSION, FaceDb};
+
+use super::json_store::{VersionPolicy, load_versioned_json, save_json};
+
+/// 读取人脸库；文件不存在视为空库（首次 `faces scan`）。
+///
+/// # Errors
+///
+/// 读盘失败、内容不是合法人脸库或版本不受支持时返回 `Err`——人名是用户手工标注、不可
+/// 重算，损坏或不认识的库不当空处理，否则随后的保存会把全部 embedding 与命名覆盖掉。
+pub fn load_face_db(path: &str) -> common::Result<FaceDb> {
+    load_versioned_json(path, "face db", VersionPolicy::Reject(FACE_DB_VERSION))
+}
+
+/// 原子写回人脸库。
+///
+/// # Errors
+///
+/// 临时文件创建、写入或 rename 失败时返回 `Err`。
+pub fn save_face_db(path: &str, db: &FaceDb) -> common::Result<()> {
+    // 只含 String / 整数 / 有限 f32（scan 已丢弃非有限 embedding），序列化不可能失败。
+    save_json(path, db)
+}
+
+#[cfg(test)]
+#[path = "face_db_store_tests.rs"]
+mod tests;
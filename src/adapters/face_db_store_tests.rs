@@ -0,0 +1,18 @@
+use tempfile::tempdir;
+
+use super::*;
+
+/// 不认识的版本报错而非重建：人名不可重算。
+#[test]
+fn unsupported_version_is_an_error() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("faces.json");
+    std::fs::write(
+        &path,
+        br#"{"version":99,"images":{},"names":{},"next_cluster_id":1}"#,
+    )
+    .unwrap();
+    let err = load_face_db(path.to_str().unwrap()).unwrap_err();
+    assert!(err.to_string().contains("face db"), "{err}");
+    assert!(err.to_string().contains("version 99"), "{err}");
+}
@@ -0,0 +1,128 @@
+//! 跨次运行的 JSON 状态文件（catalog / 人脸库 / cull 分析缓存）共用读写。
+//!
+//! - 文件不存在视为空状态（首次使用）
+//! - 内容损坏报错，绝不静默当空：随后的保存会把原文件整体覆盖掉
+//! - 版本号先于其余字段探测，不符时按 [`VersionPolicy`] 报错或整体重建
+//! - 写盘走临时文件 + 原子 rename（[`super::report_sink::write_atomic`]），中途崩溃不会
+//!   留下半截文件
+//!
+//! 泛型入口只做反序列化 / 序列化一步，其余逻辑放在非泛型函数里：同 `write_atomic`，
+//! 避免每个状态类型一份 monomorphization 让 llvm-cov 分别计 region。
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_derive::Deserialize;
+use tracing::warn;
+
+use super::report_sink::write_atomic;
+use crate::entities::common;
+
+const FEATURE_STORE: &str = "state_file";
+
+/// 文件中的格式版本与当前不符时的处理。
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum VersionPolicy {
+    /// 无版本字段，不探测。
+    Unversioned,
+    /// 报错：内容不可再生（如人脸命名），宁可拒绝也不覆盖。
+    Reject(u32),
+    /// 丢弃重建：纯缓存，重算即可；旧格式无需能解析其余字段。
+    Rebuild(u32),
+}
+
+/// 只读版本号。
+#[derive(Deserialize)]
+struct VersionProbe {
+    version: u32,
+}
+
+/// 读取状态文件；`what` 为错误信息与日志中的文件称谓（如 `"catalog"`）。
+///
+/// # Errors
+///
+/// 读盘失败、内容不是合法 JSON / 缺版本号，或 [`VersionPolicy::Reject`] 下版本不符时返回
+/// `Err`。
+pub(crate) fn load_versioned_json<T: DeserializeOwned + Default>(
+    path: &str,
+    what: &'static str,
+    policy: VersionPolicy,
+) -> common::Result<T> {
+    match read_versioned(path, what, policy)? {
+        Some(bytes) => serde_json::from_slice(&bytes).map_err(corrupt(path, what)),
+        None => Ok(T::default()),
+    }
+}
+
+/// 原子写回状态文件。调用方保证 `value` 只含可序列化的值（有限浮点、字符串键等）。
+///
+/// # Errors
+///
+/// 临时文件创建、写入或 rename 失败时返回 `Err`。
+pub(crate) fn save_json<T: Serialize>(path: &str, value: &T) -> common::Result<()> {
+    let json =
+        serde_json::to_vec(value).expect("internal error: serializing state file must not fail");
+    write_atomic(path, &json)
+}
+
+// 文件字节；None = 文件不存在或按策略整体重建，调用方取默认值。
+fn read_versioned(
+    path: &str,
+    what: &'static str,
+    policy: VersionPolicy,
+) -> common::Result<Option<Vec<u8>>> {
+    let bytes = match std::fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let (expected, rebuild) = match policy {
+        VersionPolicy::Unversioned => return Ok(Some(bytes)),
+        VersionPolicy::Reject(expected) => (expected, false),
+        VersionPolicy::Rebuild(expected) => (expected, true),
+    };
+    let probe: VersionProbe = serde_json::from_slice(&bytes).map_err(corrupt(path, what))?;
+    if probe.version == expected {
+        return Ok(Some(bytes));
+    }
+    if rebuild {
+        log_rebuilt(path, what, probe.version);
+        return Ok(None);
+    }
+    Err(common::Error::Io(std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!(
+            "{what} {path} version {} is not supported (expected {expected})",
+            probe.version
+        ),
+    )))
+}
+
+// 返回闭包而非在泛型入口里写闭包：闭包体只此一份 instance。
+fn corrupt<'a>(
+    path: &'a str,
+    what: &'static str,
+) -> impl FnOnce(serde_json::Error) -> common::Error + 'a {
+    move |e| {
+        common::Error::Io(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("{what} {path} is corrupt: {e}"),
+        ))
+    }
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+fn log_rebuilt(path: &str, what: &'static str, version: u32) {
+    warn!(
+        feature = FEATURE_STORE,
+        operation = "load",
+        result = "rebuilt",
+        file = what,
+        path,
+        version,
+        "state file version is not supported; starting from an empty state"
+    );
+}
+
+#[cfg(test)]
+#[path = "json_store_tests.rs"]
+mod tests;
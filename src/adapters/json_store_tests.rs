@@ -0,0 +1,112 @@
+use serde_derive::Serialize;
+use tempfile::tempdir;
+
+use super::*;
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+struct Doc {
+    version: u32,
+    items: Vec<String>,
+}
+
+fn doc() -> Doc {
+    Doc {
+        version: 2,
+        items: vec!["a".into(), "b".into()],
+    }
+}
+
+#[test]
+fn missing_file_loads_as_empty_and_save_round_trips() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("state.json");
+    let path = path.to_str().unwrap();
+
+    for policy in [
+        VersionPolicy::Unversioned,
+        VersionPolicy::Reject(2),
+        VersionPolicy::Rebuild(2),
+    ] {
+        let empty: Doc = load_versioned_json(path, "doc", policy).unwrap();
+        assert_eq!(empty, Doc::default());
+    }
+    save_json(path, &doc()).unwrap();
+    for policy in [
+        VersionPolicy::Unversioned,
+        VersionPolicy::Reject(2),
+        VersionPolicy::Rebuild(2),
+    ] {
+        let loaded: Doc = load_versioned_json(path, "doc", policy).unwrap();
+        assert_eq!(loaded, doc());
+    }
+}
+
+/// 损坏的文件报错而非当空：否则下一次保存会覆盖它。缓存同样如此——路径可能误指向别的文件。
+#[test]
+fn corrupt_file_is_an_error() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("state.json");
+    std::fs::write(&path, b"{not json").unwrap();
+    for policy in [
+        VersionPolicy::Unversioned,
+        VersionPolicy::Reject(2),
+        VersionPolicy::Rebuild(2),
+    ] {
+        let err = load_versioned_json::<Doc>(path.to_str().unwrap(), "doc", policy).unwrap_err();
+        assert!(err.to_string().contains("doc "), "{err}");
+        assert!(err.to_string().contains("is corrupt"), "{err}");
+    }
+}
+
+/// 版本号对上但其余字段不合法同样算损坏。
+#[test]
+fn matching_version_with_bad_layout_is_corrupt() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("state.json");
+    std::fs::write(&path, br#"{"version":2,"items":"oops"}"#).unwrap();
+    let err = load_versioned_json::<Doc>(path.to_str().unwrap(), "doc", VersionPolicy::Rebuild(2))
+        .unwrap_err();
+    assert!(err.to_string().contains("is corrupt"), "{err}");
+}
+
+/// 缺版本号：有版本策略时视为不是本类文件。
+#[test]
+fn missing_version_is_corrupt() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("state.json");
+    std::fs::write(&path, br#"{"items":[]}"#).unwrap();
+    let err = load_versioned_json::<Doc>(path.to_str().unwrap(), "doc", VersionPolicy::Rebuild(2))
+        .unwrap_err();
+    assert!(err.to_string().contains("is corrupt"), "{err}");
+}
+
+#[test]
+fn other_version_is_rejected_or_rebuilt_by_policy() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("state.json");
+    let path = path.to_str().unwrap();
+    // 新格式的其余字段旧代码解析不了：探测只读版本号。
+    std::fs::write(path, br#"{"version":9,"items":{"new":"layout"}}"#).unwrap();
+
+    let err = load_versioned_json::<Doc>(path, "doc", VersionPolicy::Reject(2)).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("version 9 is not supported (expected 2)"),
+        "{err}"
+    );
+    let rebuilt: Doc = load_versioned_json(path, "doc", VersionPolicy::Rebuild(2)).unwrap();
+    assert_eq!(rebuilt, Doc::default());
+}
+
+#[test]
+fn unreadable_path_is_an_error() {
+    let dir = tempdir().unwrap();
+    // 目录不能当文件读：非 NotFound 的 IO 错误原样上抛。
+    let err = load_versioned_json::<Doc>(
+        dir.path().to_str().unwrap(),
+        "doc",
+        VersionPolicy::Unversioned,
+    )
+    .unwrap_err();
+    assert!(!err.to_string().contains("is corrupt"), "{err}");
+}
@@ -1,8 +1,15 @@
 // Interface Adapters 层：CLI 解析、命令调度、Gateway 实现。
 pub mod backend;
+pub mod catalog_store;
 pub mod cli;
+pub mod cull_cache_store;
+pub mod cull_review;
 pub mod dispatch;
 pub mod face;
+pub mod face_db_store;
+pub(crate) mod json_store;
+pub(crate) mod model_pool;
 pub mod ocr;
+pub mod photos_library;
 pub mod report_sink;
 pub mod sidecar;
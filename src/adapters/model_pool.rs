@@ -0,0 +1,205 @@
+//! tract 推理 worker 池与批量切块策略，供 `adapters::face` / `adapters::ocr` 各 adapter 共用。
+//!
+//! - [`ModelPool`]：按配置的 `inference_workers` 开若干槽，每槽持一份推理实例。调用线程
+//!   优先取自己的槽（rayon worker 下标取模），被占时取任一空槽，全满才在自己的槽上等待。
+//!   槽数即同一模型的并发推理上限——每次推理的中间张量（SCRFD / `EyeState` 640×640
+//!   特征图、`DBNet` 整图概率图）按槽数封顶，内存紧的机器调低即可
+//! - 槽内实例懒加载；权重经 [`SharedModel`] 只解析一次、各槽共享（tract 执行计划只读）
+//! - [`BatchPolicy`]：批量入口按 `inference_batch_size` 切块；多项块失败时逐张重跑，逐张
+//!   全部成功才认定模型不支持批量（导出时固定 batch=1），此后该 adapter 不再尝试批量。
+//!   单张也失败说明是该项本身的问题，错误上抛，批量照旧
+
+use std::io;
+use std::num::NonZeroUsize;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::available_parallelism;
+
+use parking_lot::{Mutex, MutexGuard};
+use tracing::warn;
+
+const FEATURE_INFERENCE: &str = "inference";
+
+/// 推理实例池：`R` 为各 adapter 的 `Raw*` trait 对象。
+pub(crate) struct ModelPool<R: ?Sized> {
+    slots: Box<[Mutex<Option<Box<R>>>]>,
+}
+
+impl<R: ?Sized> ModelPool<R> {
+    /// `workers` 为 0 时取 CPU 核数，与 rayon 全局池同宽。
+    pub(crate) fn new(workers: usize) -> Self {
+        let workers = match workers {
+            0 => available_parallelism().map_or(4, NonZeroUsize::get),
+            n => n,
+        };
+        Self {
+            slots: (0..workers).map(|_| Mutex::new(None)).collect(),
+        }
+    }
+
+    /// 单槽且实例已就位：单测注入 stub 用。
+    #[cfg(test)]
+    pub(crate) fn preloaded(instance: Box<R>) -> Self {
+        Self {
+            slots: Box::new([Mutex::new(Some(instance))]),
+        }
+    }
+
+    pub(crate) fn workers(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// 已加载实例的槽数（Debug 输出用）；正被占用的槽必已加载。
+    pub(crate) fn loaded(&self) -> usize {
+        self.slots
+            .iter()
+            .filter(|slot| slot.try_lock().is_none_or(|s| s.is_some()))
+            .count()
+    }
+
+    /// 取一个槽跑 `run`；槽内尚无实例时先 `load`。加载失败槽仍空着，下次调用重试。
+    ///
+    /// # Errors
+    ///
+    /// `load` 或 `run` 失败时原样返回。
+    pub(crate) fn run<T>(
+        &self,
+        load: impl FnOnce() -> io::Result<Box<R>>,
+        run: impl FnOnce(&R) -> io::Result<T>,
+    ) -> io::Result<T> {
+        let mut slot = self.acquire();
+        let instance = match &mut *slot {
+            Some(instance) => instance,
+            empty => empty.insert(load()?),
+        };
+        run(instance)
+    }
+
+    fn acquire(&self) -> MutexGuard<'_, Option<Box<R>>> {
+        let n = self.slots.len();
+        let home = rayon::current_thread_index().unwrap_or(0) % n;
+        (0..n)
+            .find_map(|i| self.slots[(home + i) % n].try_lock())
+            .unwrap_or_else(|| self.slots[home].lock())
+    }
+}
+
+/// 各槽共享的只读权重。首次加载在锁内双查：N 个 worker 同时冷启动也只解析一次 ONNX
+/// （250 MB 的 SCRFD 解析 + tract optimize 并行跑 N 次，只有一份能留下）。
+pub(crate) struct SharedModel<M> {
+    cell: OnceLock<M>,
+    load_lock: Mutex<()>,
+}
+
+impl<M> Default for SharedModel<M> {
+    fn default() -> Self {
+        Self {
+            cell: OnceLock::new(),
+            load_lock: Mutex::new(()),
+        }
+    }
+}
+
+impl<M: Clone> SharedModel<M> {
+    /// 已加载时返回共享句柄，否则调 `load` 加载；加载失败不缓存，下次调用重试。
+    ///
+    /// # Errors
+    ///
+    /// `load` 失败时原样返回。
+    pub(crate) fn get_or_load(&self, load: impl FnOnce() -> io::Result<M>) -> io::Result<M> {
+        if let Some(model) = self.cell.get() {
+            return Ok(model.clone());
+        }
+        let _guard = self.load_lock.lock();
+        if let Some(model) = self.cell.get() {
+            return Ok(model.clone());
+        }
+        let model = load()?;
+        Ok(self.cell.get_or_init(|| model).clone())
+    }
+}
+
+/// 批量推理切块策略，见模块文档。
+#[derive(Debug)]
+pub(crate) struct BatchPolicy {
+    size: usize,
+    unbatched: AtomicBool,
+}
+
+impl BatchPolicy {
+    /// `size` 为 0 按 1 处理（逐张）。
+    pub(crate) fn new(size: usize) -> Self {
+        Self {
+            size: size.max(1),
+            unbatched: AtomicBool::new(false),
+        }
+    }
+
+    /// 按块调 `run`（返回须与块等长同序）拼回整批结果。多项块失败（含返回条数不符）时
+    /// 该块逐张重跑：逐张全部成功即记下模型不支持批量；任一单张失败则原样上抛，不改变
+    /// 批量策略。
+    ///
+    /// # Errors
+    ///
+    /// 单张 `run` 失败，或返回条数与输入不等（`InvalidData`）时返回 `Err`，已算出的结果
+    /// 随之丢弃。
+    pub(crate) fn run<I, T>(
+        &self,
+        model: &'static str,
+        items: &[I],
+        mut run: impl FnMut(&[I]) -> io::Result<Vec<T>>,
+    ) -> io::Result<Vec<T>> {
+        let size = if self.unbatched.load(Ordering::Relaxed) {
+            1
+        } else {
+            self.size
+        };
+        let mut out = Vec::with_capacity(items.len());
+        for chunk in items.chunks(size) {
+            match run(chunk).and_then(|results| exact_len(model, results, chunk.len())) {
+                Ok(results) => out.extend(results),
+                Err(e) if chunk.len() > 1 => {
+                    for one in chunk.chunks(1) {
+                        out.extend(exact_len(model, run(one)?, 1)?);
+                    }
+                    if !self.unbatched.swap(true, Ordering::Relaxed) {
+                        log_batch_fallback(model, chunk.len(), &e);
+                    }
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(out)
+    }
+}
+
+// 少返回一行会让 embedding / 闭眼概率与人脸错位，宁可整批报错。
+fn exact_len<T>(model: &'static str, results: Vec<T>, expected: usize) -> io::Result<Vec<T>> {
+    if results.len() == expected {
+        return Ok(results);
+    }
+    Err(io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!(
+            "{model} returned {} results for {expected} inputs",
+            results.len()
+        ),
+    ))
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+fn log_batch_fallback(model: &'static str, batch: usize, e: &io::Error) {
+    warn!(
+        feature = FEATURE_INFERENCE,
+        operation = "batch",
+        result = "fallback",
+        model,
+        batch,
+        error = %e,
+        "batched inference failed; falling back to one item per run"
+    );
+}
+
+#[cfg(test)]
+#[path = "model_pool_tests.rs"]
+mod tests;
@@ -0,0 +1,142 @@
+use std::cell::Cell;
+
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use super::*;
+
+#[test]
+fn pool_loads_each_slot_lazily_once() {
+    let pool: ModelPool<u32> = ModelPool::new(1);
+    assert_eq!((pool.workers(), pool.loaded()), (1, 0));
+    let loads = Cell::new(0);
+    let load = || {
+        loads.set(loads.get() + 1);
+        Ok(Box::new(7))
+    };
+    assert_eq!(pool.run(load, |v| Ok(*v + 1)).unwrap(), 8);
+    assert_eq!(pool.run(load, |v| Ok(*v)).unwrap(), 7);
+    assert_eq!((loads.get(), pool.loaded()), (1, 1));
+}
+
+/// 加载失败不占槽：下次调用重新加载。
+#[test]
+fn pool_retries_failed_load() {
+    let pool: ModelPool<u32> = ModelPool::new(1);
+    let err = pool
+        .run(|| Err(io::Error::other("no model")), |v| Ok(*v))
+        .unwrap_err();
+    assert_eq!(err.to_string(), "no model");
+    assert_eq!(pool.loaded(), 0);
+    assert_eq!(pool.run(|| Ok(Box::new(3)), |v| Ok(*v)).unwrap(), 3);
+}
+
+#[test]
+fn pool_sizes_zero_workers_by_cpu_count() {
+    assert!(ModelPool::<u32>::new(0).workers() >= 1);
+    assert_eq!(ModelPool::<u32>::new(3).workers(), 3);
+}
+
+/// 并发调用不超过槽数个实例，且每个调用都拿到结果。
+#[test]
+fn pool_serves_concurrent_callers_from_bounded_slots() {
+    let pool: ModelPool<usize> = ModelPool::new(2);
+    let sum: usize = (0..64_usize)
+        .into_par_iter()
+        .map(|i| pool.run(|| Ok(Box::new(1)), |v| Ok(*v + i)).unwrap())
+        .sum();
+    assert_eq!(sum, 64 + (0..64).sum::<usize>());
+    assert!((1..=2).contains(&pool.loaded()));
+}
+
+/// 权重只加载一次；加载失败不缓存。
+#[test]
+fn shared_model_loads_once_and_retries_failures() {
+    let shared: SharedModel<u32> = SharedModel::default();
+    assert!(
+        shared
+            .get_or_load(|| Err(io::Error::other("missing")))
+            .is_err()
+    );
+    assert_eq!(shared.get_or_load(|| Ok(5)).unwrap(), 5);
+    assert_eq!(shared.get_or_load(|| Ok(9)).unwrap(), 5);
+}
+
+#[test]
+fn batch_policy_chunks_items() {
+    let policy = BatchPolicy::new(3);
+    let mut sizes = Vec::new();
+    let out = policy
+        .run("test", &[1, 2, 3, 4, 5, 6, 7], |chunk| {
+            sizes.push(chunk.len());
+            Ok(chunk.iter().map(|v| v * 10).collect())
+        })
+        .unwrap();
+    assert_eq!(out, [10, 20, 30, 40, 50, 60, 70]);
+    assert_eq!(sizes, [3, 3, 1]);
+}
+
+/// 固定 batch=1 的模型：首个多项块失败后退回逐张，此后直接逐张。
+#[test]
+fn batch_policy_falls_back_to_single_items() {
+    let policy = BatchPolicy::new(4);
+    let mut sizes = Vec::new();
+    let mut run = |chunk: &[u8]| {
+        sizes.push(chunk.len());
+        if chunk.len() > 1 {
+            return Err(io::Error::other("batch dim is fixed"));
+        }
+        Ok(vec![chunk[0]])
+    };
+    assert_eq!(policy.run("test", &[1, 2, 3], &mut run).unwrap(), [1, 2, 3]);
+    assert_eq!(policy.run("test", &[4, 5], &mut run).unwrap(), [4, 5]);
+    assert_eq!(sizes, [3, 1, 1, 1, 1, 1]);
+}
+
+#[test]
+fn batch_policy_propagates_single_item_error() {
+    let policy = BatchPolicy::new(0);
+    let err = policy
+        .run("test", &[1, 2], |chunk: &[u8]| {
+            if chunk[0] == 2 {
+                return Err(io::Error::other("bad input"));
+            }
+            Ok(vec![chunk[0]])
+        })
+        .unwrap_err();
+    assert_eq!(err.to_string(), "bad input");
+}
+
+/// 单张也失败说明是该项本身的问题：错误上抛，批量策略不变。
+#[test]
+fn batch_policy_keeps_batching_when_an_item_is_bad() {
+    let policy = BatchPolicy::new(4);
+    let mut sizes = Vec::new();
+    let mut run = |chunk: &[u8]| {
+        sizes.push(chunk.len());
+        if chunk.contains(&2) {
+            return Err(io::Error::other("bad crop"));
+        }
+        Ok(chunk.to_vec())
+    };
+    let err = policy.run("test", &[1, 2, 3], &mut run).unwrap_err();
+    assert_eq!(err.to_string(), "bad crop");
+    assert_eq!(policy.run("test", &[4, 5], &mut run).unwrap(), [4, 5]);
+    assert_eq!(sizes, [3, 1, 1, 2]);
+    assert!(format!("{policy:?}").contains("unbatched: false"));
+}
+
+/// 返回条数与输入不等：多项块按批量失败处理，单张直接报 `InvalidData`。
+#[test]
+fn batch_policy_rejects_result_count_mismatch() {
+    let policy = BatchPolicy::new(2);
+    let out = policy
+        .run("test", &[1, 2], |chunk: &[u8]| Ok(chunk[..1].to_vec()))
+        .unwrap();
+    assert_eq!(out, [1, 2]);
+
+    let err = policy
+        .run("test", &[1], |_: &[u8]| Ok(Vec::<u8>::new()))
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert!(err.to_string().contains("0 results for 1 inputs"), "{err}");
+}
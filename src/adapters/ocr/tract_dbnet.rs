@@ -1,9 +1,9 @@
 //! tract-onnx 实现的 `TextDetector`：跑 `PaddleOCR` `DBNet` `det.onnx` 仅判「有/无文本」。
 //!
 //! 设计：
-//! - **懒加载**：首次 `has_text` 触发模型加载（`OnceLock<Result<...>>` 风格——但 stable
-//!   Rust 不支持 `get_or_try_init`，用 `Mutex<Option<...>>` 兼容；模型加载是 idempotent
-//!   读 ONNX 文件，竞态首次加载多次也无副作用）
+//! - **懒加载 + worker 池**：首次 `has_text` 触发模型加载，权重只解析一次；推理实例按
+//!   `inference_workers` 开槽（`adapters::model_pool`），多个 worker 并行 OCR。输入按原图
+//!   长宽比 resize，各图尺寸不同，不做跨图批量
 //! - **极简后处理**：sigmoid 输出图中 `v > binarize_threshold` 像素占比 > `min_text_pixel_ratio`
 //!   即判命中。**跳过 polygon contour**（不取文本框坐标，本子命令只需二元判定）
 //! - **真实模型加载隔离**：`tract_dbnet_real::*` 拆出整文件让 `--ignore-filename-regex='_real\.rs$'`
@@ -15,10 +15,10 @@ use std::sync::Arc;
 
 use camino::Utf8Path;
 use image::GenericImageView;
-use parking_lot::Mutex;
 use tract_onnx::prelude::*;
 
 use super::tract_dbnet_real::load_runnable;
+use crate::adapters::model_pool::{ModelPool, SharedModel};
 use crate::usecases::config::OcrConfig;
 use crate::usecases::ocr::TextDetector;
 
@@ -40,9 +40,9 @@ pub(crate) trait RawDetector: Send + Sync {
 /// 真实 tract `RunnableModel` 适配为 `RawDetector`：仅薄包一层，让前/后处理在
 /// 本文件保持单元测试可达，真实模型路径走 `_real.rs`（CI 不触发）。
 ///
-/// 本 struct 与 `run` impl 仅在真实 ONNX 模型存在时才被构造（`ensure_raw` 中），
+/// 本 struct 与 `run` impl 仅在真实 ONNX 模型存在时才被构造（`load_instance` 中），
 /// CI 环境无模型文件不可触发；`run` 加 `coverage(off)`，struct 自身字段
-/// 由 `ensure_raw` 的 `coverage(off)` 间接排除（attribute 不能加在 struct 上）。
+/// 由 `load_instance` 的 `coverage(off)` 间接排除（attribute 不能加在 struct 上）。
 struct TractRawDetector {
     model: DetModel,
 }
@@ -62,11 +62,11 @@ impl RawDetector for TractRawDetector {
     }
 }
 
-/// Detector 主体：持有 OCR 配置 + 懒加载模型。`Mutex<Option<...>>` 替代 nightly 的
-/// `OnceLock::get_or_try_init`；模型加载是 idempotent，竞态首次加载多次无副作用。
+/// Detector 主体：持有 OCR 配置 + 懒加载的共享权重 + 推理实例池。
 pub struct TractDbnetDetector {
     cfg: OcrConfig,
-    raw: Mutex<Option<Box<dyn RawDetector>>>,
+    model: SharedModel<DetModel>,
+    pool: ModelPool<dyn RawDetector>,
 }
 
 impl std::fmt::Debug for TractDbnetDetector {
@@ -76,48 +76,46 @@ impl std::fmt::Debug for TractDbnetDetector {
             .field("binarize_threshold", &self.cfg.binarize_threshold)
             .field("min_text_pixel_ratio", &self.cfg.min_text_pixel_ratio)
             .field("resize_max_side", &self.cfg.resize_max_side)
-            .field("loaded", &self.raw.lock().is_some())
+            .field("workers", &self.pool.workers())
+            .field("loaded", &(self.pool.loaded() > 0))
             .finish()
     }
 }
 
 impl TractDbnetDetector {
-    #[cfg(test)]
-    pub(crate) fn with_raw(cfg: OcrConfig, raw: Box<dyn RawDetector>) -> Self {
+    fn new(cfg: &OcrConfig, pool: ModelPool<dyn RawDetector>) -> Self {
         Self {
-            cfg,
-            raw: Mutex::new(Some(raw)),
+            cfg: cfg.clone(),
+            model: SharedModel::default(),
+            pool,
         }
     }
 
+    #[cfg(test)]
+    pub(crate) fn with_raw(cfg: OcrConfig, raw: Box<dyn RawDetector>) -> Self {
+        Self::new(&cfg, ModelPool::preloaded(raw))
+    }
+
     // `coverage(off)`：真实模型加载路径——`load_runnable` 在 `_real.rs` 由
-    // ignore-regex 排除；CI 不分发 ONNX 文件，ensure_raw 在 lib unit / 集成测试
-    // 始终因为 `with_raw` 提前注入 `Some(...)` 而 early return。逻辑由
+    // ignore-regex 排除；CI 不分发 ONNX 文件，lib unit / 集成测试经 `with_raw`
+    // 预先注入实例，从不走到这里。逻辑由
     // `dispatch_returns_invalid_input_when_model_path_empty` + 真模型手动验证
     // （plan「验证」第 5 步）覆盖。
     #[cfg_attr(coverage_nightly, coverage(off))]
-    fn ensure_raw(&self) -> io::Result<()> {
-        let mut guard = self.raw.lock();
-        if guard.is_some() {
-            return Ok(());
-        }
-        let model = load_runnable(Path::new(&self.cfg.det_model_path))?;
-        *guard = Some(Box::new(TractRawDetector { model }));
-        Ok(())
+    fn load_instance(&self) -> io::Result<Box<dyn RawDetector>> {
+        let model = self
+            .model
+            .get_or_load(|| load_runnable(Path::new(&self.cfg.det_model_path)))?;
+        Ok(Box::new(TractRawDetector { model }))
     }
 }
 
 impl TextDetector for TractDbnetDetector {
     fn has_text(&self, _path: &Utf8Path, image_bytes: &[u8]) -> io::Result<bool> {
-        self.ensure_raw()?;
         let input = preprocess(image_bytes, self.cfg.resize_max_side)?;
-        let output = {
-            let guard = self.raw.lock();
-            guard
-                .as_ref()
-                .expect("ensure_raw set Some before lock release")
-                .run(input)?
-        };
+        let output = self
+            .pool
+            .run(|| self.load_instance(), |raw| raw.run(input))?;
         decide(
             &output,
             self.cfg.binarize_threshold,
@@ -139,10 +137,10 @@ pub fn build_detector(cfg: &OcrConfig) -> io::Result<Box<dyn TextDetector>> {
             "backend.ocr.det_model_path is empty; set TIDYMEDIA_OCR_DET_MODEL or config.yaml",
         ));
     }
-    Ok(Box::new(TractDbnetDetector {
-        cfg: cfg.clone(),
-        raw: Mutex::new(None),
-    }))
+    Ok(Box::new(TractDbnetDetector::new(
+        cfg,
+        ModelPool::new(cfg.inference_workers),
+    )))
 }
 
 /// 把图像字节解码、resize 到 32 倍数短边、ImageNet normalize、HWC→CHW、add batch
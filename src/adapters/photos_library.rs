@@ -0,0 +1,253 @@
+//! Apple Photos 图库 Gateway：把 macOS `*.photoslibrary` 包内的 `database/Photos.sqlite`
+//! 解析成原图路径 → 元数据映射，并向 copy 流程注入 P0 [`Candidate`] 与整理标记
+//! [`Tags`]（收藏 → `--favorite`，相册 → `{album}`，导入前原名 → `{original_name}`）。
+//!
+//! 只读打开本地 SQLite（`SQLITE_OPEN_READ_ONLY`，不修改图库；Photos 运行中亦可读）。
+//! SQLite（rusqlite bundled）仅在 `photos-library` feature（默认启用）下编译，查询实现见
+//! `photos_library_sqlite.rs`；未启用时 [`PhotosLibrary::open`] 返 `Unsupported`，
+//! 图库包按普通目录归档（仍只扫 `originals/`）。
+//! Schema 覆盖 Photos 5+（Catalina 起 `originals/` 布局）：
+//!   - 资产表 `ZASSET`（Big Sur+）/ `ZGENERICASSET`（Catalina）
+//!   - `ZADDITIONALASSETATTRIBUTES.ZTIMEZONEOFFSET` 给出拍摄地时区
+//!   - 相册 `ZGENERICALBUM` 经多对多表 `Z_<n>ASSETS` 关联（`<n>` 随版本漂移，运行期探测）
+//!   - Live Photo（`ZKINDSUBTYPE = 2`）视频部分位于 `originals/<dir>/<uuid>_3.mov`
+//!
+//! Photos 4 及更早的 `Masters/` 布局 schema 完全不同，不支持：打开即报 `Unsupported`。
+
+use std::collections::HashMap;
+use std::io;
+use std::sync::Arc;
+use std::sync::LazyLock;
+
+use camino::Utf8Path;
+use camino::Utf8PathBuf;
+use chrono::DateTime;
+use chrono::FixedOffset;
+use chrono::Utc;
+use parking_lot::Mutex;
+use tracing::debug;
+use tracing::warn;
+
+use crate::entities::backend::Backend;
+use crate::entities::media_time::Candidate;
+use crate::entities::media_time::Source;
+use crate::entities::tags::Tags;
+use crate::entities::uri::Location;
+
+const FEATURE_PHOTOS: &str = "photos_library";
+
+/// 图库包扩展名（Finder 中显示为单个文件的目录包）。
+pub const LIBRARY_EXTENSION: &str = "photoslibrary";
+
+/// 单个未进回收站的资产。路径均为绝对路径（`<root>/originals/...`）。
+#[derive(Clone, Debug, PartialEq)]
+pub struct PhotosAsset {
+    pub uuid: String,
+    pub original: Utf8PathBuf,
+    /// 导入前的原始文件名（Photos 把原图改名为 `<uuid>.<ext>` 存放）。
+    pub original_filename: Option<String>,
+    pub created: DateTime<Utc>,
+    pub offset: Option<FixedOffset>,
+    pub favorite: bool,
+    /// 所属用户相册标题，按标题排序去重。
+    pub albums: Vec<String>,
+    /// Live Photo 配对的视频部分；非 Live Photo 为 None。
+    pub live_photo_video: Option<Utf8PathBuf>,
+}
+
+impl PhotosAsset {
+    /// 转为 P0 时间候选：时区来自数据库原生字段，非推断。
+    #[must_use]
+    pub fn candidate(&self) -> Candidate {
+        Candidate {
+            utc: self.created,
+            offset: self.offset,
+            source: Source::PhotosLibrary,
+            inferred_offset: false,
+        }
+    }
+
+    /// 转为整理标记：星级 / 色标不由图库给出，留给 XMP。`Tags::album` 只容一个值，
+    /// 属多个相册时取标题排序第一个（`{album}` 据此归档）；完整列表见 [`Self::albums`]。
+    #[must_use]
+    pub fn tags(&self) -> Tags {
+        Tags {
+            favorite: self.favorite,
+            album: self.albums.first().cloned(),
+            original_name: self
+                .original_filename
+                .as_deref()
+                .and_then(|n| Utf8Path::new(n).file_stem())
+                .filter(|stem| !stem.is_empty())
+                .map(str::to_string),
+            ..Tags::default()
+        }
+    }
+}
+
+/// 已解析的图库：资产列表 + 原图/Live Photo 视频路径 → 资产下标索引。
+#[derive(Debug)]
+pub struct PhotosLibrary {
+    root: Utf8PathBuf,
+    assets: Vec<PhotosAsset>,
+    by_path: HashMap<Utf8PathBuf, usize>,
+}
+
+impl PhotosLibrary {
+    /// 只读打开 `<root>/database/Photos.sqlite` 并一次性载入全部资产。
+    ///
+    /// # Errors
+    ///
+    /// 数据库不存在、无法打开、schema 不识别（Photos 4 及更早）或查询失败时返回 `Err`；
+    /// 未启用 `photos-library` feature 时返 `Unsupported`：图库源退化为普通目录。
+    pub fn open(root: &Utf8Path) -> io::Result<Self> {
+        let assets = sqlite::load(root)?;
+        Ok(Self::from_assets(root.to_path_buf(), assets))
+    }
+
+    fn from_assets(root: Utf8PathBuf, assets: Vec<PhotosAsset>) -> Self {
+        let mut by_path = HashMap::with_capacity(assets.len());
+        for (idx, a) in assets.iter().enumerate() {
+            by_path.insert(a.original.clone(), idx);
+            if let Some(v) = &a.live_photo_video {
+                by_path.insert(v.clone(), idx);
+            }
+        }
+        Self {
+            root,
+            assets,
+            by_path,
+        }
+    }
+
+    #[must_use]
+    pub fn root(&self) -> &Utf8Path {
+        &self.root
+    }
+
+    #[must_use]
+    pub fn assets(&self) -> &[PhotosAsset] {
+        &self.assets
+    }
+
+    /// 按绝对路径查资产；Live Photo 的 `.mov` 视频部分命中其静态图所属资产，
+    /// 让两者得到同一拍摄时间、归档进同一目录。
+    #[must_use]
+    pub fn lookup(&self, path: &Utf8Path) -> Option<&PhotosAsset> {
+        self.by_path.get(path).map(|&i| &self.assets[i])
+    }
+}
+
+/// `path` 自身或任一祖先以 `.photoslibrary` 结尾时返回该图库根。
+#[must_use]
+pub fn library_root(path: &Utf8Path) -> Option<&Utf8Path> {
+    path.ancestors()
+        .find(|p| p.extension() == Some(LIBRARY_EXTENSION))
+}
+
+/// 源目录恰为图库根时改写为 `<root>/originals`：包内 `resources/derivatives` 等缩略图
+/// 同为 JPEG/HEIC，不收窄会被当作独立媒体归档（与原图内容不同，去重也拦不住）。
+/// 非 Local / 非图库根原样返回。
+#[must_use]
+pub fn source_root(loc: Location) -> Location {
+    let Location::Local(p) = &loc else {
+        return loc;
+    };
+    if p.extension() != Some(LIBRARY_EXTENSION) {
+        return loc;
+    }
+    Location::Local(p.join("originals"))
+}
+
+/// 已打开图库缓存：copy 对每个 indexed 文件并行调用 provider，同一图库只解析一次。
+/// 打开失败也缓存（None），避免每个文件重复报同一条 warn。
+static LIBRARIES: LazyLock<Mutex<HashMap<Utf8PathBuf, Option<Arc<PhotosLibrary>>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+fn cached_library(root: &Utf8Path) -> Option<Arc<PhotosLibrary>> {
+    let mut guard = LIBRARIES.lock();
+    if let Some(hit) = guard.get(root) {
+        return hit.clone();
+    }
+    let opened = match PhotosLibrary::open(root) {
+        Ok(lib) => {
+            let assets = lib.assets().len();
+            debug!(
+                feature = FEATURE_PHOTOS,
+                operation = "open_library",
+                root = %root,
+                assets,
+                result = "ok",
+                "photos library loaded"
+            );
+            Some(Arc::new(lib))
+        }
+        Err(e) => {
+            let err = e.to_string();
+            warn!(
+                feature = FEATURE_PHOTOS,
+                operation = "open_library",
+                root = %root,
+                result = "error",
+                err,
+                "cannot read photos library database; falling back to file metadata"
+            );
+            None
+        }
+    };
+    guard.insert(root.to_path_buf(), opened.clone());
+    opened
+}
+
+/// [`crate::entities::file_index::CandidateProvider`] 入口：媒体位于本地图库包内时
+/// 注入数据库中的 P0 拍摄时间。SQLite 只能本地读，非 Local scheme 直接返空。
+pub fn discover_with_backend(media_loc: &Location, _backend: &Arc<dyn Backend>) -> Vec<Candidate> {
+    library_for(media_loc)
+        .and_then(|(lib, path)| lib.lookup(path).map(PhotosAsset::candidate))
+        .into_iter()
+        .collect()
+}
+
+/// [`crate::entities::file_index::TagProvider`] 入口：媒体位于本地图库包内时给出
+/// 收藏 / 相册 / 导入前原名；图库外或无任何标记返 None。
+pub fn discover_tags_with_backend(
+    media_loc: &Location,
+    _backend: &Arc<dyn Backend>,
+) -> Option<Tags> {
+    library_for(media_loc)
+        .and_then(|(lib, path)| lib.lookup(path).map(PhotosAsset::tags))
+        .filter(|t| !t.is_empty())
+}
+
+// SQLite 只能本地读：非 Local scheme、不在图库包内或图库打不开时返 None。
+fn library_for(media_loc: &Location) -> Option<(Arc<PhotosLibrary>, &Utf8Path)> {
+    let Location::Local(path) = media_loc else {
+        return None;
+    };
+    let lib = cached_library(library_root(path)?)?;
+    Some((lib, path))
+}
+
+#[cfg(feature = "photos-library")]
+#[path = "photos_library_sqlite.rs"]
+mod sqlite;
+
+#[cfg(not(feature = "photos-library"))]
+mod sqlite {
+    use std::io;
+
+    use camino::Utf8Path;
+
+    use super::PhotosAsset;
+
+    pub(super) fn load(root: &Utf8Path) -> io::Result<Vec<PhotosAsset>> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("{root}: reading Photos libraries requires `--features photos-library`"),
+        ))
+    }
+}
+
+#[cfg(all(test, feature = "photos-library"))]
+#[path = "photos_library_tests.rs"]
+mod tests;
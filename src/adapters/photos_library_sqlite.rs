@@ -0,0 +1,212 @@
+//! `PhotosLibrary::open` 的 SQLite 实现：只读打开 `database/Photos.sqlite` 并载入资产。
+//!
+//! 仅在 `photos-library` feature（默认启用）下编译；rusqlite bundled 静态链接 SQLite。
+
+use std::collections::HashMap;
+use std::io;
+
+use camino::Utf8Path;
+use chrono::DateTime;
+use chrono::FixedOffset;
+use chrono::TimeDelta;
+use chrono::Utc;
+use rusqlite::Connection;
+use rusqlite::OpenFlags;
+
+use super::PhotosAsset;
+
+/// Core Data 时间戳零点 2001-01-01T00:00:00Z 的 Unix 秒。
+pub(super) const CORE_DATA_EPOCH: i64 = 978_307_200;
+
+/// `ZKINDSUBTYPE` 取值：Live Photo。
+const KIND_SUBTYPE_LIVE_PHOTO: i64 = 2;
+
+/// 只读打开 `<root>/database/Photos.sqlite`，载入未进回收站的全部资产。
+///
+/// # Errors
+///
+/// 数据库不存在、无法打开、查询失败时返回 `Err`；无资产表（Photos 4 及更早）返 `Unsupported`。
+pub(super) fn load(root: &Utf8Path) -> io::Result<Vec<PhotosAsset>> {
+    let db = root.join("database").join("Photos.sqlite");
+    let conn = Connection::open_with_flags(
+        db.as_std_path(),
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .map_err(|e| sql_err("open", &db, &e))?;
+    load_assets(&conn, root)
+        .map_err(|e| sql_err("query", &db, &e))?
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("{db}: no ZASSET/ZGENERICASSET table (Photos 4 or older library)"),
+            )
+        })
+}
+
+fn sql_err(operation: &str, db: &Utf8Path, e: &rusqlite::Error) -> io::Error {
+    io::Error::other(format!("{operation} photos database {db}: {e}"))
+}
+
+fn table_exists(conn: &Connection, name: &str) -> rusqlite::Result<bool> {
+    let n: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?1",
+        [name],
+        |row| row.get(0),
+    )?;
+    Ok(n > 0)
+}
+
+struct AssetRow {
+    pk: i64,
+    uuid: String,
+    directory: String,
+    filename: String,
+    created: f64,
+    favorite: bool,
+    kind_subtype: i64,
+    tz_offset: Option<i64>,
+    original_filename: Option<String>,
+}
+
+// 外层 Option：schema 不识别（无资产表）；内层 Vec 为已过滤回收站的资产。
+fn load_assets(conn: &Connection, root: &Utf8Path) -> rusqlite::Result<Option<Vec<PhotosAsset>>> {
+    let asset_table = if table_exists(conn, "ZASSET")? {
+        "ZASSET"
+    } else if table_exists(conn, "ZGENERICASSET")? {
+        "ZGENERICASSET"
+    } else {
+        return Ok(None);
+    };
+    let sql = format!(
+        "SELECT a.Z_PK, a.ZUUID, a.ZDIRECTORY, a.ZFILENAME, a.ZDATECREATED, a.ZFAVORITE, \
+                a.ZKINDSUBTYPE, attr.ZTIMEZONEOFFSET, attr.ZORIGINALFILENAME \
+         FROM {asset_table} a \
+         LEFT JOIN ZADDITIONALASSETATTRIBUTES attr ON attr.ZASSET = a.Z_PK \
+         WHERE a.ZTRASHEDSTATE = 0 \
+           AND a.ZUUID IS NOT NULL AND a.ZDIRECTORY IS NOT NULL \
+           AND a.ZFILENAME IS NOT NULL AND a.ZDATECREATED IS NOT NULL"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(AssetRow {
+                pk: row.get(0)?,
+                uuid: row.get(1)?,
+                directory: row.get(2)?,
+                filename: row.get(3)?,
+                created: row.get(4)?,
+                favorite: row.get::<_, Option<i64>>(5)?.unwrap_or(0) != 0,
+                kind_subtype: row.get::<_, Option<i64>>(6)?.unwrap_or(0),
+                tz_offset: row.get(7)?,
+                original_filename: row.get(8)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    let mut albums = load_albums(conn)?;
+
+    let originals = root.join("originals");
+    let mut out = Vec::with_capacity(rows.len());
+    for r in rows {
+        let Some(created) = core_data_to_utc(r.created) else {
+            continue;
+        };
+        let dir = originals.join(&r.directory);
+        let live_photo_video = (r.kind_subtype == KIND_SUBTYPE_LIVE_PHOTO)
+            .then(|| dir.join(format!("{}_3.mov", r.uuid)));
+        let mut asset_albums = albums.remove(&r.pk).unwrap_or_default();
+        asset_albums.sort();
+        asset_albums.dedup();
+        out.push(PhotosAsset {
+            original: dir.join(&r.filename),
+            uuid: r.uuid,
+            original_filename: r.original_filename,
+            created,
+            offset: r
+                .tz_offset
+                .and_then(|s| i32::try_from(s).ok())
+                .and_then(FixedOffset::east_opt),
+            favorite: r.favorite,
+            albums: asset_albums,
+            live_photo_video,
+        });
+    }
+    Ok(Some(out))
+}
+
+/// 资产主键 → 用户相册标题。多对多表名 `Z_<n>ASSETS` 与列名 `Z_<n>ALBUMS` /
+/// `Z_<m>ASSETS` 的数字随 Photos 版本漂移，按 `sqlite_master` + `table_info` 探测；
+/// 探测不到视为无相册（不影响时间候选）。
+fn load_albums(conn: &Connection) -> rusqlite::Result<HashMap<i64, Vec<String>>> {
+    let mut out: HashMap<i64, Vec<String>> = HashMap::new();
+    if !table_exists(conn, "ZGENERICALBUM")? {
+        return Ok(out);
+    }
+    let Some((table, album_col, asset_col)) = find_album_join(conn)? else {
+        return Ok(out);
+    };
+    let sql = format!(
+        "SELECT j.{asset_col}, g.ZTITLE FROM {table} j \
+         JOIN ZGENERICALBUM g ON g.Z_PK = j.{album_col} \
+         WHERE g.ZTITLE IS NOT NULL AND g.ZTRASHEDSTATE = 0"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+    })?;
+    for r in rows {
+        let (asset, title) = r?;
+        out.entry(asset).or_default().push(title);
+    }
+    Ok(out)
+}
+
+fn find_album_join(conn: &Connection) -> rusqlite::Result<Option<(String, String, String)>> {
+    let mut stmt = conn.prepare(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name GLOB 'Z_[0-9]*ASSETS'",
+    )?;
+    let tables = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    for table in tables {
+        // 标识符来自数据库自身，拼进 SQL 前仍限制字符集，防止畸形库注入语句。
+        if !is_plain_identifier(&table) {
+            continue;
+        }
+        let mut info = conn.prepare(&format!("PRAGMA table_info({table})"))?;
+        let cols = info
+            .query_map([], |row| row.get::<_, String>(1))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        let album = cols.iter().find(|c| c.ends_with("ALBUMS"));
+        // `Z_FOK_<m>ASSETS` 是相册内排序键，同以 ASSETS 结尾，需排除。
+        let asset = cols
+            .iter()
+            .find(|c| c.ends_with("ASSETS") && !c.starts_with("Z_FOK_"));
+        if let (Some(album), Some(asset)) = (album, asset)
+            && is_plain_identifier(album)
+            && is_plain_identifier(asset)
+        {
+            return Ok(Some((table, album.clone(), asset.clone())));
+        }
+    }
+    Ok(None)
+}
+
+fn is_plain_identifier(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_')
+}
+
+/// Core Data 秒（`REAL`，可为负，即 2001 年前）→ UTC；NaN/越界返 None。
+pub(super) fn core_data_to_utc(secs: f64) -> Option<DateTime<Utc>> {
+    if !secs.is_finite() {
+        return None;
+    }
+    // 亚秒在归档粒度上无意义，截断到整秒；f64 → i64 的饱和转换由下方 checked 运算兜底。
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "is_finite 已排除 NaN/Inf；超界值饱和后由 checked_add 拒绝"
+    )]
+    let whole = secs.trunc() as i64;
+    let unix = whole.checked_add(CORE_DATA_EPOCH)?;
+    let delta = TimeDelta::try_seconds(unix)?;
+    DateTime::<Utc>::UNIX_EPOCH.checked_add_signed(delta)
+}
@@ -0,0 +1,260 @@
+use camino::Utf8PathBuf;
+use rusqlite::params;
+use tempfile::TempDir;
+
+use super::sqlite::{CORE_DATA_EPOCH, core_data_to_utc};
+use super::*;
+use crate::adapters::backend::local::LocalBackend;
+use crate::entities::media_time::Priority;
+
+/// 2024-05-01T06:30:00Z 的 Core Data 秒（Unix 1_714_545_000 - 978_307_200）。
+const CREATED_CORE_DATA: f64 = 736_237_800.0;
+const CREATED_UNIX: i64 = 1_714_545_000;
+
+struct Fixture {
+    _dir: TempDir,
+    root: Utf8PathBuf,
+}
+
+/// 合成最小 Photos 5+ 图库：3 个资产（普通收藏 / Live Photo / 已删除）+ 2 个相册。
+fn fixture(asset_table: &str, with_albums: bool) -> Fixture {
+    let dir = tempfile::tempdir().unwrap();
+    let base = Utf8PathBuf::from_path_buf(dir.path().to_path_buf()).unwrap();
+    let root = base.join("Family.photoslibrary");
+    std::fs::create_dir_all(root.join("database")).unwrap();
+    std::fs::create_dir_all(root.join("originals/A")).unwrap();
+    std::fs::create_dir_all(root.join("originals/B")).unwrap();
+    let conn = Connection::open(root.join("database/Photos.sqlite").as_std_path()).unwrap();
+    conn.execute_batch(&format!(
+        "CREATE TABLE {asset_table} (Z_PK INTEGER PRIMARY KEY, ZUUID TEXT, ZDIRECTORY TEXT, \
+             ZFILENAME TEXT, ZDATECREATED REAL, ZFAVORITE INTEGER, ZKINDSUBTYPE INTEGER, \
+             ZTRASHEDSTATE INTEGER);
+         CREATE TABLE ZADDITIONALASSETATTRIBUTES (Z_PK INTEGER PRIMARY KEY, ZASSET INTEGER, \
+             ZTIMEZONEOFFSET INTEGER, ZORIGINALFILENAME TEXT);"
+    ))
+    .unwrap();
+    let insert = format!(
+        "INSERT INTO {asset_table} (Z_PK, ZUUID, ZDIRECTORY, ZFILENAME, ZDATECREATED, ZFAVORITE, \
+             ZKINDSUBTYPE, ZTRASHEDSTATE) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)"
+    );
+    conn.execute(
+        &insert,
+        params![1, "AAAA", "A", "AAAA.jpeg", CREATED_CORE_DATA, 1, 0, 0],
+    )
+    .unwrap();
+    conn.execute(
+        &insert,
+        params![
+            2,
+            "BBBB",
+            "B",
+            "BBBB.heic",
+            CREATED_CORE_DATA + 60.0,
+            0,
+            2,
+            0
+        ],
+    )
+    .unwrap();
+    conn.execute(
+        &insert,
+        params![3, "CCCC", "A", "CCCC.jpeg", CREATED_CORE_DATA, 0, 0, 1],
+    )
+    .unwrap();
+    conn.execute(
+        "INSERT INTO ZADDITIONALASSETATTRIBUTES (ZASSET, ZTIMEZONEOFFSET, ZORIGINALFILENAME) \
+         VALUES (1, 28800, 'IMG_0001.JPG')",
+        [],
+    )
+    .unwrap();
+    if with_albums {
+        conn.execute_batch(
+            "CREATE TABLE ZGENERICALBUM (Z_PK INTEGER PRIMARY KEY, ZTITLE TEXT, \
+                 ZTRASHEDSTATE INTEGER);
+             CREATE TABLE Z_28ASSETS (Z_28ALBUMS INTEGER, Z_3ASSETS INTEGER, \
+                 Z_FOK_3ASSETS INTEGER);
+             INSERT INTO ZGENERICALBUM VALUES (10, 'Trip', 0), (11, 'Birthday', 0), \
+                 (12, 'Old', 1), (13, NULL, 0);
+             INSERT INTO Z_28ASSETS VALUES (10, 1, 100), (11, 1, 200), (12, 1, 300), \
+                 (13, 1, 400), (10, 2, 500);",
+        )
+        .unwrap();
+    }
+    Fixture { _dir: dir, root }
+}
+
+#[test]
+fn open_loads_time_offset_favourite_and_albums() {
+    let fx = fixture("ZASSET", true);
+    let lib = PhotosLibrary::open(&fx.root).unwrap();
+    assert_eq!(lib.root(), fx.root.as_path());
+    // 回收站中的 CCCC 被过滤。
+    assert_eq!(lib.assets().len(), 2);
+
+    let a = lib.lookup(&fx.root.join("originals/A/AAAA.jpeg")).unwrap();
+    assert_eq!(a.uuid, "AAAA");
+    assert_eq!(a.created.timestamp(), CREATED_UNIX);
+    assert_eq!(a.offset, FixedOffset::east_opt(8 * 3600));
+    assert_eq!(a.original_filename.as_deref(), Some("IMG_0001.JPG"));
+    assert!(a.favorite);
+    // 已删除相册与无标题相册不计入，标题排序。
+    assert_eq!(a.albums, vec!["Birthday".to_string(), "Trip".to_string()]);
+    assert!(a.live_photo_video.is_none());
+}
+
+#[test]
+fn live_photo_video_resolves_to_still_asset() {
+    let fx = fixture("ZASSET", true);
+    let lib = PhotosLibrary::open(&fx.root).unwrap();
+    let still = lib.lookup(&fx.root.join("originals/B/BBBB.heic")).unwrap();
+    let video = lib.lookup(&fx.root.join("originals/B/BBBB_3.mov")).unwrap();
+    assert_eq!(still, video);
+    assert_eq!(
+        still.live_photo_video.as_deref(),
+        Some(fx.root.join("originals/B/BBBB_3.mov").as_path())
+    );
+    assert_eq!(still.created.timestamp(), CREATED_UNIX + 60);
+    // 无 ZADDITIONALASSETATTRIBUTES 行 → 无时区、无原始文件名。
+    assert!(still.offset.is_none());
+    assert!(still.original_filename.is_none());
+    assert_eq!(still.albums, vec!["Trip".to_string()]);
+}
+
+#[test]
+fn trashed_asset_not_indexed() {
+    let fx = fixture("ZASSET", true);
+    let lib = PhotosLibrary::open(&fx.root).unwrap();
+    assert!(lib.lookup(&fx.root.join("originals/A/CCCC.jpeg")).is_none());
+}
+
+/// Catalina（Photos 5）资产表名为 `ZGENERICASSET`；无相册表时相册为空。
+#[test]
+fn open_falls_back_to_generic_asset_table_without_albums() {
+    let fx = fixture("ZGENERICASSET", false);
+    let lib = PhotosLibrary::open(&fx.root).unwrap();
+    let a = lib.lookup(&fx.root.join("originals/A/AAAA.jpeg")).unwrap();
+    assert!(a.albums.is_empty());
+    assert_eq!(a.created.timestamp(), CREATED_UNIX);
+}
+
+#[test]
+fn open_missing_database_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = Utf8PathBuf::from_path_buf(dir.path().join("Empty.photoslibrary")).unwrap();
+    std::fs::create_dir_all(&root).unwrap();
+    assert!(PhotosLibrary::open(&root).is_err());
+}
+
+#[test]
+fn open_unknown_schema_is_unsupported() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = Utf8PathBuf::from_path_buf(dir.path().join("Old.photoslibrary")).unwrap();
+    std::fs::create_dir_all(root.join("database")).unwrap();
+    let conn = Connection::open(root.join("database/Photos.sqlite").as_std_path()).unwrap();
+    conn.execute_batch("CREATE TABLE RKMaster (modelId INTEGER PRIMARY KEY);")
+        .unwrap();
+    drop(conn);
+    let e = PhotosLibrary::open(&root).unwrap_err();
+    assert_eq!(e.kind(), io::ErrorKind::Unsupported);
+}
+
+#[test]
+fn discover_injects_p0_candidate_for_library_original() {
+    let fx = fixture("ZASSET", true);
+    let backend = LocalBackend::arc();
+    let loc = Location::Local(fx.root.join("originals/A/AAAA.jpeg"));
+    let cands = discover_with_backend(&loc, &backend);
+    assert_eq!(cands.len(), 1);
+    assert_eq!(cands[0].source, Source::PhotosLibrary);
+    assert_eq!(cands[0].priority(), Priority::P0);
+    assert_eq!(cands[0].utc.timestamp(), CREATED_UNIX);
+    assert!(!cands[0].inferred_offset);
+}
+
+/// 收藏 / 首个相册 / 导入前原名（去扩展名）进标记；Live Photo 视频随静态图取同一组。
+#[test]
+fn discover_tags_reports_favourite_album_and_original_name() {
+    let fx = fixture("ZASSET", true);
+    let backend = LocalBackend::arc();
+    let a = Location::Local(fx.root.join("originals/A/AAAA.jpeg"));
+    let tags = discover_tags_with_backend(&a, &backend).unwrap();
+    assert!(tags.favorite);
+    assert_eq!(tags.album.as_deref(), Some("Birthday"));
+    assert_eq!(tags.original_name.as_deref(), Some("IMG_0001"));
+    assert!(tags.rating.is_none() && tags.label.is_none());
+
+    let video = Location::Local(fx.root.join("originals/B/BBBB_3.mov"));
+    let tags = discover_tags_with_backend(&video, &backend).unwrap();
+    assert!(!tags.favorite);
+    assert_eq!(tags.album.as_deref(), Some("Trip"));
+    assert!(tags.original_name.is_none());
+}
+
+/// 无收藏、无相册、无原名的资产与图库外文件一样返 None。
+#[test]
+fn discover_tags_without_marks_returns_none() {
+    let fx = fixture("ZGENERICASSET", false);
+    let backend = LocalBackend::arc();
+    let still = Location::Local(fx.root.join("originals/B/BBBB.heic"));
+    assert!(discover_tags_with_backend(&still, &backend).is_none());
+    let outside = Location::Local(Utf8PathBuf::from("/photos/2024/a.jpg"));
+    assert!(discover_tags_with_backend(&outside, &backend).is_none());
+}
+
+#[test]
+fn discover_unknown_file_in_library_returns_empty() {
+    let fx = fixture("ZASSET", true);
+    let backend = LocalBackend::arc();
+    let loc = Location::Local(fx.root.join("originals/A/ZZZZ.jpeg"));
+    assert!(discover_with_backend(&loc, &backend).is_empty());
+}
+
+#[test]
+fn discover_outside_library_returns_empty() {
+    let backend = LocalBackend::arc();
+    let loc = Location::Local(Utf8PathBuf::from("/photos/2024/a.jpg"));
+    assert!(discover_with_backend(&loc, &backend).is_empty());
+}
+
+/// 数据库缺失：warn 一次并缓存 None，后续文件同样返空。
+#[test]
+fn discover_broken_library_returns_empty_twice() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = Utf8PathBuf::from_path_buf(dir.path().join("Broken.photoslibrary")).unwrap();
+    let backend = LocalBackend::arc();
+    let loc = Location::Local(root.join("originals/A/x.jpg"));
+    assert!(discover_with_backend(&loc, &backend).is_empty());
+    assert!(discover_with_backend(&loc, &backend).is_empty());
+}
+
+#[test]
+fn library_root_finds_enclosing_package() {
+    let p = Utf8Path::new("/Users/me/Pictures/Family.photoslibrary/originals/A/x.jpeg");
+    assert_eq!(
+        library_root(p),
+        Some(Utf8Path::new("/Users/me/Pictures/Family.photoslibrary"))
+    );
+    assert!(library_root(Utf8Path::new("/Users/me/Pictures/x.jpeg")).is_none());
+}
+
+#[test]
+fn source_root_narrows_library_to_originals() {
+    let lib = Location::Local(Utf8PathBuf::from("/p/Family.photoslibrary"));
+    assert_eq!(
+        source_root(lib),
+        Location::Local(Utf8PathBuf::from("/p/Family.photoslibrary/originals"))
+    );
+    let plain = Location::Local(Utf8PathBuf::from("/p/photos"));
+    assert_eq!(source_root(plain.clone()), plain);
+}
+
+#[test]
+fn core_data_to_utc_handles_pre_2001_and_rejects_nan() {
+    assert_eq!(core_data_to_utc(0.0).unwrap().timestamp(), CORE_DATA_EPOCH);
+    assert_eq!(
+        core_data_to_utc(-86_400.5).unwrap().timestamp(),
+        CORE_DATA_EPOCH - 86_400
+    );
+    assert!(core_data_to_utc(f64::NAN).is_none());
+    assert!(core_data_to_utc(f64::INFINITY).is_none());
+}
@@ -11,6 +11,10 @@ const FEATURE_MOVE: &str = "move";
 const FEATURE_FIND: &str = "find";
 const FEATURE_MOVE_TEXT_SHOT: &str = "move_text_shot";
 const FEATURE_CULL: &str = "cull";
+const FEATURE_RENAME: &str = "rename";
+const FEATURE_STATS: &str = "stats";
+const FEATURE_AUDIT: &str = "audit";
+const FEATURE_FACES: &str = "faces";
 
 /// 把报告原子写到 `path`（先写临时文件再 persist）。
 /// 写盘失败仅 warn，不阻断主流程。
@@ -39,6 +43,10 @@ impl ReportSink for JsonFileReportSink {
             Report::Find(r) => write_report_json(&self.path, *r, FEATURE_FIND),
             Report::MoveTextShot(r) => write_report_json(&self.path, *r, FEATURE_MOVE_TEXT_SHOT),
             Report::Cull(r) => write_report_json(&self.path, *r, FEATURE_CULL),
+            Report::Rename(r) => write_report_json(&self.path, *r, FEATURE_RENAME),
+            Report::Stats(r) => write_report_json(&self.path, *r, FEATURE_STATS),
+            Report::Audit(r) => write_report_json(&self.path, *r, FEATURE_AUDIT),
+            Report::Faces(r) => write_report_json(&self.path, *r, FEATURE_FACES),
         }
     }
 }
@@ -48,7 +56,7 @@ fn write_report_json<T: serde::Serialize>(path: &str, report: &T, feature: &str)
     // 序列化不可能失败。
     let json = serde_json::to_string_pretty(report)
         .expect("internal error: serializing report must not fail");
-    match try_write_report_json(path, json.as_bytes()) {
+    match write_atomic(path, json.as_bytes()) {
         Ok(()) => {}
         Err(e) => {
             warn!(
@@ -63,7 +71,7 @@ fn write_report_json<T: serde::Serialize>(path: &str, report: &T, feature: &str)
     }
 }
 
-// 临时文件 + 原子 persist 的 object-safe 抽象：让 try_write_report_json 不再硬编码
+// 临时文件 + 原子 persist 的 object-safe 抽象：让 write_atomic 不再硬编码
 // NamedTempFile，单测可注入 mock 实现触发 write/flush/persist 各自的 Err arm（real
 // NamedTempFile 写盘失败在测试环境不可稳定触发）。`self: Box<Self>` 让 trait 对象
 // 持有 sole ownership 并消耗 self（NamedTempFile::persist 签名要求）。
@@ -86,8 +94,8 @@ impl TempPersist for tempfile::NamedTempFile {
 }
 
 // 非泛型：所有调用方共享一份 instance，避免 generic monomorphization 让 llvm-cov
-// 每份独立计 region 出现虚报。
-fn try_write_report_json(path: &str, bytes: &[u8]) -> common::Result<()> {
+// 每份独立计 region 出现虚报。catalog 持久化同走此路径。
+pub(crate) fn write_atomic(path: &str, bytes: &[u8]) -> common::Result<()> {
     let parent = std::path::Path::new(path)
         .parent()
         .unwrap_or(std::path::Path::new("."));
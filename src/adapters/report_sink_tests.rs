@@ -66,8 +66,11 @@ fn sample_find_report() -> FindReport {
         groups: vec![crate::usecases::report::DuplicateGroupReport {
             size: 1024,
             paths: vec!["a.jpg".into(), "a_copy.jpg".into()],
+            survivor: "a.jpg".into(),
+            survivor_reason: "path_order".into(),
         }],
         bytes_read: 1024,
+        ..FindReport::default()
     }
 }
 
@@ -1,6 +1,6 @@
 //! Sidecar Gateway：识别 P3 旁路文件并把它们转成 [`Candidate`]（entities 时间候选）。
 //! 仅识别两种常见格式，避免引入 XML 库：
-//!   - `<media>.xmp` 中的 `photoshop:DateCreated="<RFC3339>"`（纯文本搜索）
+//!   - `<media>.xmp` 中的 `photoshop:DateCreated` / `exif:DateTimeOriginal`（`entities::xmp`）
 //!   - Google Takeout `<media>.<ext>.json` 中的 `photoTakenTime.timestamp`（`serde_json`）
 //!
 //! `docs/media-time-detection.md` §二.P3。本模块属 Interface Adapters：把外部
@@ -22,6 +22,7 @@ use crate::adapters::backend::local::LocalBackend;
 use crate::entities::backend::Backend;
 use crate::entities::media_time::Candidate;
 use crate::entities::media_time::Source;
+use crate::entities::tags::Tags;
 use crate::entities::uri::Location;
 use crate::entities::xmp;
 
@@ -48,6 +49,51 @@ pub fn discover_with_backend(media_loc: &Location, backend: &Arc<dyn Backend>) -
     out
 }
 
+/// 读 `<media>.xmp` 的星级 / 色标（`TagProvider` 注入点）；无 sidecar 或两项皆缺返 None。
+/// 与 [`discover_with_backend`] 分开：仅在 `--min-rating` / `--label` 或模板用到时才调用。
+pub fn discover_tags_with_backend(
+    media_loc: &Location,
+    backend: &Arc<dyn Backend>,
+) -> Option<Tags> {
+    let xmp_loc = with_extension(media_loc, "xmp");
+    let content = read_sidecar(&xmp_loc, backend.as_ref(), "read_xmp_tags")?;
+    Some(Tags::from_xmp(&content)).filter(|t| !t.is_empty())
+}
+
+/// 列出实际存在的伴随 sidecar（`CompanionProvider` 注入点）：`<stem>.xmp`、
+/// darktable 风格 `<name>.xmp` 与 Takeout `<name>.json`。`exists` 出错按不存在处理
+/// 并记 debug——伴随文件搬运是 best-effort，不应阻断媒体本身。
+pub fn companions_with_backend(media_loc: &Location, backend: &Arc<dyn Backend>) -> Vec<Location> {
+    let mut candidates = vec![
+        with_extension(media_loc, "xmp"),
+        append_suffix(media_loc, ".xmp"),
+        append_suffix(media_loc, ".json"),
+    ];
+    // 无扩展名媒体时前两者同为 `<name>.xmp`，去重避免重复搬运；源本身就是
+    // `.xmp`（--include-non-media）时 `<stem>.xmp` 即自身，剔除。
+    candidates.dedup();
+    candidates
+        .into_iter()
+        .filter(|loc| loc != media_loc)
+        .filter(|loc| match backend.exists(loc) {
+            Ok(found) => found,
+            Err(e) => {
+                let path = loc.display();
+                let err = e.to_string();
+                debug!(
+                    feature = FEATURE_SIDECAR,
+                    operation = "probe_companion",
+                    path,
+                    result = "error",
+                    err,
+                    "cannot probe sidecar companion"
+                );
+                false
+            }
+        })
+        .collect()
+}
+
 fn try_xmp(media_loc: &Location, backend: &dyn Backend) -> Option<Candidate> {
     let xmp_loc = with_extension(media_loc, "xmp");
     let content = read_sidecar(&xmp_loc, backend, "read_xmp")?;
@@ -134,10 +180,13 @@ fn log_parse_failure(operation: &'static str, loc: &Location) {
 }
 
 pub(crate) fn parse_xmp_date(content: &str) -> Option<DateTime<Utc>> {
-    // P3 sidecar 沿用历史语义只取 photoshop:DateCreated。packet 嗅探 + 多键解析
-    // 复用 entities::xmp 同一实现（避免 sidecar / EXIF fallback 两份 XML 解析漂移）。
-    xmp::parse_xmp_dates(content)
-        .photoshop_date_created
+    // P3 sidecar 取拍摄时刻：photoshop:DateCreated（Adobe 系）优先，其次
+    // exif:DateTimeOriginal（darktable / digiKam 只写这一键，常为 element 形式）。
+    // 解析复用 entities::xmp 同一实现（避免 sidecar / EXIF fallback 两份 XML 解析漂移）；
+    // 无时区值无从换算 UTC，丢弃。
+    let meta = xmp::parse_xmp(content, None);
+    meta.photoshop_date_created
+        .or(meta.exif_date_time_original)
         .map(|dt| dt.with_timezone(&Utc))
 }
 
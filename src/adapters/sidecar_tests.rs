@@ -10,6 +10,22 @@ fn parse_xmp_date_ok() {
     assert_eq!(utc.timestamp(), 1_714_545_000);
 }
 
+/// darktable / digiKam sidecar 只写 element 形式 `exif:DateTimeOriginal`；
+/// 同时存在时 `photoshop:DateCreated` 优先。
+#[test]
+fn parse_xmp_date_element_form_exif_date_time_original() {
+    let xml = "<rdf:Description><exif:DateTimeOriginal>2024-05-01T14:30:00+08:00\
+               </exif:DateTimeOriginal></rdf:Description>";
+    assert_eq!(parse_xmp_date(xml).unwrap().timestamp(), 1_714_545_000);
+    let both = r#"<rdf:Description photoshop:DateCreated="2020-01-01T00:00:00Z">
+        <exif:DateTimeOriginal>2024-05-01T14:30:00+08:00</exif:DateTimeOriginal>
+        </rdf:Description>"#;
+    assert_eq!(parse_xmp_date(both).unwrap().timestamp(), 1_577_836_800);
+    // 无时区值无从换算 UTC → 丢弃。
+    let naive = "<exif:DateTimeOriginal>2024-05-01T14:30:00</exif:DateTimeOriginal>";
+    assert!(parse_xmp_date(naive).is_none());
+}
+
 #[test]
 fn parse_xmp_date_missing_key_none() {
     assert!(parse_xmp_date("no key here").is_none());
@@ -256,3 +272,51 @@ fn discover_with_fake_backend_finds_xmp() {
     assert_eq!(cands.len(), 1);
     assert_eq!(cands[0].source, Source::XmpSidecar);
 }
+
+#[test]
+fn discover_tags_reads_rating_and_label_from_xmp_sidecar() {
+    use crate::adapters::backend::fake::FakeBackend;
+    let fake = std::sync::Arc::new(FakeBackend::new("local"));
+    let media = Location::Local(Utf8PathBuf::from("/in-mem/x.cr3"));
+    let xmp = Location::Local(Utf8PathBuf::from("/in-mem/x.xmp"));
+    fake.add_file(
+        xmp,
+        br#"<rdf:Description xmp:Rating="3" xmp:Label="Green"/>"#.to_vec(),
+    );
+    let backend: std::sync::Arc<dyn Backend> = fake;
+    let tags = discover_tags_with_backend(&media, &backend).unwrap();
+    assert_eq!(tags.rating, Some(3));
+    assert_eq!(tags.label.as_deref(), Some("Green"));
+}
+
+/// 无 sidecar / sidecar 无星级色标 → None（不覆盖内嵌 XMP）。
+#[test]
+fn discover_tags_missing_or_untagged_sidecar_returns_none() {
+    use crate::adapters::backend::fake::FakeBackend;
+    let fake = std::sync::Arc::new(FakeBackend::new("local"));
+    let tagged = Location::Local(Utf8PathBuf::from("/in-mem/a.jpg"));
+    let untagged = Location::Local(Utf8PathBuf::from("/in-mem/b.jpg"));
+    fake.add_file(
+        Location::Local(Utf8PathBuf::from("/in-mem/b.xmp")),
+        br#"photoshop:DateCreated="2024-05-01T14:30:00+00:00""#.to_vec(),
+    );
+    let backend: std::sync::Arc<dyn Backend> = fake;
+    assert!(discover_tags_with_backend(&tagged, &backend).is_none());
+    assert!(discover_tags_with_backend(&untagged, &backend).is_none());
+}
+
+/// 伴随文件：只返回实际存在的 `<stem>.xmp` / `<name>.xmp` / `<name>.json`。
+#[test]
+fn companions_lists_existing_sidecars_only() {
+    use crate::adapters::backend::fake::FakeBackend;
+    let fake = std::sync::Arc::new(FakeBackend::new("local"));
+    let media = Location::Local(Utf8PathBuf::from("/in-mem/x.cr3"));
+    let xmp = Location::Local(Utf8PathBuf::from("/in-mem/x.xmp"));
+    let json = Location::Local(Utf8PathBuf::from("/in-mem/x.cr3.json"));
+    fake.add_file(xmp.clone(), b"<x/>".to_vec());
+    fake.add_file(json.clone(), b"{}".to_vec());
+    let backend: std::sync::Arc<dyn Backend> = fake;
+    assert_eq!(companions_with_backend(&media, &backend), vec![xmp, json]);
+    let lonely = Location::Local(Utf8PathBuf::from("/in-mem/y.jpg"));
+    assert!(companions_with_backend(&lonely, &backend).is_empty());
+}
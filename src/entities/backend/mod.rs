@@ -117,6 +117,53 @@ pub trait Backend: Send + Sync {
     /// 当 scheme 不匹配、源不存在、父目录创建失败或底层复制失败时返回 `Err`。
     fn copy_file(&self, src: &Location, dst: &Location, mkparents: bool) -> io::Result<u64>;
 
+    /// 在 `link` 处新建指向 `original` 的硬链接（`link` 不得已存在）。仅本地文件系统
+    /// 支持；default 实现返回 `Unsupported`。
+    ///
+    /// # Errors
+    ///
+    /// 后端不支持、`link` 已存在、跨文件系统或底层调用失败时返回 `Err`。
+    fn hard_link(&self, original: &Location, link: &Location) -> io::Result<()> {
+        Err(unsupported_link("hard link", original, link))
+    }
+
+    /// 在 `link` 处新建 `original` 的 reflink（写时复制克隆，`link` 不得已存在）。
+    /// 需 Btrfs / XFS / APFS / `ReFS` 等支持克隆的本地文件系统；default 实现返回
+    /// `Unsupported`。
+    ///
+    /// # Errors
+    ///
+    /// 后端或文件系统不支持、`link` 已存在或底层调用失败时返回 `Err`。
+    fn reflink(&self, original: &Location, link: &Location) -> io::Result<()> {
+        Err(unsupported_link("reflink", original, link))
+    }
+
+    /// `a` 与 `b` 是否为同一底层文件（同一 inode 的两个硬链接或同一路径）。仅本地
+    /// 文件系统能判定；default 实现返回 `false`，按不同文件处理。
+    ///
+    /// # Errors
+    ///
+    /// 任一位置不存在或读取元数据失败时返回 `Err`。
+    fn same_file(&self, _a: &Location, _b: &Location) -> io::Result<bool> {
+        Ok(false)
+    }
+
+    /// 删除指定位置的空目录；目录非空时返回 `Err` 且不删任何内容。仅本地文件系统
+    /// 支持；default 实现返回 `Unsupported`（调用方按 best-effort 对待，留下空目录）。
+    ///
+    /// # Errors
+    ///
+    /// 后端不支持、目录不存在、目录非空或底层调用失败时返回 `Err`。
+    fn remove_dir(&self, loc: &Location) -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!(
+                "removing directories is only supported on local storage: {}",
+                loc.display()
+            ),
+        ))
+    }
+
     /// 在同一 backend 内原子重命名/移动文件；`mkparents` 为 `true` 时自动创建目标父目录。
     ///
     /// Local 实现用 `std::fs::rename`（同一文件系统时原子，跨设备 fallback 到 copy + remove）。
@@ -143,3 +190,14 @@ pub trait Backend: Send + Sync {
         })
     }
 }
+
+fn unsupported_link(what: &str, original: &Location, link: &Location) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!(
+            "{what} is only supported on local storage: {} -> {}",
+            link.display(),
+            original.display()
+        ),
+    )
+}
@@ -60,6 +60,18 @@ fn accessors_return_zero_for_missing_fields() {
     assert_eq!(exif.mime_type(), "");
 }
 
+#[test]
+fn metadata_fields_counts_present_fields_only() {
+    assert_eq!(mk_exif("image/jpeg", |_| {}).metadata_fields(), 0);
+    let rich = mk_exif("image/jpeg", |e| {
+        e.date_time_original = 1_700_000_000;
+        e.make = Some("Canon".into());
+        e.rating = Some(0);
+        e.keywords = vec!["trip".into()];
+    });
+    assert_eq!(rich.metadata_fields(), 4);
+}
+
 #[test]
 fn entry_value_to_epoch_datetime_aware() {
     // chrono::DateTime<FixedOffset> via parse: 2024-01-01 12:00:00+00:00 = 1704110400
@@ -96,3 +96,23 @@ fn build_gps_utc_zero_denominator_rational_returns_none() {
     // s 分母为 0（h、m 先成功，s 失败）
     assert!(super::build_gps_utc(Some("2023:06:15"), Some([ok, ok, zero])).is_none());
 }
+
+/// `build_gps_position`：度分秒换算十进制度，南纬 / 西经取负；越界或分母为 0 → None。
+#[test]
+fn build_gps_position_converts_dms_with_hemisphere() {
+    let r = |n, d| nom_exif::URational::new(n, d);
+    // 30°15'36" N, 120°9'18" E（杭州西湖附近）
+    let lat = [r(30, 1), r(15, 1), r(3600, 100)];
+    let lon = [r(120, 1), r(9, 1), r(18, 1)];
+    let (y, x) = super::build_gps_position("N", lat, "E", lon).unwrap();
+    assert!((y - 30.26).abs() < 1e-9, "{y}");
+    assert!((x - 120.155).abs() < 1e-9, "{x}");
+
+    let (y, x) = super::build_gps_position("S", lat, "W", lon).unwrap();
+    assert!(y < 0.0 && x < 0.0);
+
+    let zero = [r(1, 0), r(0, 1), r(0, 1)];
+    assert!(super::build_gps_position("N", zero, "E", lon).is_none());
+    let beyond = [r(91, 1), r(0, 1), r(0, 1)];
+    assert!(super::build_gps_position("N", beyond, "E", lon).is_none());
+}
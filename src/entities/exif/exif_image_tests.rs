@@ -149,6 +149,21 @@ fn apply_tiff_ifd_with_empty_fields_writes_nothing() {
     assert_eq!(exif.model(), None);
 }
 
+/// MakerNote 连拍标识随 `apply_tiff_ifd` 写入（PNG eXIf / APP1 fallback 共用）。
+#[test]
+fn apply_tiff_ifd_copies_burst_identifiers() {
+    use super::super::tiff_ifd::TiffIfd;
+    let mut exif = mk_exif("image/jpeg", |_| {});
+    let tiff = TiffIfd {
+        burst_id: Some("B1".to_string()),
+        sequence_number: Some(2),
+        ..TiffIfd::default()
+    };
+    super::apply_tiff_ifd(&mut exif, tiff, super::tests_common::utc());
+    assert_eq!(exif.burst_id(), Some("B1"));
+    assert_eq!(exif.burst_sequence(), Some(2));
+}
+
 /// 验证 fixture 的 nom-exif 主路径**真的**失败（fixture 失效时立刻报警）。
 /// 这是 fallback 路径覆盖率的前提；fixture 改动若让 nom-exif 突然接受，
 /// 上面 fallback 集成 case 仍可能通过（走主路径），但本 case 会失败提示。
@@ -536,3 +536,13 @@ fn open_with_extensionless_path_and_unknown_bytes_yields_empty_mime() {
     assert_eq!(exif.mime_type(), "");
     assert!(!exif.is_media());
 }
+
+/// `{media_kind}` 占位符口径：image → photo、video → video、办公文档 → doc、其余 other。
+#[test]
+fn media_kind_classifies_by_mime() {
+    assert_eq!(Exif::with_mime("image/jpeg").media_kind(), "photo");
+    assert_eq!(Exif::with_mime("video/mp4").media_kind(), "video");
+    assert_eq!(Exif::with_mime("application/pdf").media_kind(), "doc");
+    assert_eq!(Exif::with_mime("application/zip").media_kind(), "other");
+    assert_eq!(Exif::with_mime("").media_kind(), "other");
+}
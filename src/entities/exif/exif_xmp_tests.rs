@@ -4,6 +4,11 @@ use super::super::test_common as common;
 use super::Exif;
 use super::tests_common::mk_exif;
 use super::tests_common::utc;
+use crate::entities::xmp::XmpMeta;
+
+fn head_xmp(head: &[u8]) -> Option<XmpMeta> {
+    super::parse_head_xmp(head, utc())
+}
 
 /// EXIF block 完全无三日期、XMP packet 含 photoshop:DateCreated + xmp:CreateDate
 /// → fallback 把两者分别填入 `date_time_original` / `create_date`。
@@ -62,7 +67,7 @@ fn populate_image_xmp_fallback_fills_both_keys() {
         xmp:CreateDate=\"2024-05-02T15:30:00+00:00\"/>\
         </x:xmpmeta>tail";
     let mut exif = mk_exif("image/jpeg", |_| {});
-    super::populate_image_xmp_fallback(head, &mut exif);
+    super::populate_image_xmp_fallback(head_xmp(head).as_ref(), &mut exif);
     assert_eq!(exif.date_time_original(), 1_714_545_000);
     assert_eq!(exif.exif_create_date(), 1_714_663_800);
 }
@@ -71,7 +76,7 @@ fn populate_image_xmp_fallback_fills_both_keys() {
 #[test]
 fn populate_image_xmp_fallback_no_packet_no_change() {
     let mut exif = mk_exif("image/jpeg", |_| {});
-    super::populate_image_xmp_fallback(b"random bytes no packet", &mut exif);
+    super::populate_image_xmp_fallback(head_xmp(b"random bytes no packet").as_ref(), &mut exif);
     assert_eq!(exif.date_time_original(), 0);
     assert_eq!(exif.exif_create_date(), 0);
 }
@@ -84,7 +89,7 @@ fn populate_image_xmp_fallback_negative_epoch_rejected() {
         xmp:CreateDate=\"1969-01-01T00:00:00+00:00\"/>\
         </x:xmpmeta>";
     let mut exif = mk_exif("image/jpeg", |_| {});
-    super::populate_image_xmp_fallback(head, &mut exif);
+    super::populate_image_xmp_fallback(head_xmp(head).as_ref(), &mut exif);
     assert_eq!(exif.date_time_original(), 0);
     assert_eq!(exif.exif_create_date(), 0);
 }
@@ -94,7 +99,7 @@ fn populate_image_xmp_fallback_negative_epoch_rejected() {
 fn populate_image_xmp_fallback_packet_without_keys() {
     let head = b"<x:xmpmeta xmlns:x='adobe:ns:meta/'></x:xmpmeta>";
     let mut exif = mk_exif("image/jpeg", |_| {});
-    super::populate_image_xmp_fallback(head, &mut exif);
+    super::populate_image_xmp_fallback(head_xmp(head).as_ref(), &mut exif);
     assert_eq!(exif.date_time_original(), 0);
     assert_eq!(exif.exif_create_date(), 0);
 }
@@ -125,3 +130,112 @@ fn populate_image_dates_seek_failure_returns_early() {
     super::populate_image_dates(reader, &mut exif, utc());
     assert_eq!(exif.date_time_original(), 0);
 }
+
+/// EXIF 日期已有 → 日期不被 XMP 覆盖，但星级 / 关键词 / 缺失的 Make 仍取自 XMP。
+#[test]
+fn xmp_fallback_if_empty_applies_descriptive_fields_when_dates_present() {
+    let head = b"<x:xmpmeta><rdf:Description xmp:Rating=\"5\" tiff:Make=\"XmpMake\" \
+        photoshop:DateCreated=\"2020-01-01T00:00:00Z\">\
+        <dc:subject><rdf:Bag><rdf:li>trip</rdf:li></rdf:Bag></dc:subject>\
+        </rdf:Description></x:xmpmeta>";
+    let mut exif = mk_exif("image/jpeg", |e| e.date_time_original = 1_704_110_400);
+    super::populate_image_xmp_fallback_if_empty(head_xmp(head).as_ref(), &mut exif);
+    assert_eq!(exif.date_time_original(), 1_704_110_400);
+    assert_eq!(exif.rating(), Some(5));
+    assert_eq!(exif.keywords(), ["trip".to_string()]);
+    assert_eq!(exif.make(), Some("XmpMake"));
+}
+
+/// EXIF 已给镜头时不被 XMP 覆盖；国家只来自 XMP。
+#[test]
+fn xmp_descriptive_fills_country_and_keeps_exif_lens() {
+    let head = b"<x:xmpmeta><rdf:Description aux:Lens=\"XmpLens\" \
+        photoshop:Country=\"Japan\"/></x:xmpmeta>";
+    let mut exif = mk_exif("image/jpeg", |e| e.lens = Some("ExifLens".into()));
+    super::populate_image_xmp_fallback(head_xmp(head).as_ref(), &mut exif);
+    assert_eq!(exif.lens(), Some("ExifLens"));
+    assert_eq!(exif.country(), Some("Japan"));
+
+    let mut exif = mk_exif("image/jpeg", |_| {});
+    super::populate_image_xmp_fallback(head_xmp(head).as_ref(), &mut exif);
+    assert_eq!(exif.lens(), Some("XmpLens"));
+}
+
+/// element 形式 `exif:DateTimeOriginal` 优先于 `photoshop:DateCreated` 填 DTO；
+/// 无时区值按调用方 offset 解释。
+#[test]
+fn xmp_fallback_prefers_exif_date_time_original() {
+    let head = b"<x:xmpmeta><rdf:Description photoshop:DateCreated=\"2024-05-01\">\
+        <exif:DateTimeOriginal>2024-05-01T06:30:00</exif:DateTimeOriginal>\
+        </rdf:Description></x:xmpmeta>";
+    let mut exif = mk_exif("image/jpeg", |_| {});
+    super::populate_image_xmp_fallback(head_xmp(head).as_ref(), &mut exif);
+    assert_eq!(exif.date_time_original(), 1_714_545_000);
+}
+
+fn jpeg_segment(marker: u8, payload: &[u8]) -> Vec<u8> {
+    let len = u16::try_from(payload.len() + 2).unwrap();
+    let mut out = vec![0xFF, marker];
+    out.extend_from_slice(&len.to_be_bytes());
+    out.extend_from_slice(payload);
+    out
+}
+
+fn extended_segment(guid: &str, full: &[u8], offset: usize, len: usize) -> Vec<u8> {
+    let mut payload = b"http://ns.adobe.com/xmp/extension/\0".to_vec();
+    payload.extend_from_slice(guid.as_bytes());
+    payload.extend_from_slice(&u32::try_from(full.len()).unwrap().to_be_bytes());
+    payload.extend_from_slice(&u32::try_from(offset).unwrap().to_be_bytes());
+    payload.extend_from_slice(&full[offset..offset + len]);
+    jpeg_segment(0xE1, &payload)
+}
+
+/// 主 packet 声明 `HasExtendedXMP` → 回读两段续段（中间夹一个 APP2 被跳过）重组合并。
+#[test]
+fn read_image_xmp_merges_extended_segments() {
+    use std::io::Cursor;
+
+    const GUID: &str = "0123456789ABCDEF0123456789ABCDEF";
+    let main = format!(
+        "http://ns.adobe.com/xap/1.0/\0<x:xmpmeta><rdf:Description \
+         xmpNote:HasExtendedXMP=\"{GUID}\" tiff:Make=\"Canon\"/></x:xmpmeta>"
+    );
+    let extended = b"<x:xmpmeta><rdf:Description xmp:Rating=\"3\" tiff:Make=\"Other\">\
+        <exif:DateTimeOriginal>2024-05-01T14:30:00+08:00</exif:DateTimeOriginal>\
+        </rdf:Description></x:xmpmeta>";
+    let half = extended.len() / 2;
+    let mut jpeg = vec![0xFF, 0xD8];
+    jpeg.extend_from_slice(&jpeg_segment(0xE1, main.as_bytes()));
+    jpeg.extend_from_slice(&extended_segment(
+        GUID,
+        extended,
+        half,
+        extended.len() - half,
+    ));
+    jpeg.extend_from_slice(&jpeg_segment(0xE2, b"ICC_PROFILE\0"));
+    jpeg.extend_from_slice(&extended_segment(GUID, extended, 0, half));
+    jpeg.extend_from_slice(&[0xFF, 0xDA, 0x00, 0x02]);
+
+    let mut reader = Cursor::new(jpeg.clone());
+    let meta = super::read_image_xmp(&jpeg, &mut reader, utc()).unwrap();
+    assert_eq!(meta.make.as_deref(), Some("Canon"));
+    assert_eq!(meta.rating, Some(3));
+    assert_eq!(
+        meta.exif_date_time_original.unwrap().timestamp(),
+        1_714_545_000
+    );
+
+    // 续段不全（只剩后半段）→ 丢弃续段，主 packet 值照用。
+    let mut broken = vec![0xFF, 0xD8];
+    broken.extend_from_slice(&jpeg_segment(0xE1, main.as_bytes()));
+    broken.extend_from_slice(&extended_segment(
+        GUID,
+        extended,
+        half,
+        extended.len() - half,
+    ));
+    let mut reader = Cursor::new(broken.clone());
+    let meta = super::read_image_xmp(&broken, &mut reader, utc()).unwrap();
+    assert_eq!(meta.make.as_deref(), Some("Canon"));
+    assert!(meta.rating.is_none());
+}
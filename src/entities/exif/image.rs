@@ -14,13 +14,15 @@ use super::super::backend::MediaReader;
 use super::super::file_info::read_fill;
 use super::super::tiff_ifd::TiffIfd;
 use super::super::xmp;
+use super::super::xmp::XmpMeta;
 use super::image_jpeg::parse_jpeg_app1_exif;
+use super::image_jpeg::read_jpeg_extended_xmp;
 use super::types::Exif;
 use super::types::entry_value_to_epoch;
 use super::video::ascii_datetime_to_epoch;
 
-/// XMP packet fallback 扫描窗口。单段 APP1 最大 65533 字节，64 KB 覆盖单段
-/// XMP packet 起始；ExtendedXMP（跨多 APP1 段）不在范围。
+/// XMP packet 扫描窗口。单段 APP1 最大 65533 字节，64 KB 覆盖主 XMP packet 起始；
+/// `ExtendedXMP` 续段由 [`read_image_xmp`] 按需回读 JPEG 段，不受此窗口限制。
 const XMP_SCAN_BYTES: usize = 64 * 1024;
 
 pub(super) fn populate_image_dates(
@@ -28,19 +30,27 @@ pub(super) fn populate_image_dates(
     exif: &mut Exif,
     local_offset: FixedOffset,
 ) {
-    // 先 buffer 头部供 XMP fallback；seek 回起点后再喂给 nom-exif。
-    // seek 失败时跳过 nom-exif 主路径但仍尝试 XMP fallback——head 已读入，
+    // 先 buffer 头部并解析 XMP（主 packet + 必要时回读 ExtendedXMP 段）；seek 回
+    // 起点后再喂给 nom-exif。seek 失败时跳过 nom-exif 主路径但仍用 XMP——
     // 仅靠头部字节即可补 P0/P1 候选，比 mtime 兜底准确得多。
     let mut head = vec![0u8; XMP_SCAN_BYTES];
     let head_len = read_fill(reader.as_mut(), &mut head).unwrap_or(0);
     head.truncate(head_len);
+    let xmp_meta = read_image_xmp(&head, reader.as_mut(), local_offset);
+    let xmp_meta = xmp_meta.as_ref();
+    // nom-exif 不解 MakerNote：连拍标识总从 APP1 裸 IFD 自解析取；nom-exif 整体
+    // Err 时同一结果兼作日期 fallback。
+    let app1 = parse_jpeg_app1_exif(&head);
+    if let Some(tiff) = &app1 {
+        apply_burst(exif, tiff);
+    }
     if reader.seek(io::SeekFrom::Start(0)).is_err() {
-        populate_image_xmp_fallback(&head, exif);
+        populate_image_xmp_fallback(xmp_meta, exif);
         return;
     }
 
     let Ok(ms) = MediaSource::seekable(reader) else {
-        populate_image_xmp_fallback(&head, exif);
+        populate_image_xmp_fallback(xmp_meta, exif);
         return;
     };
     let mut parser = MediaParser::new();
@@ -48,10 +58,10 @@ pub(super) fn populate_image_dates(
         // nom-exif 整体 Err（如 Canon EOS 7D MakerNotes 偏移异常）→ 先试 JPEG APP1
         // 裸 IFD 自解析；APP1 仅含 Make/Model（re-tag 后 IFD0 仅剩 ModifyDate）的
         // 情况通过共享 helper 再退 XMP，与主路径同口径。
-        if let Some(tiff) = parse_jpeg_app1_exif(&head) {
+        if let Some(tiff) = app1 {
             apply_tiff_ifd(exif, tiff, local_offset);
         }
-        populate_image_xmp_fallback_if_empty(&head, exif);
+        populate_image_xmp_fallback_if_empty(xmp_meta, exif);
         return;
     };
     let parsed: nom_exif::Exif = iter.into();
@@ -63,6 +73,7 @@ pub(super) fn populate_image_dates(
     }
     // GPSDateStamp + GPSTimeStamp 合成 GPS UTC 作校验锚点。
     exif.gps_utc = parse_gps_utc(&parsed);
+    exif.gps_position = parse_gps_position(&parsed);
     // ModifyDate 不进时间候选（编辑/导出时间会污染判定），仅供多数派仲裁
     // 识别 re-save 痕迹（filename+mtime+ModifyDate 三方互证 → 否决推翻 P0）。
     if let Some(v) = parsed.get(ExifTag::ModifyDate) {
@@ -75,18 +86,44 @@ pub(super) fn populate_image_dates(
     exif.model = parsed
         .get(ExifTag::Model)
         .and_then(|v| v.as_str().map(str::to_owned));
+    exif.lens = parsed
+        .get(ExifTag::LensModel)
+        .and_then(|v| v.as_str().map(str::to_owned));
 
     // XMP fallback：EXIF DTO/CreateDate 均缺（re-tag 后 IFD0 仅剩 ModifyDate 类
-    // 场景）时扫已 buffer 的头部，从 XMP packet 补 P0/P1 候选。
-    populate_image_xmp_fallback_if_empty(&head, exif);
+    // 场景）时从 XMP packet 补 P0/P1 候选；星级/关键词无论日期来源都取。
+    populate_image_xmp_fallback_if_empty(xmp_meta, exif);
+}
+
+/// 从头部 buffer 找主 XMP packet 并解析；声明了 `xmpNote:HasExtendedXMP` 时经
+/// `reader` 回读 JPEG APP1 续段重组后合并。调用方负责之后 seek 回起点。
+pub(super) fn read_image_xmp(
+    head: &[u8],
+    reader: &mut dyn MediaReader,
+    local_offset: FixedOffset,
+) -> Option<XmpMeta> {
+    let mut meta = parse_head_xmp(head, local_offset)?;
+    if let Some(guid) = meta.extended_guid.clone()
+        && let Some(extended) = read_jpeg_extended_xmp(reader, &guid)
+    {
+        meta.merge(xmp::parse_xmp(&extended, Some(local_offset)));
+    }
+    Some(meta)
+}
+
+/// 仅头部 buffer 内的主 packet（无 IO）。无时区 XMP 日期按 `local_offset` 解释。
+pub(super) fn parse_head_xmp(head: &[u8], local_offset: FixedOffset) -> Option<XmpMeta> {
+    xmp::find_xmp_packet(head).map(|packet| xmp::parse_xmp(packet, Some(local_offset)))
 }
 
 /// 主路径 + APP1 fallback 路径 + PNG eXIf 路径共享的"空日期才退 XMP"决策点。
 /// 单点 `&&` short-circuit 让 LLVM 仅生成一组 BR，避免多调用点各自的 BR 子分支
 /// 分散导致 multi-instance 累加下某些 sub-branch 0-hit。
-pub(super) fn populate_image_xmp_fallback_if_empty(head: &[u8], exif: &mut Exif) {
+pub(super) fn populate_image_xmp_fallback_if_empty(xmp: Option<&XmpMeta>, exif: &mut Exif) {
     if exif.date_time_original == 0 && exif.create_date == 0 {
-        populate_image_xmp_fallback(head, exif);
+        populate_image_xmp_fallback(xmp, exif);
+    } else if let Some(meta) = xmp {
+        apply_xmp_descriptive(meta, exif);
     }
 }
 
@@ -102,48 +139,67 @@ pub(super) fn apply_tiff_ifd(exif: &mut Exif, tiff: TiffIfd, local_offset: Fixed
     if let Some(s) = tiff.modify_date.as_deref() {
         exif.modify_date = ascii_datetime_to_epoch(s, local_offset);
     }
+    apply_burst(exif, &tiff);
     exif.make = tiff.make;
     exif.model = tiff.model;
 }
 
-pub(super) fn populate_image_xmp_fallback(head: &[u8], exif: &mut Exif) {
-    let Some(packet) = xmp::find_xmp_packet(head) else {
+fn apply_burst(exif: &mut Exif, tiff: &TiffIfd) {
+    exif.burst_id.clone_from(&tiff.burst_id);
+    exif.burst_sequence = tiff.sequence_number;
+}
+
+/// XMP 日期写入 `Exif`：`exif:DateTimeOriginal` 优先于常只精确到日的
+/// `photoshop:DateCreated` 填 DTO（P0），`xmp:CreateDate` 填 `CreateDate`（P1）；
+/// 再补 Make/Model/星级/关键词。
+pub(super) fn populate_image_xmp_fallback(xmp: Option<&XmpMeta>, exif: &mut Exif) {
+    let Some(meta) = xmp else {
         return;
     };
-    let dates = xmp::parse_xmp_dates(packet);
-    if let Some(dt) = dates.photoshop_date_created {
-        // XMP 时间带 timezone，DateTime<FixedOffset>::timestamp() 直接是 UTC epoch。
-        // Exif 字段为 u64 不能存 1970 前的负值（数字摄影几乎不涉及，扫描件 XMP
-        // 偶有 1960s 标注会落入此分支）；丢弃前发 debug 让缺口可见。
-        let secs = dt.timestamp();
-        if secs > 0 {
-            exif.date_time_original = secs.cast_unsigned();
-        } else {
-            debug!(
-                feature = "exif",
-                operation = "xmp_fallback",
-                field = "DateTimeOriginal",
-                value = %dt,
-                secs,
-                "xmp date predates Unix epoch; cannot encode in u64 field"
-            );
-        }
+    if let Some(dt) = meta.exif_date_time_original.or(meta.photoshop_date_created) {
+        exif.date_time_original = xmp_epoch(dt, "DateTimeOriginal");
     }
-    if let Some(dt) = dates.xmp_create_date {
-        let secs = dt.timestamp();
-        if secs > 0 {
-            exif.create_date = secs.cast_unsigned();
-        } else {
-            debug!(
-                feature = "exif",
-                operation = "xmp_fallback",
-                field = "CreateDate",
-                value = %dt,
-                secs,
-                "xmp date predates Unix epoch; cannot encode in u64 field"
-            );
-        }
+    if let Some(dt) = meta.xmp_create_date {
+        exif.create_date = xmp_epoch(dt, "CreateDate");
+    }
+    apply_xmp_descriptive(meta, exif);
+}
+
+// XMP 时间带 timezone，DateTime<FixedOffset>::timestamp() 直接是 UTC epoch。
+// Exif 字段为 u64 不能存 1970 前的负值（数字摄影几乎不涉及，扫描件 XMP
+// 偶有 1960s 标注会落入此分支）；丢弃前发 debug 让缺口可见。
+fn xmp_epoch(dt: DateTime<FixedOffset>, field: &'static str) -> u64 {
+    let secs = dt.timestamp();
+    if secs > 0 {
+        return secs.cast_unsigned();
     }
+    debug!(
+        feature = "exif",
+        operation = "xmp_fallback",
+        field,
+        value = %dt,
+        secs,
+        "xmp date predates Unix epoch; cannot encode in u64 field"
+    );
+    0
+}
+
+/// 非日期字段：EXIF 已给 Make/Model/镜头时不覆盖；星级/色标/关键词/国家只来自 XMP。
+fn apply_xmp_descriptive(meta: &XmpMeta, exif: &mut Exif) {
+    if exif.make.is_none() {
+        exif.make.clone_from(&meta.make);
+    }
+    if exif.model.is_none() {
+        exif.model.clone_from(&meta.model);
+    }
+    if exif.lens.is_none() {
+        exif.lens.clone_from(&meta.lens);
+    }
+    exif.country.clone_from(&meta.country);
+    exif.city.clone_from(&meta.city);
+    exif.rating = meta.rating;
+    exif.label.clone_from(&meta.label);
+    exif.keywords.clone_from(&meta.keywords);
 }
 
 /// 从已解析的 EXIF 读 `GPSDateStamp`（文本 "YYYY:MM:DD"）和
@@ -184,6 +240,65 @@ fn parse_gps_utc(parsed: &nom_exif::Exif) -> Option<DateTime<Utc>> {
     build_gps_utc(date_str.as_deref(), time_rationals)
 }
 
+const GPS_LATITUDE_REF: u16 = ExifTag::GPSLatitudeRef.code();
+const GPS_LATITUDE: u16 = ExifTag::GPSLatitude.code();
+const GPS_LONGITUDE_REF: u16 = ExifTag::GPSLongitudeRef.code();
+const GPS_LONGITUDE: u16 = ExifTag::GPSLongitude.code();
+
+/// 从已解析的 EXIF 读 `GPSLatitude` / `GPSLongitude`（3 元素 `URationalArray`：度, 分, 秒）
+/// 及其 Ref（`N`/`S`、`E`/`W`），合成十进制度。任一缺失返回 None。
+// 与 parse_gps_utc 同走 iter() + raw code。Interop IFD 的 0x0001/0x0002 与 GPS
+// Ref/纬度同码：Ref 只认 N/S/E/W、度分秒只认 3 元素 URational，其余值忽略。
+fn parse_gps_position(parsed: &nom_exif::Exif) -> Option<(f64, f64)> {
+    let (mut lat_ref, mut lon_ref) = (None, None);
+    let (mut lat, mut lon): (Option<[URational; 3]>, Option<[URational; 3]>) = (None, None);
+    let dms = |v: &nom_exif::EntryValue| {
+        v.as_urational_slice()
+            .and_then(|s| <[URational; 3]>::try_from(s).ok())
+    };
+    for entry in parsed.iter() {
+        let text = entry.value.as_str().map(str::trim);
+        match entry.tag.code() {
+            GPS_LATITUDE_REF => {
+                lat_ref = text
+                    .filter(|r| matches!(*r, "N" | "S"))
+                    .map(str::to_owned)
+                    .or(lat_ref);
+            }
+            GPS_LONGITUDE_REF => {
+                lon_ref = text
+                    .filter(|r| matches!(*r, "E" | "W"))
+                    .map(str::to_owned)
+                    .or(lon_ref);
+            }
+            GPS_LATITUDE => lat = dms(&entry.value).or(lat),
+            GPS_LONGITUDE => lon = dms(&entry.value).or(lon),
+            _ => {}
+        }
+    }
+    build_gps_position(lat_ref.as_deref()?, lat?, lon_ref.as_deref()?, lon?)
+}
+
+/// 度分秒 + 半球 → 十进制度；超出 ±90 / ±180 视为损坏返回 None。
+pub(super) fn build_gps_position(
+    lat_ref: &str,
+    lat: [URational; 3],
+    lon_ref: &str,
+    lon: [URational; 3],
+) -> Option<(f64, f64)> {
+    let degrees = |[d, m, s]: [URational; 3]| -> Option<f64> {
+        Some(rational_to_f64(d)? + rational_to_f64(m)? / 60.0 + rational_to_f64(s)? / 3600.0)
+    };
+    let lat = degrees(lat)? * if lat_ref == "S" { -1.0 } else { 1.0 };
+    let lon = degrees(lon)? * if lon_ref == "W" { -1.0 } else { 1.0 };
+    (lat.abs() <= 90.0 && lon.abs() <= 180.0).then_some((lat, lon))
+}
+
+fn rational_to_f64(r: URational) -> Option<f64> {
+    let denom = r.denominator();
+    (denom != 0).then(|| f64::from(r.numerator()) / f64::from(denom))
+}
+
 /// `date_str` = "YYYY:MM:DD", `time` = [hour, min, sec] Rational。
 /// 全部转为整秒（纳秒丢弃），合成 `DateTime<Utc>`。
 pub(super) fn build_gps_utc(
@@ -7,8 +7,15 @@
 //!
 //! 只读 `image::populate_image_dates` 已 buffer 的 64 KiB 头部，不再 IO；
 //! 多 APP1 段（XMP APP1 在前 + Exif APP1 在后）按规范遍历命中 Exif APP1。
+//!
+//! 例外：`ExtendedXMP` 续段每段近 64 KiB、常有多段，头部装不下，由
+//! [`read_jpeg_extended_xmp`] 经 reader 逐段回读（非 APP1 段 seek 跳过，不读入内存）。
+
+use std::io;
 
+use super::super::backend::MediaReader;
 use super::super::tiff_ifd;
+use super::super::xmp;
 
 /// JPEG marker prefix。
 const MARKER_PREFIX: u8 = 0xFF;
@@ -27,6 +34,10 @@ const EXIF_MAGIC: &[u8; 6] = b"Exif\0\0";
 /// 扫描的 marker 上限，防恶意/损坏数据死循环。
 const MAX_MARKERS: usize = 64;
 
+/// `ExtendedXMP` 回读的 marker 上限：16 MiB 重组上限 / 每段 ~64 KiB ≈ 256 段，
+/// 另留余量给其它 APP 段。
+const MAX_EXTENDED_MARKERS: usize = 512;
+
 /// 从 JPEG 头部 buffer（推荐 ≥ 64 KiB）提取 Exif APP1 内 TIFF/IFD 字段。
 /// 非 JPEG / 无 Exif APP1 / TIFF header 损坏均返 None。
 pub(super) fn parse_jpeg_app1_exif(head: &[u8]) -> Option<tiff_ifd::TiffIfd> {
@@ -65,6 +76,50 @@ pub(super) fn parse_jpeg_app1_exif(head: &[u8]) -> Option<tiff_ifd::TiffIfd> {
     None
 }
 
+/// 从 JPEG 起点遍历 SOS 前的全部段，收集 GUID 匹配的 `ExtendedXMP` 续段并重组。
+/// 非 JPEG、段结构损坏或续段不完整均返 None；reader 位置不复原（调用方 seek 回起点）。
+pub(super) fn read_jpeg_extended_xmp(reader: &mut dyn MediaReader, guid: &str) -> Option<String> {
+    reader.seek(io::SeekFrom::Start(0)).ok()?;
+    let mut soi = [0u8; 2];
+    reader.read_exact(&mut soi).ok()?;
+    if soi != [MARKER_PREFIX, MARKER_SOI] {
+        return None;
+    }
+    let mut chunks = Vec::new();
+    for _ in 0..MAX_EXTENDED_MARKERS {
+        // 读失败 / 结构异常即停：已收集的段仍交给重组判定是否完整。
+        let mut header = [0u8; 4];
+        if reader.read_exact(&mut header).is_err() || header[0] != MARKER_PREFIX {
+            break;
+        }
+        let code = header[1];
+        if code == MARKER_SOS || code == MARKER_EOI {
+            break;
+        }
+        // segment length = BE u16，包含自身 2 字节但不含 marker。
+        let Some(payload_len) = u16::from_be_bytes([header[2], header[3]]).checked_sub(2) else {
+            break;
+        };
+        if code == MARKER_APP1 {
+            let mut payload = vec![0u8; usize::from(payload_len)];
+            if reader.read_exact(&mut payload).is_err() {
+                break;
+            }
+            if let Some(chunk) = xmp::parse_extended_chunk(&payload)
+                && chunk.guid == guid
+            {
+                chunks.push(chunk);
+            }
+        } else if reader
+            .seek(io::SeekFrom::Current(i64::from(payload_len)))
+            .is_err()
+        {
+            break;
+        }
+    }
+    xmp::reassemble_extended(&chunks, guid)
+}
+
 #[cfg(test)]
 #[path = "jpeg_fallback_tests.rs"]
 mod tests;
@@ -13,6 +13,7 @@ use super::super::backend::MediaReader;
 use super::super::file_info::read_fill;
 use super::super::png;
 use super::image::apply_tiff_ifd;
+use super::image::parse_head_xmp;
 use super::image::populate_image_xmp_fallback;
 use super::image::populate_image_xmp_fallback_if_empty;
 use super::types::Exif;
@@ -28,18 +29,21 @@ pub(super) fn populate_png_dates(
     let mut head = vec![0u8; XMP_SCAN_BYTES];
     let head_len = read_fill(reader.as_mut(), &mut head).unwrap_or(0);
     head.truncate(head_len);
+    // PNG 的 XMP 在单个 iTXt chunk 内，无 ExtendedXMP 续段：只看头部。
+    let xmp_meta = parse_head_xmp(&head, local_offset);
+    let xmp_meta = xmp_meta.as_ref();
     if reader.seek(io::SeekFrom::Start(0)).is_err() {
-        populate_image_xmp_fallback(&head, exif);
+        populate_image_xmp_fallback(xmp_meta, exif);
         return;
     }
 
     let Some(ifd) = png::parse_png_exif(reader.as_mut()) else {
-        populate_image_xmp_fallback(&head, exif);
+        populate_image_xmp_fallback(xmp_meta, exif);
         return;
     };
     apply_tiff_ifd(exif, ifd, local_offset);
 
     // PNG eXIf 但所有日期字段全空：仍尝试 XMP fallback（导出工具常并行写）。
     // 复用 image.rs 的 helper 收敛 `&&` 短路 BR 到单点。
-    populate_image_xmp_fallback_if_empty(&head, exif);
+    populate_image_xmp_fallback_if_empty(xmp_meta, exif);
 }
@@ -38,6 +38,12 @@ pub(super) fn sniff_mime(reader: &mut dyn MediaReader) -> io::Result<String> {
         .unwrap_or_default())
 }
 
+/// 嗅探是否为视频容器（口径同 [`super::Exif::open`]，含老 QuickTime / m2ts / 3GPP 兜底）；
+/// 之后 reader 位于 0。
+pub(crate) fn is_video(reader: &mut dyn MediaReader) -> io::Result<bool> {
+    Ok(sniff_mime(reader)?.starts_with(META_TYPE_VIDEO))
+}
+
 // `infer` 只匹配 `ftyp` brand 的现代 QuickTime/MP4；老 QuickTime 有两种变体：
 //   - `pnot` preview atom 起头（NIKON COOLPIX S5/P5000、Casio 早期机型）
 //   - `mdat` 直接起头（无任何头 atom、moov 在文件末尾的 mdat-first 变体）
@@ -6,6 +6,7 @@ mod mime;
 mod types;
 mod video;
 
+pub(crate) use self::mime::is_video;
 pub use self::types::Exif;
 
 // 测试要访问的内部 helper 在父 mod 私有 re-export，
@@ -13,16 +14,24 @@ pub use self::types::Exif;
 #[cfg(test)]
 use self::image::apply_tiff_ifd;
 #[cfg(test)]
+use self::image::build_gps_position;
+#[cfg(test)]
 use self::image::build_gps_utc;
 #[cfg(test)]
 use self::image::parse_gps_date;
 #[cfg(test)]
+use self::image::parse_head_xmp;
+#[cfg(test)]
 use self::image::populate_image_dates;
 #[cfg(test)]
 use self::image::populate_image_xmp_fallback;
 #[cfg(test)]
+use self::image::populate_image_xmp_fallback_if_empty;
+#[cfg(test)]
 use self::image::rational_to_u32;
 #[cfg(test)]
+use self::image::read_image_xmp;
+#[cfg(test)]
 use self::image_png::populate_png_dates;
 #[cfg(test)]
 use self::mime::bmff_3gpp_mime;
@@ -55,6 +55,19 @@ pub struct Exif {
     // 用于 archive_template 的 `{make}` / `{model}` 占位符。
     pub(super) make: Option<String>,
     pub(super) model: Option<String>,
+    // 镜头型号：EXIF `LensModel`，缺失时退 XMP `exifEX:LensModel` / `aux:Lens`；
+    // 国家：XMP `photoshop:Country`（Lightroom / digiKam 按 GPS 反查写入）。
+    // 分别供 `{lens}` / `{country}` 占位符。
+    pub(super) lens: Option<String>,
+    pub(super) country: Option<String>,
+    // 城市：XMP `photoshop:City`，同上由照片管理软件写入；`{event}` 命名用。
+    pub(super) city: Option<String>,
+    // EXIF GPS 经纬度（十进制度，南纬 / 西经为负）；`{event}` 聚类的空间半径判定用。
+    pub(super) gps_position: Option<(f64, f64)>,
+    // 连拍标识：Apple MakerNote `BurstUUID` / Canon MakerNote 连拍序号；
+    // 仅 JPEG APP1 / PNG eXIf 自解析路径填写，供 `cull` 连拍分组。
+    pub(super) burst_id: Option<String>,
+    pub(super) burst_sequence: Option<u32>,
 
     // 办公文档容器内创建/修改时间（dcterms:created / PDF /CreationDate /
     // CFB PID_CREATE_DTM / iWork plist createdDate / `.mm` CREATED 等），
@@ -64,6 +77,12 @@ pub struct Exif {
     // `doc_modified` 仅作 re-save 旁证（与 EXIF `ModifyDate` 同口径），不进候选。
     pub(super) doc_created: u64,
     pub(super) doc_modified: u64,
+
+    // XMP `xmp:Rating`（-1 拒绝 … 5 星）、`xmp:Label` 色标与 `dc:subject` 关键词；
+    // 图片内嵌 packet（含 `ExtendedXMP`）解析，与日期是否已由 EXIF 给出无关。
+    pub(super) rating: Option<i8>,
+    pub(super) label: Option<String>,
+    pub(super) keywords: Vec<String>,
 }
 
 impl Exif {
@@ -199,6 +218,88 @@ impl Exif {
         self.model.as_deref()
     }
 
+    /// 镜头型号（EXIF `LensModel`，缺失退 XMP）。
+    pub fn lens(&self) -> Option<&str> {
+        self.lens.as_deref()
+    }
+
+    /// XMP `photoshop:Country`（照片管理软件按 GPS 反查写入）；本工具不内置离线地理库。
+    pub fn country(&self) -> Option<&str> {
+        self.country.as_deref()
+    }
+
+    /// XMP `photoshop:City`；与 [`Self::country`] 同源。
+    pub fn city(&self) -> Option<&str> {
+        self.city.as_deref()
+    }
+
+    /// EXIF `GPSLatitude` / `GPSLongitude`（含 Ref）换算的 (纬度, 经度) 十进制度。
+    pub fn gps_position(&self) -> Option<(f64, f64)> {
+        self.gps_position
+    }
+
+    /// Apple `BurstUUID`：同一次连拍的帧共享同一值。
+    pub fn burst_id(&self) -> Option<&str> {
+        self.burst_id.as_deref()
+    }
+
+    /// Canon 连拍序号（1 起，0 = 单张）。
+    pub fn burst_sequence(&self) -> Option<u32> {
+        self.burst_sequence
+    }
+
+    /// 媒体大类：`photo` / `video` / `doc`（办公文档）/ `other`，按 MIME 判定。
+    pub fn media_kind(&self) -> &'static str {
+        let mime_type = self.mime_type();
+        if mime_type.starts_with(META_TYPE_IMAGE) {
+            "photo"
+        } else if mime_type.starts_with(META_TYPE_VIDEO) {
+            "video"
+        } else if is_office_mime(mime_type) {
+            "doc"
+        } else {
+            "other"
+        }
+    }
+
+    /// XMP 星级；无 XMP 或未评级为 None。
+    pub fn rating(&self) -> Option<i8> {
+        self.rating
+    }
+
+    /// XMP `xmp:Label` 色标；无 XMP 或未标记为 None。
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// XMP `dc:subject` 关键词，按出现顺序去重。
+    pub fn keywords(&self) -> &[String] {
+        &self.keywords
+    }
+
+    /// 已解析到的元数据字段数（各类时间、GPS、厂商 / 型号 / 镜头、国家、星级 / 色标 /
+    /// 关键词）。同内容副本间比较"谁的元数据更全"：被社交软件 / 导出流程剥离的副本记 0。
+    pub fn metadata_fields(&self) -> usize {
+        let dates = [
+            self.date_time_original,
+            self.create_date,
+            self.modify_date,
+            self.qt_create_date,
+            self.doc_created,
+        ];
+        let present = [
+            self.gps_utc.is_some(),
+            self.make.is_some(),
+            self.model.is_some(),
+            self.lens.is_some(),
+            self.country.is_some(),
+            self.rating.is_some(),
+            self.label.is_some(),
+            !self.keywords.is_empty(),
+        ];
+        dates.iter().filter(|&&d| d != 0).count() + present.iter().filter(|&&p| p).count()
+    }
+
     pub fn is_media(&self) -> bool {
         let mime_type = self.mime_type();
         (mime_type.starts_with(META_TYPE_IMAGE) || mime_type.starts_with(META_TYPE_VIDEO))
@@ -255,6 +356,20 @@ impl Exif {
         self
     }
 
+    /// 跨模块测试用：链式设置镜头型号与国家。
+    pub(crate) fn with_lens_country(mut self, lens: &str, country: &str) -> Self {
+        self.lens = Some(lens.to_string());
+        self.country = Some(country.to_string());
+        self
+    }
+
+    /// 跨模块测试用：链式设置 GPS 经纬度与城市。
+    pub(crate) fn with_position_city(mut self, lat: f64, lon: f64, city: Option<&str>) -> Self {
+        self.gps_position = Some((lat, lon));
+        self.city = city.map(str::to_string);
+        self
+    }
+
     /// 跨模块测试用：链式设置 Make / Model。
     pub(crate) fn with_make_model(mut self, make: &str, model: &str) -> Self {
         self.make = Some(make.to_string());
@@ -15,6 +15,7 @@ use super::common;
 use super::exif;
 use super::file_info::Info;
 use super::media_time;
+use super::tags::Tags;
 use super::threadpool::install_io;
 use super::uri::Location;
 // 测试 helper `Index::visit_dir` 需要构造 LocalBackend instance。仅 #[cfg(test)]
@@ -29,6 +30,13 @@ const FEATURE_INDEX: &str = "index";
 /// 普通 fn 指针即可——provider 无状态、`Send + Sync`、可直接进 rayon 并行。
 pub type CandidateProvider = fn(&Location, &Arc<dyn Backend>) -> Vec<media_time::Candidate>;
 
+/// 星级 / 色标注入：adapters 层读 `.xmp` sidecar，返 None 表示无 sidecar 或无相关字段。
+pub type TagProvider = fn(&Location, &Arc<dyn Backend>) -> Option<Tags>;
+
+/// 随媒体文件一起搬运的 sidecar 伴随文件（`.xmp` / Takeout `.json`）发现：adapters
+/// 层探测存在性，返回实际存在的伴随文件位置；无伴随文件返空。
+pub type CompanionProvider = fn(&Location, &Arc<dyn Backend>) -> Vec<Location>;
+
 /// 一组重复文件：相同 size + 相同 content hash。size 仅 metadata，组身份由 paths 决定。
 /// 避免旧 `BTreeMap<u64, Vec<Utf8PathBuf>>` 用 size 作唯一键导致同 size 不同内容互相覆盖。
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -46,6 +54,9 @@ pub struct VisitStats {
     pub skipped_unreadable: u64,
     /// walker 自身报错的 entry（包括非 UTF-8 路径、metadata 失败）
     pub walker_errors: u64,
+    /// [`Index::visit_size_collisions`]：size 在全部 root 间唯一、不可能有重复而
+    /// 未读内容也未入索引的文件
+    pub skipped_unique_size: u64,
 }
 
 pub struct Index {
@@ -134,6 +145,8 @@ impl Index {
         Ok(None)
     }
 
+    /// 分档判等：fast_hash（首 4 KiB）冲突桶 → 桶内 (size, 末 4 KiB) 冲突 → `calc`
+    /// 整文件哈希。每档落单者不可能有重复，直接出局，不再读后续档的字节。
     pub fn calc_same<F, T>(&self, calc: F) -> Vec<HashMap<(u64, T), HashSet<Utf8PathBuf>>>
     where
         F: Fn(&Info) -> io::Result<T> + Send + Sync,
@@ -144,15 +157,11 @@ impl Index {
             .filter(|(_, paths)| paths.len() > 1)
             .map(|(_, paths)| {
                 let mut same = HashMap::new();
-                for path in paths {
-                    let info = self
-                        .files
-                        .get(path)
-                        .expect("internal: similar_files entries must point to a known file");
+                for info in self.partial_collisions(paths) {
                     if let Ok(key) = calc(info) {
                         same.entry((info.size, key))
                             .or_insert_with(HashSet::new)
-                            .insert(path.clone());
+                            .insert(info.full_path.clone());
                     }
                 }
                 same
@@ -160,6 +169,27 @@ impl Index {
             .collect::<Vec<_>>()
     }
 
+    // head/tail 档：同 fast_hash 桶内按 (size, tail_hash) 细分，只放行仍冲突的文件。
+    // 只差尾部的大视频（同机位连拍、截断的下载）在这里出局，免去整文件读取。
+    // tail 读失败的文件与 calc 失败同口径静默跳过。
+    fn partial_collisions<'a>(&'a self, paths: &'a HashSet<Utf8PathBuf>) -> Vec<&'a Info> {
+        let mut by_partial: HashMap<(u64, u64), Vec<&Info>> = HashMap::new();
+        for path in paths {
+            let info = self
+                .files
+                .get(path)
+                .expect("internal: similar_files entries must point to a known file");
+            if let Ok(tail) = info.tail_hash() {
+                by_partial.entry((info.size, tail)).or_default().push(info);
+            }
+        }
+        by_partial
+            .into_values()
+            .filter(|infos| infos.len() > 1)
+            .flatten()
+            .collect()
+    }
+
     pub fn search_same(&self) -> Vec<DuplicateGroup> {
         let results: Vec<_> = self.calc_same(super::file_info::Info::secure_hash);
         Self::filter_and_sort(&results)
@@ -279,7 +309,45 @@ impl Index {
     /// - `Info::open` 失败（chmod 000 / 中途删除等）→ `skipped_unreadable += 1`
     ///
     pub fn visit_location(&mut self, root: &Location, backend: &Arc<dyn Backend>) {
-        let mut locs: Vec<Location> = Vec::new();
+        let entries = self
+            .walk_files(root, backend)
+            .into_iter()
+            .map(|(loc, _)| (loc, Arc::clone(backend)))
+            .collect();
+        self.open_all(entries);
+    }
+
+    /// find 的 size 档入口：先走遍全部 `roots`，只用 walk 元数据里的 size 分组；size
+    /// 在全部 root 间唯一的文件不可能有重复，不读内容、不入索引，计入
+    /// `skipped_unique_size`。只有 size 冲突的文件才 `Info::open` 读首 4 KiB 入索引，
+    /// 后续 head/tail 与整文件档见 [`Self::calc_same`]。
+    ///
+    /// 只适用于"找重复"：copy/move 要对每个源文件判去重，仍走 [`Self::visit_location`]。
+    pub fn visit_size_collisions(&mut self, roots: &[(Location, Arc<dyn Backend>)]) {
+        let mut by_size: HashMap<u64, Vec<(Location, Arc<dyn Backend>)>> = HashMap::new();
+        for (root, backend) in roots {
+            for (loc, size) in self.walk_files(root, backend) {
+                by_size
+                    .entry(size)
+                    .or_default()
+                    .push((loc, Arc::clone(backend)));
+            }
+        }
+        let mut colliding = Vec::new();
+        for entries in by_size.into_values() {
+            if entries.len() > 1 {
+                colliding.extend(entries);
+            } else {
+                self.stats.skipped_unique_size += 1;
+            }
+        }
+        self.open_all(colliding);
+    }
+
+    // walk 一个 root，返回非空普通文件的 (location, size)；walker 报错与 0 字节文件
+    // 计入 stats。
+    fn walk_files(&mut self, root: &Location, backend: &Arc<dyn Backend>) -> Vec<(Location, u64)> {
+        let mut files = Vec::new();
         for entry_res in backend.walk(root) {
             let entry = match entry_res {
                 Ok(e) => e,
@@ -311,19 +379,24 @@ impl Index {
                 );
                 continue;
             }
-            locs.push(entry.location);
+            files.push((entry.location, entry.size));
         }
+        files
+    }
 
+    // 并行 `Info::open` 并入索引；打开失败计入 `skipped_unreadable`。
+    fn open_all(&mut self, entries: Vec<(Location, Arc<dyn Backend>)>) {
         // 跑在 I/O 专用线程池（CPU × 4，clamp [8, 64]）：远端 backend 的
         // Info::open → metadata + open_read + fast_hash_stream 是同步阻塞 IO，
         // 走全局 rayon 池会让远端 RTT 占满 CPU 核数线程让后续 CPU-bound 阶段
         // 饿死。本地 backend 也受益（更高并发隐藏 stat 抖动）。
         let infos: Vec<_> = install_io(|| {
-            locs.par_iter()
-                .map(|loc| Info::open(loc, Arc::clone(backend)))
+            entries
+                .par_iter()
+                .map(|(loc, backend)| Info::open(loc, Arc::clone(backend)))
                 .collect()
         });
-        for (loc, result) in locs.iter().zip(infos) {
+        for ((loc, _), result) in entries.iter().zip(infos) {
             match result {
                 Ok(info) => _ = self.add(info),
                 Err(e) => {
@@ -371,6 +444,35 @@ impl Index {
             });
         });
     }
+
+    /// 按完整路径注入事件名；不在索引里的路径忽略。
+    pub fn set_events(&mut self, events: HashMap<Utf8PathBuf, String>) {
+        for (path, event) in events {
+            if let Some(info) = self.files.get_mut(&path) {
+                info.set_event(event);
+            }
+        }
+    }
+
+    /// 按完整路径注入已命名人物；不在索引里的路径忽略。
+    pub fn set_persons(&mut self, persons: HashMap<Utf8PathBuf, Vec<String>>) {
+        for (path, names) in persons {
+            if let Some(info) = self.files.get_mut(&path) {
+                info.set_persons(names);
+            }
+        }
+    }
+
+    /// 并行注入 sidecar 星级 / 色标；仅在过滤或模板用到时调用（每文件多一次 sidecar 读）。
+    pub fn enrich_tags(&mut self, provider: TagProvider) {
+        install_io(|| {
+            self.files.par_iter_mut().for_each(|(_, info)| {
+                if let Some(tags) = provider(info.location(), &info.backend()) {
+                    info.set_sidecar_tags(tags);
+                }
+            });
+        });
+    }
 }
 
 #[cfg(test)]
@@ -205,7 +205,8 @@ fn visit_stats_default_is_zero() {
         super::VisitStats {
             skipped_empty: 0,
             skipped_unreadable: 0,
-            walker_errors: 0
+            walker_errors: 0,
+            skipped_unique_size: 0
         }
     );
 }
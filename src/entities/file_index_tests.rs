@@ -398,3 +398,58 @@ fn calc_same_skips_files_with_calc_error_and_singletons() {
     // a 被 calc Err 过滤掉；b 剩单独一条，paths.len()==1，filter_and_sort 不保留
     assert!(same.is_empty());
 }
+
+// size 档：size 在全部 root 间唯一的文件不打开、不入索引，只计数；跨 root 的同 size
+// 文件照常入索引。bytes_read 只含冲突文件的首段读取。
+#[test]
+fn visit_size_collisions_skips_unique_sizes_without_reading() {
+    use std::sync::Arc;
+
+    use crate::adapters::backend::local::LocalBackend;
+    use crate::entities::backend::Backend;
+    use crate::entities::uri::Location;
+
+    let (left, right) = (tempdir().unwrap(), tempdir().unwrap());
+    fs::write(left.path().join("a.bin"), b"ten bytes!").unwrap();
+    fs::write(left.path().join("unique.bin"), b"xyz").unwrap();
+    fs::write(right.path().join("b.bin"), b"TEN BYTES?").unwrap();
+
+    let backend: Arc<dyn Backend> = LocalBackend::arc();
+    let roots: Vec<_> = [left.path(), right.path()]
+        .iter()
+        .map(|p| {
+            let root = file_info::full_path(p.to_str().unwrap()).unwrap();
+            (Location::Local(root), Arc::clone(&backend))
+        })
+        .collect();
+    let mut index = Index::new();
+    index.visit_size_collisions(&roots);
+
+    assert_eq!(index.files().len(), 2);
+    assert_eq!(index.stats().skipped_unique_size, 1);
+    assert_eq!(index.bytes_read(), 20);
+}
+
+// head/tail 档：首 4 KiB 相同、只差尾部的同 size 文件在整文件哈希前出局——
+// 每份只读首尾各 4 KiB，calc 从未被调用。
+#[test]
+fn calc_same_drops_tail_mismatch_before_full_hash() {
+    let dir = tempdir().unwrap();
+    let mut body = vec![0u8; 10_000];
+    let a_path = dir.path().join("a.bin");
+    fs::write(&a_path, &body).unwrap();
+    body[9_999] = 1;
+    let b_path = dir.path().join("b.bin");
+    fs::write(&b_path, &body).unwrap();
+
+    let mut index = Index::new();
+    index.insert(a_path.to_str().unwrap()).unwrap();
+    index.insert(b_path.to_str().unwrap()).unwrap();
+
+    let results = index.calc_same(|_: &Info| -> std::io::Result<u64> {
+        panic!("tail mismatch must not reach the full-hash tier")
+    });
+    assert!(results.iter().all(std::collections::HashMap::is_empty));
+    assert_eq!(index.bytes_read(), 2 * (4096 + 4096));
+    assert!(index.search_same().is_empty());
+}
@@ -176,3 +176,29 @@ fn secure_hash_stream_io_error_propagates() {
         io::ErrorKind::PermissionDenied
     );
 }
+
+// tail_hash：≤ 4 KiB 不读直接 0；否则只读末 4 KiB，首段不同不影响结果。
+#[test]
+fn info_tail_hash_reads_only_last_block() {
+    let dir = tempfile::tempdir().unwrap();
+    let small = dir.path().join("small.bin");
+    fs::write(&small, vec![7u8; 4096]).unwrap();
+    let info = super::Info::from(small.to_str().unwrap()).unwrap();
+    assert_eq!(info.tail_hash().unwrap(), 0);
+    assert_eq!(info.bytes_read(), 4096);
+
+    let mut body = vec![1u8; 9000];
+    let a = dir.path().join("a.bin");
+    fs::write(&a, &body).unwrap();
+    body[0] = 2;
+    let b = dir.path().join("b.bin");
+    fs::write(&b, &body).unwrap();
+    let (a, b) = (
+        super::Info::from(a.to_str().unwrap()).unwrap(),
+        super::Info::from(b.to_str().unwrap()).unwrap(),
+    );
+    assert_ne!(a.fast_hash, b.fast_hash);
+    assert_eq!(a.tail_hash().unwrap(), b.tail_hash().unwrap());
+    assert_eq!(a.tail_hash().unwrap(), xxh3::xxh3_64(&body[9000 - 4096..]));
+    assert_eq!(a.bytes_read(), 4096 * 3);
+}
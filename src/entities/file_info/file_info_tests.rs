@@ -276,6 +276,22 @@ fn create_time_uses_modify_when_smaller_than_create() {
     assert_eq!(secs, 631_152_000);
 }
 
+/// `{source_priority}` 口径：采纳 EXIF 记 P0，低于阈值回退 fs 时记 P4。
+#[test]
+fn create_time_with_priority_reports_source_level() {
+    let mut info = Info::from(common::DATA_SMALL).unwrap();
+    let exif =
+        super::super::exif::Exif::with_mime("image/png").with_date_time_original(1_700_000_000);
+    info.set_exif(exif);
+    let (_, priority) = info.create_time_with_priority(TEST_VALID_THRESHOLD_SECS, utc0());
+    assert_eq!(priority, super::super::media_time::Priority::P0);
+
+    let exif = super::super::exif::Exif::with_mime("image/png").with_date_time_original(100);
+    info.set_exif(exif);
+    let (_, priority) = info.create_time_with_priority(TEST_VALID_THRESHOLD_SECS, utc0());
+    assert_eq!(priority, super::super::media_time::Priority::P4);
+}
+
 #[test]
 fn is_media_false_when_no_exif() {
     let info = Info::from(common::DATA_SMALL).unwrap();
@@ -13,8 +13,11 @@ use chrono::Utc;
 use parking_lot::Mutex;
 use tracing::warn;
 
-use super::streams::{fast_hash_stream, full_hash_stream, secure_hash_stream};
+use super::streams::{
+    FAST_READ_SIZE, fast_hash_stream, full_hash_stream, secure_hash_stream, tail_hash_stream,
+};
 use crate::entities::backend::{Backend, EntryKind, Metadata as BackendMetadata};
+use crate::entities::tags::Tags;
 use crate::entities::uri::Location;
 use crate::entities::{SecureHash, exif, media_time};
 // 测试 helper `Info::from` 需要构造 LocalBackend instance。仅 #[cfg(test)] 下引用
@@ -59,6 +62,14 @@ pub struct Info {
     /// P3 候选（XMP / Takeout sidecar）：协议解析在 adapters 层，经
     /// [`Self::add_candidates`] 注入；entities 只消费转换好的 [`media_time::Candidate`]。
     extra_candidates: Vec<media_time::Candidate>,
+    /// 外部标记（`.xmp` sidecar 星级 / 色标、Photos 图库收藏 / 相册 / 原名），经
+    /// [`Self::set_sidecar_tags`] 注入；优先于内嵌 XMP。
+    sidecar_tags: Tags,
+    /// `{event}` 目录名：copy / rename 的事件聚类预扫经 [`Self::set_event`] 注入。
+    event: Option<String>,
+    /// `{person}` / `--person`：人脸库里该内容出现的已命名人物（主体在前），经
+    /// [`Self::set_persons`] 注入。
+    persons: Vec<String>,
     lazy: Mutex<Lazy>,
     meta: BackendMetadata,
 }
@@ -102,6 +113,9 @@ impl Info {
             backend,
             exif: None,
             extra_candidates: Vec::new(),
+            sidecar_tags: Tags::default(),
+            event: None,
+            persons: Vec::new(),
             lazy: Mutex::new(Lazy::new(bytes_read as u64, second_hash)),
             meta,
         })
@@ -116,6 +130,11 @@ impl Info {
         &self.location
     }
 
+    /// backend stat 所得的 mtime；后端不提供时为 None。
+    pub fn modified(&self) -> Option<SystemTime> {
+        self.meta.modified
+    }
+
     /// 返回打开 Info 时使用的 backend 句柄；caller 用来对相同文件再做 IO（如 `remove_file`）。
     pub fn backend(&self) -> Arc<dyn Backend> {
         Arc::clone(&self.backend)
@@ -136,6 +155,18 @@ impl Info {
         Ok(full)
     }
 
+    /// 末 4 KiB 的 xxh3：find 在整文件哈希前按 head/tail 分档的第二档。不超过 4 KiB
+    /// 的文件首段已覆盖全文，返回 0 不再读。不缓存——每个文件只在 `calc_same` 里用一次。
+    pub fn tail_hash(&self) -> io::Result<u64> {
+        if self.size <= FAST_READ_SIZE as u64 {
+            return Ok(0);
+        }
+        let mut reader = self.backend.open_read(&self.location)?;
+        let (bytes_read, tail) = tail_hash_stream(reader.as_mut(), self.size)?;
+        self.lazy.lock().bytes_read += bytes_read as u64;
+        Ok(tail)
+    }
+
     pub(super) fn full_hash(&self) -> u64 {
         self.lazy.lock().hash
     }
@@ -175,6 +206,41 @@ impl Info {
         self.extra_candidates.extend(candidates);
     }
 
+    /// 注入外部标记（adapters 层解析）；缺的星级 / 色标仍回退内嵌 XMP。
+    pub fn set_sidecar_tags(&mut self, tags: Tags) {
+        self.sidecar_tags = tags;
+    }
+
+    /// 注入事件名（usecases 层按时间 / GPS 聚类后给出）。
+    pub fn set_event(&mut self, event: String) {
+        self.event = Some(event);
+    }
+
+    /// 事件名；未经聚类预扫时为 None。
+    pub fn event(&self) -> Option<&str> {
+        self.event.as_deref()
+    }
+
+    /// 注入已命名人物（usecases 层查人脸库给出，主体在前）。
+    pub fn set_persons(&mut self, persons: Vec<String>) {
+        self.persons = persons;
+    }
+
+    /// 已命名人物；未查人脸库或无已命名的脸时为空。
+    pub fn persons(&self) -> &[String] {
+        &self.persons
+    }
+
+    /// 整理标记：外部注入优先，缺的星级 / 色标回退图片内嵌 XMP（`parse_exif` 之后才有值）。
+    pub fn tags(&self) -> Tags {
+        let embedded = self.exif.as_ref().map_or_else(Tags::default, |e| Tags {
+            rating: e.rating(),
+            label: e.label().map(str::to_string),
+            ..Tags::default()
+        });
+        self.sidecar_tags.clone().or(embedded)
+    }
+
     /// 把当前 Info 的 hash / size / EXIF / 候选状态复制到新 location + backend。
     /// 用于 copy/move 完成后向 `output_index` 注册 dst 副本——dst 内容与 src 字节
     /// 等同，hash 直接复用避免对 dst 重新 stat + 读 4 KiB，也消除 `Info::open(dst)`
@@ -194,6 +260,9 @@ impl Info {
             backend: new_backend,
             exif: self.exif.clone(),
             extra_candidates: self.extra_candidates.clone(),
+            sidecar_tags: self.sidecar_tags.clone(),
+            event: self.event.clone(),
+            persons: self.persons.clone(),
             lazy: Mutex::new(lazy_snapshot),
             meta: self.meta.clone(),
         }
@@ -211,6 +280,29 @@ impl Info {
         valid_threshold_secs: u64,
         default_offset: FixedOffset,
     ) -> SystemTime {
+        self.create_time_with_priority(valid_threshold_secs, default_offset)
+            .0
+    }
+
+    /// 同 [`Self::create_time`]，另返采纳时间的来源等级；fs 兜底记 P4。
+    /// 归档模板 `{source_priority}` 据此区分"可信时间"与"mtime 猜测"。
+    pub fn create_time_with_priority(
+        &self,
+        valid_threshold_secs: u64,
+        default_offset: FixedOffset,
+    ) -> (SystemTime, media_time::Priority) {
+        let (time, priority, _) =
+            self.create_time_with_confidence(valid_threshold_secs, default_offset);
+        (time, priority)
+    }
+
+    /// 同 [`Self::create_time_with_priority`]，另返判定置信度；fs 兜底记 Low。
+    /// `stats` 按此统计库内有多少时间是"猜的"。
+    pub fn create_time_with_confidence(
+        &self,
+        valid_threshold_secs: u64,
+        default_offset: FixedOffset,
+    ) -> (SystemTime, media_time::Priority, media_time::Confidence) {
         let modified = self.meta.modified;
         let created = self.meta.created;
         let fs_fallback = pick_fs_fallback(modified, created);
@@ -231,19 +323,7 @@ impl Info {
             .filter(|&s| s > 0)
             .and_then(|s| i64::try_from(s).ok())
             .and_then(|s| Utc.timestamp_opt(s, 0).single());
-        let mut candidates = match self.exif.as_ref() {
-            Some(exif) => media_time::candidates_from_exif(exif, default_offset),
-            None => Vec::new(),
-        };
-        // P2：文件名启发式（IMG_/DSC_/Screenshot_/毫秒戳等）。
-        candidates.extend(media_time::candidates_from_filename(
-            Utf8Path::new(self.full_path.as_str()),
-            default_offset,
-        ));
-        // P3：adapters 层发现并注入的 sidecar 候选（XMP / Google Takeout）。
-        candidates.extend(self.extra_candidates.iter().copied());
-        // P4。Option<Candidate> 实现 IntoIterator → extend 不引入 if-let 分支。
-        candidates.extend(media_time::fs_time::from_modified(modified));
+        let candidates = self.time_candidates(default_offset);
 
         // resolve 返回 None（候选全部被过滤）与"低于阈值"走同一条 fallback 路径，
         // 避免在 create_time 里多一条不可稳定触发的分支。
@@ -260,14 +340,44 @@ impl Info {
                 "media time candidates conflict"
             );
         }
-        let secs = decision.map_or(0, |d| d.utc.timestamp());
+        let (secs, priority, confidence) = decision.map_or(
+            (0, media_time::Priority::P4, media_time::Confidence::Low),
+            |d| (d.utc.timestamp(), d.priority, d.confidence),
+        );
         if secs > 0 && secs.cast_unsigned() >= valid_threshold_secs {
-            SystemTime::UNIX_EPOCH + Duration::from_secs(secs.cast_unsigned())
+            (
+                SystemTime::UNIX_EPOCH + Duration::from_secs(secs.cast_unsigned()),
+                priority,
+                confidence,
+            )
         } else {
-            fs_fallback
+            (
+                fs_fallback,
+                media_time::Priority::P4,
+                media_time::Confidence::Low,
+            )
         }
     }
 
+    /// 参与时间判定的全部 P0..P4 候选（未经 resolve 过滤）。`audit` 据此发现被
+    /// resolve 剔除的未来时间、相机默认日期等"元数据本身有问题"的文件。
+    pub fn time_candidates(&self, default_offset: FixedOffset) -> Vec<media_time::Candidate> {
+        let mut candidates = match self.exif.as_ref() {
+            Some(exif) => media_time::candidates_from_exif(exif, default_offset),
+            None => Vec::new(),
+        };
+        // P2：文件名启发式（IMG_/DSC_/Screenshot_/毫秒戳等）。
+        candidates.extend(media_time::candidates_from_filename(
+            Utf8Path::new(self.full_path.as_str()),
+            default_offset,
+        ));
+        // P3：adapters 层发现并注入的 sidecar 候选（XMP / Google Takeout）。
+        candidates.extend(self.extra_candidates.iter().copied());
+        // P4。Option<Candidate> 实现 IntoIterator → extend 不引入 if-let 分支。
+        candidates.extend(media_time::fs_time::from_modified(self.meta.modified));
+        candidates
+    }
+
     pub fn is_media(&self) -> bool {
         self.exif.as_ref().is_some_and(exif::Exif::is_media)
     }
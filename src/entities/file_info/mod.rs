@@ -7,7 +7,7 @@ pub(super) mod streams;
 
 pub use self::info::Info;
 pub use self::paths::full_path;
-pub(crate) use self::streams::read_fill;
+pub(crate) use self::streams::{full_hash_stream, read_fill};
 
 // 测试经 `super::X` 访问的内部项（私有 use 对子模块可见，生产侧不暴露）。
 #[cfg(test)]
@@ -16,8 +16,7 @@ use self::info::pick_fs_fallback;
 use self::paths::strip_windows_unc;
 #[cfg(test)]
 use self::streams::{
-    FAST_READ_SIZE, fast_hash, fast_hash_stream, full_hash, full_hash_stream, secure_hash,
-    secure_hash_stream,
+    FAST_READ_SIZE, fast_hash, fast_hash_stream, full_hash, secure_hash, secure_hash_stream,
 };
 #[cfg(test)]
 use crate::entities::SecureHash;
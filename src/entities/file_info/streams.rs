@@ -1,6 +1,8 @@
-//! 流式哈希：fast(前 4 KiB 双哈希) / full(xxh3) / secure(SHA-512) 与 `read_fill` 复用。
+//! 流式哈希：fast(前 4 KiB 双哈希) / tail(末 4 KiB) / full(xxh3) / secure(SHA-512) 与
+//! `read_fill` 复用。
 
 use std::io;
+use std::io::{Seek, SeekFrom};
 
 use sha2::Digest;
 use sha2::Sha512;
@@ -28,6 +30,16 @@ pub fn fast_hash_stream(r: &mut dyn MediaReader) -> io::Result<(usize, u64, u64)
     ))
 }
 
+/// seek 到末尾 [`FAST_READ_SIZE`] 字节起点（`size` 为 stat 所得长度）算 xxh3。
+///
+/// 返回 (`bytes_read`, xxh3)。
+pub fn tail_hash_stream(r: &mut dyn MediaReader, size: u64) -> io::Result<(usize, u64)> {
+    r.seek(SeekFrom::Start(size.saturating_sub(FAST_READ_SIZE as u64)))?;
+    let mut buffer = [0u8; FAST_READ_SIZE];
+    let n = read_fill(r, &mut buffer)?;
+    Ok((n, xxhash_rust::xxh3::xxh3_64(&buffer[..n])))
+}
+
 /// 流式整文件 xxh3-64 哈希。返回 (`bytes_read`, xxh3-64)。
 /// 调用方须保证 reader 已 seek 到起点。
 pub fn full_hash_stream(r: &mut dyn MediaReader) -> io::Result<(u64, u64)> {
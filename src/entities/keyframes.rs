@@ -0,0 +1,336 @@
+//! 视频帧抽取（近似重复视频指纹用）：纯 Rust 解析容器，只取无需视频解码器、
+//! 本身就是完整静态图片的帧。
+//!
+//! 支持：
+//! - QuickTime / MP4 中 JPEG 编码的视频轨（`jpeg` / `mjpa` / `AVDJ` / `dmb1`，老式
+//!   相机与部分行车记录仪的 MJPEG MOV）：经 `stsz` / `stsc` / `stco`|`co64` 定位样本
+//! - RIFF AVI 的 MJPEG 流（`movi` 下 `##dc` / `##db` chunk，数据以 JPEG SOI 起头）
+//! - 以上都不满足时，QuickTime / MP4 的封面缩略图（`moov > udta > meta > ilst > covr`）
+//!   作单帧兜底
+//!
+//! H.264 / HEVC 等帧间编码没有可用的纯 Rust 解码器，一律返回 None，由调用方计为
+//! 不支持。`moov` / `hdrl` 整段入内存（有上限），`mdat` / `movi` 只按需 seek 读选中的帧。
+
+use std::io::{Read, Seek, SeekFrom};
+
+use super::backend::MediaReader;
+
+const JPEG_FOURCCS: [&[u8; 4]; 4] = [b"jpeg", b"mjpa", b"AVDJ", b"dmb1"];
+const JPEG_SOI: [u8; 2] = [0xFF, 0xD8];
+
+/// moov 只含索引（长视频也仅数 MiB）；cap 防损坏 size 字段吃内存。
+const MAX_MOOV_BYTES: u64 = 64 << 20;
+/// 同 riff.rs：hdrl 仅含头信息。
+const MAX_HDRL_BYTES: u64 = 1 << 20;
+/// 单帧 JPEG 上限；超出视为损坏。
+const MAX_FRAME_BYTES: u64 = 16 << 20;
+/// moov / movi 之前最多容忍的顶层 box / chunk 数。
+const MAX_TOP_BOXES: usize = 64;
+/// 样本表展开上限（约 4 小时 @ 240 fps）。
+const MAX_SAMPLES: usize = 1 << 22;
+
+/// 抽出的帧与容器声明的时长。
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct Keyframes {
+    /// 容器声明的时长（毫秒）；缺失为 0。
+    pub duration_ms: u64,
+    /// 按时间均匀抽取的帧，每帧是一张完整的 JPEG / PNG。
+    pub frames: Vec<Vec<u8>>,
+}
+
+/// 从视频 reader 中按时间均匀抽取至多 `max_frames` 帧。
+/// 非 MP4 / AVI、编码不受支持或结构损坏一律返回 None。
+pub(crate) fn extract_keyframes(r: &mut dyn MediaReader, max_frames: usize) -> Option<Keyframes> {
+    if max_frames == 0 {
+        return None;
+    }
+    let mut head = [0u8; 12];
+    r.seek(SeekFrom::Start(0)).ok()?;
+    r.read_exact(&mut head).ok()?;
+    if &head[..4] == b"RIFF" && &head[8..] == b"AVI " {
+        avi_keyframes(r, max_frames)
+    } else {
+        mp4_keyframes(r, max_frames)
+    }
+}
+
+fn mp4_keyframes(r: &mut dyn MediaReader, max_frames: usize) -> Option<Keyframes> {
+    let moov = read_top_box(r, b"moov")?;
+    let duration_ms = movie_duration_ms(&moov).unwrap_or(0);
+    let frames = match jpeg_track_samples(&moov) {
+        Some(samples) if !samples.is_empty() => {
+            read_samples(r, &pick_evenly(&samples, max_frames))?
+        }
+        _ => vec![cover_art(&moov)?.to_vec()],
+    };
+    Some(Keyframes {
+        duration_ms,
+        frames,
+    })
+}
+
+// 顶层逐个 box 跳读，命中 `kind` 即整段读入；mdat（可达数 GiB）只 seek 跳过。
+fn read_top_box(r: &mut dyn MediaReader, kind: &[u8; 4]) -> Option<Vec<u8>> {
+    let mut pos = 0u64;
+    for _ in 0..MAX_TOP_BOXES {
+        r.seek(SeekFrom::Start(pos)).ok()?;
+        let mut hdr = [0u8; 8];
+        r.read_exact(&mut hdr).ok()?;
+        let (header, size) = match u32::from_be_bytes([hdr[0], hdr[1], hdr[2], hdr[3]]) {
+            1 => {
+                let mut large = [0u8; 8];
+                r.read_exact(&mut large).ok()?;
+                (16, u64::from_be_bytes(large))
+            }
+            // size 0 = 延伸到文件尾，只可能是最后一个 box。
+            0 if &hdr[4..] == kind => (8, 8 + MAX_MOOV_BYTES),
+            0 => return None,
+            n => (8, u64::from(n)),
+        };
+        if size < header {
+            return None;
+        }
+        if &hdr[4..] == kind {
+            let body = size - header;
+            if body > MAX_MOOV_BYTES {
+                return None;
+            }
+            let mut buf = Vec::new();
+            r.take(body).read_to_end(&mut buf).ok()?;
+            return Some(buf);
+        }
+        pos = pos.checked_add(size)?;
+    }
+    None
+}
+
+// 内存中逐个子 box：(类型, 负载)。越界 / 损坏即结束迭代。
+fn boxes(mut buf: &[u8]) -> impl Iterator<Item = (&[u8], &[u8])> {
+    std::iter::from_fn(move || {
+        let size = u64::from(be_u32(buf, 0)?);
+        let kind = buf.get(4..8)?;
+        let (header, size) = match size {
+            1 => (16, be_u64(buf, 8)?),
+            0 => (8, buf.len() as u64),
+            n => (8, n),
+        };
+        let size = usize::try_from(size).ok()?;
+        let payload = buf.get(header..size)?;
+        buf = &buf[size..];
+        Some((kind, payload))
+    })
+}
+
+fn child<'a>(buf: &'a [u8], kind: &[u8; 4]) -> Option<&'a [u8]> {
+    boxes(buf)
+        .find(|(k, _)| *k == kind)
+        .map(|(_, payload)| payload)
+}
+
+fn descend<'a>(buf: &'a [u8], path: &[&[u8; 4]]) -> Option<&'a [u8]> {
+    path.iter().try_fold(buf, |b, kind| child(b, kind))
+}
+
+// mvhd v0：version/flags(4) ctime(4) mtime(4) timescale(4) duration(4)；
+// v1 的 ctime / mtime / duration 为 8 字节。
+fn movie_duration_ms(moov: &[u8]) -> Option<u64> {
+    let mvhd = child(moov, b"mvhd")?;
+    let (timescale, duration) = if *mvhd.first()? == 1 {
+        (be_u32(mvhd, 20)?, be_u64(mvhd, 24)?)
+    } else {
+        (be_u32(mvhd, 12)?, u64::from(be_u32(mvhd, 16)?))
+    };
+    (timescale > 0).then(|| duration.saturating_mul(1000) / u64::from(timescale))
+}
+
+// 首个 JPEG 编码视频轨的样本表，(文件偏移, 字节数)；帧间编码的轨跳过。
+fn jpeg_track_samples(moov: &[u8]) -> Option<Vec<(u64, u64)>> {
+    boxes(moov)
+        .filter(|(kind, _)| *kind == b"trak")
+        .find_map(|(_, trak)| {
+            let mdia = child(trak, b"mdia")?;
+            // hdlr：version/flags(4) pre_defined(4) handler_type(4)
+            if child(mdia, b"hdlr")?.get(8..12)? != b"vide" {
+                return None;
+            }
+            let stbl = descend(mdia, &[b"minf", b"stbl"])?;
+            // stsd：version/flags(4) entry_count(4) 首条 entry 的 size(4) format(4)
+            let format = child(stbl, b"stsd")?.get(12..16)?;
+            if !JPEG_FOURCCS.iter().any(|f| f.as_slice() == format) {
+                return None;
+            }
+            sample_table(stbl)
+        })
+}
+
+// stsz（逐样本大小）+ stco/co64（chunk 偏移）+ stsc（chunk → 样本数游程）展开成
+// 每个样本的 (偏移, 大小)。
+fn sample_table(stbl: &[u8]) -> Option<Vec<(u64, u64)>> {
+    let stsz = child(stbl, b"stsz")?;
+    let fixed = be_u32(stsz, 4)?;
+    let count = (be_u32(stsz, 8)? as usize).min(MAX_SAMPLES);
+    let sizes: Vec<u64> = (0..count)
+        .map(|i| match fixed {
+            0 => be_u32(stsz, 12 + 4 * i).map(u64::from),
+            n => Some(u64::from(n)),
+        })
+        .collect::<Option<_>>()?;
+    let chunk_offsets: Vec<u64> = if let Some(stco) = child(stbl, b"stco") {
+        (0..be_u32(stco, 4)? as usize)
+            .map(|i| be_u32(stco, 8 + 4 * i).map(u64::from))
+            .collect::<Option<_>>()?
+    } else {
+        let co64 = child(stbl, b"co64")?;
+        (0..be_u32(co64, 4)? as usize)
+            .map(|i| be_u64(co64, 8 + 8 * i))
+            .collect::<Option<_>>()?
+    };
+    let stsc = child(stbl, b"stsc")?;
+    // 每条游程：first_chunk(4, 1 起) samples_per_chunk(4) sample_description_index(4)
+    let runs: Vec<(u32, u32)> = (0..be_u32(stsc, 4)? as usize)
+        .map(|i| Some((be_u32(stsc, 8 + 12 * i)?, be_u32(stsc, 12 + 12 * i)?)))
+        .collect::<Option<_>>()?;
+
+    let mut samples = Vec::with_capacity(sizes.len());
+    let mut sizes = sizes.into_iter();
+    for (index, &chunk_offset) in chunk_offsets.iter().enumerate() {
+        let chunk_no = u32::try_from(index + 1).ok()?;
+        let per_chunk = runs
+            .iter()
+            .take_while(|(first, _)| *first <= chunk_no)
+            .last()?
+            .1;
+        let mut offset = chunk_offset;
+        for _ in 0..per_chunk {
+            let Some(size) = sizes.next() else {
+                return Some(samples);
+            };
+            samples.push((offset, size));
+            offset = offset.checked_add(size)?;
+        }
+    }
+    Some(samples)
+}
+
+// ISO 的 meta 是 full box（多 4 字节 version/flags），QuickTime 的不是：看首个子 box
+// 是否为 hdlr 决定是否跳过。covr > data：type(4) locale(4) 之后即图片字节。
+fn cover_art(moov: &[u8]) -> Option<&[u8]> {
+    let meta = descend(moov, &[b"udta", b"meta"])?;
+    let meta = if meta.get(4..8) == Some(b"hdlr".as_slice()) {
+        meta
+    } else {
+        meta.get(4..)?
+    };
+    descend(meta, &[b"ilst", b"covr", b"data"])?
+        .get(8..)
+        .filter(|img| !img.is_empty())
+}
+
+// RIFF 顶层：hdrl 取时长，movi 收集视频帧 chunk 位置后均匀抽取；抽中的帧
+// 必须都是 JPEG（DivX / Xvid 等帧间编码的 AVI 在此被拒）。
+fn avi_keyframes(r: &mut dyn MediaReader, max_frames: usize) -> Option<Keyframes> {
+    let mut pos = 12u64;
+    let mut duration_ms = 0;
+    for _ in 0..MAX_TOP_BOXES {
+        let (id, size) = riff_chunk_header(r, pos)?;
+        let size = u64::from(size);
+        if &id == b"LIST" && size >= 4 {
+            let mut list_type = [0u8; 4];
+            r.read_exact(&mut list_type).ok()?;
+            if &list_type == b"hdrl" {
+                let hdrl = read_at(r, pos + 12, (size - 4).min(MAX_HDRL_BYTES))?;
+                duration_ms = avi_duration_ms(&hdrl).unwrap_or(0);
+            } else if &list_type == b"movi" {
+                let chunks = movi_frame_chunks(r, pos + 12, pos + 8 + size)?;
+                let frames = read_samples(r, &pick_evenly(&chunks, max_frames))?;
+                if frames.is_empty() || !frames.iter().all(|f| f.starts_with(&JPEG_SOI)) {
+                    return None;
+                }
+                return Some(Keyframes {
+                    duration_ms,
+                    frames,
+                });
+            }
+        }
+        pos += 8 + size + (size & 1);
+    }
+    None
+}
+
+fn riff_chunk_header(r: &mut dyn MediaReader, pos: u64) -> Option<([u8; 4], u32)> {
+    r.seek(SeekFrom::Start(pos)).ok()?;
+    let mut hdr = [0u8; 8];
+    r.read_exact(&mut hdr).ok()?;
+    Some((
+        [hdr[0], hdr[1], hdr[2], hdr[3]],
+        u32::from_le_bytes([hdr[4], hdr[5], hdr[6], hdr[7]]),
+    ))
+}
+
+// hdrl 首个子 chunk 即 avih：dwMicroSecPerFrame @0、dwTotalFrames @16。
+fn avi_duration_ms(hdrl: &[u8]) -> Option<u64> {
+    if hdrl.get(..4)? != b"avih" {
+        return None;
+    }
+    let avih = hdrl.get(8..)?;
+    let us_per_frame = u64::from(le_u32(avih, 0)?);
+    let total_frames = u64::from(le_u32(avih, 16)?);
+    Some(us_per_frame.saturating_mul(total_frames) / 1000)
+}
+
+// movi 内视频帧 chunk（`##dc` 压缩 / `##db` 未压缩）的 (数据偏移, 大小)；只读 chunk 头。
+fn movi_frame_chunks(r: &mut dyn MediaReader, start: u64, end: u64) -> Option<Vec<(u64, u64)>> {
+    let mut chunks = Vec::new();
+    let mut pos = start;
+    while pos + 8 <= end && chunks.len() < MAX_SAMPLES {
+        let (id, size) = riff_chunk_header(r, pos)?;
+        let size = u64::from(size);
+        if &id[2..] == b"dc" || &id[2..] == b"db" {
+            chunks.push((pos + 8, size));
+        }
+        pos += 8 + size + (size & 1);
+    }
+    Some(chunks)
+}
+
+// 取各等分区间的中点：头尾黑场 / 片头字幕不占满样本。
+fn pick_evenly<T: Copy>(items: &[T], k: usize) -> Vec<T> {
+    let n = items.len();
+    let k = k.min(n);
+    (0..k).map(|i| items[(2 * i + 1) * n / (2 * k)]).collect()
+}
+
+fn read_samples(r: &mut dyn MediaReader, picks: &[(u64, u64)]) -> Option<Vec<Vec<u8>>> {
+    picks
+        .iter()
+        .map(|&(offset, size)| {
+            if size > MAX_FRAME_BYTES {
+                return None;
+            }
+            read_at(r, offset, size)
+        })
+        .collect()
+}
+
+fn read_at(r: &mut dyn MediaReader, offset: u64, len: u64) -> Option<Vec<u8>> {
+    r.seek(SeekFrom::Start(offset)).ok()?;
+    let mut buf = vec![0u8; usize::try_from(len).ok()?];
+    r.read_exact(&mut buf).ok()?;
+    Some(buf)
+}
+
+fn be_u32(b: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(b.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn be_u64(b: &[u8], at: usize) -> Option<u64> {
+    Some(u64::from_be_bytes(b.get(at..at + 8)?.try_into().ok()?))
+}
+
+fn le_u32(b: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(b.get(at..at + 4)?.try_into().ok()?))
+}
+
+#[cfg(test)]
+#[path = "keyframes_tests.rs"]
+mod tests;
@@ -0,0 +1,195 @@
+use std::io::Cursor;
+
+use image::{ImageFormat, Rgb, RgbImage};
+
+use super::*;
+
+fn jpeg(shade: u8) -> Vec<u8> {
+    let mut out = Cursor::new(Vec::new());
+    RgbImage::from_pixel(16, 16, Rgb([shade, 0, 0]))
+        .write_to(&mut out, ImageFormat::Jpeg)
+        .unwrap();
+    out.into_inner()
+}
+
+fn frames(n: u8) -> Vec<Vec<u8>> {
+    (0..n).map(|i| jpeg(i * 40)).collect()
+}
+
+fn extract(bytes: Vec<u8>, max_frames: usize) -> Option<Keyframes> {
+    extract_keyframes(&mut Cursor::new(bytes), max_frames)
+}
+
+// ── MP4 / QuickTime 构造 ──
+
+fn bx(kind: &[u8; 4], payload: &[u8]) -> Vec<u8> {
+    let mut v = u32::try_from(payload.len() + 8)
+        .unwrap()
+        .to_be_bytes()
+        .to_vec();
+    v.extend_from_slice(kind);
+    v.extend_from_slice(payload);
+    v
+}
+
+// full box：负载前 4 字节 version/flags。
+fn full(kind: &[u8; 4], fields: &[u32]) -> Vec<u8> {
+    let mut payload = vec![0u8; 4];
+    for f in fields {
+        payload.extend_from_slice(&f.to_be_bytes());
+    }
+    bx(kind, &payload)
+}
+
+fn mvhd_ms(duration_ms: u32) -> Vec<u8> {
+    full(b"mvhd", &[0, 0, 1000, duration_ms])
+}
+
+fn video_trak(format: &[u8; 4], sizes: &[u32], samples_per_chunk: u32, chunks: &[u32]) -> Vec<u8> {
+    let mut hdlr = vec![0u8; 8];
+    hdlr.extend_from_slice(b"vide");
+    hdlr.extend_from_slice(&[0u8; 13]);
+    let mut stsd = vec![0u8; 4];
+    stsd.extend_from_slice(&1u32.to_be_bytes());
+    stsd.extend_from_slice(&16u32.to_be_bytes());
+    stsd.extend_from_slice(format);
+    stsd.extend_from_slice(&[0u8; 8]);
+    let mut stsz = vec![0, u32::try_from(sizes.len()).unwrap()];
+    stsz.extend_from_slice(sizes);
+    let mut stco = vec![u32::try_from(chunks.len()).unwrap()];
+    stco.extend_from_slice(chunks);
+    let stbl = [
+        bx(b"stsd", &stsd),
+        full(b"stsz", &stsz),
+        full(b"stsc", &[1, 1, samples_per_chunk, 1]),
+        full(b"stco", &stco),
+    ]
+    .concat();
+    bx(
+        b"trak",
+        &bx(
+            b"mdia",
+            &[bx(b"hdlr", &hdlr), bx(b"minf", &bx(b"stbl", &stbl))].concat(),
+        ),
+    )
+}
+
+/// ftyp + mdat(全部帧) + moov；`moov_extra` 追加进 moov（如 udta 封面）。
+fn mp4(format: &[u8; 4], frames: &[Vec<u8>], per_chunk: usize, moov_extra: &[u8]) -> Vec<u8> {
+    let ftyp = bx(b"ftyp", b"qt  \0\0\0\0");
+    let data_start = u32::try_from(ftyp.len() + 8).unwrap();
+    let sizes: Vec<u32> = frames
+        .iter()
+        .map(|f| u32::try_from(f.len()).unwrap())
+        .collect();
+    let mut chunks = Vec::new();
+    let mut offset = data_start;
+    for group in sizes.chunks(per_chunk) {
+        chunks.push(offset);
+        offset += group.iter().sum::<u32>();
+    }
+    let trak = video_trak(format, &sizes, u32::try_from(per_chunk).unwrap(), &chunks);
+    let moov = bx(
+        b"moov",
+        &[mvhd_ms(12_000), trak, moov_extra.to_vec()].concat(),
+    );
+    [ftyp, bx(b"mdat", &frames.concat()), moov].concat()
+}
+
+fn cover_udta(image: &[u8]) -> Vec<u8> {
+    let mut data = vec![0, 0, 0, 13, 0, 0, 0, 0];
+    data.extend_from_slice(image);
+    let ilst = bx(b"ilst", &bx(b"covr", &bx(b"data", &data)));
+    let mut meta = vec![0u8; 4];
+    meta.extend_from_slice(&bx(b"hdlr", &[0u8; 25]));
+    meta.extend_from_slice(&ilst);
+    bx(b"udta", &bx(b"meta", &meta))
+}
+
+/// MJPEG MOV：跨 chunk 的样本表展开正确，按等分区间中点抽帧，时长取自 mvhd。
+#[test]
+fn mjpeg_mov_samples_evenly_across_chunks() {
+    let all = frames(6);
+    let got = extract(mp4(b"jpeg", &all, 4, &[]), 3).unwrap();
+    assert_eq!(got.duration_ms, 12_000);
+    assert_eq!(
+        got.frames,
+        vec![all[1].clone(), all[3].clone(), all[5].clone()]
+    );
+
+    // 帧数不足时全取。
+    let got = extract(mp4(b"mjpa", &all[..2], 1, &[]), 8).unwrap();
+    assert_eq!(got.frames, all[..2].to_vec());
+}
+
+/// H.264 轨不可解码：无封面 → None；有 covr 封面 → 单帧兜底。
+#[test]
+fn inter_frame_codec_falls_back_to_cover_art() {
+    let all = frames(3);
+    assert_eq!(extract(mp4(b"avc1", &all, 3, &[]), 4), None);
+
+    let cover = jpeg(200);
+    let got = extract(mp4(b"avc1", &all, 3, &cover_udta(&cover)), 4).unwrap();
+    assert_eq!(got.frames, vec![cover]);
+    assert_eq!(got.duration_ms, 12_000);
+}
+
+// ── RIFF AVI 构造 ──
+
+fn chunk(id: &[u8; 4], data: &[u8]) -> Vec<u8> {
+    let mut v = id.to_vec();
+    v.extend_from_slice(&u32::try_from(data.len()).unwrap().to_le_bytes());
+    v.extend_from_slice(data);
+    if data.len() % 2 == 1 {
+        v.push(0);
+    }
+    v
+}
+
+fn list(kind: &[u8; 4], children: &[u8]) -> Vec<u8> {
+    chunk(b"LIST", &[kind.as_slice(), children].concat())
+}
+
+fn avi(frames: &[Vec<u8>], us_per_frame: u32) -> Vec<u8> {
+    let mut avih = vec![0u8; 56];
+    avih[..4].copy_from_slice(&us_per_frame.to_le_bytes());
+    avih[16..20].copy_from_slice(&u32::try_from(frames.len()).unwrap().to_le_bytes());
+    let hdrl = list(b"hdrl", &chunk(b"avih", &avih));
+    let mut movi = Vec::new();
+    for f in frames {
+        movi.extend_from_slice(&chunk(b"00dc", f));
+        movi.extend_from_slice(&chunk(b"01wb", &[1, 2, 3]));
+    }
+    let body = [b"AVI ".to_vec(), hdrl, list(b"movi", &movi)].concat();
+    [
+        b"RIFF".to_vec(),
+        u32::try_from(body.len()).unwrap().to_le_bytes().to_vec(),
+        body,
+    ]
+    .concat()
+}
+
+/// MJPEG AVI：跳过音频 chunk 与奇数长度补位，时长 = 每帧微秒 × 总帧数。
+#[test]
+fn mjpeg_avi_picks_video_chunks_and_reads_duration() {
+    let all = frames(5);
+    let got = extract(avi(&all, 40_000), 2).unwrap();
+    assert_eq!(got.duration_ms, 200);
+    assert_eq!(got.frames, vec![all[1].clone(), all[3].clone()]);
+}
+
+/// 非 JPEG 帧（Xvid 等）的 AVI、垃圾数据、max_frames = 0 均返回 None。
+#[test]
+fn unsupported_or_corrupt_input_returns_none() {
+    let xvid = vec![vec![0u8, 0, 1, 0xB6, 9, 9]; 3];
+    assert_eq!(extract(avi(&xvid, 40_000), 2), None);
+    assert_eq!(extract(b"not a video at all".to_vec(), 4), None);
+    assert_eq!(extract(Vec::new(), 4), None);
+    assert_eq!(extract(mp4(b"jpeg", &frames(2), 2, &[]), 0), None);
+
+    // 顶层 box 链损坏（mdat 头丢失）→ None，不 panic。
+    let mut truncated = mp4(b"jpeg", &frames(2), 2, &[]);
+    let ftyp_len = 16;
+    truncated.drain(ftyp_len..ftyp_len + 8);
+    assert_eq!(extract(truncated, 2), None);
+}
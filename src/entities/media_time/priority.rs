@@ -10,6 +10,20 @@ pub enum Priority {
     P4,
 }
 
+impl Priority {
+    /// 稳定短名（`P0`…`P4`），供归档模板 `{source_priority}` 使用。
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Priority::P0 => "P0",
+            Priority::P1 => "P1",
+            Priority::P2 => "P2",
+            Priority::P3 => "P3",
+            Priority::P4 => "P4",
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Source {
     // P0 — 容器内"拍摄时刻"
@@ -20,6 +34,9 @@ pub enum Source {
     /// `PID_CREATE_DTM` / iWork plist `createdDate` / `.mm` CREATED 等），由
     /// `entities::office` 子模块归一为 Unix UTC epoch。
     DocumentCreated,
+    /// Apple Photos 图库数据库 `ZASSET.ZDATECREATED`：Photos 导入时已按 EXIF/QuickTime
+    /// 归一且保留用户在 Photos 内的时间校正，由 `adapters::photos_library` 注入。
+    PhotosLibrary,
     // P1 — 容器内"数字化/写入"
     ExifCreateDate,
     QuickTimeCreateDate,
@@ -49,7 +66,8 @@ impl Source {
             Source::ExifDateTimeOriginal
             | Source::QuickTimeCreationDate
             | Source::MkvDateUtc
-            | Source::DocumentCreated => Priority::P0,
+            | Source::DocumentCreated
+            | Source::PhotosLibrary => Priority::P0,
             Source::ExifCreateDate | Source::QuickTimeCreateDate => Priority::P1,
             Source::FilenameCamera
             | Source::FilenamePhone
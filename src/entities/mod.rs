@@ -12,11 +12,13 @@ pub mod common;
 pub(crate) mod exif;
 pub mod file_index;
 pub mod file_info;
+pub(crate) mod keyframes;
 pub(crate) mod m2ts;
 pub mod media_time;
 pub(crate) mod office;
 pub(crate) mod png;
 pub(crate) mod riff;
+pub mod tags;
 #[cfg(test)]
 pub(crate) mod test_common;
 pub(crate) mod threadpool;
@@ -0,0 +1,67 @@
+//! 星级 / 色标：Lightroom / Bridge / darktable 写入 XMP 的 `xmp:Rating` / `xmp:Label`；
+//! 收藏 / 相册 / 导入前原名：Apple Photos 图库数据库。
+//!
+//! 三个来源：图片内嵌 packet（`exif` 解析时顺带取，见 `Exif::rating`）、
+//! `<media>.xmp` sidecar 与 Photos 图库（后两者由 adapters 层读取后经
+//! `Info::set_sidecar_tags` 注入）。sidecar 优先：RAW 工作流里 Lightroom 只回写
+//! sidecar，内嵌值是导入时的快照。
+
+use crate::entities::xmp;
+
+/// 一个文件的用户整理标记。`rating` 为 -1（拒绝）..=5，未评级为 None。
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Tags {
+    pub rating: Option<i8>,
+    pub label: Option<String>,
+    /// Photos 中标为收藏。
+    pub favorite: bool,
+    /// 所属 Photos 相册（多个时取标题排序后的第一个）。
+    pub album: Option<String>,
+    /// 导入 Photos 前的文件名（不含扩展名）；图库内原图已改名为 `<uuid>.<ext>`。
+    pub original_name: Option<String>,
+}
+
+impl Tags {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rating.is_none()
+            && self.label.is_none()
+            && !self.favorite
+            && self.album.is_none()
+            && self.original_name.is_none()
+    }
+
+    /// 从 XMP 文本（sidecar 全文或内嵌 packet）取星级与色标。
+    #[must_use]
+    pub fn from_xmp(content: &str) -> Self {
+        let meta = xmp::parse_xmp(content, None);
+        Self {
+            rating: meta.rating,
+            label: meta.label,
+            ..Self::default()
+        }
+    }
+
+    /// 在整段文件字节里找内嵌 packet；无 packet 返空。供已整读图像字节的流程
+    /// （cull 扫描）使用，免去再走一遍 `Exif::open`。
+    #[must_use]
+    pub fn from_embedded(bytes: &[u8]) -> Self {
+        xmp::find_xmp_packet(bytes).map_or_else(Self::default, Self::from_xmp)
+    }
+
+    /// `self` 缺的字段用 `fallback` 补齐（`self` 优先）。
+    #[must_use]
+    pub fn or(self, fallback: Self) -> Self {
+        Self {
+            rating: self.rating.or(fallback.rating),
+            label: self.label.or(fallback.label),
+            favorite: self.favorite || fallback.favorite,
+            album: self.album.or(fallback.album),
+            original_name: self.original_name.or(fallback.original_name),
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tags_tests.rs"]
+mod tests;
@@ -0,0 +1,55 @@
+use super::*;
+
+#[test]
+fn from_xmp_reads_rating_and_label() {
+    let xml = r#"<rdf:Description xmp:Rating="4" xmp:Label="Red"/>"#;
+    let tags = Tags::from_xmp(xml);
+    assert_eq!(tags.rating, Some(4));
+    assert_eq!(tags.label.as_deref(), Some("Red"));
+    assert!(!tags.is_empty());
+}
+
+#[test]
+fn from_embedded_finds_packet_in_file_bytes() {
+    let mut bytes = vec![0xFF, 0xD8, 0xFF, 0xE1, 0x00, 0x10];
+    bytes.extend_from_slice(b"<x:xmpmeta><rdf:Description><xmp:Rating>-1</xmp:Rating>");
+    bytes.extend_from_slice(b"</rdf:Description></x:xmpmeta>");
+    bytes.extend_from_slice(&[0xFF, 0xD9]);
+    assert_eq!(Tags::from_embedded(&bytes).rating, Some(-1));
+    assert!(Tags::from_embedded(b"no packet").is_empty());
+}
+
+#[test]
+fn or_prefers_self_and_fills_gaps() {
+    let sidecar = Tags {
+        rating: Some(2),
+        ..Tags::default()
+    };
+    let embedded = Tags {
+        rating: Some(5),
+        label: Some("Blue".into()),
+        favorite: true,
+        album: Some("Trip".into()),
+        original_name: Some("IMG_0001".into()),
+    };
+    let merged = sidecar.or(embedded);
+    assert_eq!(merged.rating, Some(2));
+    assert_eq!(merged.label.as_deref(), Some("Blue"));
+    assert!(merged.favorite);
+    assert_eq!(merged.album.as_deref(), Some("Trip"));
+    assert_eq!(merged.original_name.as_deref(), Some("IMG_0001"));
+}
+
+#[test]
+fn photos_fields_alone_are_not_empty() {
+    let favorite = Tags {
+        favorite: true,
+        ..Tags::default()
+    };
+    assert!(!favorite.is_empty());
+    let named = Tags {
+        original_name: Some("IMG_0001".into()),
+        ..Tags::default()
+    };
+    assert!(!named.is_empty());
+}
@@ -9,7 +9,9 @@
 //!   header，固定 LE，offset 基准 = `strd` + 8）；走 [`parse_ifds`]。
 //!
 //! 仅读归档需要的 5 个 ASCII/LONG 标签（Make/Model/DTO/CreateDate/ModifyDate
-//! + `ExifIFDPointer` 指针），不实现完整 TIFF（YAGNI）。
+//! + `ExifIFDPointer` 指针），不实现完整 TIFF（YAGNI）。ExifIFD 内的 `MakerNote`
+//! 只认两种连拍标识：Apple `BurstUUID` 与 Canon `ShotInfo` 里的 `SequenceNumber`，
+//! 供 `cull` 连拍分组。
 
 /// IFD 字节序。
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -25,8 +27,22 @@ const TAG_MODIFY_DATE: u16 = 0x0132;
 const TAG_EXIF_OFFSET: u16 = 0x8769;
 const TAG_DATE_TIME_ORIGINAL: u16 = 0x9003;
 const TAG_CREATE_DATE: u16 = 0x9004;
+const TAG_MAKER_NOTE: u16 = 0x927c;
 const TYPE_ASCII: u16 = 2;
+const TYPE_SHORT: u16 = 3;
 const TYPE_LONG: u16 = 4;
+const TYPE_UNDEFINED: u16 = 7;
+
+/// Apple `MakerNote` 头：`Apple iOS\0` + 2 字节版本 + `MM`；子 IFD 紧随其后，固定大端，
+/// 偏移相对 `MakerNote` 起点。
+const APPLE_MAKER_NOTE_MAGIC: &[u8] = b"Apple iOS\0";
+const APPLE_MAKER_NOTE_IFD: usize = 14;
+const APPLE_TAG_BURST_UUID: u16 = 0x000b;
+
+/// Canon `MakerNote` 是与主 TIFF 同字节序的裸 IFD，偏移相对 TIFF header；
+/// `ShotInfo`（SHORT 数组）第 9 项为连拍序号，单张为 0。
+const CANON_TAG_SHOT_INFO: u16 = 0x0004;
+const CANON_SHOT_INFO_SEQUENCE: usize = 9;
 
 /// TIFF magic（II/MM 字节序读取后均为 `0x002A`）。
 const TIFF_MAGIC: u16 = 0x002A;
@@ -43,6 +59,10 @@ pub(crate) struct TiffIfd {
     pub modify_date: Option<String>,
     pub make: Option<String>,
     pub model: Option<String>,
+    /// Apple `BurstUUID`：同一次连拍的所有帧共享。
+    pub burst_id: Option<String>,
+    /// Canon 连拍序号（1 起；0 = 非连拍）。
+    pub sequence_number: Option<u32>,
 }
 
 /// 完整 TIFF header 入口：`II`/`MM` byte order + `0x002A` magic + IFD0 offset。
@@ -123,12 +143,65 @@ fn scan_ifd(
             (TAG_MODIFY_DATE, TYPE_ASCII) => {
                 out.modify_date = read_ascii(base, val_bytes, val_offset, cnt);
             }
+            (TAG_MAKER_NOTE, TYPE_UNDEFINED) => parse_maker_note(base, val_offset, cnt, order, out),
             _ => {}
         }
     }
     Some(())
 }
 
+// `MakerNote` 各厂私有格式：Apple 按头部 magic 识别，Canon 按 IFD0 已读出的 Make 识别
+//（Make 在 IFD0、`MakerNote` 在 ExifIFD，扫描顺序保证先到）。其余厂商忽略。
+fn parse_maker_note(base: &[u8], off: usize, cnt: usize, order: ByteOrder, out: &mut TiffIfd) {
+    let Some(note) = base.get(off..off.saturating_add(cnt)) else {
+        return;
+    };
+    if note.starts_with(APPLE_MAKER_NOTE_MAGIC) {
+        out.burst_id = find_entry(
+            note,
+            APPLE_MAKER_NOTE_IFD,
+            ByteOrder::Be,
+            APPLE_TAG_BURST_UUID,
+        )
+        .filter(|&(typ, ..)| typ == TYPE_ASCII)
+        .and_then(|(_, cnt, val)| {
+            read_ascii(note, val, u32_from_bytes(val, ByteOrder::Be) as usize, cnt)
+        });
+    } else if out.make.as_deref().is_some_and(|m| m.starts_with("Canon")) {
+        out.sequence_number = canon_sequence_number(base, off, order);
+    }
+}
+
+// ShotInfo 数组远超 4 字节，val 字段恒为偏移。
+fn canon_sequence_number(base: &[u8], ifd_off: usize, order: ByteOrder) -> Option<u32> {
+    let (typ, cnt, val) = find_entry(base, ifd_off, order, CANON_TAG_SHOT_INFO)?;
+    if typ != TYPE_SHORT || cnt <= CANON_SHOT_INFO_SEQUENCE {
+        return None;
+    }
+    let data = u32_from_bytes(val, order) as usize;
+    u16_at(base, data + CANON_SHOT_INFO_SEQUENCE * 2, order).map(u32::from)
+}
+
+// 在 `base[ifd_off..]` 的 IFD 中找 `tag`，返回 (类型, count, val 4 字节)；越界返 None。
+fn find_entry(
+    base: &[u8],
+    ifd_off: usize,
+    order: ByteOrder,
+    tag: u16,
+) -> Option<(u16, usize, [u8; 4])> {
+    let count = u16_at(base, ifd_off, order)? as usize;
+    (0..count).find_map(|i| {
+        let e = ifd_off + 2 + i * 12;
+        if u16_at(base, e, order)? != tag {
+            return None;
+        }
+        let typ = u16_at(base, e + 2, order)?;
+        let cnt = u32_at(base, e + 4, order)? as usize;
+        let val = <[u8; 4]>::try_from(base.get(e + 8..e + 12)?).ok()?;
+        Some((typ, cnt, val))
+    })
+}
+
 // ASCII 字段读取：cnt ≤ 4 走 inline（val 字段的 cnt 个字节），cnt > 4 走 base[off..off+cnt]。
 // 短 Make/Model（"DJI\0" cnt=4、"LG\0" cnt=3）的 inline 形式过去被静默拒绝。
 fn read_ascii(base: &[u8], val_bytes: [u8; 4], off: usize, cnt: usize) -> Option<String> {
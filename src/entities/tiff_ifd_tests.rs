@@ -353,3 +353,57 @@ fn parse_ifds_ascii_cnt_zero_returns_none() {
     let ifd = parse_ifds(&buf, 0, ByteOrder::Le).unwrap();
     assert_eq!(ifd.make, None);
 }
+
+// ---------- MakerNote 连拍标识 ----------
+
+#[test]
+fn parse_ifds_reads_apple_burst_uuid_from_maker_note() {
+    // MM：IFD0 仅 MakerNote（offset 18）；note = magic(10) + 版本(2) + "MM" + 子 IFD，
+    // 子 IFD 偏移相对 note 起点，BurstUUID 数据位于 note+32。
+    let order = ByteOrder::Be;
+    let uuid = b"6F2B0A8E-3C1D-4E7A-9B55-2D1F0C9E8A77\0";
+    let mut note = b"Apple iOS\0\0\x01MM".to_vec();
+    note.extend_from_slice(&u16_bytes(1, order));
+    note.extend_from_slice(&ifd_entry(0x000b, 2, 37, 32, order));
+    note.extend_from_slice(&u32_bytes(0, order));
+    note.extend_from_slice(uuid);
+    assert_eq!(note.len(), 69);
+    let mut buf = Vec::new();
+    buf.extend_from_slice(&u16_bytes(1, order));
+    buf.extend_from_slice(&ifd_entry(0x927c, 7, 69, 18, order));
+    buf.extend_from_slice(&u32_bytes(0, order));
+    buf.extend_from_slice(&note);
+    let ifd = parse_ifds(&buf, 0, order).unwrap();
+    assert_eq!(
+        ifd.burst_id.as_deref(),
+        Some("6F2B0A8E-3C1D-4E7A-9B55-2D1F0C9E8A77")
+    );
+    assert_eq!(ifd.sequence_number, None);
+}
+
+/// Canon 裸 IFD：ShotInfo 第 9 项为序号；Make 非 Canon 时同样字节不解析。
+#[test]
+fn parse_ifds_reads_canon_sequence_number_only_for_canon_make() {
+    let order = ByteOrder::Le;
+    let build = |make: &[u8; 6]| {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&u16_bytes(2, order));
+        buf.extend_from_slice(&ifd_entry(0x010f, 2, 6, 30, order));
+        buf.extend_from_slice(&ifd_entry(0x927c, 7, 38, 36, order));
+        buf.extend_from_slice(&u32_bytes(0, order));
+        buf.extend_from_slice(make);
+        // MakerNote @36：1 entry，ShotInfo 数据 @54
+        buf.extend_from_slice(&u16_bytes(1, order));
+        buf.extend_from_slice(&ifd_entry(0x0004, 3, 10, 54, order));
+        buf.extend_from_slice(&u32_bytes(0, order));
+        for i in 0..10u16 {
+            buf.extend_from_slice(&u16_bytes(if i == 9 { 3 } else { 0 }, order));
+        }
+        buf
+    };
+    let canon = parse_ifds(&build(b"Canon\0"), 0, order).unwrap();
+    assert_eq!(canon.sequence_number, Some(3));
+    assert_eq!(canon.burst_id, None);
+    let nikon = parse_ifds(&build(b"Nikon\0"), 0, order).unwrap();
+    assert_eq!(nikon.sequence_number, None);
+}
@@ -0,0 +1,67 @@
+//! `ExtendedXMP`：主 packet 超过单段 APP1 上限（~64 KiB）时，超出部分按
+//! XMP spec Part 3 §1.1.3.1 切成多段 APP1，以主 packet `xmpNote:HasExtendedXMP`
+//! 的 GUID 关联。本文件只做分段解析与按偏移重组，JPEG 段遍历在 `exif::image_jpeg`。
+
+/// `ExtendedXMP` APP1 段签名（含结尾 NUL），其后依次为 32 字节 GUID、
+/// 4 字节 BE 全长、4 字节 BE 本段偏移、数据。
+const EXTENDED_SIGNATURE: &[u8] = b"http://ns.adobe.com/xmp/extension/\0";
+const EXTENDED_GUID_LEN: usize = 32;
+const EXTENDED_HEADER_LEN: usize = EXTENDED_SIGNATURE.len() + EXTENDED_GUID_LEN + 8;
+/// 重组上限：正常 `ExtendedXMP`（Google 深度图 / Photoshop 历史）在数 MB 内；
+/// 拒绝损坏/恶意头部声明的超大全长，避免按其分配内存。
+const EXTENDED_MAX_LEN: usize = 16 * 1024 * 1024;
+
+/// 单个 `ExtendedXMP` 段（APP1 payload 已去掉 marker 与长度字段）。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ExtendedChunk {
+    pub guid: String,
+    pub full_len: usize,
+    pub offset: usize,
+    pub data: Vec<u8>,
+}
+
+/// payload 不以 `ExtendedXMP` 签名起头、头部不完整或 GUID 非 ASCII 时返 None。
+pub(crate) fn parse_extended_chunk(payload: &[u8]) -> Option<ExtendedChunk> {
+    let rest = payload.strip_prefix(EXTENDED_SIGNATURE)?;
+    if payload.len() < EXTENDED_HEADER_LEN {
+        return None;
+    }
+    let guid = std::str::from_utf8(&rest[..EXTENDED_GUID_LEN]).ok()?;
+    let be = |at: usize| {
+        u32::from_be_bytes([rest[at], rest[at + 1], rest[at + 2], rest[at + 3]]) as usize
+    };
+    Some(ExtendedChunk {
+        guid: guid.to_string(),
+        full_len: be(EXTENDED_GUID_LEN),
+        offset: be(EXTENDED_GUID_LEN + 4),
+        data: rest[EXTENDED_GUID_LEN + 8..].to_vec(),
+    })
+}
+
+/// 按偏移拼接 `guid` 对应各段；全长不一致、有缺口、越界或超限时返 None
+/// （残缺 packet 不如不用：主 packet 的值仍有效）。重复段（同偏移）容忍。
+pub(crate) fn reassemble_extended(chunks: &[ExtendedChunk], guid: &str) -> Option<String> {
+    let mut parts: Vec<&ExtendedChunk> = chunks.iter().filter(|c| c.guid == guid).collect();
+    let full_len = parts.first()?.full_len;
+    if full_len > EXTENDED_MAX_LEN || parts.iter().any(|c| c.full_len != full_len) {
+        return None;
+    }
+    parts.sort_by_key(|c| c.offset);
+    let mut buf = Vec::with_capacity(full_len);
+    for c in parts {
+        if c.offset > buf.len() {
+            return None;
+        }
+        let skip = buf.len() - c.offset;
+        if let Some(fresh) = c.data.get(skip..) {
+            buf.extend_from_slice(fresh);
+        }
+        if buf.len() > full_len {
+            return None;
+        }
+    }
+    if buf.len() != full_len {
+        return None;
+    }
+    String::from_utf8(buf).ok()
+}
@@ -0,0 +1,491 @@
+//! XMP packet 自解析：JPEG/HEIC/TIFF/PNG 内嵌 packet 与 `.xmp` sidecar 共用。
+//!
+//! 用途：相机原拍后用 Lightroom / Bridge / ACR / darktable / `digiKam` 等 re-tag 工具改过的图，
+//! EXIF IFD0 可能只剩 `ModifyDate`、原始拍摄时间落在 XMP packet 的 `xmp:CreateDate` /
+//! `photoshop:DateCreated` / `exif:DateTimeOriginal`。nom-exif 3.5/3.6 只读 EXIF IFD0，
+//! 遇到这类文件会跳过日期走 P4 `FsMtime` 兜底归错桶。本模块在 `entities::exif`
+//! 解析后做兜底嗅探，与 exiftool 行为对齐。
+//!
+//! 范围：不引 XML lib 的最小 RDF/XML 子集解析——
+//!   - attribute 形式 `<rdf:Description xmp:CreateDate="..."/>`（Adobe 系默认）
+//!   - element 形式 `<xmp:CreateDate>...</xmp:CreateDate>`（darktable / `digiKam` / Google）
+//!   - `rdf:Bag` / `rdf:Seq` / `rdf:Alt` 的 `rdf:li` 列表（`dc:subject` 关键词）
+//!   - `xmlns` 前缀按作用域解析（`xap:` 等旧前缀同样命中）；未声明前缀按惯用名兜底
+//!   - `ExtendedXMP`（`xmpNote:HasExtendedXMP` GUID + 多段 APP1 拼接）：分段解析与
+//!     重组在此，JPEG 段遍历 IO 在 `exif::image_jpeg`
+//!
+//! 不做：DTD / 外部实体、`rdf:parseType="Literal"`、语言标签择优（取首个 `rdf:li`）。
+
+mod extended;
+mod tokenizer;
+
+use chrono::DateTime;
+use chrono::FixedOffset;
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+
+pub(crate) use self::extended::parse_extended_chunk;
+pub(crate) use self::extended::reassemble_extended;
+use self::tokenizer::Token;
+use self::tokenizer::Tokenizer;
+
+const PACKET_START: &str = "<x:xmpmeta";
+const PACKET_END: &str = "</x:xmpmeta>";
+// XML attribute 值可用单引号或双引号包裹（W3C XML 1.0 §3.1）；XMP packet 在
+// 不同工具下两种都见过（exiftool Shorthand 输出单引号、Adobe 系工具多用双引号）。
+const KEY_PHOTOSHOP_DATE_CREATED: &str = "photoshop:DateCreated=";
+const KEY_XMP_CREATE_DATE: &str = "xmp:CreateDate=";
+
+const NS_RDF: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+const NS_XMP: &str = "http://ns.adobe.com/xap/1.0/";
+const NS_XMP_NOTE: &str = "http://ns.adobe.com/xmp/note/";
+const NS_PHOTOSHOP: &str = "http://ns.adobe.com/photoshop/1.0/";
+const NS_EXIF: &str = "http://ns.adobe.com/exif/1.0/";
+const NS_TIFF: &str = "http://ns.adobe.com/tiff/1.0/";
+const NS_DC: &str = "http://purl.org/dc/elements/1.1/";
+const NS_EXIF_EX: &str = "http://cipa.jp/exif/1.0/";
+const NS_AUX: &str = "http://ns.adobe.com/exif/1.0/aux/";
+
+/// 手写 sidecar / 截断 packet 常缺 `xmlns` 声明：未声明前缀按 Adobe 惯用名兜底。
+const DEFAULT_PREFIXES: [(&str, &str); 10] = [
+    ("rdf", NS_RDF),
+    ("xmp", NS_XMP),
+    ("xap", NS_XMP),
+    ("xmpNote", NS_XMP_NOTE),
+    ("photoshop", NS_PHOTOSHOP),
+    ("exif", NS_EXIF),
+    ("tiff", NS_TIFF),
+    ("dc", NS_DC),
+    ("exifEX", NS_EXIF_EX),
+    ("aux", NS_AUX),
+];
+
+/// XMP packet 内的两个候选时间。两键互独立，可能同时为 Some/None。
+#[doc(hidden)]
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct XmpDates {
+    /// `photoshop:DateCreated`：等价 EXIF `DateTimeOriginal`（P0）。
+    pub photoshop_date_created: Option<DateTime<FixedOffset>>,
+    /// `xmp:CreateDate`：等价 EXIF `CreateDate`（P1）。
+    pub xmp_create_date: Option<DateTime<FixedOffset>>,
+}
+
+/// 结构化解析结果。同一属性出现多次时取首个合法值；关键词按出现顺序去重。
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct XmpMeta {
+    /// `photoshop:DateCreated`：等价 EXIF `DateTimeOriginal`（P0），常只有日期。
+    pub photoshop_date_created: Option<DateTime<FixedOffset>>,
+    /// `xmp:CreateDate`：等价 EXIF `CreateDate`（P1）。
+    pub xmp_create_date: Option<DateTime<FixedOffset>>,
+    /// `exif:DateTimeOriginal`：darktable / digiKam 镜像的 EXIF 拍摄时间（P0）。
+    pub exif_date_time_original: Option<DateTime<FixedOffset>>,
+    /// `tiff:Make` / `tiff:Model`。
+    pub make: Option<String>,
+    pub model: Option<String>,
+    /// `xmp:Rating`：-1（拒绝）到 5 星；越界值视为缺失。
+    pub rating: Option<i8>,
+    /// `xmp:Label`：色标（Lightroom 写本地化文本，如 `Red` / `红色`）。
+    pub label: Option<String>,
+    /// `dc:subject` 关键词。
+    pub keywords: Vec<String>,
+    /// `exifEX:LensModel`（CIPA 2.31 镜像）或旧式 `aux:Lens`。
+    pub lens: Option<String>,
+    /// `photoshop:Country`：Lightroom / `digiKam` 按 GPS 反查后写入的国家名。
+    pub country: Option<String>,
+    /// `photoshop:City`：同上，按 GPS 反查后写入的城市名。
+    pub city: Option<String>,
+    /// `xmpNote:HasExtendedXMP`：`ExtendedXMP` 段的 GUID（32 位十六进制 MD5）。
+    pub extended_guid: Option<String>,
+}
+
+impl XmpMeta {
+    /// 用 `ExtendedXMP` 段解析结果补齐主 packet 缺失的字段（主 packet 优先）。
+    pub(crate) fn merge(&mut self, other: XmpMeta) {
+        self.photoshop_date_created = self.photoshop_date_created.or(other.photoshop_date_created);
+        self.xmp_create_date = self.xmp_create_date.or(other.xmp_create_date);
+        self.exif_date_time_original = self
+            .exif_date_time_original
+            .or(other.exif_date_time_original);
+        self.make = self.make.take().or(other.make);
+        self.model = self.model.take().or(other.model);
+        self.rating = self.rating.or(other.rating);
+        self.label = self.label.take().or(other.label);
+        self.lens = self.lens.take().or(other.lens);
+        self.country = self.country.take().or(other.country);
+        self.city = self.city.take().or(other.city);
+        for kw in other.keywords {
+            if !self.keywords.contains(&kw) {
+                self.keywords.push(kw);
+            }
+        }
+    }
+}
+
+/// 在原始字节流中线性搜 `<x:xmpmeta ... </x:xmpmeta>` 子串。
+/// `buf` 通常是 JPEG/HEIC/TIFF 前 ~64 KB；含非 UTF-8 字节也返回 None
+/// （`from_utf8` 验证一次性，避免后续解析触雷）。
+pub(crate) fn find_xmp_packet(buf: &[u8]) -> Option<&str> {
+    let start_pat = PACKET_START.as_bytes();
+    let end_pat = PACKET_END.as_bytes();
+    let start = buf.windows(start_pat.len()).position(|w| w == start_pat)?;
+    // start 是 windows().position 返回的合法索引；tail 切片不会越界。
+    let tail = &buf[start..];
+    let end_rel = tail.windows(end_pat.len()).position(|w| w == end_pat)?;
+    // end_rel + end_pat.len() ≤ tail.len() 由 windows 语义保证。
+    let packet = &tail[..end_rel + end_pat.len()];
+    std::str::from_utf8(packet).ok()
+}
+
+/// 解析 XMP packet 子串，返回 photoshop:DateCreated 与 xmp:CreateDate 两键
+/// （必须带时区）。`.xmp` sidecar 沿用的窄入口，完整字段见 [`parse_xmp`]。
+#[doc(hidden)]
+#[must_use]
+pub fn parse_xmp_dates(content: &str) -> XmpDates {
+    let meta = parse_xmp(content, None);
+    XmpDates {
+        photoshop_date_created: meta.photoshop_date_created,
+        xmp_create_date: meta.xmp_create_date,
+    }
+}
+
+/// 结构化解析 XMP packet。`default_offset` 用于无时区日期（darktable 写
+/// `exif:DateTimeOriginal="2019-06-01T12:00:00.000"`）：与 EXIF 无时区字段同口径按
+/// 相机本地时区解释；传 None 时无时区日期视为缺失。
+#[must_use]
+pub(crate) fn parse_xmp(content: &str, default_offset: Option<FixedOffset>) -> XmpMeta {
+    parse_stripped(&strip_xml_comments(content), default_offset)
+}
+
+fn parse_stripped(content: &str, default_offset: Option<FixedOffset>) -> XmpMeta {
+    let mut b = Builder {
+        meta: XmpMeta::default(),
+        default_offset,
+        scopes: Vec::new(),
+        frames: Vec::new(),
+    };
+    for token in Tokenizer::new(content) {
+        match token {
+            Token::Start { name, attrs, empty } => b.start(name, &attrs, empty),
+            Token::End => b.end(),
+            Token::Text(t) => {
+                if let Some(f) = b.frames.last_mut() {
+                    f.text.push_str(&t);
+                }
+            }
+        }
+    }
+    let mut meta = b.meta;
+    // 结构化解析未命中（截断/畸形 packet 让 tokenizer 提前终止）时，两个历史日期键
+    // 退回逐属性线性扫描，保持与旧实现同等的容错。
+    if meta.photoshop_date_created.is_none() {
+        meta.photoshop_date_created = find_attr_rfc3339(content, KEY_PHOTOSHOP_DATE_CREATED);
+    }
+    if meta.xmp_create_date.is_none() {
+        meta.xmp_create_date = find_attr_rfc3339(content, KEY_XMP_CREATE_DATE);
+    }
+    meta
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Property {
+    PhotoshopDateCreated,
+    XmpCreateDate,
+    ExifDateTimeOriginal,
+    Make,
+    Model,
+    Rating,
+    Label,
+    Keywords,
+    Lens,
+    Country,
+    City,
+    HasExtendedXmp,
+}
+
+fn property_of(ns: &str, local: &str) -> Option<Property> {
+    match (ns, local) {
+        (NS_PHOTOSHOP, "DateCreated") => Some(Property::PhotoshopDateCreated),
+        (NS_XMP, "CreateDate") => Some(Property::XmpCreateDate),
+        (NS_XMP, "Rating") => Some(Property::Rating),
+        (NS_XMP, "Label") => Some(Property::Label),
+        (NS_EXIF, "DateTimeOriginal") => Some(Property::ExifDateTimeOriginal),
+        (NS_TIFF, "Make") => Some(Property::Make),
+        (NS_TIFF, "Model") => Some(Property::Model),
+        (NS_DC, "subject") => Some(Property::Keywords),
+        (NS_EXIF_EX, "LensModel") | (NS_AUX, "Lens") => Some(Property::Lens),
+        (NS_PHOTOSHOP, "Country") => Some(Property::Country),
+        (NS_PHOTOSHOP, "City") => Some(Property::City),
+        (NS_XMP_NOTE, "HasExtendedXMP") => Some(Property::HasExtendedXmp),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FrameKind {
+    /// 普通元素：已知属性元素带 `Some`，其余（`rdf:Description` 等）为 None。
+    Element(Option<Property>),
+    /// `rdf:Bag` / `rdf:Seq` / `rdf:Alt`。
+    Container,
+    /// `rdf:li` / `rdf:value`：文本归属最近的非容器祖先属性。
+    Item,
+}
+
+struct Frame {
+    kind: FrameKind,
+    text: String,
+}
+
+struct Builder {
+    meta: XmpMeta,
+    default_offset: Option<FixedOffset>,
+    /// 每个已打开元素一层 `xmlns` 声明（prefix, uri），与 `frames` 同步压弹。
+    scopes: Vec<Vec<(String, String)>>,
+    frames: Vec<Frame>,
+}
+
+impl Builder {
+    fn start(&mut self, name: &str, attrs: &[(&str, String)], empty: bool) {
+        let decls = attrs
+            .iter()
+            .filter_map(|(k, v)| {
+                let prefix = k.strip_prefix("xmlns")?;
+                let prefix = if prefix.is_empty() {
+                    ""
+                } else {
+                    prefix.strip_prefix(':')?
+                };
+                Some((prefix.to_string(), v.clone()))
+            })
+            .collect();
+        self.scopes.push(decls);
+        for (k, v) in attrs {
+            if let Some(p) = self.property(k) {
+                self.record(p, v);
+            }
+        }
+        let kind = match self.resolve(name) {
+            Some((NS_RDF, "Bag" | "Seq" | "Alt")) => FrameKind::Container,
+            Some((NS_RDF, "li" | "value")) => FrameKind::Item,
+            _ => FrameKind::Element(self.property(name)),
+        };
+        if empty {
+            self.scopes.pop();
+            return;
+        }
+        self.frames.push(Frame {
+            kind,
+            text: String::new(),
+        });
+    }
+
+    // 结束标签名不校验配对：畸形 packet 尽量多拿值，而非整体放弃。
+    fn end(&mut self) {
+        let Some(frame) = self.frames.pop() else {
+            return;
+        };
+        self.scopes.pop();
+        let text = frame.text.trim();
+        if text.is_empty() {
+            return;
+        }
+        let target = match frame.kind {
+            FrameKind::Element(p) => p,
+            FrameKind::Container => None,
+            FrameKind::Item => self
+                .frames
+                .iter()
+                .rev()
+                .find_map(|f| match f.kind {
+                    FrameKind::Element(p) => Some(p),
+                    FrameKind::Container | FrameKind::Item => None,
+                })
+                .flatten(),
+        };
+        if let Some(p) = target {
+            self.record(p, text);
+        }
+    }
+
+    fn resolve<'s>(&'s self, qname: &'s str) -> Option<(&'s str, &'s str)> {
+        let (prefix, local) = qname.split_once(':').unwrap_or(("", qname));
+        if prefix == "xmlns" || (prefix.is_empty() && local == "xmlns") {
+            return None;
+        }
+        let declared = self
+            .scopes
+            .iter()
+            .rev()
+            .flat_map(|scope| scope.iter())
+            .find(|(p, _)| p == prefix)
+            .map(|(_, uri)| uri.as_str());
+        let uri = declared.or_else(|| {
+            DEFAULT_PREFIXES
+                .iter()
+                .find(|(p, _)| *p == prefix)
+                .map(|(_, uri)| *uri)
+        })?;
+        Some((uri, local))
+    }
+
+    fn property(&self, qname: &str) -> Option<Property> {
+        let (ns, local) = self.resolve(qname)?;
+        property_of(ns, local)
+    }
+
+    fn record(&mut self, property: Property, value: &str) {
+        let value = value.trim();
+        if value.is_empty() {
+            return;
+        }
+        let m = &mut self.meta;
+        match property {
+            Property::PhotoshopDateCreated => {
+                set_once_date(&mut m.photoshop_date_created, value, self.default_offset);
+            }
+            Property::XmpCreateDate => {
+                set_once_date(&mut m.xmp_create_date, value, self.default_offset);
+            }
+            Property::ExifDateTimeOriginal => {
+                set_once_date(&mut m.exif_date_time_original, value, self.default_offset);
+            }
+            Property::Make => set_once_string(&mut m.make, value),
+            Property::Model => set_once_string(&mut m.model, value),
+            Property::Rating => {
+                if m.rating.is_none() {
+                    m.rating = value.parse::<i8>().ok().filter(|r| (-1..=5).contains(r));
+                }
+            }
+            Property::Label => set_once_string(&mut m.label, value),
+            Property::Keywords => {
+                if !m.keywords.iter().any(|k| k == value) {
+                    m.keywords.push(value.to_string());
+                }
+            }
+            Property::Lens => set_once_string(&mut m.lens, value),
+            Property::Country => set_once_string(&mut m.country, value),
+            Property::City => set_once_string(&mut m.city, value),
+            Property::HasExtendedXmp => set_once_string(&mut m.extended_guid, value),
+        }
+    }
+}
+
+fn set_once_date(
+    slot: &mut Option<DateTime<FixedOffset>>,
+    value: &str,
+    default_offset: Option<FixedOffset>,
+) {
+    // 解析失败不占位：description 等字段里的同名字面量残留不应挡住后续真实值。
+    if slot.is_none() {
+        *slot = parse_xmp_date(value, default_offset);
+    }
+}
+
+fn set_once_string(slot: &mut Option<String>, value: &str) {
+    if slot.is_none() {
+        *slot = Some(value.to_string());
+    }
+}
+
+/// XMP 日期（ISO 8601 子集）：`YYYY-MM-DDThh:mm:ss[.s]TZD`、省略秒的
+/// `YYYY-MM-DDThh:mmTZD`；无时区形式与纯日期仅在给定 `default_offset` 时接受。
+fn parse_xmp_date(
+    value: &str,
+    default_offset: Option<FixedOffset>,
+) -> Option<DateTime<FixedOffset>> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Some(dt);
+    }
+    if let Ok(dt) = DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M%:z") {
+        return Some(dt);
+    }
+    let offset = default_offset?;
+    let naive = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f")
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M"))
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+        })?;
+    naive.and_local_timezone(offset).single()
+}
+
+fn find_attr_rfc3339(haystack: &str, key: &str) -> Option<DateTime<FixedOffset>> {
+    // XML attribute 边界：key 必须紧跟 whitespace 或 packet/element 起始（'<'），
+    // 否则 `dc:description="...xmp:CreateDate='OLD'..."` 这类属性值内子串会
+    // 抢在真实属性前命中。线性扫描多次 find，匹配到合法 RFC3339 即返；
+    // 边界通过但 parse 失败（如 description 含字面 `photoshop:DateCreated="text"`
+    // 注释残留）必须 continue 而非 return None，否则真实属性在后面出现将被永久跳过。
+    let bytes = haystack.as_bytes();
+    let key_bytes = key.as_bytes();
+    let mut search_from = 0usize;
+    loop {
+        // search_from 不变量：每轮推进 = key_idx + key_bytes.len() ≤ haystack.len()，
+        // 故 `&haystack[search_from..]` 永不越界（直接切片省一处不可达的 `?` 死区）。
+        let rel = haystack[search_from..].find(key)?;
+        let key_idx = search_from + rel;
+        // 推进 cursor 前置：所有 continue 路径共享同一更新无遗漏死循环。
+        // find 已确保 key_idx + key_bytes.len() ≤ haystack.len()，切片不越界。
+        search_from = key_idx + key_bytes.len();
+        let prev = if key_idx == 0 {
+            b' '
+        } else {
+            bytes[key_idx - 1]
+        };
+        // 边界字符 = ASCII 单字节即可：whitespace（space/tab/LF/CR）或 '<' 起始
+        // attribute（针对 element 形态 <ns:Key>... 不命中，YAGNI 保持单段属性）。
+        if !matches!(prev, b' ' | b'\t' | b'\n' | b'\r' | b'<') {
+            continue;
+        }
+        let after_eq = &haystack[search_from..];
+        let Some(quote) = after_eq.chars().next() else {
+            continue;
+        };
+        if quote != '"' && quote != '\'' {
+            continue;
+        }
+        let rest = &after_eq[quote.len_utf8()..];
+        let Some(end) = rest.find(quote) else {
+            continue;
+        };
+        if let Ok(dt) = DateTime::parse_from_rfc3339(&rest[..end]) {
+            return Some(dt);
+        }
+    }
+}
+
+// 把 `<!-- ... -->` 注释体替换为同字节数的空格，保持偏移与原串一致。
+// XML 注释不可嵌套，按 `<!--`/`-->` 线性配对。注释边界全 ASCII，操作不破坏 UTF-8。
+// 未闭合 `<!--` 视为延伸到 EOF 的注释体（与 lenient XML 解析对齐）：避免把注释
+// 中的 `photoshop:DateCreated="OLD"` 字面量误读为合法属性。XMP packet 是 well-formed
+// XML 子集，未闭合注释属 malformed，宁可放弃后续也不冒误读风险。
+pub(crate) fn strip_xml_comments(content: &str) -> String {
+    let mut out = String::with_capacity(content.len());
+    let bytes = content.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i..].starts_with(b"<!--") {
+            let body_start = i + 4;
+            let end = bytes
+                .get(body_start..)
+                .and_then(|tail| tail.windows(3).position(|w| w == b"-->"))
+                .map_or(bytes.len(), |p| body_start + p + 3);
+            for _ in i..end {
+                out.push(' ');
+            }
+            i = end;
+        } else {
+            // 非注释字节按 UTF-8 字符边界推进，避免切到多字节字符内部。
+            let ch_end = (i + 1..=bytes.len())
+                .find(|&j| content.is_char_boundary(j))
+                .unwrap_or(bytes.len());
+            out.push_str(&content[i..ch_end]);
+            i = ch_end;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+#[path = "xmp_tests.rs"]
+mod tests;
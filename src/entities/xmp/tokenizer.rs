@@ -0,0 +1,152 @@
+//! XMP packet 用到的 XML 子集词法：开始/结束标签、属性、文本与实体解码。
+
+pub(super) enum Token<'a> {
+    Start {
+        name: &'a str,
+        attrs: Vec<(&'a str, String)>,
+        empty: bool,
+    },
+    End,
+    Text(String),
+}
+
+/// 最小 XML pull tokenizer：注释已由 [`super::strip_xml_comments`] 抹空；处理指令 /
+/// DOCTYPE 跳过；CDATA 当文本；未闭合标签终止迭代。
+pub(super) struct Tokenizer<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Tokenizer<'a> {
+    pub(super) fn new(src: &'a str) -> Self {
+        Self { src, pos: 0 }
+    }
+}
+
+impl<'a> Iterator for Tokenizer<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        loop {
+            let src = self.src;
+            let rest = &src[self.pos..];
+            if rest.is_empty() {
+                return None;
+            }
+            if !rest.starts_with('<') {
+                let end = rest.find('<').unwrap_or(rest.len());
+                self.pos += end;
+                return Some(Token::Text(decode_entities(&rest[..end])));
+            }
+            if let Some(body) = rest.strip_prefix("<![CDATA[") {
+                let (text, consumed) = match body.find("]]>") {
+                    Some(e) => (&body[..e], "<![CDATA[".len() + e + "]]>".len()),
+                    None => (body, rest.len()),
+                };
+                self.pos += consumed;
+                return Some(Token::Text(text.to_string()));
+            }
+            if rest.starts_with("<?") || rest.starts_with("<!") {
+                self.pos += rest.find('>').map_or(rest.len(), |e| e + 1);
+                continue;
+            }
+            let end = find_tag_end(rest)?;
+            let inner = &rest[1..end];
+            self.pos += end + 1;
+            if inner.starts_with('/') {
+                return Some(Token::End);
+            }
+            let (inner, empty) = match inner.strip_suffix('/') {
+                Some(i) => (i, true),
+                None => (inner, false),
+            };
+            let name_end = inner
+                .find(|c: char| c.is_ascii_whitespace())
+                .unwrap_or(inner.len());
+            return Some(Token::Start {
+                name: &inner[..name_end],
+                attrs: parse_attrs(&inner[name_end..]),
+                empty,
+            });
+        }
+    }
+}
+
+/// `rest` 以 `<` 开头；返回引号外第一个 `>` 的下标（属性值内可含 `>`）。
+fn find_tag_end(rest: &str) -> Option<usize> {
+    let mut quote: Option<u8> = None;
+    for (i, b) in rest.bytes().enumerate() {
+        match (quote, b) {
+            (None, b'"' | b'\'') => quote = Some(b),
+            (Some(q), _) if q == b => quote = None,
+            (None, b'>') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// 解析 `name="v" name2='v2'` 序列；遇畸形（无引号值等）即停，保留已解析部分。
+fn parse_attrs(mut s: &str) -> Vec<(&str, String)> {
+    let mut out = Vec::new();
+    loop {
+        s = s.trim_start();
+        let Some(eq) = s.find('=') else {
+            break;
+        };
+        let name = s[..eq].trim();
+        let after = s[eq + 1..].trim_start();
+        let Some(quote) = after.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+            break;
+        };
+        let body = &after[1..];
+        let Some(close) = body.find(quote) else {
+            break;
+        };
+        if !name.is_empty() && !name.contains(|c: char| c.is_ascii_whitespace()) {
+            out.push((name, decode_entities(&body[..close])));
+        }
+        s = &body[close + 1..];
+    }
+    out
+}
+
+/// XML 预定义实体 + 数字字符引用；未知实体原样保留。
+fn decode_entities(s: &str) -> String {
+    if !s.contains('&') {
+        return s.to_string();
+    }
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        let tail = &rest[amp..];
+        let decoded = tail.find(';').and_then(|semi| {
+            let ch = match &tail[1..semi] {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                ent => {
+                    let code = if let Some(hex) = ent.strip_prefix("#x") {
+                        u32::from_str_radix(hex, 16).ok()?
+                    } else {
+                        ent.strip_prefix('#')?.parse().ok()?
+                    };
+                    char::from_u32(code)?
+                }
+            };
+            Some((ch, semi + 1))
+        });
+        if let Some((ch, consumed)) = decoded {
+            out.push(ch);
+            rest = &tail[consumed..];
+        } else {
+            out.push('&');
+            rest = &tail[1..];
+        }
+    }
+    out.push_str(rest);
+    out
+}
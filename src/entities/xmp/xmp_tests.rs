@@ -0,0 +1,438 @@
+use super::*;
+
+// ── find_xmp_packet ──
+
+#[test]
+fn find_xmp_packet_in_jpeg_head() {
+    let head = b"\xff\xd8\xff\xe1...<x:xmpmeta xmlns:x='adobe:ns:meta/'>body</x:xmpmeta>tail";
+    let packet = find_xmp_packet(head).expect("packet found");
+    assert!(packet.starts_with("<x:xmpmeta"));
+    assert!(packet.ends_with("</x:xmpmeta>"));
+    assert!(packet.contains("body"));
+}
+
+/// 起始 marker 缺失（既无 `<x:xmpmeta` 也无 `</x:xmpmeta>`）→ None。
+#[test]
+fn find_xmp_packet_missing_start_returns_none() {
+    assert!(find_xmp_packet(b"random bytes without marker").is_none());
+}
+
+/// 有起始 marker 但无结束 marker（packet 被截断）→ None。
+/// 注意：内部 close 字符串 "</x:xmpmeta>" 故意嵌在最后避免命中。
+#[test]
+fn find_xmp_packet_missing_end_returns_none() {
+    let buf = b"prefix<x:xmpmeta body without close";
+    assert!(find_xmp_packet(buf).is_none());
+}
+
+/// packet 字节流非 UTF-8（即便 marker 完整）→ `from_utf8` 验证失败 → None。
+#[test]
+fn find_xmp_packet_non_utf8_returns_none() {
+    let mut buf = Vec::new();
+    buf.extend_from_slice(b"<x:xmpmeta ");
+    buf.push(0xff); // 非法 UTF-8 起始字节
+    buf.push(0xfe);
+    buf.extend_from_slice(b"</x:xmpmeta>");
+    assert!(find_xmp_packet(&buf).is_none());
+}
+
+#[test]
+fn find_xmp_packet_empty_buf_returns_none() {
+    assert!(find_xmp_packet(b"").is_none());
+}
+
+// ── parse_xmp_dates ──
+
+#[test]
+fn parse_xmp_dates_attribute_double_quoted() {
+    let xml = r#"<rdf:Description photoshop:DateCreated="2024-05-01T14:30:00+08:00"/>"#;
+    let dates = parse_xmp_dates(xml);
+    let dt = dates.photoshop_date_created.expect("photoshop key present");
+    // 14:30 +08:00 = 06:30 UTC = 1714545000
+    assert_eq!(dt.timestamp(), 1_714_545_000);
+    assert!(dates.xmp_create_date.is_none());
+}
+
+/// exiftool Shorthand 模式输出的单引号 attribute 也要识别（与 fixture 同形态）。
+#[test]
+fn parse_xmp_dates_attribute_single_quoted() {
+    let xml = "<rdf:Description xmp:CreateDate='2008-10-31T09:15:01+08:00'/>";
+    let dates = parse_xmp_dates(xml);
+    let dt = dates.xmp_create_date.expect("xmp:CreateDate present");
+    // 09:15:01 +08:00 = 01:15:01 UTC = 1225415701
+    assert_eq!(dt.timestamp(), 1_225_415_701);
+    assert!(dates.photoshop_date_created.is_none());
+}
+
+/// 同时含两个键 → 两字段都有值。
+#[test]
+fn parse_xmp_dates_both_keys_present() {
+    let xml = r#"<rdf:Description
+        photoshop:DateCreated="2024-05-01T14:30:00+08:00"
+        xmp:CreateDate="2024-05-02T15:30:00+00:00"/>"#;
+    let dates = parse_xmp_dates(xml);
+    assert_eq!(
+        dates.photoshop_date_created.unwrap().timestamp(),
+        1_714_545_000
+    );
+    assert_eq!(dates.xmp_create_date.unwrap().timestamp(), 1_714_663_800);
+}
+
+/// 同形态字面量藏在 XML 注释里 → 正文优先；注释里的不能误命中。
+#[test]
+fn parse_xmp_dates_skips_xml_comment() {
+    let xml = r#"<!-- photoshop:DateCreated="2020-01-01T00:00:00Z" -->
+<rdf:Description photoshop:DateCreated="2024-05-01T14:30:00+08:00"/>"#;
+    let dates = parse_xmp_dates(xml);
+    assert_eq!(
+        dates.photoshop_date_created.unwrap().timestamp(),
+        1_714_545_000
+    );
+}
+
+#[test]
+fn parse_xmp_dates_no_keys_returns_default() {
+    let dates = parse_xmp_dates("<rdf:Description rdf:about=''/>");
+    assert_eq!(dates, XmpDates::default());
+}
+
+/// `=` 后既不是 `"` 也不是 `'` → 不当作 attribute → None。
+#[test]
+fn parse_xmp_dates_non_quote_after_equals_returns_none() {
+    // 等号后是 `<` —— 不合法 XML，但解析器不应 panic 也不应解析出值。
+    let xml = r"<x photoshop:DateCreated=<bad/>";
+    assert!(parse_xmp_dates(xml).photoshop_date_created.is_none());
+}
+
+/// 引号内 RFC3339 解析失败（非日期字符串）→ None。
+#[test]
+fn parse_xmp_dates_invalid_rfc3339_returns_none() {
+    let xml = r#"<x photoshop:DateCreated="not a date"/>"#;
+    assert!(parse_xmp_dates(xml).photoshop_date_created.is_none());
+}
+
+/// 起始引号有、终止引号缺 → find 返 None → None。
+#[test]
+fn parse_xmp_dates_unterminated_quote_returns_none() {
+    let xml = r#"<x photoshop:DateCreated="2024-05-01T14:30:00"#;
+    assert!(parse_xmp_dates(xml).photoshop_date_created.is_none());
+}
+
+/// key 在串尾出现（key+`=` 后已无内容）→ `after_eq.chars().next()` None → None。
+/// 这里 `aaa` 前缀让 boundary 失败 → 走 `if !matches!(prev, ...)` continue arm；不到
+/// `let Some(quote) = ... else continue` 的 None continue。
+#[test]
+fn parse_xmp_dates_key_at_end_with_non_boundary_prefix_returns_none() {
+    let xml = "aaaphotoshop:DateCreated=";
+    assert!(parse_xmp_dates(xml).photoshop_date_created.is_none());
+}
+
+/// key 在串尾出现且前缀是 boundary（空格）→ boundary check 通过、`after_eq` 空、
+/// `chars().next()` None → 触发 `let Some(quote) ... else continue;` arm。
+#[test]
+fn parse_xmp_dates_key_at_end_with_boundary_prefix_returns_none() {
+    let xml = " photoshop:DateCreated=";
+    assert!(parse_xmp_dates(xml).photoshop_date_created.is_none());
+}
+
+/// parse 失败（边界通过但值非 RFC3339）必须 continue 而非 return None：
+/// 后续真实属性应被命中。否则 description 字面注入即可让 P0 候选丢失。
+#[test]
+fn parse_xmp_dates_continues_past_parse_failure() {
+    let s = " photoshop:DateCreated=\"not-a-date\" \
+             photoshop:DateCreated=\"2020-01-01T00:00:00Z\"";
+    let dates = parse_xmp_dates(s);
+    assert!(
+        dates.photoshop_date_created.is_some(),
+        "real attribute after failed parse must still be found"
+    );
+}
+
+/// 第一处 key 前缀非边界字符（紧邻 ASCII 字母）→ continue；后续真实属性命中。
+#[test]
+fn parse_xmp_dates_continues_past_non_boundary_prefix() {
+    let s = "xphotoshop:DateCreated=\"2020-01-01T00:00:00Z\" \
+             photoshop:DateCreated=\"2021-02-02T00:00:00Z\"";
+    let dates = parse_xmp_dates(s);
+    assert_eq!(
+        dates.photoshop_date_created.unwrap().to_rfc3339(),
+        "2021-02-02T00:00:00+00:00",
+        "first occurrence had non-boundary prefix → must skip"
+    );
+}
+
+// ── strip_xml_comments ──
+
+/// 注释体替换为同字节数空格，前后正文与偏移原样保留。注释起点远离 0
+/// 能区分 `i+4` 被变异成 `i*4`（越界→整段误抹）、`p+3` 变异成 `p-3`
+/// （尾部 `-->` 残留）等算术错误——仅断言解析结果杀不掉这些变异。
+#[test]
+fn strip_xml_comments_blanks_mid_text_comment_preserving_offsets() {
+    assert_eq!(
+        strip_xml_comments("12345<!--x-->after"),
+        "12345        after"
+    );
+}
+
+/// 未闭合注释：剩余内容都按注释体处理 → 整体抹空。
+#[test]
+fn strip_xml_comments_unterminated_blanks_to_end() {
+    let out = strip_xml_comments("abc<!--tail");
+    assert!(out.starts_with("abc"));
+    assert!(out[3..].chars().all(|c| c == ' '));
+}
+
+/// 注释体含多字节 UTF-8 字符（中文）不破坏字符边界。
+#[test]
+fn strip_xml_comments_multibyte_in_body() {
+    let out = strip_xml_comments("p<!-- 中文 -->q");
+    assert!(out.starts_with('p'));
+    assert!(out.ends_with('q'));
+    assert_eq!(out.len(), "p<!-- 中文 -->q".len());
+}
+
+/// 无注释直通：每字节按 UTF-8 边界推进，输出与输入逐字节相同。
+#[test]
+fn strip_xml_comments_no_comments_pass_through() {
+    let s = "hello 世界";
+    assert_eq!(strip_xml_comments(s), s);
+}
+
+// ── parse_xmp：element 形式 / 命名空间 / 列表 ──
+
+fn cst() -> FixedOffset {
+    FixedOffset::east_opt(8 * 3600).unwrap()
+}
+
+/// darktable / digiKam 风格：属性全部写成子元素，嵌套 `rdf:Description`。
+#[test]
+fn parse_xmp_element_form_dates_make_model() {
+    let xml = r#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+ <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+  <rdf:Description rdf:about=""
+    xmlns:exif="http://ns.adobe.com/exif/1.0/"
+    xmlns:tiff="http://ns.adobe.com/tiff/1.0/"
+    xmlns:xmp="http://ns.adobe.com/xap/1.0/">
+   <exif:DateTimeOriginal>2024-05-01T14:30:00+08:00</exif:DateTimeOriginal>
+   <xmp:CreateDate>2024-05-02T15:30:00Z</xmp:CreateDate>
+   <tiff:Make>Canon</tiff:Make>
+   <tiff:Model>EOS R5</tiff:Model>
+   <xmp:Rating>4</xmp:Rating>
+  </rdf:Description>
+ </rdf:RDF>
+</x:xmpmeta>"#;
+    let meta = parse_xmp(xml, None);
+    assert_eq!(
+        meta.exif_date_time_original.unwrap().timestamp(),
+        1_714_545_000
+    );
+    assert_eq!(meta.xmp_create_date.unwrap().timestamp(), 1_714_663_800);
+    assert_eq!(meta.make.as_deref(), Some("Canon"));
+    assert_eq!(meta.model.as_deref(), Some("EOS R5"));
+    assert_eq!(meta.rating, Some(4));
+    assert!(meta.photoshop_date_created.is_none());
+}
+
+/// `dc:subject` 的 `rdf:Bag` 列表 → 关键词，去重保序；实体解码。
+#[test]
+fn parse_xmp_dc_subject_bag_keywords() {
+    let xml = r#"<rdf:Description xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <dc:subject><rdf:Bag>
+    <rdf:li>家人</rdf:li><rdf:li>Tom &amp; Jerry</rdf:li><rdf:li>家人</rdf:li><rdf:li> </rdf:li>
+  </rdf:Bag></dc:subject>
+</rdf:Description>"#;
+    let meta = parse_xmp(xml, None);
+    assert_eq!(
+        meta.keywords,
+        vec!["家人".to_string(), "Tom & Jerry".to_string()]
+    );
+}
+
+/// 前缀按 `xmlns` 解析：旧前缀 `xap:` 与自定义前缀都命中同一命名空间；
+/// 前缀相同但 URI 不同的属性不误命中。
+#[test]
+fn parse_xmp_resolves_prefix_by_namespace() {
+    let xml = r#"<rdf:Description
+    xmlns:xap="http://ns.adobe.com/xap/1.0/"
+    xmlns:ps="http://ns.adobe.com/photoshop/1.0/"
+    xmlns:tiff="urn:not-tiff"
+    xap:CreateDate="2024-05-02T15:30:00Z"
+    ps:DateCreated="2024-05-01T14:30:00+08:00"
+    tiff:Make="Bogus"/>"#;
+    let meta = parse_xmp(xml, None);
+    assert_eq!(meta.xmp_create_date.unwrap().timestamp(), 1_714_663_800);
+    assert_eq!(
+        meta.photoshop_date_created.unwrap().timestamp(),
+        1_714_545_000
+    );
+    assert!(meta.make.is_none());
+}
+
+/// 星级越界 / 非数字丢弃；-1（Lightroom "拒绝"）保留。
+#[test]
+fn parse_xmp_rating_range() {
+    let rating =
+        |v: &str| parse_xmp(&format!(r#"<rdf:Description xmp:Rating="{v}"/>"#), None).rating;
+    assert_eq!(rating("-1"), Some(-1));
+    assert_eq!(rating("0"), Some(0));
+    assert_eq!(rating("5"), Some(5));
+    assert_eq!(rating("6"), None);
+    assert_eq!(rating("-2"), None);
+    assert_eq!(rating("3.5"), None);
+}
+
+/// 无时区日期：仅在调用方给默认偏移时接受（sidecar 传 None → 丢弃）；
+/// 纯日期取当日 00:00，省略秒的 `hh:mmTZD` 形式直接接受。
+#[test]
+fn parse_xmp_timezone_less_dates_need_default_offset() {
+    let xml = "<rdf:Description>\
+        <exif:DateTimeOriginal>2024-05-01T14:30:00</exif:DateTimeOriginal>\
+        <photoshop:DateCreated>2024-05-01</photoshop:DateCreated>\
+        <xmp:CreateDate>2024-05-02T15:30+00:00</xmp:CreateDate>\
+        </rdf:Description>";
+    let none = parse_xmp(xml, None);
+    assert!(none.exif_date_time_original.is_none());
+    assert!(none.photoshop_date_created.is_none());
+    assert_eq!(none.xmp_create_date.unwrap().timestamp(), 1_714_663_800);
+
+    let meta = parse_xmp(xml, Some(cst()));
+    assert_eq!(
+        meta.exif_date_time_original.unwrap().timestamp(),
+        1_714_545_000
+    );
+    // 2024-05-01T00:00:00+08:00 = 2024-04-30T16:00:00Z
+    assert_eq!(
+        meta.photoshop_date_created.unwrap().timestamp(),
+        1_714_492_800
+    );
+}
+
+/// CDATA 当文本；处理指令（`<?xpacket ...?>`）跳过；属性值内 `>` 不截断标签。
+#[test]
+fn parse_xmp_cdata_processing_instruction_and_gt_in_attr() {
+    let xml = r#"<?xpacket begin="" id="W5M0MpCehiHzreSzNTczkc9d"?>
+<rdf:Description dc:title="a > b" tiff:Make="Nikon">
+  <tiff:Model><![CDATA[Z 8]]></tiff:Model>
+</rdf:Description>"#;
+    let meta = parse_xmp(xml, None);
+    assert_eq!(meta.make.as_deref(), Some("Nikon"));
+    assert_eq!(meta.model.as_deref(), Some("Z 8"));
+}
+
+/// 同键多次出现取首个可解析值：前面的无效值不占位。
+#[test]
+fn parse_xmp_first_valid_value_wins() {
+    let xml = "<rdf:Description>\
+        <xmp:CreateDate>garbage</xmp:CreateDate>\
+        <xmp:CreateDate>2024-05-02T15:30:00Z</xmp:CreateDate>\
+        <xmp:CreateDate>2020-01-01T00:00:00Z</xmp:CreateDate>\
+        </rdf:Description>";
+    let meta = parse_xmp(xml, None);
+    assert_eq!(meta.xmp_create_date.unwrap().timestamp(), 1_714_663_800);
+}
+
+#[test]
+fn parse_xmp_reads_extended_guid() {
+    let xml = r#"<rdf:Description xmpNote:HasExtendedXMP="0123456789ABCDEF0123456789ABCDEF"/>"#;
+    let meta = parse_xmp(xml, None);
+    assert_eq!(
+        meta.extended_guid.as_deref(),
+        Some("0123456789ABCDEF0123456789ABCDEF")
+    );
+}
+
+/// 镜头：`exifEX:LensModel` 与旧式 `aux:Lens` 均认，首个合法值胜；国家 / 城市取
+/// `photoshop:Country` / `photoshop:City`。
+#[test]
+fn parse_xmp_reads_lens_and_country() {
+    let xml = r#"<rdf:Description aux:Lens="Old 50mm" photoshop:Country="Japan" photoshop:City="Kyoto">
+    <exifEX:LensModel>RF24-70mm F2.8 L IS USM</exifEX:LensModel></rdf:Description>"#;
+    let meta = parse_xmp(xml, None);
+    assert_eq!(meta.lens.as_deref(), Some("Old 50mm"));
+    assert_eq!(meta.country.as_deref(), Some("Japan"));
+    assert_eq!(meta.city.as_deref(), Some("Kyoto"));
+
+    let meta = parse_xmp(
+        r#"<rdf:Description><exifEX:LensModel>RF 50mm</exifEX:LensModel></rdf:Description>"#,
+        None,
+    );
+    assert_eq!(meta.lens.as_deref(), Some("RF 50mm"));
+    assert!(meta.country.is_none());
+    assert!(meta.city.is_none());
+}
+
+/// merge：主 packet 已有的标量不被续段覆盖；关键词并集。
+#[test]
+fn xmp_meta_merge_keeps_primary_values() {
+    let mut primary = parse_xmp(
+        r#"<rdf:Description tiff:Make="Canon"><dc:subject><rdf:Bag><rdf:li>a</rdf:li></rdf:Bag></dc:subject></rdf:Description>"#,
+        None,
+    );
+    let extended = parse_xmp(
+        r#"<rdf:Description tiff:Make="Other" tiff:Model="R5" xmp:Rating="3"><dc:subject><rdf:Bag><rdf:li>a</rdf:li><rdf:li>b</rdf:li></rdf:Bag></dc:subject></rdf:Description>"#,
+        None,
+    );
+    primary.merge(extended);
+    assert_eq!(primary.make.as_deref(), Some("Canon"));
+    assert_eq!(primary.model.as_deref(), Some("R5"));
+    assert_eq!(primary.rating, Some(3));
+    assert_eq!(primary.keywords, vec!["a".to_string(), "b".to_string()]);
+}
+
+// ── ExtendedXMP 分段 ──
+
+const GUID: &str = "0123456789ABCDEF0123456789ABCDEF";
+
+fn extended_payload(guid: &str, full_len: u32, offset: u32, data: &[u8]) -> Vec<u8> {
+    let mut out = b"http://ns.adobe.com/xmp/extension/\0".to_vec();
+    out.extend_from_slice(guid.as_bytes());
+    out.extend_from_slice(&full_len.to_be_bytes());
+    out.extend_from_slice(&offset.to_be_bytes());
+    out.extend_from_slice(data);
+    out
+}
+
+fn chunk(offset: u32, data: &[u8]) -> extended::ExtendedChunk {
+    parse_extended_chunk(&extended_payload(GUID, 10, offset, data)).unwrap()
+}
+
+#[test]
+fn parse_extended_chunk_reads_header() {
+    let c = chunk(4, b"efgh");
+    assert_eq!(c.guid, GUID);
+    assert_eq!(c.full_len, 10);
+    assert_eq!(c.offset, 4);
+    assert_eq!(c.data, b"efgh");
+}
+
+#[test]
+fn parse_extended_chunk_rejects_foreign_or_short_payload() {
+    assert!(parse_extended_chunk(b"http://ns.adobe.com/xap/1.0/\0<x:xmpmeta").is_none());
+    assert!(parse_extended_chunk(b"http://ns.adobe.com/xmp/extension/\0short").is_none());
+}
+
+/// 乱序 + 重复段照样拼回；GUID 不符的段忽略。
+#[test]
+fn reassemble_extended_sorts_and_tolerates_duplicates() {
+    let mut chunks = vec![chunk(6, b"ghij"), chunk(0, b"abcdef"), chunk(6, b"ghij")];
+    chunks.push(parse_extended_chunk(&extended_payload(&"F".repeat(32), 3, 0, b"xyz")).unwrap());
+    assert_eq!(
+        reassemble_extended(&chunks, GUID).as_deref(),
+        Some("abcdefghij")
+    );
+}
+
+#[test]
+fn reassemble_extended_rejects_gap_short_or_mismatched() {
+    // 缺口：0..4 之后直接 6..
+    assert!(reassemble_extended(&[chunk(0, b"abcd"), chunk(6, b"ghij")], GUID).is_none());
+    // 不足全长
+    assert!(reassemble_extended(&[chunk(0, b"abcdef")], GUID).is_none());
+    // 超出全长
+    assert!(reassemble_extended(&[chunk(0, b"abcdefghijk")], GUID).is_none());
+    // 各段全长不一致
+    let odd = parse_extended_chunk(&extended_payload(GUID, 11, 6, b"ghij")).unwrap();
+    assert!(reassemble_extended(&[chunk(0, b"abcdef"), odd], GUID).is_none());
+    // 无匹配段
+    assert!(reassemble_extended(&[], GUID).is_none());
+}
@@ -67,9 +67,8 @@ pub(crate) fn load() -> Config {
 ///   永不执行恒返 `None`，所有 copy/move 静默失败
 /// - 非法 `archive_template`（嵌套/错配/未知占位符）会渲染出字面 `{xxx}` 目录
 fn sanitize(mut cfg: Config) -> Config {
-    // `copy.timezone_offset_hours` 上限：chrono::FixedOffset::east_opt 限 ±24h-1s、
-    // time::UtcOffset::from_whole_seconds 限 ±25:59:59。统一收紧到 ±23 给两库都留
-    // buffer；超界让 offset_from_hours / chrono_offset_from_hours 静默回退 UTC，
+    // `copy.timezone_offset_hours` 上限：chrono::FixedOffset::east_opt 限 ±24h-1s，
+    // 收紧到 ±23 留 buffer；超界让 chrono_offset_from_hours 静默回退 UTC，
     // 月末文件跨月归错桶，必须 warn + 回退默认。const 内联避免顶层 const 在
     // multi-binary instance 下被 LLVM 单独计 region。
     const MAX_TIMEZONE_HOURS_ABS: u8 = 23;
@@ -98,6 +97,32 @@ fn sanitize(mut cfg: Config) -> Config {
         );
         cfg.copy.unique_name_max_attempts = fallback;
     }
+    if cfg.copy.event_gap_minutes == 0 {
+        let fallback = CopyConfig::default().event_gap_minutes;
+        warn!(
+            feature = "config",
+            operation = "sanitize",
+            result = "invalid_value",
+            field = "copy.event_gap_minutes",
+            fallback,
+            "event_gap_minutes must be >= 1; falling back to default"
+        );
+        cfg.copy.event_gap_minutes = fallback;
+    }
+    // NaN / 负数会让距离比较恒假或恒真，等同静默关闭或打散所有事件。
+    if !cfg.copy.event_radius_km.is_finite() || cfg.copy.event_radius_km < 0.0 {
+        let fallback = CopyConfig::default().event_radius_km;
+        warn!(
+            feature = "config",
+            operation = "sanitize",
+            result = "invalid_value",
+            field = "copy.event_radius_km",
+            value = cfg.copy.event_radius_km,
+            fallback,
+            "event_radius_km must be a non-negative number; falling back to default"
+        );
+        cfg.copy.event_radius_km = fallback;
+    }
     if let Err(e) = validate_archive_template(&cfg.copy.archive_template) {
         let fallback = CopyConfig::default().archive_template;
         warn!(
@@ -189,6 +214,11 @@ fn sanitize_ocr(ocr: &mut OcrConfig) {
         );
         ocr.resize_max_side = defaults.resize_max_side;
     }
+    sanitize_max_image_bytes(
+        &mut ocr.max_image_bytes,
+        defaults.max_image_bytes,
+        "backend.ocr.max_image_bytes",
+    );
 }
 
 // 开区间 (0.0, 1.0) 内的有限正数。NaN/Inf 均通过 `is_finite()` 拒绝。
@@ -267,24 +297,50 @@ fn sanitize_face(face: &mut FaceConfig) {
     );
     sanitize_face_weight(&mut face.w_blink, defaults.w_blink, "backend.face.w_blink");
     sanitize_face_weight(&mut face.w_smile, defaults.w_smile, "backend.face.w_smile");
-    sanitize_max_image_bytes(face, &defaults);
+    sanitize_face_weight(
+        &mut face.w_exposure,
+        defaults.w_exposure,
+        "backend.face.w_exposure",
+    );
+    sanitize_face_weight(&mut face.w_noise, defaults.w_noise, "backend.face.w_noise");
+    sanitize_face_weight(
+        &mut face.w_motion_blur,
+        defaults.w_motion_blur,
+        "backend.face.w_motion_blur",
+    );
+    sanitize_face_weight(&mut face.w_tilt, defaults.w_tilt, "backend.face.w_tilt");
+    sanitize_face_weight(
+        &mut face.w_subject_sharpness,
+        defaults.w_subject_sharpness,
+        "backend.face.w_subject_sharpness",
+    );
+    sanitize_face_weight(
+        &mut face.w_quality,
+        defaults.w_quality,
+        "backend.face.w_quality",
+    );
+    sanitize_max_image_bytes(
+        &mut face.max_image_bytes,
+        defaults.max_image_bytes,
+        "backend.face.max_image_bytes",
+    );
 }
 
-// max_image_bytes 太小会让所有图都被判超限跳过整个 cull pipeline；
+// max_image_bytes 太小会让所有图都被判超限跳过整个 cull / move-text-shot pipeline；
 // 1 MiB 以下没有业务场景（JPEG 缩略图都 > 100 KiB），统一收紧到 ≥ 1 MiB。
-fn sanitize_max_image_bytes(face: &mut FaceConfig, defaults: &FaceConfig) {
+fn sanitize_max_image_bytes(value: &mut u64, fallback: u64, field: &str) {
     const MIN_IMAGE_BYTES: u64 = 1024 * 1024;
-    if face.max_image_bytes < MIN_IMAGE_BYTES {
+    if *value < MIN_IMAGE_BYTES {
         warn!(
             feature = "config",
             operation = "sanitize",
             result = "invalid_value",
-            field = "backend.face.max_image_bytes",
-            value = face.max_image_bytes,
-            fallback = defaults.max_image_bytes,
+            field,
+            value = *value,
+            fallback,
             "max_image_bytes must be >= 1 MiB; falling back to default"
         );
-        face.max_image_bytes = defaults.max_image_bytes;
+        *value = fallback;
     }
 }
 
@@ -79,6 +79,23 @@ fn load_sanitizes_zero_unique_name_max_attempts_to_default() {
     remove_env_var("TIDYMEDIA_CONFIG");
 }
 
+// 事件间隔 0 会把每个文件切成独立事件；负半径让 GPS 判定恒切分。两者回退默认。
+#[test]
+fn load_sanitizes_invalid_event_clustering_to_default() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("event.yaml");
+    std::fs::write(
+        &path,
+        "copy:\n  event_gap_minutes: 0\n  event_radius_km: -5\n",
+    )
+    .unwrap();
+    set_env_var("TIDYMEDIA_CONFIG", path.to_str().unwrap());
+    let cfg = load();
+    assert_eq!(cfg.copy.event_gap_minutes, 120);
+    assert!(cfg.copy.event_radius_km.abs() < f64::EPSILON);
+    remove_env_var("TIDYMEDIA_CONFIG");
+}
+
 // 超 ±23h 时区（chrono::FixedOffset / time::UtcOffset 在更大值上越界静默回退 UTC）：
 // sanitize 必须 warn + 回退默认 8，避免月末文件按 UTC 解释跨月归错桶且无告警。
 #[test]
@@ -389,6 +406,20 @@ fn load_sanitizes_midrange_max_image_bytes_to_default() {
     remove_env_var("TIDYMEDIA_CONFIG");
 }
 
+#[test]
+fn load_sanitizes_undersized_ocr_max_image_bytes_to_default() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("ocr_max_bytes_small.yaml");
+    std::fs::write(&path, "backend:
+  ocr:
+    max_image_bytes: 1024
+").unwrap();
+    set_env_var("TIDYMEDIA_CONFIG", path.to_str().unwrap());
+    let cfg = load();
+    assert_eq!(cfg.backend.ocr.max_image_bytes, 50 * 1024 * 1024);
+    remove_env_var("TIDYMEDIA_CONFIG");
+}
+
 #[test]
 fn load_keeps_valid_face_fields_unchanged() {
     let dir = tempfile::tempdir().unwrap();
@@ -412,3 +443,24 @@ fn load_keeps_valid_face_fields_unchanged() {
     assert!((cfg.backend.face.w_smile - 0.8).abs() < f32::EPSILON);
     remove_env_var("TIDYMEDIA_CONFIG");
 }
+
+/// 画面质量权重默认 0（opt-in）；显式开启的合法值原样保留。
+#[test]
+fn load_keeps_opted_in_quality_weights() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("face_quality_weights.yaml");
+    std::fs::write(
+        &path,
+        "backend:\n  face:\n    w_exposure: 200.0\n    w_noise: 4.0\n    \
+         w_motion_blur: 80.0\n    w_tilt: 12.0\n    w_subject_sharpness: 0.5\n",
+    )
+    .unwrap();
+    set_env_var("TIDYMEDIA_CONFIG", path.to_str().unwrap());
+    let cfg = load();
+    assert!((cfg.backend.face.w_exposure - 200.0).abs() < f32::EPSILON);
+    assert!((cfg.backend.face.w_noise - 4.0).abs() < f32::EPSILON);
+    assert!((cfg.backend.face.w_motion_blur - 80.0).abs() < f32::EPSILON);
+    assert!((cfg.backend.face.w_tilt - 12.0).abs() < f32::EPSILON);
+    assert!((cfg.backend.face.w_subject_sharpness - 0.5).abs() < f32::EPSILON);
+    remove_env_var("TIDYMEDIA_CONFIG");
+}
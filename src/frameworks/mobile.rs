@@ -133,6 +133,12 @@ pub fn tidy_find_duplicates(
             secure,
             sources: locs,
             output: None,
+            keep: Vec::new(),
+            apply: None,
+            quarantine: None,
+            similar: false,
+            similar_video: false,
+            phash_max: None,
             report: None,
         },
     )?;
@@ -178,6 +184,14 @@ fn run_copy_internal(src: &str, output: &str, dry_run: bool) -> Result<TidyStats
             sources: vec![src_loc],
             output: out_loc,
             archive_template: None,
+            name_template: None,
+            min_rating: None,
+            label: None,
+            favorite: false,
+            catalog: None,
+            skip_known: false,
+            person: None,
+            faces_db: None,
             report: None,
         },
     )?;
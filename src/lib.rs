@@ -13,9 +13,13 @@ pub use adapters::backend::factory::DefaultBackendFactory;
 pub use adapters::backend::local::LocalBackend;
 pub use adapters::backend::mtp::{MtpBackend, MtpClient, MtpMatch, MtpTarget};
 pub use adapters::backend::smb::{SmbBackend, SmbClient, SmbTarget};
-pub use adapters::cli::{Cli, Commands, run_cli};
+pub use adapters::cli::{Cli, Commands, FacesAction, run_cli};
 pub use adapters::dispatch::{CommandResult, tidy, tidy_with};
-pub use usecases::cull::{CullReport, CulledEntry, GroupReport, ScoreBreakdown};
+pub use usecases::cull::{
+    CullDecisions, CullReport, CulledEntry, DecisionFrame, FaceMark, GroupBy, GroupDecision,
+    GroupReason, GroupReport, ScoreBreakdown,
+};
+pub use usecases::faces::{FaceCluster, FacesReport};
 pub use usecases::move_text_shot::MoveTextShotReport;
 
 // ── Entity re-exports ──
@@ -31,6 +35,8 @@ pub use entities::uri::{Location, ParseError as LocationParseError};
 // Sidecar Gateway 的公开入口：协议解析在 adapters，路径名独立于 media_time 模块以
 // 体现"外部数据格式适配器"职责。
 pub use adapters::sidecar;
+// Apple Photos 图库 Gateway：同为外部数据格式适配器，copy 经 dispatch 注入 P0 候选。
+pub use adapters::photos_library;
 
 #[doc(hidden)]
 pub use adapters::backend::fake::{FakeBackend, Op as FakeOp};
@@ -41,17 +47,19 @@ pub use adapters::backend::fake::{FakeBackend, Op as FakeOp};
 pub use adapters::ocr::fake::FakeTextDetector;
 pub use usecases::ocr::TextDetector;
 
-// Face Ports（cull 子命令用）：4 个 trait + `FaceDetection` DTO 在 usecases/face；
-// 真实实现 + 4 个 build_* 装配函数 + Fake 在 adapters/face。
+// Face Ports（cull 子命令用）：4 个人脸 trait + 可选 `QualityScorer` + `FaceDetection` DTO
+// 在 usecases/face；真实实现 + 5 个 build_* 装配函数 + Fake 在 adapters/face。
 #[doc(hidden)]
 pub use adapters::face::fake::{
     FakeEyeStateClassifier, FakeFaceDetector, FakeFaceEmbedder, FakeFaceMeshDetector,
+    FakeQualityScorer,
 };
 pub use adapters::face::{
-    build_eyestate_classifier, build_facemesh, build_facenet_embedder, build_scrfd_detector,
+    build_eyestate_classifier, build_facemesh, build_facenet_embedder, build_quality_scorer,
+    build_scrfd_detector,
 };
 pub use usecases::face::{
-    EyeStateClassifier, FaceDetection, FaceDetector, FaceEmbedder, FaceMeshDetector,
+    EyeStateClassifier, FaceDetection, FaceDetector, FaceEmbedder, FaceMeshDetector, QualityScorer,
 };
 
 // uniffi 0.31 proc-macro 模式要求 setup_scaffolding! 出现在 crate 根；FFI 入口
@@ -1,41 +1,219 @@
-// 归档目录模板渲染：把 `{year}` / `{month}` / `{day}` / `{model}` / `{make}` /
-// `{valuable_name}` 等占位符替换为实际值，生成目标子目录相对路径。
+// 归档目录模板渲染：把 `{year}` / `{month}` / `{make}` / `{date:%Y-%m-%d}` 等占位符
+// 替换为实际值，生成目标子目录相对路径；`--name-template` 文件名复用同一引擎
+// （见 `render_file_name`）。
+//
+// 占位符语法：`{name[:spec][|fallback]}`
+// - `spec`：`{date:…}` 为 strftime 格式（默认 `%Y-%m-%d`）；其余占位符为大小写
+//   变换 `upper` / `lower` / `title`，只作用于取到的值，不作用于 fallback。
+// - `fallback`：值缺失或为空时的字面替代（如 `{make|unknown}`、`{valuable_name|misc}`），
+//   同样经路径段清洗；`{make|}` 表示缺失时渲染空串（该段随后被丢弃）。
 //
 // 设计决策：
-// - 不引入模板引擎（R1：保持零外部依赖），仅用 `str::replace` 顺序展开。
-// - `{model}` / `{make}` 无值时用 `"unknown"` 兜底并发 warn，对齐任务规格。
-// - `{valuable_name}` 为空串时保持原样（下游 join 会产生空路径段，由 caller 处理）。
+// - 不引入模板引擎（R1：保持零外部依赖），单次扫描按 name 派发。
+// - 无 fallback 时沿用各占位符的内置兜底：`{make}` / `{model}` / `{lens}` 为
+//   `"unknown"` 并发 warn（对齐任务规格）；`{country}` / `{mime_type}` 为
+//   `"unknown"`；`{rating}` 未评级渲染 `0`（与 `--min-rating` 同口径）；`{label}`
+//   无色标渲染 `unlabeled`；`{valuable_name}` / `{ext}` 渲染空串（空段由
+//   `clean_segments` 丢弃）。`{hash8}` 为内容 SHA-512 前 8 位十六进制，调用方
+//   未提供时兜底 `00000000`（生产路径按需计算，不会走到兜底）。`{event}` 由
+//   `usecases::event` 预扫聚类给出，未聚类（非媒体文件）时退回 `{date}`。`{person}`
+//   为人脸库里图中主体（人脸框最大）的已命名人物，无已命名人脸时为 `"unknown"`。
+//   `{album}` 为 Photos 图库相册（多个时取标题排序第一个），图库外或未入相册为
+//   `"unknown"`；`{original_name}` 对图库原图（`<uuid>.<ext>`）取导入前原名。
+// - 元数据来源的值（EXIF / XMP / 文件名）一律过 `sanitize_path_segment`；`{date:…}`
+//   的格式串来自用户模板本身，允许用 `/` 拆多级目录（如 `{date:%Y/%m}`）。
+
+use std::fmt::Write as _;
 
+use chrono::DateTime;
+use chrono::Datelike;
+use chrono::FixedOffset;
+use chrono::Timelike;
+use chrono::format::Item;
+use chrono::format::StrftimeItems;
 use tracing::warn;
 
 use crate::entities::exif::Exif;
+use crate::entities::media_time::Priority;
 
 const FEATURE_COPY: &str = "copy";
 
 /// `render` 支持的全部占位符名。`validate_archive_template` 据此拒绝未知占位符
 ///（未知名渲染时不被替换，会产生形如 `{foo}` 的字面目录段）。
-/// 单源：render 走单次扫描按 name 派发，新增占位符仅需扩这里 + render 内的 match。
-pub(crate) const PLACEHOLDERS: [&str; 6] =
-    ["year", "month", "day", "valuable_name", "make", "model"];
+/// 单源：新增占位符仅需扩这里 + `lookup` 内的 match（必要时再扩 [`ALWAYS_NON_EMPTY`]）。
+pub(crate) const PLACEHOLDERS: [&str; 24] = [
+    "year",
+    "month",
+    "day",
+    "hour",
+    "week",
+    "week_year",
+    "quarter",
+    "date",
+    "valuable_name",
+    "original_name",
+    "ext",
+    "mime_type",
+    "media_kind",
+    "source_priority",
+    "make",
+    "model",
+    "lens",
+    "country",
+    "rating",
+    "label",
+    "hash8",
+    "event",
+    "person",
+    "album",
+];
+
+/// 不带 fallback 时也保证渲染非空的占位符（缺失时有内置兜底值）。
+/// `validate_archive_template` 要求模板至少含一个，防止文件全落 output 根。
+pub(crate) const ALWAYS_NON_EMPTY: [&str; 21] = [
+    "year",
+    "month",
+    "day",
+    "hour",
+    "week",
+    "week_year",
+    "quarter",
+    "date",
+    "mime_type",
+    "media_kind",
+    "source_priority",
+    "make",
+    "model",
+    "lens",
+    "country",
+    "rating",
+    "label",
+    "hash8",
+    "event",
+    "person",
+    "album",
+];
+
+/// 非 `date` 占位符可用的 `:spec` 大小写变换。
+pub(crate) const CASE_TRANSFORMS: [&str; 3] = ["upper", "lower", "title"];
+
+/// `{date}` 未给格式时的默认 strftime 格式。
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// `{label}` 无色标时的目录名。
+const NO_LABEL: &str = "unlabeled";
+
+/// 缺失元数据的通用兜底。
+const UNKNOWN: &str = "unknown";
 
 pub struct TemplateContext<'a> {
-    pub year: &'a str,
-    pub month: &'a str,
-    pub day: &'a str,
+    /// 拍摄时间（已换算到配置时区）；`{year}` … `{quarter}` / `{date:…}` 均由此派生。
+    pub time: DateTime<FixedOffset>,
+    /// 采纳时间的来源等级（P0–P4），渲染为 `{source_priority}`。
+    pub priority: Priority,
     pub valuable_name: &'a str,
-    /// EXIF 句柄；仅在模板含 `{make}` 或 `{model}` 时被访问。
+    /// 源文件名去掉扩展名的部分；Photos 图库原图为导入前原名。
+    pub original_name: &'a str,
+    /// 源文件扩展名（不含 `.`，保留原大小写）；无扩展名为空串。
+    pub ext: &'a str,
+    /// EXIF 句柄；仅在模板含 `{make}` / `{model}` / `{lens}` / `{mime_type}` 等时被访问。
     pub exif: Option<&'a Exif>,
+    /// XMP 星级（sidecar 优先）；-1 为拒绝。
+    pub rating: Option<i8>,
+    /// XMP 色标（sidecar 优先）。
+    pub label: Option<&'a str>,
+    /// SHA-512 前 8 位十六进制；仅模板引用 `{hash8}` 时由调用方计算，否则为 `None`。
+    pub hash8: Option<&'a str>,
+    /// 事件名（如 `2024-05-01_Hangzhou`）；仅模板引用 `{event}` 时预扫聚类，否则为 `None`。
+    pub event: Option<&'a str>,
+    /// 图中主体的已命名人物；仅模板引用 `{person}` 时查人脸库，否则为 `None`。
+    pub person: Option<&'a str>,
+    /// Photos 图库相册；仅模板引用 `{album}` 时查图库，否则为 `None`。
+    pub album: Option<&'a str>,
+}
+
+/// 单个 `{…}` 记号的拆解结果：`name[:spec][|fallback]`。
+/// 先按首个 `|` 切出 fallback，再按首个 `:` 切出 spec——`{date:%H:%M}` 的格式串
+/// 可含 `:`，fallback 可含 `:`，但 spec 不能含 `|`。
+pub(crate) struct Placeholder<'a> {
+    pub name: &'a str,
+    pub spec: Option<&'a str>,
+    pub fallback: Option<&'a str>,
+}
+
+impl<'a> Placeholder<'a> {
+    pub(crate) fn parse(token: &'a str) -> Self {
+        let (head, fallback) = match token.split_once('|') {
+            Some((head, fallback)) => (head, Some(fallback)),
+            None => (token, None),
+        };
+        let (name, spec) = match head.split_once(':') {
+            Some((name, spec)) => (name, Some(spec)),
+            None => (head, None),
+        };
+        Self {
+            name,
+            spec,
+            fallback,
+        }
+    }
+
+    /// 语法校验：占位符名已知、spec 合法（`date` 为可解析的 strftime，其余为大小写变换）。
+    ///
+    /// # Errors
+    ///
+    /// 返回不带 `archive_template` 前缀的错误描述，由调用方拼接。
+    pub(crate) fn check(&self) -> Result<(), String> {
+        // `{gps_country}`（按 EXIF GPS 反查国家）需离线地理边界数据，未实现；
+        // 报错时指向读 XMP 的 `{country}`。
+        if self.name == "gps_country" {
+            return Err(
+                "unknown placeholder {gps_country}: reverse geocoding EXIF GPS is not \
+                 supported, use {country} (XMP photoshop:Country) instead"
+                    .into(),
+            );
+        }
+        if !PLACEHOLDERS.contains(&self.name) {
+            return Err(format!("unknown placeholder {{{}}}", self.name));
+        }
+        match (self.name, self.spec) {
+            (_, None) => Ok(()),
+            ("date", Some(format)) => check_strftime(format),
+            (_, Some(case)) if CASE_TRANSFORMS.contains(&case) => Ok(()),
+            (name, Some(spec)) => Err(format!(
+                "unsupported format `{spec}` for {{{name}}} (expected upper/lower/title)"
+            )),
+        }
+    }
+
+    /// 渲染结果是否恒非空：显式 fallback 时看 fallback 本身，否则看内置兜底。
+    pub(crate) fn always_non_empty(&self) -> bool {
+        match self.fallback {
+            Some(fallback) => !fallback.trim().is_empty(),
+            None => ALWAYS_NON_EMPTY.contains(&self.name),
+        }
+    }
+}
+
+// chrono 遇到未知 `%` 说明符产出 `Item::Error`，格式化时才报错；这里提前拒绝。
+fn check_strftime(format: &str) -> Result<(), String> {
+    if format.is_empty() {
+        return Err("empty date format in {date:}".into());
+    }
+    if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+        return Err(format!(
+            "invalid date format `{format}` in {{date:{format}}}"
+        ));
+    }
+    Ok(())
 }
 
 /// 渲染归档模板，返回去掉末尾空段的相对路径字符串。
 ///
-/// 单次扫描模板字面量按 `{name}` 派发；相比旧版逐占位符 `str::replace` 减少 6
-/// 次 String 中间分配。`{make}` / `{model}` 仍懒访问 EXIF（首次遇到时缓存）。
-/// 未知占位符按 `validate_archive_template` 早期拒绝，此处仍按原 `str::replace`
-/// 行为原样保留 `{name}` 字面段（防御性兜底，与旧版字符串等价）。
+/// 单次扫描模板字面量按 `{…}` 记号派发；EXIF 字段懒访问（仅模板引用时读取）。
+/// 未知占位符按 `validate_archive_template` 早期拒绝，此处仍原样保留 `{…}`
+/// 字面段（防御性兜底，与旧 `str::replace` 不命中行为等价）。
 pub fn render(template: &str, ctx: &TemplateContext<'_>) -> String {
-    let mut make: Option<String> = None;
-    let mut model: Option<String> = None;
     let mut out = String::with_capacity(template.len());
     let mut rest = template;
     while let Some(open) = rest.find('{') {
@@ -47,30 +225,14 @@ pub fn render(template: &str, ctx: &TemplateContext<'_>) -> String {
             out.push_str(tail);
             return clean_segments(&out);
         };
-        let name = &tail[..close];
-        let resolved: Option<&str> = match name {
-            "year" => Some(ctx.year),
-            "month" => Some(ctx.month),
-            "day" => Some(ctx.day),
-            "valuable_name" => Some(ctx.valuable_name),
-            "make" => Some(
-                make.get_or_insert_with(|| read_exif_field(ctx.exif, Exif::make, "make"))
-                    .as_str(),
-            ),
-            "model" => Some(
-                model
-                    .get_or_insert_with(|| read_exif_field(ctx.exif, Exif::model, "model"))
-                    .as_str(),
-            ),
-            _ => None,
-        };
-        if let Some(v) = resolved {
-            out.push_str(v);
+        let token = &tail[..close];
+        if let Some(v) = expand(&Placeholder::parse(token), ctx) {
+            out.push_str(&v);
         } else {
-            // 未知占位符（validate_archive_template 已早期拒绝）：原样保留 `{name}`
-            // 字面段，与旧 `str::replace` 不命中行为等价，作为防御性兜底。
+            // 未知占位符（validate_archive_template 已早期拒绝）：原样保留 `{…}`
+            // 字面段，作为防御性兜底。
             out.push('{');
-            out.push_str(name);
+            out.push_str(token);
             out.push('}');
         }
         rest = &tail[close + 1..];
@@ -79,6 +241,118 @@ pub fn render(template: &str, ctx: &TemplateContext<'_>) -> String {
     clean_segments(&out)
 }
 
+/// 渲染文件名模板：同 [`render`] 的占位符语义，但结果是单个路径段——
+/// `{date:%Y/%m}` 等产生的 `/` / `\` 一律替换为 `_`，不会拆出子目录。
+pub fn render_file_name(template: &str, ctx: &TemplateContext<'_>) -> String {
+    render(template, ctx).replace(['/', '\\'], "_")
+}
+
+// 取值 → 大小写变换；缺失或空值走显式 fallback，否则走内置兜底。未知名返 None。
+fn expand(ph: &Placeholder<'_>, ctx: &TemplateContext<'_>) -> Option<String> {
+    let value = lookup(ph, ctx)?.filter(|v| !v.is_empty());
+    let rendered = match (value, ph.fallback) {
+        (Some(v), _) if ph.name == "date" => v,
+        (Some(v), _) => apply_case(v, ph.spec),
+        (None, Some(fallback)) => sanitize_path_segment(fallback),
+        (None, None) => default_value(ph.name),
+    };
+    Some(rendered)
+}
+
+// 外层 None = 未知占位符；内层 None = 该项元数据缺失。
+fn lookup(ph: &Placeholder<'_>, ctx: &TemplateContext<'_>) -> Option<Option<String>> {
+    let t = &ctx.time;
+    let exif_str =
+        |get: fn(&Exif) -> Option<&str>| ctx.exif.and_then(get).map(sanitize_path_segment);
+    let value = match ph.name {
+        "year" => Some(t.year().to_string()),
+        "month" => Some(format!("{:02}", t.month())),
+        "day" => Some(format!("{:02}", t.day())),
+        "hour" => Some(format!("{:02}", t.hour())),
+        // ISO 周与 ISO 年配对：年末 / 年初几天的 `{week}` 属于相邻年份，与 `{year}` 不一致。
+        "week" => Some(format!("{:02}", t.iso_week().week())),
+        "week_year" => Some(t.iso_week().year().to_string()),
+        "quarter" => Some(format!("Q{}", (t.month() - 1) / 3 + 1)),
+        "date" => Some(format_date(t, ph.spec.unwrap_or(DEFAULT_DATE_FORMAT))),
+        "valuable_name" => Some(ctx.valuable_name.to_string()),
+        "original_name" => Some(sanitize_path_segment(ctx.original_name)),
+        "ext" => Some(sanitize_path_segment(ctx.ext)),
+        "mime_type" => ctx.exif.map(|e| sanitize_path_segment(e.mime_type())),
+        "media_kind" => Some(ctx.exif.map_or("other", Exif::media_kind).to_string()),
+        "source_priority" => Some(ctx.priority.as_str().to_string()),
+        "make" => exif_str(Exif::make),
+        "model" => exif_str(Exif::model),
+        "lens" => exif_str(Exif::lens),
+        "country" => exif_str(Exif::country),
+        "rating" => ctx.rating.map(|r| r.to_string()),
+        "label" => ctx.label.map(|l| sanitize_path_segment(l.trim())),
+        "hash8" => ctx.hash8.map(str::to_string),
+        "event" => Some(ctx.event.map_or_else(
+            || format_date(t, DEFAULT_DATE_FORMAT),
+            sanitize_path_segment,
+        )),
+        "person" => ctx.person.map(|p| sanitize_path_segment(p.trim())),
+        "album" => ctx.album.map(|a| sanitize_path_segment(a.trim())),
+        _ => return None,
+    };
+    Some(value)
+}
+
+// strftime 渲染；格式已由 validate 把关，此处出错（理论不可达）退默认格式。
+fn format_date(t: &DateTime<FixedOffset>, format: &str) -> String {
+    let mut out = String::new();
+    if write!(out, "{}", t.format_with_items(StrftimeItems::new(format))).is_err() {
+        out.clear();
+        let _ = write!(out, "{}", t.format(DEFAULT_DATE_FORMAT));
+    }
+    out
+}
+
+fn apply_case(value: String, spec: Option<&str>) -> String {
+    match spec {
+        Some("upper") => value.to_uppercase(),
+        Some("lower") => value.to_lowercase(),
+        Some("title") => title_case(&value),
+        _ => value,
+    }
+}
+
+// 词首大写、其余小写；空白 / `_` / `-` 视为词边界（`EOS R5` → `Eos R5`）。
+fn title_case(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut at_word_start = true;
+    for c in s.chars() {
+        if at_word_start {
+            out.extend(c.to_uppercase());
+        } else {
+            out.extend(c.to_lowercase());
+        }
+        at_word_start = c.is_whitespace() || c == '_' || c == '-';
+    }
+    out
+}
+
+// 无 fallback 时的内置兜底；EXIF 相机字段缺失发 warn（对齐任务规格）。
+fn default_value(name: &str) -> String {
+    match name {
+        "make" | "model" | "lens" => {
+            warn!(
+                feature = FEATURE_COPY,
+                operation = "render_template",
+                result = "missing_exif_field",
+                placeholder = name,
+                "EXIF field not found; substituting \"unknown\""
+            );
+            UNKNOWN.to_string()
+        }
+        "mime_type" | "country" | "person" | "album" => UNKNOWN.to_string(),
+        "hash8" => "00000000".to_string(),
+        "rating" => "0".to_string(),
+        "label" => NO_LABEL.to_string(),
+        _ => String::new(),
+    }
+}
+
 // 过滤空段（如 `{valuable_name}` 渲染空串后产生的空组件），拼回斜杠分隔路径。
 fn clean_segments(s: &str) -> String {
     s.split('/')
@@ -87,31 +361,11 @@ fn clean_segments(s: &str) -> String {
         .join("/")
 }
 
-// {make} / {model} 共用的字段读取：无值（EXIF 缺失或字段空串）→ warn + "unknown"。
-// fn 指针入参（`Exif::make` / `Exif::model`）消除两份同体函数。
-fn read_exif_field(
-    exif: Option<&Exif>,
-    get: fn(&Exif) -> Option<&str>,
-    placeholder: &'static str,
-) -> String {
-    let value = exif.and_then(|e| get(e).filter(|s| !s.is_empty()));
-    if value.is_none() {
-        warn!(
-            feature = FEATURE_COPY,
-            operation = "render_template",
-            result = "missing_exif_field",
-            placeholder,
-            "EXIF field not found; substituting \"unknown\""
-        );
-    }
-    sanitize_path_segment(value.unwrap_or("unknown"))
-}
-
-/// 清洗 EXIF 字面值，防止 `{make}` / `{model}` 模板下 EXIF `Make="../evil"` 这类
+/// 清洗 EXIF / XMP 字面值，防止 `{make}` / `{model}` 模板下 EXIF `Make="../evil"` 这类
 /// 恶意值经字面 replace 渗入路径后跨目录写入：路径分隔符、控制字符、`NUL` 一律
 /// 替换为 `_`；纯 `.` 段（包括 `.` / `..` / `...`）整段替换为 `_`，避免与
 /// `naming::generate_unique_name` 的逐段 `Utf8PathBuf::join` 拼出 `output/..`。
-/// `#[inline(never)]`：opt-level=0 时小函数仍可能被 LLVM 内联到 `read_exif_field`
+/// `#[inline(never)]`：opt-level=0 时小函数仍可能被 LLVM 内联到 `lookup`
 /// 让 region instrumentation 丢失，强制独立 codegen 保证覆盖率工具能看到本函数行。
 #[inline(never)]
 fn sanitize_path_segment(s: &str) -> String {
@@ -1,21 +1,43 @@
 #[cfg(test)]
 mod test_render {
+    use chrono::{FixedOffset, TimeZone};
+
     use crate::entities::exif::Exif;
-    use crate::usecases::archive_template::{TemplateContext, render};
+    use crate::entities::media_time::Priority;
+    use crate::usecases::archive_template::{TemplateContext, render, render_file_name};
 
+    // 按 "YYYY" / "MM" / "DD" 字面构造 09:30 +08:00 的拍摄时间，保持旧用例可读。
     fn ctx<'a>(
-        year: &'a str,
-        month: &'a str,
-        day: &'a str,
+        year: &str,
+        month: &str,
+        day: &str,
         valuable_name: &'a str,
         exif: Option<&'a Exif>,
     ) -> TemplateContext<'a> {
+        let time = FixedOffset::east_opt(8 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(
+                year.parse().unwrap(),
+                month.parse().unwrap(),
+                day.parse().unwrap(),
+                9,
+                30,
+                0,
+            )
+            .unwrap();
         TemplateContext {
-            year,
-            month,
-            day,
+            time,
+            priority: Priority::P0,
             valuable_name,
+            original_name: "IMG_0001",
+            ext: "JPG",
             exif,
+            rating: None,
+            label: None,
+            hash8: None,
+            event: None,
+            person: None,
+            album: None,
         }
     }
 
@@ -159,4 +181,173 @@ mod test_render {
         let result = render("{year}/{foo}/x", &c);
         assert_eq!(result, "2024/{foo}/x");
     }
+
+    #[test]
+    fn render_rating_and_label() {
+        let c = TemplateContext {
+            rating: Some(4),
+            label: Some("Red"),
+            ..ctx("2024", "01", "15", "", None)
+        };
+        assert_eq!(render("{rating}/{label}/{year}", &c), "4/Red/2024");
+    }
+
+    /// 未评级 → `0`；无色标 / 空白色标 → `unlabeled`；拒绝照片渲染 `-1`。
+    #[test]
+    fn render_rating_and_label_defaults() {
+        let c = ctx("2024", "01", "15", "", None);
+        assert_eq!(render("{rating}/{label}", &c), "0/unlabeled");
+        let c = TemplateContext {
+            rating: Some(-1),
+            label: Some("  "),
+            ..ctx("2024", "01", "15", "", None)
+        };
+        assert_eq!(render("{rating}/{label}", &c), "-1/unlabeled");
+    }
+
+    /// 色标是用户自由文本，同 make/model 走路径段清洗。
+    #[test]
+    fn render_label_sanitizes_path_separators() {
+        let c = TemplateContext {
+            label: Some("../evil"),
+            ..ctx("2024", "01", "15", "", None)
+        };
+        assert_eq!(render("{label}", &c), ".._evil");
+    }
+
+    #[test]
+    fn render_time_placeholders() {
+        let c = ctx("2024", "05", "01", "", None);
+        assert_eq!(
+            render("{year}/{quarter}/W{week}/{day}-{hour}", &c),
+            "2024/Q2/W18/01-09"
+        );
+        let c = ctx("2024", "12", "31", "", None);
+        // ISO 周：2024-12-31 属于 2025 年第 1 周，`{week_year}` 给出配对的 ISO 年。
+        assert_eq!(render("{quarter}/{week}", &c), "Q4/01");
+        assert_eq!(render("{year}/{week_year}-W{week}", &c), "2024/2025-W01");
+    }
+
+    #[test]
+    fn render_date_with_strftime_format() {
+        let c = ctx("2024", "05", "01", "", None);
+        assert_eq!(render("{date}", &c), "2024-05-01");
+        assert_eq!(render("{date:%Y%m%d_%H%M}", &c), "20240501_0930");
+        // 格式串里的 `/` 来自用户模板，允许拆多级目录；`:` 不截断格式。
+        assert_eq!(render("{date:%Y/%m}/{date:%H:%M}", &c), "2024/05/09:30");
+    }
+
+    #[test]
+    fn render_file_and_source_placeholders() {
+        let exif = Exif::with_mime("video/mp4");
+        let c = TemplateContext {
+            priority: Priority::P4,
+            ..ctx("2024", "01", "15", "", Some(&exif))
+        };
+        assert_eq!(
+            render(
+                "{media_kind}/{mime_type}/{source_priority}/{original_name}.{ext}",
+                &c
+            ),
+            "video/video_mp4/P4/IMG_0001.JPG"
+        );
+        // 无 EXIF：media_kind 归 other，mime_type 兜底 unknown。
+        let c = ctx("2024", "01", "15", "", None);
+        assert_eq!(render("{media_kind}/{mime_type}", &c), "other/unknown");
+    }
+
+    #[test]
+    fn render_lens_and_country() {
+        let exif = Exif::with_mime("image/jpeg").with_lens_country("RF24-70mm F2.8", "Japan");
+        let c = ctx("2024", "01", "15", "", Some(&exif));
+        assert_eq!(render("{country}/{lens}", &c), "Japan/RF24-70mm F2.8");
+        let c = ctx("2024", "01", "15", "", None);
+        assert_eq!(render("{country}/{lens}", &c), "unknown/unknown");
+    }
+
+    /// `|fallback` 只在值缺失 / 为空时生效，且同样过路径段清洗；空 fallback 让段消失。
+    #[test]
+    fn render_fallback_replaces_missing_values_only() {
+        let exif = Exif::with_mime("image/jpeg").with_make_model("Sony", "");
+        let c = ctx("2024", "01", "15", "", Some(&exif));
+        assert_eq!(
+            render("{make|NoMake}/{model|NoModel}/{valuable_name|misc}", &c),
+            "Sony/NoModel/misc"
+        );
+        assert_eq!(render("{year}/{lens|}/{label|a/b}", &c), "2024/a_b");
+    }
+
+    /// 大小写变换作用于取到的值；fallback 原样输出；`{date:…}` 的 spec 是格式串。
+    #[test]
+    fn render_case_transforms() {
+        let exif = Exif::with_mime("image/jpeg").with_make_model("NIKON CORPORATION", "z 9");
+        let c = ctx("2024", "01", "15", "", Some(&exif));
+        assert_eq!(
+            render("{make:lower}/{model:upper}/{ext:lower}", &c),
+            "nikon corporation/Z 9/jpg"
+        );
+        assert_eq!(render("{make:title}", &c), "Nikon Corporation");
+        let c = ctx("2024", "01", "15", "", None);
+        assert_eq!(render("{lens:upper|Mixed}", &c), "Mixed");
+    }
+
+    /// 文件名模板：`/` 不拆目录而折成 `_`；`{hash8}` 取调用方给的内容摘要前缀。
+    #[test]
+    fn render_file_name_flattens_separators_and_uses_hash8() {
+        let exif = Exif::with_mime("image/jpeg").with_make_model("Canon", "EOS R5");
+        let c = TemplateContext {
+            hash8: Some("0a1b2c3d"),
+            ..ctx("2024", "05", "01", "", Some(&exif))
+        };
+        assert_eq!(
+            render_file_name("{date:%Y%m%d_%H%M%S}_{make}_{hash8}.{ext:lower}", &c),
+            "20240501_093000_Canon_0a1b2c3d.jpg"
+        );
+        assert_eq!(
+            render_file_name("{date:%Y/%m}-{original_name}", &c),
+            "2024_05-IMG_0001"
+        );
+    }
+
+    /// `{event}` 取聚类给出的事件名并清洗路径分隔符；未聚类时退回日期。
+    #[test]
+    fn render_event_uses_cluster_name_or_falls_back_to_date() {
+        let c = TemplateContext {
+            event: Some("2024-05-01_Hang/zhou"),
+            ..ctx("2024", "05", "01", "", None)
+        };
+        assert_eq!(render("{year}/{event}", &c), "2024/2024-05-01_Hang_zhou");
+        let c = ctx("2024", "05", "01", "", None);
+        assert_eq!(render("{year}/{event}", &c), "2024/2024-05-01");
+    }
+
+    /// `{person}` 取主体人物名并清洗；无已命名人脸时为 `unknown`，fallback 优先。
+    #[test]
+    fn render_person_uses_primary_name_or_unknown() {
+        let c = TemplateContext {
+            person: Some(" Alice/Bob "),
+            ..ctx("2024", "05", "01", "", None)
+        };
+        assert_eq!(
+            render("People/{person}/{year}", &c),
+            "People/Alice_Bob/2024"
+        );
+        assert_eq!(render("{person:upper}", &c), "ALICE_BOB");
+        let c = ctx("2024", "05", "01", "", None);
+        assert_eq!(render("People/{person}", &c), "People/unknown");
+        assert_eq!(render("People/{person|strangers}", &c), "People/strangers");
+    }
+
+    /// `{album}` 取 Photos 相册并清洗；图库外或未入相册为 `unknown`，fallback 优先。
+    #[test]
+    fn render_album_uses_photos_album_or_unknown() {
+        let c = TemplateContext {
+            album: Some("Trip/2024"),
+            ..ctx("2024", "05", "01", "", None)
+        };
+        assert_eq!(render("{album}/{year}", &c), "Trip_2024/2024");
+        let c = ctx("2024", "05", "01", "", None);
+        assert_eq!(render("{album}", &c), "unknown");
+        assert_eq!(render("{album|loose}", &c), "loose");
+    }
 }
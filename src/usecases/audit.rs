@@ -0,0 +1,249 @@
+//! `audit` 子命令：只读扫描，在归档时间线上找"时间明显不对"的文件簇。
+//!
+//! - `fs_mtime_cluster`：某月内大部分文件只能靠 mtime（P4）定时间——EXIF 在转存中丢失
+//! - `future_date`：最权威的候选时间在未来（相机日期未设置）；resolve 会剔除它并回退，
+//!   所以这里看原始候选而非判定结果
+//! - `epoch_default`：最权威的候选落在 1970-01-01 / 1980-01-01 / 2000-01-01——相机
+//!   复位后的默认时钟
+//! - `gap`：首末月之间连续 `gap_months` 个月以上没有任何文件
+//!
+//! 月份与 `stats` / 归档目录同口径，按 `copy.timezone_offset_hours` 切分。
+
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, Utc};
+use serde_derive::Serialize;
+use tracing::debug;
+
+use super::copy::Source;
+use super::copy::run::configured_chrono_offset;
+use super::find::escape_py_string;
+use crate::entities::file_index::{CandidateProvider, Index};
+use crate::entities::file_info::Info;
+use crate::entities::media_time::filter::{Validity, classify};
+use crate::entities::media_time::{Candidate, Priority};
+use crate::usecases::config::config;
+
+const FEATURE_AUDIT: &str = "audit";
+
+/// 某月 P4 文件占比达到此百分比才算"丢了 EXIF"的簇。
+const FS_MTIME_SHARE_PERCENT: usize = 90;
+
+/// 相机 / 文件系统复位后的默认日期（月, 日 固定为 1 月 1 日）。
+const EPOCH_DEFAULT_YEARS: [i32; 3] = [1970, 1980, 2000];
+
+/// 异常类别；JSON 中为 snake_case 字符串。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditKind {
+    FsMtimeCluster,
+    FutureDate,
+    EpochDefault,
+    Gap,
+}
+
+impl AuditKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            AuditKind::FsMtimeCluster => "fs_mtime_cluster",
+            AuditKind::FutureDate => "future_date",
+            AuditKind::EpochDefault => "epoch_default",
+            AuditKind::Gap => "gap",
+        }
+    }
+}
+
+/// 单条发现。`key` 随类别而定：簇为 `YYYY-MM`，默认日期为 `YYYY-01-01`，
+/// 空档为 `YYYY-MM..YYYY-MM`，未来时间为 `future`。`files` 为命中文件数（空档为 0）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AuditFinding {
+    pub kind: AuditKind,
+    pub key: String,
+    pub files: usize,
+    /// 命中文件的完整路径，按路径序排列。
+    pub paths: Vec<String>,
+}
+
+/// audit 报告。`scanned` = walker 触达文件总数（含空文件 / 读不到的）；
+/// 只审计媒体文件，`findings` 按 (类别, key) 排序。
+#[derive(Debug, Default, Serialize)]
+pub struct AuditReport {
+    pub scanned: usize,
+    pub media_files: usize,
+    pub findings: Vec<AuditFinding>,
+}
+
+/// 审计阈值：`min_cluster` = P4 簇的最小文件数；`gap_months` = 报告空档的最小连续月数。
+#[derive(Debug, Clone, Copy)]
+pub struct AuditOpts {
+    pub min_cluster: usize,
+    pub gap_months: u32,
+}
+
+// 单月累计：总文件数 + 只能靠 mtime 定时间的文件路径。
+#[derive(Default)]
+struct Month {
+    files: usize,
+    fs_mtime: Vec<String>,
+}
+
+/// 入口：扫 sources，审计媒体文件的时间线并把发现打印到 stdout。不修改任何文件。
+pub fn audit(
+    sources: &[Source],
+    opts: AuditOpts,
+    sidecar: Option<CandidateProvider>,
+) -> AuditReport {
+    let offset = configured_chrono_offset();
+    let mut index = Index::new();
+    for (loc, backend) in sources {
+        index.visit_location(loc, backend);
+    }
+    index.parse_exif(offset);
+    if let Some(provider) = sidecar {
+        index.enrich_candidates(provider);
+    }
+    let visit = index.stats();
+    let skipped = visit.skipped_empty + visit.skipped_unreadable + visit.walker_errors;
+
+    let mut files: Vec<&Info> = index.files().values().filter(|i| i.is_media()).collect();
+    files.sort_by(|a, b| a.full_path.cmp(&b.full_path));
+    let report = AuditReport {
+        scanned: index.files().len() + usize::try_from(skipped).unwrap_or(usize::MAX),
+        media_files: files.len(),
+        findings: analyse(&files, opts, offset, Utc::now()),
+    };
+    debug!(
+        feature = FEATURE_AUDIT,
+        operation = "summary",
+        result = "ok",
+        scanned = report.scanned,
+        media_files = report.media_files,
+        findings = report.findings.len(),
+        "timeline audit finished"
+    );
+    render_findings(&report.findings, &mut std::io::stdout());
+    report
+}
+
+// `files` 已按路径排序，各发现的 paths 随之有序。
+fn analyse(
+    files: &[&Info],
+    opts: AuditOpts,
+    offset: FixedOffset,
+    now: DateTime<Utc>,
+) -> Vec<AuditFinding> {
+    let valid_secs = config().exif.valid_date_time_secs;
+    let mut months: BTreeMap<i32, Month> = BTreeMap::new();
+    let mut flagged: BTreeMap<(AuditKind, String), Vec<String>> = BTreeMap::new();
+    for info in files {
+        let path = info.full_path.to_string();
+        if let Some(claim) = primary_claim(&info.time_candidates(offset)) {
+            if classify(claim.utc, now) == Validity::RejectFuture {
+                flagged
+                    .entry((AuditKind::FutureDate, "future".to_owned()))
+                    .or_default()
+                    .push(path.clone());
+            } else if let Some(day) = epoch_default_day(claim.utc, offset) {
+                flagged
+                    .entry((AuditKind::EpochDefault, day))
+                    .or_default()
+                    .push(path.clone());
+            }
+        }
+        let (time, priority) = info.create_time_with_priority(valid_secs, offset);
+        let local = DateTime::<Utc>::from(time).with_timezone(&offset);
+        let month = months.entry(month_index(local.date_naive())).or_default();
+        month.files += 1;
+        if priority == Priority::P4 {
+            month.fs_mtime.push(path);
+        }
+    }
+
+    let mut findings: Vec<AuditFinding> = flagged
+        .into_iter()
+        .map(|((kind, key), paths)| AuditFinding {
+            kind,
+            key,
+            files: paths.len(),
+            paths,
+        })
+        .collect();
+    findings.extend(fs_mtime_clusters(&months, opts.min_cluster));
+    findings.extend(gaps(&months, opts.gap_months));
+    findings.sort_by(|a, b| (a.kind, &a.key).cmp(&(b.kind, &b.key)));
+    findings
+}
+
+// 最权威（等级最小）的候选；同级取首个，与候选组装顺序（EXIF → 文件名 → sidecar）一致。
+fn primary_claim(candidates: &[Candidate]) -> Option<&Candidate> {
+    candidates.iter().min_by_key(|c| c.priority())
+}
+
+// UTC 与配置时区任一落在默认日即命中：1970 epoch 是 UTC 零点，相机默认时钟是本地零点。
+fn epoch_default_day(utc: DateTime<Utc>, offset: FixedOffset) -> Option<String> {
+    [utc.date_naive(), utc.with_timezone(&offset).date_naive()]
+        .into_iter()
+        .find(|d| d.month() == 1 && d.day() == 1 && EPOCH_DEFAULT_YEARS.contains(&d.year()))
+        .map(|d| d.format("%Y-%m-%d").to_string())
+}
+
+fn fs_mtime_clusters(
+    months: &BTreeMap<i32, Month>,
+    min_cluster: usize,
+) -> impl Iterator<Item = AuditFinding> + '_ {
+    months
+        .iter()
+        .filter(move |(_, m)| {
+            m.fs_mtime.len() >= min_cluster
+                && m.fs_mtime.len() * 100 >= m.files * FS_MTIME_SHARE_PERCENT
+        })
+        .map(|(&idx, m)| AuditFinding {
+            kind: AuditKind::FsMtimeCluster,
+            key: month_key(idx),
+            files: m.fs_mtime.len(),
+            paths: m.fs_mtime.clone(),
+        })
+}
+
+// 相邻两个有文件的月份之间缺 ≥ gap_months 个月即报一段空档。
+fn gaps(months: &BTreeMap<i32, Month>, gap_months: u32) -> Vec<AuditFinding> {
+    let present: Vec<i32> = months.keys().copied().collect();
+    present
+        .windows(2)
+        .filter(|w| (w[1] - w[0] - 1).unsigned_abs() >= gap_months.max(1))
+        .map(|w| AuditFinding {
+            kind: AuditKind::Gap,
+            key: format!("{}..{}", month_key(w[0] + 1), month_key(w[1] - 1)),
+            files: 0,
+            paths: Vec::new(),
+        })
+        .collect()
+}
+
+// 月序号 = 年 × 12 + (月 - 1)：相邻月份差 1，跨年无需特判。
+fn month_index(date: NaiveDate) -> i32 {
+    date.year() * 12 + i32::try_from(date.month0()).unwrap_or(0)
+}
+
+fn month_key(index: i32) -> String {
+    format!(
+        "{:04}-{:02}",
+        index.div_euclid(12),
+        index.rem_euclid(12) + 1
+    )
+}
+
+// 每条发现一个标题行，其下逐行列出带引号的路径（与 find 脚本同一转义口径）。
+fn render_findings(findings: &[AuditFinding], sink: &mut impl Write) {
+    for f in findings {
+        let _ = writeln!(sink, "## {} {} ({} files)", f.kind.as_str(), f.key, f.files);
+        for path in &f.paths {
+            let _ = writeln!(sink, "\"{}\"", escape_py_string(path));
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "audit_tests.rs"]
+mod tests;
@@ -0,0 +1,128 @@
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use camino::Utf8PathBuf;
+use chrono::TimeZone;
+use tempfile::tempdir;
+
+use super::*;
+use crate::adapters::backend::local::LocalBackend;
+use crate::entities::backend::Backend;
+use crate::entities::test_common as tc;
+use crate::entities::uri::Location;
+
+// 复制 fixture 并钉 mtime：EXIF 被剔除 / 低于阈值的文件回退到同一个月。
+fn copy_pinned(src: &str, dir: &Path, name: &str) {
+    let dst = dir.join(name);
+    fs::copy(src, &dst).unwrap();
+    let ts = filetime::FileTime::from_unix_time(tc::FIXED_MEDIA_MTIME, 0);
+    filetime::set_file_mtime(&dst, ts).unwrap();
+}
+
+fn offset() -> FixedOffset {
+    FixedOffset::east_opt(8 * 3600).unwrap()
+}
+
+/// 2099 EXIF、1980 复位时钟、纯 mtime 文件同落 2024-01 成簇；与 2017 的文件之间报空档。
+#[test]
+fn audit_flags_future_epoch_default_mtime_cluster_and_gap() {
+    let dir = tempdir().unwrap();
+    copy_pinned(
+        &format!("{}/sample-future-2099.jpg", tc::DATA_DIR),
+        dir.path(),
+        "future.jpg",
+    );
+    copy_pinned(
+        &format!("{}/sample-pre-1995.jpg", tc::DATA_DIR),
+        dir.path(),
+        "reset.jpg",
+    );
+    tc::copy_png_to(dir.path(), "fallback.png").unwrap();
+    fs::copy(tc::DATA_PNG_EXIF, dir.path().join("canon.png")).unwrap();
+    fs::write(dir.path().join("notes.txt"), b"not media").unwrap();
+    let backend: Arc<dyn Backend> = LocalBackend::arc();
+    let root = Location::Local(Utf8PathBuf::from(dir.path().to_str().unwrap()));
+
+    let opts = AuditOpts {
+        min_cluster: 3,
+        gap_months: 12,
+    };
+    let report = audit(&[(root, backend)], opts, None);
+
+    assert_eq!((report.scanned, report.media_files), (5, 4));
+    let summary: Vec<(AuditKind, &str, usize)> = report
+        .findings
+        .iter()
+        .map(|f| (f.kind, f.key.as_str(), f.files))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (AuditKind::FsMtimeCluster, "2024-01", 3),
+            (AuditKind::FutureDate, "future", 1),
+            (AuditKind::EpochDefault, "1980-01-01", 1),
+            (AuditKind::Gap, "2017-03..2023-12", 0),
+        ]
+    );
+    assert!(report.findings[1].paths[0].ends_with("future.jpg"));
+    assert!(report.findings[2].paths[0].ends_with("reset.jpg"));
+}
+
+#[test]
+fn epoch_default_day_matches_utc_or_local_midnight_defaults() {
+    let utc_epoch = Utc.timestamp_opt(0, 0).unwrap();
+    assert_eq!(
+        epoch_default_day(utc_epoch, offset()).as_deref(),
+        Some("1970-01-01")
+    );
+    // 本地 2000-01-01 00:00 (+8) = UTC 1999-12-31 16:00。
+    let local_reset = Utc.with_ymd_and_hms(1999, 12, 31, 16, 0, 0).unwrap();
+    assert_eq!(
+        epoch_default_day(local_reset, offset()).as_deref(),
+        Some("2000-01-01")
+    );
+    let new_year = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    assert_eq!(epoch_default_day(new_year, offset()), None);
+}
+
+/// 空档只在相邻有文件月份之间计算，跨年连续编号；gap_months = 0 按 1 处理。
+#[test]
+fn gaps_reports_runs_of_empty_months_at_least_threshold() {
+    let mut months: BTreeMap<i32, Month> = BTreeMap::new();
+    for date in [(2022, 11), (2023, 1), (2024, 6)] {
+        let d = NaiveDate::from_ymd_opt(date.0, date.1, 1).unwrap();
+        months.insert(month_index(d), Month::default());
+    }
+    let keys = |gap| -> Vec<String> { gaps(&months, gap).into_iter().map(|f| f.key).collect() };
+    assert_eq!(keys(12), ["2023-02..2024-05"]);
+    assert_eq!(keys(0), ["2022-12..2022-12", "2023-02..2024-05"]);
+    assert_eq!(
+        month_key(month_index(NaiveDate::from_ymd_opt(2024, 12, 1).unwrap())),
+        "2024-12"
+    );
+}
+
+#[test]
+fn render_findings_lists_quoted_paths_under_each_heading() {
+    let findings = [
+        AuditFinding {
+            kind: AuditKind::FutureDate,
+            key: "future".into(),
+            files: 1,
+            paths: vec!["/a/\"x\".jpg".into()],
+        },
+        AuditFinding {
+            kind: AuditKind::Gap,
+            key: "2020-01..2020-12".into(),
+            files: 0,
+            paths: Vec::new(),
+        },
+    ];
+    let mut out = Vec::new();
+    render_findings(&findings, &mut out);
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "## future_date future (1 files)\n\"/a/\\\"x\\\".jpg\"\n## gap 2020-01..2020-12 (0 files)\n"
+    );
+}
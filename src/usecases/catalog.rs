@@ -0,0 +1,65 @@
+//! 跨次运行的内容哈希目录（catalog）：回答"这份文件以前归档过吗"。
+//!
+//! - 键为 SHA-512（十六进制），值为 size + 最后已知位置；`copy` / `move` 每次落盘或
+//!   判为 output 内重复时更新
+//! - 归档区即使已重组目录、或整个离线（外置盘未挂载），`--skip-known` 仍可据此把
+//!   命中的源文件当作重复跳过
+//! - 只是"曾经见过"的记录，不能证明归档区此刻仍有该内容：命中后 move 也不删源
+//! - 持久化格式与写盘由 adapters 层负责，本模块只管内存中的查询与登记
+
+use std::collections::BTreeMap;
+
+use serde_derive::{Deserialize, Serialize};
+
+use crate::entities::SecureHash;
+
+/// 内容哈希目录。`BTreeMap` 让写出的文件按键有序、可 diff。
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Catalog {
+    entries: BTreeMap<String, CatalogEntry>,
+}
+
+/// 一条归档记录。
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CatalogEntry {
+    pub size: u64,
+    /// 最后一次落盘 / 判重时的位置（`Location::display` 形式）。
+    pub location: String,
+}
+
+impl Catalog {
+    /// 按内容查记录；size 不符（损坏或手改的条目）视为未命中。
+    #[must_use]
+    pub fn lookup(&self, hash: &SecureHash, size: u64) -> Option<&CatalogEntry> {
+        self.entries
+            .get(&hex::encode(&hash[..]))
+            .filter(|entry| entry.size == size)
+    }
+
+    /// 登记或覆盖为最新位置。
+    pub fn record(&mut self, hash: &SecureHash, size: u64, location: String) {
+        self.entries
+            .insert(hex::encode(&hash[..]), CatalogEntry { size, location });
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// copy / move 使用 catalog 的方式：登记总是进行，`skip_known` 决定是否据此跳过。
+pub struct CatalogOpts<'a> {
+    pub catalog: &'a mut Catalog,
+    /// `--skip-known`：源文件内容已在 catalog 中即跳过，不要求归档区在线。
+    pub skip_known: bool,
+}
+
+#[cfg(test)]
+#[path = "catalog_tests.rs"]
+mod tests;
@@ -0,0 +1,42 @@
+use sha2::{Digest, Sha512};
+
+use super::*;
+
+fn sha(bytes: &[u8]) -> SecureHash {
+    Sha512::digest(bytes)
+}
+
+#[test]
+fn record_then_lookup_requires_matching_size() {
+    let mut catalog = Catalog::default();
+    assert!(catalog.is_empty());
+    catalog.record(&sha(b"a"), 1, "/archive/2024/a.jpg".into());
+
+    let hit = catalog.lookup(&sha(b"a"), 1).unwrap();
+    assert_eq!(hit.location, "/archive/2024/a.jpg");
+    assert_eq!(catalog.lookup(&sha(b"a"), 2), None);
+    assert_eq!(catalog.lookup(&sha(b"b"), 1), None);
+}
+
+/// 再次登记覆盖为最新位置（归档区重组后路径随之更新），条目数不变。
+#[test]
+fn record_overwrites_last_known_location() {
+    let mut catalog = Catalog::default();
+    catalog.record(&sha(b"a"), 1, "/old/a.jpg".into());
+    catalog.record(&sha(b"a"), 1, "/new/a.jpg".into());
+    assert_eq!(catalog.len(), 1);
+    assert_eq!(
+        catalog.lookup(&sha(b"a"), 1).unwrap().location,
+        "/new/a.jpg"
+    );
+}
+
+#[test]
+fn serializes_hex_keys_round_trip() {
+    let mut catalog = Catalog::default();
+    catalog.record(&sha(b"a"), 1, "/a.jpg".into());
+    let json = serde_json::to_string(&catalog).unwrap();
+    assert!(json.contains(&hex::encode(&sha(b"a")[..])), "{json}");
+    let back: Catalog = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.lookup(&sha(b"a"), 1).unwrap().location, "/a.jpg");
+}
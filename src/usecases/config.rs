@@ -8,6 +8,8 @@ use std::sync::OnceLock;
 
 use serde_derive::Deserialize;
 
+use crate::usecases::archive_template::{ALWAYS_NON_EMPTY, Placeholder};
+
 static CONFIG: OnceLock<Config> = OnceLock::new();
 static LOADER: OnceLock<fn() -> Config> = OnceLock::new();
 
@@ -34,6 +36,12 @@ pub struct CopyConfig {
     pub timezone_offset_hours: i8,
     pub unique_name_max_attempts: u32,
     pub archive_template: String,
+    /// 内容哈希 catalog 文件路径；空串 = 不启用（`--catalog` 可临时指定）。
+    pub catalog_path: String,
+    /// `{event}` 聚类：按时间排序后相邻两张间隔超过此分钟数即切分新事件。
+    pub event_gap_minutes: u32,
+    /// `{event}` 聚类：两张都带 GPS 且相距超过此公里数也切分；0 = 不看 GPS。
+    pub event_radius_km: f64,
 }
 
 impl Default for CopyConfig {
@@ -42,60 +50,103 @@ impl Default for CopyConfig {
             timezone_offset_hours: 8,
             unique_name_max_attempts: 10,
             archive_template: DEFAULT_ARCHIVE_TEMPLATE.to_string(),
+            catalog_path: String::new(),
+            event_gap_minutes: 120,
+            event_radius_km: 0.0,
         }
     }
 }
 
-/// 校验归档模板：非空 + `{` `}` 结构配对 + 占位符名属已知集合。
+/// 校验归档模板：非空 + `{` `}` 结构配对 + 占位符名属已知集合 + `:spec` 合法。
 ///
 /// 结构扫描替代旧的字符计数：`{year/{month}}` 计数配平但渲染时占位符无法
 /// 整 token 匹配，会静默产生字面 `{year` 目录；未知占位符（如 `{foo}`）同理。
+/// 占位符语法（`{name[:spec][|fallback]}`）见 `archive_template` 模块注释。
 ///
 /// # Errors
 ///
-/// 模板为空、花括号嵌套/错配/未闭合、或占位符名未知时返回 `Err`。
+/// 模板为空、花括号嵌套/错配/未闭合、占位符名未知、strftime 格式或大小写变换
+/// 非法时返回 `Err`。
 pub fn validate_archive_template(template: &str) -> Result<(), String> {
-    // 保证渲染后至少有一段非空目录的占位符——剩下的 {valuable_name} 可能渲染为
-    // 空串致全部文件落 output 根；要求模板含至少一个 always-non-empty 占位符。
-    // const 须放 fn 顶（clippy::items_after_statements，pedantic）。
-    const ALWAYS_NON_EMPTY: [&str; 5] = ["year", "month", "day", "make", "model"];
+    // 保证渲染后至少有一段非空目录——{valuable_name} 等可能渲染为空串致全部
+    // 文件落 output 根；要求至少一个恒非空占位符（或带非空 fallback 的占位符）。
+    let scan = scan_template(template, "archive_template")?;
+    if !scan.has_safe_placeholder {
+        return Err(missing_safe_placeholder(
+            "archive_template",
+            "a non-empty subdirectory",
+        ));
+    }
+    Ok(())
+}
+
+/// 校验 `--name-template`：语法同 [`validate_archive_template`]，另要求模板字面
+/// 不含 `/` / `\`（文件名只能是单个路径段；占位符值里的分隔符渲染时折成 `_`）。
+///
+/// # Errors
+///
+/// 同 [`validate_archive_template`]；字面含路径分隔符时亦返回 `Err`。
+pub fn validate_name_template(template: &str) -> Result<(), String> {
+    let scan = scan_template(template, "name_template")?;
+    if scan.has_literal_separator {
+        return Err("name_template must not contain '/' or '\\' outside placeholders".into());
+    }
+    if !scan.has_safe_placeholder {
+        return Err(missing_safe_placeholder(
+            "name_template",
+            "a non-empty file name",
+        ));
+    }
+    Ok(())
+}
+
+// 模板结构扫描结果；两类模板共享扫描，各自决定哪些性质必须满足。
+struct TemplateScan {
+    has_safe_placeholder: bool,
+    has_literal_separator: bool,
+}
+
+// `what` 为报错前缀（archive_template / name_template），让错误指向用户实际写错的配置项。
+fn scan_template(template: &str, what: &str) -> Result<TemplateScan, String> {
     if template.is_empty() {
-        return Err("archive_template must not be empty".into());
+        return Err(format!("{what} must not be empty"));
     }
     let mut start: Option<usize> = None;
-    let mut has_safe_placeholder = false;
+    let mut scan = TemplateScan {
+        has_safe_placeholder: false,
+        has_literal_separator: false,
+    };
     for (i, c) in template.char_indices() {
         match c {
             '{' if start.is_some() => {
-                return Err("archive_template has unbalanced braces: nested '{'".into());
+                return Err(format!("{what} has unbalanced braces: nested '{{'"));
             }
             '{' => start = Some(i + 1),
             '}' => {
                 let Some(s) = start.take() else {
-                    return Err("archive_template has unbalanced braces: unmatched '}'".into());
+                    return Err(format!("{what} has unbalanced braces: unmatched '}}'"));
                 };
-                let name = &template[s..i];
-                if !crate::usecases::archive_template::PLACEHOLDERS.contains(&name) {
-                    return Err(format!(
-                        "archive_template has unknown placeholder {{{name}}}"
-                    ));
-                }
-                if ALWAYS_NON_EMPTY.contains(&name) {
-                    has_safe_placeholder = true;
-                }
+                let placeholder = Placeholder::parse(&template[s..i]);
+                placeholder.check().map_err(|e| format!("{what} has {e}"))?;
+                scan.has_safe_placeholder |= placeholder.always_non_empty();
             }
+            '/' | '\\' if start.is_none() => scan.has_literal_separator = true,
             _ => {}
         }
     }
     if start.is_some() {
-        return Err("archive_template has unbalanced braces: unclosed '{'".into());
+        return Err(format!("{what} has unbalanced braces: unclosed '{{'"));
     }
-    if !has_safe_placeholder {
-        return Err("archive_template must contain at least one of \
-             {year}/{month}/{day}/{make}/{model} to guarantee a non-empty subdirectory"
-            .into());
-    }
-    Ok(())
+    Ok(scan)
+}
+
+fn missing_safe_placeholder(what: &str, guarantee: &str) -> String {
+    format!(
+        "{what} must contain at least one of {} \
+         (or a placeholder with a non-empty fallback such as {{valuable_name|misc}}) \
+         to guarantee {guarantee}",
+        ALWAYS_NON_EMPTY.map(|n| format!("{{{n}}}")).join("/")
+    )
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -179,6 +230,10 @@ pub struct OcrConfig {
     pub min_text_pixel_ratio: f32,
     /// 推理前 resize 的短边像素上限；DBNet 要求 32 倍数（实际 resize 时按 32 对齐）。
     pub resize_max_side: u32,
+    /// 同时推理的模型实例数（`move-text-shot` 并行 OCR 的上限）；0 = CPU 核数。
+    pub inference_workers: usize,
+    /// 单文件字节上限（防 OOM）：并行 OCR 同时驻留的字节 ≤ 池宽 × 此值，超限的图计入 failed。
+    pub max_image_bytes: u64,
 }
 
 impl Default for OcrConfig {
@@ -188,6 +243,8 @@ impl Default for OcrConfig {
             binarize_threshold: 0.3,
             min_text_pixel_ratio: 0.005,
             resize_max_side: 736,
+            inference_workers: 0,
+            max_image_bytes: 50 * 1024 * 1024,
         }
     }
 }
@@ -214,13 +271,21 @@ pub struct FaceConfig {
     /// `YOLOv8` `EyeState`（640×640 letterbox → 检测头 max conf；
     /// `MichalMlodawski/open-closed-eye-detection`）ONNX 路径。
     pub eyestate_model_path: String,
+    /// 可选的 NIMA 类整图质量模型（224×224 → 10 档分布或标量）ONNX 路径；空 = 不启用，
+    /// 评分不含 `quality_bonus` 项。
+    pub quality_model_path: String,
     /// pHash 汉明距离阈值；≤ 此值的两图视为相似入同组。范围 `[1, 64]`。
     pub phash_hamming_max: u8,
+    /// 连拍时间窗（秒）；拍摄时间差 ≤ 此值的两图视为同一连拍入同组
+    ///（`cull --group-by time|both`；both 下另须 pHash 距离 ≤ 2 × `phash_hamming_max`）。
+    pub burst_window_secs: u32,
+    /// `--group-by both` 下 pHash 链接的时间差上限（秒）：两图拍摄时间都已知且相差
+    /// 超过此值时不因画面相似并组（隔天同机位重拍不算连拍）。0 关闭此限制。
+    pub phash_max_gap_secs: u64,
     /// 全图 Laplacian 方差下限；低于此值视整图模糊丢弃（单图组例外保留）。
     pub sharpness_min: f32,
     /// 跨图人脸 embedding 余弦相似度阈值；≥ 此值判同一身份。范围 `(0, 1)`。
-    /// 当前仅 `cluster_identities` 产 debug 日志消费，`pick_best_for_group` 不消费
-    /// cluster 结果；未来 per-identity 策略接入后此值才影响 best 选择（TODO）。
+    /// `cull` 据此把组内人脸分成身份，优先选全员睁眼的帧（见 `identity_pick`）。
     pub face_cosine_min: f32,
     /// EAR（眼睑纵横比）阈值；低于此值视为闭眼。范围 `(0, 1)`。
     pub ear_blink_max: f32,
@@ -235,9 +300,37 @@ pub struct FaceConfig {
     pub w_blink: f32,
     /// 综合评分中微笑加分权重。
     pub w_smile: f32,
+    /// 曝光惩罚权重：乘死黑（≤2）+ 过曝（≥253）像素占比 `[0, 1]`。
+    ///
+    /// 本项与以下 `w_noise` / `w_motion_blur` / `w_tilt` / `w_subject_sharpness` 默认均为 0：
+    /// 量纲各异、尚无标定数据，开箱即用会悄悄改变升级前的选优结果，按需显式开启。
+    pub w_exposure: f32,
+    /// 噪声惩罚权重：乘 Immerkær 噪声标准差估计（灰度级）。
+    pub w_noise: f32,
+    /// 运动模糊惩罚权重：乘 Sobel 梯度方向相干度 `[0, 1]`。
+    pub w_motion_blur: f32,
+    /// 地平线倾斜惩罚权重：乘倾斜角绝对值（度）。
+    pub w_tilt: f32,
+    /// 主体清晰度加分权重：乘人脸框内 Laplacian 方差均值。与 `w_sharpness` 的全图清晰度
+    /// 同量纲、高度相关，开启时宜相应调低 `w_sharpness`，避免清晰度被计两次。
+    pub w_subject_sharpness: f32,
+    /// 质量模型加分权重：乘 `quality_model_path` 模型输出分（NIMA 1~10）。
+    pub w_quality: f32,
     /// 单文件字节上限；超过此值的图扫描阶段直接 skip 计入 `failed`（防 OOM）。
     /// 默认 50 MiB，覆盖典型相机原始 JPEG/HEIC + 适度裕度；大 RAW 文件需自行调高。
     pub max_image_bytes: u64,
+    /// 每个模型同时推理的实例数（各 worker 独占一份，共享权重）；0 = CPU 核数。
+    /// 内存紧时调低：每份实例推理时各自驻留一份中间张量。
+    pub inference_workers: usize,
+    /// 批量推理每次送入模型的最大张数（`MobileFaceNet` 对齐人脸、`EyeState` 眼部 crop）；
+    /// 导出时固定 batch=1 的模型自动退回逐张。
+    pub inference_batch_size: usize,
+    /// `faces` 子命令的人脸库文件（embedding + 身份簇 + 命名）；`{person}` / `--person`
+    /// 据此查人物。空 = 未配置，须经 `--db` / `--faces-db` 指定。
+    pub db_path: String,
+    /// `cull` 的分析缓存文件（pHash / 清晰度 / 推理输出，按内容 SHA-512）；改权重与阈值后
+    /// 重跑不再推理。空 = 不启用，可经 `cull --cache` 指定。
+    pub cull_cache_path: String,
 }
 
 impl Default for FaceConfig {
@@ -249,7 +342,10 @@ impl Default for FaceConfig {
             facenet_model_path: String::new(),
             facemesh_model_path: String::new(),
             eyestate_model_path: String::new(),
+            quality_model_path: String::new(),
             phash_hamming_max: 10,
+            burst_window_secs: 2,
+            phash_max_gap_secs: 86_400,
             sharpness_min: 100.0,
             face_cosine_min: 0.5,
             ear_blink_max: 0.21,
@@ -258,7 +354,17 @@ impl Default for FaceConfig {
             w_sharpness: 1.0,
             w_blink: 2.0,
             w_smile: 0.5,
+            w_exposure: 0.0,
+            w_noise: 0.0,
+            w_motion_blur: 0.0,
+            w_tilt: 0.0,
+            w_subject_sharpness: 0.0,
+            w_quality: 20.0,
             max_image_bytes: 50 * 1024 * 1024,
+            inference_workers: 0,
+            inference_batch_size: 8,
+            db_path: String::new(),
+            cull_cache_path: String::new(),
         }
     }
 }
@@ -283,7 +389,7 @@ pub struct Config {
 
 #[cfg(test)]
 mod tests {
-    use super::{Config, validate_archive_template};
+    use super::{Config, validate_archive_template, validate_name_template};
 
     #[test]
     fn config_defaults_match_historical_constants() {
@@ -291,6 +397,9 @@ mod tests {
         assert_eq!(c.copy.timezone_offset_hours, 8);
         assert_eq!(c.copy.unique_name_max_attempts, 10);
         assert_eq!(c.copy.archive_template, "{year}/{month}/{valuable_name}");
+        assert_eq!(c.copy.catalog_path, "");
+        assert_eq!(c.copy.event_gap_minutes, 120);
+        assert!(c.copy.event_radius_km.abs() < f64::EPSILON);
         assert_eq!(c.exif.valid_date_time_secs, 946_684_800);
         assert_eq!(c.backend.smb.default_user, "");
         assert_eq!(c.backend.smb.workgroup, "WORKGROUP");
@@ -300,13 +409,18 @@ mod tests {
         assert!((c.backend.ocr.binarize_threshold - 0.3).abs() < f32::EPSILON);
         assert!((c.backend.ocr.min_text_pixel_ratio - 0.005).abs() < f32::EPSILON);
         assert_eq!(c.backend.ocr.resize_max_side, 736);
+        assert_eq!(c.backend.ocr.inference_workers, 0);
+        assert_eq!(c.backend.ocr.max_image_bytes, 50 * 1024 * 1024);
         assert_eq!(c.backend.face.scrfd_model_path, "");
         assert!((c.backend.face.scrfd_score_threshold - 0.5).abs() < f32::EPSILON);
         assert!((c.backend.face.scrfd_nms_iou - 0.4).abs() < f32::EPSILON);
         assert_eq!(c.backend.face.facenet_model_path, "");
         assert_eq!(c.backend.face.facemesh_model_path, "");
         assert_eq!(c.backend.face.eyestate_model_path, "");
+        assert_eq!(c.backend.face.quality_model_path, "");
         assert_eq!(c.backend.face.phash_hamming_max, 10);
+        assert_eq!(c.backend.face.burst_window_secs, 2);
+        assert_eq!(c.backend.face.phash_max_gap_secs, 86_400);
         assert!((c.backend.face.sharpness_min - 100.0).abs() < f32::EPSILON);
         assert!((c.backend.face.face_cosine_min - 0.5).abs() < f32::EPSILON);
         assert!((c.backend.face.ear_blink_max - 0.21).abs() < f32::EPSILON);
@@ -315,7 +429,17 @@ mod tests {
         assert!((c.backend.face.w_sharpness - 1.0).abs() < f32::EPSILON);
         assert!((c.backend.face.w_blink - 2.0).abs() < f32::EPSILON);
         assert!((c.backend.face.w_smile - 0.5).abs() < f32::EPSILON);
+        assert!(c.backend.face.w_exposure.abs() < f32::EPSILON);
+        assert!(c.backend.face.w_noise.abs() < f32::EPSILON);
+        assert!(c.backend.face.w_motion_blur.abs() < f32::EPSILON);
+        assert!(c.backend.face.w_tilt.abs() < f32::EPSILON);
+        assert!(c.backend.face.w_subject_sharpness.abs() < f32::EPSILON);
+        assert!((c.backend.face.w_quality - 20.0).abs() < f32::EPSILON);
         assert_eq!(c.backend.face.max_image_bytes, 50 * 1024 * 1024);
+        assert_eq!(c.backend.face.inference_workers, 0);
+        assert_eq!(c.backend.face.inference_batch_size, 8);
+        assert_eq!(c.backend.face.db_path, "");
+        assert_eq!(c.backend.face.cull_cache_path, "");
         assert_eq!(c.log.level, "info");
     }
 
@@ -360,6 +484,13 @@ mod tests {
         assert!(err.contains("unknown placeholder {foo}"), "got: {err}");
     }
 
+    /// `{gps_country}` 未实现（不做 GPS 反查）：报错指向 `{country}`。
+    #[test]
+    fn validate_archive_template_points_gps_country_to_country() {
+        let err = validate_archive_template("{year}/{gps_country}").unwrap_err();
+        assert!(err.contains("use {country}"), "got: {err}");
+    }
+
     #[test]
     fn validate_archive_template_accepts_all_known_placeholders() {
         assert!(
@@ -368,6 +499,12 @@ mod tests {
         );
     }
 
+    /// `{rating}` / `{label}` 渲染恒非空（缺失时 `0` / `unlabeled`），可单独构成模板。
+    #[test]
+    fn validate_archive_template_accepts_rating_and_label() {
+        assert!(validate_archive_template("{rating}/{label}/{valuable_name}").is_ok());
+    }
+
     /// 仅 `{valuable_name}` 单占位符 → 渲染时 `valuable_name` 可能空 → 文件全落
     /// output 根；validate 必须显式拒绝缺乏 always-non-empty 占位符的模板。
     #[test]
@@ -379,6 +516,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn validate_archive_template_accepts_extended_syntax() {
+        assert!(validate_archive_template("{date:%Y/%m-%d}/{hour}").is_ok());
+        assert!(validate_archive_template("{quarter}/{week}/{media_kind}/{mime_type}").is_ok());
+        assert!(validate_archive_template("{make:upper|unknown}/{model:title}").is_ok());
+        assert!(
+            validate_archive_template("{lens}/{country}/{source_priority}/{week_year}").is_ok()
+        );
+        assert!(validate_archive_template("{year}/{original_name}.{ext:lower}").is_ok());
+    }
+
+    /// 非空 fallback 让本可能为空的占位符也算"保证非空"；空 fallback 反之。
+    #[test]
+    fn validate_archive_template_fallback_decides_safety() {
+        assert!(validate_archive_template("{valuable_name|misc}").is_ok());
+        let err = validate_archive_template("{make|}").unwrap_err();
+        assert!(err.contains("at least one of"), "got: {err}");
+    }
+
+    #[test]
+    fn validate_archive_template_rejects_bad_spec() {
+        let err = validate_archive_template("{date:%Q}").unwrap_err();
+        assert!(err.contains("invalid date format"), "got: {err}");
+        let err = validate_archive_template("{date:}").unwrap_err();
+        assert!(err.contains("empty date format"), "got: {err}");
+        let err = validate_archive_template("{make:shout}").unwrap_err();
+        assert!(err.contains("unsupported format `shout`"), "got: {err}");
+        let err = validate_archive_template("{foo|x}").unwrap_err();
+        assert!(err.contains("unknown placeholder {foo}"), "got: {err}");
+    }
+
     /// 单 always-non-empty 占位符即可通过（year 已保证非空）。
     #[test]
     fn validate_archive_template_accepts_single_safe_placeholder() {
@@ -391,4 +559,29 @@ mod tests {
         let err = validate_archive_template("archive").unwrap_err();
         assert!(err.contains("at least one of"), "got: {err}");
     }
+
+    #[test]
+    fn validate_name_template_accepts_single_segment() {
+        assert!(validate_name_template("{date:%Y%m%d_%H%M%S}_{make}_{hash8}.{ext}").is_ok());
+        // 占位符内部的 `/`（strftime）渲染时折成 `_`，不算字面分隔符。
+        assert!(validate_name_template("{date:%Y/%m}_{original_name}").is_ok());
+    }
+
+    #[test]
+    fn validate_name_template_rejects_literal_separator() {
+        let err = validate_name_template("{year}/{original_name}").unwrap_err();
+        assert!(err.contains("name_template must not contain"), "got: {err}");
+        assert!(validate_name_template("{year}\\{original_name}").is_err());
+    }
+
+    #[test]
+    fn validate_name_template_requires_safe_placeholder() {
+        let err = validate_name_template("{original_name}.{ext}").unwrap_err();
+        assert!(
+            err.contains("name_template must contain at least one of"),
+            "got: {err}"
+        );
+        let err = validate_name_template("{hash8").unwrap_err();
+        assert!(err.contains("name_template has unbalanced"), "got: {err}");
+    }
 }
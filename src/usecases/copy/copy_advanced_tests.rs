@@ -3,6 +3,7 @@
 
 #[cfg(test)]
 mod test_advanced {
+    use std::collections::HashSet;
     use std::fs;
     use std::path::Path;
     use std::sync::Arc;
@@ -49,6 +50,10 @@ mod test_advanced {
             remove: false,
             include_non_media: false,
             template,
+            filter: TagFilter::default(),
+            name_template: None,
+            companions: None,
+            person: None,
         }
     }
 
@@ -110,6 +115,10 @@ mod test_advanced {
             remove: true,
             include_non_media: false,
             template: DEFAULT_TMPL,
+            filter: TagFilter::default(),
+            name_template: None,
+            companions: None,
+            person: None,
         };
         let res = do_copy(&info, &out_dir, &local_arc(), &mut idx, &opts);
 
@@ -194,6 +203,10 @@ mod test_advanced {
             remove: true,
             include_non_media: false,
             template: DEFAULT_TMPL,
+            filter: TagFilter::default(),
+            name_template: None,
+            companions: None,
+            person: None,
         };
         let res = do_copy(&info, &local_loc(&out), &local_arc(), &mut idx, &opts);
 
@@ -258,6 +271,10 @@ mod test_advanced {
             remove: false,
             include_non_media: true,
             template: DEFAULT_TMPL,
+            filter: TagFilter::default(),
+            name_template: None,
+            companions: None,
+            person: None,
         };
         let did = do_copy(&info, &local_loc(out.path()), &local_arc(), &mut idx, &opts).unwrap();
         assert!(did, "non-media must be copied when include_non_media=true");
@@ -296,6 +313,8 @@ mod test_advanced {
             &local_loc(out.path()),
             &local_arc(),
             "{valuable_name}",
+            None,
+            &HashSet::new(),
         )
         .unwrap()
         .expect("should generate even with empty subdir");
@@ -486,6 +505,10 @@ mod test_advanced {
             remove: true,
             include_non_media: false,
             template: DEFAULT_TMPL,
+            filter: TagFilter::default(),
+            name_template: None,
+            companions: None,
+            person: None,
         };
         let ok = do_copy(&info, &out_loc, &backend_arc, &mut idx, &opts).unwrap();
         assert!(ok, "stream_copy should succeed");
@@ -0,0 +1,169 @@
+//! `do_copy` 与内容哈希 catalog 的交互：登记落盘位置 / output 内重复的位置，
+//! `skip_known` 命中即跳过且 move 不删源。
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+
+use camino::Utf8PathBuf;
+use tempfile::tempdir;
+
+use super::ops::do_copy;
+use super::*;
+use crate::adapters::backend::local::LocalBackend;
+use crate::entities::backend::Backend;
+use crate::entities::file_index::Index;
+use crate::entities::test_common as tc;
+use crate::entities::uri::Location;
+use crate::usecases::catalog::{Catalog, CatalogOpts};
+
+fn local_loc(p: &Path) -> Location {
+    Location::Local(Utf8PathBuf::from(p.to_str().unwrap()))
+}
+
+fn media_info(dir: &Path, name: &str) -> Info {
+    let png = tc::copy_png_to(dir, name).unwrap();
+    let mut info = Info::from(png.to_str().unwrap()).unwrap();
+    info.set_exif(crate::entities::exif::Exif::with_mime("image/png"));
+    info
+}
+
+fn opts(remove: bool) -> CopyOpts<'static> {
+    CopyOpts {
+        dry_run: false,
+        remove,
+        include_non_media: false,
+        template: "{year}",
+        filter: TagFilter::default(),
+        name_template: None,
+        companions: None,
+        person: None,
+    }
+}
+
+fn run(info: &Info, out: &Path, opts: &CopyOpts<'_>, catalog: &mut CatalogOpts<'_>) -> bool {
+    let backend: Arc<dyn Backend> = LocalBackend::arc();
+    let mut index = Index::new();
+    index.visit_location(&local_loc(out), &backend);
+    do_copy(
+        info,
+        &local_loc(out),
+        &backend,
+        &mut index,
+        &mut HashSet::new(),
+        &mut HashSet::new(),
+        opts,
+        Some(catalog),
+    )
+    .unwrap()
+}
+
+/// 落盘后登记目标位置；换一个空 output（归档区离线）再跑，`skip_known` 命中即跳过，
+/// move 也保留源文件。
+#[test]
+fn records_transfers_and_skips_known_content_without_deleting() {
+    let src = tempdir().unwrap();
+    let info = media_info(src.path(), "a.png");
+    let hash = info.secure_hash().unwrap();
+    let archive = tempdir().unwrap();
+    let mut catalog = Catalog::default();
+
+    let copied = run(
+        &info,
+        archive.path(),
+        &opts(false),
+        &mut CatalogOpts {
+            catalog: &mut catalog,
+            skip_known: false,
+        },
+    );
+    assert!(copied);
+    let recorded = catalog.lookup(&hash, info.size).unwrap().location.clone();
+    assert!(Path::new(&recorded).is_file(), "{recorded}");
+    assert!(recorded.starts_with(archive.path().to_str().unwrap()));
+
+    let offline = tempdir().unwrap();
+    let moved = run(
+        &info,
+        offline.path(),
+        &opts(true),
+        &mut CatalogOpts {
+            catalog: &mut catalog,
+            skip_known: true,
+        },
+    );
+    assert!(!moved);
+    assert!(src.path().join("a.png").is_file());
+    assert_eq!(std::fs::read_dir(offline.path()).unwrap().count(), 0);
+}
+
+/// output 内已有副本：登记该副本的位置；未开 `skip_known` 时 catalog 不拦截复制。
+#[test]
+fn records_existing_duplicate_and_ignores_catalog_without_skip_known() {
+    let src = tempdir().unwrap();
+    let info = media_info(src.path(), "a.png");
+    let out = tempdir().unwrap();
+    let existing = tc::copy_png_to(out.path(), "already.png").unwrap();
+    let mut catalog = Catalog::default();
+    let mut catalog_opts = CatalogOpts {
+        catalog: &mut catalog,
+        skip_known: false,
+    };
+
+    assert!(!run(&info, out.path(), &opts(false), &mut catalog_opts));
+    let hash = info.secure_hash().unwrap();
+    assert_eq!(
+        catalog.lookup(&hash, info.size).unwrap().location,
+        existing.to_str().unwrap()
+    );
+
+    let fresh = tempdir().unwrap();
+    let mut catalog_opts = CatalogOpts {
+        catalog: &mut catalog,
+        skip_known: false,
+    };
+    assert!(run(&info, fresh.path(), &opts(false), &mut catalog_opts));
+}
+
+/// 远端 output 内已有副本：与落盘登记一样记 `Location::display` 形态（带 scheme 的 URI），
+/// 而不是裸路径。
+#[test]
+fn records_remote_duplicate_as_uri() {
+    use crate::adapters::backend::fake::FakeBackend;
+
+    let src = tempdir().unwrap();
+    let info = media_info(src.path(), "a.png");
+    let fake = Arc::new(FakeBackend::new("smb"));
+    let out = Location::parse("smb://nas/photos/out").unwrap();
+    let existing = out.join_path("already.png");
+    fake.add_dir(out.clone());
+    fake.add_file(
+        existing.clone(),
+        std::fs::read(src.path().join("a.png")).unwrap(),
+    );
+    let backend: Arc<dyn Backend> = fake;
+    let mut index = Index::new();
+    index.visit_location(&out, &backend);
+    let mut catalog = Catalog::default();
+
+    let copied = do_copy(
+        &info,
+        &out,
+        &backend,
+        &mut index,
+        &mut HashSet::new(),
+        &mut HashSet::new(),
+        &opts(false),
+        Some(&mut CatalogOpts {
+            catalog: &mut catalog,
+            skip_known: false,
+        }),
+    )
+    .unwrap();
+    assert!(!copied);
+    let hash = info.secure_hash().unwrap();
+    assert_eq!(
+        catalog.lookup(&hash, info.size).unwrap().location,
+        existing.display()
+    );
+}
@@ -1,6 +1,7 @@
 //! `generate_unique_name` 冲突序号与 `do_copy` 异常 / trace / dry-run 路径测试
 //!（从 `copy_tests.rs` 拆出）。
 
+use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
 use std::sync::Arc;
@@ -55,6 +56,10 @@ fn default_opts(template: &str) -> CopyOpts<'_> {
         remove: false,
         include_non_media: false,
         template,
+        filter: TagFilter::default(),
+        name_template: None,
+        companions: None,
+        person: None,
     }
 }
 
@@ -68,10 +73,16 @@ fn generate_unique_name_uses_suffix_when_first_taken() {
     fs::create_dir_all(&sub).unwrap();
     fs::write(sub.join("photo.png"), b"x").unwrap();
     let _ = out_utf8;
-    let (_, target) =
-        generate_unique_name(&info, &local_loc(out.path()), &local_arc(), DEFAULT_TMPL)
-            .unwrap()
-            .expect("unique name should be generated");
+    let (_, target) = generate_unique_name(
+        &info,
+        &local_loc(out.path()),
+        &local_arc(),
+        DEFAULT_TMPL,
+        None,
+        &HashSet::new(),
+    )
+    .unwrap()
+    .expect("unique name should be generated");
     let target_str = target.display();
     assert!(target_str.ends_with("photo_1.png"), "got {target_str}");
 }
@@ -82,8 +93,15 @@ fn generate_unique_name_none_after_max_collisions() {
     let info = make_media_info(src.path(), "photo.png");
     let out = tempdir().unwrap();
     fill_collisions(&out.path().join("2024").join("01"));
-    let res =
-        generate_unique_name(&info, &local_loc(out.path()), &local_arc(), DEFAULT_TMPL).unwrap();
+    let res = generate_unique_name(
+        &info,
+        &local_loc(out.path()),
+        &local_arc(),
+        DEFAULT_TMPL,
+        None,
+        &HashSet::new(),
+    )
+    .unwrap();
     assert!(
         res.is_none(),
         "should exhaust after max_attempts+1 collisions"
@@ -100,10 +118,16 @@ fn generate_unique_name_no_extension_omits_trailing_dot() {
     let sub = out.path().join("2024").join("01");
     fs::create_dir_all(&sub).unwrap();
     fs::write(sub.join("photo"), b"x").unwrap();
-    let (_, target) =
-        generate_unique_name(&info, &local_loc(out.path()), &local_arc(), DEFAULT_TMPL)
-            .unwrap()
-            .expect("unique name should be generated");
+    let (_, target) = generate_unique_name(
+        &info,
+        &local_loc(out.path()),
+        &local_arc(),
+        DEFAULT_TMPL,
+        None,
+        &HashSet::new(),
+    )
+    .unwrap()
+    .expect("unique name should be generated");
     let target_str = target.display();
     assert!(target_str.ends_with("photo_1"), "got {target_str}");
 }
@@ -178,8 +202,15 @@ fn generate_unique_name_propagates_exists_error() {
     let target = Location::Local(Utf8PathBuf::from("/out/1970/01/photo.png"));
     be.inject_error(target, crate::FakeOp::Exists, std::io::ErrorKind::TimedOut);
     let out_loc = Location::Local(Utf8PathBuf::from("/out"));
-    let err =
-        generate_unique_name(&info, &out_loc, &(be as Arc<dyn Backend>), DEFAULT_TMPL).unwrap_err();
+    let err = generate_unique_name(
+        &info,
+        &out_loc,
+        &(be as Arc<dyn Backend>),
+        DEFAULT_TMPL,
+        None,
+        &HashSet::new(),
+    )
+    .unwrap_err();
     assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
 }
 
@@ -230,12 +261,55 @@ fn do_copy_dry_run_reports_target_but_writes_nothing() {
         remove: false,
         include_non_media: false,
         template: DEFAULT_TMPL,
+        filter: TagFilter::default(),
+        name_template: None,
+        companions: None,
+        person: None,
     };
     let did_copy = do_copy(&info, &local_loc(out.path()), &local_arc(), &mut idx, &opts).unwrap();
     assert!(did_copy);
     assert_eq!(fs::read_dir(out.path()).unwrap().count(), 0);
 }
 
+/// dry-run 不落盘：本批已预定的目标名同样视为占用，同名的第二个源拿到 `_1`，
+/// 与真跑结果一致。
+#[test]
+fn do_copy_dry_run_reserves_planned_names() {
+    let (src_a, src_b) = (tempdir().unwrap(), tempdir().unwrap());
+    let infos = [
+        make_media_info(src_a.path(), "photo.png"),
+        make_media_info(src_b.path(), "photo.png"),
+    ];
+    let out = tempdir().unwrap();
+    let mut idx = crate::entities::file_index::Index::new();
+    let mut planned = HashSet::new();
+    let opts = CopyOpts {
+        dry_run: true,
+        ..default_opts(DEFAULT_TMPL)
+    };
+    for info in &infos {
+        let did_copy = super::ops::do_copy(
+            info,
+            &local_loc(out.path()),
+            &local_arc(),
+            &mut idx,
+            &mut HashSet::new(),
+            &mut planned,
+            &opts,
+            None,
+        )
+        .unwrap();
+        assert!(did_copy);
+    }
+    let month = utf8(out.path()).join("2024").join("01");
+    let want: HashSet<Location> = ["photo.png", "photo_1.png"]
+        .into_iter()
+        .map(|name| Location::Local(month.join(name)))
+        .collect();
+    assert_eq!(planned, want);
+    assert_eq!(fs::read_dir(out.path()).unwrap().count(), 0);
+}
+
 // 启用 trace 级别 subscriber，让 copy() 里的 trace! 宏闭包被求值，覆盖 L62 region。
 #[test]
 fn copy_with_trace_subscriber_executes_trace_branch() {
@@ -271,10 +345,16 @@ fn generate_unique_name_drops_dot_and_dotdot_segments() {
     let out = tempdir().unwrap();
     let info = make_media_info(src.path(), "photo.png");
     let template = "{year}/../target/.";
-    let (sub_dir, target) =
-        generate_unique_name(&info, &local_loc(out.path()), &local_arc(), template)
-            .unwrap()
-            .expect("path generated");
+    let (sub_dir, target) = generate_unique_name(
+        &info,
+        &local_loc(out.path()),
+        &local_arc(),
+        template,
+        None,
+        &HashSet::new(),
+    )
+    .unwrap()
+    .expect("path generated");
     let sub_dir_str = sub_dir.path().as_str();
     let out_str = utf8(out.path());
     assert!(
@@ -312,3 +392,287 @@ fn copy_with_output_as_file_errors() {
     .unwrap_err();
     let _ = err;
 }
+
+// ── --min-rating / --label / {rating} {label} ──
+
+fn rated_info(dir: &Path, name: &str, rating: Option<i8>, label: Option<&str>) -> Info {
+    let mut info = make_media_info(dir, name);
+    info.set_sidecar_tags(crate::entities::tags::Tags {
+        rating,
+        label: label.map(str::to_string),
+        ..Default::default()
+    });
+    info
+}
+
+/// 未达星级 / 色标不符 → 跳过且 move 模式也不删源；达标 → 复制。
+#[test]
+fn do_copy_filter_skips_unmatched_and_keeps_source() {
+    let src = tempdir().unwrap();
+    let out = tempdir().unwrap();
+    let mut idx = crate::entities::file_index::Index::new();
+    let opts = CopyOpts {
+        remove: true,
+        filter: TagFilter {
+            min_rating: Some(3),
+            label: Some("Red".into()),
+            ..TagFilter::default()
+        },
+        ..default_opts(DEFAULT_TMPL)
+    };
+
+    let rejected = rated_info(src.path(), "rejected.png", Some(-1), Some("Red"));
+    let wrong_label = rated_info(src.path(), "blue.png", Some(5), Some("Blue"));
+    let keeper = rated_info(src.path(), "keeper.png", Some(4), Some("red"));
+    let out_loc = local_loc(out.path());
+    assert!(!do_copy(&rejected, &out_loc, &local_arc(), &mut idx, &opts).unwrap());
+    assert!(!do_copy(&wrong_label, &out_loc, &local_arc(), &mut idx, &opts).unwrap());
+    assert!(src.path().join("rejected.png").exists());
+    assert!(src.path().join("blue.png").exists());
+    assert!(do_copy(&keeper, &out_loc, &local_arc(), &mut idx, &opts).unwrap());
+    assert!(!src.path().join("keeper.png").exists());
+}
+
+/// `--person`：人脸库里不含该人物的源文件跳过且 move 也不删源；命中忽略大小写。
+#[test]
+fn do_copy_person_filter_skips_unmatched_and_keeps_source() {
+    let src = tempdir().unwrap();
+    let out = tempdir().unwrap();
+    let mut idx = crate::entities::file_index::Index::new();
+    let opts = CopyOpts {
+        remove: true,
+        person: Some("alice"),
+        ..default_opts(DEFAULT_TMPL)
+    };
+
+    let mut stranger = make_media_info(src.path(), "stranger.png");
+    stranger.set_persons(vec!["Bob".into()]);
+    let mut alice = make_media_info(src.path(), "alice.png");
+    alice.set_persons(vec!["Bob".into(), "Alice".into()]);
+    let out_loc = local_loc(out.path());
+    assert!(!do_copy(&stranger, &out_loc, &local_arc(), &mut idx, &opts).unwrap());
+    assert!(src.path().join("stranger.png").exists());
+    assert!(do_copy(&alice, &out_loc, &local_arc(), &mut idx, &opts).unwrap());
+    assert!(!src.path().join("alice.png").exists());
+}
+
+/// `{person}` 取主体（首个）人物；未注入时为 `unknown`。
+#[test]
+fn generate_unique_name_renders_primary_person() {
+    let src = tempdir().unwrap();
+    let out = tempdir().unwrap();
+    let mut info = make_media_info(src.path(), "photo.png");
+    let render = |info: &Info| {
+        generate_unique_name(
+            info,
+            &local_loc(out.path()),
+            &local_arc(),
+            "{person}",
+            None,
+            &HashSet::new(),
+        )
+        .unwrap()
+        .unwrap()
+        .0
+    };
+    assert_eq!(render(&info).path(), utf8(out.path()).join("unknown"));
+    info.set_persons(vec!["Alice".into(), "Bob".into()]);
+    assert_eq!(render(&info).path(), utf8(out.path()).join("Alice"));
+}
+
+#[test]
+fn generate_unique_name_renders_rating_and_label() {
+    let src = tempdir().unwrap();
+    let info = rated_info(src.path(), "photo.png", Some(5), Some("Green"));
+    let out = tempdir().unwrap();
+    let (dir, _) = generate_unique_name(
+        &info,
+        &local_loc(out.path()),
+        &local_arc(),
+        "{rating}/{label}",
+        None,
+        &HashSet::new(),
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(dir.path(), utf8(out.path()).join("5").join("Green"));
+}
+
+/// Photos 图库标记：`{album}` 取相册，`{original_name}` 取导入前原名而非 `<uuid>`。
+#[test]
+fn generate_unique_name_renders_photos_album_and_original_name() {
+    let src = tempdir().unwrap();
+    let mut info = make_media_info(src.path(), "AAAA.png");
+    info.set_sidecar_tags(crate::entities::tags::Tags {
+        album: Some("Trip".into()),
+        original_name: Some("IMG_0001".into()),
+        ..Default::default()
+    });
+    let out = tempdir().unwrap();
+    let (dir, target) = generate_unique_name(
+        &info,
+        &local_loc(out.path()),
+        &local_arc(),
+        "{album}",
+        Some("{original_name}.{ext}"),
+        &HashSet::new(),
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(dir.path(), utf8(out.path()).join("Trip"));
+    assert_eq!(target.path(), utf8(out.path()).join("Trip/IMG_0001.png"));
+}
+
+/// copy 全流程：无 sidecar 的 PNG 未评级（0 星）→ `--min-rating 1` 全部跳过。
+#[test]
+fn copy_with_min_rating_skips_unrated_files() {
+    fn no_tags(_: &Location, _: &Arc<dyn Backend>) -> Option<crate::entities::tags::Tags> {
+        None
+    }
+    let src = tempdir().unwrap();
+    tc::copy_png_to(src.path(), "a.png").unwrap();
+    let out = tempdir().unwrap();
+    let report = copy_with_sidecar(
+        &[local_source(src.path())],
+        local_source(out.path()),
+        CopyRequest {
+            tags: Some(no_tags),
+            filter: TagFilter {
+                min_rating: Some(1),
+                label: None,
+                ..TagFilter::default()
+            },
+            ..CopyRequest::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(report.copied, 0);
+    assert_eq!(report.ignored, 1);
+}
+
+/// name_template：`{hash8}` 取 SHA-512 前 8 位；渲染同名时同样追加 `_N`。
+#[test]
+fn generate_unique_name_renders_name_template_with_suffix() {
+    let src = tempdir().unwrap();
+    let info = make_media_info(src.path(), "photo.png");
+    let out = tempdir().unwrap();
+    let hash8 = hex::encode(&info.secure_hash().unwrap()[..4]);
+    let generate = || {
+        generate_unique_name(
+            &info,
+            &local_loc(out.path()),
+            &local_arc(),
+            "{media_kind}",
+            Some("{hash8}_{original_name:upper}.{ext}"),
+            &HashSet::new(),
+        )
+        .unwrap()
+        .unwrap()
+    };
+    let (dir, target) = generate();
+    assert_eq!(target.path(), dir.path().join(format!("{hash8}_PHOTO.png")));
+    fs::create_dir_all(dir.path()).unwrap();
+    fs::write(target.path(), b"x").unwrap();
+    let (_, target) = generate();
+    assert_eq!(
+        target.path().file_name(),
+        Some(format!("{hash8}_PHOTO_1.png").as_str())
+    );
+}
+
+/// 改名 + move：`.xmp` / `.json` 伴随文件随媒体改名搬运，源侧一并移除。
+#[test]
+fn do_copy_name_template_carries_companions() {
+    let src = tempdir().unwrap();
+    let out = tempdir().unwrap();
+    let info = make_media_info(src.path(), "photo.png");
+    fs::write(src.path().join("photo.xmp"), b"<x/>").unwrap();
+    fs::write(src.path().join("photo.png.json"), b"{}").unwrap();
+    let mut idx = crate::entities::file_index::Index::new();
+    let opts = CopyOpts {
+        remove: true,
+        name_template: Some("{media_kind}-{original_name}.{ext}"),
+        companions: Some(crate::adapters::sidecar::companions_with_backend),
+        ..default_opts("{media_kind}")
+    };
+    assert!(do_copy(&info, &local_loc(out.path()), &local_arc(), &mut idx, &opts).unwrap());
+    let dir = out.path().join("photo");
+    for name in ["photo-photo.png", "photo-photo.xmp", "photo-photo.png.json"] {
+        assert!(dir.join(name).is_file(), "missing {name}");
+    }
+    assert!(!src.path().join("photo.xmp").exists());
+    assert!(!src.path().join("photo.png.json").exists());
+}
+
+/// 伴随文件目标已存在时不覆盖；媒体本身仍计成功。
+#[test]
+fn do_copy_name_template_never_overwrites_companion() {
+    let src = tempdir().unwrap();
+    let out = tempdir().unwrap();
+    let info = make_media_info(src.path(), "photo.png");
+    fs::write(src.path().join("photo.xmp"), b"new").unwrap();
+    let dir = out.path().join("photo");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("shot.xmp"), b"old").unwrap();
+    let mut idx = crate::entities::file_index::Index::new();
+    let opts = CopyOpts {
+        name_template: Some("shot.{ext}"),
+        companions: Some(crate::adapters::sidecar::companions_with_backend),
+        ..default_opts("{media_kind}")
+    };
+    assert!(do_copy(&info, &local_loc(out.path()), &local_arc(), &mut idx, &opts).unwrap());
+    assert!(dir.join("shot.png").is_file());
+    assert_eq!(fs::read(dir.join("shot.xmp")).unwrap(), b"old");
+    assert!(src.path().join("photo.xmp").exists());
+}
+
+/// 伴随文件目标已是同内容副本（重跑）：视为已搬运，move 下只移除源侧。
+#[test]
+fn do_copy_name_template_move_drops_already_archived_companion() {
+    let src = tempdir().unwrap();
+    let out = tempdir().unwrap();
+    let info = make_media_info(src.path(), "photo.png");
+    fs::write(src.path().join("photo.xmp"), b"<x/>").unwrap();
+    let dir = out.path().join("photo");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("shot.xmp"), b"<x/>").unwrap();
+    let mut idx = crate::entities::file_index::Index::new();
+    let opts = CopyOpts {
+        remove: true,
+        name_template: Some("shot.{ext}"),
+        companions: Some(crate::adapters::sidecar::companions_with_backend),
+        ..default_opts("{media_kind}")
+    };
+    assert!(do_copy(&info, &local_loc(out.path()), &local_arc(), &mut idx, &opts).unwrap());
+    assert!(dir.join("shot.png").is_file());
+    assert!(!src.path().join("photo.xmp").exists());
+}
+
+/// move + 判重命中：源媒体删除，被认领的 sidecar 随到归档区已有副本旁，不留在源目录。
+#[test]
+fn move_duplicate_carries_claimed_companion_to_existing_copy() {
+    let src = tempdir().unwrap();
+    let out = tempdir().unwrap();
+    tc::copy_png_to(src.path(), "photo.png").unwrap();
+    fs::write(src.path().join("photo.xmp"), b"<x/>").unwrap();
+    let archived = out.path().join("photo");
+    fs::create_dir_all(&archived).unwrap();
+    tc::copy_png_to(&archived, "shot.png").unwrap();
+
+    let report = copy_with_sidecar(
+        &[local_source(src.path())],
+        local_source(out.path()),
+        CopyRequest {
+            remove: true,
+            archive_template: Some("{media_kind}"),
+            name_template: Some("shot.{ext}"),
+            companions: Some(crate::adapters::sidecar::companions_with_backend),
+            ..CopyRequest::default()
+        },
+    )
+    .unwrap();
+    assert_eq!((report.copied, report.ignored, report.failed), (0, 2, 0));
+    assert!(!src.path().join("photo.png").exists());
+    assert!(!src.path().join("photo.xmp").exists());
+    assert_eq!(fs::read(archived.join("shot.xmp")).unwrap(), b"<x/>");
+}
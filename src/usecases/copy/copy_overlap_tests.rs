@@ -133,12 +133,10 @@ fn copy_with_provider_runs_enrich_candidates() {
     let report = copy_with_sidecar(
         &[local_source(dir.path())],
         local_source(out.path()),
-        /* dry_run = */ false,
-        /* remove = */ false,
-        /* include_non_media = */ false,
-        None,
-        None,
-        Some(no_candidates),
+        CopyRequest {
+            sidecar: Some(no_candidates),
+            ..CopyRequest::default()
+        },
     )
     .unwrap();
     assert_eq!(report.copied, 1);
@@ -70,19 +70,6 @@ mod test {
         assert_eq!(extract_valuable_name(path), "a高一 元旦晚会");
     }
 
-    #[test]
-    fn offset_from_hours_valid_value_produces_expected_offset() {
-        let off = offset_from_hours(8);
-        assert_eq!(off.whole_hours(), 8);
-    }
-
-    #[test]
-    fn offset_from_hours_out_of_range_falls_back_to_utc() {
-        // 127*3600 秒远超 time crate ±25:59:59 范围，应回退 UTC
-        let off = offset_from_hours(127);
-        assert_eq!(off, UtcOffset::UTC);
-    }
-
     #[test]
     fn chrono_offset_from_hours_valid() {
         let off = chrono_offset_from_hours(8);
@@ -5,7 +5,7 @@ pub(super) mod naming;
 pub(super) mod ops;
 pub(super) mod run;
 
-pub(crate) use self::run::{Source, copy_with_sidecar};
+pub(crate) use self::run::{CopyRequest, Source, copy_with_sidecar};
 
 // 测试经 `super::super::*` glob 访问的内部项（私有 use 对子模块可见，生产侧不暴露）。
 #[cfg(test)]
@@ -17,15 +17,15 @@ use self::ops::do_copy_with_default_cache as do_copy;
 #[cfg(test)]
 use self::run::copy;
 #[cfg(test)]
-use self::run::{CopyOpts, chrono_offset_from_hours, offset_from_hours, summary_result};
+use self::run::{CopyOpts, chrono_offset_from_hours, summary_result};
 #[cfg(test)]
 use crate::entities::common::canonical_prefix;
 #[cfg(test)]
 use crate::entities::file_info::Info;
 #[cfg(test)]
-use camino::Utf8Path;
+use crate::usecases::tag_filter::TagFilter;
 #[cfg(test)]
-use time::UtcOffset;
+use camino::Utf8Path;
 
 #[cfg(test)]
 #[path = "copy_tests.rs"]
@@ -42,3 +42,7 @@ mod generate_tests;
 #[cfg(test)]
 #[path = "copy_overlap_tests.rs"]
 mod overlap_tests;
+
+#[cfg(test)]
+#[path = "copy_catalog_tests.rs"]
+mod catalog_tests;
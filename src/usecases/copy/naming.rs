@@ -1,54 +1,154 @@
-//! 目标命名策略：archive\_template 渲染 + 拍摄时间展开 + 冲突追加序号。
+//! 目标命名策略：archive\_template / name\_template 渲染 + 拍摄时间展开 + 冲突追加序号。
 
+use std::collections::HashSet;
 use std::io;
 use std::sync::Arc;
 
 use camino::Utf8Component;
 use camino::Utf8Path;
-use time::OffsetDateTime;
+use chrono::DateTime;
+use chrono::FixedOffset;
+use chrono::Utc;
 
-use super::run::{MONTH, configured_chrono_offset, configured_offset};
+use super::run::configured_chrono_offset;
 use crate::entities::backend::Backend;
 use crate::entities::file_info::Info;
+use crate::entities::media_time::Priority;
+use crate::entities::tags::Tags;
 use crate::entities::uri::Location;
-use crate::usecases::archive_template::{TemplateContext, render};
+use crate::usecases::archive_template::{TemplateContext, render, render_file_name};
 use crate::usecases::config::config;
 
+/// 逐文件的模板取值（owned），由 [`Self::context`] 借出 [`TemplateContext`]。
+/// copy 的目录 / 文件名渲染与 `rename` 共用，保证同一文件在两处得到相同取值。
+pub(crate) struct TemplateFields {
+    time: DateTime<FixedOffset>,
+    priority: Priority,
+    valuable_name: String,
+    original_name: String,
+    ext: String,
+    tags: Tags,
+    hash8: Option<String>,
+    event: Option<String>,
+    person: Option<String>,
+}
+
+impl TemplateFields {
+    /// 解析拍摄时间（`MediaTimeDecision`，fs 兜底为 P4）与文件名字段；`templates`
+    /// 任一引用 `{hash8}` 时才读全文件算 SHA-512（判重阶段多半已缓存）。
+    pub(crate) fn from_info(src: &Info, templates: &[&str]) -> io::Result<Self> {
+        let display_path = Utf8Path::new(src.full_path.as_str());
+        let hash8 = if templates.iter().any(|t| t.contains("{hash8")) {
+            Some(hex::encode(&src.secure_hash()?[..4]))
+        } else {
+            None
+        };
+        let tags = src.tags();
+        let offset = configured_chrono_offset();
+        let (create_time, priority) =
+            src.create_time_with_priority(config().exif.valid_date_time_secs, offset);
+        Ok(Self {
+            time: DateTime::<Utc>::from(create_time).with_timezone(&offset),
+            priority,
+            valuable_name: extract_valuable_name(display_path),
+            // Photos 图库原图名为 `<uuid>.<ext>`，有导入前原名时用原名。
+            original_name: tags.original_name.clone().unwrap_or_else(|| {
+                display_path
+                    .file_stem()
+                    .expect("file with name must have a stem")
+                    .to_string()
+            }),
+            ext: display_path.extension().unwrap_or("").to_string(),
+            tags,
+            hash8,
+            event: src.event().map(str::to_string),
+            person: src.persons().first().cloned(),
+        })
+    }
+
+    pub(crate) fn context<'a>(&'a self, src: &'a Info) -> TemplateContext<'a> {
+        TemplateContext {
+            time: self.time,
+            priority: self.priority,
+            valuable_name: &self.valuable_name,
+            original_name: &self.original_name,
+            ext: &self.ext,
+            exif: src.exif_ref(),
+            rating: self.tags.rating,
+            label: self.tags.label.as_deref(),
+            hash8: self.hash8.as_deref(),
+            event: self.event.as_deref(),
+            person: self.person.as_deref(),
+            album: self.tags.album.as_deref(),
+        }
+    }
+}
+
+/// 目标文件名三元组 `(name, stem, ext)`：有 `name_template` 时按模板渲染，否则沿用
+/// 源文件名。渲染结果为空或纯点（`.` / `..`）时同样退回源文件名，避免写出怪名或
+/// 逃出目标目录。
+pub(crate) fn target_file_name(
+    src: &Info,
+    name_template: Option<&str>,
+    ctx: &TemplateContext<'_>,
+) -> (String, String, String) {
+    let rendered = name_template
+        .map(|t| render_file_name(t, ctx))
+        .filter(|n| !n.chars().all(|c| c == '.'));
+    let name = rendered.unwrap_or_else(|| {
+        Utf8Path::new(src.full_path.as_str())
+            .file_name()
+            .expect("Info::open guarantees file path has a name")
+            .to_string()
+    });
+    split_file_name(name)
+}
+
+/// 第 `i` 个冲突候选名：`i == 0` 为原名，其余在扩展名前插 `_i`。
+/// 无扩展名文件不拼 '.'：尾点文件名在 Linux 是怪文件，Windows 下 `CreateFile`
+/// 会剥掉尾点，使 exists 判定与实际创建路径不一致。
+pub(crate) fn candidate_name(name: &str, stem: &str, ext: &str, i: u32) -> String {
+    match (i, ext.is_empty()) {
+        (0, _) => name.to_string(),
+        (_, true) => format!("{stem}_{i}"),
+        (_, false) => format!("{stem}_{i}.{ext}"),
+    }
+}
+
+/// 伴随 sidecar 的新位置：文件名按媒体原名 / 原 stem 前缀替换为新名 / 新 stem
+///（`IMG_1.jpg.json` → `<新名>.json`、`IMG_1.xmp` → `<新 stem>.xmp`），落在媒体
+/// 新位置的同目录；前缀都不匹配时返 None（不搬）。
+pub(crate) fn companion_target(
+    media: &Location,
+    media_target: &Location,
+    companion: &Location,
+) -> Option<Location> {
+    let (old, new) = (media.path(), media_target.path());
+    let name = companion.path().file_name()?;
+    let renamed = if let Some(rest) = name.strip_prefix(old.file_name()?) {
+        format!("{}{rest}", new.file_name()?)
+    } else {
+        let rest = name.strip_prefix(old.file_stem()?)?;
+        format!("{}{rest}", new.file_stem()?)
+    };
+    Some(media_target.with_path(new.with_file_name(renamed)))
+}
+
+/// 渲染目标目录与文件名，冲突时追加 `_1.._N`。`planned` 为本批已预定的目标：
+/// dry-run 下尚未落盘的名字同样视为占用，预演结果与真跑一致。
 pub(super) fn generate_unique_name(
     src_file: &Info,
     output_dir: &Location,
     output_backend: &Arc<dyn Backend>,
     template: &str,
+    name_template: Option<&str>,
+    planned: &HashSet<Location>,
 ) -> io::Result<Option<(Location, Location)>> {
-    let display_path = Utf8Path::new(src_file.full_path.as_str());
-    let file_name = display_path
-        .file_name()
-        .expect("Info::open guarantees file path has a name");
-    let file_stem = display_path
-        .file_stem()
-        .expect("file with name must have a stem")
-        .to_string();
-    let ext = display_path.extension().unwrap_or("").to_string();
-
-    let create_time = src_file.create_time(
-        config().exif.valid_date_time_secs,
-        configured_chrono_offset(),
-    );
-    let dt = OffsetDateTime::from(create_time).to_offset(configured_offset());
-    let year = dt.year().to_string();
-    let month = MONTH[dt.month() as usize];
-    let day = format!("{:02}", dt.day());
-
-    let valuable_name = extract_valuable_name(display_path);
-
-    let template_ctx = TemplateContext {
-        year: &year,
-        month,
-        day: &day,
-        valuable_name: &valuable_name,
-        exif: src_file.exif_ref(),
-    };
+    let templates: Vec<&str> = std::iter::once(template).chain(name_template).collect();
+    let fields = TemplateFields::from_info(src_file, &templates)?;
+    let template_ctx = fields.context(src_file);
     let sub_dir_rel = render(template, &template_ctx);
+    let (base_name, base_stem, base_ext) = target_file_name(src_file, name_template, &template_ctx);
 
     // render 输出以 '/' 分隔；整串 join 会把 '/' 原样嵌进路径，Windows 下产生
     // `D:\Pictures\2003/09` 混合分隔符。逐段 join 让分隔符回到 OS 原生形态。
@@ -66,31 +166,31 @@ pub(super) fn generate_unique_name(
     // 范围 `0..=max_attempts`：i=0 试原名，i=1..=N 试 `_1..=_N`，共 N+1 候选；
     // 与配置文档"`unique_name_max_attempts` = N 个数字后缀"一致。旧 `0..N` 让
     // 后缀只到 _{N-1}，N=10 时 _10 永不被尝试，第 11 个同名文件直接失败。
+    // name_template 渲染出同名时同样追加序号；run_copy_loop 按 full_path 排序
+    // 处理，同输入下哪个文件拿到 `_1` 是确定的。
     for i in 0..=max_attempts {
-        let target_path = if i == 0 {
-            sub_dir_path.join(file_name)
-        } else {
-            // 无扩展名文件不拼 '.'：尾点文件名在 Linux 是怪文件，Windows 下
-            // CreateFile 会剥掉尾点，使 exists 判定与实际创建路径不一致。
-            let name = if ext.is_empty() {
-                format!("{file_stem}_{i}")
-            } else {
-                format!("{file_stem}_{i}.{ext}")
-            };
-            sub_dir_path.join(name)
-        };
-        let target_loc = output_dir.with_path(target_path);
+        let name = candidate_name(&base_name, &base_stem, &base_ext, i);
+        let target_loc = output_dir.with_path(sub_dir_path.join(name));
 
         // 对远端 backend 也通过 backend.exists 检测；同 backend 实例对 Local 等价。
         // exists 的 IO 错误（网络抖动等）必须传播：若吞成"不存在"，后续 open_write
         // 会 truncate 覆盖已存在目标，move 模式下源随后被删即永久数据丢失。
-        if !output_backend.exists(&target_loc)? {
+        if !planned.contains(&target_loc) && !output_backend.exists(&target_loc)? {
             return Ok(Some((sub_dir_loc, target_loc)));
         }
     }
     Ok(None)
 }
 
+// 渲染出的文件名按最后一个 '.' 拆 stem / ext，供冲突序号插在扩展名前；
+// `.hidden` 这类点开头的名字整体视为 stem（与 Utf8Path::file_stem 一致）。
+fn split_file_name(name: String) -> (String, String, String) {
+    let path = Utf8Path::new(&name);
+    let stem = path.file_stem().unwrap_or(&name).to_string();
+    let ext = path.extension().unwrap_or("").to_string();
+    (name, stem, ext)
+}
+
 pub(super) fn any_non_english(s: &str) -> bool {
     s.chars().any(|c| c as u32 > 127)
 }
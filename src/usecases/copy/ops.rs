@@ -1,19 +1,22 @@
 //! 单文件复制/移动操作：重复检测 → 媒体过滤 → 唯一命名 → fast-path rename 或流式拷贝。
 
 use std::collections::HashSet;
-use std::io::{BufReader, BufWriter, Write};
+use std::io::{BufReader, BufWriter, Read, Write};
 use std::sync::Arc;
 
 use tracing::debug;
 use tracing::warn;
 
-use super::naming::generate_unique_name;
+use super::naming::{companion_target, generate_unique_name};
 use super::run::{CopyOpts, feature_of};
+use crate::entities::SecureHash;
 use crate::entities::backend::Backend;
 use crate::entities::common;
 use crate::entities::file_index::Index;
 use crate::entities::file_info::Info;
 use crate::entities::uri::Location;
+use crate::usecases::catalog::CatalogOpts;
+use crate::usecases::faces::has_person;
 
 /// `stream_copy` 的 `BufReader`/`BufWriter` 容量：1 MiB 与 `STREAM_CHUNK` 同口径，
 /// 对远端单文件 5 GiB 视频比 `std::io::copy` 默认 8 KiB 减少 ~128× syscall/RTT
@@ -33,31 +36,72 @@ const STREAM_BUFFER_BYTES: usize = 1 << 20;
 // 累加下的虚假 miss。
 #[inline(never)]
 #[cfg_attr(coverage_nightly, coverage(off))]
+#[expect(
+    clippy::too_many_arguments,
+    reason = "mkdir_cache / planned 为 run_copy_loop 持有的 loop 级状态，逐个透传"
+)]
 pub(super) fn do_copy(
     src: &Info,
     output_dir: &Location,
     output_backend: &Arc<dyn Backend>,
     output_index: &mut Index,
     mkdir_cache: &mut HashSet<Location>,
+    planned: &mut HashSet<Location>,
     opts: &CopyOpts<'_>,
+    mut catalog: Option<&mut CatalogOpts<'_>>,
 ) -> common::Result<bool> {
     let src_loc = src.location().clone();
     let src_display = src.full_path.as_str();
     let feature = feature_of(opts.remove);
 
+    // 星级/色标与 `--person` 过滤先于判重：未命中的源文件原地不动——move 模式下
+    // 即使 output 已有副本也不删源（被拒的照片不该因归档区恰有副本而消失）。
+    if opts.filter.is_active() && !opts.filter.matches(&src.tags()) {
+        debug!(
+            feature,
+            operation = "filter_tags",
+            result = "skipped_filtered",
+            source = %src_display,
+            "file does not match --min-rating / --label"
+        );
+        return Ok(false);
+    }
+    if let Some(person) = opts.person
+        && !has_person(src.persons(), person)
+    {
+        debug!(
+            feature,
+            operation = "filter_person",
+            result = "skipped_filtered",
+            source = %src_display,
+            person,
+            "file does not contain the --person named in face db"
+        );
+        return Ok(false);
+    }
+
     // 涉及物理删除/移动，判等用 SHA-512 杜绝 xxh3 碰撞误删。
     if let Some(dup) = output_index.exists(src, true)? {
+        let dup = output_dir.with_path(dup);
         debug!(
             feature,
             operation = "detect_duplicate",
             result = "duplicate",
             source = %src_display,
-            duplicate = %dup,
+            duplicate = %dup.display(),
             "source duplicates an existing file in output"
         );
+        if let Some(c) = catalog.as_mut().filter(|_| !opts.dry_run) {
+            // exists(secure) 命中时 SHA-512 已缓存，此处不再读盘。
+            c.catalog
+                .record(&src.secure_hash()?, src.size, dup.display());
+        }
         if opts.remove && !opts.dry_run {
             src.backend().remove_file(&src_loc)?;
         }
+        // 伴随 sidecar 已被本媒体认领、不会单独处理：随到归档区已有副本旁（move 下一并
+        // 移出源目录），否则媒体删了 sidecar 却孤零零留在源目录。
+        carry_companions(src, &dup, output_backend, opts);
         return Ok(false);
     }
 
@@ -72,9 +116,40 @@ pub(super) fn do_copy(
         return Ok(false);
     }
 
-    if let Some((target_dir_loc, target_loc)) =
-        generate_unique_name(src, output_dir, output_backend, opts.template)?
-    {
+    // move fast-path 会把源改名走：SHA-512 须在搬运前算好，落盘后再登记。
+    let catalog_hash = match catalog.as_deref() {
+        Some(c) => {
+            let hash = src.secure_hash()?;
+            let known = c.catalog.lookup(&hash, src.size).filter(|_| c.skip_known);
+            if let Some(known) = known {
+                let archived = known.location.as_str();
+                // catalog 只说明"曾经归档过"，不证明归档区此刻仍有该内容：move 也不删源。
+                debug!(
+                    feature,
+                    operation = "detect_duplicate",
+                    result = "known",
+                    source = %src_display,
+                    archived,
+                    "source content is already recorded in the catalog"
+                );
+                return Ok(false);
+            }
+            Some(hash)
+        }
+        None => None,
+    };
+
+    if let Some((target_dir_loc, target_loc)) = generate_unique_name(
+        src,
+        output_dir,
+        output_backend,
+        opts.template,
+        opts.name_template,
+        planned,
+    )? {
+        // 选中即预定：dry-run 下目标不落盘，后续同名源靠它拿到 `_1`；真跑时
+        // 传输失败也保留预定，至多让后者多一个序号。
+        planned.insert(target_loc.clone());
         if opts.dry_run {
             let target_display = target_loc.display();
             debug!(
@@ -85,6 +160,7 @@ pub(super) fn do_copy(
                 target = %target_display,
                 "would transfer file"
             );
+            carry_companions(src, &target_loc, output_backend, opts);
             return Ok(true);
         }
 
@@ -104,6 +180,7 @@ pub(super) fn do_copy(
             // bind mount / btrfs subvol 等所有边界），自己再判一遍既冗余又会漏边界。
             // mkparents=false：上方 mkdir_p 已建好父目录。
             src.backend().rename(&src_loc, &target_loc, false)?;
+            record_transfer(catalog, catalog_hash, src.size, &target_loc);
         } else {
             // 跨 backend 或 copy（remove=false）走 stream（mkparents=false 同上）。
             stream_copy(src, &target_loc, output_backend.as_ref())?;
@@ -111,6 +188,7 @@ pub(super) fn do_copy(
             // 若 remove 失败仍向上传 Err 计 failed，但 dst 已登记 → 重跑或下批同
             // hash 源不会再写一份副本（旧实现 ? 直接传 Err 跳过 add 致重复副本）。
             _ = output_index.add(src.cloned_at(target_loc.clone(), Arc::clone(output_backend)));
+            record_transfer(catalog, catalog_hash, src.size, &target_loc);
             if opts.remove {
                 remove_src_after_stream_copy(src, &src_loc, src_display, &target_loc)?;
             }
@@ -123,6 +201,7 @@ pub(super) fn do_copy(
                 target = %target_display,
                 "file transferred"
             );
+            carry_companions(src, &target_loc, output_backend, opts);
             return Ok(true);
         }
         let target_display = target_loc.display();
@@ -134,6 +213,7 @@ pub(super) fn do_copy(
             target = %target_display,
             "file transferred"
         );
+        carry_companions(src, &target_loc, output_backend, opts);
 
         // fast-path rename 成功路径：dst 字节与 src 等同，复用 src 的 hash / size /
         // EXIF 入 output_index，避免对刚写完的 dst 重新 stat + 读 4 KiB；同时消除
@@ -149,6 +229,18 @@ pub(super) fn do_copy(
     }
 }
 
+// 内容已落到 `target`：登记（或覆盖）catalog 的最后已知位置。
+fn record_transfer(
+    catalog: Option<&mut CatalogOpts<'_>>,
+    hash: Option<SecureHash>,
+    size: u64,
+    target: &Location,
+) {
+    if let (Some(c), Some(hash)) = (catalog, hash) {
+        c.catalog.record(&hash, size, target.display());
+    }
+}
+
 // stream_copy + remove(=move) 路径下的 src 删除步骤。抽出独立 fn 是为了用
 // `#[cfg_attr(coverage_nightly, coverage(off))]` 把内部 wrap 错误的 closure 从严格
 // 100% region 分母剔除——该 closure 仅在 stream_copy 成功后 remove_file Err 时触发，
@@ -177,7 +269,111 @@ fn remove_src_after_stream_copy(
         .map_err(common::Error::from)
 }
 
-/// 测试 shim：调原 [`do_copy`] 时按需构造空 `mkdir_cache`。生产路径走
+/// 改名模式下把伴随 sidecar（`.xmp` / `.json`）搬到媒体新名旁并同步改名
+///（规则见 [`companion_target`]）。best-effort：媒体已落盘，伴随文件失败只 warn
+/// 不计 failed；目标已存在时不覆盖（同内容视为已搬运）。判重命中的媒体同样调用，
+/// `target_loc` 为归档区已有副本。
+fn carry_companions(
+    src: &Info,
+    target_loc: &Location,
+    output_backend: &Arc<dyn Backend>,
+    opts: &CopyOpts<'_>,
+) {
+    let Some(provider) = opts.companions.filter(|_| opts.name_template.is_some()) else {
+        return;
+    };
+    let feature = feature_of(opts.remove);
+    let src_be = src.backend();
+    for companion in provider(src.location(), &src_be) {
+        let Some(dst) = companion_target(src.location(), target_loc, &companion) else {
+            continue;
+        };
+        let source = companion.display();
+        let target = dst.display();
+        if opts.dry_run {
+            debug!(
+                feature,
+                operation = "carry_companion",
+                result = "dry_run",
+                source,
+                target,
+                "would transfer sidecar companion"
+            );
+            continue;
+        }
+        match transfer_companion(&companion, &dst, &src_be, output_backend, opts.remove) {
+            Ok(()) => debug!(
+                feature,
+                operation = "carry_companion",
+                result = "ok",
+                source,
+                target,
+                "sidecar companion transferred"
+            ),
+            Err(e) => {
+                let err = e.to_string();
+                warn!(
+                    feature,
+                    operation = "carry_companion",
+                    result = "error",
+                    source,
+                    target,
+                    err,
+                    "cannot transfer sidecar companion"
+                );
+            }
+        }
+    }
+}
+
+fn transfer_companion(
+    companion: &Location,
+    dst: &Location,
+    src_be: &Arc<dyn Backend>,
+    output_backend: &Arc<dyn Backend>,
+    remove: bool,
+) -> common::Result<()> {
+    // 与媒体同口径：exists 出错即放弃，绝不在不确定时覆盖已有文件。
+    if output_backend.exists(dst)? {
+        // 目标已是同内容副本（重跑 / 重复媒体）：视为已搬运，move 下只移除源侧。
+        if same_content(src_be.as_ref(), companion, output_backend.as_ref(), dst)? {
+            if remove {
+                src_be.remove_file(companion)?;
+            }
+            return Ok(());
+        }
+        return Err(common::Error::Io(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            format!("{} already exists", dst.display()),
+        )));
+    }
+    if remove && src_be.scheme() == "local" && output_backend.scheme() == "local" {
+        src_be.rename(companion, dst, false)?;
+        return Ok(());
+    }
+    stream_copy_loc(src_be.as_ref(), companion, dst, output_backend.as_ref())?;
+    if remove {
+        src_be.remove_file(companion)?;
+    }
+    Ok(())
+}
+
+// sidecar 体积小（XMP / Takeout JSON），整读比较即可。
+fn same_content(
+    src_be: &dyn Backend,
+    src: &Location,
+    dst_be: &dyn Backend,
+    dst: &Location,
+) -> std::io::Result<bool> {
+    let read = |be: &dyn Backend, loc: &Location| -> std::io::Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        be.open_read(loc)?.read_to_end(&mut bytes)?;
+        Ok(bytes)
+    };
+    Ok(read(src_be, src)? == read(dst_be, dst)?)
+}
+
+/// 测试 shim：调原 [`do_copy`] 时按需构造空 `mkdir_cache` / `planned`。生产路径走
 /// `run_copy_loop` 持有的 loop 级缓存（命中已建目录跳过重复 `mkdir_p` RTT），
 /// 测试桩不关心缓存复用，每次空 set 入参等价旧行为；既保留 `mkdir_cache` 参数
 /// 强制每次调用决策（不退化为隐式默认），又让 12 处测试调用零改动。
@@ -190,7 +386,16 @@ pub(super) fn do_copy_with_default_cache(
     opts: &CopyOpts<'_>,
 ) -> common::Result<bool> {
     let mut mc: HashSet<Location> = HashSet::new();
-    do_copy(src, output_dir, output_backend, output_index, &mut mc, opts)
+    do_copy(
+        src,
+        output_dir,
+        output_backend,
+        output_index,
+        &mut mc,
+        &mut HashSet::new(),
+        opts,
+        None,
+    )
 }
 
 /// 用源 Info 的 backend 读 + 输出 backend 写。两个 backend 同一实例时与 `copy_file`
@@ -204,8 +409,17 @@ pub(super) fn do_copy_with_default_cache(
 /// 单文件单 RTT 已是上限；buffered 让 writer flush 也按 MiB 触发）。
 #[inline(never)]
 fn stream_copy(src: &Info, target: &Location, out_be: &dyn Backend) -> common::Result<()> {
-    let src_be = src.backend();
-    let reader = src_be.open_read(src.location())?;
+    stream_copy_loc(src.backend().as_ref(), src.location(), target, out_be)
+}
+
+// stream_copy 的 Location 版：伴随 sidecar 没有 Info，直接按位置读写。
+pub(crate) fn stream_copy_loc(
+    src_be: &dyn Backend,
+    src_loc: &Location,
+    target: &Location,
+    out_be: &dyn Backend,
+) -> common::Result<()> {
+    let reader = src_be.open_read(src_loc)?;
     let writer = out_be.open_write(target, false)?;
     let mut br = BufReader::with_capacity(STREAM_BUFFER_BYTES, reader);
     let mut bw = BufWriter::with_capacity(STREAM_BUFFER_BYTES, writer);
@@ -5,7 +5,6 @@ use std::sync::Arc;
 
 use chrono::FixedOffset;
 use chrono::Offset;
-use time::UtcOffset;
 use tracing::debug;
 use tracing::error;
 use tracing::trace;
@@ -14,19 +13,22 @@ use super::ops::do_copy;
 use crate::entities::backend::Backend;
 use crate::entities::common;
 use crate::entities::common::{canonical_prefix, under_prefix};
-use crate::entities::file_index::{CandidateProvider, Index, VisitStats};
+use crate::entities::file_index::{
+    CandidateProvider, CompanionProvider, Index, TagProvider, VisitStats,
+};
+use crate::entities::file_info::Info;
 use crate::entities::uri::Location;
+use crate::usecases::catalog::CatalogOpts;
 use crate::usecases::config::config;
+use crate::usecases::event::{assign_events, uses_event};
+use crate::usecases::faces::{PersonOpts, assign_persons};
 use crate::usecases::report::{CopyReport, Report, ReportError, ReportSink};
+use crate::usecases::tag_filter::TagFilter;
 
 /// usecase 入口的 source / output 对：把 [`Location`] 与负责该 scheme 的
 /// [`Backend`] 句柄一起传入，避免内层重新解析 URI。
 pub type Source = (Location, Arc<dyn Backend>);
 
-pub(super) const MONTH: [&str; 13] = [
-    "00", "01", "02", "03", "04", "05", "06", "07", "08", "09", "10", "11", "12",
-];
-
 pub(super) const FEATURE_COPY: &str = "copy";
 pub(super) const FEATURE_MOVE: &str = "move";
 
@@ -42,20 +44,48 @@ pub struct CopyOpts<'a> {
     pub remove: bool,
     pub include_non_media: bool,
     pub template: &'a str,
+    /// `--min-rating` / `--label`；未命中的源文件原地不动（move 也不删）。
+    pub filter: TagFilter,
+    /// `--name-template`；None 时沿用源文件名。
+    pub name_template: Option<&'a str>,
+    /// 伴随 sidecar 发现；仅在 `name_template` 生效时使用——改名后 `.xmp` / `.json`
+    /// 不随之改名就会与媒体失联，保留原名时则维持旧行为（sidecar 作为普通文件处理）。
+    pub companions: Option<CompanionProvider>,
+    /// `--person`；人脸库里不含该已命名人物的源文件原地不动（move 也不删）。
+    pub person: Option<&'a str>,
 }
 
-pub(super) fn configured_offset() -> UtcOffset {
-    offset_from_hours(config().copy.timezone_offset_hours)
-}
-
-// 越界回退到 UTC，避免 panic；time crate 合法范围 ±25:59:59。
-pub(super) fn offset_from_hours(hours: i8) -> UtcOffset {
-    UtcOffset::from_whole_seconds(i32::from(hours) * 3600).unwrap_or(UtcOffset::UTC)
+/// [`copy_with_sidecar`] 的调用选项：CLI flag 与 adapters 注入的 provider 一并打包。
+/// `Default` 即最简复制：不删源、仅媒体、`copy.archive_template`、沿用源文件名，不过滤、
+/// 不接 sidecar / catalog / 人脸库，不写报告。
+#[derive(Default)]
+pub struct CopyRequest<'a> {
+    pub dry_run: bool,
+    /// true 即 move：落盘（或判重命中）后删源。
+    pub remove: bool,
+    pub include_non_media: bool,
+    /// `--archive-template`；None 时取 `copy.archive_template`。
+    pub archive_template: Option<&'a str>,
+    /// `--name-template`；None 时沿用源文件名。
+    pub name_template: Option<&'a str>,
+    pub report_sink: Option<&'a dyn ReportSink>,
+    /// 外部时间候选（P3 sidecar / P0 Photos 图库）发现。
+    pub sidecar: Option<CandidateProvider>,
+    /// 星级 / 色标 sidecar 读取；仅在过滤启用或模板引用时被调用。
+    pub tags: Option<TagProvider>,
+    /// 改名时随媒体搬运的伴随 sidecar 发现，见 [`CopyOpts::companions`]。
+    pub companions: Option<CompanionProvider>,
+    /// `--min-rating` / `--label`。
+    pub filter: TagFilter,
+    /// 内容哈希 catalog；None 时不查不记。
+    pub catalog: Option<CatalogOpts<'a>>,
+    /// 人脸库；仅 `--person` 或模板引用 `{person}` 时由调用方加载。
+    pub persons: Option<PersonOpts<'a>>,
 }
 
-// chrono::FixedOffset 用于把 EXIF / 文件名内无时区的 NaiveDateTime 当相机本地时间
-// 解释；与 time::UtcOffset 共用同一份 timezone_offset_hours 配置。
-pub(super) fn configured_chrono_offset() -> FixedOffset {
+// chrono::FixedOffset 既用于把 EXIF / 文件名内无时区的 NaiveDateTime 当相机本地
+// 时间解释，也用于归档模板按配置时区展开 `{year}` / `{date:…}` 等占位符。
+pub(crate) fn configured_chrono_offset() -> FixedOffset {
     chrono_offset_from_hours(config().copy.timezone_offset_hours)
 }
 
@@ -64,7 +94,8 @@ pub(super) fn chrono_offset_from_hours(hours: i8) -> FixedOffset {
     FixedOffset::east_opt(i32::from(hours) * 3600).unwrap_or_else(|| chrono::Utc.fix())
 }
 
-/// 测试 shim：等价于 `copy_with_sidecar(..., None)`。
+/// 测试 shim：其余 [`CopyRequest`] 字段取默认（不带 name template / sidecar provider /
+/// 过滤 / catalog / 人脸库）。
 /// 生产路径（dispatch）走 [`copy_with_sidecar`] 注入 P3 发现；仅测试用本简短入口。
 #[cfg(test)]
 pub fn copy(
@@ -79,37 +110,60 @@ pub fn copy(
     copy_with_sidecar(
         sources,
         output,
-        dry_run,
-        remove,
-        include_non_media,
-        archive_template,
-        report_sink,
-        None,
+        CopyRequest {
+            dry_run,
+            remove,
+            include_non_media,
+            archive_template,
+            report_sink,
+            ..CopyRequest::default()
+        },
     )
 }
 
-// 8 个参数源于 CLI 选项的一比一透传；与 make_report 同理。
-#[expect(
-    clippy::too_many_arguments,
-    reason = "CLI 选项 + sidecar provider 一比一透传，折结构体会让 dispatch 调用点同样冗长"
-)]
+/// copy / move 入口：按 `request` 把 sources 归档到 output。
+///
+/// # Errors
+///
+/// 源目录位于 output 内，或 output 目录无法创建时返回 `Err`。单文件失败累计到
+/// `report.failed` / `errors`，不中断主流程。
 pub fn copy_with_sidecar(
     sources: &[Source],
     output: Source,
-    dry_run: bool,
-    remove: bool,
-    include_non_media: bool,
-    archive_template: Option<&str>,
-    report_sink: Option<&dyn ReportSink>,
-    sidecar: Option<CandidateProvider>,
+    request: CopyRequest<'_>,
 ) -> common::Result<CopyReport> {
+    let CopyRequest {
+        dry_run,
+        remove,
+        include_non_media,
+        archive_template,
+        name_template,
+        report_sink,
+        sidecar,
+        tags,
+        companions,
+        filter,
+        catalog,
+        persons,
+    } = request;
     let (output_loc, output_backend) = output;
     let template = archive_template.unwrap_or(&config().copy.archive_template);
 
     let output_prefix = canonical_prefix(&output_loc);
     ensure_sources_outside_output(sources, &output_prefix)?;
     let feature = feature_of(remove);
-    let source = build_source_index(sources, &output_prefix, sidecar, feature);
+    // 外部标记每文件多一次读，仅在过滤或模板用到时才注入。
+    let tags = tags.filter(|_| {
+        filter.is_active() || uses_tags(template) || name_template.is_some_and(uses_tags)
+    });
+    let mut source = build_source_index(sources, &output_prefix, sidecar, tags, feature);
+    if uses_event(template) || name_template.is_some_and(uses_event) {
+        assign_events(&mut source, configured_chrono_offset());
+    }
+    // 调用方只在 `--person` 或模板引用 `{person}` 时才加载人脸库。
+    if let Some(p) = &persons {
+        assign_persons(&mut source, p.db);
+    }
 
     let total_files = source.files().len();
     let scan_stats = source.stats();
@@ -148,9 +202,13 @@ pub fn copy_with_sidecar(
         remove,
         include_non_media,
         template,
+        filter,
+        name_template,
+        companions,
+        person: persons.and_then(|p| p.person),
     };
     let (copied, ignored, failed, errors) =
-        run_copy_loop(&source, &output_loc, &output_backend, &opts);
+        run_copy_loop(&source, &output_loc, &output_backend, &opts, catalog);
 
     log_operation_summary(
         feature,
@@ -265,6 +323,7 @@ fn build_source_index(
     sources: &[Source],
     output_prefix: &str,
     sidecar: Option<CandidateProvider>,
+    tags: Option<TagProvider>,
     feature: &'static str,
 ) -> Index {
     let mut source = Index::new();
@@ -290,15 +349,27 @@ fn build_source_index(
     if let Some(provider) = sidecar {
         source.enrich_candidates(provider);
     }
+    if let Some(provider) = tags {
+        source.enrich_tags(provider);
+    }
     source
 }
 
+// 模板是否引用外部标记可能提供的占位符（sidecar 星级 / 色标、Photos 相册 / 原名）；
+// 按前缀匹配以覆盖 `{label:upper}` / `{rating|0}` 等带修饰的写法。
+pub(crate) fn uses_tags(template: &str) -> bool {
+    ["{rating", "{label", "{album", "{original_name"]
+        .iter()
+        .any(|p| template.contains(p))
+}
+
 // 拆出循环体，让 copy() 保持在 100 行内。
 fn run_copy_loop(
     source: &Index,
     output_loc: &Location,
     output_backend: &Arc<dyn Backend>,
     opts: &CopyOpts<'_>,
+    mut catalog: Option<CatalogOpts<'_>>,
 ) -> (usize, usize, usize, Vec<ReportError>) {
     let mut output_index = Index::new();
     output_index.visit_location(output_loc, output_backend);
@@ -316,21 +387,40 @@ fn run_copy_loop(
     // 仅命中已成功路径；mkdir_p 失败的 dir 不入缓存让下次仍尝试创建（避开
     // 「首次失败永驻 false-positive」陷阱）。
     let mut mkdir_cache: HashSet<Location> = HashSet::new();
+    // 本批已预定的目标名：dry-run 不落盘，`exists` 看不到前面文件选中的名字，
+    // 同名源须靠它拿到与真跑一致的 `_N`（同 rename 的 `planned`）。
+    let mut planned: HashSet<Location> = HashSet::new();
 
     // HashMap.values() 迭代顺序受哈希种子影响；move 模式下两份同 hash 源文件,
     // 哪份留下哪份删随版本/进程变化致结果不可重现。按 full_path 排序后迭代保证
     // 同输入同输出，便于审计回放和测试断言。
     let mut entries: Vec<_> = source.files().values().collect();
     entries.sort_by(|a, b| a.full_path.cmp(&b.full_path));
+    let claimed = claimed_companions(&entries, opts);
 
     for src in entries {
+        if claimed.contains(src.location()) {
+            // 伴随 sidecar 只随其媒体处理：媒体搬运（含判重命中）时随行，媒体因过滤 /
+            // catalog 留在源目录时一同原地保留，不再单独处理。
+            debug!(
+                feature,
+                operation = "carry_companion",
+                result = "skipped_companion",
+                source = %src.full_path,
+                "sidecar travels with its media file"
+            );
+            ignored += 1;
+            continue;
+        }
         match do_copy(
             src,
             output_loc,
             output_backend,
             &mut output_index,
             &mut mkdir_cache,
+            &mut planned,
             opts,
+            catalog.as_mut(),
         ) {
             Ok(true) => {
                 copied += 1;
@@ -361,6 +451,18 @@ fn run_copy_loop(
     (copied, ignored, failed, errors)
 }
 
+// 改名模式下被媒体认领的伴随 sidecar 集合；未启用 name_template 时为空集。
+// 认领即承诺由媒体一侧处置（见 `ops::carry_companions`），不会被单独搬运或遗弃。
+fn claimed_companions(entries: &[&Info], opts: &CopyOpts<'_>) -> HashSet<Location> {
+    let Some(provider) = opts.companions.filter(|_| opts.name_template.is_some()) else {
+        return HashSet::new();
+    };
+    entries
+        .iter()
+        .flat_map(|src| provider(src.location(), &src.backend()))
+        .collect()
+}
+
 // 构造 CopyReport 值对象；抽出避免参数列表过长。
 // scanned = 入索引文件数（indexed）+ walker 触达但跳过的（empty/unreadable/walker_errors）。
 #[expect(
@@ -0,0 +1,184 @@
+//! 连拍分组：拍摄时间窗 + EXIF 连拍标识（Apple `BurstUUID` / Canon 连拍序号）+ pHash。
+//!
+//! 两两判定是否相连（[`Rules::link`]），再经 `phash::group_by` 取传递闭包成组；
+//! 每组记下组内相连成员对用到的依据（[`GroupReason`]），写入 `GroupReport.reasons`。
+//!
+//! 判定顺序即可信度：连拍 ID 相同 → 同机型序号相邻且时间相近 → 时间窗内 → pHash 相似。
+//! `--group-by both` 下 pHash 链接另受 `phash_max_gap_secs` 约束：两图时间都已知却相差
+//! 过远（隔天同机位重拍）时不因画面相似并组；时间窗链接另须 pHash 距离不超过
+//! [`TIME_WINDOW_HAMMING_FACTOR`] 倍阈值——否则持续拍摄时每两张都在时间窗内，传递闭包会
+//! 把数百张无关照片串成一组。默认 `phash`，升级不改变既有分组。
+
+use std::io::Cursor;
+use std::str::FromStr;
+
+use camino::Utf8Path;
+use chrono::Utc;
+
+use super::phash::{group_by, hamming};
+use super::report::GroupReason;
+use crate::entities::exif::Exif;
+use crate::entities::media_time;
+use crate::usecases::config::{FaceConfig, config};
+use crate::usecases::copy::run::configured_chrono_offset;
+
+/// Canon 连拍序号每次连拍从 1 重新计数：相邻序号只在该时间差内才视为同一连拍。
+const SEQUENCE_MAX_GAP_SECS: u64 = 60;
+
+/// `--group-by both` 下时间窗链接的宽松 pHash 上限：`phash_max_hamming` 的倍数。
+const TIME_WINDOW_HAMMING_FACTOR: u32 = 2;
+
+/// `cull --group-by`：分组依据。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GroupBy {
+    /// 仅拍摄时间窗与 EXIF 连拍标识。
+    Time,
+    /// 仅 pHash 画面相似（默认）。
+    #[default]
+    Phash,
+    /// 两者并用：pHash 链接受拍摄时间差上限约束，时间窗链接受宽松 pHash 上限约束。
+    Both,
+}
+
+impl GroupBy {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Time => "time",
+            Self::Phash => "phash",
+            Self::Both => "both",
+        }
+    }
+}
+
+impl FromStr for GroupBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "time" => Ok(Self::Time),
+            "phash" => Ok(Self::Phash),
+            "both" => Ok(Self::Both),
+            _ => Err(format!(
+                "unknown group-by mode '{s}'; expected time, phash or both"
+            )),
+        }
+    }
+}
+
+/// 单张图参与分组的特征；`capture_secs` 为判定后的拍摄时间（UTC epoch），未知为 None。
+#[derive(Debug, Default)]
+pub(super) struct Shot {
+    pub hash: u64,
+    pub capture_secs: Option<i64>,
+    pub burst_id: Option<String>,
+    /// Canon 连拍序号；0（单张）在构造时已归为 None。
+    pub sequence: Option<u32>,
+    pub model: Option<String>,
+}
+
+impl Shot {
+    /// 从 scan 阶段已整读的字节解析 EXIF：拍摄时间走 EXIF（P0/P1）+ 文件名（P2）候选
+    /// 的 `media_time::resolve`，不取 mtime——拷贝/同步会把整批文件的 mtime 刷成同一
+    /// 时刻，按时间窗会把无关照片并成一组。低于 `exif.valid_date_time_secs` 视为未知。
+    pub(super) fn parse(bytes: Vec<u8>, mime: &str, path: &Utf8Path, hash: u64) -> Self {
+        let offset = configured_chrono_offset();
+        let exif = Exif::from_reader(Box::new(Cursor::new(bytes)), mime, offset);
+        let mut candidates = media_time::candidates_from_exif(&exif, offset);
+        candidates.extend(media_time::candidates_from_filename(path, offset));
+        let valid_secs = config().exif.valid_date_time_secs;
+        let capture_secs = media_time::resolve(candidates, exif.gps_utc(), None, Utc::now())
+            .map(|d| d.utc.timestamp())
+            .filter(|&secs| secs > 0 && secs.cast_unsigned() >= valid_secs);
+        Self {
+            hash,
+            capture_secs,
+            burst_id: exif.burst_id().map(str::to_string),
+            sequence: exif.burst_sequence().filter(|&n| n > 0),
+            model: exif.model().map(str::to_string),
+        }
+    }
+}
+
+/// 两两链接规则。
+pub(super) struct Rules {
+    mode: GroupBy,
+    phash_max_hamming: u8,
+    window_secs: u64,
+    phash_max_gap_secs: Option<u64>,
+}
+
+impl Rules {
+    pub(super) fn new(mode: GroupBy, phash_max_hamming: u8, face_cfg: &FaceConfig) -> Self {
+        Self {
+            mode,
+            phash_max_hamming,
+            window_secs: u64::from(face_cfg.burst_window_secs),
+            phash_max_gap_secs: (face_cfg.phash_max_gap_secs > 0)
+                .then_some(face_cfg.phash_max_gap_secs),
+        }
+    }
+
+    /// `a`、`b` 是否相连；相连时返最可信的依据。
+    pub(super) fn link(&self, a: &Shot, b: &Shot) -> Option<GroupReason> {
+        let gap = a
+            .capture_secs
+            .zip(b.capture_secs)
+            .map(|(x, y)| x.abs_diff(y));
+        let within = |limit: u64| gap.is_some_and(|g| g <= limit);
+        let distance = hamming(a.hash, b.hash);
+        let max_hamming = u32::from(self.phash_max_hamming);
+        if self.mode != GroupBy::Phash {
+            if a.burst_id.is_some() && a.burst_id == b.burst_id {
+                return Some(GroupReason::BurstId);
+            }
+            if a.model.is_some()
+                && a.model == b.model
+                && a.sequence
+                    .zip(b.sequence)
+                    .is_some_and(|(x, y)| x.abs_diff(y) == 1)
+                && within(SEQUENCE_MAX_GAP_SECS)
+            {
+                return Some(GroupReason::Sequence);
+            }
+            if within(self.window_secs)
+                && (self.mode == GroupBy::Time
+                    || distance <= max_hamming * TIME_WINDOW_HAMMING_FACTOR)
+            {
+                return Some(GroupReason::TimeWindow);
+            }
+        }
+        if self.mode == GroupBy::Time || distance > max_hamming {
+            return None;
+        }
+        let too_far = self.mode == GroupBy::Both
+            && gap.is_some()
+            && self.phash_max_gap_secs.is_some_and(|max| !within(max));
+        (!too_far).then_some(GroupReason::Phash)
+    }
+}
+
+/// 按 `rules` 把 `shots` 分组，返 `(组内下标, 成组依据)`；单图组依据为空。
+pub(super) fn group_shots(shots: &[&Shot], rules: &Rules) -> Vec<(Vec<usize>, Vec<GroupReason>)> {
+    group_by(shots.len(), |i, j| rules.link(shots[i], shots[j]).is_some())
+        .into_iter()
+        .map(|members| {
+            let mut reasons: Vec<GroupReason> = members
+                .iter()
+                .enumerate()
+                .flat_map(|(k, &i)| {
+                    members[k + 1..]
+                        .iter()
+                        .filter_map(move |&j| rules.link(shots[i], shots[j]))
+                })
+                .collect();
+            reasons.sort_unstable();
+            reasons.dedup();
+            (members, reasons)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "burst_tests.rs"]
+mod tests;
@@ -0,0 +1,127 @@
+//! `burst` 单测：覆盖 `--group-by` 解析、三种模式的链接规则与组内依据汇总。
+
+use super::*;
+
+fn rules(mode: GroupBy) -> Rules {
+    Rules::new(mode, 4, &FaceConfig::default())
+}
+
+fn shot(hash: u64, secs: Option<i64>) -> Shot {
+    Shot {
+        hash,
+        capture_secs: secs,
+        ..Shot::default()
+    }
+}
+
+#[test]
+fn group_by_parses_case_insensitively_and_round_trips() {
+    for mode in [GroupBy::Time, GroupBy::Phash, GroupBy::Both] {
+        assert_eq!(mode.as_str().parse::<GroupBy>(), Ok(mode));
+    }
+    assert_eq!(" PHash ".parse::<GroupBy>(), Ok(GroupBy::Phash));
+    let err = "exif".parse::<GroupBy>().unwrap_err();
+    assert!(err.contains("expected time, phash or both"), "{err}");
+}
+
+#[test]
+fn group_by_defaults_to_phash() {
+    assert_eq!(GroupBy::default(), GroupBy::Phash);
+}
+
+/// 画面完全不同但 1 秒内连拍：仅 time 成组；both 要求时间窗链接也过宽松 pHash 上限。
+#[test]
+fn link_time_window_ignores_phash_only_in_time_mode() {
+    let (a, b) = (shot(0, Some(1_000)), shot(u64::MAX, Some(1_001)));
+    assert_eq!(
+        rules(GroupBy::Time).link(&a, &b),
+        Some(GroupReason::TimeWindow)
+    );
+    assert_eq!(rules(GroupBy::Both).link(&a, &b), None);
+    assert_eq!(rules(GroupBy::Phash).link(&a, &b), None);
+}
+
+/// 1 秒内、pHash 距离 8（超阈值 4、未超 2 倍）：both 以时间窗成组，phash 不成组。
+#[test]
+fn link_time_window_in_both_mode_accepts_loosely_similar_shots() {
+    let (a, b) = (shot(0, Some(1_000)), shot(0xFF, Some(1_001)));
+    assert_eq!(
+        rules(GroupBy::Both).link(&a, &b),
+        Some(GroupReason::TimeWindow)
+    );
+    assert_eq!(rules(GroupBy::Phash).link(&a, &b), None);
+    let far = shot(0x1FF, Some(1_001));
+    assert_eq!(rules(GroupBy::Both).link(&a, &far), None, "距离 9 超 2 倍阈值");
+}
+
+/// 持续拍摄、每两张相隔 1 秒但画面各不相同：both 不把整段串成一组。
+#[test]
+fn group_shots_both_does_not_chain_steady_shooting() {
+    let shots: Vec<Shot> = (0..6)
+        .map(|i| shot(if i % 2 == 0 { 0 } else { u64::MAX }, Some(i)))
+        .collect();
+    let refs: Vec<&Shot> = shots.iter().collect();
+    let groups = group_shots(&refs, &rules(GroupBy::Both));
+    assert_eq!(groups.len(), 2, "仅画面相似的两组：{groups:?}");
+}
+
+/// 画面相似但相隔两天：both 否决，phash 照常成组，时间未知时 both 不否决。
+#[test]
+fn link_vetoes_phash_across_large_time_gap_only_in_both_mode() {
+    let (a, b) = (shot(0, Some(0)), shot(1, Some(2 * 86_400)));
+    assert_eq!(rules(GroupBy::Both).link(&a, &b), None);
+    assert_eq!(rules(GroupBy::Phash).link(&a, &b), Some(GroupReason::Phash));
+    assert_eq!(rules(GroupBy::Time).link(&a, &b), None);
+    let unknown = shot(1, None);
+    assert_eq!(
+        rules(GroupBy::Both).link(&a, &unknown),
+        Some(GroupReason::Phash)
+    );
+}
+
+#[test]
+fn link_prefers_burst_id_then_adjacent_sequence_of_same_model() {
+    let mut a = shot(0, Some(0));
+    let mut b = shot(u64::MAX, Some(30));
+    a.burst_id = Some("B".into());
+    b.burst_id = Some("B".into());
+    assert_eq!(
+        rules(GroupBy::Time).link(&a, &b),
+        Some(GroupReason::BurstId)
+    );
+
+    a.burst_id = None;
+    (a.model, b.model) = (Some("EOS R5".into()), Some("EOS R5".into()));
+    (a.sequence, b.sequence) = (Some(3), Some(4));
+    assert_eq!(
+        rules(GroupBy::Time).link(&a, &b),
+        Some(GroupReason::Sequence)
+    );
+    b.sequence = Some(5);
+    assert_eq!(rules(GroupBy::Time).link(&a, &b), None, "序号不相邻");
+    b.sequence = Some(4);
+    b.capture_secs = Some(3_600);
+    assert_eq!(
+        rules(GroupBy::Time).link(&a, &b),
+        None,
+        "序号相邻但相隔一小时"
+    );
+}
+
+#[test]
+fn group_shots_collects_sorted_reasons_per_group() {
+    let shots = [
+        shot(0, Some(100)),
+        shot(0xFF, Some(101)),
+        shot(1, None),
+        shot(0x00FF_00FF_00FF_00FF, Some(5_000)),
+    ];
+    let refs: Vec<&Shot> = shots.iter().collect();
+    let mut groups = group_shots(&refs, &rules(GroupBy::Both));
+    groups.sort_by_key(|(members, _)| members[0]);
+    assert_eq!(groups.len(), 2);
+    let (members, reasons) = &groups[0];
+    assert_eq!(members, &[0, 1, 2]);
+    assert_eq!(reasons, &[GroupReason::TimeWindow, GroupReason::Phash]);
+    assert_eq!(groups[1], (vec![3], Vec::new()));
+}
@@ -0,0 +1,226 @@
+//! `cull` 分析缓存：按内容 SHA-512 记录每张图的 scan 特征（pHash + 全图清晰度）与评分阶段
+//! 的推理输出（SCRFD 人脸框 + 5 点、`MobileFaceNet` embedding、mesh 派生的 EAR / 微笑度、
+//! 左右眼闭眼概率）及未加权的画面质量度量。
+//!
+//! - 命中 scan 特征的图只读字节算哈希 + 解析 EXIF，不再整图 decode；命中推理输出的图在
+//!   评分阶段不读、不 decode、不跑模型。改 `w_*` / `ear_blink_max` / `phash_hamming_max` /
+//!   `face_cosine_min` 等权重与阈值后重跑只剩这部分开销
+//! - 推理输出依赖模型与检测参数：[`inference_fingerprint`] 与缓存记录的不符时丢弃全部推理
+//!   输出，pHash / 清晰度与模型无关照常保留。指纹只看路径，同路径替换模型文件需手动删缓存
+//! - 含 NaN / ∞ 的结果不入缓存（JSON 写不出），下次照常重算；facemesh / eyestate / 质量
+//!   模型失败后退化的结果（`ImageAnalysis::degraded`）同样不入缓存，免得一次故障永久生效
+//! - 持久化格式与写盘由 adapters 层负责，本模块只管内存中的查询与登记
+
+use std::collections::BTreeMap;
+
+use serde_derive::{Deserialize, Serialize};
+use sha2::{Digest, Sha512};
+
+use super::face_scoring::MeshFeatures;
+use super::identity_cluster::EMBED_DIM;
+use super::quality::ImageQuality;
+use super::run::ImageAnalysis;
+use crate::usecases::config::FaceConfig;
+use crate::usecases::face::FaceDetection;
+
+/// 缓存文件格式版本；结构不兼容地变化时递增（旧版本文件整体重建）。
+pub const CULL_CACHE_VERSION: u32 = 1;
+
+/// 全部图片的分析缓存。`BTreeMap` 让写出的文件按键有序、可 diff。
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CullCache {
+    pub version: u32,
+    /// 产出 `analysis` 时的推理口径，见 [`inference_fingerprint`]。
+    inference: String,
+    /// 内容 SHA-512 十六进制 → 该内容的分析结果。
+    entries: BTreeMap<String, CachedImage>,
+}
+
+impl Default for CullCache {
+    fn default() -> Self {
+        Self {
+            version: CULL_CACHE_VERSION,
+            inference: String::new(),
+            entries: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct CachedImage {
+    phash: u64,
+    sharpness: f32,
+    /// 评分阶段的推理输出；只有进过多图组的图才有。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    analysis: Option<CachedAnalysis>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct CachedAnalysis {
+    faces: Vec<CachedFace>,
+    quality: ImageQuality,
+}
+
+/// 单张脸：对齐 / 嵌入失败而被丢弃的脸不在其列，与 [`ImageAnalysis`] 同口径。
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct CachedFace {
+    bbox: [f32; 4],
+    score: f32,
+    landmarks_5pt: [[f32; 2]; 5],
+    /// `MobileFaceNet` embedding（[`EMBED_DIM`] 维）。
+    embedding: Vec<f32>,
+    mesh: MeshFeatures,
+    /// 左 / 右眼闭眼概率。
+    eye_state: (f32, f32),
+}
+
+impl CullCache {
+    /// 与当前推理口径对齐：指纹不符时丢弃全部推理输出并记下新指纹，返回丢弃条数。
+    pub(super) fn align_inference(&mut self, fingerprint: &str) -> usize {
+        if self.inference == fingerprint {
+            return 0;
+        }
+        self.inference = fingerprint.to_string();
+        self.entries
+            .values_mut()
+            .filter_map(|e| e.analysis.take())
+            .count()
+    }
+
+    /// 缓存的 `(pHash, 全图清晰度)`。
+    pub(super) fn scan(&self, key: &str) -> Option<(u64, f32)> {
+        self.entries.get(key).map(|e| (e.phash, e.sharpness))
+    }
+
+    /// 登记 scan 特征；已登记的内容不覆盖（推理输出随之保留）。
+    pub(super) fn record_scan(&mut self, key: &str, phash: u64, sharpness: f32) {
+        if !sharpness.is_finite() || self.entries.contains_key(key) {
+            return;
+        }
+        self.entries.insert(
+            key.to_string(),
+            CachedImage {
+                phash,
+                sharpness,
+                analysis: None,
+            },
+        );
+    }
+
+    /// 缓存的推理输出；手改过、embedding 维数不符的条目视为未命中。
+    pub(super) fn analysis(&self, key: &str) -> Option<ImageAnalysis> {
+        self.entries.get(key)?.analysis.as_ref()?.to_analysis()
+    }
+
+    /// 登记推理输出；须先经 [`Self::record_scan`] 登记过该内容。
+    pub(super) fn record_analysis(&mut self, key: &str, analysis: &ImageAnalysis) {
+        let cached = CachedAnalysis::from_analysis(analysis);
+        if let Some(entry) = self.entries.get_mut(key)
+            && cached.is_finite()
+        {
+            entry.analysis = Some(cached);
+        }
+    }
+
+    /// 已缓存的内容数。
+    #[must_use]
+    pub fn image_count(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+impl CachedAnalysis {
+    fn from_analysis(a: &ImageAnalysis) -> Self {
+        let faces = a
+            .faces
+            .iter()
+            .zip(&a.embeddings)
+            .zip(&a.meshes)
+            .zip(&a.eye_states)
+            .map(|(((face, embedding), mesh), eye_state)| CachedFace {
+                bbox: face.bbox,
+                score: face.score,
+                landmarks_5pt: face.landmarks_5pt,
+                embedding: embedding.to_vec(),
+                mesh: *mesh,
+                eye_state: *eye_state,
+            })
+            .collect();
+        Self {
+            faces,
+            quality: a.quality,
+        }
+    }
+
+    fn is_finite(&self) -> bool {
+        let q = &self.quality;
+        let quality = [
+            q.clipped,
+            q.noise_sigma,
+            q.motion_coherence,
+            q.motion_angle_deg,
+            q.tilt_deg,
+            q.subject_sharpness,
+        ]
+        .into_iter()
+        .chain(q.model_score);
+        let faces = self.faces.iter().flat_map(|f| {
+            f.bbox
+                .into_iter()
+                .chain([f.score, f.eye_state.0, f.eye_state.1])
+                .chain(f.landmarks_5pt.into_iter().flatten())
+                .chain(f.embedding.iter().copied())
+                .chain(f.mesh.ear)
+                .chain(f.mesh.smile)
+        });
+        quality.chain(faces).all(f32::is_finite)
+    }
+
+    fn to_analysis(&self) -> Option<ImageAnalysis> {
+        let mut analysis = ImageAnalysis {
+            faces: Vec::with_capacity(self.faces.len()),
+            embeddings: Vec::with_capacity(self.faces.len()),
+            meshes: Vec::with_capacity(self.faces.len()),
+            eye_states: Vec::with_capacity(self.faces.len()),
+            quality: self.quality,
+            degraded: false,
+        };
+        for face in &self.faces {
+            let embedding: [f32; EMBED_DIM] = face.embedding.as_slice().try_into().ok()?;
+            analysis.faces.push(FaceDetection {
+                bbox: face.bbox,
+                score: face.score,
+                landmarks_5pt: face.landmarks_5pt,
+            });
+            analysis.embeddings.push(embedding);
+            analysis.meshes.push(face.mesh);
+            analysis.eye_states.push(face.eye_state);
+        }
+        Some(analysis)
+    }
+}
+
+/// 缓存键：内容 SHA-512 十六进制，与 catalog / 人脸库同口径。
+pub(super) fn content_key(bytes: &[u8]) -> String {
+    hex::encode(&Sha512::digest(bytes)[..])
+}
+
+/// 推理口径指纹：模型路径 + 影响模型输出的检测 / crop 参数。权重与判定阈值不在其中——
+/// 它们只作用于缓存之后的评分。
+pub(super) fn inference_fingerprint(cfg: &FaceConfig) -> String {
+    format!(
+        "scrfd={}@{}/{};facenet={};facemesh={};eyestate={}@{};quality={}",
+        cfg.scrfd_model_path,
+        cfg.scrfd_score_threshold,
+        cfg.scrfd_nms_iou,
+        cfg.facenet_model_path,
+        cfg.facemesh_model_path,
+        cfg.eyestate_model_path,
+        cfg.eye_crop_radius_ratio,
+        cfg.quality_model_path,
+    )
+}
+
+#[cfg(test)]
+#[path = "cache_tests.rs"]
+mod tests;
@@ -0,0 +1,154 @@
+use super::*;
+
+fn sample_analysis(ear: f32) -> ImageAnalysis {
+    let mut embedding = [0.0; EMBED_DIM];
+    embedding[0] = 1.0;
+    ImageAnalysis {
+        faces: vec![FaceDetection {
+            bbox: [1.0, 2.0, 30.0, 40.0],
+            score: 0.9,
+            landmarks_5pt: [[5.0, 6.0]; 5],
+        }],
+        embeddings: vec![embedding],
+        meshes: vec![MeshFeatures {
+            ear: Some(ear),
+            smile: None,
+        }],
+        eye_states: vec![(0.1, 0.8)],
+        quality: ImageQuality {
+            noise_sigma: 2.5,
+            model_score: Some(6.0),
+            ..ImageQuality::default()
+        },
+        degraded: false,
+    }
+}
+
+fn assert_same(a: &ImageAnalysis, b: &ImageAnalysis) {
+    assert_eq!(
+        CachedAnalysis::from_analysis(a),
+        CachedAnalysis::from_analysis(b)
+    );
+}
+
+#[test]
+fn scan_features_are_recorded_once() {
+    let mut cache = CullCache::default();
+    assert_eq!(cache.scan("k"), None);
+    cache.record_scan("k", 0xabc, 120.0);
+    // 同内容再次登记不覆盖。
+    cache.record_scan("k", 0xdef, 1.0);
+    assert_eq!(cache.scan("k"), Some((0xabc, 120.0)));
+    // NaN 清晰度写不进 JSON：不登记，下次重算。
+    cache.record_scan("nan", 1, f32::NAN);
+    assert_eq!(cache.scan("nan"), None);
+    assert_eq!(cache.image_count(), 1);
+}
+
+#[test]
+fn analysis_round_trips_through_json() {
+    let mut cache = CullCache::default();
+    // 未经 record_scan 的内容不登记推理输出。
+    cache.record_analysis("k", &sample_analysis(0.3));
+    assert!(cache.analysis("k").is_none());
+
+    cache.record_scan("k", 7, 150.0);
+    cache.record_analysis("k", &sample_analysis(0.3));
+    let json = serde_json::to_string(&cache).unwrap();
+    let loaded: CullCache = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.version, CULL_CACHE_VERSION);
+    assert_eq!(loaded.scan("k"), Some((7, 150.0)));
+    assert_same(&loaded.analysis("k").unwrap(), &sample_analysis(0.3));
+}
+
+#[test]
+fn non_finite_analysis_is_not_recorded() {
+    let mut cache = CullCache::default();
+    cache.record_scan("k", 7, 150.0);
+    cache.record_analysis("k", &sample_analysis(f32::INFINITY));
+    assert!(cache.analysis("k").is_none());
+    assert_eq!(cache.scan("k"), Some((7, 150.0)));
+}
+
+/// 推理口径变化只丢推理输出；pHash / 清晰度与模型无关，照常命中。
+#[test]
+fn changed_fingerprint_drops_inference_but_keeps_scan_features() {
+    let mut cache = CullCache::default();
+    assert_eq!(cache.align_inference("models-a"), 0);
+    cache.record_scan("k", 7, 150.0);
+    cache.record_analysis("k", &sample_analysis(0.3));
+    assert_eq!(cache.align_inference("models-a"), 0);
+    assert!(cache.analysis("k").is_some());
+
+    assert_eq!(cache.align_inference("models-b"), 1);
+    assert!(cache.analysis("k").is_none());
+    assert_eq!(cache.scan("k"), Some((7, 150.0)));
+}
+
+/// 手改过的缓存里 embedding 维数不符：视为未命中而非 panic。
+#[test]
+fn malformed_embedding_is_a_miss() {
+    let json = serde_json::json!({
+        "version": 1,
+        "inference": "",
+        "entries": {
+            "k": {
+                "phash": 7,
+                "sharpness": 150.0,
+                "analysis": {
+                    "faces": [{
+                        "bbox": [0.0, 0.0, 1.0, 1.0],
+                        "score": 0.9,
+                        "landmarks_5pt": [[0.0, 0.0], [0.0, 0.0], [0.0, 0.0], [0.0, 0.0], [0.0, 0.0]],
+                        "embedding": [1.0, 0.0, 0.0],
+                        "mesh": { "ear": null, "smile": null },
+                        "eye_state": [0.0, 0.0],
+                    }],
+                    "quality": serde_json::to_value(ImageQuality::default()).unwrap(),
+                },
+            },
+        },
+    });
+    let cache: CullCache = serde_json::from_value(json).unwrap();
+    assert!(cache.analysis("k").is_none());
+    assert_eq!(cache.scan("k"), Some((7, 150.0)));
+}
+
+#[test]
+fn content_key_is_sha512_hex() {
+    let key = content_key(b"photo");
+    assert_eq!(key.len(), 128);
+    assert_ne!(key, content_key(b"other"));
+}
+
+/// 权重与判定阈值只作用于评分，不进指纹；模型路径与检测参数进指纹。
+#[test]
+fn fingerprint_tracks_models_not_weights() {
+    let base = FaceConfig::default();
+    let reweighted = FaceConfig {
+        w_blink: 9.0,
+        ear_blink_max: 0.3,
+        phash_hamming_max: 20,
+        ..FaceConfig::default()
+    };
+    assert_eq!(
+        inference_fingerprint(&base),
+        inference_fingerprint(&reweighted)
+    );
+    let remodeled = FaceConfig {
+        facenet_model_path: "models/other.onnx".into(),
+        ..FaceConfig::default()
+    };
+    assert_ne!(
+        inference_fingerprint(&base),
+        inference_fingerprint(&remodeled)
+    );
+    let rethresholded = FaceConfig {
+        scrfd_score_threshold: 0.3,
+        ..FaceConfig::default()
+    };
+    assert_ne!(
+        inference_fingerprint(&base),
+        inference_fingerprint(&rethresholded)
+    );
+}
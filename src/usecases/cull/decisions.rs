@@ -0,0 +1,390 @@
+//! 人工复核决定：review 页导出的 `cull-decisions.json` 与 `cull --apply-decisions` 落实。
+//!
+//! 每组记录全部帧（源路径 + cull 时的 group 目录落点）与复核后的选择：
+//! - `best`：留在源处并复制 `BEST_<basename>` 的帧（默认 = cull 选出的 best，「swap best」改之）
+//! - `keep_all`：整组全留，劣质副本全部搬回源处、删 `BEST_` 副本
+//!
+//! 落实按文件现状对账而非重放：保留帧若不在源处就从 group 目录搬回，淘汰帧若仍在源处
+//! 就搬进 group 目录，`BEST_` 副本按最终 best 重建。因此同一份决定对 dry-run 报告
+//! （尚未搬迁）与真跑报告都适用，重复执行也不会二次搬迁。
+//!
+//! `MANIFEST.json` 随之重写为最终现场（best 与淘汰帧清单），`cull --restore` 据此能把
+//! 换下的原 best 一并搬回；cull 时的评分、身份等其余字段原样保留。
+
+use std::io;
+use std::sync::Arc;
+
+use camino::Utf8PathBuf;
+use serde_derive::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tracing::debug;
+
+use super::group_writer::{
+    BEST_PREFIX, MANIFEST_NAME, copy_file_cross_scheme, move_file, unique_name_in_dir,
+};
+use super::report::CullReport;
+use super::util::{FEATURE, record_failure};
+use crate::entities::backend::Backend;
+use crate::entities::backend::factory::BackendFactory;
+use crate::entities::common;
+use crate::entities::file_info::full_hash_stream;
+use crate::entities::uri::Location;
+
+/// 决定文件格式版本；结构不兼容变更时递增。
+pub const DECISIONS_VERSION: u32 = 1;
+
+/// 一次 cull 运行的复核决定。
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CullDecisions {
+    pub version: u32,
+    /// 来源报告是否为 dry-run（仅供参考；落实按文件现状对账）。
+    pub dry_run: bool,
+    pub groups: Vec<GroupDecision>,
+}
+
+/// 单组决定。
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupDecision {
+    pub group_id: usize,
+    /// cull 时 `BEST_` 副本路径；其父目录即 group 目录。
+    pub best_dest: String,
+    /// 组内全部帧，首项为 cull 选出的 best。
+    pub frames: Vec<DecisionFrame>,
+    /// 复核后的 best 源路径，必须是 `frames` 之一。
+    pub best: String,
+    pub keep_all: bool,
+}
+
+/// 组内单帧。
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecisionFrame {
+    pub source_path: String,
+    /// cull 时搬入 group 目录的路径；原 best 不搬为 None。
+    pub dest_path: Option<String>,
+}
+
+impl CullDecisions {
+    /// 以 cull 报告的选择为初值：每组 best 不变、不整组保留。
+    #[must_use]
+    pub fn from_report(report: &CullReport) -> Self {
+        let groups = report
+            .groups
+            .iter()
+            .map(|g| {
+                let mut frames = Vec::with_capacity(g.culled.len() + 1);
+                frames.push(DecisionFrame {
+                    source_path: g.best_source.clone(),
+                    dest_path: None,
+                });
+                frames.extend(g.culled.iter().map(|c| DecisionFrame {
+                    source_path: c.source_path.clone(),
+                    dest_path: Some(c.dest_path.clone()),
+                }));
+                GroupDecision {
+                    group_id: g.group_id,
+                    best_dest: g.best_dest.clone(),
+                    frames,
+                    best: g.best_source.clone(),
+                    keep_all: false,
+                }
+            })
+            .collect();
+        Self {
+            version: DECISIONS_VERSION,
+            dry_run: report.dry_run,
+            groups,
+        }
+    }
+}
+
+/// 按 `decisions` 重新落实各组。单组失败计入 `report.failed` 后继续下一组。
+///
+/// 返回的报告复用 [`CullReport`]：`grouped` = 决定组数，`best_count` = 未整组保留的组数，
+/// `culled_count` = 最终淘汰帧数，`moved` = 本次实际搬迁数（`dry_run` 时 = 0）；
+/// `scanned` / `groups` 不适用，留空。
+///
+/// # Errors
+///
+/// `version` 不是 [`DECISIONS_VERSION`] 时返回 `InvalidInput`。
+pub fn apply_decisions(
+    factory: &dyn BackendFactory,
+    decisions: &CullDecisions,
+    dry_run: bool,
+) -> common::Result<CullReport> {
+    if decisions.version != DECISIONS_VERSION {
+        return Err(common::Error::Io(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "cull decisions version {} is not supported (expected {DECISIONS_VERSION})",
+                decisions.version
+            ),
+        )));
+    }
+    let mut report = CullReport {
+        dry_run,
+        ..CullReport::default()
+    };
+    for group in &decisions.groups {
+        report.grouped += 1;
+        match apply_group(factory, group, dry_run, &mut report.moved) {
+            Ok(culled) => {
+                if !group.keep_all {
+                    report.best_count += 1;
+                }
+                report.culled_count += culled;
+                log_apply_group(group, "ok");
+            }
+            Err(e) => {
+                log_apply_group(group, "error");
+                record_failure(&mut report, group.best_dest.clone(), &e);
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// 单组对账；返回最终淘汰帧数。
+fn apply_group(
+    factory: &dyn BackendFactory,
+    group: &GroupDecision,
+    dry_run: bool,
+    moved: &mut usize,
+) -> io::Result<usize> {
+    if !group.frames.iter().any(|f| f.source_path == group.best) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "cull decisions: best {} is not a frame of group {}",
+                group.best, group.group_id
+            ),
+        ));
+    }
+    let best_dest = parse(&group.best_dest)?;
+    let group_dir = best_dest.with_path(
+        best_dest
+            .path()
+            .parent()
+            .map_or_else(Utf8PathBuf::new, Utf8PathBuf::from),
+    );
+    let out_backend = backend_for(factory, &group_dir)?;
+
+    // 最终淘汰帧：(源路径, group 目录内落点)，供重写 manifest。
+    let mut culled: Vec<(&str, Location)> = Vec::new();
+    for frame in &group.frames {
+        let source = parse(&frame.source_path)?;
+        let src_backend = backend_for(factory, &source)?;
+        let keep = group.keep_all || frame.source_path == group.best;
+        let at_source = src_backend.exists(&source)?;
+        // 原 best 无 dest 记录，按此前换下时的默认落点 `group_dir/<basename>` 找。
+        let recorded_dest = || match frame.dest_path.as_deref() {
+            Some(d) => parse(d),
+            None => Ok(group_dir.join_path(file_name(&source)?)),
+        };
+        if keep && !at_source {
+            // 保留帧已被搬走：从 group 目录搬回原处。
+            let dest = recorded_dest()?;
+            if !out_backend.exists(&dest)? {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!(
+                        "cull decisions: cannot find {} to restore",
+                        source.display()
+                    ),
+                ));
+            }
+            if !dry_run {
+                move_file(&out_backend, &dest, &src_backend, &source)?;
+                *moved += 1;
+            }
+        } else if !keep && at_source {
+            // 淘汰帧仍在源处（原 best 被换下，或来源为 dry-run 报告）：搬进 group 目录。
+            if !dry_run {
+                out_backend.mkdir_p(&group_dir)?;
+            }
+            let name = file_name(&source)?;
+            let dest = unique_name_in_dir(&group_dir, name, &out_backend, dry_run)?;
+            if !dry_run {
+                move_file(&src_backend, &source, &out_backend, &dest)?;
+                *moved += 1;
+            }
+            culled.push((frame.source_path.as_str(), dest));
+        } else if !keep {
+            culled.push((frame.source_path.as_str(), recorded_dest()?));
+        }
+    }
+
+    if !dry_run {
+        let best_copy = refresh_best_copy(factory, group, &group_dir, &best_dest, &out_backend)?;
+        rewrite_manifest(
+            &out_backend,
+            &group_dir,
+            group,
+            best_copy.as_ref().unwrap_or(&best_dest),
+            &culled,
+        )?;
+    }
+    Ok(culled.len())
+}
+
+/// `BEST_` 副本对齐最终选择：best 未换沿用 cull 时的副本路径，换了则为
+/// `BEST_<新 best basename>`；其余帧的 `BEST_` 副本（含此前换过的）一律删除，整组保留
+/// 时全删。目标副本已在则不重复复制，保重复执行幂等。返回最终副本路径（整组保留为 None）。
+fn refresh_best_copy(
+    factory: &dyn BackendFactory,
+    group: &GroupDecision,
+    group_dir: &Location,
+    best_dest: &Location,
+    out_backend: &Arc<dyn Backend>,
+) -> io::Result<Option<Location>> {
+    let best = parse(&group.best)?;
+    let unchanged = group
+        .frames
+        .first()
+        .is_some_and(|f| f.source_path == group.best);
+    let wanted = match (group.keep_all, unchanged) {
+        (true, _) => None,
+        (false, true) => Some(best_dest.clone()),
+        (false, false) => Some(best_copy_in(group_dir, &best)?),
+    };
+    let mut stale = vec![best_dest.clone()];
+    for frame in &group.frames {
+        stale.push(best_copy_in(group_dir, &parse(&frame.source_path)?)?);
+    }
+    for loc in &stale {
+        if Some(loc) != wanted.as_ref() && out_backend.exists(loc)? {
+            out_backend.remove_file(loc)?;
+        }
+    }
+    let Some(wanted) = wanted else {
+        return Ok(None);
+    };
+    if !out_backend.exists(&wanted)? {
+        out_backend.mkdir_p(group_dir)?;
+        let src_backend = backend_for(factory, &best)?;
+        copy_file_cross_scheme(&src_backend, &best, out_backend, &wanted)?;
+    }
+    Ok(Some(wanted))
+}
+
+/// 按落实后的现场重写 group 目录的 `MANIFEST.json`：`best` 改为最终 best 与其副本路径，
+/// `culled` 改为最终淘汰帧。沿用的淘汰帧保留 cull 时的记录（评分、内容哈希），新换下的帧
+/// 现算整文件 xxh3-64；其余字段原样。来源为 dry-run 报告时 manifest 尚不存在，只写这两项。
+/// 整组保留时没有可撤销的东西，删掉 manifest。
+fn rewrite_manifest(
+    out_backend: &Arc<dyn Backend>,
+    group_dir: &Location,
+    group: &GroupDecision,
+    best_copy: &Location,
+    culled: &[(&str, Location)],
+) -> io::Result<()> {
+    let manifest_loc = group_dir.join_path(MANIFEST_NAME);
+    if group.keep_all {
+        if out_backend.exists(&manifest_loc)? {
+            out_backend.remove_file(&manifest_loc)?;
+        }
+        return Ok(());
+    }
+    let mut manifest = if out_backend.exists(&manifest_loc)? {
+        let text = out_backend.read_to_string(&manifest_loc)?;
+        match serde_json::from_str(&text) {
+            Ok(Value::Object(m)) => m,
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "cull decisions: {} is not a cull manifest",
+                        manifest_loc.display()
+                    ),
+                ));
+            }
+        }
+    } else {
+        let mut m = Map::new();
+        m.insert("group_id".into(), group.group_id.into());
+        m
+    };
+
+    let previous = manifest
+        .get("culled")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    let mut entries = Vec::with_capacity(culled.len());
+    for (source, dest) in culled {
+        let dest_path = dest.display();
+        let recorded = previous.iter().find(|e| {
+            e["source_path"].as_str() == Some(*source)
+                && e["dest_path"].as_str() == Some(dest_path.as_str())
+        });
+        let entry = match recorded {
+            Some(e) => e.clone(),
+            None => {
+                let (_, hash) = full_hash_stream(out_backend.open_read(dest)?.as_mut())?;
+                let mut e = Map::new();
+                e.insert("source_path".into(), (*source).into());
+                e.insert("dest_path".into(), dest_path.into());
+                e.insert("content_hash".into(), format!("{hash:016x}").into());
+                Value::Object(e)
+            }
+        };
+        entries.push(entry);
+    }
+
+    // best 未换时保留 cull 时的分数；换了则旧分数不再对应，只记路径。
+    let mut best = Map::new();
+    if let Some(Value::Object(old)) = manifest.get("best")
+        && old.get("src").and_then(Value::as_str) == Some(group.best.as_str())
+    {
+        best.clone_from(old);
+    }
+    best.insert("src".into(), group.best.clone().into());
+    best.insert("dst".into(), best_copy.display().into());
+    manifest.insert("best".into(), Value::Object(best));
+    manifest.insert("culled".into(), Value::Array(entries));
+
+    let json = serde_json::to_vec_pretty(&Value::Object(manifest)).map_err(io::Error::other)?;
+    out_backend.mkdir_p(group_dir)?;
+    let mut writer = out_backend.open_write(&manifest_loc, false)?;
+    writer.write_all(&json)?;
+    writer.finish()
+}
+
+fn best_copy_in(group_dir: &Location, source: &Location) -> io::Result<Location> {
+    Ok(group_dir.join_path(&format!("{BEST_PREFIX}{}", file_name(source)?)))
+}
+
+fn parse(s: &str) -> io::Result<Location> {
+    Location::parse(s).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("cull decisions: invalid location {s:?}: {e}"),
+        )
+    })
+}
+
+fn backend_for(factory: &dyn BackendFactory, loc: &Location) -> io::Result<Arc<dyn Backend>> {
+    factory.for_location(loc).map_err(|common::Error::Io(e)| e)
+}
+
+fn file_name(loc: &Location) -> io::Result<&str> {
+    loc.path()
+        .file_name()
+        .ok_or_else(|| io::Error::other(format!("{} has no file name", loc.display())))
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+fn log_apply_group(group: &GroupDecision, result: &'static str) {
+    debug!(
+        feature = FEATURE,
+        operation = "apply_decisions",
+        result,
+        group_id = group.group_id,
+        keep_all = group.keep_all,
+        best = %group.best,
+        "cull decision applied"
+    );
+}
+
+#[cfg(test)]
+#[path = "decisions_tests.rs"]
+mod tests;
@@ -0,0 +1,246 @@
+//! `decisions` 单测：真实 local 文件布局上验 swap / keep-all / dry-run / 幂等与校验失败。
+
+use std::fs;
+use std::path::Path;
+
+use tempfile::{TempDir, tempdir};
+
+use super::*;
+use crate::adapters::backend::factory::DefaultBackendFactory;
+use crate::usecases::cull::report::{CulledEntry, GroupReport, PickStrategy, ScoreBreakdown};
+
+/// 模拟一次真跑后的现场：`src/a.jpg` 为 best 留源，`b.jpg` 已搬进 `out/group-001/`，
+/// 旁边是 `BEST_a.jpg` 副本。返回 (tempdir, 初始决定)。
+fn after_real_run() -> (TempDir, CullDecisions) {
+    let dir = tempdir().unwrap();
+    let src = dir.path().join("src");
+    let group = dir.path().join("out").join("group-001");
+    fs::create_dir_all(&src).unwrap();
+    fs::create_dir_all(&group).unwrap();
+    fs::write(src.join("a.jpg"), b"frame-a").unwrap();
+    fs::write(group.join("BEST_a.jpg"), b"frame-a").unwrap();
+    fs::write(group.join("b.jpg"), b"frame-b").unwrap();
+    let decisions = CullDecisions {
+        version: DECISIONS_VERSION,
+        dry_run: false,
+        groups: vec![GroupDecision {
+            group_id: 1,
+            best_dest: path_str(&group.join("BEST_a.jpg")),
+            frames: vec![
+                DecisionFrame {
+                    source_path: path_str(&src.join("a.jpg")),
+                    dest_path: None,
+                },
+                DecisionFrame {
+                    source_path: path_str(&src.join("b.jpg")),
+                    dest_path: Some(path_str(&group.join("b.jpg"))),
+                },
+            ],
+            best: path_str(&src.join("a.jpg")),
+            keep_all: false,
+        }],
+    };
+    (dir, decisions)
+}
+
+fn path_str(p: &Path) -> String {
+    p.to_str().unwrap().to_string()
+}
+
+fn names_in(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .unwrap()
+        .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn from_report_lists_best_first_and_keeps_cull_choice() {
+    let report = CullReport {
+        dry_run: true,
+        groups: vec![GroupReport {
+            group_id: 3,
+            best_source: "/src/a.jpg".to_string(),
+            best_dest: "/out/group-003/BEST_a.jpg".to_string(),
+            culled: vec![CulledEntry {
+                source_path: "/src/b.jpg".to_string(),
+                dest_path: "/out/group-003/b.jpg".to_string(),
+                score: 1.0,
+                score_breakdown: ScoreBreakdown::default(),
+                faces: Vec::new(),
+                content_hash: String::new(),
+            }],
+            score_breakdown: ScoreBreakdown::default(),
+            best_faces: Vec::new(),
+            strategy: PickStrategy::Total,
+            reasons: Vec::new(),
+            identities: Vec::new(),
+        }],
+        ..CullReport::default()
+    };
+    let d = CullDecisions::from_report(&report);
+    assert_eq!(d.version, DECISIONS_VERSION);
+    assert!(d.dry_run);
+    let g = &d.groups[0];
+    assert_eq!(g.group_id, 3);
+    assert_eq!(g.best, "/src/a.jpg");
+    assert!(!g.keep_all);
+    assert_eq!(g.frames[0].dest_path, None);
+    assert_eq!(
+        g.frames[1].dest_path.as_deref(),
+        Some("/out/group-003/b.jpg")
+    );
+}
+
+#[test]
+fn decisions_roundtrip_through_json() {
+    let (_dir, d) = after_real_run();
+    let json = serde_json::to_string(&d).unwrap();
+    let back: CullDecisions = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, d);
+}
+
+#[test]
+fn unchanged_decisions_leave_layout_untouched() {
+    let (dir, d) = after_real_run();
+    let report = apply_decisions(&DefaultBackendFactory, &d, false).unwrap();
+    assert_eq!(report.failed, 0, "errors: {:?}", report.errors);
+    assert_eq!(report.moved, 0);
+    assert_eq!(
+        (report.grouped, report.best_count, report.culled_count),
+        (1, 1, 1)
+    );
+    let group = dir.path().join("out").join("group-001");
+    assert_eq!(names_in(&group), ["BEST_a.jpg", "MANIFEST.json", "b.jpg"]);
+}
+
+#[test]
+fn swap_best_restores_new_best_and_culls_old_one() {
+    let (dir, mut d) = after_real_run();
+    let src = dir.path().join("src");
+    d.groups[0].best = path_str(&src.join("b.jpg"));
+    let report = apply_decisions(&DefaultBackendFactory, &d, false).unwrap();
+    assert_eq!(report.failed, 0, "errors: {:?}", report.errors);
+    assert_eq!(report.moved, 2);
+    assert_eq!(names_in(&src), ["b.jpg"]);
+    let group = dir.path().join("out").join("group-001");
+    assert_eq!(names_in(&group), ["BEST_b.jpg", "MANIFEST.json", "a.jpg"]);
+    assert_eq!(fs::read(group.join("BEST_b.jpg")).unwrap(), b"frame-b");
+    let manifest: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(group.join("MANIFEST.json")).unwrap()).unwrap();
+    assert_eq!(manifest["best"]["src"], path_str(&src.join("b.jpg")));
+    assert_eq!(manifest["best"]["dst"], path_str(&group.join("BEST_b.jpg")));
+    let culled = manifest["culled"].as_array().unwrap();
+    assert_eq!(culled.len(), 1);
+    assert_eq!(culled[0]["source_path"], path_str(&src.join("a.jpg")));
+    assert_eq!(culled[0]["dest_path"], path_str(&group.join("a.jpg")));
+    assert_eq!(
+        culled[0]["content_hash"],
+        format!("{:016x}", xxhash_rust::xxh3::xxh3_64(b"frame-a"))
+    );
+
+    // 再执行一次：现场已对齐，不再搬迁。
+    let again = apply_decisions(&DefaultBackendFactory, &d, false).unwrap();
+    assert_eq!(again.failed, 0, "errors: {:?}", again.errors);
+    assert_eq!(again.moved, 0);
+    assert_eq!(names_in(&src), ["b.jpg"]);
+}
+
+#[test]
+fn keep_all_moves_every_frame_back_and_drops_best_copy() {
+    let (dir, mut d) = after_real_run();
+    d.groups[0].keep_all = true;
+    let report = apply_decisions(&DefaultBackendFactory, &d, false).unwrap();
+    assert_eq!(report.failed, 0, "errors: {:?}", report.errors);
+    assert_eq!(
+        (report.best_count, report.culled_count, report.moved),
+        (0, 0, 1)
+    );
+    assert_eq!(names_in(&dir.path().join("src")), ["a.jpg", "b.jpg"]);
+    // 没有可撤销的帧：manifest 一并删除。
+    assert!(names_in(&dir.path().join("out").join("group-001")).is_empty());
+}
+
+#[test]
+fn dry_run_counts_without_touching_files() {
+    let (dir, mut d) = after_real_run();
+    d.groups[0].best = path_str(&dir.path().join("src").join("b.jpg"));
+    let report = apply_decisions(&DefaultBackendFactory, &d, true).unwrap();
+    assert!(report.dry_run);
+    assert_eq!(report.failed, 0, "errors: {:?}", report.errors);
+    assert_eq!(report.moved, 0);
+    assert_eq!(names_in(&dir.path().join("src")), ["a.jpg"]);
+    assert_eq!(
+        names_in(&dir.path().join("out").join("group-001")),
+        ["BEST_a.jpg", "b.jpg"]
+    );
+}
+
+/// 来源为 dry-run 报告：全部帧仍在源处、group 目录不存在，落实时才真正搬迁。
+#[test]
+fn decisions_from_dry_run_report_perform_the_cull() {
+    let (dir, d) = after_real_run();
+    let src = dir.path().join("src");
+    let group = dir.path().join("out").join("group-001");
+    fs::rename(group.join("b.jpg"), src.join("b.jpg")).unwrap();
+    fs::remove_dir_all(&group).unwrap();
+    let report = apply_decisions(&DefaultBackendFactory, &d, false).unwrap();
+    assert_eq!(report.failed, 0, "errors: {:?}", report.errors);
+    assert_eq!(report.moved, 1);
+    assert_eq!(names_in(&src), ["a.jpg"]);
+    assert_eq!(names_in(&group), ["BEST_a.jpg", "MANIFEST.json", "b.jpg"]);
+}
+
+#[test]
+fn best_outside_frames_fails_that_group() {
+    let (_dir, mut d) = after_real_run();
+    d.groups[0].best = "/elsewhere/c.jpg".to_string();
+    let report = apply_decisions(&DefaultBackendFactory, &d, false).unwrap();
+    assert_eq!(report.failed, 1);
+    assert!(
+        report.errors[0]
+            .message
+            .contains("is not a frame of group 1"),
+        "got: {:?}",
+        report.errors
+    );
+}
+
+#[test]
+fn missing_kept_frame_fails_that_group() {
+    let (dir, mut d) = after_real_run();
+    d.groups[0].keep_all = true;
+    fs::remove_file(dir.path().join("out").join("group-001").join("b.jpg")).unwrap();
+    let report = apply_decisions(&DefaultBackendFactory, &d, false).unwrap();
+    assert_eq!(report.failed, 1);
+    assert!(
+        report.errors[0].message.contains("cannot find"),
+        "got: {:?}",
+        report.errors
+    );
+}
+
+#[test]
+fn unsupported_version_is_rejected() {
+    let (_dir, mut d) = after_real_run();
+    d.version = DECISIONS_VERSION + 1;
+    let err = apply_decisions(&DefaultBackendFactory, &d, false).unwrap_err();
+    assert!(err.to_string().contains("not supported"), "got: {err}");
+}
+
+/// 先换成 b 再换回 a：a 已在 group 目录（无 dest 记录），按默认落点找回。
+#[test]
+fn swapping_back_restores_original_best() {
+    let (dir, mut d) = after_real_run();
+    let src = dir.path().join("src");
+    d.groups[0].best = path_str(&src.join("b.jpg"));
+    apply_decisions(&DefaultBackendFactory, &d, false).unwrap();
+    d.groups[0].best = path_str(&src.join("a.jpg"));
+    let report = apply_decisions(&DefaultBackendFactory, &d, false).unwrap();
+    assert_eq!(report.failed, 0, "errors: {:?}", report.errors);
+    assert_eq!(names_in(&src), ["a.jpg"]);
+    let group = dir.path().join("out").join("group-001");
+    assert_eq!(names_in(&group), ["BEST_a.jpg", "MANIFEST.json", "b.jpg"]);
+}
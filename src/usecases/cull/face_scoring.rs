@@ -11,6 +11,11 @@
 //! `EAR` 6 点索引依 `MediaPipe` 标准（`face_landmarker.task` 468 点 mesh）：
 //! - 左眼：33（外角）/ 160 / 158 / 133（内角）/ 153 / 144
 //! - 右眼：362（外角）/ 385 / 387 / 263（内角）/ 373 / 380
+//!
+//! 468 点 mesh 在 `run::analyze_image` 内即折成 [`MeshFeatures`]（`EAR` + 微笑度）：
+//! 评分只用这两个标量，分析缓存也只存它们而非整张 mesh。
+
+use serde_derive::{Deserialize, Serialize};
 
 use crate::usecases::config::FaceConfig;
 use crate::usecases::face::FaceDetection;
@@ -30,6 +35,24 @@ const MOUTH_RIGHT: usize = 291;
 const LIP_UPPER: usize = 13;
 const LIP_LOWER: usize = 14;
 
+/// 单张脸 mesh 派生的评分特征；mesh 退化（不足 468 点 / 眼宽或嘴宽为 0）的项为 `None`。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct MeshFeatures {
+    /// 左右眼 `EAR` 平均。
+    pub ear: Option<f32>,
+    /// 嘴角上扬幅度（按嘴宽归一化，非负）。
+    pub smile: Option<f32>,
+}
+
+impl MeshFeatures {
+    pub(crate) fn from_mesh(mesh: &[[f32; 3]]) -> Self {
+        Self {
+            ear: ear_from_mesh(mesh),
+            smile: smile_from_mesh(mesh),
+        }
+    }
+}
+
 /// 综合 4 模型输出与全图清晰度算 `ScoreBreakdown`。
 ///
 /// `meshes` / `eye_states` 长度应等于 `faces.len()`；不足部分按"无 mesh / 无
@@ -38,7 +61,7 @@ const LIP_LOWER: usize = 14;
 pub(crate) fn score_image(
     sharpness: f32,
     faces: &[FaceDetection],
-    meshes: &[Vec<[f32; 3]>],
+    meshes: &[MeshFeatures],
     eye_states: &[(f32, f32)],
     cfg: &FaceConfig,
 ) -> ScoreBreakdown {
@@ -47,17 +70,11 @@ pub(crate) fn score_image(
     let mut smile_sum: f32 = 0.0;
     let mut smile_face_count: f32 = 0.0;
     for i in 0..face_count {
-        let ear_closed = meshes
-            .get(i)
-            .and_then(|m| ear_from_mesh(m))
-            .is_some_and(|ear| ear < cfg.ear_blink_max);
-        let eye_closed = eye_states
-            .get(i)
-            .is_some_and(|(left, right)| left.max(*right) > cfg.eye_blink_score_max);
-        if ear_closed || eye_closed {
+        let mesh = meshes.get(i).copied().unwrap_or_default();
+        if face_closed(mesh, eye_states.get(i).copied(), cfg) {
             blink_faces += 1.0;
         }
-        if let Some(smile) = meshes.get(i).and_then(|m| smile_from_mesh(m)) {
+        if let Some(smile) = mesh.smile {
             smile_sum += smile;
             smile_face_count += 1.0;
         }
@@ -77,9 +94,43 @@ pub(crate) fn score_image(
         blink_penalty,
         smile_bonus,
         total,
+        ..ScoreBreakdown::default()
     }
 }
 
+/// 单张脸的评分：是否睁眼 + 个人分（全图清晰度项 + 该脸微笑加分 - 该脸闭眼惩罚）。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct FaceScore {
+    pub eyes_open: bool,
+    pub score: f32,
+}
+
+/// 按 `score_image` 同一套双印证与权重给单张脸打分；`identity_pick` 据此按人比较各帧。
+pub(crate) fn score_face(
+    sharpness: f32,
+    mesh: MeshFeatures,
+    eye_state: Option<(f32, f32)>,
+    cfg: &FaceConfig,
+) -> FaceScore {
+    let closed = face_closed(mesh, eye_state, cfg);
+    let smile = mesh.smile.unwrap_or(0.0);
+    let blink_penalty = if closed { cfg.w_blink } else { 0.0 };
+    FaceScore {
+        eyes_open: !closed,
+        score: cfg
+            .w_sharpness
+            .mul_add(sharpness, cfg.w_smile.mul_add(smile, -blink_penalty)),
+    }
+}
+
+/// `EAR` 与 `EyeState` 任一命中即判闭眼；两者都缺失视为睁眼。
+fn face_closed(mesh: MeshFeatures, eye_state: Option<(f32, f32)>, cfg: &FaceConfig) -> bool {
+    let ear_closed = mesh.ear.is_some_and(|ear| ear < cfg.ear_blink_max);
+    let eye_closed =
+        eye_state.is_some_and(|(left, right)| left.max(right) > cfg.eye_blink_score_max);
+    ear_closed || eye_closed
+}
+
 /// 左右眼 `EAR` 平均。mesh 不足 468 点 / 索引退化（眼宽=0）返 `None` 让调用方跳过印证。
 fn ear_from_mesh(mesh: &[[f32; 3]]) -> Option<f32> {
     if mesh.len() < MESH_POINT_COUNT {
@@ -35,6 +35,10 @@ fn make_mesh(eye_ratio: f32, smile_curl: f32) -> Vec<[f32; 3]> {
     mesh
 }
 
+fn features(mesh: &[[f32; 3]]) -> MeshFeatures {
+    MeshFeatures::from_mesh(mesh)
+}
+
 fn set_eye(mesh: &mut [[f32; 3]], idx: [usize; 6], half_v: f32) {
     mesh[idx[0]] = [0.0, 5.0, 0.0]; // 外角
     mesh[idx[3]] = [10.0, 5.0, 0.0]; // 内角
@@ -62,7 +66,7 @@ fn score_image_open_eyes_no_smile_no_penalty() {
     let s = score_image(
         100.0,
         &[sample_detection()],
-        &[mesh],
+        &[features(&mesh)],
         &[(0.1_f32, 0.1)],
         &cfg,
     );
@@ -74,7 +78,13 @@ fn score_image_ear_below_threshold_counts_blink() {
     // ratio=0.2 → EAR=0.1 < 0.21 → EAR 命中闭眼
     let cfg = default_cfg();
     let mesh = make_mesh(0.2, 0.0);
-    let s = score_image(0.0, &[sample_detection()], &[mesh], &[(0.1_f32, 0.1)], &cfg);
+    let s = score_image(
+        0.0,
+        &[sample_detection()],
+        &[features(&mesh)],
+        &[(0.1_f32, 0.1)],
+        &cfg,
+    );
     assert!((s.blink_penalty - cfg.w_blink).abs() < 1e-5, "got: {s:?}");
 }
 
@@ -83,7 +93,13 @@ fn score_image_eyestate_above_threshold_counts_blink() {
     // EAR ratio=0.5 → 不命中；EyeState 0.9 > 0.5 → 命中
     let cfg = default_cfg();
     let mesh = make_mesh(0.5, 0.0);
-    let s = score_image(0.0, &[sample_detection()], &[mesh], &[(0.9_f32, 0.1)], &cfg);
+    let s = score_image(
+        0.0,
+        &[sample_detection()],
+        &[features(&mesh)],
+        &[(0.9_f32, 0.1)],
+        &cfg,
+    );
     assert!((s.blink_penalty - cfg.w_blink).abs() < 1e-5);
 }
 
@@ -92,7 +108,13 @@ fn score_image_double_match_counts_blink_once() {
     // EAR 命中 + EyeState 命中 → 仍 1 次惩罚（不双计）
     let cfg = default_cfg();
     let mesh = make_mesh(0.2, 0.0);
-    let s = score_image(0.0, &[sample_detection()], &[mesh], &[(0.9_f32, 0.9)], &cfg);
+    let s = score_image(
+        0.0,
+        &[sample_detection()],
+        &[features(&mesh)],
+        &[(0.9_f32, 0.9)],
+        &cfg,
+    );
     assert!((s.blink_penalty - cfg.w_blink).abs() < 1e-5);
 }
 
@@ -103,7 +125,7 @@ fn score_image_mesh_under_size_skips_ear_judgment() {
     let s = score_image(
         0.0,
         &[sample_detection()],
-        &[vec![[0.0_f32; 3]; 10]],
+        &[features(&[[0.0_f32; 3]; 10])],
         &[(0.9_f32, 0.0)],
         &cfg,
     );
@@ -115,7 +137,13 @@ fn score_image_smile_curl_positive_yields_bonus() {
     // curl 负值 → 嘴角 y < center → 上扬 → smile_bonus > 0
     let cfg = default_cfg();
     let mesh = make_mesh(0.5, -2.0); // 嘴角 y=8 < center 10
-    let s = score_image(0.0, &[sample_detection()], &[mesh], &[(0.0_f32, 0.0)], &cfg);
+    let s = score_image(
+        0.0,
+        &[sample_detection()],
+        &[features(&mesh)],
+        &[(0.0_f32, 0.0)],
+        &cfg,
+    );
     // smile = -(-2)/10 = 0.2，bonus = w_smile * 0.2 = 0.5 * 0.2 = 0.1
     assert!((s.smile_bonus - 0.1).abs() < 1e-3, "got: {s:?}");
 }
@@ -125,7 +153,13 @@ fn score_image_smile_curl_negative_clamps_to_zero() {
     // curl > 0 → 嘴角下垂 → smile.max(0) = 0
     let cfg = default_cfg();
     let mesh = make_mesh(0.5, 2.0);
-    let s = score_image(0.0, &[sample_detection()], &[mesh], &[(0.0_f32, 0.0)], &cfg);
+    let s = score_image(
+        0.0,
+        &[sample_detection()],
+        &[features(&mesh)],
+        &[(0.0_f32, 0.0)],
+        &cfg,
+    );
     assert!(s.smile_bonus.abs() < 1e-5, "got: {s:?}");
 }
 
@@ -139,7 +173,7 @@ fn score_image_all_weights_zero_yields_total_zero() {
     let s = score_image(
         100.0,
         &[sample_detection()],
-        &[mesh],
+        &[features(&mesh)],
         &[(0.9_f32, 0.9)],
         &cfg,
     );
@@ -155,7 +189,7 @@ fn score_image_missing_eye_state_skips_eyestate_judgment() {
     let s = score_image(
         0.0,
         &[sample_detection(), sample_detection()],
-        &[m_open, m_closed],
+        &[features(&m_open), features(&m_closed)],
         &[(0.0_f32, 0.0)], // 仅 1 个，覆盖第一张脸
         &cfg,
     );
@@ -212,3 +246,31 @@ fn dist_2d_pythagoras() {
     let d = dist_2d([0.0, 0.0], [3.0, 4.0]);
     assert!((d - 5.0).abs() < 1e-5);
 }
+
+#[test]
+fn score_face_penalizes_closed_eyes_and_adds_smile() {
+    let cfg = default_cfg();
+    let open = score_face(
+        10.0,
+        features(&make_mesh(0.6, -2.0)),
+        Some((0.1, 0.1)),
+        &cfg,
+    );
+    assert!(open.eyes_open);
+    // 10 × 1.0 + 0.2 × 0.5 = 10.1
+    assert!((open.score - 10.1).abs() < 1e-4, "got: {open:?}");
+
+    let closed = score_face(10.0, features(&make_mesh(0.2, 0.0)), None, &cfg);
+    assert!(!closed.eyes_open);
+    assert!((closed.score - 8.0).abs() < 1e-4, "got: {closed:?}");
+
+    // mesh 与 EyeState 都缺失：无证据视为睁眼，只剩清晰度项。
+    let bare = score_face(10.0, MeshFeatures::default(), None, &cfg);
+    assert_eq!(
+        bare,
+        FaceScore {
+            eyes_open: true,
+            score: 10.0
+        }
+    );
+}
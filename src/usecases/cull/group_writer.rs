@@ -10,24 +10,39 @@ use std::sync::Arc;
 use camino::Utf8PathBuf;
 use tracing::warn;
 
-use super::report::GroupReport;
+use super::report::{
+    FaceMark, GroupReason, GroupReport, IdentityPick, PickStrategy, ScoreBreakdown,
+};
 use crate::entities::backend::Backend;
 use crate::entities::uri::Location;
 use crate::usecases::config::config;
 
 const FEATURE: &str = "cull";
-const BEST_PREFIX: &str = "BEST_";
-const MANIFEST_NAME: &str = "MANIFEST.json";
+pub(super) const BEST_PREFIX: &str = "BEST_";
+pub(super) const MANIFEST_NAME: &str = "MANIFEST.json";
 
 /// 单个相似组的搬迁计划：最佳源 + 全部劣质源（按评分降序）。
 pub(crate) struct GroupPlan<'a> {
     pub group_id: usize,
     pub best_source: &'a Location,
     pub best_source_backend: &'a Arc<dyn Backend>,
-    /// `(source_loc, source_backend, score)` 三元组。
-    pub culled: Vec<(&'a Location, &'a Arc<dyn Backend>, f32)>,
+    pub culled: Vec<CulledSource<'a>>,
     pub best_score: f32,
-    pub score_breakdown: super::report::ScoreBreakdown,
+    pub score_breakdown: ScoreBreakdown,
+    pub best_faces: Vec<FaceMark>,
+    pub strategy: PickStrategy,
+    pub identities: Vec<IdentityPick>,
+    pub reasons: Vec<GroupReason>,
+}
+
+/// 组内单张劣质源：位置 + 所在 backend + 评分明细 + 人脸标注 + 整文件 xxh3-64
+/// （原样进 `CulledEntry`）。
+pub(crate) struct CulledSource<'a> {
+    pub source: &'a Location,
+    pub backend: &'a Arc<dyn Backend>,
+    pub breakdown: ScoreBreakdown,
+    pub faces: Vec<FaceMark>,
+    pub content_hash: u64,
 }
 
 /// 把 `plan` 落盘：返填好的 `GroupReport`。`dry_run` 时只算路径不真搬。
@@ -73,20 +88,24 @@ pub(crate) fn write_group(
     }
 
     let mut culled_reports = Vec::with_capacity(plan.culled.len());
-    for (src_loc, src_backend, score) in &plan.culled {
-        let src_basename = src_loc
+    for culled in &plan.culled {
+        let src_basename = culled
+            .source
             .path()
             .file_name()
             .ok_or_else(|| io::Error::other("culled source has no file name"))?;
         let dst = unique_name_in_dir(&group_dir, src_basename, output_backend, dry_run)?;
         if !dry_run {
-            move_file(src_backend, src_loc, output_backend, &dst)?;
+            move_file(culled.backend, culled.source, output_backend, &dst)?;
             *moved_counter += 1;
         }
         culled_reports.push(super::report::CulledEntry {
-            source_path: src_loc.display(),
+            source_path: culled.source.display(),
             dest_path: dst.display(),
-            score: *score,
+            score: culled.breakdown.total,
+            score_breakdown: culled.breakdown,
+            faces: culled.faces.clone(),
+            content_hash: format!("{:016x}", culled.content_hash),
         });
     }
 
@@ -96,6 +115,10 @@ pub(crate) fn write_group(
         best_dest: best_dst.display(),
         culled: culled_reports,
         score_breakdown: plan.score_breakdown,
+        best_faces: plan.best_faces.clone(),
+        strategy: plan.strategy,
+        reasons: plan.reasons.clone(),
+        identities: plan.identities.clone(),
     };
     if !dry_run {
         write_manifest(&group_dir, output_backend, &report, plan.best_score);
@@ -126,7 +149,7 @@ fn compute_group_dir(
 
 /// basename 冲突走 `unique_name`：`a.jpg` 存在则 `a_1.jpg` / `a_2.jpg`。
 /// `dry_run` 时直接返原 basename 不检 `exists`（避免 backend 调用）。
-fn unique_name_in_dir(
+pub(super) fn unique_name_in_dir(
     dir: &Location,
     file_name: &str,
     backend: &Arc<dyn Backend>,
@@ -168,15 +191,15 @@ fn split_stem_ext(name: &str) -> (&str, &str) {
 /// scheme（`local.rs::copy_file_rejects_non_local_*_scheme` / `adb_ops_tests::copy_file_rejects_non_adb_scheme_on_either_side`
 /// 等测试印证）。跨 scheme（local↔smb/adb/mtp）必走 stream：`src.open_read` +
 /// `dst.open_write` + `io::copy` + `writer.finish`，保 caller 经 ? 上抛单点。
-/// 同 scheme 仍走 `src_backend.copy_file` 以利用 backend 原生 copy（同卷 rename / SMB
-/// `SRV_COPYCHUNK` 等）。
-fn copy_file_cross_scheme(
+/// 同端点（见 [`same_endpoint`]）仍走 `src_backend.copy_file` 以利用 backend 原生 copy
+///（同卷 rename / SMB `SRV_COPYCHUNK` 等）；同 scheme 不同主机 / 设备同样走 stream。
+pub(super) fn copy_file_cross_scheme(
     src_backend: &Arc<dyn Backend>,
     src: &Location,
     dst_backend: &Arc<dyn Backend>,
     dst: &Location,
 ) -> io::Result<u64> {
-    if src_backend.scheme() == dst_backend.scheme() {
+    if same_endpoint(src, dst) {
         return src_backend.copy_file(src, dst, false);
     }
     let mut reader = src_backend.open_read(src)?;
@@ -188,6 +211,13 @@ fn copy_file_cross_scheme(
     Ok(bytes)
 }
 
+/// 两端是否落在同一 backend 实例可达的位置：scheme 与连接参数（host/share、device、
+/// serial）都相同。同 scheme 不同主机（如两台 NAS）的 `copy_file` 会让源 backend
+/// 去写一个它连不上的目标，必须走流式拷贝。
+fn same_endpoint(a: &Location, b: &Location) -> bool {
+    a.with_path(Utf8PathBuf::new()) == b.with_path(Utf8PathBuf::new())
+}
+
 /// best-effort partial dst 清理失败时 warn：远端 SMB/ADB 会话断开让 remove 也 Err
 /// 时让用户能从日志发现残留累积；tracing macro micro-region 集中此处 coverage(off)。
 #[cfg_attr(coverage_nightly, coverage(off))]
@@ -202,7 +232,7 @@ fn log_remove_err(loc: &Location, e: &io::Error) {
     );
 }
 
-fn move_file(
+pub(super) fn move_file(
     src_backend: &Arc<dyn Backend>,
     src_loc: &Location,
     output_backend: &Arc<dyn Backend>,
@@ -237,7 +267,10 @@ fn write_manifest(
         group_id: usize,
         best: BestEntry<'a>,
         culled: &'a [super::report::CulledEntry],
-        score_breakdown: super::report::ScoreBreakdown,
+        score_breakdown: ScoreBreakdown,
+        strategy: PickStrategy,
+        reasons: &'a [GroupReason],
+        identities: &'a [IdentityPick],
     }
     #[derive(serde_derive::Serialize)]
     struct BestEntry<'a> {
@@ -255,6 +288,9 @@ fn write_manifest(
         },
         culled: &report.culled,
         score_breakdown: report.score_breakdown,
+        strategy: report.strategy,
+        reasons: &report.reasons,
+        identities: &report.identities,
     };
     // Manifest 含 ScoreBreakdown 4 个 f32 字段；serde_json 对 NaN/Inf 返 Err 不写 null
     // （上游 face_scoring 异常路径 + EyeState/FaceMesh 模型 NaN 输出经 unwrap_or 之外
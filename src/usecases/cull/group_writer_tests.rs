@@ -8,6 +8,23 @@ fn local_loc(path: &str) -> Location {
 Location::Local(camino::Utf8PathBuf::from(path))
 }
 
+fn culled_source<'a>(
+    source: &'a Location,
+    backend: &'a Arc<dyn Backend>,
+    total: f32,
+) -> CulledSource<'a> {
+    CulledSource {
+        source,
+        backend,
+        breakdown: ScoreBreakdown {
+            total,
+            ..ScoreBreakdown::default()
+        },
+        faces: Vec::new(),
+        content_hash: 0,
+    }
+}
+
 #[test]
 fn compute_group_dir_uses_relative_path() {
     let best = local_loc("/src/2024/05/IMG_001.jpg");
@@ -73,6 +90,10 @@ fn write_group_dry_run_does_not_create_files() {
         culled: vec![],
         best_score: 100.0,
         score_breakdown: super::super::report::ScoreBreakdown::default(),
+        best_faces: Vec::new(),
+        strategy: super::super::report::PickStrategy::default(),
+        identities: Vec::new(),
+        reasons: Vec::new(),
     };
     let mut moved = 0;
     let report =
@@ -122,11 +143,15 @@ fn write_group_non_dry_run_copies_best_and_moves_culled() {
         best_source: &best_loc,
         best_source_backend: &backend,
         culled: vec![
-            (&first_culled, &backend, 50.0),
-            (&second_culled, &backend, 30.0),
+            culled_source(&first_culled, &backend, 50.0),
+            culled_source(&second_culled, &backend, 30.0),
         ],
         best_score: 99.0,
         score_breakdown: super::super::report::ScoreBreakdown::default(),
+        best_faces: Vec::new(),
+        strategy: super::super::report::PickStrategy::default(),
+        identities: Vec::new(),
+        reasons: Vec::new(),
     };
     let mut moved = 0;
     let report = write_group(&plan, &root_loc, &out_loc, &backend, false, &mut moved).unwrap();
@@ -139,6 +164,10 @@ fn write_group_non_dry_run_copies_best_and_moves_culled() {
     assert!(group_dir.join("MANIFEST.json").exists());
     // 源文件 culled 被 rename 走 → 原路径不存在
     assert!(!culled_a.exists());
+    // content_hash 以 16 位 hex 进 report 与 MANIFEST，供 --restore 校验
+    assert_eq!(report.culled[0].content_hash, "0000000000000000");
+    let manifest = std::fs::read_to_string(group_dir.join("MANIFEST.json")).unwrap();
+    assert!(manifest.contains("\"content_hash\""), "got: {manifest}");
 }
 
 #[test]
@@ -157,6 +186,10 @@ fn write_group_propagates_mkdir_p_failure() {
         culled: vec![],
         best_score: 1.0,
         score_breakdown: super::super::report::ScoreBreakdown::default(),
+        best_faces: Vec::new(),
+        strategy: super::super::report::PickStrategy::default(),
+        identities: Vec::new(),
+        reasons: Vec::new(),
     };
     let mut moved = 0;
     let err = write_group(&plan, &root, &out, &backend, false, &mut moved).unwrap_err();
@@ -181,6 +214,10 @@ fn write_group_propagates_copy_failure() {
         culled: vec![],
         best_score: 1.0,
         score_breakdown: super::super::report::ScoreBreakdown::default(),
+        best_faces: Vec::new(),
+        strategy: super::super::report::PickStrategy::default(),
+        identities: Vec::new(),
+        reasons: Vec::new(),
     };
     let mut moved = 0;
     let err = write_group(&plan, &root, &out, &backend, false, &mut moved).unwrap_err();
@@ -206,6 +243,10 @@ fn write_group_propagates_best_unique_name_failure() {
         culled: vec![],
         best_score: 1.0,
         score_breakdown: super::super::report::ScoreBreakdown::default(),
+        best_faces: Vec::new(),
+        strategy: super::super::report::PickStrategy::default(),
+        identities: Vec::new(),
+        reasons: Vec::new(),
     };
     let mut moved = 0;
     let err = write_group(&plan, &root, &out, &backend, false, &mut moved).unwrap_err();
@@ -234,9 +275,13 @@ fn write_group_propagates_culled_unique_name_failure() {
         group_id: 1,
         best_source: &best,
         best_source_backend: &backend,
-        culled: vec![(&culled, &backend, 1.0)],
+        culled: vec![culled_source(&culled, &backend, 1.0)],
         best_score: 2.0,
         score_breakdown: super::super::report::ScoreBreakdown::default(),
+        best_faces: Vec::new(),
+        strategy: super::super::report::PickStrategy::default(),
+        identities: Vec::new(),
+        reasons: Vec::new(),
     };
     let mut moved = 0;
     let err = write_group(&plan, &root, &out, &backend, false, &mut moved).unwrap_err();
@@ -265,9 +310,13 @@ fn write_group_propagates_move_file_failure_for_culled() {
         group_id: 1,
         best_source: &best,
         best_source_backend: &backend,
-        culled: vec![(&culled, &backend, 1.0)],
+        culled: vec![culled_source(&culled, &backend, 1.0)],
         best_score: 2.0,
         score_breakdown: super::super::report::ScoreBreakdown::default(),
+        best_faces: Vec::new(),
+        strategy: super::super::report::PickStrategy::default(),
+        identities: Vec::new(),
+        reasons: Vec::new(),
     };
     let mut moved = 0;
     let err = write_group(&plan, &root, &out, &backend, false, &mut moved).unwrap_err();
@@ -291,6 +340,10 @@ fn write_group_rejects_best_source_without_file_name() {
         culled: vec![],
         best_score: 1.0,
         score_breakdown: super::super::report::ScoreBreakdown::default(),
+        best_faces: Vec::new(),
+        strategy: super::super::report::PickStrategy::default(),
+        identities: Vec::new(),
+        reasons: Vec::new(),
     };
     let mut moved = 0;
     let err = write_group(&plan, &root_loc, &out_loc, &backend, true, &mut moved).unwrap_err();
@@ -316,9 +369,13 @@ fn write_group_rejects_culled_source_without_file_name() {
         group_id: 1,
         best_source: &best_loc,
         best_source_backend: &backend,
-        culled: vec![(&weird_culled, &backend, 1.0)],
+        culled: vec![culled_source(&weird_culled, &backend, 1.0)],
         best_score: 1.0,
         score_breakdown: super::super::report::ScoreBreakdown::default(),
+        best_faces: Vec::new(),
+        strategy: super::super::report::PickStrategy::default(),
+        identities: Vec::new(),
+        reasons: Vec::new(),
     };
     let mut moved = 0;
     let err = write_group(&plan, &root_loc, &out_loc, &backend, true, &mut moved).unwrap_err();
@@ -439,6 +496,10 @@ fn write_manifest_warns_on_open_write_failure() {
         best_dest: "/out/group-001/BEST_x.jpg".into(),
         culled: vec![],
         score_breakdown: super::super::report::ScoreBreakdown::default(),
+        best_faces: Vec::new(),
+        strategy: super::super::report::PickStrategy::default(),
+        identities: Vec::new(),
+        reasons: Vec::new(),
     };
     // 不 panic 即成功
     write_manifest(&group_dir, &backend, &report, 1.0);
@@ -458,7 +519,26 @@ fn write_manifest_warns_on_write_failure() {
         best_dest: "/out/group-001/BEST_x.jpg".into(),
         culled: vec![],
         score_breakdown: super::super::report::ScoreBreakdown::default(),
+        best_faces: Vec::new(),
+        strategy: super::super::report::PickStrategy::default(),
+        identities: Vec::new(),
+        reasons: Vec::new(),
     };
     write_manifest(&group_dir, &backend, &report, 1.0);
 }
 
+#[test]
+fn same_endpoint_requires_matching_connection_params() {
+    assert!(same_endpoint(&smb_loc("/a.jpg"), &smb_loc("/out/b.jpg")));
+    assert!(same_endpoint(&local_loc("/a.jpg"), &local_loc("/b.jpg")));
+    let other_host = Location::Smb {
+        user: None,
+        host: "backup".into(),
+        port: None,
+        share: "x".into(),
+        path: camino::Utf8PathBuf::from("/a.jpg"),
+    };
+    assert!(!same_endpoint(&smb_loc("/a.jpg"), &other_host));
+    assert!(!same_endpoint(&smb_loc("/a.jpg"), &local_loc("/a.jpg")));
+}
+
@@ -6,6 +6,8 @@
 //! 2. O(N²) 两两比较余弦相似度，≥ threshold 合入同一 Union-Find 集合（传递闭包）；
 //! 3. 按 root 聚合，每簇取去重后的图索引列表（同图多脸不重复计数）。
 //!
+//! `face_identities` 给出逐脸的身份 ID，供 `identity_pick` 按人挑帧。
+//!
 //! 不依赖 embedding 已归一化——内部按 L2 norm 再除一次让 Fake 输入也稳定。
 
 use std::collections::{BTreeMap, BTreeSet};
@@ -20,32 +22,51 @@ pub(crate) fn cluster_identities(
     embeddings: &[Vec<[f32; EMBED_DIM]>],
     threshold: f32,
 ) -> Vec<Vec<usize>> {
-    let mut flat: Vec<(usize, [f32; EMBED_DIM])> = Vec::new();
-    for (img_idx, faces) in embeddings.iter().enumerate() {
-        for face in faces {
-            flat.push((img_idx, *face));
+    let ids = face_identities(embeddings, threshold);
+    let mut by_id: BTreeMap<usize, BTreeSet<usize>> = BTreeMap::new();
+    for (img_idx, faces) in ids.iter().enumerate() {
+        for &id in faces {
+            by_id.entry(id).or_default().insert(img_idx);
         }
     }
+    by_id
+        .into_values()
+        .map(|s| s.into_iter().collect())
+        .collect()
+}
+
+/// 逐脸身份编号：返回值与 `embeddings` 同形（外层每张图，内层每脸一个身份 ID）。
+///
+/// ID 从 0 起按首次出现（图序优先、同图按脸序）稠密分配，同簇的脸共享同一 ID。
+pub(crate) fn face_identities(
+    embeddings: &[Vec<[f32; EMBED_DIM]>],
+    threshold: f32,
+) -> Vec<Vec<usize>> {
+    let flat: Vec<&[f32; EMBED_DIM]> = embeddings.iter().flatten().collect();
     let n = flat.len();
-    if n == 0 {
-        return Vec::new();
-    }
     let mut uf = UnionFind::new(n);
     for i in 0..n {
         for j in (i + 1)..n {
-            if cosine_similarity(&flat[i].1, &flat[j].1) >= threshold {
+            if cosine_similarity(flat[i], flat[j]) >= threshold {
                 uf.union(i, j);
             }
         }
     }
-    let mut by_root: BTreeMap<usize, BTreeSet<usize>> = BTreeMap::new();
-    for (k, (img_idx, _)) in flat.iter().enumerate() {
-        let r = uf.find(k);
-        by_root.entry(r).or_default().insert(*img_idx);
-    }
-    by_root
-        .into_values()
-        .map(|s| s.into_iter().collect())
+    let mut dense: BTreeMap<usize, usize> = BTreeMap::new();
+    let mut k = 0_usize;
+    embeddings
+        .iter()
+        .map(|faces| {
+            faces
+                .iter()
+                .map(|_| {
+                    let root = uf.find(k);
+                    k += 1;
+                    let next = dense.len();
+                    *dense.entry(root).or_insert(next)
+                })
+                .collect()
+        })
         .collect()
 }
 
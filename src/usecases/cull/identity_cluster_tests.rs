@@ -168,3 +168,15 @@ fn union_find_union_already_connected_is_noop() {
     uf.union(1, 0); // 已连通
     assert_eq!(uf.find(0), root_before);
 }
+
+#[test]
+fn face_identities_assigns_dense_ids_in_first_seen_order() {
+    let mut a = [0.0_f32; EMBED_DIM];
+    let mut b = [0.0_f32; EMBED_DIM];
+    a[0] = 1.0;
+    b[1] = 1.0;
+    // 图 0：b、a；图 1：无脸；图 2：a、a、b → b 首见得 0，a 得 1。
+    let out = face_identities(&[vec![b, a], vec![], vec![a, a, b]], 0.5);
+    assert_eq!(out, vec![vec![0_usize, 1], vec![], vec![1, 1, 0]]);
+    assert!(face_identities(&[], 0.5).is_empty());
+}
@@ -0,0 +1,148 @@
+//! 合影按人挑帧：把 `identity_cluster::face_identities` 的逐脸身份与
+//! `face_scoring::score_face` 的个人分合起来决定组内 best。
+//!
+//! 只有出现在组内至少半数帧里的身份才参与选择（「主要身份」）：路人只入镜一帧、或同一人
+//! 在阈值附近被拆成两个簇时，那个零星身份不能让唯一含它的帧压过其余帧。
+//!
+//! 选择顺序（[`PickStrategy`]）：
+//! 1. 组内无主要身份 → 按 `total` 取最高（与只看综合分时一致）
+//! 2. 存在全部主要身份都在场且睁眼的帧 → 只在这些帧里按 `total` 取最高
+//! 3. 否则取「最差那个主要身份的个人分」最高的帧（缺席者按 -∞ 计），`total` 作并列裁决
+//!
+//! 同时给出每个身份各自的最佳帧（[`IdentityBest`]），作为按人合成的推荐。
+//! 所有比较 NaN 视为 -∞，并列取组内靠前的帧（先扫描者优先）。
+
+use std::cmp::Ordering;
+
+use super::crop::total_cmp_nan_as_neg_inf;
+use super::face_scoring::FaceScore;
+use super::report::PickStrategy;
+
+/// 单个身份的最佳帧；`pos` 为组内位置（与 `totals` 下标对齐）。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) struct IdentityBest {
+    pub identity: usize,
+    pub pos: usize,
+    pub score: FaceScore,
+    /// 出现该身份的帧数。
+    pub frames: usize,
+}
+
+/// 组内选择结果。
+#[derive(Debug, PartialEq)]
+pub(super) struct Selection {
+    pub best_pos: usize,
+    pub strategy: PickStrategy,
+    pub identities: Vec<IdentityBest>,
+}
+
+/// `totals[p]` 为第 p 帧综合分；`faces[p]` 为第 p 帧逐脸 `(身份 ID, 个人分)`，两者等长。
+pub(super) fn select(totals: &[f32], faces: &[Vec<(usize, FaceScore)>]) -> Selection {
+    let identity_count = faces
+        .iter()
+        .flatten()
+        .map(|&(id, _)| id + 1)
+        .max()
+        .unwrap_or(0);
+    let per_frame: Vec<Vec<Option<FaceScore>>> = faces
+        .iter()
+        .map(|frame| frame_scores(frame, identity_count))
+        .collect();
+    let identities: Vec<IdentityBest> = (0..identity_count)
+        .filter_map(|id| identity_best(&per_frame, id))
+        .collect();
+    let main: Vec<usize> = identities
+        .iter()
+        .filter(|b| b.frames * 2 >= per_frame.len())
+        .map(|b| b.identity)
+        .collect();
+    let by_total = |a: usize, b: usize| total_cmp_nan_as_neg_inf(totals[a], totals[b]);
+
+    if main.is_empty() {
+        return Selection {
+            best_pos: first_max(0..totals.len(), by_total),
+            strategy: PickStrategy::Total,
+            identities,
+        };
+    }
+    let all_open = (0..per_frame.len()).filter(|&p| {
+        main.iter()
+            .all(|&id| per_frame[p][id].is_some_and(|s| s.eyes_open))
+    });
+    if all_open.clone().next().is_some() {
+        return Selection {
+            best_pos: first_max(all_open, by_total),
+            strategy: PickStrategy::AllEyesOpen,
+            identities,
+        };
+    }
+    let weakest: Vec<f32> = per_frame
+        .iter()
+        .map(|frame| weakest_score(frame, &main))
+        .collect();
+    Selection {
+        best_pos: first_max(0..per_frame.len(), |a, b| {
+            total_cmp_nan_as_neg_inf(weakest[a], weakest[b]).then_with(|| by_total(a, b))
+        }),
+        strategy: PickStrategy::MaxMinIdentity,
+        identities,
+    }
+}
+
+// 单帧按身份展开；同帧同身份多脸（聚类把两张相似脸并成一人）取个人分最高者。
+fn frame_scores(frame: &[(usize, FaceScore)], identity_count: usize) -> Vec<Option<FaceScore>> {
+    let mut slots: Vec<Option<FaceScore>> = vec![None; identity_count];
+    for &(id, score) in frame {
+        let slot = &mut slots[id];
+        if slot.is_none_or(|s| total_cmp_nan_as_neg_inf(score.score, s.score) == Ordering::Greater)
+        {
+            *slot = Some(score);
+        }
+    }
+    slots
+}
+
+fn identity_best(per_frame: &[Vec<Option<FaceScore>>], id: usize) -> Option<IdentityBest> {
+    let present: Vec<(usize, FaceScore)> = per_frame
+        .iter()
+        .enumerate()
+        .filter_map(|(p, f)| f[id].map(|s| (p, s)))
+        .collect();
+    let best = first_max(0..present.len(), |a, b| {
+        total_cmp_nan_as_neg_inf(present[a].1.score, present[b].1.score)
+    });
+    present.get(best).map(|&(pos, score)| IdentityBest {
+        identity: id,
+        pos,
+        score,
+        frames: present.len(),
+    })
+}
+
+// 帧内 `ids` 中最差的个人分；缺席者与 NaN 记 -∞。
+fn weakest_score(frame: &[Option<FaceScore>], ids: &[usize]) -> f32 {
+    ids.iter()
+        .map(|&id| frame[id].map_or(f32::NEG_INFINITY, |s| s.score))
+        .map(|v| if v.is_nan() { f32::NEG_INFINITY } else { v })
+        .fold(f32::INFINITY, f32::min)
+}
+
+// 严格大于才替换，并列保留首个；空迭代返 0（调用方保证组非空）。
+fn first_max(
+    positions: impl Iterator<Item = usize>,
+    cmp: impl Fn(usize, usize) -> Ordering,
+) -> usize {
+    positions
+        .reduce(|best, p| {
+            if cmp(p, best) == Ordering::Greater {
+                p
+            } else {
+                best
+            }
+        })
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+#[path = "identity_pick_tests.rs"]
+mod tests;
@@ -0,0 +1,95 @@
+//! `identity_pick` 单测：覆盖无脸回退、全员睁眼优先、max-min 兜底、缺席者、零星身份、
+//! 按人最佳帧。
+
+use super::*;
+
+fn open(score: f32) -> FaceScore {
+    FaceScore {
+        eyes_open: true,
+        score,
+    }
+}
+
+fn closed(score: f32) -> FaceScore {
+    FaceScore {
+        eyes_open: false,
+        score,
+    }
+}
+
+#[test]
+fn select_falls_back_to_total_when_group_has_no_faces() {
+    let sel = select(&[1.0, 3.0, 3.0], &[vec![], vec![], vec![]]);
+    assert_eq!(sel.best_pos, 1, "并列取靠前者");
+    assert_eq!(sel.strategy, PickStrategy::Total);
+    assert!(sel.identities.is_empty());
+}
+
+/// 帧 0 综合分最高但 B 闭眼；帧 1 两人都睁眼 → 帧 1 胜出。
+#[test]
+fn select_prefers_frame_where_everyone_has_eyes_open() {
+    let faces = [
+        vec![(0, open(9.0)), (1, closed(7.0))],
+        vec![(0, open(8.0)), (1, open(8.5))],
+        vec![(0, open(8.0))],
+    ];
+    let sel = select(&[20.0, 15.0, 18.0], &faces);
+    assert_eq!(sel.best_pos, 1);
+    assert_eq!(sel.strategy, PickStrategy::AllEyesOpen);
+}
+
+/// 无一帧全员睁眼：帧 1 最差者 6.0 高于帧 0 的 5.0；帧 2 缺席 B 记 -∞。
+#[test]
+fn select_maximises_weakest_identity_when_no_frame_is_all_open() {
+    let faces = [
+        vec![(0, open(9.0)), (1, closed(5.0))],
+        vec![(0, closed(6.0)), (1, open(8.0))],
+        vec![(0, open(10.0))],
+    ];
+    let sel = select(&[30.0, 10.0, 40.0], &faces);
+    assert_eq!(sel.best_pos, 1);
+    assert_eq!(sel.strategy, PickStrategy::MaxMinIdentity);
+    let picks: Vec<(usize, usize, usize)> = sel
+        .identities
+        .iter()
+        .map(|b| (b.identity, b.pos, b.frames))
+        .collect();
+    assert_eq!(picks, [(0, 2, 3), (1, 1, 2)]);
+}
+
+#[test]
+fn select_breaks_weakest_ties_by_total_and_ignores_nan() {
+    let faces = [
+        vec![(0, closed(f32::NAN))],
+        vec![(0, closed(4.0))],
+        vec![(0, closed(4.0)), (0, closed(1.0))],
+    ];
+    let sel = select(&[50.0, 1.0, 2.0], &faces);
+    assert_eq!(sel.best_pos, 2, "同帧同身份取高分脸，最差者并列后比 total");
+    assert_eq!(sel.identities[0].pos, 1);
+}
+
+/// 路人只入镜帧 0 且 A 在帧 0 闭眼：路人不是主要身份，不让帧 0 胜出；
+/// 按人最佳帧仍列出路人。
+#[test]
+fn select_ignores_identity_seen_in_a_single_frame() {
+    let faces = [
+        vec![(0, closed(5.0)), (1, open(9.0))],
+        vec![(0, open(7.0))],
+        vec![(0, open(8.0))],
+    ];
+    let sel = select(&[30.0, 10.0, 20.0], &faces);
+    assert_eq!(sel.best_pos, 2);
+    assert_eq!(sel.strategy, PickStrategy::AllEyesOpen);
+    assert_eq!(sel.identities.len(), 2);
+    assert_eq!((sel.identities[1].pos, sel.identities[1].frames), (0, 1));
+}
+
+/// 组内只有零星身份（各出现一帧）：没有主要身份，按 `total` 选。
+#[test]
+fn select_falls_back_to_total_without_main_identity() {
+    let faces = [vec![(0, closed(1.0))], vec![(1, open(9.0))], vec![]];
+    let sel = select(&[5.0, 1.0, 3.0], &faces);
+    assert_eq!(sel.best_pos, 0);
+    assert_eq!(sel.strategy, PickStrategy::Total);
+}
@@ -7,16 +7,37 @@
 //! `FaceMesh` EAR + `EyeState` 双印证流水线，留 e2e 步骤 6 真跑后补足（对应模块
 //! `face_align` / `identity_cluster` / `face_scoring` 届时新增）。
 
+mod burst;
+mod cache;
 mod crop;
+mod decisions;
 mod face_align;
 mod face_scoring;
 mod group_writer;
 mod identity_cluster;
+mod identity_pick;
 mod phash;
+mod quality;
 mod report;
+mod restore;
 mod run;
 mod sharpness;
 mod util;
 
-pub use report::{CullReport, CulledEntry, GroupReport, ScoreBreakdown};
+// `find --similar` 复用同一套 pHash 分组与图片嗅探。
+pub use burst::GroupBy;
+pub use cache::{CULL_CACHE_VERSION, CullCache};
+pub use decisions::{
+    CullDecisions, DECISIONS_VERSION, DecisionFrame, GroupDecision, apply_decisions,
+};
+// `faces` 复用对齐与 embedding 相似度。
+pub(crate) use face_align::align_face;
+pub(crate) use identity_cluster::{EMBED_DIM, cosine_similarity};
+pub(crate) use phash::{group_by, group_by_hash, hamming, phash};
+pub use report::{
+    CullReport, CulledEntry, FaceMark, GroupReason, GroupReport, IdentityPick, PickStrategy,
+    ScoreBreakdown,
+};
+pub use restore::restore;
 pub use run::cull;
+pub(crate) use util::{MIME_SNIFF_BYTES, is_image};
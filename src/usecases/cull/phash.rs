@@ -130,6 +130,15 @@ pub(crate) fn hamming(a: u64, b: u64) -> u32 {
 /// 返回每组在原 slice 中的索引列表，按组首索引升序。
 #[must_use]
 pub(crate) fn group_by_hash(hashes: &[u64], max_hamming: u8) -> Vec<Vec<usize>> {
+    group_by(hashes.len(), |i, j| {
+        hamming(hashes[i], hashes[j]) <= u32::from(max_hamming)
+    })
+}
+
+/// 对 `0..n` 两两调用 `close`，命中即合并；其余同 [`group_by_hash`]。
+/// 供多帧指纹等非单 hash 的距离复用同一套传递闭包。
+#[must_use]
+pub(crate) fn group_by(n: usize, close: impl Fn(usize, usize) -> bool) -> Vec<Vec<usize>> {
     fn find(parent: &mut [usize], i: usize) -> usize {
         if parent[i] == i {
             return i;
@@ -146,11 +155,10 @@ pub(crate) fn group_by_hash(hashes: &[u64], max_hamming: u8) -> Vec<Vec<usize>>
         }
     }
 
-    let n = hashes.len();
     let mut parent: Vec<usize> = (0..n).collect();
     for i in 0..n {
         for j in (i + 1)..n {
-            if hamming(hashes[i], hashes[j]) <= u32::from(max_hamming) {
+            if close(i, j) {
                 union(&mut parent, i, j);
             }
         }
@@ -353,6 +361,13 @@ mod tests {
         assert_eq!(g[0], vec![0]);
     }
 
+    #[test]
+    fn group_by_applies_custom_closeness() {
+        let values = [10_i32, 11, 30, 12];
+        let g = group_by(values.len(), |i, j| (values[i] - values[j]).abs() <= 1);
+        assert_eq!(g, vec![vec![0, 1, 3], vec![2]]);
+    }
+
     #[test]
     fn group_by_hash_redundant_union_hits_same_root() {
         let hashes = vec![0b00_u64, 0b01, 0b10, 0b11];
@@ -0,0 +1,205 @@
+//! 画面质量项：曝光削波 / 噪声 / 运动模糊方向 / 地平线倾斜 / 主体（人脸框内）清晰度。
+//!
+//! 除主体清晰度外均基于灰度图一次 3×3 邻域遍历（边缘 1 像素不计）：
+//! - 曝光：亮度 ≤ `CLIP_LOW` 或 ≥ `CLIP_HIGH` 的像素占比（死黑 + 过曝）
+//! - 噪声：Immerkær 快速估计 `σ = √(π/2) · Σ|I∗M| / (6·(W-2)·(H-2))`，
+//!   `M = [1,-2,1; -2,4,-2; 1,-2,1]` 两次二阶差分，对边缘与平滑渐变近乎无响应
+//! - 运动模糊：Sobel 梯度结构张量相干度 `(λ1-λ2)/(λ1+λ2)` ∈ [0, 1]；运动方向上的梯度被
+//!   抹平，剩余梯度集中于其垂直方向 → 相干度升高。方向角为模糊走向（与主梯度垂直），
+//!   [0°, 180°)，0° = 水平拖影。场景自身纹理的方向性对同组各帧相同，组内比较时抵消
+//! - 倾斜：只取强边缘（幅值 ≥ `EDGE_MIN`）里走向偏水平（|gy| ≥ |gx|）的像素单独累积
+//!   结构张量求主走向；偏离水平超过 `TILT_MAX_DEG` 或此类边缘过少时视为无地平线记 0。
+//!   角度按图像坐标（y 向下）给出，符号只用于报告，惩罚取绝对值
+//! - 主体清晰度：各人脸 bbox 内 Laplacian 方差均值；无脸 0
+//!
+//! 可选的质量模型（`QualityScorer`，NIMA 类）输出分由 `run::analyze_image` 填入
+//! [`ImageQuality::model_score`]，与上述各项一起按 `backend.face.w_*` 加权计入
+//! `ScoreBreakdown`（见 [`apply`]）；组内任一图缺模型分时 `run::pick_best_for_group`
+//! 整组清掉该项再 `apply`，模型分只在全员有分时比较。
+
+use image::{GrayImage, RgbImage};
+use serde_derive::{Deserialize, Serialize};
+
+use super::crop::crop_face_bbox;
+use super::report::ScoreBreakdown;
+use super::sharpness::laplacian_variance;
+use crate::usecases::config::FaceConfig;
+use crate::usecases::face::FaceDetection;
+
+const CLIP_LOW: u8 = 2;
+const CLIP_HIGH: u8 = 253;
+/// Sobel 幅值下限（0..255 灰度；完整黑白阶跃约 1020）：滤掉噪声起伏，只留真实轮廓。
+const EDGE_MIN: f64 = 128.0;
+const TILT_MAX_DEG: f64 = 20.0;
+/// 近水平强边缘占内点比例下限；低于此视为画面无可用的水平参照。
+const TILT_MIN_EDGE_FRACTION: f64 = 0.002;
+
+/// 单张图的原始质量度量（未加权）；未加权才能进分析缓存，改 `w_*` 后直接重算。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub(super) struct ImageQuality {
+    /// 削波像素占比，[0, 1]。
+    pub clipped: f32,
+    /// 噪声标准差估计（灰度级）。
+    pub noise_sigma: f32,
+    /// 梯度方向相干度，[0, 1]；越高越像单向拖影。
+    pub motion_coherence: f32,
+    /// 拖影走向角，[0°, 180°)。
+    pub motion_angle_deg: f32,
+    /// 地平线倾斜角（度）；无水平参照为 0。
+    pub tilt_deg: f32,
+    /// 人脸 bbox 内 Laplacian 方差均值。
+    pub subject_sharpness: f32,
+    /// 质量模型输出分；未配置模型或推理失败为 None。
+    pub model_score: Option<f32>,
+}
+
+/// 对解码后的整图与 SCRFD 人脸框算全部质量度量。
+pub(super) fn measure(image: &RgbImage, faces: &[FaceDetection]) -> ImageQuality {
+    let luma = image::imageops::grayscale(image);
+    ImageQuality {
+        subject_sharpness: subject_sharpness(image, faces),
+        ..measure_luma(&luma)
+    }
+}
+
+/// 把质量项按权重写入 `breakdown` 并计入 `total`：主体清晰度与质量模型加分，其余扣分。
+pub(super) fn apply(
+    mut breakdown: ScoreBreakdown,
+    quality: &ImageQuality,
+    cfg: &FaceConfig,
+) -> ScoreBreakdown {
+    breakdown.exposure_penalty = cfg.w_exposure * quality.clipped;
+    breakdown.noise_penalty = cfg.w_noise * quality.noise_sigma;
+    breakdown.motion_blur_penalty = cfg.w_motion_blur * quality.motion_coherence;
+    breakdown.motion_blur_angle = quality.motion_angle_deg;
+    breakdown.tilt_penalty = cfg.w_tilt * quality.tilt_deg.abs();
+    breakdown.subject_sharpness_bonus = cfg.w_subject_sharpness * quality.subject_sharpness;
+    breakdown.quality_bonus = quality.model_score.map_or(0.0, |s| cfg.w_quality * s);
+    breakdown.total += breakdown.subject_sharpness_bonus + breakdown.quality_bonus
+        - breakdown.exposure_penalty
+        - breakdown.noise_penalty
+        - breakdown.motion_blur_penalty
+        - breakdown.tilt_penalty;
+    breakdown
+}
+
+/// 累积量：全图结构张量 + 近水平强边缘结构张量 + 噪声残差和。
+#[derive(Default)]
+struct Sums {
+    noise: f64,
+    tensor: Tensor,
+    horizon: Tensor,
+    horizon_pixels: f64,
+}
+
+#[derive(Default)]
+struct Tensor {
+    xx: f64,
+    yy: f64,
+    xy: f64,
+}
+
+impl Tensor {
+    fn add(&mut self, gx: f64, gy: f64) {
+        self.xx += gx * gx;
+        self.yy += gy * gy;
+        self.xy += gx * gy;
+    }
+
+    /// (相干度, 主梯度方向角°)；全零张量返 None。
+    fn orientation(&self) -> Option<(f64, f64)> {
+        let trace = self.xx + self.yy;
+        if trace <= 0.0 {
+            return None;
+        }
+        let diff = (self.xx - self.yy).hypot(2.0 * self.xy);
+        let phi = 0.5 * (2.0 * self.xy).atan2(self.xx - self.yy);
+        Some((diff / trace, phi.to_degrees()))
+    }
+}
+
+fn measure_luma(luma: &GrayImage) -> ImageQuality {
+    let (width, height) = luma.dimensions();
+    if width < 3 || height < 3 {
+        return ImageQuality::default();
+    }
+    let clipped = luma
+        .pixels()
+        .filter(|p| p.0[0] <= CLIP_LOW || p.0[0] >= CLIP_HIGH)
+        .fold(0.0_f64, |n, _| n + 1.0);
+    let px = |x: u32, y: u32| f64::from(luma.get_pixel(x, y).0[0]);
+    let mut sums = Sums::default();
+    for y in 1..height - 1 {
+        for x in 1..width - 1 {
+            let n = [
+                [px(x - 1, y - 1), px(x, y - 1), px(x + 1, y - 1)],
+                [px(x - 1, y), px(x, y), px(x + 1, y)],
+                [px(x - 1, y + 1), px(x, y + 1), px(x + 1, y + 1)],
+            ];
+            let corners = n[0][0] + n[0][2] + n[2][0] + n[2][2];
+            let sides = n[0][1] + n[1][0] + n[1][2] + n[2][1];
+            sums.noise += 4.0f64
+                .mul_add(n[1][1], 2.0f64.mul_add(-sides, corners))
+                .abs();
+            let gx = 2.0f64.mul_add(n[1][2], n[0][2] + n[2][2])
+                - 2.0f64.mul_add(n[1][0], n[0][0] + n[2][0]);
+            let gy = 2.0f64.mul_add(n[2][1], n[2][0] + n[2][2])
+                - 2.0f64.mul_add(n[0][1], n[0][0] + n[0][2]);
+            sums.tensor.add(gx, gy);
+            if gx.hypot(gy) >= EDGE_MIN && gy.abs() >= gx.abs() {
+                sums.horizon.add(gx, gy);
+                sums.horizon_pixels += 1.0;
+            }
+        }
+    }
+    let total = f64::from(width) * f64::from(height);
+    let inner = f64::from(width - 2) * f64::from(height - 2);
+    let (coherence, angle) = sums
+        .tensor
+        .orientation()
+        .map_or((0.0, 0.0), |(c, phi)| (c, (phi + 90.0).rem_euclid(180.0)));
+    ImageQuality {
+        clipped: to_f32(clipped / total),
+        noise_sigma: to_f32(std::f64::consts::FRAC_PI_2.sqrt() * sums.noise / (6.0 * inner)),
+        motion_coherence: to_f32(coherence),
+        motion_angle_deg: to_f32(angle),
+        tilt_deg: to_f32(horizon_tilt(&sums, inner)),
+        subject_sharpness: 0.0,
+        model_score: None,
+    }
+}
+
+// 近水平边缘的主梯度方向接近 ±90°，走向 = 梯度方向 ∓ 90°。
+fn horizon_tilt(sums: &Sums, inner: f64) -> f64 {
+    if sums.horizon_pixels / inner < TILT_MIN_EDGE_FRACTION {
+        return 0.0;
+    }
+    let Some((_, phi)) = sums.horizon.orientation() else {
+        return 0.0;
+    };
+    let edge = if phi > 0.0 { phi - 90.0 } else { phi + 90.0 };
+    if edge.abs() > TILT_MAX_DEG { 0.0 } else { edge }
+}
+
+fn subject_sharpness(image: &RgbImage, faces: &[FaceDetection]) -> f32 {
+    let mut sum = 0.0_f32;
+    let mut count = 0.0_f32;
+    for face in faces {
+        let crop = crop_face_bbox(image, face);
+        sum += laplacian_variance(&image::imageops::grayscale(&crop));
+        count += 1.0;
+    }
+    if count > 0.0 { sum / count } else { 0.0 }
+}
+
+#[expect(
+    clippy::cast_possible_truncation,
+    reason = "度量值均为有限小量（比例/角度/灰度级/方差），f32 精度足够"
+)]
+fn to_f32(v: f64) -> f32 {
+    v as f32
+}
+
+#[cfg(test)]
+#[path = "quality_tests.rs"]
+mod tests;
@@ -0,0 +1,165 @@
+//! `quality` 单测：各度量在合成图上的量级/方向，以及 `apply` 的加权口径。
+
+use image::{Rgb, RgbImage};
+
+use super::*;
+
+fn gray(width: u32, height: u32, f: impl Fn(u32, u32) -> u8) -> RgbImage {
+    RgbImage::from_fn(width, height, |x, y| {
+        let v = f(x, y);
+        Rgb([v, v, v])
+    })
+}
+
+/// 线性同余伪随机：测试可复现，不引 rand。
+fn noise(seed: &mut u32) -> i32 {
+    *seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+    i32::try_from((*seed >> 16) % 41).unwrap() - 20
+}
+
+#[test]
+fn uniform_mid_gray_has_no_defects() {
+    let q = measure(&gray(32, 32, |_, _| 128), &[]);
+    assert_eq!(q, ImageQuality::default());
+}
+
+#[test]
+fn tiny_image_falls_back_to_default() {
+    let q = measure(&gray(2, 2, |_, _| 255), &[]);
+    assert_eq!(q, ImageQuality::default());
+}
+
+#[test]
+fn clipped_counts_blown_and_crushed_pixels() {
+    assert!((measure(&gray(16, 16, |_, _| 255), &[]).clipped - 1.0).abs() < 1e-6);
+    // 左半死黑、右半正常曝光
+    let q = measure(&gray(16, 16, |x, _| if x < 8 { 0 } else { 128 }), &[]);
+    assert!((q.clipped - 0.5).abs() < 1e-6, "got: {q:?}");
+}
+
+#[test]
+fn noise_sigma_rises_with_random_noise() {
+    let clean = measure(&gray(64, 64, |x, _| u8::try_from(x * 2 + 40).unwrap()), &[]);
+    let mut seed = 7;
+    let noisy = measure(
+        &gray(64, 64, |x, _| {
+            u8::try_from(i32::try_from(x * 2 + 40).unwrap() + noise(&mut seed)).unwrap()
+        }),
+        &[],
+    );
+    assert!(clean.noise_sigma < 0.5, "线性渐变不应算噪声: {clean:?}");
+    assert!(noisy.noise_sigma > 5.0, "got: {noisy:?}");
+}
+
+/// 水平条纹 ≈ 水平方向拖影：梯度全在竖直方向 → 相干度≈1、走向≈0°。
+#[test]
+fn horizontal_streaks_read_as_coherent_horizontal_motion() {
+    let q = measure(
+        &gray(48, 48, |_, y| if (y / 3) % 2 == 0 { 60 } else { 190 }),
+        &[],
+    );
+    assert!(q.motion_coherence > 0.99, "got: {q:?}");
+    assert!(
+        q.motion_angle_deg < 1.0 || q.motion_angle_deg > 179.0,
+        "got: {q:?}"
+    );
+}
+
+/// 上亮下暗、分界线倾斜 5° 的「地平线」：测得角度同号且接近 5°。
+#[test]
+fn tilted_horizon_is_measured_with_sign() {
+    let tan = 5.0_f32.to_radians().tan();
+    let horizon = |x: u32, y: u32| {
+        #[expect(clippy::cast_precision_loss, reason = "测试坐标很小")]
+        let (xf, yf) = (x as f32, y as f32);
+        if yf < 50.0 + (xf - 100.0) * tan {
+            200
+        } else {
+            40
+        }
+    };
+    let q = measure(&gray(200, 100, horizon), &[]);
+    assert!((q.tilt_deg - 5.0).abs() < 1.5, "got: {q:?}");
+
+    let level = measure(&gray(200, 100, |_, y| if y < 50 { 200 } else { 40 }), &[]);
+    assert!(level.tilt_deg.abs() < 0.1, "got: {level:?}");
+}
+
+#[test]
+fn subject_sharpness_only_counts_face_boxes() {
+    let face = FaceDetection {
+        bbox: [8.0, 8.0, 24.0, 24.0],
+        score: 0.9,
+        landmarks_5pt: [[0.0; 2]; 5],
+    };
+    let checker = gray(32, 32, |x, y| if (x + y) % 2 == 0 { 30 } else { 220 });
+    assert!(measure(&checker, &[face]).subject_sharpness > 0.0);
+    assert!(measure(&checker, &[]).subject_sharpness.abs() < f32::EPSILON);
+    let flat = gray(32, 32, |_, _| 128);
+    assert!(measure(&flat, &[face]).subject_sharpness.abs() < f32::EPSILON);
+}
+
+#[test]
+fn apply_weights_each_term_into_total() {
+    let cfg = FaceConfig {
+        w_exposure: 500.0,
+        w_noise: 5.0,
+        w_motion_blur: 100.0,
+        w_tilt: 10.0,
+        w_subject_sharpness: 1.0,
+        ..FaceConfig::default()
+    };
+    let base = ScoreBreakdown {
+        sharpness: 100.0,
+        total: 100.0,
+        ..ScoreBreakdown::default()
+    };
+    let q = ImageQuality {
+        clipped: 0.1,
+        noise_sigma: 2.0,
+        motion_coherence: 0.2,
+        motion_angle_deg: 30.0,
+        tilt_deg: -1.5,
+        subject_sharpness: 40.0,
+        model_score: Some(6.5),
+    };
+    let b = apply(base, &q, &cfg);
+    assert!((b.exposure_penalty - cfg.w_exposure * 0.1).abs() < 1e-3);
+    assert!((b.noise_penalty - cfg.w_noise * 2.0).abs() < 1e-3);
+    assert!((b.motion_blur_penalty - cfg.w_motion_blur * 0.2).abs() < 1e-3);
+    assert!((b.motion_blur_angle - 30.0).abs() < f32::EPSILON);
+    assert!(
+        (b.tilt_penalty - cfg.w_tilt * 1.5).abs() < 1e-3,
+        "倾斜惩罚取绝对值"
+    );
+    assert!((b.subject_sharpness_bonus - cfg.w_subject_sharpness * 40.0).abs() < 1e-3);
+    assert!((b.quality_bonus - cfg.w_quality * 6.5).abs() < 1e-3);
+    let expected = 100.0 + b.subject_sharpness_bonus + b.quality_bonus
+        - b.exposure_penalty
+        - b.noise_penalty
+        - b.motion_blur_penalty
+        - b.tilt_penalty;
+    assert!((b.total - expected).abs() < 1e-3, "got: {b:?}");
+    let untouched = apply(base, &ImageQuality::default(), &cfg);
+    assert!((untouched.total - base.total).abs() < f32::EPSILON);
+}
+
+/// 画面质量项默认权重为 0：未显式开启时不改变升级前的选优结果。
+#[test]
+fn default_weights_leave_total_unchanged() {
+    let base = ScoreBreakdown {
+        sharpness: 100.0,
+        total: 100.0,
+        ..ScoreBreakdown::default()
+    };
+    let q = ImageQuality {
+        clipped: 0.3,
+        noise_sigma: 4.0,
+        motion_coherence: 0.5,
+        tilt_deg: 3.0,
+        subject_sharpness: 80.0,
+        ..ImageQuality::default()
+    };
+    let b = apply(base, &q, &FaceConfig::default());
+    assert!((b.total - base.total).abs() < f32::EPSILON, "got: {b:?}");
+}
@@ -20,6 +20,8 @@ pub struct CullReport {
     /// 因 `backend.face.sharpness_min` 阈值在多图组里被剔除的模糊图数；
     /// 单图组（pHash 后 len=1）不参与阈值过滤（避免误丢唯一证据）。
     pub dropped_blurry: usize,
+    /// 评分阶段推理输出取自分析缓存（未重读图、未跑模型）的图数；未启用缓存时为 0。
+    pub cache_hits: usize,
     /// 失败计数（任一阶段 IO/解码/模型 Err）。
     pub failed: usize,
     pub dry_run: bool,
@@ -40,6 +42,56 @@ pub struct GroupReport {
     pub culled: Vec<CulledEntry>,
     /// 最佳照片的综合评分明细。
     pub score_breakdown: ScoreBreakdown,
+    /// 最佳照片上的人脸框与睁闭眼判定（原图像素坐标）。
+    pub best_faces: Vec<FaceMark>,
+    /// best 的选择依据。
+    pub strategy: PickStrategy,
+    /// 成组依据：组内相连成员对用到的链接类型，去重后按枚举序排列。
+    pub reasons: Vec<GroupReason>,
+    /// 组内每个身份（跨图人脸簇）各自的最佳帧，按身份 ID 升序；无人脸的组为空。
+    /// 无一帧全员睁眼时即为按人合成的推荐素材。
+    pub identities: Vec<IdentityPick>,
+}
+
+/// best 的选择依据；JSON 中为 snake_case 字符串。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PickStrategy {
+    /// 组内无人脸，或无主要身份（出现在至少半数帧里的身份）：按综合评分 `total` 取最高。
+    #[default]
+    Total,
+    /// 存在主要身份全员在场且睁眼的帧：在这些帧里按 `total` 取最高。
+    AllEyesOpen,
+    /// 无全员睁眼帧：取「最差那个主要身份的个人分」最高的帧，`total` 作并列裁决。
+    MaxMinIdentity,
+}
+
+/// 两张图被并入同组的依据；JSON 中为 snake_case 字符串。按可信度从高到低排列。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupReason {
+    /// EXIF `MakerNote` 连拍 ID 相同（Apple `BurstUUID`）。
+    BurstId,
+    /// 同机型连拍序号相邻（Canon `SequenceNumber`），且拍摄时间相近。
+    Sequence,
+    /// 拍摄时间差 ≤ `backend.face.burst_window_secs`。
+    TimeWindow,
+    /// pHash 汉明距离 ≤ 阈值。
+    Phash,
+}
+
+/// 单个身份在组内的最佳帧。
+#[derive(Debug, Clone, Serialize)]
+pub struct IdentityPick {
+    /// 组内身份 ID，从 0 起按首次出现分配。
+    pub identity: usize,
+    /// 该身份个人分最高的源照片绝对路径。
+    pub best_source: String,
+    /// 该身份在此帧的个人分（清晰度项 + 微笑 - 闭眼）。
+    pub score: f32,
+    pub eyes_open: bool,
+    /// 组内出现该身份的帧数。
+    pub frames: usize,
 }
 
 /// 单张被搬走的劣质副本：源路径 + group 目录里的 dst 路径 + 综合评分。
@@ -48,13 +100,42 @@ pub struct CulledEntry {
     pub source_path: String,
     pub dest_path: String,
     pub score: f32,
+    /// 评分明细；`total` 即 `score`。
+    pub score_breakdown: ScoreBreakdown,
+    /// 人脸框与睁闭眼判定（原图像素坐标）。
+    pub faces: Vec<FaceMark>,
+    /// 整文件 xxh3-64（16 位小写 hex）；`cull --restore` 搬回前据此校验。
+    pub content_hash: String,
+}
+
+/// 单张人脸的复核标注：SCRFD bbox + 双印证后的睁眼判定。
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct FaceMark {
+    /// `[x1, y1, x2, y2]` 原图像素坐标。
+    pub bbox: [f32; 4],
+    pub eyes_open: bool,
 }
 
-/// 综合评分明细：清晰度 / 闭眼惩罚 / 微笑加分 / 总分。
+/// 综合评分明细：清晰度 / 闭眼惩罚 / 微笑加分 / 画面质量各项 / 质量模型加分 / 总分。
 #[derive(Clone, Copy, Debug, Default, Serialize)]
 pub struct ScoreBreakdown {
     pub sharpness: f32,
     pub blink_penalty: f32,
     pub smile_bonus: f32,
+    /// 死黑 + 过曝像素占比 × `w_exposure`。
+    pub exposure_penalty: f32,
+    /// 噪声标准差估计 × `w_noise`。
+    pub noise_penalty: f32,
+    /// 梯度方向相干度 × `w_motion_blur`。
+    pub motion_blur_penalty: f32,
+    /// 拖影走向角（度，`[0, 180)`，0 = 水平）；仅供报告，不计分。
+    pub motion_blur_angle: f32,
+    /// 地平线倾斜角绝对值（度）× `w_tilt`。
+    pub tilt_penalty: f32,
+    /// 人脸框内清晰度均值 × `w_subject_sharpness`；无脸为 0。
+    pub subject_sharpness_bonus: f32,
+    /// 质量模型（`quality_model_path`）输出分 × `w_quality`；未配置，或组内任一图推理失败
+    /// （整组不计此项）为 0。
+    pub quality_bonus: f32,
     pub total: f32,
 }
@@ -0,0 +1,301 @@
+//! `cull --restore`：按 output 下各 group 目录的 `MANIFEST.json` 撤销一次 cull。
+//!
+//! 每个劣质副本先校验整文件 xxh3-64 与 manifest 记录一致，再搬回原源路径；源路径已有
+//! 文件时不覆盖、计失败。一组全部搬回后删 `BEST_` 副本与 manifest，再尝试删除已空的
+//! group 目录（backend 不支持删目录时留下空目录）。已搬回的副本（dest 不在、源在）视为
+//! 完成，重复执行不报错。不带 `content_hash` 的旧 manifest 跳过校验直接搬回。
+//!
+//! 清理前若 group 目录里还有 manifest 未记录的图片（非 `BEST_` 副本），该组计失败并原样
+//! 保留 manifest 与目录，不当作撤销完成——那张图无从得知原路径，删掉 manifest 就再没有记录。
+
+use std::io;
+use std::sync::Arc;
+
+use camino::Utf8PathBuf;
+use serde_derive::Deserialize;
+use tracing::debug;
+
+use super::group_writer::{BEST_PREFIX, MANIFEST_NAME, move_file};
+use super::report::CullReport;
+use super::util::{FEATURE, MIME_SNIFF_BYTES, is_image, record_failure};
+use crate::entities::backend::factory::BackendFactory;
+use crate::entities::backend::{Backend, EntryKind};
+use crate::entities::common;
+use crate::entities::file_info::{full_hash_stream, read_fill};
+use crate::entities::uri::Location;
+
+/// `MANIFEST.json` 中 restore 需要的字段；其余字段忽略。
+#[derive(Debug, Deserialize)]
+struct ManifestRecord {
+    group_id: usize,
+    best: ManifestBest,
+    culled: Vec<ManifestCulled>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ManifestBest {
+    dst: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ManifestCulled {
+    source_path: String,
+    dest_path: String,
+    #[serde(default)]
+    content_hash: Option<String>,
+}
+
+/// 遍历 `output` 找全部 `MANIFEST.json` 逐组撤销。单个副本或单组失败计入
+/// `report.failed` 后继续。
+///
+/// 返回的报告复用 [`CullReport`]：`scanned` = 找到的 manifest 数，`grouped` = 完整撤销的
+/// 组数，`culled_count` = manifest 记录的劣质副本总数，`moved` = 本次搬回数（`dry_run`
+/// 时 = 0）。
+///
+/// # Errors
+///
+/// `output` 的 backend 无法创建时返回 `Err`。
+pub fn restore(
+    factory: &dyn BackendFactory,
+    output: &Location,
+    dry_run: bool,
+) -> common::Result<CullReport> {
+    let out_backend = factory.for_location(output)?;
+    let mut report = CullReport {
+        dry_run,
+        ..CullReport::default()
+    };
+    let mut manifests = Vec::new();
+    for entry in out_backend.walk(output) {
+        match entry {
+            Ok(e)
+                if e.kind == EntryKind::File
+                    && e.location.path().file_name() == Some(MANIFEST_NAME) =>
+            {
+                manifests.push(e.location);
+            }
+            Ok(_) => {}
+            Err(e) => record_failure(&mut report, output.display(), &e),
+        }
+    }
+    manifests.sort_by_key(Location::display);
+    for manifest_loc in &manifests {
+        report.scanned += 1;
+        let record = match read_manifest(&out_backend, manifest_loc) {
+            Ok(r) => r,
+            Err(e) => {
+                record_failure(&mut report, manifest_loc.display(), &e);
+                continue;
+            }
+        };
+        report.culled_count += record.culled.len();
+        if restore_group(
+            factory,
+            &out_backend,
+            manifest_loc,
+            &record,
+            dry_run,
+            &mut report,
+        ) {
+            report.grouped += 1;
+        }
+    }
+    Ok(report)
+}
+
+fn read_manifest(backend: &Arc<dyn Backend>, loc: &Location) -> io::Result<ManifestRecord> {
+    let text = backend.read_to_string(loc)?;
+    serde_json::from_str(&text).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "cull restore: {} is not a cull manifest: {e}",
+                loc.display()
+            ),
+        )
+    })
+}
+
+/// 单组撤销；全部副本就位（且非 dry-run 时清理完成）返 true。
+fn restore_group(
+    factory: &dyn BackendFactory,
+    out_backend: &Arc<dyn Backend>,
+    manifest_loc: &Location,
+    record: &ManifestRecord,
+    dry_run: bool,
+    report: &mut CullReport,
+) -> bool {
+    let mut complete = true;
+    for culled in &record.culled {
+        match restore_one(factory, out_backend, culled, dry_run) {
+            Ok(true) => report.moved += usize::from(!dry_run),
+            Ok(false) => {}
+            Err(e) => {
+                complete = false;
+                record_failure(report, culled.dest_path.clone(), &e);
+            }
+        }
+    }
+    if !complete {
+        log_restore_group(record.group_id, "partial");
+        return false;
+    }
+    if !dry_run && let Err(e) = clean_group_dir(out_backend, manifest_loc, record) {
+        record_failure(report, manifest_loc.display(), &e);
+        log_restore_group(record.group_id, "error");
+        return false;
+    }
+    log_restore_group(record.group_id, "ok");
+    true
+}
+
+/// 单个副本：校验后搬回源路径。返回是否需要搬（已在源处返 false）。
+fn restore_one(
+    factory: &dyn BackendFactory,
+    out_backend: &Arc<dyn Backend>,
+    culled: &ManifestCulled,
+    dry_run: bool,
+) -> io::Result<bool> {
+    let dest = parse(&culled.dest_path)?;
+    let source = parse(&culled.source_path)?;
+    let src_backend = factory
+        .for_location(&source)
+        .map_err(|common::Error::Io(e)| e)?;
+    let source_exists = src_backend.exists(&source)?;
+    if !out_backend.exists(&dest)? {
+        if source_exists {
+            return Ok(false);
+        }
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!(
+                "cull restore: {} is gone and {} was not restored",
+                dest.display(),
+                source.display()
+            ),
+        ));
+    }
+    if source_exists {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!(
+                "cull restore: {} already exists; not overwriting it with {}",
+                source.display(),
+                dest.display()
+            ),
+        ));
+    }
+    if let Some(expected) = &culled.content_hash {
+        let (_, actual) = full_hash_stream(out_backend.open_read(&dest)?.as_mut())?;
+        let actual = format!("{actual:016x}");
+        if !actual.eq_ignore_ascii_case(expected) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "cull restore: {} hash {actual} does not match manifest {expected}",
+                    dest.display()
+                ),
+            ));
+        }
+    }
+    if !dry_run {
+        if let Some(parent) = source.path().parent() {
+            src_backend.mkdir_p(&source.with_path(Utf8PathBuf::from(parent)))?;
+        }
+        move_file(out_backend, &dest, &src_backend, &source)?;
+    }
+    Ok(true)
+}
+
+/// 删 `BEST_` 副本与 manifest，再 best-effort 删空 group 目录。目录里还有未记录的图片时
+/// 什么都不删、返 `Err`。
+fn clean_group_dir(
+    out_backend: &Arc<dyn Backend>,
+    manifest_loc: &Location,
+    record: &ManifestRecord,
+) -> io::Result<()> {
+    let dir = manifest_loc.with_path(
+        manifest_loc
+            .path()
+            .parent()
+            .map_or_else(Utf8PathBuf::new, Utf8PathBuf::from),
+    );
+    if let Some(stray) = unrecorded_image(out_backend, &dir)? {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "cull restore: {} is not recorded in {MANIFEST_NAME}; group left in place",
+                stray.display()
+            ),
+        ));
+    }
+    let best_copy = parse(&record.best.dst)?;
+    if out_backend.exists(&best_copy)? {
+        out_backend.remove_file(&best_copy)?;
+    }
+    out_backend.remove_file(manifest_loc)?;
+    if let Err(e) = out_backend.remove_dir(&dir) {
+        log_keep_group_dir(&dir, &e);
+    }
+    Ok(())
+}
+
+/// 副本全部搬回后 group 目录里剩下的、非 `BEST_` 副本的图片（按头部嗅探）。
+fn unrecorded_image(
+    out_backend: &Arc<dyn Backend>,
+    dir: &Location,
+) -> io::Result<Option<Location>> {
+    for entry in out_backend.walk(dir) {
+        let entry = entry?;
+        let Some(name) = entry.location.path().file_name() else {
+            continue;
+        };
+        if entry.kind != EntryKind::File || name == MANIFEST_NAME || name.starts_with(BEST_PREFIX)
+        {
+            continue;
+        }
+        let mut head = vec![0u8; MIME_SNIFF_BYTES];
+        let n = read_fill(out_backend.open_read(&entry.location)?.as_mut(), &mut head)?;
+        if is_image(&head[..n]) {
+            return Ok(Some(entry.location));
+        }
+    }
+    Ok(None)
+}
+
+fn parse(s: &str) -> io::Result<Location> {
+    Location::parse(s).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("cull restore: invalid location {s:?} in manifest: {e}"),
+        )
+    })
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+fn log_restore_group(group_id: usize, result: &'static str) {
+    debug!(
+        feature = FEATURE,
+        operation = "restore_group",
+        result,
+        group_id,
+        "cull group restored"
+    );
+}
+
+/// group 目录非空（用户放了别的文件）或 backend 不支持删目录：保留目录。
+#[cfg_attr(coverage_nightly, coverage(off))]
+fn log_keep_group_dir(dir: &Location, e: &io::Error) {
+    debug!(
+        feature = FEATURE,
+        operation = "remove_group_dir",
+        result = "kept",
+        path = %dir.display(),
+        error = %e,
+        "group directory left in place"
+    );
+}
+
+#[cfg(test)]
+#[path = "restore_tests.rs"]
+mod tests;
@@ -0,0 +1,241 @@
+//! `restore` 单测：先用 `write_group` 真实落盘一组，再验撤销、校验失败与幂等。
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tempfile::{TempDir, tempdir};
+
+use super::*;
+use crate::adapters::backend::factory::DefaultBackendFactory;
+use crate::usecases::cull::group_writer::{CulledSource, GroupPlan, write_group};
+use crate::usecases::cull::report::{PickStrategy, ScoreBreakdown};
+
+fn loc(p: &Path) -> Location {
+    Location::Local(Utf8PathBuf::from_path_buf(p.to_path_buf()).unwrap())
+}
+
+fn culled<'a>(source: &'a Location, backend: &'a Arc<dyn Backend>, body: &str) -> CulledSource<'a> {
+    CulledSource {
+        source,
+        backend,
+        breakdown: ScoreBreakdown::default(),
+        faces: Vec::new(),
+        content_hash: xxhash_rust::xxh3::xxh3_64(body.as_bytes()),
+    }
+}
+
+/// `src/{best,a,b}.jpg` 经 `write_group` 非 dry-run 落到 `out/group-001/`。
+/// 返回 (src tempdir, out tempdir, group 目录)。
+fn culled_layout() -> (TempDir, TempDir, PathBuf) {
+    let src = tempdir().unwrap();
+    let out = tempdir().unwrap();
+    for (name, body) in [("best.jpg", "BEST"), ("a.jpg", "AAA"), ("b.jpg", "BBB")] {
+        fs::write(src.path().join(name), body).unwrap();
+    }
+    let best = loc(&src.path().join("best.jpg"));
+    let a = loc(&src.path().join("a.jpg"));
+    let b = loc(&src.path().join("b.jpg"));
+    let backend = DefaultBackendFactory.for_location(&best).unwrap();
+    let plan = GroupPlan {
+        group_id: 1,
+        best_source: &best,
+        best_source_backend: &backend,
+        culled: vec![culled(&a, &backend, "AAA"), culled(&b, &backend, "BBB")],
+        best_score: 1.0,
+        score_breakdown: ScoreBreakdown::default(),
+        best_faces: Vec::new(),
+        strategy: PickStrategy::default(),
+        identities: Vec::new(),
+        reasons: Vec::new(),
+    };
+    let mut moved = 0;
+    write_group(
+        &plan,
+        &loc(src.path()),
+        &loc(out.path()),
+        &backend,
+        false,
+        &mut moved,
+    )
+    .unwrap();
+    assert_eq!(moved, 2);
+    let group = out.path().join("group-001");
+    (src, out, group)
+}
+
+#[test]
+fn restore_moves_culled_back_and_removes_group_dir() {
+    let (src, out, group) = culled_layout();
+    assert!(!src.path().join("a.jpg").exists());
+    let report = restore(&DefaultBackendFactory, &loc(out.path()), false).unwrap();
+    assert_eq!(report.failed, 0, "errors: {:?}", report.errors);
+    assert_eq!(
+        (
+            report.scanned,
+            report.grouped,
+            report.culled_count,
+            report.moved
+        ),
+        (1, 1, 2, 2)
+    );
+    assert_eq!(fs::read(src.path().join("a.jpg")).unwrap(), b"AAA");
+    assert_eq!(fs::read(src.path().join("b.jpg")).unwrap(), b"BBB");
+    assert!(src.path().join("best.jpg").exists());
+    assert!(!group.exists(), "空 group 目录应删除");
+
+    // 再执行：已无 manifest，什么也不做。
+    let again = restore(&DefaultBackendFactory, &loc(out.path()), false).unwrap();
+    assert_eq!((again.scanned, again.moved, again.failed), (0, 0, 0));
+}
+
+#[test]
+fn restore_dry_run_verifies_without_moving() {
+    let (src, out, group) = culled_layout();
+    let report = restore(&DefaultBackendFactory, &loc(out.path()), true).unwrap();
+    assert!(report.dry_run);
+    assert_eq!(report.failed, 0, "errors: {:?}", report.errors);
+    assert_eq!((report.grouped, report.moved), (1, 0));
+    assert!(!src.path().join("a.jpg").exists());
+    assert!(group.join("MANIFEST.json").exists());
+    assert!(group.join("BEST_best.jpg").exists());
+}
+
+#[test]
+fn restore_refuses_file_whose_hash_changed() {
+    let (src, out, group) = culled_layout();
+    fs::write(group.join("a.jpg"), b"tampered").unwrap();
+    let report = restore(&DefaultBackendFactory, &loc(out.path()), false).unwrap();
+    assert_eq!((report.failed, report.grouped, report.moved), (1, 0, 1));
+    assert!(
+        report.errors[0].message.contains("does not match manifest"),
+        "got: {:?}",
+        report.errors
+    );
+    assert!(!src.path().join("a.jpg").exists());
+    assert!(src.path().join("b.jpg").exists());
+    // 组未撤销完：manifest 与 BEST_ 副本保留，修好后可重跑。
+    assert!(group.join("MANIFEST.json").exists());
+    assert!(group.join("BEST_best.jpg").exists());
+}
+
+#[test]
+fn restore_never_overwrites_existing_source() {
+    let (src, out, group) = culled_layout();
+    fs::write(src.path().join("a.jpg"), b"new file").unwrap();
+    let report = restore(&DefaultBackendFactory, &loc(out.path()), false).unwrap();
+    assert_eq!(report.failed, 1);
+    assert!(
+        report.errors[0].message.contains("already exists"),
+        "got: {:?}",
+        report.errors
+    );
+    assert_eq!(fs::read(src.path().join("a.jpg")).unwrap(), b"new file");
+    assert!(group.join("a.jpg").exists());
+}
+
+#[test]
+fn restore_keeps_group_dir_holding_other_files() {
+    let (_src, out, group) = culled_layout();
+    fs::write(group.join("notes.txt"), b"mine").unwrap();
+    let report = restore(&DefaultBackendFactory, &loc(out.path()), false).unwrap();
+    assert_eq!(report.failed, 0, "errors: {:?}", report.errors);
+    assert_eq!(report.grouped, 1);
+    assert!(group.join("notes.txt").exists());
+    assert!(!group.join("MANIFEST.json").exists());
+    assert!(!group.join("BEST_best.jpg").exists());
+}
+
+#[test]
+fn restore_reports_missing_copy_and_corrupt_manifest() {
+    let (_src, out, group) = culled_layout();
+    fs::remove_file(group.join("b.jpg")).unwrap();
+    let broken = out.path().join("group-002");
+    fs::create_dir(&broken).unwrap();
+    fs::write(broken.join("MANIFEST.json"), "{").unwrap();
+    let report = restore(&DefaultBackendFactory, &loc(out.path()), false).unwrap();
+    assert_eq!((report.scanned, report.grouped, report.failed), (2, 0, 2));
+    let messages: Vec<&str> = report.errors.iter().map(|e| e.message.as_str()).collect();
+    assert!(
+        messages.iter().any(|m| m.contains("was not restored")),
+        "got: {messages:?}"
+    );
+    assert!(
+        messages
+            .iter()
+            .any(|m| m.contains("is not a cull manifest")),
+        "got: {messages:?}"
+    );
+}
+
+/// 旧 manifest 无 `content_hash`：跳过校验照常搬回。
+#[test]
+fn restore_accepts_manifest_without_hashes() {
+    let (src, out, group) = culled_layout();
+    let manifest = fs::read_to_string(group.join("MANIFEST.json")).unwrap();
+    let mut json: serde_json::Value = serde_json::from_str(&manifest).unwrap();
+    for c in json["culled"].as_array_mut().unwrap() {
+        c.as_object_mut().unwrap().remove("content_hash");
+    }
+    fs::write(group.join("MANIFEST.json"), json.to_string()).unwrap();
+    let report = restore(&DefaultBackendFactory, &loc(out.path()), false).unwrap();
+    assert_eq!(report.failed, 0, "errors: {:?}", report.errors);
+    assert_eq!(report.moved, 2);
+    assert!(src.path().join("a.jpg").exists());
+}
+
+/// group 目录里有 manifest 未记录的图片：该组计失败，manifest 与 `BEST_` 副本原样保留。
+#[test]
+fn restore_fails_group_holding_unrecorded_image() {
+    let (src, out, group) = culled_layout();
+    fs::write(group.join("stray.png"), b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR").unwrap();
+    let report = restore(&DefaultBackendFactory, &loc(out.path()), false).unwrap();
+    assert_eq!((report.failed, report.grouped, report.moved), (1, 0, 2));
+    assert!(
+        report.errors[0].message.contains("stray.png is not recorded"),
+        "got: {:?}",
+        report.errors
+    );
+    assert!(src.path().join("a.jpg").exists());
+    assert!(group.join("MANIFEST.json").exists());
+    assert!(group.join("BEST_best.jpg").exists());
+}
+
+/// `--apply-decisions` 换 best 后 manifest 随之重写：restore 连同换下的原 best 全部搬回。
+#[test]
+fn restore_after_swapped_best_returns_every_frame() {
+    use crate::usecases::cull::decisions::{
+        CullDecisions, DECISIONS_VERSION, DecisionFrame, GroupDecision, apply_decisions,
+    };
+    let (src, out, group) = culled_layout();
+    let path = |p: PathBuf| p.to_str().unwrap().to_string();
+    let frame = |name: &str, dest: Option<&str>| DecisionFrame {
+        source_path: path(src.path().join(name)),
+        dest_path: dest.map(|d| path(group.join(d))),
+    };
+    let decisions = CullDecisions {
+        version: DECISIONS_VERSION,
+        dry_run: false,
+        groups: vec![GroupDecision {
+            group_id: 1,
+            best_dest: path(group.join("BEST_best.jpg")),
+            frames: vec![
+                frame("best.jpg", None),
+                frame("a.jpg", Some("a.jpg")),
+                frame("b.jpg", Some("b.jpg")),
+            ],
+            best: path(src.path().join("a.jpg")),
+            keep_all: false,
+        }],
+    };
+    let applied = apply_decisions(&DefaultBackendFactory, &decisions, false).unwrap();
+    assert_eq!(applied.failed, 0, "errors: {:?}", applied.errors);
+    assert!(group.join("best.jpg").exists());
+
+    let report = restore(&DefaultBackendFactory, &loc(out.path()), false).unwrap();
+    assert_eq!(report.failed, 0, "errors: {:?}", report.errors);
+    assert_eq!((report.grouped, report.culled_count), (1, 2));
+    assert_eq!(fs::read(src.path().join("best.jpg")).unwrap(), b"BEST");
+    assert_eq!(fs::read(src.path().join("a.jpg")).unwrap(), b"AAA");
+    assert_eq!(fs::read(src.path().join("b.jpg")).unwrap(), b"BBB");
+    assert!(!group.exists(), "空 group 目录应删除");
+}
@@ -1,19 +1,27 @@
-//! `cull` 主流程：scan → pHash 分组 → 4 模型印证评分 → `group_writer` 落盘。
+//! `cull` 主流程：scan → 连拍分组 → 4 模型印证评分 → `group_writer` 落盘。
 //!
 //! 5 阶段：
-//! 1. **扫源**：walk 所有 source，按 `max_image_bytes` 跳过超大文件；逐张读字节 +
-//!    `image::load_from_memory` 解码 + 计算 pHash + 灰度清晰度，**计算完即 drop 字节与
-//!    解码图**——仅 `ScannedFile`（metadata + hash + sharpness）入 `Vec` 累计。
+//! 1. **扫源**：walk 所有 source（本地或 `smb://` / `adb://` 等远端，统一经
+//!    `Backend::open_read` 读字节），按 `max_image_bytes` 跳过超大文件（读时再封顶一次，
+//!    防远端 walker 报的 size 不可信）；逐张读字节 +
+//!    `image::load_from_memory` 解码 + 计算 pHash + 灰度清晰度 + 解析 EXIF 拍摄时间与连拍
+//!    标识，**计算完即 drop 字节与解码图**——仅 `ScannedFile`（metadata + `Shot` + sharpness）
+//!    入 `Vec` 累计。
 //!    旧实现把 `Arc<Vec<u8>>` + `Arc<RgbImage>` 也存进 `ScannedFile` 让 `pick_best` 阶段复用，
 //!    但 `scan` 主循环会让整批图（2847 张 × ~17 MB ≈ 48 GB）驻留致 OOM；现按需在
 //!    `analyze_image` 内对组内成员重读+重 decode（仅多图组承担二次开销，单图组直接跳过）。
-//! 2. **pHash 分组**：Union-Find 按汉明距离 ≤ `phash_hamming_max` 分组
+//! 2. **连拍分组**：`burst::group_shots` 按 `--group-by` 两两链接（连拍 ID / 相邻序号 /
+//!    `burst_window_secs` 时间窗 / pHash 汉明距离 ≤ `phash_hamming_max`），Union-Find 成组
 //! 3. **粗筛**：单图组（len=1）跳过：无可比较对象不搬迁不入 report
 //! 4. **评分**：每组 per-image 跑 SCRFD → 5 点对齐 → `MobileFaceNet` 128 维 embedding +
 //!    `FaceMesh` 468 点 EAR + `EyeState` 闭眼概率，`face_scoring::score_image` 出
-//!    `ScoreBreakdown`；组内全部图算完后调 `identity_cluster::cluster_identities` 输出
-//!    跨图身份簇日志；选 `breakdown.total` 最高者为 best
+//!    `ScoreBreakdown`，再经 `quality::apply` 计入曝光 / 噪声 / 运动模糊 / 倾斜 / 主体
+//!    清晰度 / 可选质量模型（`QualityScorer`）分；组内全部图算完后调 `identity_cluster::face_identities` 给每张脸编身份，
+//!    `identity_pick::select` 按「全员睁眼 → 最差者最好 → 总分」选 best
 //! 5. **落盘**：调 `group_writer::write_group` 写 group 目录
+//!
+//! 给了分析缓存（`cache`）时，阶段 1 命中的图跳过 decode + pHash + 清晰度，阶段 4 命中的图
+//! 跳过重读 + 重 decode + 全部推理；新算出的结果在各阶段末由主线程串行写回缓存。
 
 use std::io;
 use std::sync::Arc;
@@ -21,47 +29,66 @@ use std::sync::Arc;
 use image::RgbImage;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
-use super::crop::{crop_eye_around, crop_face_bbox, total_cmp_nan_as_neg_inf};
+use super::burst::{GroupBy, Rules, Shot, group_shots};
+use super::cache::{CullCache, content_key, inference_fingerprint};
+use super::crop::{crop_eye_around, crop_face_bbox};
 // 测试侧 `use super::*` 经此 re-export 拿到 `u32_from_f32_clamped` 微测试入口。
 #[cfg(test)]
 use super::crop::u32_from_f32_clamped;
 use super::face_align;
-use super::face_scoring;
-use super::group_writer::{GroupPlan, write_group};
+use super::face_scoring::{self, MeshFeatures};
+use super::group_writer::{CulledSource, GroupPlan, write_group};
 use super::identity_cluster;
-use super::phash::{group_by_hash, phash};
-use super::report::{CullReport, ScoreBreakdown};
+use super::identity_pick::{self, IdentityBest, Selection};
+use super::phash::phash;
+use super::quality::{self, ImageQuality};
+use super::report::{
+    CullReport, FaceMark, GroupReason, IdentityPick, PickStrategy, ScoreBreakdown,
+};
 use super::sharpness::laplacian_variance;
 use super::util::{
-    ensure_sources_outside_output, is_image, log_analyze_image, log_commit_group, log_cull_summary,
-    log_identity_clusters, log_pick_best, log_scan_entry_ok, log_scan_source_complete,
-    log_scan_source_start, read_all, record_failure,
+    ensure_sources_outside_output, image_mime, log_analyze_image, log_commit_group,
+    log_cull_summary, log_identity_clusters, log_inference_cache_reset, log_pick_best,
+    log_scan_entry_filtered, log_scan_entry_ok, log_scan_source_complete, log_scan_source_start,
+    read_all, record_failure,
 };
 use crate::entities::backend::factory::BackendFactory;
 use crate::entities::backend::{Backend, Entry, EntryKind};
 use crate::entities::common::{self, canonical_prefix, under_prefix};
+use crate::entities::file_index::TagProvider;
+use crate::entities::tags::Tags;
 use crate::entities::uri::Location;
 use crate::usecases::config::{FaceConfig, config};
 use crate::usecases::face::{
-    EyeStateClassifier, FaceDetection, FaceDetector, FaceEmbedder, FaceMeshDetector,
+    EyeStateClassifier, FaceDetection, FaceDetector, FaceEmbedder, FaceMeshDetector, QualityScorer,
 };
+use crate::usecases::tag_filter::TagFilter;
 
-/// 单文件扫描结果。仅持 metadata + phash + sharpness：`raw_bytes` 与 `decoded` 在
+/// 单文件扫描结果。仅持 metadata + 分组特征 + sharpness：`raw_bytes` 与 `decoded` 在
 /// `scan_entry` 内算完即 drop，`analyze_image` 阶段按需重读重 decode（避免整批图驻留 OOM）。
 struct ScannedFile {
     src_loc: Location,
     src_backend: Arc<dyn Backend>,
     source_root: Location,
-    hash: u64,
+    shot: Shot,
     sharpness: f32,
+    /// 整文件 xxh3-64：记入 `CulledEntry.content_hash` 供 `--restore` 搬回前校验。
+    content_hash: u64,
+    /// 分析缓存键（内容 SHA-512 十六进制）；未启用缓存时不算。
+    cache_key: Option<String>,
 }
 
 /// 单张图 4 模型印证结果（faces 长度与其余 3 vec 一致：对齐失败/嵌入失败的 face 整体丢弃）。
-struct ImageAnalysis {
-    faces: Vec<FaceDetection>,
-    embeddings: Vec<[f32; identity_cluster::EMBED_DIM]>,
-    meshes: Vec<Vec<[f32; 3]>>,
-    eye_states: Vec<(f32, f32)>,
+#[derive(Clone)]
+pub(super) struct ImageAnalysis {
+    pub faces: Vec<FaceDetection>,
+    pub embeddings: Vec<[f32; identity_cluster::EMBED_DIM]>,
+    pub meshes: Vec<MeshFeatures>,
+    pub eye_states: Vec<(f32, f32)>,
+    pub quality: ImageQuality,
+    /// 有推理阶段失败后退化（facemesh 空 mesh / eyestate 0 闭眼概率 / 质量模型无分）：
+    /// 本次照常参与评分，但不写入分析缓存，下次运行重试。
+    pub degraded: bool,
 }
 
 /// 入口：5 阶段串联。
@@ -71,22 +98,34 @@ struct ImageAnalysis {
 /// - source ⊆ output 返 `InvalidInput`
 /// - factory 构造 backend 失败、output `mkdir_p` 失败传播
 /// - 单文件失败累计到 `report.failed`/`errors`
+///
+/// `cache` 为分析缓存：推理口径（模型路径等）变了先丢弃其中的推理输出，运行中按需读写，
+/// 由调用方落盘；`dry_run` 同样写回（缓存不是归档结果）。
 #[expect(
     clippy::too_many_arguments,
-    reason = "4 detector + factory + sources/output + flags：注入侧主入口契约"
+    reason = "4 detector + 可选质量模型 + factory + sources/output + flags + 缓存：注入侧主入口契约"
 )]
 pub fn cull(
     scrfd: &dyn FaceDetector,
     facenet: &dyn FaceEmbedder,
     facemesh: &dyn FaceMeshDetector,
     eyestate: &dyn EyeStateClassifier,
+    quality_model: Option<&dyn QualityScorer>,
     factory: &dyn BackendFactory,
     sources: &[Location],
     output: &Location,
     dry_run: bool,
     phash_max_hamming: u8,
+    group_by: GroupBy,
+    filter: &TagFilter,
+    tags: Option<TagProvider>,
+    mut cache: Option<&mut CullCache>,
 ) -> common::Result<CullReport> {
     let face_cfg = &config().backend.face;
+    let gate = TagGate {
+        filter,
+        sidecar: tags,
+    };
     let output_backend = factory.for_location(output)?;
     // canonical_prefix 让 symlink output（如 /tmp/out → /photos/cull_output）下
     // src 路径与 output prefix 字面可比；裸 display() 会让 under_prefix 误返 false
@@ -101,6 +140,9 @@ pub fn cull(
     if !dry_run {
         output_backend.mkdir_p(output)?;
     }
+    if let Some(c) = cache.as_deref_mut() {
+        log_inference_cache_reset(c.align_inference(&inference_fingerprint(face_cfg)));
+    }
 
     let mut scanned: Vec<ScannedFile> = Vec::new();
     for source in sources {
@@ -110,20 +152,29 @@ pub fn cull(
             &src_backend,
             &output_prefix,
             face_cfg,
+            gate,
+            cache.as_deref(),
             &mut scanned,
             &mut report,
         );
     }
+    if let Some(c) = cache.as_deref_mut() {
+        for s in &scanned {
+            if let Some(key) = &s.cache_key {
+                c.record_scan(key, s.shot.hash, s.sharpness);
+            }
+        }
+    }
     // report.scanned 由 scan_source 增量累加触达的 source 文件数（含被识别为非媒体
     // 跳过、超大跳过、解码失败、IO 失败），口径与 CopyReport.scanned 一致；
     // 而 scanned vec 仅含成功解码的图（用于后续分组/评分），不是 report 的 scanned。
 
-    let hashes: Vec<u64> = scanned.iter().map(|s| s.hash).collect();
-    let groups = group_by_hash(&hashes, phash_max_hamming);
+    let shots: Vec<&Shot> = scanned.iter().map(|s| &s.shot).collect();
+    let groups = group_shots(&shots, &Rules::new(group_by, phash_max_hamming, face_cfg));
 
     // 阶段 A：模糊过滤串行（写 report.dropped_blurry）+ 收集合规多图组。
-    let mut filtered: Vec<Vec<usize>> = Vec::new();
-    for grp_indices in groups {
+    let mut filtered: Vec<(Vec<usize>, Vec<GroupReason>)> = Vec::new();
+    for (grp_indices, reasons) in groups {
         if grp_indices.len() < 2 {
             continue;
         }
@@ -135,31 +186,46 @@ pub fn cull(
         if kept.len() < 2 {
             continue;
         }
-        filtered.push(kept);
+        filtered.push((kept, reasons));
     }
 
-    // 阶段 B：组级 ONNX 评分并行（4 个 face trait 都是 Send+Sync，
-    // tract `TypedRunnableModel::run(&self,...)` 支持 `&self` 并发调用）。
+    // 阶段 B：组级 ONNX 评分并行（4 个 face trait 与 QualityScorer 都是 Send+Sync，
+    // tract `TypedRunnableModel::run(&self,...)` 支持 `&self` 并发调用）。缓存只读，
+    // 新推理结果随 ScoredGroup 带回阶段 C 写入。
+    let cache_view = cache.as_deref();
     let scored: Vec<ScoredGroup> = filtered
         .into_par_iter()
-        .map(|indices| {
+        .map(|(indices, reasons)| {
             let mut failures = Vec::new();
-            let (best_idx, best_breakdown, breakdowns) = pick_best_for_group(
+            let mut lookup = AnalysisLookup {
+                cache: cache_view,
+                hits: 0,
+                fresh: Vec::new(),
+            };
+            let (selection, breakdowns, face_marks) = pick_best_for_group(
                 &indices,
                 &scanned,
                 scrfd,
                 facenet,
                 facemesh,
                 eyestate,
+                quality_model,
                 face_cfg,
+                &mut lookup,
                 &mut failures,
             );
             ScoredGroup {
+                best_idx: indices[selection.best_pos],
+                best_breakdown: breakdowns[selection.best_pos],
+                strategy: selection.strategy,
+                identities: selection.identities,
+                reasons,
                 indices,
-                best_idx,
-                best_breakdown,
                 breakdowns,
+                face_marks,
                 failures,
+                cache_hits: lookup.hits,
+                fresh: lookup.fresh,
             }
         })
         .collect();
@@ -174,6 +240,14 @@ pub fn cull(
         for (path, err) in failures {
             record_failure(&mut report, path, &err);
         }
+        report.cache_hits += sg.cache_hits;
+        if let Some(c) = cache.as_deref_mut() {
+            for (i, analysis) in std::mem::take(&mut sg.fresh) {
+                if let Some(key) = &scanned[i].cache_key {
+                    c.record_analysis(key, &analysis);
+                }
+            }
+        }
         commit_scored_group(
             sg,
             &scanned,
@@ -198,7 +272,48 @@ struct ScoredGroup {
     best_idx: usize,
     best_breakdown: ScoreBreakdown,
     breakdowns: Vec<ScoreBreakdown>,
+    /// 逐图人脸框 + 睁眼标注，与 `indices` 同序；供 review 页绘制。
+    face_marks: Vec<Vec<FaceMark>>,
+    strategy: PickStrategy,
+    /// `IdentityBest::pos` 为 `indices` 内位置。
+    identities: Vec<IdentityBest>,
+    reasons: Vec<GroupReason>,
     failures: Vec<(String, io::Error)>,
+    /// 推理输出取自缓存的图数。
+    cache_hits: usize,
+    /// 本次新算出的推理输出（`scanned` 下标），待主线程写回缓存。
+    fresh: Vec<(usize, ImageAnalysis)>,
+}
+
+/// 评分阶段的缓存读写：并行组内只读 `cache`，新算出的结果攒在 `fresh` 交主线程写回。
+struct AnalysisLookup<'a> {
+    cache: Option<&'a CullCache>,
+    hits: usize,
+    fresh: Vec<(usize, ImageAnalysis)>,
+}
+
+impl AnalysisLookup<'_> {
+    /// 命中缓存直接返回；否则调 `analyze` 现算，启用缓存时另存一份待写回。
+    fn get(
+        &mut self,
+        idx: usize,
+        item: &ScannedFile,
+        analyze: impl FnOnce() -> Option<ImageAnalysis>,
+    ) -> Option<ImageAnalysis> {
+        let key = item.cache_key.as_deref();
+        if let Some(hit) = self.cache.zip(key).and_then(|(c, k)| c.analysis(k)) {
+            self.hits += 1;
+            return Some(hit);
+        }
+        let analysis = analyze();
+        if let Some(a) = analysis
+            .as_ref()
+            .filter(|a| self.cache.is_some() && !a.degraded)
+        {
+            self.fresh.push((idx, a.clone()));
+        }
+        analysis
+    }
 }
 
 /// 把并行评分结果落盘：组装 `GroupPlan` → `write_group` → 累计 report。
@@ -225,19 +340,24 @@ fn commit_scored_group(
     let best = &scanned[sg.best_idx];
     // culled.score 用对应 breakdown.total（综合评分），与 best_breakdown.total 同口径，
     // 取代旧实现单字段 sharpness（CulledEntry 文档承诺综合评分）。
-    let culled_refs: Vec<(&Location, &Arc<dyn Backend>, f32)> = sg
+    let culled_refs: Vec<CulledSource> = sg
         .indices
         .iter()
         .enumerate()
         .filter(|&(_, &i)| i != sg.best_idx)
-        .map(|(pos, &i)| {
-            (
-                &scanned[i].src_loc,
-                &scanned[i].src_backend,
-                sg.breakdowns[pos].total,
-            )
+        .map(|(pos, &i)| CulledSource {
+            source: &scanned[i].src_loc,
+            backend: &scanned[i].src_backend,
+            breakdown: sg.breakdowns[pos],
+            faces: sg.face_marks[pos].clone(),
+            content_hash: scanned[i].content_hash,
         })
         .collect();
+    let best_pos = sg
+        .indices
+        .iter()
+        .position(|&i| i == sg.best_idx)
+        .unwrap_or_default();
     let culled_len = culled_refs.len();
     let plan = GroupPlan {
         group_id: *next_group_id,
@@ -246,6 +366,20 @@ fn commit_scored_group(
         culled: culled_refs,
         best_score: sg.best_breakdown.total,
         score_breakdown: sg.best_breakdown,
+        best_faces: sg.face_marks[best_pos].clone(),
+        strategy: sg.strategy,
+        identities: sg
+            .identities
+            .iter()
+            .map(|b| IdentityPick {
+                identity: b.identity,
+                best_source: scanned[sg.indices[b.pos]].src_loc.display(),
+                score: b.score.score,
+                eyes_open: b.score.eyes_open,
+                frames: b.frames,
+            })
+            .collect(),
+        reasons: sg.reasons.clone(),
     };
     // 计数搬到 Ok arm 内：write_group Err 时 groups 不 push，best_count/culled_count
     // 也必须保持原子（曾经在外提前累加，让 best_count != groups.len() 误导消费方）。
@@ -295,7 +429,29 @@ fn filter_blurry(indices: &[usize], scanned: &[ScannedFile], min: f32) -> (Vec<u
     (kept, dropped)
 }
 
-/// 单文件并行处理结果：`None` = 非图静默跳；`Err` = 该计入 failed 的 IO/decode 错误。
+/// `--min-rating` / `--label` 判定：过滤条件 + sidecar 星级注入。未命中的图不参与
+/// 分组（原地不动），等同非图静默跳过。
+#[derive(Clone, Copy)]
+struct TagGate<'a> {
+    filter: &'a TagFilter,
+    sidecar: Option<TagProvider>,
+}
+
+impl TagGate<'_> {
+    /// sidecar 优先、缺项回退内嵌 XMP（scan 已整读字节，直接在其中找 packet）。
+    fn admits(self, loc: &Location, backend: &Arc<dyn Backend>, bytes: &[u8]) -> bool {
+        if !self.filter.is_active() {
+            return true;
+        }
+        let sidecar = self
+            .sidecar
+            .and_then(|p| p(loc, backend))
+            .unwrap_or_default();
+        self.filter.matches(&sidecar.or(Tags::from_embedded(bytes)))
+    }
+}
+
+/// 单文件并行处理结果：`None` = 非图 / 未过星级色标过滤静默跳；`Err` = 该计入 failed 的 IO/decode 错误。
 type ScanOutcome = Option<Result<ScannedFile, (String, io::Error)>>;
 
 fn scan_source(
@@ -303,6 +459,8 @@ fn scan_source(
     src_backend: &Arc<dyn Backend>,
     output_prefix: &str,
     face_cfg: &FaceConfig,
+    gate: TagGate<'_>,
+    cache: Option<&CullCache>,
     out: &mut Vec<ScannedFile>,
     report: &mut CullReport,
 ) {
@@ -351,7 +509,16 @@ fn scan_source(
     // 让 rayon worker 安全调用；OOM 已修（不再缓存图），N 核同时驻留 N 张图安全。
     let results: Vec<ScanOutcome> = entries
         .into_par_iter()
-        .map(|e| scan_entry(src_backend, &e.location, source))
+        .map(|e| {
+            scan_entry(
+                src_backend,
+                &e.location,
+                source,
+                gate,
+                cache,
+                face_cfg.max_image_bytes,
+            )
+        })
         .collect();
 
     // 阶段 3：主线程合并结果（report 是 &mut，并发改造仅限 IO/decode 阶段）。
@@ -370,53 +537,70 @@ fn scan_source(
     log_scan_source_complete(&source.display(), valid, total_entries);
 }
 
-/// 单文件 scan：读字节 → MIME 嗅探 → decode → pHash + 灰度清晰度。
+/// 单文件 scan：读字节 → MIME 嗅探 → decode → pHash + 灰度清晰度 → EXIF 分组特征。
 /// 纯函数（不持 `&mut report`）让 rayon worker 并发安全；错误以 `(path, err)` 形式
-/// 上抛由主线程统一 `record_failure`。
+/// 上抛由主线程统一 `record_failure`。缓存命中时跳过 decode，直接取缓存的 pHash + 清晰度。
 fn scan_entry(
     src_backend: &Arc<dyn Backend>,
     location: &Location,
     source: &Location,
+    gate: TagGate<'_>,
+    cache: Option<&CullCache>,
+    max_bytes: u64,
 ) -> ScanOutcome {
-    let bytes = match read_all(src_backend, location) {
+    let bytes = match read_all(src_backend, location, max_bytes) {
         Ok(b) => b,
         Err(e) => return Some(Err((location.display(), e))),
     };
-    if !is_image(&bytes) {
+    let mime = image_mime(&bytes)?;
+    // 先于 decode：被过滤的图省掉整图解码。
+    if !gate.admits(location, src_backend, &bytes) {
+        log_scan_entry_filtered(&location.display());
         return None;
     }
-    let img = match image::load_from_memory(&bytes) {
-        Ok(i) => i.to_rgb8(),
-        Err(e) => {
-            return Some(Err((
-                location.display(),
-                io::Error::new(io::ErrorKind::InvalidData, format!("decode image: {e}")),
-            )));
-        }
+    let cache_key = cache.map(|_| content_key(&bytes));
+    let cached = cache.zip(cache_key.as_deref()).and_then(|(c, k)| c.scan(k));
+    let (hash, sharp) = match cached {
+        Some(features) => features,
+        None => match scan_features(&bytes) {
+            Ok(features) => features,
+            Err(e) => return Some(Err((location.display(), e))),
+        },
     };
-    let hash = phash(&img);
-    // grayscale 接 GenericImageView，直接传 &img 避免 DynamicImage::ImageRgb8(img.clone())
-    // 整图克隆（20 MiB 大图 peak RSS 三倍放大致批量扫 OOM 风险）。
-    let luma = image::imageops::grayscale(&img);
-    let sharp = laplacian_variance(&luma);
     // P0 §14 业务 debug：单图特征供 AI 分析分组前分布。
     log_scan_entry_ok(&location.display(), bytes.len() as u64, hash, sharp);
-    // scan 阶段产出仅含 metadata + hash + sharpness：bytes 与 img 走出作用域立即释放，
+    let content_hash = xxhash_rust::xxh3::xxh3_64(&bytes);
+    // scan 阶段产出仅含 metadata + 分组特征 + sharpness：bytes 交给 EXIF 解析后随之释放，
     // 避免整批图驻留 OOM；analyze_image 对组内成员重读+重 decode（多图组承担）。
+    let shot = Shot::parse(bytes, mime, location.path(), hash);
     Some(Ok(ScannedFile {
         src_loc: location.clone(),
         src_backend: src_backend.clone(),
         source_root: source.clone(),
-        hash,
+        shot,
         sharpness: sharp,
+        content_hash,
+        cache_key,
     }))
 }
 
-/// 组内逐图跑 4 模型 + `face_scoring`，选 `breakdown.total` 最高者；同时调
-/// `identity_cluster` 输出跨图身份簇日志（不影响选择，留作未来 per-identity 策略接入点）。
+/// decode 后算 pHash + 全图灰度清晰度。
+fn scan_features(bytes: &[u8]) -> io::Result<(u64, f32)> {
+    let img = image::load_from_memory(bytes)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("decode image: {e}")))?
+        .to_rgb8();
+    // grayscale 接 GenericImageView，直接传 &img 避免 DynamicImage::ImageRgb8(img.clone())
+    // 整图克隆（20 MiB 大图 peak RSS 三倍放大致批量扫 OOM 风险）。
+    let luma = image::imageops::grayscale(&img);
+    Ok((phash(&img), laplacian_variance(&luma)))
+}
+
+/// 组内逐图跑 4 模型（或取缓存）+ `face_scoring`，按 `face_cosine_min` 把各图人脸聚成身份后交
+/// `identity_pick::select` 选 best；返回选择结果、逐图 breakdown 与逐图人脸标注（均与
+/// `indices` 同序）。
 #[expect(
     clippy::too_many_arguments,
-    reason = "组评分接 4 detector + cfg + failures：调用单点不再拆"
+    reason = "组评分接 4 detector + 质量模型 + cfg + 缓存 + failures：调用单点不再拆"
 )]
 fn pick_best_for_group(
     indices: &[usize],
@@ -425,9 +609,11 @@ fn pick_best_for_group(
     facenet: &dyn FaceEmbedder,
     facemesh: &dyn FaceMeshDetector,
     eyestate: &dyn EyeStateClassifier,
+    quality_model: Option<&dyn QualityScorer>,
     face_cfg: &FaceConfig,
+    lookup: &mut AnalysisLookup<'_>,
     failures: &mut Vec<(String, io::Error)>,
-) -> (usize, ScoreBreakdown, Vec<ScoreBreakdown>) {
+) -> (Selection, Vec<ScoreBreakdown>, Vec<Vec<FaceMark>>) {
     // 每个 indices 项总是有 breakdown：analyze_image 失败时退化为 sharpness-only
     // 计算（face_count=0 时 score_image 仅含 w_sharpness*sharpness 项）。这样 culled
     // 项的 score 字段也用 breakdown.total，与 best 的 score_breakdown.total 同口径，
@@ -435,14 +621,49 @@ fn pick_best_for_group(
     let mut breakdowns: Vec<ScoreBreakdown> = Vec::with_capacity(indices.len());
     let mut per_image_embeddings: Vec<Vec<[f32; identity_cluster::EMBED_DIM]>> =
         Vec::with_capacity(indices.len());
+    let mut per_image_scores: Vec<Vec<face_scoring::FaceScore>> = Vec::with_capacity(indices.len());
+    let mut face_marks: Vec<Vec<FaceMark>> = Vec::with_capacity(indices.len());
+    let mut qualities: Vec<ImageQuality> = Vec::with_capacity(indices.len());
     for &i in indices {
         let item = &scanned[i];
-        let analysis = analyze_image(item, scrfd, facenet, facemesh, eyestate, face_cfg, failures);
-        let (faces, meshes, eye_states, embeddings) = match analysis {
-            Some(a) => (a.faces, a.meshes, a.eye_states, a.embeddings),
-            None => (Vec::new(), Vec::new(), Vec::new(), Vec::new()),
+        let analysis = lookup.get(i, item, || {
+            analyze_image(
+                item,
+                scrfd,
+                facenet,
+                facemesh,
+                eyestate,
+                quality_model,
+                face_cfg,
+                failures,
+            )
+        });
+        let (faces, meshes, eye_states, embeddings, image_quality) = match analysis {
+            Some(a) => (a.faces, a.meshes, a.eye_states, a.embeddings, a.quality),
+            None => Default::default(),
         };
         per_image_embeddings.push(embeddings);
+        let scores: Vec<face_scoring::FaceScore> = (0..faces.len())
+            .map(|f| {
+                face_scoring::score_face(
+                    item.sharpness,
+                    meshes.get(f).copied().unwrap_or_default(),
+                    eye_states.get(f).copied(),
+                    face_cfg,
+                )
+            })
+            .collect();
+        face_marks.push(
+            faces
+                .iter()
+                .zip(&scores)
+                .map(|(face, score)| FaceMark {
+                    bbox: face.bbox,
+                    eyes_open: score.eyes_open,
+                })
+                .collect(),
+        );
+        per_image_scores.push(scores);
         breakdowns.push(face_scoring::score_image(
             item.sharpness,
             &faces,
@@ -450,50 +671,65 @@ fn pick_best_for_group(
             &eye_states,
             face_cfg,
         ));
+        qualities.push(image_quality);
+    }
+    // 模型分只在组内可比：任一成员缺分（推理失败 / 分析失败）时整组不计该项，
+    // 否则有分者凭 w_quality × 分白拿数十分的优势。
+    if qualities.iter().any(|q| q.model_score.is_none()) {
+        for q in &mut qualities {
+            q.model_score = None;
+        }
     }
-    // TODO: per-identity 策略接入：clusters 当前仅产 debug 日志，pick_best 按全组
-    // max(total) 选 best 不区分身份；若需「同人多张里选最佳 + 不同人各自保留」语义，
-    // 在此按 clusters 分桶再于每桶内 max_by_key 取首张 → 当前 face_cosine_min 才有效。
-    let clusters =
-        identity_cluster::cluster_identities(&per_image_embeddings, face_cfg.face_cosine_min);
-    log_identity_clusters(&clusters);
+    for (breakdown, q) in breakdowns.iter_mut().zip(&qualities) {
+        *breakdown = quality::apply(*breakdown, q, face_cfg);
+    }
+    let face_ids =
+        identity_cluster::face_identities(&per_image_embeddings, face_cfg.face_cosine_min);
+    let faces: Vec<Vec<(usize, face_scoring::FaceScore)>> = face_ids
+        .into_iter()
+        .zip(per_image_scores)
+        .map(|(ids, scores)| ids.into_iter().zip(scores).collect())
+        .collect();
 
-    // 选最高 total；NaN 视为 -∞ 让 NaN total 永远输给 finite 同分 → max_by 在
-    // 全 finite 同分时 Rust 标准取末尾元素，配 `>` 严格比较保稳定（同 total 取首张
-    // 即「先扫描的更优」直觉）；NaN 同 NaN 视为 Equal，返首个 NaN。
-    // indices.len() >= 2（调用方保证）+ breakdowns 同长 → ok_or_else 兜底返
-    // 第 0 项 breakdown 防 caller-contract 失守。
-    let best_pos = breakdowns
-        .iter()
-        .enumerate()
-        .max_by(|(_, a), (_, b)| total_cmp_nan_as_neg_inf(a.total, b.total))
-        .map_or(0, |(p, _)| p);
-    let best_idx = indices[best_pos];
-    let best_breakdown = breakdowns[best_pos];
-    // P0 §14 业务 debug：组规模 + 选中分数。
-    log_pick_best(indices.len(), best_idx, best_breakdown.total);
-    (best_idx, best_breakdown, breakdowns)
+    let totals: Vec<f32> = breakdowns.iter().map(|b| b.total).collect();
+    let selection = identity_pick::select(&totals, &faces);
+    log_identity_clusters(selection.identities.len());
+    // P0 §14 业务 debug：组规模 + 选中分数 + 选择依据。
+    log_pick_best(
+        indices.len(),
+        indices[selection.best_pos],
+        breakdowns[selection.best_pos].total,
+        selection.strategy,
+    );
+    (selection, breakdowns, face_marks)
 }
 
-/// 单图 4 模型印证：按需重读字节 + 重 decode → `SCRFD` → 每脸 (`face_align` → `facenet`)
-/// + (bbox crop → `facemesh`) + (eye crop → `eyestate`)。
+/// 单图 4 模型印证：按需重读字节 + 重 decode → `SCRFD` → 整图画面质量（`quality::measure`
+/// + 可选 `QualityScorer`）+ 全部脸 `face_align` 后一批 `facenet` + 每脸 bbox crop → `facemesh`
+/// + 全部眼部 crop 一批 `eyestate`。
 ///
 /// 重读+重 decode 是 OOM 修复（scan 阶段不再缓存 `raw_bytes`/`decoded`）：仅多图组成员承担
 /// 二次开销，单图组在 caller 已跳过。
 ///
-/// `read_all`/`load_from_memory` Err 与 `SCRFD` Err 整图记 failure 返 None；
-/// 单脸 `face_align`/`facenet` Err 整脸丢弃；`facemesh`/`eyestate` Err 退化为空 mesh
-/// / 0 闭眼概率（不丢脸）。
+/// `read_all`/`load_from_memory`/`SCRFD`/`facenet` Err 整图记 failure 返 None；
+/// 单脸 `face_align` Err 整脸丢弃；`facemesh`/`eyestate` Err 退化为空 mesh
+/// / 0 闭眼概率（不丢脸）；质量模型 Err 记 failure 并退化为无模型分（不丢图）。退化时标
+/// `degraded`，结果不进缓存。
+#[expect(
+    clippy::too_many_arguments,
+    reason = "单图评分接 4 detector + 质量模型 + cfg + failures：与 pick_best_for_group 同口径"
+)]
 fn analyze_image(
     item: &ScannedFile,
     scrfd: &dyn FaceDetector,
     facenet: &dyn FaceEmbedder,
     facemesh: &dyn FaceMeshDetector,
     eyestate: &dyn EyeStateClassifier,
+    quality_model: Option<&dyn QualityScorer>,
     face_cfg: &FaceConfig,
     failures: &mut Vec<(String, io::Error)>,
 ) -> Option<ImageAnalysis> {
-    let bytes = match read_all(&item.src_backend, &item.src_loc) {
+    let bytes = match read_all(&item.src_backend, &item.src_loc, face_cfg.max_image_bytes) {
         Ok(b) => b,
         Err(e) => {
             failures.push((item.src_loc.display(), e));
@@ -510,41 +746,76 @@ fn analyze_image(
             return None;
         }
     };
-    let detections = match scrfd.detect_faces(item.src_loc.path(), &bytes) {
+    let path = item.src_loc.path();
+    let detections = match scrfd.detect_faces(path, &bytes) {
         Ok(f) => f,
         Err(e) => {
             failures.push((item.src_loc.display(), e));
             return None;
         }
     };
-    let mut analysis = ImageAnalysis {
-        faces: Vec::with_capacity(detections.len()),
-        embeddings: Vec::with_capacity(detections.len()),
-        meshes: Vec::with_capacity(detections.len()),
-        eye_states: Vec::with_capacity(detections.len()),
+    let (faces, aligned): (Vec<FaceDetection>, Vec<RgbImage>) = detections
+        .iter()
+        .filter_map(|face| {
+            Some((
+                *face,
+                face_align::align_face(&decoded, &face.landmarks_5pt).ok()?,
+            ))
+        })
+        .unzip();
+    // 同图全部脸一批嵌入：批量接口整批成败。失败与 SCRFD Err 同口径整图记 failure，
+    // 不当作无脸图评分（也不进缓存，下次重试）。
+    let embeddings = match facenet.embed_faces(path, &aligned) {
+        Ok(e) => e,
+        Err(e) => {
+            failures.push((item.src_loc.display(), e));
+            return None;
+        }
+    };
+    let mesh_results: Vec<io::Result<MeshFeatures>> = faces
+        .iter()
+        .map(|face| {
+            facemesh
+                .detect_mesh(path, &crop_face_bbox(&decoded, face))
+                .map(|m| MeshFeatures::from_mesh(&m))
+        })
+        .collect();
+    let mut degraded = mesh_results.iter().any(Result::is_err);
+    let meshes = mesh_results
+        .into_iter()
+        .map(Result::unwrap_or_default)
+        .collect();
+    let eye_states = classify_eye_pairs(
+        item,
+        &decoded,
+        &faces,
+        eyestate,
+        face_cfg.eye_crop_radius_ratio,
+    )
+    .unwrap_or_else(|_| {
+        degraded = true;
+        vec![(0.0, 0.0); faces.len()]
+    });
+    let model_score = match quality_model.map(|m| m.score_quality(path, &decoded)) {
+        Some(Ok(score)) => Some(score),
+        Some(Err(e)) => {
+            failures.push((item.src_loc.display(), e));
+            degraded = true;
+            None
+        }
+        None => None,
+    };
+    let analysis = ImageAnalysis {
+        quality: ImageQuality {
+            model_score,
+            ..quality::measure(&decoded, &detections)
+        },
+        faces,
+        embeddings,
+        meshes,
+        eye_states,
+        degraded,
     };
-    for face in &detections {
-        let Ok(aligned) = face_align::align_face(&decoded, &face.landmarks_5pt) else {
-            continue;
-        };
-        let Ok(embedding) = facenet.embed_face(item.src_loc.path(), &aligned) else {
-            continue;
-        };
-        let mesh = facemesh
-            .detect_mesh(item.src_loc.path(), &crop_face_bbox(&decoded, face))
-            .unwrap_or_default();
-        let eye_pair = classify_eye_pair(
-            item,
-            &decoded,
-            face,
-            eyestate,
-            face_cfg.eye_crop_radius_ratio,
-        );
-        analysis.faces.push(*face);
-        analysis.embeddings.push(embedding);
-        analysis.meshes.push(mesh);
-        analysis.eye_states.push(eye_pair);
-    }
     // P0 §14 ONNX 外部调用 debug：SCRFD 检测到的脸数 + 成功 embed/对齐的脸数（req=image，resp=faces）。
     log_analyze_image(
         &item.src_loc.display(),
@@ -554,25 +825,31 @@ fn analyze_image(
     Some(analysis)
 }
 
-/// 用 SCRFD 5 点的左/右眼坐标各 crop 一个方形眼区域调 EyeState，返左/右闭眼概率对。
-fn classify_eye_pair(
+/// 用 SCRFD 5 点的左/右眼坐标各 crop 一个方形眼区域，同图全部眼一批调 EyeState，
+/// 返每张脸的左/右闭眼概率对；推理失败由调用方退化为 0（视为睁眼，不丢脸）。
+fn classify_eye_pairs(
     item: &ScannedFile,
     decoded: &RgbImage,
-    face: &FaceDetection,
+    faces: &[FaceDetection],
     eyestate: &dyn EyeStateClassifier,
     eye_crop_radius_ratio: f32,
-) -> (f32, f32) {
-    let bbox_h = (face.bbox[3] - face.bbox[1]).max(1.0);
-    let radius = (bbox_h * eye_crop_radius_ratio).round();
-    let left_crop = crop_eye_around(decoded, face.landmarks_5pt[0], radius);
-    let right_crop = crop_eye_around(decoded, face.landmarks_5pt[1], radius);
-    let left = eyestate
-        .classify_eye(item.src_loc.path(), &left_crop)
-        .unwrap_or(0.0);
-    let right = eyestate
-        .classify_eye(item.src_loc.path(), &right_crop)
-        .unwrap_or(0.0);
-    (left, right)
+) -> io::Result<Vec<(f32, f32)>> {
+    let crops: Vec<RgbImage> = faces
+        .iter()
+        .flat_map(|face| {
+            let bbox_h = (face.bbox[3] - face.bbox[1]).max(1.0);
+            let radius = (bbox_h * eye_crop_radius_ratio).round();
+            [
+                crop_eye_around(decoded, face.landmarks_5pt[0], radius),
+                crop_eye_around(decoded, face.landmarks_5pt[1], radius),
+            ]
+        })
+        .collect();
+    let probs = eyestate.classify_eyes(item.src_loc.path(), &crops)?;
+    Ok(probs
+        .chunks_exact(2)
+        .map(|eyes| (eyes[0], eyes[1]))
+        .collect())
 }
 
 #[cfg(test)]
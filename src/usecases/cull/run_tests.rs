@@ -5,10 +5,14 @@ use std::sync::Arc;
 
 use super::*;
 use crate::FakeFaceDetector;
+use crate::adapters::backend::adb::{AdbBackend, AdbTarget};
 use crate::adapters::backend::factory::DefaultBackendFactory;
 use crate::adapters::backend::fake::{FakeBackend, Op};
+use crate::adapters::backend::fake_remote::FakeRemoteClient;
 use crate::adapters::backend::local::LocalBackend;
-use crate::adapters::face::fake::{FakeEyeStateClassifier, FakeFaceEmbedder, FakeFaceMeshDetector};
+use crate::adapters::face::fake::{
+    FakeEyeStateClassifier, FakeFaceEmbedder, FakeFaceMeshDetector, FakeQualityScorer,
+};
 use crate::entities::backend::Backend;
 use crate::entities::backend::factory::BackendFactory;
 use camino::Utf8PathBuf;
@@ -115,11 +119,16 @@ fn cull_empty_source_returns_ok_zero() {
         &facenet,
         &facemesh,
         &eyestate,
+        None,
         &DefaultBackendFactory,
         &[src],
         &out,
         true,
         10,
+        GroupBy::Both,
+        &TagFilter::default(),
+        None,
+        None,
     )
     .unwrap();
     assert_eq!(report.scanned, 0);
@@ -143,11 +152,16 @@ fn cull_source_inside_output_returns_err() {
         &facenet,
         &facemesh,
         &eyestate,
+        None,
         &DefaultBackendFactory,
         &[src],
         &out,
         true,
         10,
+        GroupBy::Both,
+        &TagFilter::default(),
+        None,
+        None,
     )
     .unwrap_err();
     let msg = err.to_string();
@@ -196,11 +210,16 @@ fn cull_dry_run_two_similar_images_picks_one_best() {
         &facenet,
         &facemesh,
         &eyestate,
+        None,
         &DefaultBackendFactory,
         &[src],
         &out,
         true,
         10,
+        GroupBy::Both,
+        &TagFilter::default(),
+        None,
+        None,
     )
     .unwrap();
     assert_eq!(report.scanned, 2);
@@ -219,6 +238,237 @@ fn cull_dry_run_two_similar_images_picks_one_best() {
         "got: {:?}",
         group.score_breakdown
     );
+    // 只有 a 有脸且睁眼 → 唯一身份在 a 上全员睁眼。
+    assert_eq!(group.strategy, PickStrategy::AllEyesOpen);
+    assert_eq!(group.identities.len(), 1);
+    assert!(group.identities[0].best_source.ends_with("a.png"));
+}
+
+/// 分析缓存：第二次运行推理输出全取自缓存——即使 detector 此时对两张图都报错，结果也与
+/// 首次相同；推理口径变了则缓存的推理输出作废、照常重跑（并照常报错）。
+#[test]
+fn cull_reuses_cached_analysis_without_inference() {
+    let src_dir = tempfile::tempdir().unwrap();
+    let a = src_dir.path().join("a.png");
+    let b = src_dir.path().join("b.png");
+    // 亮度不同 → 内容（缓存键）不同；pHash 对绝对亮度不敏感 → 仍同组。
+    write_png(&a, [128, 128, 128]);
+    write_png(&b, [130, 130, 130]);
+    let src = local_loc(src_dir.path().to_str().unwrap());
+    let out_dir = tempfile::tempdir().unwrap();
+    let out = local_loc(out_dir.path().to_str().unwrap());
+    let face = crate::FaceDetection {
+        bbox: [0.0, 0.0, 16.0, 16.0],
+        score: 0.9,
+        landmarks_5pt: [
+            [38.2946, 51.6963],
+            [73.5318, 51.5014],
+            [56.0252, 71.7366],
+            [41.5493, 92.3655],
+            [70.7299, 92.2041],
+        ],
+    };
+    let a_camino = camino::Utf8PathBuf::from(a.to_str().unwrap());
+    let b_camino = camino::Utf8PathBuf::from(b.to_str().unwrap());
+    let mut smile_mesh = vec![[0.0_f32; 3]; 468];
+    smile_mesh[61] = [0.0, 8.0, 0.0];
+    smile_mesh[291] = [10.0, 8.0, 0.0];
+    smile_mesh[13] = [5.0, 5.0, 0.0];
+    smile_mesh[14] = [5.0, 15.0, 0.0];
+    let facenet = FakeFaceEmbedder::new([0.0; 128]);
+    let eyestate = FakeEyeStateClassifier::new(0.0);
+    let run = |scrfd: &FakeFaceDetector, facemesh: &FakeFaceMeshDetector, cache: &mut CullCache| {
+        cull(
+            scrfd,
+            &facenet,
+            facemesh,
+            &eyestate,
+            None,
+            &DefaultBackendFactory,
+            std::slice::from_ref(&src),
+            &out,
+            true,
+            10,
+            GroupBy::Phash,
+            &TagFilter::default(),
+            None,
+            Some(cache),
+        )
+        .unwrap()
+    };
+    let mut cache = CullCache::default();
+
+    let first = run(
+        &FakeFaceDetector::new(vec![]).with_result(a_camino.clone(), vec![face]),
+        &FakeFaceMeshDetector::new(vec![[0.0; 3]; 468]).with_result(a_camino.clone(), smile_mesh),
+        &mut cache,
+    );
+    assert_eq!((first.grouped, first.failed, first.cache_hits), (1, 0, 0));
+    assert_eq!(cache.image_count(), 2);
+
+    let broken = FakeFaceDetector::new(vec![])
+        .with_error(a_camino)
+        .with_error(b_camino);
+    let blank_mesh = FakeFaceMeshDetector::new(vec![[0.0; 3]; 468]);
+    let second = run(&broken, &blank_mesh, &mut cache);
+    assert_eq!(
+        (second.grouped, second.failed, second.cache_hits),
+        (1, 0, 2)
+    );
+    let (g1, g2) = (&first.groups[0], &second.groups[0]);
+    assert_eq!(g1.best_source, g2.best_source);
+    assert!(g2.best_source.ends_with("a.png"), "got: {}", g2.best_source);
+    assert!((g1.score_breakdown.total - g2.score_breakdown.total).abs() < f32::EPSILON);
+    assert_eq!(g2.best_faces.len(), 1);
+
+    assert_eq!(cache.align_inference("other models"), 2);
+    let third = run(&broken, &blank_mesh, &mut cache);
+    assert_eq!((third.failed, third.cache_hits), (2, 0));
+}
+
+/// 眼态模型对 a 报错：本次退化为睁眼照常评分，但 a 的推理输出不进缓存；模型恢复后
+/// 重跑只有 b 命中缓存，a 重新推理。
+#[test]
+fn cull_does_not_cache_degraded_analysis() {
+    let src_dir = tempfile::tempdir().unwrap();
+    let a = src_dir.path().join("a.png");
+    let b = src_dir.path().join("b.png");
+    write_png(&a, [128, 128, 128]);
+    write_png(&b, [130, 130, 130]);
+    let src = local_loc(src_dir.path().to_str().unwrap());
+    let out_dir = tempfile::tempdir().unwrap();
+    let out = local_loc(out_dir.path().to_str().unwrap());
+    let face = crate::FaceDetection {
+        bbox: [0.0, 0.0, 16.0, 16.0],
+        score: 0.9,
+        landmarks_5pt: [
+            [38.2946, 51.6963],
+            [73.5318, 51.5014],
+            [56.0252, 71.7366],
+            [41.5493, 92.3655],
+            [70.7299, 92.2041],
+        ],
+    };
+    let a_camino = camino::Utf8PathBuf::from(a.to_str().unwrap());
+    let scrfd = FakeFaceDetector::new(vec![face]);
+    let facenet = FakeFaceEmbedder::new([0.0; 128]);
+    let facemesh = FakeFaceMeshDetector::new(vec![[0.0; 3]; 468]);
+    let run = |eyestate: &FakeEyeStateClassifier, cache: &mut CullCache| {
+        cull(
+            &scrfd,
+            &facenet,
+            &facemesh,
+            eyestate,
+            None,
+            &DefaultBackendFactory,
+            std::slice::from_ref(&src),
+            &out,
+            true,
+            10,
+            GroupBy::Phash,
+            &TagFilter::default(),
+            None,
+            Some(cache),
+        )
+        .unwrap()
+    };
+    let mut cache = CullCache::default();
+
+    let first = run(
+        &FakeEyeStateClassifier::new(0.0).with_error(a_camino),
+        &mut cache,
+    );
+    assert_eq!((first.grouped, first.failed, first.cache_hits), (1, 0, 0));
+    let second = run(&FakeEyeStateClassifier::new(0.0), &mut cache);
+    assert_eq!((second.grouped, second.cache_hits), (1, 1));
+    let third = run(&FakeEyeStateClassifier::new(0.0), &mut cache);
+    assert_eq!(third.cache_hits, 2);
+}
+
+/// 三张同图，质量模型对一张返给定分、对一张报错；返 `(report, [a, b, c] 路径)`。
+fn run_quality_group(error_on_c: bool) -> (CullReport, [std::path::PathBuf; 3]) {
+    let src_dir = tempfile::tempdir().unwrap();
+    let paths = ["a.png", "b.png", "c.png"].map(|n| src_dir.path().join(n));
+    for p in &paths {
+        write_png(p, [128, 128, 128]);
+    }
+    let src = local_loc(src_dir.path().to_str().unwrap());
+    let out_dir = tempfile::tempdir().unwrap();
+    let out = local_loc(out_dir.path().to_str().unwrap());
+    let scrfd = FakeFaceDetector::new(vec![]);
+    let facenet = FakeFaceEmbedder::new([0.0; 128]);
+    let facemesh = FakeFaceMeshDetector::new(vec![[0.0; 3]; 468]);
+    let eyestate = FakeEyeStateClassifier::new(0.0);
+    let mut quality_model =
+        FakeQualityScorer::new(3.0).with_result(paths[1].to_str().unwrap(), 8.0);
+    if error_on_c {
+        quality_model = quality_model.with_error(paths[2].to_str().unwrap());
+    }
+
+    let report = cull(
+        &scrfd,
+        &facenet,
+        &facemesh,
+        &eyestate,
+        Some(&quality_model),
+        &DefaultBackendFactory,
+        &[src],
+        &out,
+        true,
+        10,
+        GroupBy::Both,
+        &TagFilter::default(),
+        None,
+        None,
+    )
+    .unwrap();
+    (report, paths)
+}
+
+/// 三张同图其余各项同分：质量模型分决定 best。
+#[test]
+fn cull_quality_model_score_decides_best_among_equal_frames() {
+    let (report, _) = run_quality_group(false);
+    assert_eq!((report.failed, report.grouped), (0, 1));
+    let group = &report.groups[0];
+    assert!(
+        group.best_source.ends_with("b.png"),
+        "got: {}",
+        group.best_source
+    );
+    let w = config().backend.face.w_quality;
+    assert!(
+        (group.score_breakdown.quality_bonus - w * 8.0).abs() < 1e-3,
+        "got: {:?}",
+        group.score_breakdown
+    );
+    for e in &group.culled {
+        assert!(
+            (e.score_breakdown.quality_bonus - w * 3.0).abs() < 1e-3,
+            "{e:?}"
+        );
+    }
+}
+
+/// 模型对组内一张图报错：该错记入 failed / errors（图仍参与评分），且整组不计
+/// `quality_bonus`——缺分者不因推理失败而输给有分者。
+#[test]
+fn cull_quality_model_error_drops_quality_term_for_whole_group() {
+    let (report, paths) = run_quality_group(true);
+    assert_eq!((report.failed, report.grouped), (1, 1));
+    assert!(
+        report.errors[0]
+            .path
+            .ends_with(paths[2].file_name().unwrap().to_str().unwrap()),
+        "{:?}",
+        report.errors
+    );
+    let group = &report.groups[0];
+    assert_eq!(group.culled.len(), 2, "报错的图仍参与分组评分");
+    assert!(group.score_breakdown.quality_bonus.abs() < f32::EPSILON);
+    for e in &group.culled {
+        assert!(e.score_breakdown.quality_bonus.abs() < f32::EPSILON, "{e:?}");
+    }
 }
 
 #[test]
@@ -237,14 +487,19 @@ fn cull_scan_skips_non_image_file() {
         &facenet,
         &facemesh,
         &eyestate,
+        None,
         &DefaultBackendFactory,
         &[src],
         &out,
         true,
         10,
+        GroupBy::Both,
+        &TagFilter::default(),
+        None,
+        None,
     )
     .unwrap();
-    // 非图按 walker 触达计入 scanned，但 is_image 早返不进 scanned vec；失败=0。
+    // 非图按 walker 触达计入 scanned，但 image_mime 嗅探失败早返不进 scanned vec；失败=0。
     assert_eq!(report.scanned, 1, "walker 触达 1 文件即 scanned=1");
     assert_eq!(report.failed, 0);
 }
@@ -265,11 +520,16 @@ fn cull_returns_err_when_factory_rejects_output() {
         &facenet,
         &facemesh,
         &eyestate,
+        None,
         &factory,
         &[src],
         &out,
         true,
         10,
+        GroupBy::Both,
+        &TagFilter::default(),
+        None,
+        None,
     )
     .unwrap_err();
     assert!(err.to_string().contains("no fake backend"), "got: {err}");
@@ -295,11 +555,16 @@ fn cull_returns_err_when_factory_rejects_source() {
         &facenet,
         &facemesh,
         &eyestate,
+        None,
         &factory,
         &[src],
         &out,
         true,
         10,
+        GroupBy::Both,
+        &TagFilter::default(),
+        None,
+        None,
     )
     .unwrap_err();
     assert!(err.to_string().contains("no fake backend"), "got: {err}");
@@ -325,11 +590,16 @@ fn cull_mkdir_p_failure_propagates_when_not_dry_run() {
         &facenet,
         &facemesh,
         &eyestate,
+        None,
         &factory,
         &[src],
         &out,
         false, // 非 dry-run → 触发 mkdir_p
         10,
+        GroupBy::Both,
+        &TagFilter::default(),
+        None,
+        None,
     )
     .unwrap_err();
     assert!(err.to_string().contains("injected"), "got: {err}");
@@ -352,11 +622,16 @@ fn cull_skips_single_image_group() {
         &facenet,
         &facemesh,
         &eyestate,
+        None,
         &DefaultBackendFactory,
         &[src],
         &out,
         true,
         10,
+        GroupBy::Both,
+        &TagFilter::default(),
+        None,
+        None,
     )
     .unwrap();
     assert_eq!(report.scanned, 1);
@@ -384,11 +659,16 @@ fn cull_records_failure_when_walk_yields_err() {
         &facenet,
         &facemesh,
         &eyestate,
+        None,
         &factory,
         &[src],
         &out,
         true,
         10,
+        GroupBy::Both,
+        &TagFilter::default(),
+        None,
+        None,
     )
     .unwrap();
     assert_eq!(report.failed, 1, "walk Err counted as failure");
@@ -418,11 +698,16 @@ fn cull_records_failure_when_open_read_errs() {
         &facenet,
         &facemesh,
         &eyestate,
+        None,
         &factory,
         &[src],
         &out,
         true,
         10,
+        GroupBy::Both,
+        &TagFilter::default(),
+        None,
+        None,
     )
     .unwrap();
     assert_eq!(report.failed, 1);
@@ -452,11 +737,16 @@ fn cull_skips_entry_under_output_prefix() {
         &facenet,
         &facemesh,
         &eyestate,
+        None,
         &DefaultBackendFactory,
         &[src],
         &out,
         true,
         10,
+        GroupBy::Both,
+        &TagFilter::default(),
+        None,
+        None,
     )
     .unwrap();
     // nested.png 在 output prefix 下被过滤
@@ -486,11 +776,16 @@ fn cull_records_failure_when_scrfd_detect_errs_on_all() {
         &facenet,
         &facemesh,
         &eyestate,
+        None,
         &DefaultBackendFactory,
         &[src],
         &out,
         true,
         10,
+        GroupBy::Both,
+        &TagFilter::default(),
+        None,
+        None,
     )
     .unwrap();
     assert_eq!(report.scanned, 2);
@@ -534,11 +829,16 @@ fn cull_records_failure_when_oversize_image_skipped() {
         &facenet,
         &facemesh,
         &eyestate,
+        None,
         &DefaultBackendFactory,
         &[src],
         &out,
         true,
         10,
+        GroupBy::Both,
+        &TagFilter::default(),
+        None,
+        None,
     )
     .unwrap();
     // walker 触达 1 文件后 size 超阈值，按口径仍计入 scanned（含 OOM 跳过项）；failed=1。
@@ -594,11 +894,16 @@ fn cull_records_failure_when_write_group_errs() {
         &facenet,
         &facemesh,
         &eyestate,
+        None,
         &factory,
         &[src],
         &out,
         false, // 非 dry-run 触发 group_writer
         10,
+        GroupBy::Both,
+        &TagFilter::default(),
+        None,
+        None,
     )
     .unwrap();
     assert!(report.failed >= 1, "write_group 失败计入 failed");
@@ -628,11 +933,16 @@ fn cull_records_failure_on_corrupt_image() {
         &facenet,
         &facemesh,
         &eyestate,
+        None,
         &DefaultBackendFactory,
         &[src],
         &out,
         true,
         10,
+        GroupBy::Both,
+        &TagFilter::default(),
+        None,
+        None,
     )
     .unwrap();
     assert_eq!(report.failed, 1);
@@ -720,11 +1030,16 @@ fn cull_skips_face_when_align_fit_is_singular() {
         &facenet,
         &facemesh,
         &eyestate,
+        None,
         &DefaultBackendFactory,
         &[src],
         &out,
         true,
         10,
+        GroupBy::Both,
+        &TagFilter::default(),
+        None,
+        None,
     )
     .unwrap();
     assert_eq!(report.scanned, 2);
@@ -754,11 +1069,16 @@ fn cull_records_failure_when_read_to_end_errs() {
         &facenet,
         &facemesh,
         &eyestate,
+        None,
         &factory,
         &[src],
         &out,
         true,
         10,
+        GroupBy::Both,
+        &TagFilter::default(),
+        None,
+        None,
     )
     .unwrap();
     assert_eq!(report.failed, 1);
@@ -766,8 +1086,8 @@ fn cull_records_failure_when_read_to_end_errs() {
 }
 
 #[test]
-fn cull_skips_face_when_embedder_returns_err() {
-    // SCRFD valid 5 点 + facenet.with_error → align ok → embed_face Err → continue
+fn cull_records_failure_when_embedder_returns_err() {
+    // SCRFD valid 5 点 + facenet.with_error → align ok → embed_faces Err → 整图记 failure
     let src_dir = tempfile::tempdir().unwrap();
     let a = src_dir.path().join("a.png");
     let b = src_dir.path().join("b.png");
@@ -802,13 +1122,261 @@ fn cull_skips_face_when_embedder_returns_err() {
         &facenet,
         &facemesh,
         &eyestate,
+        None,
         &DefaultBackendFactory,
         &[src],
         &out,
         true,
         10,
+        GroupBy::Both,
+        &TagFilter::default(),
+        None,
+        None,
     )
     .unwrap();
     assert_eq!(report.scanned, 2);
     assert_eq!(report.best_count, 1);
+    assert_eq!(report.failed, 2, "errors: {:?}", report.errors);
+    assert!(report.errors[0].message.contains("FakeFaceEmbedder"));
+}
+
+/// `--min-rating`：sidecar 只给 a.png 评 3 星 → b.png 被过滤 → 只剩单图组，不分组。
+#[test]
+fn cull_min_rating_excludes_unrated_images_from_grouping() {
+    fn rate_all(_: &Location, _: &Arc<dyn Backend>) -> Option<Tags> {
+        Some(Tags {
+            rating: Some(5),
+            ..Tags::default()
+        })
+    }
+    fn rate_a_only(loc: &Location, _: &Arc<dyn Backend>) -> Option<Tags> {
+        loc.path().as_str().ends_with("a.png").then_some(Tags {
+            rating: Some(3),
+            ..Tags::default()
+        })
+    }
+    let src_dir = tempfile::tempdir().unwrap();
+    write_png(&src_dir.path().join("a.png"), [128, 128, 128]);
+    write_png(&src_dir.path().join("b.png"), [128, 128, 128]);
+    let src = local_loc(src_dir.path().to_str().unwrap());
+    let out_dir = tempfile::tempdir().unwrap();
+    let out = local_loc(out_dir.path().to_str().unwrap());
+    let scrfd = FakeFaceDetector::new(vec![]);
+    let facenet = FakeFaceEmbedder::new([0.0; 128]);
+    let facemesh = FakeFaceMeshDetector::new(vec![[0.0; 3]; 468]);
+    let eyestate = FakeEyeStateClassifier::new(0.0);
+    let filter = TagFilter {
+        min_rating: Some(1),
+        label: None,
+        ..TagFilter::default()
+    };
+    let run = |tags: Option<TagProvider>| {
+        cull(
+            &scrfd,
+            &facenet,
+            &facemesh,
+            &eyestate,
+            None,
+            &DefaultBackendFactory,
+            std::slice::from_ref(&src),
+            &out,
+            true,
+            10,
+            GroupBy::Both,
+            &filter,
+            tags,
+            None,
+        )
+        .unwrap()
+    };
+    let report = run(Some(rate_a_only));
+    assert_eq!(report.scanned, 2);
+    assert_eq!(report.grouped, 0);
+    // 无 sidecar 且无内嵌 XMP → 两张都未评级 → 都被过滤。
+    assert_eq!(run(None).grouped, 0);
+    // 两张都达标 → 照常分组。
+    assert_eq!(run(Some(rate_all)).grouped, 1);
+}
+
+/// 文件名带拍摄时间的同图：`names` 决定时间，`group_by` 决定链接规则。
+fn cull_named_pair(names: [&str; 2], group_by: GroupBy) -> CullReport {
+    let src_dir = tempfile::tempdir().unwrap();
+    for name in names {
+        write_png(&src_dir.path().join(name), [60, 60, 60]);
+    }
+    let src = local_loc(src_dir.path().to_str().unwrap());
+    let out_dir = tempfile::tempdir().unwrap();
+    let out = local_loc(out_dir.path().to_str().unwrap());
+    let scrfd = FakeFaceDetector::new(vec![]);
+    let facenet = FakeFaceEmbedder::new([0.0; 128]);
+    let facemesh = FakeFaceMeshDetector::new(vec![[0.0; 3]; 468]);
+    let eyestate = FakeEyeStateClassifier::new(0.0);
+    cull(
+        &scrfd,
+        &facenet,
+        &facemesh,
+        &eyestate,
+        None,
+        &DefaultBackendFactory,
+        &[src],
+        &out,
+        true,
+        10,
+        group_by,
+        &TagFilter::default(),
+        None,
+        None,
+    )
+    .unwrap()
+}
+
+/// 1 秒内两帧：time 与 both 模式均按时间窗成组。
+#[test]
+fn cull_group_by_time_links_shots_within_burst_window() {
+    let names = ["IMG_20240501_120000.png", "IMG_20240501_120001.png"];
+    let report = cull_named_pair(names, GroupBy::Time);
+    assert_eq!(report.grouped, 1);
+    assert_eq!(report.groups[0].reasons, [GroupReason::TimeWindow]);
+    // 每对只记最可信的依据：时间窗命中后不再记 pHash。
+    let report = cull_named_pair(names, GroupBy::Both);
+    assert_eq!(report.groups[0].reasons, [GroupReason::TimeWindow]);
+}
+
+/// 相隔两天的同图：both 按 `phash_max_gap_secs` 否决，phash 模式照常成组。
+#[test]
+fn cull_group_by_both_splits_similar_shots_days_apart() {
+    let names = ["IMG_20240501_120000.png", "IMG_20240503_120000.png"];
+    assert_eq!(cull_named_pair(names, GroupBy::Both).grouped, 0);
+    assert_eq!(cull_named_pair(names, GroupBy::Time).grouped, 0);
+    let report = cull_named_pair(names, GroupBy::Phash);
+    assert_eq!(report.grouped, 1);
+    assert_eq!(report.groups[0].reasons, [GroupReason::Phash]);
+}
+
+fn adb_loc(path: &str) -> Location {
+    Location::Adb {
+        serial: Some("EMULATOR5554".into()),
+        path: Utf8PathBuf::from(path),
+    }
+}
+
+/// 两张同图放进 `FakeRemoteClient<AdbTarget>` 的 `/sdcard/DCIM`，返回 client + 相册路径。
+fn adb_client_with_burst() -> Arc<FakeRemoteClient<AdbTarget>> {
+    let tmp = tempfile::tempdir().unwrap();
+    let png = tmp.path().join("burst.png");
+    write_png(&png, [90, 90, 90]);
+    let bytes = std::fs::read(&png).unwrap();
+    let client = Arc::new(FakeRemoteClient::<AdbTarget>::new());
+    client.add_dir("/sdcard/DCIM");
+    client.add_file("/sdcard/DCIM/a.png", bytes.clone());
+    client.add_file("/sdcard/DCIM/b.png", bytes);
+    client
+}
+
+fn cull_with_factory(factory: &dyn BackendFactory, src: Location, out: &Location) -> CullReport {
+    let scrfd = FakeFaceDetector::new(vec![]);
+    let facenet = FakeFaceEmbedder::new([0.0; 128]);
+    let facemesh = FakeFaceMeshDetector::new(vec![[0.0; 3]; 468]);
+    let eyestate = FakeEyeStateClassifier::new(0.0);
+    cull(
+        &scrfd,
+        &facenet,
+        &facemesh,
+        &eyestate,
+        None,
+        factory,
+        &[src],
+        out,
+        false,
+        10,
+        GroupBy::Both,
+        &TagFilter::default(),
+        None,
+        None,
+    )
+    .unwrap()
+}
+
+/// adb 源 → 本地 output：经 `open_read` 读字节评分，best 流式复制、劣质副本复制后删远端源。
+#[test]
+fn cull_scans_adb_source_and_writes_group_to_local_output() {
+    let client = adb_client_with_burst();
+    let mut factory = MapFactory::new();
+    factory.insert("adb", AdbBackend::arc_with_client(client.clone()));
+    let out_dir = tempfile::tempdir().unwrap();
+    let out = local_loc(out_dir.path().to_str().unwrap());
+
+    let report = cull_with_factory(&factory, adb_loc("/sdcard/DCIM"), &out);
+
+    assert_eq!((report.scanned, report.grouped, report.moved), (2, 1, 1));
+    assert_eq!(report.failed, 0, "errors: {:?}", report.errors);
+    let group = &report.groups[0];
+    let best_name = group.best_source.rsplit('/').next().unwrap();
+    let culled_name = if best_name == "a.png" {
+        "b.png"
+    } else {
+        "a.png"
+    };
+    let group_dir = out_dir.path().join("group-001");
+    assert!(group_dir.join(format!("BEST_{best_name}")).is_file());
+    assert!(group_dir.join(culled_name).is_file());
+    assert!(
+        client
+            .get_file(&format!("/sdcard/DCIM/{best_name}"))
+            .is_some()
+    );
+    assert!(
+        client
+            .get_file(&format!("/sdcard/DCIM/{culled_name}"))
+            .is_none()
+    );
+}
+
+/// adb 源与 output 同设备：group 目录与文件全部写回远端。
+#[test]
+fn cull_writes_group_back_to_same_adb_device() {
+    let client = adb_client_with_burst();
+    let mut factory = MapFactory::new();
+    factory.insert("adb", AdbBackend::arc_with_client(client.clone()));
+
+    let report = cull_with_factory(
+        &factory,
+        adb_loc("/sdcard/DCIM"),
+        &adb_loc("/sdcard/Culled"),
+    );
+
+    assert_eq!((report.grouped, report.moved, report.failed), (1, 1, 0));
+    let group = &report.groups[0];
+    let best_name = group.best_source.rsplit('/').next().unwrap();
+    let culled_name = if best_name == "a.png" {
+        "b.png"
+    } else {
+        "a.png"
+    };
+    assert!(
+        client
+            .get_file(&format!("/sdcard/Culled/group-001/BEST_{best_name}"))
+            .is_some()
+    );
+    assert!(
+        client
+            .get_file(&format!("/sdcard/Culled/group-001/{culled_name}"))
+            .is_some()
+    );
+    assert!(
+        client
+            .get_file(&format!("/sdcard/DCIM/{culled_name}"))
+            .is_none()
+    );
+}
+
+#[test]
+fn read_all_rejects_bytes_beyond_limit() {
+    let client = Arc::new(FakeRemoteClient::<AdbTarget>::new());
+    client.add_file("/sdcard/x.png", b"DATA".to_vec());
+    let backend = AdbBackend::arc_with_client(client);
+    let loc = adb_loc("/sdcard/x.png");
+    assert_eq!(read_all(&backend, &loc, 4).unwrap(), b"DATA");
+    let err = read_all(&backend, &loc, 3).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
 }
@@ -4,16 +4,16 @@
 use std::io::{self, Read};
 use std::sync::Arc;
 
-use tracing::{debug, error};
+use tracing::{debug, error, info};
 
-use super::report::CullReport;
+use super::report::{CullReport, PickStrategy};
 use crate::entities::backend::Backend;
 use crate::entities::common::{self, canonical_prefix, under_prefix};
 use crate::entities::uri::Location;
 use crate::usecases::report::ReportError;
 
 pub(super) const FEATURE: &str = "cull";
-const MIME_SNIFF_BYTES: usize = 256;
+pub(crate) const MIME_SNIFF_BYTES: usize = 256;
 
 /// `cull` 末尾的 debug! summary 抽独立 helper：release 默认不订阅 debug 级别，
 /// 内部 closure-form micro-region 永 0-hit，整 fn `coverage(off)` 让计数不漂移。
@@ -29,20 +29,35 @@ pub(super) fn log_cull_summary(report: &CullReport, dry_run: bool) {
         culled_count = report.culled_count,
         moved = report.moved,
         dropped_blurry = report.dropped_blurry,
+        cache_hits = report.cache_hits,
         failed = report.failed,
         dry_run,
         "cull summary"
     );
 }
 
+/// 推理口径（模型路径 / 检测参数）变了：缓存里的旧推理输出作废，对应图本次重跑推理。
+#[cfg_attr(coverage_nightly, coverage(off))]
+pub(super) fn log_inference_cache_reset(dropped: usize) {
+    if dropped > 0 {
+        info!(
+            feature = FEATURE,
+            operation = "cache_reset",
+            result = "ok",
+            dropped,
+            "model settings changed; cached cull inference discarded"
+        );
+    }
+}
+
 /// 同 `log_cull_summary` 套路：身份簇 debug! 输出抽独立 fn，release 不订阅 → 0-hit。
 #[cfg_attr(coverage_nightly, coverage(off))]
-pub(super) fn log_identity_clusters(clusters: &[Vec<usize>]) {
+pub(super) fn log_identity_clusters(identity_count: usize) {
     debug!(
         feature = FEATURE,
         operation = "identity_cluster",
         result = "ok",
-        cluster_count = clusters.len(),
+        cluster_count = identity_count,
         "identity clusters computed"
     );
 }
@@ -88,6 +103,18 @@ pub(super) fn log_scan_entry_ok(source: &str, size: u64, hash: u64, sharpness: f
     );
 }
 
+/// `scan_entry` 被 `--min-rating` / `--label` 过滤：不参与分组、原地不动。
+#[cfg_attr(coverage_nightly, coverage(off))]
+pub(super) fn log_scan_entry_filtered(source: &str) {
+    debug!(
+        feature = FEATURE,
+        operation = "scan_entry",
+        result = "skipped_filtered",
+        source = %source,
+        "cull scan entry does not match --min-rating / --label"
+    );
+}
+
 /// `analyze_image` 单图：SCRFD detections + 成功 embed/对齐的 face 数（外部调用 req/resp）。
 #[cfg_attr(coverage_nightly, coverage(off))]
 pub(super) fn log_analyze_image(source: &str, detections: usize, embedded: usize) {
@@ -104,7 +131,12 @@ pub(super) fn log_analyze_image(source: &str, detections: usize, embedded: usize
 
 /// `pick_best_for_group` 完成：组规模 + 选中的 best 与最高分。
 #[cfg_attr(coverage_nightly, coverage(off))]
-pub(super) fn log_pick_best(group_size: usize, best_idx: usize, best_total: f32) {
+pub(super) fn log_pick_best(
+    group_size: usize,
+    best_idx: usize,
+    best_total: f32,
+    strategy: PickStrategy,
+) {
     debug!(
         feature = FEATURE,
         operation = "pick_best",
@@ -112,6 +144,7 @@ pub(super) fn log_pick_best(group_size: usize, best_idx: usize, best_total: f32)
         group_size,
         best_idx,
         best_total,
+        ?strategy,
         "cull pick best"
     );
 }
@@ -156,16 +189,35 @@ pub(super) fn ensure_sources_outside_output(
     Ok(())
 }
 
-pub(super) fn read_all(backend: &Arc<dyn Backend>, loc: &Location) -> io::Result<Vec<u8>> {
-    let mut reader = backend.open_read(loc)?;
+/// 整读单图字节，至多 `limit` 字节：远端 walker 报的 size 可能缺失（0）或与实际不符，
+/// 多读 1 字节即判超限，不让单图把内存顶穿。
+pub(super) fn read_all(
+    backend: &Arc<dyn Backend>,
+    loc: &Location,
+    limit: u64,
+) -> io::Result<Vec<u8>> {
+    let reader = backend.open_read(loc)?;
     let mut buf = Vec::new();
-    reader.read_to_end(&mut buf)?;
+    reader.take(limit.saturating_add(1)).read_to_end(&mut buf)?;
+    if buf.len() as u64 > limit {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("cull: file exceeds backend.face.max_image_bytes={limit}"),
+        ));
+    }
     Ok(buf)
 }
 
-pub(super) fn is_image(bytes: &[u8]) -> bool {
+pub(crate) fn is_image(bytes: &[u8]) -> bool {
+    image_mime(bytes).is_some()
+}
+
+/// 头部 magic bytes 嗅探出的图片 MIME；非图片返 None。
+pub(super) fn image_mime(bytes: &[u8]) -> Option<&'static str> {
     let head_len = MIME_SNIFF_BYTES.min(bytes.len());
-    infer::get(&bytes[..head_len]).is_some_and(|t| t.mime_type().starts_with("image/"))
+    infer::get(&bytes[..head_len])
+        .map(|t| t.mime_type())
+        .filter(|m| m.starts_with("image/"))
 }
 
 pub(super) fn record_failure(report: &mut CullReport, path: String, e: &io::Error) {
@@ -0,0 +1,173 @@
+//! `{event}` 占位符的事件聚类：copy / rename 渲染前对整个源 `Index` 预扫一遍。
+//!
+//! - 媒体文件按判定时间（与 `{date}` 同源）排序，相邻两个间隔超过
+//!   `copy.event_gap_minutes` 即切分新事件
+//! - `copy.event_radius_km > 0` 时，当前文件与本事件最近一个带 GPS 的文件相距超过
+//!   该半径同样切分；缺 GPS 的文件只看时间
+//! - 事件名 = 首个文件的本地日期 + 成员里最常见的 XMP 城市（`2024-05-01_Hangzhou`），
+//!   无城市时为当天第 N 个事件（`2024-05-01_event3`）；同名事件追加 `_N`
+//! - 非媒体文件不参与聚类，`{event}` 对其退回日期
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::UNIX_EPOCH;
+
+use camino::Utf8PathBuf;
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
+use tracing::debug;
+
+use crate::entities::file_index::Index;
+use crate::usecases::config::config;
+
+const FEATURE_EVENT: &str = "event";
+
+/// 地球平均半径（km），haversine 距离用。
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// 参与聚类的单个文件。
+#[derive(Debug, Clone)]
+struct Point {
+    path: Utf8PathBuf,
+    secs: i64,
+    position: Option<(f64, f64)>,
+    city: Option<String>,
+}
+
+/// 模板是否引用 `{event}`；按前缀匹配以覆盖 `{event:lower}` / `{event|misc}`。
+pub(crate) fn uses_event(template: &str) -> bool {
+    template.contains("{event")
+}
+
+/// 对 `index` 内全部媒体文件聚类并注入事件名；`offset` 决定事件日期按哪个时区切分。
+pub(crate) fn assign_events(index: &mut Index, offset: FixedOffset) {
+    let valid_secs = config().exif.valid_date_time_secs;
+    let mut points: Vec<Point> = index
+        .files()
+        .iter()
+        .filter(|(_, info)| info.is_media())
+        .map(|(path, info)| {
+            let time = info.create_time(valid_secs, offset);
+            let exif = info.exif_ref();
+            Point {
+                path: path.clone(),
+                secs: time
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |d| i64::try_from(d.as_secs()).unwrap_or(i64::MAX)),
+                position: exif.and_then(|e| e.gps_position()),
+                city: exif.and_then(|e| e.city()).map(str::to_string),
+            }
+        })
+        .collect();
+    points.sort_by(|a, b| (a.secs, &a.path).cmp(&(b.secs, &b.path)));
+
+    let copy = &config().copy;
+    let max_gap_secs = i64::from(copy.event_gap_minutes) * 60;
+    let events = cluster(&points, max_gap_secs, copy.event_radius_km);
+    let names = name_events(&points, &events, offset);
+    let event_count = events.len();
+    let mut assigned = HashMap::with_capacity(points.len());
+    for (members, name) in events.into_iter().zip(names) {
+        for i in members {
+            assigned.insert(points[i].path.clone(), name.clone());
+        }
+    }
+    debug!(
+        feature = FEATURE_EVENT,
+        operation = "cluster",
+        result = "ok",
+        files = points.len(),
+        events = event_count,
+        max_gap_secs,
+        radius_km = copy.event_radius_km,
+        "media files clustered into events"
+    );
+    index.set_events(assigned);
+}
+
+// `points` 已按时间排序；返回每个事件的成员下标（保持时间序）。
+fn cluster(points: &[Point], max_gap_secs: i64, radius_km: f64) -> Vec<Vec<usize>> {
+    let mut events: Vec<Vec<usize>> = Vec::new();
+    let mut last_position: Option<(f64, f64)> = None;
+    for (i, p) in points.iter().enumerate() {
+        let split = match events.last().and_then(|e| e.last()) {
+            None => true,
+            Some(&prev) => {
+                let too_late = p.secs.saturating_sub(points[prev].secs) > max_gap_secs;
+                let too_far = radius_km > 0.0
+                    && last_position
+                        .zip(p.position)
+                        .is_some_and(|(a, b)| haversine_km(a, b) > radius_km);
+                too_late || too_far
+            }
+        };
+        if split {
+            events.push(Vec::new());
+            last_position = None;
+        }
+        if let Some(event) = events.last_mut() {
+            event.push(i);
+        }
+        last_position = p.position.or(last_position);
+    }
+    events
+}
+
+// 大圆距离；GPS 精度远低于球面与椭球差，haversine 足够。
+fn haversine_km((lat1, lon1): (f64, f64), (lat2, lon2): (f64, f64)) -> f64 {
+    let (phi1, phi2) = (lat1.to_radians(), lat2.to_radians());
+    let d_phi = (lat2 - lat1).to_radians();
+    let d_lambda = (lon2 - lon1).to_radians();
+    let a = (d_phi / 2.0).sin().powi(2) + phi1.cos() * phi2.cos() * (d_lambda / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+}
+
+// 按事件先后命名：当天序号对所有事件计数，城市名撞车时追加该序号保证唯一。
+fn name_events(points: &[Point], events: &[Vec<usize>], offset: FixedOffset) -> Vec<String> {
+    let mut per_day: HashMap<NaiveDate, usize> = HashMap::new();
+    let mut used: HashSet<String> = HashSet::new();
+    events
+        .iter()
+        .map(|members| {
+            let first = &points[members[0]];
+            let date = DateTime::<Utc>::from_timestamp(first.secs, 0)
+                .unwrap_or_default()
+                .with_timezone(&offset)
+                .date_naive();
+            let ordinal = per_day.entry(date).or_default();
+            *ordinal += 1;
+            let day = date.format("%Y-%m-%d");
+            let name = match dominant_city(points, members) {
+                Some(city) if used.contains(&format!("{day}_{city}")) => {
+                    format!("{day}_{city}_{ordinal}")
+                }
+                Some(city) => format!("{day}_{city}"),
+                None => format!("{day}_event{ordinal}"),
+            };
+            used.insert(name.clone());
+            name
+        })
+        .collect()
+}
+
+// 成员里出现最多的城市；并列取字典序最小者，保证同输入同输出。
+fn dominant_city<'a>(points: &'a [Point], members: &[usize]) -> Option<&'a str> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for &i in members {
+        if let Some(city) = points[i]
+            .city
+            .as_deref()
+            .map(str::trim)
+            .filter(|c| !c.is_empty())
+        {
+            *counts.entry(city).or_default() += 1;
+        }
+    }
+    let best = counts.values().copied().max()?;
+    counts
+        .into_iter()
+        .find(|&(_, n)| n == best)
+        .map(|(city, _)| city)
+}
+
+#[cfg(test)]
+#[path = "event_tests.rs"]
+mod tests;
@@ -0,0 +1,107 @@
+use std::path::Path;
+
+use tempfile::tempdir;
+
+use super::*;
+use crate::entities::exif::Exif;
+use crate::entities::file_info::Info;
+use crate::entities::test_common as tc;
+
+// 2024-05-01 09:00:00 +08:00
+const MAY_DAY_9AM: u64 = 1_714_525_200;
+
+const HANGZHOU: (f64, f64) = (30.26, 120.155);
+const SHANGHAI: (f64, f64) = (31.23, 121.47);
+
+fn offset() -> FixedOffset {
+    FixedOffset::east_opt(8 * 3600).unwrap()
+}
+
+fn point(secs: i64, position: Option<(f64, f64)>, city: Option<&str>) -> Point {
+    Point {
+        path: Utf8PathBuf::from(format!("/p/{secs}.jpg")),
+        secs,
+        position,
+        city: city.map(str::to_string),
+    }
+}
+
+fn media(dir: &Path, name: &str, exif: Exif) -> Info {
+    let png = tc::copy_png_to(dir, name).unwrap();
+    let mut info = Info::from(png.to_str().unwrap()).unwrap();
+    info.set_exif(exif);
+    info
+}
+
+/// 同一天上午两张 + 下午一张（间隔 > 2h）+ 次日一张：上午带城市的成 `_Hangzhou`，
+/// 其余无城市按当天序号命名；非媒体文件不注入。
+#[test]
+fn assign_events_names_by_city_or_daily_ordinal() {
+    let dir = tempdir().unwrap();
+    let shot = |secs: u64| Exif::with_mime("image/png").with_date_time_original(secs);
+    let mut index = Index::new();
+    index.add(media(
+        dir.path(),
+        "a.png",
+        shot(MAY_DAY_9AM).with_position_city(HANGZHOU.0, HANGZHOU.1, Some("Hangzhou")),
+    ));
+    index.add(media(dir.path(), "b.png", shot(MAY_DAY_9AM + 1800)));
+    index.add(media(dir.path(), "c.png", shot(MAY_DAY_9AM + 6 * 3600)));
+    index.add(media(dir.path(), "d.png", shot(MAY_DAY_9AM + 24 * 3600)));
+    index.add(media(dir.path(), "e.png", Exif::with_mime("text/plain")));
+
+    assign_events(&mut index, offset());
+
+    let event_of = |name: &str| {
+        let info = index.files().values().find(|i| i.full_path.ends_with(name));
+        info.unwrap().event().map(str::to_string)
+    };
+    assert_eq!(event_of("a.png").as_deref(), Some("2024-05-01_Hangzhou"));
+    assert_eq!(event_of("b.png").as_deref(), Some("2024-05-01_Hangzhou"));
+    assert_eq!(event_of("c.png").as_deref(), Some("2024-05-01_event2"));
+    assert_eq!(event_of("d.png").as_deref(), Some("2024-05-02_event1"));
+    assert_eq!(event_of("e.png"), None);
+}
+
+/// 半径启用时，时间相近但相距超半径的两张切分；缺 GPS 的一张沿用前一事件。
+#[test]
+fn cluster_splits_on_time_gap_and_gps_radius() {
+    let points = [
+        point(0, Some(HANGZHOU), None),
+        point(600, None, None),
+        point(1200, Some(SHANGHAI), None),
+        point(1200 + 7201, Some(SHANGHAI), None),
+    ];
+    assert_eq!(cluster(&points, 7200, 0.0), [vec![0, 1, 2], vec![3]]);
+    assert_eq!(cluster(&points, 7200, 50.0), [vec![0, 1], vec![2], vec![3]]);
+    assert!(cluster(&[], 7200, 50.0).is_empty());
+}
+
+#[test]
+fn haversine_km_matches_known_distance() {
+    // 杭州—上海直线约 165 km。
+    let d = haversine_km(HANGZHOU, SHANGHAI);
+    assert!((160.0..170.0).contains(&d), "{d}");
+    assert!(haversine_km(HANGZHOU, HANGZHOU).abs() < 1e-9);
+}
+
+/// 城市取众数、并列取字典序；同日同城的第二个事件追加当天序号。
+#[test]
+fn name_events_disambiguates_repeated_city_on_same_day() {
+    let base = i64::try_from(MAY_DAY_9AM).unwrap();
+    let points = [
+        point(base, None, Some("Suzhou")),
+        point(base + 1, None, Some("Hangzhou")),
+        point(base + 10_000, None, Some("Hangzhou")),
+        point(base + 20_000, None, Some(" ")),
+    ];
+    let events = [vec![0, 1], vec![2], vec![3]];
+    assert_eq!(
+        name_events(&points, &events, offset()),
+        [
+            "2024-05-01_Hangzhou",
+            "2024-05-01_Hangzhou_2",
+            "2024-05-01_event3"
+        ]
+    );
+}
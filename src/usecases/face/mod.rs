@@ -1,12 +1,15 @@
-//! 人脸质量评分 Output Port：4 个 Gateway trait（检测/嵌入/网格/眼态）+ 跨边界
-//! DTO [`FaceDetection`]。`cull` use case 通过这些接口调用人脸推理，不知道具体
-//! 推理后端（tract-onnx / Candle / ort 等可替换）。
+//! 人脸质量评分 Output Port：4 个 Gateway trait（检测/嵌入/网格/眼态）+ 可选的整图
+//! 质量模型 [`QualityScorer`] + 跨边界 DTO [`FaceDetection`]。`cull` use case 通过这些
+//! 接口调用推理，不知道具体推理后端（tract-onnx / Candle / ort 等可替换）。
 //!
 //! 设计要点：
 //! - **职责单一**：每个模型一个 trait，按 SOLID 接口隔离；只接受调用方真正
 //!   需要的入参（SCRFD 接原始图字节，自己解码；其余三个接 SCRFD 检测后的
-//!   人脸/眼部 crop `image::RgbImage`，避免重复解码）
+//!   人脸/眼部 crop `image::RgbImage`，避免重复解码；质量模型接解码后的整图）
 //! - **`Send + Sync + Debug`**：与 `TextDetector` 同约定，可放 `Arc<dyn _>` 共享
+//! - **批量入口**：嵌入与眼态每张图要跑 N 张脸 / 2N 个眼部 crop，`embed_faces` /
+//!   `classify_eyes` 一次送入，默认实现逐张回调单张接口（Fake 无需改）。SCRFD 接整图字节，
+//!   跨图批量得同时驻留多张解码图（`cull` 逐张分析正是为了避免），不设批量入口
 //! - **path 入参**：`&Utf8Path` 作为 decision context（日志键、Fake 注入键），
 //!   真实实现忽略 path 内容
 //! - **位置**：按 Clean Architecture，Output Port 定义在 use case 层；具体实现
@@ -49,6 +52,19 @@ pub trait FaceEmbedder: Send + Sync + std::fmt::Debug {
     ///
     /// 模型推理失败或路径级注入错误时返回 `Err`。
     fn embed_face(&self, path: &Utf8Path, aligned: &image::RgbImage) -> io::Result<[f32; 128]>;
+
+    /// 同一张图的多张对齐人脸一次嵌入，返回与 `aligned` 等长同序。
+    ///
+    /// # Errors
+    ///
+    /// 任一张失败即整批返回 `Err`。
+    fn embed_faces(
+        &self,
+        path: &Utf8Path,
+        aligned: &[image::RgbImage],
+    ) -> io::Result<Vec<[f32; 128]>> {
+        aligned.iter().map(|a| self.embed_face(path, a)).collect()
+    }
 }
 
 /// `FaceMesh` Gateway：输入 192×192 人脸 crop，输出 468 个 3D 关键点（用于 EAR）。
@@ -73,4 +89,32 @@ pub trait EyeStateClassifier: Send + Sync + std::fmt::Debug {
     ///
     /// 模型推理失败或路径级注入错误时返回 `Err`。
     fn classify_eye(&self, path: &Utf8Path, eye_crop: &image::RgbImage) -> io::Result<f32>;
+
+    /// 同一张图的多个眼部 crop 一次分类，返回与 `eye_crops` 等长同序的闭眼概率。
+    ///
+    /// # Errors
+    ///
+    /// 任一个失败即整批返回 `Err`。
+    fn classify_eyes(
+        &self,
+        path: &Utf8Path,
+        eye_crops: &[image::RgbImage],
+    ) -> io::Result<Vec<f32>> {
+        eye_crops
+            .iter()
+            .map(|c| self.classify_eye(path, c))
+            .collect()
+    }
+}
+
+/// 整图美学/质量回归 Gateway（NIMA 类）：输入解码后的整图，输出质量分。
+///
+/// 可选的第 5 个模型槽：未配置模型路径时 `cull` 不调用，评分不含此项。
+pub trait QualityScorer: Send + Sync + std::fmt::Debug {
+    /// 对 `image` 跑质量模型返分数（NIMA 为 1~10 分布均值；标量回归头原样返回）。
+    ///
+    /// # Errors
+    ///
+    /// 模型推理失败或路径级注入错误时返回 `Err`。
+    fn score_quality(&self, path: &Utf8Path, image: &image::RgbImage) -> io::Result<f32>;
 }
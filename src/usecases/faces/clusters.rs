@@ -0,0 +1,72 @@
+//! `faces list` / `faces name` / `faces split`：查看身份簇、给簇命名与拆开误并的簇。
+
+use std::io;
+
+use tracing::debug;
+
+use super::FEATURE_FACES;
+use super::db::FaceDb;
+use super::report::{FacesReport, render_clusters};
+use crate::entities::common;
+
+/// 打印全部身份簇到 stdout。
+pub fn list_faces(db: &FaceDb) -> FacesReport {
+    let report = FacesReport {
+        clusters: db.clusters(),
+        ..FacesReport::default()
+    };
+    render_clusters(&report.clusters, &mut std::io::stdout());
+    report
+}
+
+/// 给簇 `id` 命名（`name` 为空即清除名字）并打印更新后的簇表；库的写盘由调用方负责。
+///
+/// # Errors
+///
+/// 库中没有该簇 ID 时返回 `InvalidInput`。
+pub fn name_face_cluster(db: &mut FaceDb, id: u32, name: &str) -> common::Result<FacesReport> {
+    db.name_cluster(id, name)
+        .map_err(|msg| io::Error::new(io::ErrorKind::InvalidInput, msg))?;
+    debug!(
+        feature = FEATURE_FACES,
+        operation = "name_cluster",
+        result = "ok",
+        cluster = id,
+        name = %name.trim(),
+        "face cluster named"
+    );
+    Ok(list_faces(db))
+}
+
+/// 按余弦阈值 `threshold` 拆开簇 `id`（见 [`FaceDb::split_cluster`]）并打印更新后的簇表；
+/// 库的写盘由调用方负责。
+///
+/// # Errors
+///
+/// `threshold` 不在 `[-1, 1]`、库中没有该簇或在该阈值下拆不开时返回 `InvalidInput`。
+pub fn split_face_cluster(db: &mut FaceDb, id: u32, threshold: f32) -> common::Result<FacesReport> {
+    if !(-1.0..=1.0).contains(&threshold) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("cosine threshold {threshold} is outside [-1, 1]"),
+        )
+        .into());
+    }
+    let new_ids = db
+        .split_cluster(id, threshold)
+        .map_err(|msg| io::Error::new(io::ErrorKind::InvalidInput, msg))?;
+    debug!(
+        feature = FEATURE_FACES,
+        operation = "split_cluster",
+        result = "ok",
+        cluster = id,
+        threshold,
+        new_clusters = ?new_ids,
+        "face cluster split"
+    );
+    Ok(list_faces(db))
+}
+
+#[cfg(test)]
+#[path = "clusters_tests.rs"]
+mod tests;
@@ -0,0 +1,87 @@
+use sha2::{Digest, Sha512};
+
+use super::*;
+use crate::usecases::cull::EMBED_DIM;
+
+fn db_with_one_cluster() -> FaceDb {
+    let mut db = FaceDb::default();
+    let mut embedding = [0.0; EMBED_DIM];
+    embedding[0] = 1.0;
+    db.add_image(
+        &Sha512::digest(b"a"),
+        "/a.jpg".into(),
+        &[([0.0, 0.0, 9.0, 9.0], embedding)],
+        0.5,
+    );
+    db
+}
+
+#[test]
+fn list_faces_reports_every_cluster() {
+    let report = list_faces(&db_with_one_cluster());
+    assert_eq!(report.clusters.len(), 1);
+    assert_eq!(report.clusters[0].name, None);
+    assert_eq!((report.scanned, report.failed), (0, 0));
+}
+
+#[test]
+fn name_face_cluster_names_and_rejects_unknown_id() {
+    let mut db = db_with_one_cluster();
+    let report = name_face_cluster(&mut db, 1, "Alice").unwrap();
+    assert_eq!(report.clusters[0].name.as_deref(), Some("Alice"));
+    let err = name_face_cluster(&mut db, 9, "Bob").unwrap_err();
+    assert!(
+        err.to_string().contains("face cluster 9 does not exist"),
+        "{err}"
+    );
+}
+
+#[test]
+fn split_face_cluster_validates_threshold_and_cluster() {
+    let mut db = db_with_one_cluster();
+    let err = split_face_cluster(&mut db, 1, 1.5).unwrap_err();
+    assert!(err.to_string().contains("outside [-1, 1]"), "{err}");
+    let err = split_face_cluster(&mut db, 1, f32::NAN).unwrap_err();
+    assert!(err.to_string().contains("outside [-1, 1]"), "{err}");
+    // 单脸簇拆不开。
+    let err = split_face_cluster(&mut db, 1, 0.9).unwrap_err();
+    assert!(err.to_string().contains("stays whole"), "{err}");
+}
+
+#[test]
+fn split_face_cluster_reports_new_clusters() {
+    let mut db = FaceDb::default();
+    let mut a = [0.0; EMBED_DIM];
+    a[0] = 1.0;
+    let mut b = [0.0; EMBED_DIM];
+    b[0] = 1.0;
+    b[1] = 1.0;
+    db.add_image(
+        &Sha512::digest(b"a"),
+        "/a.jpg".into(),
+        &[([0.0, 0.0, 9.0, 9.0], a), ([0.0, 0.0, 5.0, 5.0], b)],
+        0.5,
+    );
+    let report = split_face_cluster(&mut db, 1, 0.9).unwrap();
+    assert_eq!(
+        report.clusters.iter().map(|c| c.id).collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+}
+
+#[test]
+fn render_clusters_prints_dash_for_unnamed() {
+    let mut db = db_with_one_cluster();
+    let mut out = Vec::new();
+    render_clusters(&db.clusters(), &mut out);
+    let text = String::from_utf8(out).unwrap();
+    assert!(text.starts_with("## face clusters (1)\n"), "{text}");
+    assert!(
+        text.contains("     1  -          1       1  /a.jpg"),
+        "{text}"
+    );
+    db.name_cluster(1, "Alice").unwrap();
+    let mut out = Vec::new();
+    render_clusters(&db.clusters(), &mut out);
+    assert!(String::from_utf8(out).unwrap().contains("  Alice  "));
+}
@@ -0,0 +1,439 @@
+//! 人脸库：跨全库的人脸 embedding + 身份簇 + 用户命名，`faces` / `{person}` / `--person`
+//! 共用。
+//!
+//! - 键为图片内容 SHA-512（十六进制），与 catalog 同口径：文件改名、搬家后仍能命中，
+//!   `copy` 判重阶段算过的哈希可直接复用
+//! - 每张脸存 bbox + 128 维 embedding + 所属簇 ID；命名挂在簇 ID 上
+//! - 增量聚类：新脸只与各簇质心比较余弦相似度（≥ `face_cosine_min` 即同一人），每张脸
+//!   O(簇数) 而非 O(全库脸数)。质心由各脸 embedding 派生、不落盘，载入后首次登记时重建
+//! - 新脸命中多个簇、其中至多一个已命名时把它们并为一个，保留的 ID 按「已命名 → 脸数多
+//!   → ID 小」选；命中两个以上已命名簇时绝不自动合并（名字是用户的判断），新脸只归入
+//!   最相近的簇
+//! - 簇 ID 一经分配即稳定，命名不会因后续扫描漂移；误并的簇用 [`FaceDb::split_cluster`] 拆开
+//! - 持久化格式与写盘由 adapters 层负责，本模块只管内存中的查询与登记
+
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde_derive::{Deserialize, Serialize};
+use tracing::debug;
+
+use super::FEATURE_FACES;
+use crate::entities::SecureHash;
+use crate::usecases::cull::{EMBED_DIM, cosine_similarity};
+
+/// 库文件格式版本；结构不兼容地变化时递增。
+pub const FACE_DB_VERSION: u32 = 1;
+
+/// 全库人脸库。`BTreeMap` 让写出的文件按键有序、可 diff。
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FaceDb {
+    pub version: u32,
+    images: BTreeMap<String, ImageFaces>,
+    /// 簇 ID → 用户给的人名。
+    names: BTreeMap<u32, String>,
+    next_cluster_id: u32,
+    /// 簇 ID → 质心，由 `images` 派生；None 表示待重建（刚载入或拆簇后）。
+    #[serde(skip)]
+    centroids: Option<BTreeMap<u32, Centroid>>,
+}
+
+impl Default for FaceDb {
+    fn default() -> Self {
+        Self {
+            version: FACE_DB_VERSION,
+            images: BTreeMap::new(),
+            names: BTreeMap::new(),
+            next_cluster_id: 1,
+            centroids: None,
+        }
+    }
+}
+
+/// 单张图的人脸记录。
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ImageFaces {
+    /// 最后一次扫描到的位置（`Location::display` 形式）。
+    pub location: String,
+    pub faces: Vec<StoredFace>,
+}
+
+/// 单张脸。
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StoredFace {
+    /// `[x1, y1, x2, y2]` 原图像素坐标。
+    pub bbox: [f32; 4],
+    /// `MobileFaceNet` embedding（[`EMBED_DIM`] 维）。
+    pub embedding: Vec<f32>,
+    pub cluster: u32,
+}
+
+/// 簇质心：成员 embedding 单位化后求和。余弦只看方向，和与均值等价，免去除法；
+/// 单位化让每张脸等权，不因模型输出的模长不同而偏向某几张。
+#[derive(Clone, Debug)]
+struct Centroid {
+    sum: [f32; EMBED_DIM],
+    faces: usize,
+}
+
+impl Centroid {
+    fn new() -> Self {
+        Self {
+            sum: [0.0; EMBED_DIM],
+            faces: 0,
+        }
+    }
+
+    /// 计入一张脸；维度不符或零向量（手改过的库）只计数，不影响方向。
+    fn add(&mut self, embedding: &[f32]) {
+        self.faces += 1;
+        if embedding.len() != EMBED_DIM {
+            return;
+        }
+        let norm = embedding.iter().map(|x| x * x).sum::<f32>().sqrt();
+        if !norm.is_finite() || norm < f32::EPSILON {
+            return;
+        }
+        for (s, x) in self.sum.iter_mut().zip(embedding) {
+            *s += x / norm;
+        }
+    }
+
+    fn absorb(&mut self, other: &Self) {
+        for (s, x) in self.sum.iter_mut().zip(&other.sum) {
+            *s += x;
+        }
+        self.faces += other.faces;
+    }
+}
+
+/// 一个身份簇的概况；`faces list` / 报告输出用。
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct FaceCluster {
+    pub id: u32,
+    pub name: Option<String>,
+    pub faces: usize,
+    pub images: usize,
+    /// 成员中路径序最小的图，便于用户打开辨认。
+    pub sample: String,
+}
+
+impl FaceDb {
+    /// 该内容是否已扫描过（含未检出人脸的图）。
+    #[must_use]
+    pub fn contains(&self, hash: &SecureHash) -> bool {
+        self.images.contains_key(&key(hash))
+    }
+
+    /// 已扫描过的内容换了位置：更新最后已知位置，不重跑推理。
+    pub fn relocate(&mut self, hash: &SecureHash, location: String) {
+        if let Some(image) = self.images.get_mut(&key(hash)) {
+            image.location = location;
+        }
+    }
+
+    /// 登记一张新图的全部人脸并增量聚类，返回登记的人脸数。已登记过的内容只更新位置。
+    pub fn add_image(
+        &mut self,
+        hash: &SecureHash,
+        location: String,
+        faces: &[([f32; 4], [f32; EMBED_DIM])],
+        threshold: f32,
+    ) -> usize {
+        let k = key(hash);
+        if self.images.contains_key(&k) {
+            self.relocate(hash, location);
+            return 0;
+        }
+        let mut stored: Vec<StoredFace> = Vec::with_capacity(faces.len());
+        for (bbox, embedding) in faces {
+            let cluster = self.assign(embedding, threshold, &mut stored);
+            self.centroids_mut()
+                .entry(cluster)
+                .or_insert_with(Centroid::new)
+                .add(embedding);
+            stored.push(StoredFace {
+                bbox: *bbox,
+                embedding: embedding.to_vec(),
+                cluster,
+            });
+        }
+        let added = stored.len();
+        self.images.insert(
+            k,
+            ImageFaces {
+                location,
+                faces: stored,
+            },
+        );
+        added
+    }
+
+    /// 给簇命名；`name` 去首尾空白后为空即清除名字。多个簇可用同一个名字（聚类把同一人
+    /// 拆成了几簇时），`{person}` / `--person` 把它们视为同一人。
+    ///
+    /// # Errors
+    ///
+    /// 库中没有该簇 ID 时返回 `Err`。
+    pub fn name_cluster(&mut self, id: u32, name: &str) -> Result<(), String> {
+        if !self.all_faces().any(|f| f.cluster == id) {
+            return Err(format!("face cluster {id} does not exist"));
+        }
+        let name = name.trim();
+        if name.is_empty() {
+            self.names.remove(&id);
+        } else {
+            self.names.insert(id, name.to_string());
+        }
+        Ok(())
+    }
+
+    /// 拆开误并的簇：只在簇 `id` 的脸之间按 `threshold` 重新做质心聚类（按库键与脸序遍历，
+    /// 结果确定）。脸最多的一份（同大取先出现者）保留原 ID 与名字，其余各得新的未命名 ID。
+    /// 返回新分配的 ID，升序。
+    ///
+    /// # Errors
+    ///
+    /// 库中没有该簇，或在 `threshold` 下仍聚成一份（可换更严的阈值重试）时返回 `Err`。
+    pub fn split_cluster(&mut self, id: u32, threshold: f32) -> Result<Vec<u32>, String> {
+        let mut parts: Vec<(Centroid, Vec<(String, usize)>)> = Vec::new();
+        for (key, image) in &self.images {
+            for (idx, face) in image.faces.iter().enumerate() {
+                if face.cluster != id {
+                    continue;
+                }
+                let closest = <&[f32; EMBED_DIM]>::try_from(face.embedding.as_slice())
+                    .ok()
+                    .and_then(|embedding| {
+                        closest_match(
+                            parts
+                                .iter()
+                                .enumerate()
+                                .map(|(i, (c, _))| (i, cosine_similarity(&c.sum, embedding))),
+                            threshold,
+                        )
+                    });
+                let part = closest.unwrap_or_else(|| {
+                    parts.push((Centroid::new(), Vec::new()));
+                    parts.len() - 1
+                });
+                parts[part].0.add(&face.embedding);
+                parts[part].1.push((key.clone(), idx));
+            }
+        }
+        match parts.len() {
+            0 => return Err(format!("face cluster {id} does not exist")),
+            1 => {
+                return Err(format!(
+                    "face cluster {id} stays whole at cosine similarity {threshold}; \
+                     retry with a higher threshold"
+                ));
+            }
+            _ => {}
+        }
+        let keep = (0..parts.len())
+            .max_by_key(|&i| (parts[i].1.len(), Reverse(i)))
+            .unwrap_or(0);
+        let mut new_ids = Vec::with_capacity(parts.len() - 1);
+        for (i, (_, members)) in parts.iter().enumerate() {
+            if i == keep {
+                continue;
+            }
+            let new_id = self.next_cluster_id;
+            self.next_cluster_id += 1;
+            new_ids.push(new_id);
+            for (key, idx) in members {
+                if let Some(image) = self.images.get_mut(key) {
+                    image.faces[*idx].cluster = new_id;
+                }
+            }
+        }
+        self.centroids = None;
+        Ok(new_ids)
+    }
+
+    /// 全部簇概况，按 ID 升序。
+    #[must_use]
+    pub fn clusters(&self) -> Vec<FaceCluster> {
+        let mut by_id: BTreeMap<u32, (usize, BTreeSet<&str>)> = BTreeMap::new();
+        for image in self.images.values() {
+            for face in &image.faces {
+                let entry = by_id.entry(face.cluster).or_default();
+                entry.0 += 1;
+                entry.1.insert(&image.location);
+            }
+        }
+        by_id
+            .into_iter()
+            .map(|(id, (faces, images))| FaceCluster {
+                id,
+                name: self.names.get(&id).cloned(),
+                faces,
+                images: images.len(),
+                sample: images
+                    .first()
+                    .map_or_else(String::new, |s| (*s).to_string()),
+            })
+            .collect()
+    }
+
+    /// 该内容里出现的已命名人物：按人脸框面积从大到小（主体在前），同名去重。
+    /// 未扫描过或没有已命名的脸时为空。
+    #[must_use]
+    pub fn persons(&self, hash: &SecureHash) -> Vec<String> {
+        let Some(image) = self.images.get(&key(hash)) else {
+            return Vec::new();
+        };
+        let mut faces: Vec<&StoredFace> = image.faces.iter().collect();
+        faces.sort_by(|a, b| area(&b.bbox).total_cmp(&area(&a.bbox)));
+        let mut out: Vec<String> = Vec::new();
+        for face in faces {
+            if let Some(name) = self.names.get(&face.cluster)
+                && !out.contains(name)
+            {
+                out.push(name.clone());
+            }
+        }
+        out
+    }
+
+    /// 是否有簇以 `name` 命名（去首尾空白、忽略大小写，同 `--person` 口径）。
+    #[must_use]
+    pub fn is_named(&self, name: &str) -> bool {
+        let name = name.trim();
+        self.names.values().any(|n| n.eq_ignore_ascii_case(name))
+    }
+
+    /// 已扫描的图数（含无人脸的图）。
+    #[must_use]
+    pub fn image_count(&self) -> usize {
+        self.images.len()
+    }
+
+    fn all_faces(&self) -> impl Iterator<Item = &StoredFace> {
+        self.images.values().flat_map(|i| i.faces.iter())
+    }
+
+    fn centroids_mut(&mut self) -> &mut BTreeMap<u32, Centroid> {
+        let images = &self.images;
+        self.centroids
+            .get_or_insert_with(|| build_centroids(images))
+    }
+
+    /// 为新脸选簇：无命中开新簇；命中两个以上已命名簇时不合并，只取最相近者；否则把
+    /// 命中的簇并为一个。`pending` 为本图已选好簇、尚未入库的脸，合并时一并改挂。
+    fn assign(
+        &mut self,
+        embedding: &[f32; EMBED_DIM],
+        threshold: f32,
+        pending: &mut [StoredFace],
+    ) -> u32 {
+        let matches: Vec<(u32, f32)> = self
+            .centroids_mut()
+            .iter()
+            .map(|(&id, c)| (id, cosine_similarity(&c.sum, embedding)))
+            .filter(|&(_, sim)| sim >= threshold)
+            .collect();
+        let named = matches
+            .iter()
+            .filter(|(id, _)| self.names.contains_key(id))
+            .count();
+        if named > 1 {
+            let closest =
+                closest_match(matches.iter().copied(), threshold).expect("named matches exist");
+            log_ambiguous_match(closest, named);
+            return closest;
+        }
+        let Some(keeper) = self.pick_keeper(&matches) else {
+            let id = self.next_cluster_id;
+            self.next_cluster_id += 1;
+            return id;
+        };
+        for &(other, _) in matches.iter().filter(|(id, _)| *id != keeper) {
+            self.merge_into(other, keeper, pending);
+        }
+        keeper
+    }
+
+    /// 新脸命中多个簇时保留哪个：已命名 → 脸数多 → ID 小。
+    fn pick_keeper(&self, matches: &[(u32, f32)]) -> Option<u32> {
+        let size = |id: u32| {
+            self.centroids
+                .as_ref()
+                .and_then(|c| c.get(&id))
+                .map_or(0, |c| c.faces)
+        };
+        matches
+            .iter()
+            .map(|&(id, _)| id)
+            .min_by_key(|&id| (!self.names.contains_key(&id), Reverse(size(id)), id))
+    }
+
+    /// 把簇 `from` 的全部脸（库内与本图待登记的）改挂到 `into` 并合并质心。`assign` 只在
+    /// 命中的已命名簇不超过一个时合并，且已命名者必为 `into`，`from` 没有名字可丢。
+    fn merge_into(&mut self, from: u32, into: u32, pending: &mut [StoredFace]) {
+        let faces = self
+            .images
+            .values_mut()
+            .flat_map(|i| i.faces.iter_mut())
+            .chain(pending.iter_mut());
+        for face in faces.filter(|f| f.cluster == from) {
+            face.cluster = into;
+        }
+        if let Some(centroids) = &mut self.centroids
+            && let Some(absorbed) = centroids.remove(&from)
+        {
+            centroids
+                .entry(into)
+                .or_insert_with(Centroid::new)
+                .absorb(&absorbed);
+        }
+    }
+}
+
+/// 内容哈希 → 库键。
+fn key(hash: &SecureHash) -> String {
+    hex::encode(&hash[..])
+}
+
+fn build_centroids(images: &BTreeMap<String, ImageFaces>) -> BTreeMap<u32, Centroid> {
+    let mut out: BTreeMap<u32, Centroid> = BTreeMap::new();
+    for face in images.values().flat_map(|i| i.faces.iter()) {
+        out.entry(face.cluster)
+            .or_insert_with(Centroid::new)
+            .add(&face.embedding);
+    }
+    out
+}
+
+// 相似度达到阈值者中取最高；同分取键小者，结果与遍历顺序无关。
+fn closest_match<K: Ord + Copy>(
+    candidates: impl Iterator<Item = (K, f32)>,
+    threshold: f32,
+) -> Option<K> {
+    candidates
+        .filter(|&(_, sim)| sim >= threshold)
+        .max_by(|a, b| a.1.total_cmp(&b.1).then(b.0.cmp(&a.0)))
+        .map(|(k, _)| k)
+}
+
+fn area(bbox: &[f32; 4]) -> f32 {
+    (bbox[2] - bbox[0]).max(0.0) * (bbox[3] - bbox[1]).max(0.0)
+}
+
+/// 新脸同时像两个以上已命名的人：不替用户合并，归入最相近者。
+#[cfg_attr(coverage_nightly, coverage(off))]
+fn log_ambiguous_match(cluster: u32, named_matches: usize) {
+    debug!(
+        feature = FEATURE_FACES,
+        operation = "assign_face",
+        result = "ambiguous",
+        cluster,
+        named_matches,
+        "face matches several named clusters; assigned to the closest without merging"
+    );
+}
+
+#[cfg(test)]
+#[path = "db_tests.rs"]
+mod tests;
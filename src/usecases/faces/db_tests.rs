@@ -0,0 +1,290 @@
+use sha2::{Digest, Sha512};
+
+use super::*;
+
+const T: f32 = 0.5;
+
+/// 第 `axis` 维为 1 的单位向量：不同 axis 两两正交（余弦 0），同 axis 余弦 1。
+fn unit(axis: usize) -> [f32; EMBED_DIM] {
+    let mut v = [0.0; EMBED_DIM];
+    v[axis] = 1.0;
+    v
+}
+
+/// 两个 axis 的等权混合：与各自单位向量余弦 ≈ 0.707，足以把两簇连通。
+fn mix(a: usize, b: usize) -> [f32; EMBED_DIM] {
+    let mut v = [0.0; EMBED_DIM];
+    v[a] = 1.0;
+    v[b] = 1.0;
+    v
+}
+
+fn hash(name: &str) -> SecureHash {
+    Sha512::digest(name.as_bytes())
+}
+
+fn face(size: f32, embedding: [f32; EMBED_DIM]) -> ([f32; 4], [f32; EMBED_DIM]) {
+    ([0.0, 0.0, size, size], embedding)
+}
+
+#[test]
+fn same_identity_across_images_shares_one_cluster() {
+    let mut db = FaceDb::default();
+    assert_eq!(
+        db.add_image(&hash("a"), "/a.jpg".into(), &[face(10.0, unit(0))], T),
+        1
+    );
+    assert_eq!(
+        db.add_image(
+            &hash("b"),
+            "/b.jpg".into(),
+            &[face(10.0, unit(0)), face(10.0, unit(1))],
+            T
+        ),
+        2
+    );
+    let clusters = db.clusters();
+    assert_eq!(clusters.len(), 2);
+    assert_eq!(
+        (clusters[0].id, clusters[0].faces, clusters[0].images),
+        (1, 2, 2)
+    );
+    assert_eq!(clusters[0].sample, "/a.jpg");
+    assert_eq!(
+        (clusters[1].id, clusters[1].faces, clusters[1].images),
+        (2, 1, 1)
+    );
+    assert_eq!(db.image_count(), 2);
+}
+
+#[test]
+fn known_content_only_relocates() {
+    let mut db = FaceDb::default();
+    db.add_image(&hash("a"), "/old/a.jpg".into(), &[face(10.0, unit(0))], T);
+    assert!(db.contains(&hash("a")));
+    assert!(!db.contains(&hash("b")));
+    // 再登记同一内容：不重复计脸，只换位置。
+    assert_eq!(
+        db.add_image(&hash("a"), "/new/a.jpg".into(), &[face(10.0, unit(0))], T),
+        0
+    );
+    assert_eq!(db.clusters()[0].faces, 1);
+    assert_eq!(db.clusters()[0].sample, "/new/a.jpg");
+    db.relocate(&hash("a"), "/moved/a.jpg".into());
+    assert_eq!(db.clusters()[0].sample, "/moved/a.jpg");
+}
+
+/// 无人脸的图同样登记，下次扫描不再重跑推理。
+#[test]
+fn image_without_faces_is_recorded() {
+    let mut db = FaceDb::default();
+    assert_eq!(db.add_image(&hash("a"), "/a.jpg".into(), &[], T), 0);
+    assert!(db.contains(&hash("a")));
+    assert!(db.clusters().is_empty());
+}
+
+/// 新脸同时命中两个簇：合并为一，保留已命名的那个，即使它更小、ID 更大。
+#[test]
+fn bridging_face_merges_clusters_keeping_named_one() {
+    let mut db = FaceDb::default();
+    db.add_image(&hash("a"), "/a.jpg".into(), &[face(10.0, unit(0))], T);
+    db.add_image(&hash("b"), "/b.jpg".into(), &[face(10.0, unit(0))], T);
+    db.add_image(&hash("c"), "/c.jpg".into(), &[face(10.0, unit(1))], T);
+    db.name_cluster(2, "Alice").unwrap();
+    db.add_image(&hash("d"), "/d.jpg".into(), &[face(10.0, mix(0, 1))], T);
+    let clusters = db.clusters();
+    assert_eq!(clusters.len(), 1);
+    assert_eq!(clusters[0].id, 2);
+    assert_eq!(clusters[0].name.as_deref(), Some("Alice"));
+    assert_eq!(clusters[0].faces, 4);
+    assert_eq!(db.persons(&hash("a")), vec!["Alice".to_string()]);
+}
+
+/// 都未命名时保留脸多的簇。
+#[test]
+fn merge_prefers_larger_unnamed_cluster() {
+    let mut db = FaceDb::default();
+    db.add_image(&hash("a"), "/a.jpg".into(), &[face(10.0, unit(0))], T);
+    db.add_image(&hash("b"), "/b.jpg".into(), &[face(10.0, unit(1))], T);
+    db.add_image(&hash("c"), "/c.jpg".into(), &[face(10.0, unit(1))], T);
+    db.add_image(&hash("d"), "/d.jpg".into(), &[face(10.0, mix(0, 1))], T);
+    let clusters = db.clusters();
+    assert_eq!(clusters.len(), 1);
+    assert_eq!((clusters[0].id, clusters[0].faces), (2, 4));
+}
+
+/// 两个已命名簇被新脸连通：不合并、名字都保留，新脸归入最相近者（同分取 ID 小）。
+#[test]
+fn bridging_face_never_merges_two_named_clusters() {
+    let mut db = FaceDb::default();
+    db.add_image(&hash("a"), "/a.jpg".into(), &[face(10.0, unit(0))], T);
+    db.add_image(&hash("b"), "/b.jpg".into(), &[face(10.0, unit(1))], T);
+    db.name_cluster(1, "Alice").unwrap();
+    db.name_cluster(2, "Bob").unwrap();
+    db.add_image(&hash("c"), "/c.jpg".into(), &[face(10.0, mix(0, 1))], T);
+    let clusters = db.clusters();
+    assert_eq!(clusters.len(), 2);
+    assert_eq!(
+        (
+            clusters[0].id,
+            clusters[0].name.as_deref(),
+            clusters[0].faces
+        ),
+        (1, Some("Alice"), 2)
+    );
+    assert_eq!(
+        (
+            clusters[1].id,
+            clusters[1].name.as_deref(),
+            clusters[1].faces
+        ),
+        (2, Some("Bob"), 1)
+    );
+    assert!(db.is_named("Bob"));
+
+    // 明显更像 Bob 的桥接脸归 Bob。
+    let mut closer_to_bob = mix(0, 1);
+    closer_to_bob[1] = 2.0;
+    db.add_image(&hash("d"), "/d.jpg".into(), &[face(10.0, closer_to_bob)], T);
+    assert_eq!(db.clusters()[1].faces, 2);
+}
+
+/// 新脸与簇质心比较而非与单张脸：链式相似（a~b、b~c 而 a≁c）不会把 c 拉进 a 的簇。
+#[test]
+fn new_face_is_matched_against_cluster_centroid() {
+    let mut db = FaceDb::default();
+    db.add_image(&hash("a"), "/a.jpg".into(), &[face(10.0, unit(0))], T);
+    db.add_image(&hash("b"), "/b.jpg".into(), &[face(10.0, mix(0, 1))], T);
+    assert_eq!(db.clusters().len(), 1);
+    // 与 b 余弦 ≈ 0.707，与质心 (a + b) 仅 ≈ 0.38。
+    db.add_image(&hash("c"), "/c.jpg".into(), &[face(10.0, unit(1))], T);
+    let clusters = db.clusters();
+    assert_eq!(clusters.len(), 2);
+    assert_eq!((clusters[0].faces, clusters[1].faces), (2, 1));
+}
+
+/// 载入的库没有质心：首次登记时由各脸重建，与未落盘前结果一致。
+#[test]
+fn centroids_are_rebuilt_after_load() {
+    let mut db = FaceDb::default();
+    db.add_image(&hash("a"), "/a.jpg".into(), &[face(10.0, unit(0))], T);
+    db.add_image(&hash("b"), "/b.jpg".into(), &[face(10.0, mix(0, 1))], T);
+    let json = serde_json::to_string(&db).unwrap();
+    assert!(!json.contains("centroids"), "{json}");
+    let mut loaded: FaceDb = serde_json::from_str(&json).unwrap();
+    loaded.add_image(&hash("c"), "/c.jpg".into(), &[face(10.0, unit(1))], T);
+    loaded.add_image(&hash("d"), "/d.jpg".into(), &[face(10.0, unit(0))], T);
+    let clusters = loaded.clusters();
+    assert_eq!(clusters.len(), 2);
+    assert_eq!((clusters[0].id, clusters[0].faces), (1, 3));
+}
+
+/// 误并的簇按阈值重新聚类：最大一份留原 ID 与名字，其余得新 ID；之后新脸按新簇归类。
+#[test]
+fn split_cluster_separates_merged_identities() {
+    let mut db = FaceDb::default();
+    db.add_image(&hash("a"), "/a.jpg".into(), &[face(10.0, unit(0))], T);
+    db.add_image(&hash("b"), "/b.jpg".into(), &[face(10.0, unit(1))], T);
+    // 桥接脸把两个未命名簇并成 1 号。
+    db.add_image(&hash("c"), "/c.jpg".into(), &[face(10.0, mix(0, 1))], T);
+    db.name_cluster(1, "Alice").unwrap();
+    assert_eq!(db.clusters().len(), 1);
+
+    // 三张脸两两余弦 ≤ 0.707：0.9 下各成一份。保留哪份取决于库键（哈希）序，不断言。
+    assert_eq!(db.split_cluster(1, 0.9).unwrap(), vec![3, 4]);
+    let clusters = db.clusters();
+    assert_eq!(clusters.len(), 3);
+    assert_eq!(clusters[0].name.as_deref(), Some("Alice"));
+    assert!(clusters.iter().all(|c| c.faces == 1));
+    let named: usize = ["a", "b", "c"]
+        .iter()
+        .map(|n| db.persons(&hash(n)).len())
+        .sum();
+    assert_eq!(named, 1);
+
+    // 拆后质心重建：与某一份完全相同的新脸归入该份。
+    let b_cluster = db.images[&key(&hash("b"))].faces[0].cluster;
+    db.add_image(&hash("e"), "/e.jpg".into(), &[face(10.0, unit(1))], 0.9);
+    assert_eq!(db.images[&key(&hash("e"))].faces[0].cluster, b_cluster);
+}
+
+#[test]
+fn split_cluster_rejects_unknown_or_unsplittable_cluster() {
+    let mut db = FaceDb::default();
+    db.add_image(
+        &hash("a"),
+        "/a.jpg".into(),
+        &[face(10.0, unit(0)), face(8.0, unit(0))],
+        T,
+    );
+    let err = db.split_cluster(9, T).unwrap_err();
+    assert!(err.contains("face cluster 9 does not exist"), "{err}");
+    let err = db.split_cluster(1, T).unwrap_err();
+    assert!(err.contains("stays whole"), "{err}");
+    assert_eq!(db.clusters().len(), 1);
+}
+
+#[test]
+fn name_cluster_validates_id_and_clears_on_blank() {
+    let mut db = FaceDb::default();
+    db.add_image(&hash("a"), "/a.jpg".into(), &[face(10.0, unit(0))], T);
+    let err = db.name_cluster(7, "Alice").unwrap_err();
+    assert!(err.contains("face cluster 7 does not exist"), "{err}");
+    db.name_cluster(1, "  Alice ").unwrap();
+    assert!(db.is_named("alice"));
+    assert_eq!(db.clusters()[0].name.as_deref(), Some("Alice"));
+    db.name_cluster(1, "   ").unwrap();
+    assert_eq!(db.clusters()[0].name, None);
+    assert!(!db.is_named("Alice"));
+}
+
+/// 人物按人脸框面积从大到小；同名去重；未命名的脸不出现。
+#[test]
+fn persons_orders_by_face_area_and_dedups() {
+    let mut db = FaceDb::default();
+    db.add_image(
+        &hash("a"),
+        "/a.jpg".into(),
+        &[
+            face(5.0, unit(0)),
+            face(20.0, unit(1)),
+            face(8.0, unit(2)),
+            face(30.0, unit(3)),
+        ],
+        T,
+    );
+    db.name_cluster(1, "Alice").unwrap();
+    db.name_cluster(2, "Bob").unwrap();
+    db.name_cluster(3, "Alice").unwrap();
+    assert_eq!(
+        db.persons(&hash("a")),
+        vec!["Bob".to_string(), "Alice".to_string()]
+    );
+    assert!(db.persons(&hash("missing")).is_empty());
+}
+
+/// 手改过的库里维度不符的 embedding 只计数、不影响质心方向，不会 panic。
+#[test]
+fn malformed_stored_embedding_does_not_move_centroid() {
+    let mut centroid = Centroid::new();
+    centroid.add(&[1.0; 3]);
+    centroid.add(&[0.0; EMBED_DIM]);
+    assert_eq!(centroid.faces, 2);
+    assert!(centroid.sum.iter().all(|x| x.abs() < f32::EPSILON));
+    centroid.add(&unit(0));
+    assert!(cosine_similarity(&centroid.sum, &unit(0)) > 0.99);
+}
+
+#[test]
+fn serde_round_trip_keeps_clusters_names_and_next_id() {
+    let mut db = FaceDb::default();
+    db.add_image(&hash("a"), "/a.jpg".into(), &[face(10.0, unit(0))], T);
+    db.name_cluster(1, "Alice").unwrap();
+    let json = serde_json::to_string(&db).unwrap();
+    let mut loaded: FaceDb = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.version, FACE_DB_VERSION);
+    assert_eq!(loaded.clusters(), db.clusters());
+    // 新身份继续从持久化的 next_cluster_id 分配，不与旧 ID 冲突。
+    loaded.add_image(&hash("b"), "/b.jpg".into(), &[face(10.0, unit(1))], T);
+    assert_eq!(loaded.clusters()[1].id, 2);
+}
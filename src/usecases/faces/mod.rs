@@ -0,0 +1,22 @@
+//! `faces` 子命令：全库人脸库 + 身份簇命名，供归档模板 `{person}` 与 `--person` 过滤使用。
+//!
+//! - `faces scan` 对源图片跑 SCRFD → 5 点对齐 → `MobileFaceNet`，embedding 按内容 SHA-512
+//!   登记并增量聚类成身份簇（见 `db` 模块）
+//! - `faces list` 列出簇，`faces name <id> <name>` 给簇命名，`faces split <id>` 拆开误并的簇
+//! - copy / move 时按内容哈希查库：`{person}` 渲染为图中主体人物，`--person` 只处理含
+//!   该人物的文件
+
+mod clusters;
+mod db;
+mod person;
+mod report;
+mod scan;
+
+pub use clusters::{list_faces, name_face_cluster, split_face_cluster};
+pub use db::{FACE_DB_VERSION, FaceCluster, FaceDb, ImageFaces, StoredFace};
+pub use person::PersonOpts;
+pub(crate) use person::{assign_persons, has_person, uses_person};
+pub use report::FacesReport;
+pub use scan::scan_faces;
+
+pub(crate) const FEATURE_FACES: &str = "faces";
@@ -0,0 +1,88 @@
+//! `{person}` 占位符与 `--person` 过滤：copy / move 渲染前按内容 SHA-512 查人脸库，
+//! 把已命名人物注入源 `Index`。
+//!
+//! - 只查 MIME 为 `image/*` 的文件；视频与文档不在库里，不为它们整文件算哈希
+//! - 超过 `backend.face.max_image_bytes` 的图 `faces scan` 不收录，同样跳过
+//! - 算出的 SHA-512 缓存在 `Info` 里，随后的判重直接复用，不重复读盘
+
+use std::collections::HashMap;
+
+use camino::Utf8PathBuf;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use tracing::{debug, warn};
+
+use super::FEATURE_FACES;
+use super::db::FaceDb;
+use crate::entities::file_index::Index;
+use crate::entities::file_info::Info;
+use crate::entities::threadpool::install_io;
+use crate::usecases::config::config;
+
+/// copy / move 使用人脸库的方式：`{person}` 渲染总是查库，`person` 额外做过滤。
+pub struct PersonOpts<'a> {
+    pub db: &'a FaceDb,
+    /// `--person NAME`：只处理人脸库里含该已命名人物的文件（忽略大小写）。
+    pub person: Option<&'a str>,
+}
+
+/// 模板是否引用 `{person}`；按前缀匹配以覆盖 `{person:lower}` / `{person|misc}`。
+pub(crate) fn uses_person(template: &str) -> bool {
+    template.contains("{person")
+}
+
+/// `persons` 里是否有名为 `wanted` 的人物：去首尾空白、忽略大小写比较。
+pub(crate) fn has_person(persons: &[String], wanted: &str) -> bool {
+    let wanted = wanted.trim();
+    persons.iter().any(|p| p.eq_ignore_ascii_case(wanted))
+}
+
+/// 对 `index` 内全部图片查人脸库并注入已命名人物。
+pub(crate) fn assign_persons(index: &mut Index, db: &FaceDb) {
+    let max_bytes = config().backend.face.max_image_bytes;
+    let assigned: HashMap<Utf8PathBuf, Vec<String>> = install_io(|| {
+        index
+            .files()
+            .par_iter()
+            .filter(|(_, info)| info.size <= max_bytes && is_image(info))
+            .filter_map(|(path, info)| match info.secure_hash() {
+                Ok(hash) => Some((path.clone(), db.persons(&hash))),
+                Err(e) => {
+                    log_hash_failed(path.as_str(), &e);
+                    None
+                }
+            })
+            .filter(|(_, persons)| !persons.is_empty())
+            .collect()
+    });
+    debug!(
+        feature = FEATURE_FACES,
+        operation = "assign_persons",
+        result = "ok",
+        files = index.files().len(),
+        with_persons = assigned.len(),
+        "named persons looked up in face db"
+    );
+    index.set_persons(assigned);
+}
+
+fn is_image(info: &Info) -> bool {
+    info.exif_ref()
+        .is_some_and(|e| e.mime_type().starts_with("image/"))
+}
+
+// 读不出哈希的文件按"库里没有"处理：`{person}` 落 unknown，`--person` 不命中。
+#[cfg_attr(coverage_nightly, coverage(off))]
+fn log_hash_failed(source: &str, e: &std::io::Error) {
+    warn!(
+        feature = FEATURE_FACES,
+        operation = "assign_persons",
+        result = "hash_failed",
+        source = %source,
+        error = %e,
+        "cannot hash image for face db lookup"
+    );
+}
+
+#[cfg(test)]
+#[path = "person_tests.rs"]
+mod tests;
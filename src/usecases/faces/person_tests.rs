@@ -0,0 +1,67 @@
+use tempfile::tempdir;
+
+use super::*;
+use crate::entities::exif::Exif;
+use crate::entities::test_common as tc;
+
+#[test]
+fn uses_person_matches_spec_and_fallback_forms() {
+    assert!(uses_person("People/{person}/{year}"));
+    assert!(uses_person("{person:lower|misc}"));
+    assert!(!uses_person("{year}/{event}"));
+}
+
+#[test]
+fn has_person_ignores_case_and_padding() {
+    let persons = vec!["Alice".to_string(), "Bob".to_string()];
+    assert!(has_person(&persons, " alice "));
+    assert!(has_person(&persons, "BOB"));
+    assert!(!has_person(&persons, "Carol"));
+    assert!(!has_person(&[], "Alice"));
+}
+
+/// 库里有该内容的图注入已命名人物；视频与不在库里的图保持为空。
+#[test]
+fn assign_persons_injects_names_for_known_images_only() {
+    let dir = tempdir().unwrap();
+    let mut index = Index::new();
+    for (name, mime) in [
+        ("known.png", "image/png"),
+        ("video.png", "video/mp4"),
+        ("other.png", "image/png"),
+    ] {
+        let png = tc::copy_png_to(dir.path(), name).unwrap();
+        let mut info = Info::from(png.to_str().unwrap()).unwrap();
+        info.set_exif(Exif::with_mime(mime));
+        index.add(info);
+    }
+    // 三份都是同一个 PNG 拷贝：先登记，再把 other 改成不同内容。
+    std::fs::write(dir.path().join("other.png"), b"different bytes").unwrap();
+    let hash = index
+        .files()
+        .values()
+        .find(|i| i.full_path.ends_with("known.png"))
+        .unwrap()
+        .secure_hash()
+        .unwrap();
+    let mut db = FaceDb::default();
+    let mut embedding = [0.0; crate::usecases::cull::EMBED_DIM];
+    embedding[0] = 1.0;
+    db.add_image(
+        &hash,
+        "known.png".into(),
+        &[([0.0, 0.0, 9.0, 9.0], embedding)],
+        0.5,
+    );
+    db.name_cluster(1, "Alice").unwrap();
+
+    assign_persons(&mut index, &db);
+
+    let persons_of = |name: &str| {
+        let info = index.files().values().find(|i| i.full_path.ends_with(name));
+        info.unwrap().persons().to_vec()
+    };
+    assert_eq!(persons_of("known.png"), vec!["Alice".to_string()]);
+    assert!(persons_of("video.png").is_empty());
+    assert!(persons_of("other.png").is_empty());
+}
@@ -0,0 +1,54 @@
+//! `faces` 命令的结构化报告 + stdout 簇表。
+
+use std::io::Write;
+
+use serde_derive::Serialize;
+
+use super::db::FaceCluster;
+use crate::usecases::report::ReportError;
+
+/// `faces scan` / `faces list` / `faces name` 共用的报告；后两者只填 `clusters`。
+#[derive(Debug, Default, Serialize)]
+pub struct FacesReport {
+    /// walker 触达的源端文件总数（含非图片、超大跳过、读取失败）。
+    pub scanned: usize,
+    /// 本次跑了人脸推理并登记进库的图数（含未检出人脸的图）。
+    pub analyzed: usize,
+    /// 内容已在库中、跳过推理的图数（只更新最后已知位置）。
+    pub known: usize,
+    /// 本次新登记的人脸数。
+    pub faces_added: usize,
+    /// 失败计数（读取 / 解码 / 检测 Err、超过 `max_image_bytes`）。
+    pub failed: usize,
+    pub errors: Vec<ReportError>,
+    /// 操作完成后全库的身份簇，按 ID 升序。
+    pub clusters: Vec<FaceCluster>,
+}
+
+/// 簇表打印到 `sink`：每簇一行 ID / 名字（未命名为 `-`）/ 脸数 / 图数 / 示例图。
+pub(super) fn render_clusters(clusters: &[FaceCluster], sink: &mut impl Write) {
+    let _ = writeln!(sink, "## face clusters ({})", clusters.len());
+    let width = clusters
+        .iter()
+        .filter_map(|c| c.name.as_deref())
+        .map(str::len)
+        .max()
+        .unwrap_or(0)
+        .max("name".len());
+    let _ = writeln!(
+        sink,
+        "{:>6}  {:<width$}  {:>6}  {:>6}  sample",
+        "id", "name", "faces", "images"
+    );
+    for c in clusters {
+        let _ = writeln!(
+            sink,
+            "{:>6}  {:<width$}  {:>6}  {:>6}  {}",
+            c.id,
+            c.name.as_deref().unwrap_or("-"),
+            c.faces,
+            c.images,
+            c.sample
+        );
+    }
+}
@@ -0,0 +1,217 @@
+//! `faces scan`：扫 sources 下全部图片，逐张 SCRFD → 5 点对齐 → `MobileFaceNet`，把每张脸
+//! 的 embedding 登记进 [`FaceDb`] 并增量聚类。
+//!
+//! - 按内容 SHA-512 去重：库里已有的内容只更新最后已知位置、不重跑推理，对同一个库
+//!   反复扫描只为新照片付推理开销；未检出人脸的图同样登记（空人脸列表）
+//! - 先读 256 字节嗅探，非图片不再读余下内容；超过 `backend.face.max_image_bytes` 的
+//!   文件不读，计入 failed
+//! - 推理在 rayon 池并行；登记与聚类在主线程按路径序串行，同一批输入得到相同的簇 ID
+//! - 单脸对齐失败或嵌入输出非有限值只丢该脸；解码、检测或嵌入推理失败整图计入
+//!   failed，不登记（下次重试）——模型延迟加载，路径配错时不能把整库记成无脸
+
+use std::io::{self, Read};
+use std::sync::Arc;
+
+use image::RgbImage;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use sha2::{Digest, Sha512};
+use tracing::{debug, error};
+
+use super::FEATURE_FACES;
+use super::db::FaceDb;
+use super::report::{FacesReport, render_clusters};
+use crate::entities::SecureHash;
+use crate::entities::backend::{Backend, EntryKind};
+use crate::entities::file_info::read_fill;
+use crate::entities::uri::Location;
+use crate::usecases::config::config;
+use crate::usecases::copy::Source;
+use crate::usecases::cull::{EMBED_DIM, MIME_SNIFF_BYTES, align_face, is_image};
+use crate::usecases::face::{FaceDetector, FaceEmbedder};
+use crate::usecases::report::ReportError;
+
+/// 单张图的推理结果：每脸 (bbox, embedding)。
+type Faces = Vec<([f32; 4], [f32; EMBED_DIM])>;
+
+/// 单文件处理结果。
+enum Outcome {
+    NotImage,
+    /// 内容已在库中，跳过推理。
+    Known(SecureHash),
+    Analyzed(SecureHash, Faces),
+}
+
+/// 扫描 `sources` 并把新图的人脸登记进 `db`，簇表打印到 stdout。
+/// 返回的报告含操作后的全库簇概况；库的写盘由调用方负责。
+pub fn scan_faces(
+    scrfd: &dyn FaceDetector,
+    facenet: &dyn FaceEmbedder,
+    sources: &[Source],
+    db: &mut FaceDb,
+) -> FacesReport {
+    let face_cfg = &config().backend.face;
+    let mut report = FacesReport::default();
+    let mut entries: Vec<(Location, Arc<dyn Backend>)> = Vec::new();
+    for (root, backend) in sources {
+        for entry in backend.walk(root) {
+            match entry {
+                Ok(e) if e.kind == EntryKind::File => {
+                    report.scanned += 1;
+                    if e.size > face_cfg.max_image_bytes {
+                        let err = io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            format!(
+                                "faces: file {} bytes exceeds backend.face.max_image_bytes={}",
+                                e.size, face_cfg.max_image_bytes
+                            ),
+                        );
+                        record_failure(&mut report, e.location.display(), &err);
+                        continue;
+                    }
+                    entries.push((e.location, Arc::clone(backend)));
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    report.scanned += 1;
+                    record_failure(&mut report, root.display(), &e);
+                }
+            }
+        }
+    }
+    entries.sort_by_cached_key(|(loc, _)| loc.display());
+
+    // 只读借用 db 判断是否已知；登记留到 collect 之后。
+    let known: &FaceDb = db;
+    let outcomes: Vec<(Location, io::Result<Outcome>)> = entries
+        .into_par_iter()
+        .map(|(loc, backend)| {
+            let outcome = analyze(
+                &loc,
+                &backend,
+                known,
+                scrfd,
+                facenet,
+                face_cfg.max_image_bytes,
+            );
+            (loc, outcome)
+        })
+        .collect();
+
+    for (loc, outcome) in outcomes {
+        match outcome {
+            Ok(Outcome::NotImage) => {}
+            Ok(Outcome::Known(hash)) => {
+                report.known += 1;
+                db.relocate(&hash, loc.display());
+            }
+            Ok(Outcome::Analyzed(hash, faces)) => {
+                report.analyzed += 1;
+                report.faces_added +=
+                    db.add_image(&hash, loc.display(), &faces, face_cfg.face_cosine_min);
+            }
+            Err(e) => record_failure(&mut report, loc.display(), &e),
+        }
+    }
+    report.clusters = db.clusters();
+    log_scan_summary(&report);
+    render_clusters(&report.clusters, &mut std::io::stdout());
+    report
+}
+
+/// 单文件：嗅探 → 整读（封顶）→ SHA-512 → 已知则返回 → 解码 → 检测 → 逐脸对齐 + 嵌入。
+fn analyze(
+    loc: &Location,
+    backend: &Arc<dyn Backend>,
+    db: &FaceDb,
+    scrfd: &dyn FaceDetector,
+    facenet: &dyn FaceEmbedder,
+    max_bytes: u64,
+) -> io::Result<Outcome> {
+    let mut reader = backend.open_read(loc)?;
+    let mut bytes = vec![0u8; MIME_SNIFF_BYTES];
+    let n = read_fill(reader.as_mut(), &mut bytes)?;
+    bytes.truncate(n);
+    if !is_image(&bytes) {
+        return Ok(Outcome::NotImage);
+    }
+    // 远端 walker 报的 size 不可信：多读 1 字节即判超限。
+    reader
+        .take(max_bytes.saturating_add(1).saturating_sub(n as u64))
+        .read_to_end(&mut bytes)?;
+    if bytes.len() as u64 > max_bytes {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("faces: file exceeds backend.face.max_image_bytes={max_bytes}"),
+        ));
+    }
+    let hash = Sha512::digest(&bytes);
+    if db.contains(&hash) {
+        return Ok(Outcome::Known(hash));
+    }
+    let decoded = image::load_from_memory(&bytes)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("decode image: {e}")))?
+        .to_rgb8();
+    let detections = scrfd.detect_faces(loc.path(), &bytes)?;
+    let (bboxes, aligned): (Vec<[f32; 4]>, Vec<RgbImage>) = detections
+        .iter()
+        .filter_map(|face| Some((face.bbox, align_face(&decoded, &face.landmarks_5pt).ok()?)))
+        .unzip();
+    // 同图全部脸一批嵌入；整批失败与检测失败同口径整图报错，与 `cull` 一致。
+    let embeddings = facenet.embed_faces(loc.path(), &aligned)?;
+    let faces: Faces = bboxes
+        .into_iter()
+        .zip(embeddings)
+        // 非有限值既无法比较也无法写回 JSON：与对齐失败同样只丢该脸。
+        .filter(|(bbox, embedding)| bbox.iter().chain(embedding).all(|v| v.is_finite()))
+        .collect();
+    log_analyze(&loc.display(), detections.len(), faces.len());
+    Ok(Outcome::Analyzed(hash, faces))
+}
+
+fn record_failure(report: &mut FacesReport, path: String, e: &io::Error) {
+    let msg = e.to_string();
+    error!(
+        feature = FEATURE_FACES,
+        operation = "scan_entry",
+        result = "error",
+        source = %path,
+        error = %msg,
+        "faces item failed"
+    );
+    report.errors.push(ReportError { path, message: msg });
+    report.failed += 1;
+}
+
+/// 单图推理：SCRFD 检出数 + 成功对齐嵌入数（外部调用 req/resp）。
+#[cfg_attr(coverage_nightly, coverage(off))]
+fn log_analyze(source: &str, detections: usize, embedded: usize) {
+    debug!(
+        feature = FEATURE_FACES,
+        operation = "analyze_image",
+        result = "ok",
+        source = %source,
+        detections,
+        embedded,
+        "faces analyze image"
+    );
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+fn log_scan_summary(report: &FacesReport) {
+    debug!(
+        feature = FEATURE_FACES,
+        operation = "scan",
+        result = if report.failed == 0 { "ok" } else { "partial" },
+        scanned = report.scanned,
+        analyzed = report.analyzed,
+        known = report.known,
+        faces_added = report.faces_added,
+        clusters = report.clusters.len(),
+        failed = report.failed,
+        "faces scan summary"
+    );
+}
+
+#[cfg(test)]
+#[path = "scan_tests.rs"]
+mod tests;
@@ -0,0 +1,161 @@
+//! `scan_faces` 单测：Fake detector / embedder 按路径注入，验证登记、去重与失败计数。
+
+use std::path::Path;
+
+use camino::Utf8PathBuf;
+use tempfile::tempdir;
+
+use super::*;
+use crate::adapters::backend::local::LocalBackend;
+use crate::adapters::face::fake::{FakeFaceDetector, FakeFaceEmbedder};
+use crate::usecases::face::FaceDetection;
+
+/// `ArcFace` 模板 5 点：对齐矩阵非奇异。
+const FACE: FaceDetection = FaceDetection {
+    bbox: [0.0, 0.0, 16.0, 16.0],
+    score: 0.9,
+    landmarks_5pt: [
+        [38.2946, 51.6963],
+        [73.5318, 51.5014],
+        [56.0252, 71.7366],
+        [41.5493, 92.3655],
+        [70.7299, 92.2041],
+    ],
+};
+
+fn unit(axis: usize) -> [f32; EMBED_DIM] {
+    let mut v = [0.0; EMBED_DIM];
+    v[axis] = 1.0;
+    v
+}
+
+/// 纯色 PNG：不同 `fill` 内容不同、SHA-512 不同。
+fn write_png(path: &Path, fill: u8) -> Utf8PathBuf {
+    image::RgbImage::from_pixel(16, 16, image::Rgb([fill, fill, fill]))
+        .save(path)
+        .unwrap();
+    Utf8PathBuf::from_path_buf(path.to_path_buf()).unwrap()
+}
+
+fn source(dir: &Path) -> Source {
+    (
+        Location::Local(Utf8PathBuf::from_path_buf(dir.to_path_buf()).unwrap()),
+        LocalBackend::arc(),
+    )
+}
+
+#[test]
+fn scan_registers_faces_and_skips_non_images() {
+    let dir = tempdir().unwrap();
+    write_png(&dir.path().join("a.png"), 10);
+    write_png(&dir.path().join("b.png"), 200);
+    let empty = write_png(&dir.path().join("empty.png"), 90);
+    std::fs::write(dir.path().join("notes.txt"), b"hello").unwrap();
+    let scrfd = FakeFaceDetector::new(vec![FACE]).with_result(empty, vec![]);
+    let facenet = FakeFaceEmbedder::new(unit(0));
+    let mut db = FaceDb::default();
+
+    let report = scan_faces(&scrfd, &facenet, &[source(dir.path())], &mut db);
+    assert_eq!(report.failed, 0, "errors: {:?}", report.errors);
+    assert_eq!(
+        (
+            report.scanned,
+            report.analyzed,
+            report.known,
+            report.faces_added
+        ),
+        (4, 3, 0, 2)
+    );
+    assert_eq!(report.clusters.len(), 1);
+    assert_eq!(
+        (report.clusters[0].faces, report.clusters[0].images),
+        (2, 2)
+    );
+    assert!(
+        report.clusters[0].sample.ends_with("a.png"),
+        "{:?}",
+        report.clusters
+    );
+    assert_eq!(db.image_count(), 3);
+}
+
+/// 已登记内容不重跑推理：第二次扫描即使 detector 对该路径报错也只计 known。
+#[test]
+fn rescan_skips_known_content() {
+    let dir = tempdir().unwrap();
+    let a = write_png(&dir.path().join("a.png"), 10);
+    let facenet = FakeFaceEmbedder::new(unit(0));
+    let mut db = FaceDb::default();
+    scan_faces(
+        &FakeFaceDetector::new(vec![FACE]),
+        &facenet,
+        &[source(dir.path())],
+        &mut db,
+    );
+
+    let scrfd = FakeFaceDetector::new(vec![FACE]).with_error(a);
+    let report = scan_faces(&scrfd, &facenet, &[source(dir.path())], &mut db);
+    assert_eq!(report.failed, 0, "errors: {:?}", report.errors);
+    assert_eq!(
+        (report.analyzed, report.known, report.faces_added),
+        (0, 1, 0)
+    );
+    assert_eq!(report.clusters[0].faces, 1);
+}
+
+/// 检测 / 解码 / 嵌入失败整图计入 failed 且不登记，下次扫描重试。
+#[test]
+fn scan_counts_failures_without_recording_failed_images() {
+    let dir = tempdir().unwrap();
+    let detect_err = write_png(&dir.path().join("a.png"), 10);
+    let embed_err = write_png(&dir.path().join("b.png"), 200);
+    // PNG magic 但内容截断：嗅探判为图片，解码失败。
+    let mut broken = std::fs::read(dir.path().join("a.png")).unwrap();
+    broken.truncate(40);
+    std::fs::write(dir.path().join("c.png"), broken).unwrap();
+    let scrfd = FakeFaceDetector::new(vec![FACE]).with_error(detect_err);
+    let facenet = FakeFaceEmbedder::new(unit(0)).with_error(embed_err);
+    let mut db = FaceDb::default();
+
+    let report = scan_faces(&scrfd, &facenet, &[source(dir.path())], &mut db);
+    assert_eq!((report.scanned, report.failed), (3, 3));
+    assert_eq!((report.analyzed, report.faces_added), (0, 0));
+    assert!(report.clusters.is_empty());
+    assert_eq!(db.image_count(), 0);
+    let failed: Vec<&str> = report.errors.iter().map(|e| e.path.as_str()).collect();
+    assert!(
+        failed[0].ends_with("a.png")
+            && failed[1].ends_with("b.png")
+            && failed[2].ends_with("c.png"),
+        "{failed:?}"
+    );
+
+    // 嵌入模型恢复后重扫：b.png 未登记过，照常推理入库。
+    let report = scan_faces(
+        &FakeFaceDetector::new(vec![FACE]),
+        &FakeFaceEmbedder::new(unit(0)),
+        &[source(dir.path())],
+        &mut db,
+    );
+    assert_eq!((report.analyzed, report.faces_added), (2, 2));
+}
+
+/// 非有限 embedding 与对齐失败同样只丢该脸，不进库（否则 JSON 写不回）。
+#[test]
+fn scan_drops_non_finite_embeddings() {
+    let dir = tempdir().unwrap();
+    write_png(&dir.path().join("a.png"), 10);
+    let mut nan = unit(0);
+    nan[1] = f32::NAN;
+    let mut db = FaceDb::default();
+    let report = scan_faces(
+        &FakeFaceDetector::new(vec![FACE]),
+        &FakeFaceEmbedder::new(nan),
+        &[source(dir.path())],
+        &mut db,
+    );
+    assert_eq!(
+        (report.analyzed, report.faces_added, report.failed),
+        (1, 0, 0)
+    );
+}
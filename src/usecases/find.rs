@@ -5,19 +5,25 @@ use tracing::error;
 
 use crate::entities::backend::EntryKind;
 use crate::entities::common;
-use crate::entities::common::under_prefix;
+use crate::entities::common::{canonical_prefix, under_prefix};
+use crate::entities::exif::Exif;
 use crate::entities::file_index;
 use crate::entities::file_index::DuplicateGroup;
 use crate::entities::file_info;
 use crate::entities::uri::Location;
 
 use super::copy::Source;
-use super::report::{DuplicateGroupReport, FindReport};
+use super::copy::run::configured_chrono_offset;
+use super::find_apply::{Resolution, ResolutionRecord, resolve_one};
+use super::report::{DuplicateGroupReport, FindReport, ReportError};
+use super::survivor::{self, Member, Survivor, SurvivorPolicy};
 
 const FEATURE_FIND: &str = "find";
 
-// 返回完整 FindReport（scanned = 入索引文件数；bytes_read 来自 Index 累计；
-// groups 为 DuplicateGroup 列表），dispatch 层直接落 JSON 而无需重新统计。
+// 返回完整 FindReport（scanned = 参与比对的文件数，含 size 唯一而免读的；bytes_read 来自 Index 累计；
+// groups 为 DuplicateGroup 列表 + 每组 survivor 及其理由），dispatch 层直接落 JSON
+// 而无需重新统计。`keep` 为 `--keep` 保留策略，按顺序 tie-break（见 survivor 模块）；
+// `apply` 为 `--apply` 处置参数：给出时不打印脚本，改经 Backend 直接处置（见 find_apply）。
 //
 // # Errors
 //
@@ -27,6 +33,8 @@ pub(crate) fn find_duplicates(
     secure: bool,
     sources: Vec<Source>,
     output: Option<&Source>,
+    keep: &[SurvivorPolicy],
+    apply: Option<&Resolution>,
 ) -> common::Result<FindReport> {
     let mut index = file_index::Index::new();
 
@@ -65,12 +73,13 @@ pub(crate) fn find_duplicates(
         }
     }
 
-    for (loc, backend) in sources {
-        index.visit_location(&loc, &backend);
-    }
+    // 分档预过滤：size（walk 元数据，零读取）→ 首 4 KiB → 首尾各 4 KiB → 整文件；
+    // size 唯一的文件从不打开，大库下 bytes_read 主要花在真正的候选重复上。
+    index.visit_size_collisions(&sources);
 
     let scan_stats = index.stats();
-    let scanned = index.files().len();
+    let scanned =
+        index.files().len() + usize::try_from(scan_stats.skipped_unique_size).unwrap_or(usize::MAX);
     let bytes_read = index.bytes_read();
     debug!(
         feature = FEATURE_FIND,
@@ -80,6 +89,7 @@ pub(crate) fn find_duplicates(
         files = scanned,
         similar_files = index.similar_files().len(),
         bytes_read,
+        skipped_unique_size = scan_stats.skipped_unique_size,
         skipped_empty = scan_stats.skipped_empty,
         skipped_unreadable = scan_stats.skipped_unreadable,
         walker_errors = scan_stats.walker_errors,
@@ -101,8 +111,28 @@ pub(crate) fn find_duplicates(
     );
 
     let prefix_owned = compute_output_prefix(output);
+    let keep = normalize_prefixes(keep);
+    let decide = apply.is_some() || !keep.is_empty();
+    let plans: Vec<GroupPlan> = groups
+        .iter()
+        .map(|g| {
+            plan_group(
+                g,
+                prefix_owned.as_deref(),
+                &keep,
+                &group_members(&index, g, &keep),
+                decide,
+            )
+        })
+        .collect();
 
-    render_script(&groups, prefix_owned.as_deref(), &mut std::io::stdout());
+    let (resolutions, errors) = match apply {
+        Some(resolution) => apply_plans(&index, &groups, &plans, resolution),
+        None => {
+            render_script(&groups, &plans, &mut std::io::stdout());
+            (Vec::new(), Vec::new())
+        }
+    };
 
     debug!(
         feature = FEATURE_FIND,
@@ -114,14 +144,21 @@ pub(crate) fn find_duplicates(
 
     Ok(FindReport {
         scanned,
+        skipped_unique_size: scan_stats.skipped_unique_size,
         bytes_read,
         groups: groups
             .into_iter()
-            .map(|g| DuplicateGroupReport {
+            .zip(plans)
+            .map(|(g, plan)| DuplicateGroupReport {
                 size: g.size,
+                survivor: g.paths[plan.survivor.index].to_string(),
+                survivor_reason: plan.survivor.reason,
                 paths: g.paths.into_iter().map(|p| p.to_string()).collect(),
             })
             .collect(),
+        resolutions,
+        errors,
+        ..FindReport::default()
     })
 }
 
@@ -146,47 +183,36 @@ pub fn compute_output_prefix(output: Option<&Source>) -> Option<String> {
 /// 输出 Python 删除脚本：跨平台单一格式，消除 sh/cmd 双轨。
 /// 用户审查后取消 `# os.remove(...)` 注释，`python3 script.py` 执行；
 /// windows 路径含 `\` 在 Python 字面量按 `\\` 转义，避免 sh 风格 `\` 歧义。
-const SCRIPT_HEADER: &str = "#!/usr/bin/env python3\n\
+pub(crate) const SCRIPT_HEADER: &str = "#!/usr/bin/env python3\n\
 \"\"\"tidymedia find 删除脚本：审查后取消注释 os.remove() 行后 `python3 <file>` 执行。\"\"\"\n\
 import os\n\n";
 
-pub(crate) fn render_script(
-    same: &[DuplicateGroup],
-    output_prefix: Option<&str>,
-    sink: &mut impl Write,
-) {
+pub(crate) fn render_script(same: &[DuplicateGroup], plans: &[GroupPlan], sink: &mut impl Write) {
     if same.is_empty() {
         return;
     }
     let _ = sink.write_all(SCRIPT_HEADER.as_bytes());
     // 输入已按 size 降序（DuplicateGroup filter_and_sort 内部约定）；直接顺序遍历。
-    for group in same {
+    for (group, plan) in same.iter().zip(plans) {
         let _ = writeln!(sink, "# SIZE {}", group.size);
-        // 当指定了 output_prefix 但组内**无任何**文件位于 prefix 下（用户尚未把任一份
-        // 副本归档进 output），如果按原逻辑全发 active `os.remove(...)` 用户跑脚本即
-        // 永久数据丢失。此时把首份保留为注释作 survivor，其余仍标记为删除，让用户
-        // 至少保住一份；下方 `# SURVIVOR` 标记让审查者明白该行被特殊保护的原因。
-        let any_under_prefix = output_prefix.is_some_and(|p| {
-            group
-                .paths
-                .iter()
-                .any(|path| under_prefix(path.as_str(), p))
-        });
         for (idx, path) in group.paths.iter().enumerate() {
-            let path_str = path.as_str();
-            let escaped = escape_py_string(path_str);
-            // 把 protect / is_survivor 一并解到 match arm 上，避免下游 `if a && b && c`
-            // 的 && 短路在 LLVM 出 5 个 sub-branch BR，部分组合天然 0-hit 破坏覆盖率。
-            let (protect, is_survivor) = match output_prefix {
-                None => (true, false),
-                Some(p) if any_under_prefix => (under_prefix(path_str, p), false),
-                // 无 path 在 prefix 下：保留首份作 survivor，其余仍删
-                Some(_) => (idx == 0, idx == 0),
-            };
-            if protect {
-                if is_survivor {
-                    let _ = writeln!(sink, "# SURVIVOR (no copy under output)");
+            let escaped = escape_py_string(path.as_str());
+            if idx == plan.survivor.index {
+                match plan.marker {
+                    SurvivorMarker::Hidden => {}
+                    SurvivorMarker::Plain => {
+                        let _ = writeln!(sink, "# SURVIVOR: {}", plan.survivor.reason);
+                    }
+                    SurvivorMarker::NoCopyUnderOutput => {
+                        let _ = writeln!(
+                            sink,
+                            "# SURVIVOR (no copy under output): {}",
+                            plan.survivor.reason
+                        );
+                    }
                 }
+            }
+            if plan.protect[idx] {
                 let _ = writeln!(sink, "# os.remove(\"{escaped}\")");
             } else {
                 let _ = writeln!(sink, "os.remove(\"{escaped}\")");
@@ -196,10 +222,199 @@ pub(crate) fn render_script(
     }
 }
 
-/// Python 字符串字面量转义：`\` → `\\`、`"` → `\"`。
-/// 路径含 `\n` 等控制字符极罕见，转 `\xNN` 留给后续如有需要再加。
-fn escape_py_string(s: &str) -> String {
-    s.replace('\\', "\\\\").replace('"', "\\\"")
+/// 单组的删除计划：哪些路径受保护（注释掉 `os.remove`）、谁是 survivor、脚本里
+/// 如何标注 survivor。与 [`DuplicateGroup::paths`] 按下标一一对应。
+#[derive(Clone, Debug)]
+pub(crate) struct GroupPlan {
+    pub survivor: Survivor,
+    pub protect: Vec<bool>,
+    pub marker: SurvivorMarker,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SurvivorMarker {
+    /// 全组注释保护（无 output 且无 `--keep` / `--apply`），无需额外标注。
+    Hidden,
+    /// `# SURVIVOR: <理由>`。
+    Plain,
+    /// 指定了 output 但组内无副本在其下：survivor 被特殊保护，标注原因供审查。
+    NoCopyUnderOutput,
+}
+
+/// 决定单组的保护集与 survivor：
+/// - 有 output 且组内有副本在其下：这些副本全部保护，survivor 从中按策略选
+/// - 有 output 但无副本在其下：若全发 active `os.remove` 用户跑脚本即永久丢数据，
+///   故按策略（缺省路径序首份）选一份 survivor 保护，其余仍删
+/// - 无 output 但 `decide`（给了 `--keep` 或 `--apply`）：同上，只保护 survivor
+/// - 无 output 且不 `decide`：全部注释，由用户自行审查取舍（旧行为）
+pub(crate) fn plan_group(
+    group: &DuplicateGroup,
+    output_prefix: Option<&str>,
+    policies: &[SurvivorPolicy],
+    members: &[Member<'_>],
+    decide: bool,
+) -> GroupPlan {
+    let under: Vec<bool> = group
+        .paths
+        .iter()
+        .map(|p| output_prefix.is_some_and(|prefix| under_prefix(p.as_str(), prefix)))
+        .collect();
+    let any_under = under.contains(&true);
+    let all: Vec<usize> = (0..group.paths.len()).collect();
+    // 把四种形态解到 match arm 上，避免 `if a && b` 短路在覆盖率里留天然 0-hit 分支。
+    let (candidates, seed, marker) = match (output_prefix, any_under) {
+        (Some(_), true) => {
+            let kept = all.into_iter().filter(|&i| under[i]).collect();
+            (kept, survivor::REASON_OUTPUT_DIR, SurvivorMarker::Plain)
+        }
+        (Some(_), false) => (
+            all,
+            survivor::REASON_PATH_ORDER,
+            SurvivorMarker::NoCopyUnderOutput,
+        ),
+        (None, _) if !decide => (all, survivor::REASON_PATH_ORDER, SurvivorMarker::Hidden),
+        (None, _) => (all, survivor::REASON_PATH_ORDER, SurvivorMarker::Plain),
+    };
+    let survivor = survivor::choose(members, candidates, policies, seed);
+    let protect = (0..group.paths.len())
+        .map(|i| match (marker, any_under) {
+            (SurvivorMarker::Hidden, _) => true,
+            (_, true) => under[i],
+            (_, false) => i == survivor.index,
+        })
+        .collect();
+    GroupPlan {
+        survivor,
+        protect,
+        marker,
+    }
+}
+
+// `--apply`：逐组处置未受保护的副本。顺序执行不并行——破坏性操作失败时日志与
+// 报告按组内顺序可读；单项失败只记 error，不中断其余。
+fn apply_plans(
+    index: &file_index::Index,
+    groups: &[DuplicateGroup],
+    plans: &[GroupPlan],
+    resolution: &Resolution,
+) -> (Vec<ResolutionRecord>, Vec<ReportError>) {
+    let action = resolution.action().as_str();
+    let mut records = Vec::new();
+    let mut errors = Vec::new();
+    for (group, plan) in groups.iter().zip(plans) {
+        let survivor_path = &group.paths[plan.survivor.index];
+        let Some(survivor) = index.files().get(survivor_path) else {
+            continue;
+        };
+        for (path, _) in group.paths.iter().zip(&plan.protect).filter(|(_, p)| !**p) {
+            let Some(dup) = index.files().get(path) else {
+                continue;
+            };
+            let source = path.to_string();
+            match resolve_one(survivor, dup, resolution) {
+                Ok(target) => {
+                    let target = target.map(|t| t.display());
+                    let target_str = target.as_deref().unwrap_or_default();
+                    debug!(
+                        feature = FEATURE_FIND,
+                        operation = "apply",
+                        result = "ok",
+                        action,
+                        source,
+                        target = target_str,
+                        "duplicate resolved"
+                    );
+                    records.push(ResolutionRecord {
+                        path: source,
+                        survivor: survivor_path.to_string(),
+                        action,
+                        target,
+                    });
+                }
+                Err(e) => {
+                    let message = e.to_string();
+                    error!(
+                        feature = FEATURE_FIND,
+                        operation = "apply",
+                        result = "error",
+                        action,
+                        source,
+                        error = %message,
+                        "cannot resolve duplicate"
+                    );
+                    errors.push(ReportError {
+                        path: source,
+                        message,
+                    });
+                }
+            }
+        }
+    }
+    (records, errors)
+}
+
+// 从索引取组成员的 mtime / 后端 / 元数据；`metadata` 策略才为组成员按需打开 EXIF。
+fn group_members<'a>(
+    index: &file_index::Index,
+    group: &'a DuplicateGroup,
+    policies: &[SurvivorPolicy],
+) -> Vec<Member<'a>> {
+    let need_metadata = policies.iter().any(SurvivorPolicy::needs_metadata);
+    group
+        .paths
+        .iter()
+        .map(|path| {
+            let Some(info) = index.files().get(path) else {
+                return Member {
+                    path: path.as_str(),
+                    scheme: "local",
+                    ..Member::default()
+                };
+            };
+            let metadata_fields = if need_metadata {
+                Exif::open(info.location(), &info.backend(), configured_chrono_offset())
+                    .map_or(0, |e| e.metadata_fields())
+            } else {
+                0
+            };
+            Member {
+                path: path.as_str(),
+                scheme: info.location().scheme(),
+                modified: info.modified(),
+                metadata_fields,
+            }
+        })
+        .collect()
+}
+
+// `prefix:` 与 `--output` 同口径规范化（Local canonicalize、远端 display），
+// 否则相对路径 / 符号链接下与索引中的完整路径字面不匹配。
+fn normalize_prefixes(policies: &[SurvivorPolicy]) -> Vec<SurvivorPolicy> {
+    policies
+        .iter()
+        .map(|p| match p {
+            SurvivorPolicy::Prefix(raw) => SurvivorPolicy::Prefix(
+                raw.parse::<Location>()
+                    .map_or_else(|_| raw.clone(), |loc| canonical_prefix(&loc)),
+            ),
+            other => other.clone(),
+        })
+        .collect()
+}
+
+/// Python 字符串字面量转义：`\` → `\\`、`"` → `\"`，控制字符（含 `\n` / `\r`）→ `\xNN`。
+/// 文件名允许含换行：原样写出会截断字面量，整个脚本语法错误。
+pub(crate) fn escape_py_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            c if c.is_ascii_control() => out.push_str(&format!("\\x{:02x}", u32::from(c))),
+            c => out.push(c),
+        }
+    }
+    out
 }
 
 #[cfg(test)]
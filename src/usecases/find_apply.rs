@@ -0,0 +1,254 @@
+//! `find --apply`：经 [`Backend`] 直接处置重复副本，替代打印删除脚本（远端后端上
+//! 脚本无从执行，怪异文件名经 shell 转义也易出错）。
+//!
+//! - 处置对象与脚本里未注释的 `os.remove` 行一致（见 `find::plan_group`）：survivor
+//!   与 `--output` 下的副本从不触碰
+//! - 每个破坏性步骤前重新流式计算 survivor 与副本的 SHA-512（不复用扫描期缓存）；
+//!   不一致（扫描后被改写 / 替换）即跳过并记失败
+//! - `quarantine`：副本按其在原存储上的完整路径镜像到隔离目录下，目标已存在不覆盖；
+//!   跨后端时先复制、校验隔离副本哈希，再删除原文件
+//! - `hardlink` / `reflink` 仅本地：先在同目录建临时链接，再 rename 覆盖副本，
+//!   任一步失败副本原样保留；副本已与 survivor 同 inode 时视为已处置，不再重建链接
+
+use std::io;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use camino::Utf8Component;
+use serde_derive::Serialize;
+
+use super::copy::Source;
+use super::copy::ops::stream_copy_loc;
+use crate::entities::SecureHash;
+use crate::entities::backend::Backend;
+use crate::entities::common;
+use crate::entities::file_info::Info;
+use crate::entities::uri::Location;
+
+const LINK_TEMP_SUFFIX: &str = ".tidymedia-link";
+
+/// `find --apply` 的处置动作。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ApplyAction {
+    /// 删除副本。
+    Delete,
+    /// 移入 `--quarantine` 隔离目录。
+    Quarantine,
+    /// 以指向 survivor 的硬链接替换副本（仅本地）。
+    Hardlink,
+    /// 以 survivor 的 reflink 克隆替换副本（仅本地，需文件系统支持）。
+    Reflink,
+}
+
+impl ApplyAction {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Delete => "delete",
+            Self::Quarantine => "quarantine",
+            Self::Hardlink => "hardlink",
+            Self::Reflink => "reflink",
+        }
+    }
+}
+
+impl FromStr for ApplyAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "delete" => Ok(Self::Delete),
+            "quarantine" => Ok(Self::Quarantine),
+            "hardlink" => Ok(Self::Hardlink),
+            "reflink" => Ok(Self::Reflink),
+            _ => Err(format!(
+                "unknown apply action '{s}'; expected delete, quarantine, hardlink or reflink"
+            )),
+        }
+    }
+}
+
+/// `--apply` 的完整参数：动作 + `quarantine` 动作的隔离目录。
+pub struct Resolution {
+    action: ApplyAction,
+    quarantine: Option<Source>,
+}
+
+impl Resolution {
+    /// # Errors
+    ///
+    /// `quarantine` 动作缺隔离目录，或其它动作多给了隔离目录时返回说明文案。
+    pub fn new(action: ApplyAction, quarantine: Option<Source>) -> Result<Self, String> {
+        match (action, quarantine.is_some()) {
+            (ApplyAction::Quarantine, false) => {
+                Err("--apply quarantine requires --quarantine <DIR>".to_string())
+            }
+            (_, true) if action != ApplyAction::Quarantine => Err(format!(
+                "--quarantine only applies to --apply quarantine, not {}",
+                action.as_str()
+            )),
+            _ => Ok(Self { action, quarantine }),
+        }
+    }
+
+    #[must_use]
+    pub fn action(&self) -> ApplyAction {
+        self.action
+    }
+}
+
+/// 单条处置记录（仅成功项；失败项进 `FindReport::errors`）。
+#[derive(Debug, Clone, Serialize)]
+pub struct ResolutionRecord {
+    pub path: String,
+    pub survivor: String,
+    pub action: &'static str,
+    /// `quarantine` 的目标位置；其它动作不输出。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+}
+
+/// 处置单个副本；返回 `quarantine` 的目标位置（其它动作为 None）。
+///
+/// # Errors
+///
+/// 复核哈希不一致、后端不支持该动作、隔离目标已存在或底层 IO 失败时返回 `Err`；
+/// 除 `quarantine` 跨后端复制后删源失败外，副本均保持原样。
+pub(crate) fn resolve_one(
+    survivor: &Info,
+    dup: &Info,
+    resolution: &Resolution,
+) -> common::Result<Option<Location>> {
+    verify_same_content(survivor, dup)?;
+    match resolution.action {
+        ApplyAction::Delete => {
+            dup.backend().remove_file(dup.location())?;
+            Ok(None)
+        }
+        ApplyAction::Quarantine => {
+            let (root, backend) = resolution
+                .quarantine
+                .as_ref()
+                .expect("internal: Resolution::new requires --quarantine for quarantine");
+            quarantine(dup, root, backend).map(Some)
+        }
+        ApplyAction::Hardlink => {
+            replace_with_link(survivor, dup, |be, o, l| be.hard_link(o, l)).map(|()| None)
+        }
+        ApplyAction::Reflink => {
+            replace_with_link(survivor, dup, |be, o, l| be.reflink(o, l)).map(|()| None)
+        }
+    }
+}
+
+// 不复用扫描期缓存：重新打开两端各算一次 SHA-512，堵住"扫描后文件被改写"的窗口。
+fn verify_same_content(survivor: &Info, dup: &Info) -> io::Result<()> {
+    let keep = fresh_secure_hash(survivor.location(), &survivor.backend())?;
+    let gone = fresh_secure_hash(dup.location(), &dup.backend())?;
+    if keep == gone {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!(
+            "content changed since scan: {} no longer matches survivor {}",
+            dup.location().display(),
+            survivor.location().display()
+        )))
+    }
+}
+
+fn fresh_secure_hash(loc: &Location, backend: &Arc<dyn Backend>) -> io::Result<SecureHash> {
+    Info::open(loc, Arc::clone(backend))?.secure_hash()
+}
+
+fn quarantine(
+    dup: &Info,
+    root: &Location,
+    q_backend: &Arc<dyn Backend>,
+) -> common::Result<Location> {
+    let target = quarantine_target(root, dup.location());
+    if q_backend.exists(&target)? {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("quarantine target already exists: {}", target.display()),
+        )
+        .into());
+    }
+    let src_be = dup.backend();
+    // 两端均本地：rename（同文件系统原子，跨设备由 LocalBackend 回退 copy + remove）。
+    if matches!(
+        (dup.location(), &target),
+        (Location::Local(_), Location::Local(_))
+    ) {
+        src_be.rename(dup.location(), &target, true)?;
+        return Ok(target);
+    }
+    if let Some(parent) = target.path().parent() {
+        q_backend.mkdir_p(&target.with_path(parent.to_path_buf()))?;
+    }
+    stream_copy_loc(src_be.as_ref(), dup.location(), &target, q_backend.as_ref())?;
+    // 删原文件前确认隔离副本完整；不一致时清掉半成品，原文件不动。
+    let copied = fresh_secure_hash(&target, q_backend)?;
+    if copied != fresh_secure_hash(dup.location(), &src_be)? {
+        let _ = q_backend.remove_file(&target);
+        return Err(io::Error::other(format!(
+            "quarantine copy does not match original: {}",
+            target.display()
+        ))
+        .into());
+    }
+    src_be.remove_file(dup.location())?;
+    Ok(target)
+}
+
+// 副本在原存储上的完整路径镜像到隔离目录下（根 / 盘符 / `..` 段丢弃）；远端副本
+// 多一层 scheme 目录，避免与同路径的本地副本落到一起。
+fn quarantine_target(root: &Location, dup: &Location) -> Location {
+    let mut target = match dup {
+        Location::Local(_) => root.clone(),
+        remote => root.join_path(remote.scheme()),
+    };
+    for component in dup.path().components() {
+        if let Utf8Component::Normal(segment) = component {
+            target = target.join_path(segment);
+        }
+    }
+    target
+}
+
+// 先在副本同目录建临时链接，再 rename 覆盖副本：同目录 rename 原子，任一步失败
+// 副本原样保留（临时链接 best-effort 清理）。副本与 survivor 已是同一 inode（上次
+// apply 已链接）时直接视为已处置：POSIX rename 在两端同 inode 时什么也不做，会把
+// 临时链接留在目录里。
+fn replace_with_link(
+    survivor: &Info,
+    dup: &Info,
+    link: fn(&dyn Backend, &Location, &Location) -> io::Result<()>,
+) -> common::Result<()> {
+    let backend = dup.backend();
+    let dup_loc = dup.location();
+    if backend
+        .same_file(survivor.location(), dup_loc)
+        .unwrap_or(false)
+    {
+        return Ok(());
+    }
+    let name = dup_loc.path().file_name().unwrap_or_default();
+    let tmp = dup_loc.with_path(
+        dup_loc
+            .path()
+            .with_file_name(format!(".{name}{LINK_TEMP_SUFFIX}")),
+    );
+    link(backend.as_ref(), survivor.location(), &tmp)?;
+    if let Err(e) = backend.rename(&tmp, dup_loc, false) {
+        let _ = backend.remove_file(&tmp);
+        return Err(e.into());
+    }
+    if backend.exists(&tmp).unwrap_or(false) {
+        let _ = backend.remove_file(&tmp);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "find_apply_tests.rs"]
+mod tests;
@@ -0,0 +1,215 @@
+use std::fs;
+use std::path::Path;
+
+use camino::Utf8PathBuf;
+use tempfile::tempdir;
+
+use super::*;
+use crate::adapters::backend::fake::FakeBackend;
+use crate::adapters::backend::local::LocalBackend;
+
+fn local(p: &Path) -> Location {
+    Location::Local(Utf8PathBuf::from(p.to_str().unwrap()))
+}
+
+fn open_local(p: &Path) -> Info {
+    Info::open(&local(p), LocalBackend::arc()).unwrap()
+}
+
+// tempdir 内放两份同内容文件，返回 (survivor, dup) 路径。
+fn twin_files(dir: &Path) -> (std::path::PathBuf, std::path::PathBuf) {
+    let keep = dir.join("keep.jpg");
+    let dup = dir.join("sub").join("dup.jpg");
+    fs::create_dir_all(dup.parent().unwrap()).unwrap();
+    fs::write(&keep, b"same bytes").unwrap();
+    fs::write(&dup, b"same bytes").unwrap();
+    (keep, dup)
+}
+
+fn resolution(action: ApplyAction, quarantine: Option<Source>) -> Resolution {
+    Resolution::new(action, quarantine).unwrap()
+}
+
+#[test]
+fn parse_actions_and_validate_quarantine_pairing() {
+    for (input, want) in [
+        ("delete", ApplyAction::Delete),
+        ("Quarantine", ApplyAction::Quarantine),
+        ("hardlink", ApplyAction::Hardlink),
+        ("reflink", ApplyAction::Reflink),
+    ] {
+        assert_eq!(input.parse::<ApplyAction>().unwrap(), want);
+        assert_eq!(want.as_str(), input.to_ascii_lowercase());
+    }
+    assert!(
+        "trash"
+            .parse::<ApplyAction>()
+            .unwrap_err()
+            .contains("unknown apply action")
+    );
+
+    let dir = tempdir().unwrap();
+    let q: Source = (local(dir.path()), LocalBackend::arc());
+    let err = Resolution::new(ApplyAction::Quarantine, None)
+        .err()
+        .unwrap();
+    assert!(err.contains("requires --quarantine"), "{err}");
+    let err = Resolution::new(ApplyAction::Delete, Some(q)).err().unwrap();
+    assert!(err.contains("only applies to --apply quarantine"), "{err}");
+}
+
+#[test]
+fn delete_removes_duplicate_and_keeps_survivor() {
+    let dir = tempdir().unwrap();
+    let (keep, dup) = twin_files(dir.path());
+    let got = resolve_one(
+        &open_local(&keep),
+        &open_local(&dup),
+        &resolution(ApplyAction::Delete, None),
+    )
+    .unwrap();
+    assert!(got.is_none());
+    assert!(!dup.exists());
+    assert_eq!(fs::read(&keep).unwrap(), b"same bytes");
+}
+
+/// 扫描后副本被改写：复核 SHA-512 不一致 → 拒绝处置，副本原样保留。
+#[test]
+fn content_changed_since_scan_is_refused() {
+    let dir = tempdir().unwrap();
+    let (keep, dup) = twin_files(dir.path());
+    let (survivor, dup_info) = (open_local(&keep), open_local(&dup));
+    fs::write(&dup, b"same bytes, edited").unwrap();
+    let err =
+        resolve_one(&survivor, &dup_info, &resolution(ApplyAction::Delete, None)).unwrap_err();
+    assert!(
+        err.to_string().contains("content changed since scan"),
+        "{err}"
+    );
+    assert!(dup.exists());
+}
+
+/// 本地隔离：镜像完整路径；目标已存在时不覆盖。
+#[test]
+fn quarantine_mirrors_full_path_and_never_overwrites() {
+    let dir = tempdir().unwrap();
+    let (keep, dup) = twin_files(dir.path());
+    let qdir = tempdir().unwrap();
+    let q = || {
+        resolution(
+            ApplyAction::Quarantine,
+            Some((local(qdir.path()), LocalBackend::arc())),
+        )
+    };
+    // 根 / 盘符段丢弃，其余完整镜像。
+    let mirrored: std::path::PathBuf = dup
+        .components()
+        .filter(|c| matches!(c, std::path::Component::Normal(_)))
+        .collect();
+    let expected = qdir.path().join(mirrored);
+
+    let got = resolve_one(&open_local(&keep), &open_local(&dup), &q())
+        .unwrap()
+        .unwrap();
+    assert_eq!(got, local(&expected));
+    assert!(!dup.exists());
+    assert_eq!(fs::read(&expected).unwrap(), b"same bytes");
+
+    fs::write(&dup, b"same bytes").unwrap();
+    let err = resolve_one(&open_local(&keep), &open_local(&dup), &q()).unwrap_err();
+    assert!(err.to_string().contains("already exists"), "{err}");
+    assert!(dup.exists());
+}
+
+/// 远端副本隔离到本地：复制 + 校验后删源，目标多一层 scheme 目录。
+#[test]
+fn quarantine_across_backends_copies_then_removes() {
+    let fake = Arc::new(FakeBackend::new("smb"));
+    let keep = Location::parse("smb://nas/share/a/keep.jpg").unwrap();
+    let dup = Location::parse("smb://nas/share/b/dup.jpg").unwrap();
+    fake.add_file(keep.clone(), b"remote bytes".to_vec());
+    fake.add_file(dup.clone(), b"remote bytes".to_vec());
+    let backend: Arc<dyn Backend> = fake.clone();
+    let qdir = tempdir().unwrap();
+    let q = resolution(
+        ApplyAction::Quarantine,
+        Some((local(qdir.path()), LocalBackend::arc())),
+    );
+
+    let survivor = Info::open(&keep, Arc::clone(&backend)).unwrap();
+    let dup_info = Info::open(&dup, Arc::clone(&backend)).unwrap();
+    let got = resolve_one(&survivor, &dup_info, &q).unwrap().unwrap();
+    let expected = qdir
+        .path()
+        .join("smb")
+        .join(dup.path().as_str().trim_start_matches('/'));
+    assert_eq!(got, local(&expected));
+    assert_eq!(fs::read(&expected).unwrap(), b"remote bytes");
+    assert!(fake.read_bytes(&dup).is_none());
+    assert!(fake.read_bytes(&keep).is_some());
+}
+
+#[test]
+fn hardlink_replaces_duplicate_in_place() {
+    let dir = tempdir().unwrap();
+    let (keep, dup) = twin_files(dir.path());
+    resolve_one(
+        &open_local(&keep),
+        &open_local(&dup),
+        &resolution(ApplyAction::Hardlink, None),
+    )
+    .unwrap();
+    assert_eq!(fs::read(&dup).unwrap(), b"same bytes");
+    assert!(!dup.with_file_name(".dup.jpg.tidymedia-link").exists());
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        let (a, b) = (fs::metadata(&keep).unwrap(), fs::metadata(&dup).unwrap());
+        assert_eq!(
+            a.ino(),
+            b.ino(),
+            "duplicate must now share the survivor inode"
+        );
+    }
+}
+
+/// 第二次 apply 时副本已是 survivor 的硬链接：直接视为已处置，不留临时链接。
+#[test]
+fn hardlink_apply_twice_is_a_no_op() {
+    let dir = tempdir().unwrap();
+    let (keep, dup) = twin_files(dir.path());
+    for _ in 0..2 {
+        resolve_one(
+            &open_local(&keep),
+            &open_local(&dup),
+            &resolution(ApplyAction::Hardlink, None),
+        )
+        .unwrap();
+        assert!(!dup.with_file_name(".dup.jpg.tidymedia-link").exists());
+    }
+    assert_eq!(fs::read(&dup).unwrap(), b"same bytes");
+    #[cfg(unix)]
+    assert!(
+        LocalBackend::new()
+            .same_file(&local(&keep), &local(&dup))
+            .unwrap()
+    );
+}
+
+/// 远端不支持链接：报 Unsupported，副本原样保留。
+#[test]
+fn link_actions_on_remote_backend_leave_duplicate_intact() {
+    let fake = Arc::new(FakeBackend::new("smb"));
+    let keep = Location::parse("smb://nas/share/keep.jpg").unwrap();
+    let dup = Location::parse("smb://nas/share/dup.jpg").unwrap();
+    fake.add_file(keep.clone(), b"x".to_vec());
+    fake.add_file(dup.clone(), b"x".to_vec());
+    let backend: Arc<dyn Backend> = fake.clone();
+    let survivor = Info::open(&keep, Arc::clone(&backend)).unwrap();
+    let dup_info = Info::open(&dup, backend).unwrap();
+    for action in [ApplyAction::Hardlink, ApplyAction::Reflink] {
+        let err = resolve_one(&survivor, &dup_info, &resolution(action, None)).unwrap_err();
+        assert!(err.to_string().contains("only supported on local"), "{err}");
+    }
+    assert_eq!(fake.read_bytes(&dup).unwrap(), b"x");
+}
@@ -0,0 +1,243 @@
+//! `find --similar`：按感知哈希找近似重复图片——缩放后转发的副本、重新编码的 JPEG、
+//! 同一张照片的截图。字节级判等（`find` 默认路径）找不到这些。
+//!
+//! - 复用 cull 的 DCT pHash + Union-Find（汉明距离 ≤ `max_hamming` 传递成组）
+//! - 只看 magic-bytes 判为图片的文件：先读 256 字节嗅探，非图片不再读余下内容；
+//!   超过 `backend.face.max_image_bytes` 的文件不读，防整图解码 OOM
+//! - 组内按 像素数 → 文件大小 → 是否带 EXIF → 路径序 排列，首个为建议保留的原图；
+//!   EXIF 只对成组成员解析
+//! - 近似不等于相同：脚本里全部删除行保持注释，只供人工取舍
+
+use std::cmp::Reverse;
+use std::io;
+use std::io::{Read, Write};
+use std::sync::Arc;
+
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use tracing::{debug, warn};
+
+use super::copy::Source;
+use super::copy::run::configured_chrono_offset;
+use super::cull::{MIME_SNIFF_BYTES, group_by_hash, hamming, is_image, phash};
+use super::find::{SCRIPT_HEADER, escape_py_string};
+use super::report::{FindReport, SimilarGroupReport, SimilarMemberReport};
+use crate::entities::backend::{Backend, EntryKind};
+use crate::entities::exif::Exif;
+use crate::entities::file_info::read_fill;
+use crate::entities::uri::Location;
+use crate::usecases::config::config;
+
+const FEATURE_FIND: &str = "find";
+
+/// 成功解码并算出 pHash 的图片。
+struct Hashed {
+    location: Location,
+    backend: Arc<dyn Backend>,
+    size: u64,
+    width: u32,
+    height: u32,
+    hash: u64,
+}
+
+/// 单文件扫描结果：(读取字节数, 非图片时为 None)。
+type ScanOutcome = io::Result<(u64, Option<Hashed>)>;
+
+/// 扫描 `sources` 下的图片并按 pHash 近似分组，打印审查脚本到 stdout。
+/// `scanned` = 触达的非空文件数；`bytes_read` = 嗅探 + 整图读取的字节数。
+pub(crate) fn find_similar(sources: &[Source], max_hamming: u8) -> FindReport {
+    let max_bytes = config().backend.face.max_image_bytes;
+    let entries = walk_sources(sources, "similar_walk");
+    let scanned = entries.len();
+
+    // 解码是 CPU 大头：与 cull 同走全局 rayon 池；字节与解码图算完即 drop。
+    let outcomes: Vec<(Location, ScanOutcome)> = entries
+        .into_par_iter()
+        .filter(|(_, _, size)| *size <= max_bytes)
+        .map(|(loc, backend, size)| {
+            let outcome = hash_image(&loc, backend, size);
+            (loc, outcome)
+        })
+        .collect();
+    let mut bytes_read = 0;
+    let mut images = Vec::new();
+    for (loc, outcome) in outcomes {
+        match outcome {
+            Ok((read, hashed)) => {
+                bytes_read += read;
+                images.extend(hashed);
+            }
+            Err(e) => {
+                let loc_str = loc.display();
+                warn!(
+                    feature = FEATURE_FIND,
+                    operation = "similar_hash",
+                    result = "skipped_unreadable",
+                    location = %loc_str,
+                    error = %e,
+                    "image could not be hashed",
+                );
+            }
+        }
+    }
+
+    let hashes: Vec<u64> = images.iter().map(|h| h.hash).collect();
+    let mut groups: Vec<SimilarGroupReport> = group_by_hash(&hashes, max_hamming)
+        .into_iter()
+        .filter(|members| members.len() > 1)
+        .map(|members| group_report(&images, members))
+        .collect();
+    groups.sort_by(|a, b| a.keep.cmp(&b.keep));
+    debug!(
+        feature = FEATURE_FIND,
+        operation = "similar_group",
+        result = "ok",
+        scanned,
+        images = images.len(),
+        groups = groups.len(),
+        max_hamming,
+        bytes_read,
+        "near-duplicate groups discovered"
+    );
+
+    render_similar_script(&groups, max_hamming, &mut std::io::stdout());
+    FindReport {
+        scanned,
+        bytes_read,
+        similar_groups: groups,
+        ..FindReport::default()
+    }
+}
+
+/// 收集 `sources` 下所有非空文件 (位置, backend, size)；walker 报错只记日志。
+pub(super) fn walk_sources(
+    sources: &[Source],
+    operation: &'static str,
+) -> Vec<(Location, Arc<dyn Backend>, u64)> {
+    let mut entries = Vec::new();
+    for (root, backend) in sources {
+        for entry in backend.walk(root) {
+            match entry {
+                Ok(e) if e.kind == EntryKind::File && e.size > 0 => {
+                    entries.push((e.location, Arc::clone(backend), e.size));
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    let root_str = root.display();
+                    warn!(
+                        feature = FEATURE_FIND,
+                        operation,
+                        result = "walker_error",
+                        root = %root_str,
+                        error = %e,
+                        "walker reported an error entry",
+                    );
+                }
+            }
+        }
+    }
+    entries
+}
+
+// 先嗅探头部：非图片（视频、文档）只读 256 字节即返回。
+fn hash_image(loc: &Location, backend: Arc<dyn Backend>, size: u64) -> ScanOutcome {
+    let mut reader = backend.open_read(loc)?;
+    let mut bytes = vec![0u8; MIME_SNIFF_BYTES];
+    let n = read_fill(reader.as_mut(), &mut bytes)?;
+    bytes.truncate(n);
+    if !is_image(&bytes) {
+        return Ok((n as u64, None));
+    }
+    reader.read_to_end(&mut bytes)?;
+    let read = bytes.len() as u64;
+    let img = image::load_from_memory(&bytes)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("decode image: {e}")))?
+        .to_rgb8();
+    Ok((
+        read,
+        Some(Hashed {
+            location: loc.clone(),
+            backend,
+            size,
+            width: img.width(),
+            height: img.height(),
+            hash: phash(&img),
+        }),
+    ))
+}
+
+fn group_report(images: &[Hashed], indices: Vec<usize>) -> SimilarGroupReport {
+    let offset = configured_chrono_offset();
+    let mut members: Vec<(&Hashed, bool)> = indices
+        .into_iter()
+        .map(|i| {
+            let img = &images[i];
+            let has_exif = Exif::open(&img.location, &img.backend, offset)
+                .is_ok_and(|e| e.metadata_fields() > 0);
+            (img, has_exif)
+        })
+        .collect();
+    sort_by_quality(&mut members);
+    let keep = members[0].0;
+    SimilarGroupReport {
+        keep: keep.location.display(),
+        members: members
+            .into_iter()
+            .map(|(img, has_exif)| SimilarMemberReport {
+                path: img.location.display(),
+                size: img.size,
+                width: img.width,
+                height: img.height,
+                has_exif,
+                distance: hamming(keep.hash, img.hash),
+            })
+            .collect(),
+    }
+}
+
+// 像素数多者优先（缩放副本只会更小），再比文件大小（重压缩副本更小）、EXIF 有无，
+// 最后路径序保证输出稳定。
+fn sort_by_quality(members: &mut [(&Hashed, bool)]) {
+    members.sort_by_cached_key(|(img, has_exif)| {
+        (
+            Reverse(u64::from(img.width) * u64::from(img.height)),
+            Reverse(img.size),
+            Reverse(*has_exif),
+            img.location.display(),
+        )
+    });
+}
+
+// 组内首个为建议保留者，只写注释；其余成员的 `os.remove` 一律注释掉，行尾附
+// 与保留者的距离与元数据，供人工逐行取舍。
+fn render_similar_script(groups: &[SimilarGroupReport], max_hamming: u8, sink: &mut impl Write) {
+    if groups.is_empty() {
+        return;
+    }
+    let _ = sink.write_all(SCRIPT_HEADER.as_bytes());
+    for group in groups {
+        let _ = writeln!(
+            sink,
+            "# SIMILAR {} images, max distance {max_hamming}",
+            group.members.len()
+        );
+        for (idx, m) in group.members.iter().enumerate() {
+            let escaped = escape_py_string(&m.path);
+            let exif = if m.has_exif { "exif" } else { "no exif" };
+            let meta = format!("{}x{}, {} bytes, {exif}", m.width, m.height, m.size);
+            if idx == 0 {
+                let _ = writeln!(sink, "# KEEP: \"{escaped}\"  # {meta}");
+            } else {
+                let _ = writeln!(
+                    sink,
+                    "# os.remove(\"{escaped}\")  # distance {}, {meta}",
+                    m.distance
+                );
+            }
+        }
+        let _ = writeln!(sink);
+    }
+}
+
+#[cfg(test)]
+#[path = "find_similar_tests.rs"]
+mod tests;
@@ -0,0 +1,139 @@
+use camino::Utf8PathBuf;
+use image::{ImageFormat, RgbImage};
+use tempfile::tempdir;
+
+use super::*;
+use crate::adapters::backend::local::LocalBackend;
+
+fn gradient(side: u32) -> RgbImage {
+    RgbImage::from_fn(side, side, |x, y| {
+        let v = u8::try_from((x + y) * 255 / (2 * side)).unwrap();
+        image::Rgb([v, v / 2, 255 - v])
+    })
+}
+
+fn checker(side: u32) -> RgbImage {
+    RgbImage::from_fn(side, side, |x, y| {
+        if (x / 16 + y / 16) % 2 == 0 {
+            image::Rgb([0, 0, 0])
+        } else {
+            image::Rgb([255, 255, 255])
+        }
+    })
+}
+
+fn hashed(path: &str, width: u32, size: u64) -> Hashed {
+    Hashed {
+        location: Location::Local(Utf8PathBuf::from(path)),
+        backend: LocalBackend::arc(),
+        size,
+        width,
+        height: width,
+        hash: 0,
+    }
+}
+
+/// 原图 + 缩小重编码副本成组，原图排首位；无关图片与非图片文件不入组。
+#[test]
+fn groups_resized_reencoded_copy_with_original_first() {
+    let dir = tempdir().unwrap();
+    let original = dir.path().join("original.png");
+    gradient(256).save(&original).unwrap();
+    let small = image::imageops::resize(
+        &gradient(256),
+        96,
+        96,
+        image::imageops::FilterType::Triangle,
+    );
+    small
+        .save_with_format(dir.path().join("whatsapp.jpg"), ImageFormat::Jpeg)
+        .unwrap();
+    checker(256).save(dir.path().join("unrelated.png")).unwrap();
+    std::fs::write(dir.path().join("notes.txt"), b"not an image").unwrap();
+
+    let root = Location::Local(Utf8PathBuf::from(dir.path().to_str().unwrap()));
+    let report = find_similar(&[(root, LocalBackend::arc())], 10);
+
+    assert_eq!(report.scanned, 4);
+    assert!(report.groups.is_empty());
+    assert_eq!(report.similar_groups.len(), 1, "{report:?}");
+    let group = &report.similar_groups[0];
+    assert!(group.keep.ends_with("original.png"), "{group:?}");
+    assert_eq!(group.members.len(), 2);
+    assert_eq!(
+        (group.members[0].width, group.members[0].distance),
+        (256, 0)
+    );
+    assert!(group.members[1].path.ends_with("whatsapp.jpg"));
+    assert_eq!(group.members[1].width, 96);
+    assert!(group.members[1].distance <= 10);
+}
+
+#[test]
+fn sort_by_quality_prefers_pixels_then_size_then_exif_then_path() {
+    let (big, heavy, light) = (
+        hashed("/z/big.jpg", 4000, 10),
+        hashed("/a/heavy.jpg", 1000, 900),
+        hashed("/b/light.jpg", 1000, 100),
+    );
+    let (plain, with_exif) = (hashed("/a/x.jpg", 1000, 100), hashed("/b/x.jpg", 1000, 100));
+    let mut members = vec![
+        (&light, true),
+        (&heavy, false),
+        (&big, false),
+        (&plain, false),
+        (&with_exif, true),
+    ];
+    sort_by_quality(&mut members);
+    let order: Vec<String> = members.iter().map(|(h, _)| h.location.display()).collect();
+    assert_eq!(
+        order,
+        [
+            "/z/big.jpg",
+            "/a/heavy.jpg",
+            "/b/light.jpg",
+            "/b/x.jpg",
+            "/a/x.jpg"
+        ]
+    );
+}
+
+/// 近似组的删除行全部注释：保留者只有 KEEP 注释，其余附距离与元数据。
+#[test]
+fn render_similar_script_comments_out_every_removal() {
+    let groups = [SimilarGroupReport {
+        keep: "/a/orig.jpg".into(),
+        members: vec![
+            SimilarMemberReport {
+                path: "/a/orig.jpg".into(),
+                size: 3000,
+                width: 400,
+                height: 300,
+                has_exif: true,
+                distance: 0,
+            },
+            SimilarMemberReport {
+                path: "/b/copy.jpg".into(),
+                size: 900,
+                width: 200,
+                height: 150,
+                has_exif: false,
+                distance: 3,
+            },
+        ],
+    }];
+    let mut out = Vec::new();
+    render_similar_script(&groups, 10, &mut out);
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.starts_with("#!/usr/bin/env python3"));
+    assert!(out.contains("# SIMILAR 2 images, max distance 10\n"));
+    assert!(out.contains("# KEEP: \"/a/orig.jpg\"  # 400x300, 3000 bytes, exif\n"));
+    assert!(
+        out.contains("# os.remove(\"/b/copy.jpg\")  # distance 3, 200x150, 900 bytes, no exif\n")
+    );
+    assert!(!out.lines().any(|l| l.starts_with("os.remove")));
+
+    let mut empty = Vec::new();
+    render_similar_script(&[], 10, &mut empty);
+    assert!(empty.is_empty());
+}
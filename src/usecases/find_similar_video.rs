@@ -0,0 +1,295 @@
+//! `find --similar-video`：按关键帧指纹找近似重复视频，**仅限无需视频解码即可取帧的
+//! 文件**——MJPEG 编码的 MOV / MP4 / AVI（老式相机、行车记录仪）改封装、缩放、重压缩
+//! 后的副本，以及带封面缩略图的 MP4。字节级判等（`find` 默认路径）找不到这些。
+//!
+//! - 指纹 = 按时间均匀抽取的至多 [`VIDEO_FRAMES`] 帧的 pHash 序列 + 容器时长；帧由
+//!   `entities::keyframes` 纯 Rust 抽取（MJPEG 轨，否则 MP4 封面缩略图兜底）
+//! - 手机 / 聊天软件转码出的 H.264 / HEVC 没有纯 Rust 解码器：计入
+//!   `skipped_unsupported_video` 并 warn 汇总，不猜——这类视频本模式找不到
+//! - 两段视频相近 = 时长差在容差内 + 帧数相同 + 逐帧汉明距离均值 ≤ `max_hamming`；
+//!   复用 cull 的 Union-Find 传递成组
+//! - 组内按 首帧像素数 → 文件大小 → 路径序 排列，首个为建议保留者
+//! - 同 `--similar`：脚本里全部删除行保持注释，只供人工取舍
+
+use std::cmp::Reverse;
+use std::io;
+use std::io::Write;
+use std::sync::Arc;
+
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use tracing::{debug, warn};
+
+use super::copy::Source;
+use super::cull::{MIME_SNIFF_BYTES, group_by, hamming, phash};
+use super::find::{SCRIPT_HEADER, escape_py_string};
+use super::find_similar::walk_sources;
+use super::report::{FindReport, SimilarVideoGroupReport, SimilarVideoMemberReport};
+use crate::entities::backend::Backend;
+use crate::entities::exif::is_video;
+use crate::entities::keyframes::extract_keyframes;
+use crate::entities::uri::Location;
+
+const FEATURE_FIND: &str = "find";
+/// 每段视频抽取的帧数上限。
+const VIDEO_FRAMES: usize = 8;
+/// 时长容差：绝对 1 秒与相对 2% 取大者（转码 / 剪掉片头尾黑帧常带来小幅差异）。
+const DURATION_SLACK_MS: u64 = 1000;
+const DURATION_SLACK_PERCENT: u64 = 2;
+
+/// 一段视频的关键帧指纹。
+struct Fingerprint {
+    location: Location,
+    size: u64,
+    /// 首帧尺寸，代表视频分辨率。
+    width: u32,
+    height: u32,
+    duration_ms: u64,
+    hashes: Vec<u64>,
+}
+
+enum Scanned {
+    NotVideo,
+    /// 是视频但抽不出可解码的帧（帧间编码且无封面）。
+    Unsupported,
+    Print(Fingerprint),
+}
+
+/// 扫描 `sources` 下的视频并按关键帧指纹近似分组，打印审查脚本到 stdout。
+/// `scanned` = 触达的非空文件数；`bytes_read` = 嗅探 + 抽出帧的字节数（不含容器索引）。
+pub(crate) fn find_similar_videos(sources: &[Source], max_hamming: u8) -> FindReport {
+    let entries = walk_sources(sources, "similar_video_walk");
+    let scanned = entries.len();
+
+    let outcomes: Vec<(Location, io::Result<(u64, Scanned)>)> = entries
+        .into_par_iter()
+        .map(|(loc, backend, size)| {
+            let outcome = fingerprint(&loc, &backend, size);
+            (loc, outcome)
+        })
+        .collect();
+    let mut bytes_read = 0;
+    let mut skipped_unsupported_video = 0;
+    let mut prints = Vec::new();
+    for (loc, outcome) in outcomes {
+        let loc_str = loc.display();
+        match outcome {
+            Ok((read, scanned)) => {
+                bytes_read += read;
+                match scanned {
+                    Scanned::NotVideo => {}
+                    Scanned::Unsupported => {
+                        skipped_unsupported_video += 1;
+                        debug!(
+                            feature = FEATURE_FIND,
+                            operation = "similar_video_frames",
+                            result = "skipped_unsupported",
+                            location = %loc_str,
+                            "no decodable keyframes (inter-frame codec without cover art)",
+                        );
+                    }
+                    Scanned::Print(print) => prints.push(print),
+                }
+            }
+            Err(e) => {
+                warn!(
+                    feature = FEATURE_FIND,
+                    operation = "similar_video_frames",
+                    result = "skipped_unreadable",
+                    location = %loc_str,
+                    error = %e,
+                    "video could not be fingerprinted",
+                );
+            }
+        }
+    }
+
+    let mut groups: Vec<SimilarVideoGroupReport> = group_by(prints.len(), |i, j| {
+        videos_close(&prints[i], &prints[j], max_hamming)
+    })
+    .into_iter()
+    .filter(|members| members.len() > 1)
+    .map(|members| group_report(&prints, members))
+    .collect();
+    groups.sort_by(|a, b| a.keep.cmp(&b.keep));
+    debug!(
+        feature = FEATURE_FIND,
+        operation = "similar_video_group",
+        result = "ok",
+        scanned,
+        videos = prints.len(),
+        skipped_unsupported_video,
+        groups = groups.len(),
+        max_hamming,
+        bytes_read,
+        "near-duplicate video groups discovered"
+    );
+
+    if skipped_unsupported_video > 0 {
+        log_unsupported_summary(skipped_unsupported_video);
+    }
+    render_similar_video_script(&groups, max_hamming, &mut std::io::stdout());
+    FindReport {
+        scanned,
+        bytes_read,
+        skipped_unsupported_video,
+        similar_video_groups: groups,
+        ..FindReport::default()
+    }
+}
+
+// 不支持的视频逐个只记 debug；汇总一条 warn，免得用户把"无分组"误读成"无重复"。
+#[cfg_attr(coverage_nightly, coverage(off))]
+fn log_unsupported_summary(skipped_unsupported_video: u64) {
+    warn!(
+        feature = FEATURE_FIND,
+        operation = "similar_video_frames",
+        result = "skipped_unsupported",
+        skipped_unsupported_video,
+        "videos without MJPEG frames or cover art (e.g. H.264/HEVC) were not compared",
+    );
+}
+
+// 先嗅探：非视频只读头部即返回；帧 JPEG 解码失败视为损坏（Err）而非不支持。
+fn fingerprint(
+    loc: &Location,
+    backend: &Arc<dyn Backend>,
+    size: u64,
+) -> io::Result<(u64, Scanned)> {
+    let mut reader = backend.open_read(loc)?;
+    let sniffed = size.min(MIME_SNIFF_BYTES as u64);
+    if !is_video(reader.as_mut())? {
+        return Ok((sniffed, Scanned::NotVideo));
+    }
+    let Some(keyframes) = extract_keyframes(reader.as_mut(), VIDEO_FRAMES) else {
+        return Ok((sniffed, Scanned::Unsupported));
+    };
+    let read = sniffed + keyframes.frames.iter().map(|f| f.len() as u64).sum::<u64>();
+    let mut dims = (0, 0);
+    let mut hashes = Vec::with_capacity(keyframes.frames.len());
+    for frame in &keyframes.frames {
+        let img = image::load_from_memory(frame)
+            .map_err(|e| {
+                io::Error::new(io::ErrorKind::InvalidData, format!("decode keyframe: {e}"))
+            })?
+            .to_rgb8();
+        if hashes.is_empty() {
+            dims = img.dimensions();
+        }
+        hashes.push(phash(&img));
+    }
+    Ok((
+        read,
+        Scanned::Print(Fingerprint {
+            location: loc.clone(),
+            size,
+            width: dims.0,
+            height: dims.1,
+            duration_ms: keyframes.duration_ms,
+            hashes,
+        }),
+    ))
+}
+
+fn videos_close(a: &Fingerprint, b: &Fingerprint, max_hamming: u8) -> bool {
+    durations_close(a.duration_ms, b.duration_ms)
+        && mean_hamming(a, b).is_some_and(|d| d <= u32::from(max_hamming))
+}
+
+// 任一侧时长缺失（0）时不以时长否决，只看帧。
+fn durations_close(a_ms: u64, b_ms: u64) -> bool {
+    if a_ms == 0 || b_ms == 0 {
+        return true;
+    }
+    let slack = DURATION_SLACK_MS.max(a_ms.max(b_ms) * DURATION_SLACK_PERCENT / 100);
+    a_ms.abs_diff(b_ms) <= slack
+}
+
+// 逐帧距离均值；帧数不同（如一方只有封面）不可比。
+fn mean_hamming(a: &Fingerprint, b: &Fingerprint) -> Option<u32> {
+    if a.hashes.len() != b.hashes.len() || a.hashes.is_empty() {
+        return None;
+    }
+    let total: u32 = a
+        .hashes
+        .iter()
+        .zip(&b.hashes)
+        .map(|(x, y)| hamming(*x, *y))
+        .sum();
+    Some(total / u32::try_from(a.hashes.len()).ok()?)
+}
+
+fn group_report(prints: &[Fingerprint], indices: Vec<usize>) -> SimilarVideoGroupReport {
+    let mut members: Vec<&Fingerprint> = indices.into_iter().map(|i| &prints[i]).collect();
+    sort_by_quality(&mut members);
+    let keep = members[0];
+    SimilarVideoGroupReport {
+        keep: keep.location.display(),
+        members: members
+            .into_iter()
+            .map(|v| SimilarVideoMemberReport {
+                path: v.location.display(),
+                size: v.size,
+                width: v.width,
+                height: v.height,
+                duration_ms: v.duration_ms,
+                // 传递成组的成员帧数必然相同。
+                distance: mean_hamming(keep, v).unwrap_or(0),
+            })
+            .collect(),
+    }
+}
+
+// 分辨率高者优先（缩放副本只会更小），再比文件大小（重压缩副本更小），路径序兜底。
+fn sort_by_quality(members: &mut [&Fingerprint]) {
+    members.sort_by_cached_key(|v| {
+        (
+            Reverse(u64::from(v.width) * u64::from(v.height)),
+            Reverse(v.size),
+            v.location.display(),
+        )
+    });
+}
+
+// 格式同 `--similar` 的脚本，元数据多一列时长。
+fn render_similar_video_script(
+    groups: &[SimilarVideoGroupReport],
+    max_hamming: u8,
+    sink: &mut impl Write,
+) {
+    if groups.is_empty() {
+        return;
+    }
+    let _ = sink.write_all(SCRIPT_HEADER.as_bytes());
+    for group in groups {
+        let _ = writeln!(
+            sink,
+            "# SIMILAR VIDEO {} videos, max distance {max_hamming}",
+            group.members.len()
+        );
+        for (idx, m) in group.members.iter().enumerate() {
+            let escaped = escape_py_string(&m.path);
+            let meta = format!(
+                "{}x{}, {}.{} s, {} bytes",
+                m.width,
+                m.height,
+                m.duration_ms / 1000,
+                m.duration_ms % 1000 / 100,
+                m.size
+            );
+            if idx == 0 {
+                let _ = writeln!(sink, "# KEEP: \"{escaped}\"  # {meta}");
+            } else {
+                let _ = writeln!(
+                    sink,
+                    "# os.remove(\"{escaped}\")  # distance {}, {meta}",
+                    m.distance
+                );
+            }
+        }
+        let _ = writeln!(sink);
+    }
+}
+
+#[cfg(test)]
+#[path = "find_similar_video_tests.rs"]
+mod tests;
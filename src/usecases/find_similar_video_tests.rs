@@ -0,0 +1,153 @@
+use std::io::Cursor;
+
+use camino::Utf8PathBuf;
+use image::{ImageFormat, RgbImage};
+use tempfile::tempdir;
+
+use super::*;
+use crate::adapters::backend::local::LocalBackend;
+
+fn scene(side: u32, shift: u32) -> RgbImage {
+    RgbImage::from_fn(side, side, |x, y| {
+        let v = u8::try_from(((x + shift) * 2 + y) * 255 / (4 * side)).unwrap_or(255);
+        image::Rgb([v, v / 2, 255 - v])
+    })
+}
+
+fn checker(side: u32) -> RgbImage {
+    RgbImage::from_fn(side, side, |x, y| {
+        let v = if (x / 8 + y / 8) % 2 == 0 { 0 } else { 255 };
+        image::Rgb([v, v, v])
+    })
+}
+
+fn jpeg(img: &RgbImage) -> Vec<u8> {
+    let mut out = Cursor::new(Vec::new());
+    img.write_to(&mut out, ImageFormat::Jpeg).unwrap();
+    out.into_inner()
+}
+
+fn chunk(id: &[u8; 4], data: &[u8]) -> Vec<u8> {
+    let len = u32::try_from(data.len()).unwrap();
+    let pad: &[u8] = if data.len() % 2 == 1 { &[0] } else { &[] };
+    [id.as_slice(), &len.to_le_bytes(), data, pad].concat()
+}
+
+/// 最小 MJPEG AVI：avih（每帧 40 ms）+ movi 下逐帧 `00dc`。
+fn mjpeg_avi(frames: &[RgbImage]) -> Vec<u8> {
+    let mut avih = vec![0u8; 56];
+    avih[..4].copy_from_slice(&40_000u32.to_le_bytes());
+    avih[16..20].copy_from_slice(&u32::try_from(frames.len()).unwrap().to_le_bytes());
+    let hdrl = chunk(
+        b"LIST",
+        &[b"hdrl".as_slice(), &chunk(b"avih", &avih)].concat(),
+    );
+    let movi: Vec<u8> = frames
+        .iter()
+        .flat_map(|f| chunk(b"00dc", &jpeg(f)))
+        .collect();
+    let movi = chunk(b"LIST", &[b"movi".as_slice(), &movi].concat());
+    let body = [b"AVI ".as_slice(), &hdrl, &movi].concat();
+    [
+        b"RIFF".as_slice(),
+        &u32::try_from(body.len()).unwrap().to_le_bytes(),
+        &body,
+    ]
+    .concat()
+}
+
+fn print(path: &str, width: u32, size: u64, duration_ms: u64, hashes: Vec<u64>) -> Fingerprint {
+    Fingerprint {
+        location: Location::Local(Utf8PathBuf::from(path)),
+        size,
+        width,
+        height: width,
+        duration_ms,
+        hashes,
+    }
+}
+
+/// 缩放重编码副本与原片成组（原片排首位）；无关视频、H.264 无封面、非视频不入组。
+#[test]
+fn groups_rescaled_copy_and_counts_unsupported_codecs() {
+    let dir = tempdir().unwrap();
+    let frames: Vec<RgbImage> = (0..4).map(|i| scene(128, i * 32)).collect();
+    let small: Vec<RgbImage> = frames
+        .iter()
+        .map(|f| image::imageops::resize(f, 64, 64, image::imageops::FilterType::Triangle))
+        .collect();
+    std::fs::write(dir.path().join("original.avi"), mjpeg_avi(&frames)).unwrap();
+    std::fs::write(dir.path().join("small.avi"), mjpeg_avi(&small)).unwrap();
+    let other = vec![checker(128); 4];
+    std::fs::write(dir.path().join("other.avi"), mjpeg_avi(&other)).unwrap();
+    // ftyp 之后无 moov：嗅探为 video/mp4，但抽不出帧。
+    let mut h264 = b"\0\0\0\x18ftypisom\0\0\0\0isomavc1".to_vec();
+    h264.extend_from_slice(&[0u8; 64]);
+    std::fs::write(dir.path().join("h264.mp4"), h264).unwrap();
+    std::fs::write(dir.path().join("notes.txt"), b"not a video").unwrap();
+
+    let root = Location::Local(Utf8PathBuf::from(dir.path().to_str().unwrap()));
+    let report = find_similar_videos(&[(root, LocalBackend::arc())], 10);
+
+    assert_eq!(report.scanned, 5);
+    assert_eq!(report.skipped_unsupported_video, 1);
+    assert!(report.similar_groups.is_empty());
+    assert_eq!(report.similar_video_groups.len(), 1, "{report:?}");
+    let group = &report.similar_video_groups[0];
+    assert!(group.keep.ends_with("original.avi"), "{group:?}");
+    assert_eq!(group.members.len(), 2);
+    assert_eq!(
+        (group.members[0].width, group.members[0].duration_ms),
+        (128, 160)
+    );
+    assert!(group.members[1].path.ends_with("small.avi"));
+    assert!(group.members[1].distance <= 10);
+}
+
+/// 时长容差取 1 s 与 2% 的大者；缺失时长不否决；帧数不同不可比。
+#[test]
+fn closeness_requires_compatible_duration_and_frame_count() {
+    assert!(durations_close(60_000, 61_000));
+    assert!(!durations_close(60_000, 61_300));
+    assert!(durations_close(600_000, 612_000));
+    assert!(!durations_close(600_000, 613_000));
+    assert!(durations_close(0, 99_000));
+
+    let a = print("/a.mov", 640, 10, 5_000, vec![0, 0b1111]);
+    let b = print("/b.mov", 320, 10, 5_200, vec![0b11, 0b1111]);
+    assert_eq!(mean_hamming(&a, &b), Some(1));
+    assert!(videos_close(&a, &b, 1));
+    assert!(!videos_close(&a, &b, 0));
+
+    let cover_only = print("/c.mp4", 640, 10, 5_000, vec![0]);
+    assert_eq!(mean_hamming(&a, &cover_only), None);
+    let longer = print("/d.mov", 640, 10, 9_000, vec![0, 0b1111]);
+    assert!(!videos_close(&a, &longer, 64));
+}
+
+/// 保留者排序：分辨率 → 大小 → 路径；删除行全部注释，元数据含时长。
+#[test]
+fn keeps_highest_resolution_and_comments_out_every_removal() {
+    let prints = [
+        print("/b/small.mov", 320, 900, 12_345, vec![1]),
+        print("/a/big.mov", 640, 100, 12_345, vec![0]),
+    ];
+    let group = group_report(&prints, vec![0, 1]);
+    assert_eq!(group.keep, "/a/big.mov");
+    assert_eq!(group.members[1].distance, 1);
+
+    let mut out = Vec::new();
+    render_similar_video_script(&[group], 10, &mut out);
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.starts_with("#!/usr/bin/env python3"));
+    assert!(out.contains("# SIMILAR VIDEO 2 videos, max distance 10\n"));
+    assert!(out.contains("# KEEP: \"/a/big.mov\"  # 640x640, 12.3 s, 100 bytes\n"));
+    assert!(
+        out.contains("# os.remove(\"/b/small.mov\")  # distance 1, 320x320, 12.3 s, 900 bytes\n")
+    );
+    assert!(!out.lines().any(|l| l.starts_with("os.remove")));
+
+    let mut empty = Vec::new();
+    render_similar_video_script(&[], 10, &mut empty);
+    assert!(empty.is_empty());
+}
@@ -5,12 +5,14 @@ use tempfile::tempdir;
 
 use super::compute_output_prefix;
 use super::find_duplicates;
+use super::plan_group;
 use super::render_script;
 use crate::adapters::backend::local::LocalBackend;
 use crate::entities::backend::Backend;
 use crate::entities::file_index::DuplicateGroup;
 use crate::entities::test_common as tc;
 use crate::entities::uri::Location;
+use crate::usecases::survivor::{Member, SurvivorPolicy};
 
 fn local_data_dir() -> (Location, Arc<dyn Backend>) {
     (
@@ -27,8 +29,32 @@ fn local_dir(p: &std::path::Path) -> (Location, Arc<dyn Backend>) {
 }
 
 fn run_render(same: &[DuplicateGroup], prefix: Option<&str>) -> String {
+    run_render_with(same, prefix, &[])
+}
+
+// 成员只含路径：mtime / 元数据均缺省，策略按路径相关项与路径序定案。
+fn run_render_with(
+    same: &[DuplicateGroup],
+    prefix: Option<&str>,
+    policies: &[SurvivorPolicy],
+) -> String {
+    let plans: Vec<_> = same
+        .iter()
+        .map(|g| {
+            let members: Vec<Member<'_>> = g
+                .paths
+                .iter()
+                .map(|p| Member {
+                    path: p.as_str(),
+                    scheme: "local",
+                    ..Member::default()
+                })
+                .collect();
+            plan_group(g, prefix, policies, &members, !policies.is_empty())
+        })
+        .collect();
     let mut sink: Vec<u8> = Vec::new();
-    render_script(same, prefix, &mut sink);
+    render_script(same, &plans, &mut sink);
     String::from_utf8(sink).unwrap()
 }
 
@@ -182,6 +208,20 @@ fn render_script_escapes_double_quote_in_path() {
     );
 }
 
+/// 路径含换行 / 其他控制字符转 `\xNN`，整条 `os.remove` 仍在一行内。
+#[test]
+fn render_script_escapes_control_chars_in_path() {
+    let groups = vec![DuplicateGroup {
+        size: 1,
+        paths: vec![Utf8PathBuf::from("/tmp/a\nb\x7f.jpg")],
+    }];
+    let out = run_render(&groups, None);
+    assert!(
+        out.contains(r#"# os.remove("/tmp/a\x0ab\x7f.jpg")"#),
+        "control chars must escape: {out}"
+    );
+}
+
 /// 同 size 不同 content 的两组重复集必须独立保留（旧 `BTreeMap<size, _>` 实现会覆盖）。
 #[test]
 fn search_same_preserves_distinct_groups_with_identical_size() {
@@ -232,7 +272,8 @@ fn find_duplicates_output_is_file_returns_err() {
     let tmp = tempfile::NamedTempFile::new().unwrap();
     let out_loc = Location::Local(Utf8PathBuf::from(tmp.path().to_str().unwrap()));
     let out_pair = (out_loc, LocalBackend::arc());
-    let err = find_duplicates(true, vec![local_data_dir()], Some(&out_pair)).unwrap_err();
+    let err =
+        find_duplicates(true, vec![local_data_dir()], Some(&out_pair), &[], None).unwrap_err();
     assert!(err.to_string().contains("not a directory"), "got: {err}");
 }
 
@@ -240,20 +281,21 @@ fn find_duplicates_output_is_file_returns_err() {
 fn find_duplicates_output_missing_returns_err() {
     let out_loc = Location::Local(Utf8PathBuf::from("/no/such/dir/xyz"));
     let out_pair = (out_loc, LocalBackend::arc());
-    let err = find_duplicates(true, vec![local_data_dir()], Some(&out_pair)).unwrap_err();
+    let err =
+        find_duplicates(true, vec![local_data_dir()], Some(&out_pair), &[], None).unwrap_err();
     assert!(err.to_string().contains("not a directory"), "got: {err}");
 }
 
 #[test]
 fn find_duplicates_no_output_branch_runs() {
-    find_duplicates(true, vec![local_data_dir()], None).unwrap();
+    find_duplicates(true, vec![local_data_dir()], None, &[], None).unwrap();
 }
 
 #[test]
 fn find_duplicates_with_output_branch_runs() {
     let dir = tempdir().unwrap();
     let out_pair = local_dir(dir.path());
-    find_duplicates(false, vec![local_data_dir()], Some(&out_pair)).unwrap();
+    find_duplicates(false, vec![local_data_dir()], Some(&out_pair), &[], None).unwrap();
 }
 
 /// metadata 失败（PermissionDenied / 网络错误等非 NotFound）必须传播原 Err，
@@ -278,7 +320,8 @@ fn find_duplicates_propagates_non_notfound_metadata_error() {
     );
     let backend: Arc<dyn Backend> = fake;
     let out_pair = (remote_dir, backend);
-    let err = find_duplicates(true, vec![local_data_dir()], Some(&out_pair)).unwrap_err();
+    let err =
+        find_duplicates(true, vec![local_data_dir()], Some(&out_pair), &[], None).unwrap_err();
     // 关键：错误不应被改写成 "not a directory"
     assert!(
         !err.to_string().contains("not a directory"),
@@ -315,5 +358,86 @@ fn find_duplicates_remote_output_uses_display_for_prefix() {
     fake.add_dir(remote_dir.clone());
     let backend: Arc<dyn Backend> = fake;
     let out_pair = (remote_dir, backend);
-    find_duplicates(false, vec![local_data_dir()], Some(&out_pair)).unwrap();
+    find_duplicates(false, vec![local_data_dir()], Some(&out_pair), &[], None).unwrap();
+}
+
+/// 无 output 但给了 `--keep`：只保护策略选出的 survivor 并标注理由，其余 active 删除。
+#[test]
+fn render_script_keep_policy_without_output_protects_only_survivor() {
+    let groups = vec![DuplicateGroup {
+        size: 7,
+        paths: vec![
+            Utf8PathBuf::from("/data/deep/nested/a.jpg"),
+            Utf8PathBuf::from("/data/a.jpg"),
+        ],
+    }];
+    let out = run_render_with(&groups, None, &[SurvivorPolicy::Shortest]);
+    assert!(out.contains("# SURVIVOR: shortest: 11 chars\n# os.remove(\"/data/a.jpg\")\n"));
+    assert!(out.contains("\nos.remove(\"/data/deep/nested/a.jpg\")\n"));
+}
+
+/// output 下有多份副本：全部保护，策略只决定其中谁标为 survivor。
+#[test]
+fn render_script_keep_policy_picks_among_copies_under_output() {
+    let groups = vec![DuplicateGroup {
+        size: 7,
+        paths: vec![
+            Utf8PathBuf::from("/keepers/2023-05/a.jpg"),
+            Utf8PathBuf::from("/keepers/misc/a.jpg"),
+            Utf8PathBuf::from("/other/a.jpg"),
+        ],
+    }];
+    let out = run_render_with(&groups, Some("/keepers"), &[SurvivorPolicy::DateDir]);
+    assert!(
+        out.contains("# SURVIVOR: date-dir: 2023-05\n# os.remove(\"/keepers/2023-05/a.jpg\")\n")
+    );
+    assert!(out.contains("# os.remove(\"/keepers/misc/a.jpg\")\n"));
+    assert!(out.contains("\nos.remove(\"/other/a.jpg\")\n"));
+}
+
+/// 端到端：报告每组带 survivor 与理由；`prefix:` 与索引路径同口径规范化。
+#[test]
+fn find_duplicates_reports_survivor_and_reason() {
+    let dir = tempdir().unwrap();
+    let keep = dir.path().join("keep");
+    std::fs::create_dir(&keep).unwrap();
+    std::fs::write(dir.path().join("a.bin"), b"same bytes").unwrap();
+    std::fs::write(keep.join("z.bin"), b"same bytes").unwrap();
+
+    let policy = format!("prefix:{}", keep.to_str().unwrap());
+    let report = find_duplicates(
+        false,
+        vec![local_dir(dir.path())],
+        None,
+        &[policy.parse().unwrap()],
+        None,
+    )
+    .unwrap();
+    assert_eq!(report.groups.len(), 1);
+    let group = &report.groups[0];
+    assert!(group.survivor.ends_with("keep/z.bin"), "{group:?}");
+    assert!(
+        group.survivor_reason.starts_with("prefix: under "),
+        "{group:?}"
+    );
+
+    let report = find_duplicates(false, vec![local_dir(dir.path())], None, &[], None).unwrap();
+    assert!(report.groups[0].survivor.ends_with("a.bin"));
+    assert_eq!(report.groups[0].survivor_reason, "path_order");
+}
+
+/// size 档：size 唯一的文件计入 scanned 与 skipped_unique_size，但从未被读取。
+#[test]
+fn find_duplicates_skips_unique_sizes_but_counts_them_as_scanned() {
+    let dir = tempdir().unwrap();
+    std::fs::write(dir.path().join("a.bin"), b"same bytes").unwrap();
+    std::fs::write(dir.path().join("b.bin"), b"same bytes").unwrap();
+    std::fs::write(dir.path().join("lonely.bin"), vec![9u8; 50_000]).unwrap();
+
+    let report = find_duplicates(true, vec![local_dir(dir.path())], None, &[], None).unwrap();
+    assert_eq!(report.scanned, 3);
+    assert_eq!(report.skipped_unique_size, 1);
+    assert_eq!(report.groups.len(), 1);
+    // 两份 10 字节副本：首段 + SHA-512 各读一遍；50 KB 的孤品一个字节都没读。
+    assert_eq!(report.bytes_read, 40);
 }
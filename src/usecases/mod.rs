@@ -1,17 +1,33 @@
 // Use Cases 层：编排 Entity 业务规则 + 应用级流程。
+pub(super) use audit::audit;
 pub(super) use copy::Source;
-pub(super) use copy::copy_with_sidecar;
+pub(super) use copy::{CopyRequest, copy_with_sidecar};
 pub(super) use cull::cull;
 pub(super) use find::find_duplicates;
+pub(super) use find_similar::find_similar;
+pub(super) use find_similar_video::find_similar_videos;
 pub(super) use move_text_shot::move_text_shot;
+pub(super) use rename::rename;
+pub(super) use stats::stats;
 
 pub(crate) mod config;
 pub(crate) mod face;
 pub(crate) mod ocr;
 
 mod archive_template;
+pub(crate) mod audit;
+pub(crate) mod catalog;
 mod copy;
 pub(crate) mod cull;
+pub(crate) mod event;
+pub(crate) mod faces;
 pub(crate) mod find;
+pub(crate) mod find_apply;
+pub(crate) mod find_similar;
+pub(crate) mod find_similar_video;
 pub(crate) mod move_text_shot;
+pub(crate) mod rename;
 pub(crate) mod report;
+pub(crate) mod stats;
+pub(crate) mod survivor;
+pub(crate) mod tag_filter;
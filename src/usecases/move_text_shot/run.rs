@@ -6,17 +6,24 @@
 //! - 同名冲突复用 `_1.._N`（`CopyConfig::unique_name_max_attempts`）
 //! - 移动 = 同 local + remove → `rename` fast-path；跨 backend → `stream_copy + remove`
 //! - Overlap 保护：source ⊆ output → InvalidInput；output ⊂ source → walk 时跳过子树
+//! - OCR 并行：walk 结果按 rayon 池宽分块，块内读字节 + `has_text` 并行，计数与移动按
+//!   walk 序串行——报告顺序、重名后缀与串行实现一致
+//! - 读字节先嗅探头部 256 字节，非图片（含视频）不再往下读；图片整读以
+//!   `backend.ocr.max_image_bytes` 封顶，超限计入 failed。同时驻留的字节因此不超过
+//!   块宽 × 该上限
 
 use std::io::{self, Read};
 use std::sync::Arc;
 
 use camino::Utf8Path;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use tracing::{debug, error};
 
 use super::report::MoveTextShotReport;
 use crate::entities::backend::factory::BackendFactory;
 use crate::entities::backend::{Backend, EntryKind};
 use crate::entities::common::{self, canonical_prefix, under_prefix};
+use crate::entities::file_info::read_fill;
 use crate::entities::uri::Location;
 use crate::usecases::config::config;
 use crate::usecases::ocr::TextDetector;
@@ -108,6 +115,14 @@ fn ensure_sources_outside_output(sources: &[Location], output_prefix: &str) -> c
     Ok(())
 }
 
+/// 并行阶段对单个文件的判定；串行阶段据此计数 / 移动。
+enum Verdict {
+    Unreadable(io::Error),
+    NotImage,
+    /// 图片字节 + OCR 结果；命中时字节直接用于移动，不再重读。
+    Image(Vec<u8>, io::Result<bool>),
+}
+
 #[expect(
     clippy::too_many_arguments,
     reason = "一比一参数透传；折结构体后调用点仍要先 build 该结构体"
@@ -122,11 +137,12 @@ fn process_source(
     dry_run: bool,
     report: &mut MoveTextShotReport,
 ) {
+    let mut candidates = Vec::new();
     for entry in src_backend.walk(source) {
         let entry = match entry {
             Ok(e) => e,
             Err(e) => {
-                record_failure(report, source.display(), &e);
+                candidates.push(Err(e));
                 continue;
             }
         };
@@ -138,25 +154,56 @@ fn process_source(
             continue;
         }
         report.scanned += 1;
-        process_entry(
-            detector,
-            source,
-            src_backend,
-            &entry.location,
-            output,
-            output_backend,
-            dry_run,
-            report,
-        );
+        candidates.push(Ok(entry.location));
+    }
+
+    for chunk in candidates.chunks(rayon::current_num_threads().max(1)) {
+        let outcomes: Vec<_> = chunk
+            .par_iter()
+            .map(|c| {
+                c.as_ref()
+                    .map(|src_loc| (src_loc, classify(detector, src_backend, src_loc)))
+            })
+            .collect();
+        for outcome in outcomes {
+            match outcome {
+                Ok((src_loc, verdict)) => process_entry(
+                    verdict,
+                    source,
+                    src_backend,
+                    src_loc,
+                    output,
+                    output_backend,
+                    dry_run,
+                    report,
+                ),
+                Err(e) => record_failure(report, source.display(), e),
+            }
+        }
     }
 }
 
+/// 嗅探 + 封顶整读 + OCR；纯函数，rayon worker 并发调用。
+fn classify(
+    detector: &dyn TextDetector,
+    src_backend: &Arc<dyn Backend>,
+    src_loc: &Location,
+) -> Verdict {
+    let bytes = match read_image(src_backend, src_loc, config().backend.ocr.max_image_bytes) {
+        Ok(Some(b)) => b,
+        Ok(None) => return Verdict::NotImage,
+        Err(e) => return Verdict::Unreadable(e),
+    };
+    let has_text = detector.has_text(src_loc.path(), &bytes);
+    Verdict::Image(bytes, has_text)
+}
+
 #[expect(
     clippy::too_many_arguments,
     reason = "一比一参数透传；本函数只做单文件主路径调度"
 )]
 fn process_entry(
-    detector: &dyn TextDetector,
+    verdict: Verdict,
     source: &Location,
     src_backend: &Arc<dyn Backend>,
     src_loc: &Location,
@@ -165,19 +212,19 @@ fn process_entry(
     dry_run: bool,
     report: &mut MoveTextShotReport,
 ) {
-    let bytes = match read_all(src_backend, src_loc) {
-        Ok(b) => b,
-        Err(e) => {
+    let (bytes, has_text) = match verdict {
+        Verdict::Unreadable(e) => {
             record_failure(report, src_loc.display(), &e);
             return;
         }
+        Verdict::NotImage => {
+            report.skipped_non_image += 1;
+            return;
+        }
+        Verdict::Image(bytes, has_text) => (bytes, has_text),
     };
-    if !is_image(&bytes) {
-        report.skipped_non_image += 1;
-        return;
-    }
     report.image_files += 1;
-    match detector.has_text(src_loc.path(), &bytes) {
+    match has_text {
         Ok(true) => {
             report.ocr_hits += 1;
             move_one(
@@ -200,13 +247,31 @@ fn process_entry(
     }
 }
 
-/// 把 `src_backend` 上的 `src_loc` 全部字节读入堆。远端 backend `RemoteClient::read`
-/// 整文件入堆是项目已知限制；本子命令需要把字节同时喂给 MIME 嗅探 + OCR，提前读完一份即可。
-fn read_all(backend: &Arc<dyn Backend>, loc: &Location) -> io::Result<Vec<u8>> {
+/// 先读 `MIME_SNIFF_BYTES` 嗅探，非图片返 `None`、不读余下内容；图片再整读入堆（OCR 与
+/// 跨 scheme 移动共用这一份），超过 `max_bytes` 返 `InvalidInput`。
+fn read_image(
+    backend: &Arc<dyn Backend>,
+    loc: &Location,
+    max_bytes: u64,
+) -> io::Result<Option<Vec<u8>>> {
     let mut reader = backend.open_read(loc)?;
-    let mut buf = Vec::new();
-    reader.read_to_end(&mut buf)?;
-    Ok(buf)
+    let mut bytes = vec![0u8; MIME_SNIFF_BYTES];
+    let n = read_fill(reader.as_mut(), &mut bytes)?;
+    bytes.truncate(n);
+    if !is_image(&bytes) {
+        return Ok(None);
+    }
+    // 多读 1 字节即判超限，不依赖 walker 报的 size。
+    reader
+        .take(max_bytes.saturating_add(1).saturating_sub(n as u64))
+        .read_to_end(&mut bytes)?;
+    if bytes.len() as u64 > max_bytes {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("move_text_shot: file exceeds backend.ocr.max_image_bytes={max_bytes}"),
+        ));
+    }
+    Ok(Some(bytes))
 }
 
 fn is_image(bytes: &[u8]) -> bool {
@@ -297,7 +362,7 @@ fn move_one(
 /// 真正搬一份。同 local + 同卷 → `LocalBackend::rename` 走 `fs::rename` fast-path；
 /// 跨设备由 `Backend::rename` 默认 fallback 到 copy+remove；跨 scheme 没法 rename
 /// → 用已读 `bytes` 直接 `output_backend.open_write` 写出 + src 删除，避免二次
-/// `open_read`（`read_all` 已读过一次，二次读在并发删源场景仍可能失败但不在本路径
+/// `open_read`（`read_image` 已读过一次，二次读在并发删源场景仍可能失败但不在本路径
 /// 服务的语义内）。
 fn do_move_file(
     src_backend: &Arc<dyn Backend>,
@@ -7,7 +7,7 @@
 //! - source ⊆ output overlap
 //! - output ⊂ source walk-skip
 //! - `dry_run` vs 真跑
-//! - `read_all` Err（注入 reader error）
+//! - `read_image` Err（注入 reader error）与超限
 //! - 同 scheme rename fast-path vs 跨 scheme stream
 //! - `split_stem_ext` / `relative_to` / `target_dir` 纯函数边界
 
@@ -212,6 +212,37 @@ fn move_text_shot_keeps_file_when_detector_misses() {
     assert!(fake.exists(&local_loc("/src/photo.png")).unwrap());
 }
 
+/// 超过 `backend.ocr.max_image_bytes` 的图不送 OCR，计入 failed、留在源处。
+#[test]
+fn move_text_shot_records_failure_for_oversized_image() {
+    let (fake, factory) = fake_factory();
+    fake.add_dir(local_loc("/src"));
+    let mut big = tiny_png();
+    big.resize(
+        usize::try_from(config().backend.ocr.max_image_bytes).unwrap() + 1,
+        0,
+    );
+    fake.add_file(local_loc("/src/huge.png"), big);
+    let detector = FakeTextDetector::new(true);
+
+    let report = move_text_shot(
+        &detector,
+        &factory,
+        &[local_loc("/src")],
+        &local_loc("/out"),
+        false,
+    )
+    .unwrap();
+
+    assert_eq!((report.failed, report.image_files, report.moved), (1, 0, 0));
+    assert!(
+        report.errors[0].message.contains("max_image_bytes"),
+        "{:?}",
+        report.errors
+    );
+    assert!(fake.exists(&local_loc("/src/huge.png")).unwrap());
+}
+
 #[test]
 fn move_text_shot_skips_non_image() {
     let (fake, factory) = fake_factory();
@@ -764,7 +795,7 @@ fn move_text_shot_propagates_factory_error_for_source() {
 
 #[test]
 fn move_text_shot_records_failure_when_open_read_fails() {
-    // read_all 内 open_read Err（line 196 ^0）
+    // read_image 内 open_read Err（line 196 ^0）
     let (fake, factory) = fake_factory();
     fake.add_dir(local_loc("/src"));
     fake.add_file(local_loc("/src/a.png"), tiny_png());
@@ -835,11 +866,11 @@ fn move_text_shot_cross_scheme_open_read_failure() {
         smb: Arc::clone(&src_fake) as Arc<dyn Backend>,
         local: Arc::clone(&out_fake) as Arc<dyn Backend>,
     };
-    // 关键：read_all 路径调 open_read 后 detector 触发；但已加了 OpenRead Err 在 src_loc
+    // 关键：read_image 路径调 open_read 后 detector 触发；但已加了 OpenRead Err 在 src_loc
     // → 第一次读 file 字节就失败 → record_failure，不进 do_move_file。
-    // 要触发 stream_copy 的 open_read Err 需要 read_all（第一次 open_read）成功而 stream_copy
+    // 要触发 stream_copy 的 open_read Err 需要 read_image（第一次 open_read）成功而 stream_copy
     // 第二次 open_read 失败——FakeBackend.check_error 是恒报，没法做"第 N 次失败"。
-    // 故只能验证 read_all 路径，stream_copy 内 open_read Err 实际不可单测触发——multi-binary
+    // 故只能验证 read_image 路径，stream_copy 内 open_read Err 实际不可单测触发——multi-binary
     // instance 套路：该 region 由 lib unit + 集成累加，subprocess instance 不可达。
     let detector = FakeTextDetector::new(true);
     let report = move_text_shot(
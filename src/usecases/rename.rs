@@ -0,0 +1,365 @@
+//! `rename` 子命令：按 `--name-template` 在原目录内就地改名，不复制、不跨目录。
+//!
+//! - 拍摄时间与 copy 同源（`MediaTimeDecision`，含 P3 sidecar / P0 Photos 候选），
+//!   模板取值经 `copy::naming::TemplateFields`，同一文件在 copy 与 rename 下渲染一致
+//! - 仅处理媒体文件；`.xmp` / `.json` 等伴随 sidecar 随媒体同步改名，不单独处理
+//! - 同名冲突追加 `_1.._N`（`copy.unique_name_max_attempts`）；源按完整路径排序后
+//!   依次处理，且本批已预定的目标名（含 dry-run 下尚未落盘的）同样视为占用，
+//!   同输入得到同样的编号
+//! - 渲染结果与当前名相同（含此前改名时拿到的 `_N`）计 `unchanged`，重跑幂等
+//! - 每次改名记一条 [`RenameRecord`]；给了 [`UndoLog`] 时每条改名成功即追加到还原脚本并
+//!   flush，中途崩溃 / Ctrl-C 时已执行的改名同样可还原
+
+use std::collections::HashSet;
+use std::io::{self, Write};
+
+use serde_derive::Serialize;
+use tracing::{debug, error, warn};
+
+use super::copy::Source;
+use super::copy::naming::{TemplateFields, candidate_name, companion_target, target_file_name};
+use super::copy::run::{configured_chrono_offset, uses_tags};
+use super::find::escape_py_string;
+use crate::entities::common;
+use crate::entities::file_index::{CandidateProvider, CompanionProvider, Index, TagProvider};
+use crate::entities::file_info::Info;
+use crate::entities::uri::Location;
+use crate::usecases::config::{config, validate_name_template};
+use crate::usecases::event::{assign_events, uses_event};
+use crate::usecases::report::ReportError;
+
+const FEATURE_RENAME: &str = "rename";
+
+// 改名过程中逐行追加 `RENAMES.append(...)`，脚本本身不以还原循环收尾：循环经 `atexit`
+// 在头部登记，截断在任意整行处的脚本都能完整执行。
+const UNDO_HEADER: &str = "#!/usr/bin/env python3\n\
+\"\"\"tidymedia rename 还原脚本：逆序把文件改回原名；原名已被占用的条目跳过。\"\"\"\n\
+import atexit\n\
+import os\n\n\
+RENAMES = []\n\n\n\
+@atexit.register\n\
+def undo():\n    \
+for new, old in reversed(RENAMES):\n        \
+if not os.path.exists(old):\n            \
+os.rename(new, old)\n\n\n";
+
+/// rename 操作报告。`scanned` = walker 触达文件总数（含空文件 / 读不到的）；
+/// `renamed` / `unchanged` / `skipped_non_media` / `failed` 为已入索引文件的四态计数。
+#[derive(Debug, Default, Serialize)]
+pub struct RenameReport {
+    pub scanned: usize,
+    /// 实际改名的媒体文件数；`dry_run` 下为 would-rename。
+    pub renamed: usize,
+    /// 渲染结果与当前文件名相同、无需改名的文件数。
+    pub unchanged: usize,
+    pub skipped_non_media: usize,
+    pub failed: usize,
+    pub dry_run: bool,
+    /// 逐条改名记录（含随行 sidecar），按执行顺序排列。
+    pub renames: Vec<RenameRecord>,
+    pub errors: Vec<ReportError>,
+}
+
+/// 单次改名：`from` 为原位置，`to` 为新位置（均为 `Location::display` 形态）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RenameRecord {
+    pub from: String,
+    pub to: String,
+}
+
+/// 还原脚本（Python，与 `find` 删除脚本同形态）：创建时写入头部，此后每条改名成功即
+/// [`Self::append`] 并 flush。脚本退出时逆序还原，每条仅在原名仍空闲时才
+/// `os.rename(新, 旧)`，避免覆盖用户在改名后新放入的文件。记录的是 `Location::display`
+/// 形态，远端 URI 需自行映射到挂载路径。
+pub struct UndoLog<'a> {
+    sink: &'a mut dyn Write,
+}
+
+impl<'a> UndoLog<'a> {
+    /// 写入脚本头部并 flush。
+    ///
+    /// # Errors
+    ///
+    /// 写入或 flush 失败时返回 `Err`。
+    pub fn new(sink: &'a mut dyn Write) -> io::Result<Self> {
+        sink.write_all(UNDO_HEADER.as_bytes())?;
+        sink.flush()?;
+        Ok(Self { sink })
+    }
+
+    /// 追加一条已执行的改名并 flush。
+    ///
+    /// # Errors
+    ///
+    /// 写入或 flush 失败时返回 `Err`。
+    pub fn append(&mut self, record: &RenameRecord) -> io::Result<()> {
+        let from = escape_py_string(&record.from);
+        let to = escape_py_string(&record.to);
+        // 整行一次写入：被杀在两次 write 之间的半行会让整个脚本语法错误。
+        let line = format!("RENAMES.append((\"{to}\", \"{from}\"))\n");
+        self.sink.write_all(line.as_bytes())?;
+        self.sink.flush()
+    }
+}
+
+/// 入口：扫 sources，把每个媒体文件按 `name_template` 在原目录内改名；`undo` 给定时
+/// 每条改名（含随行 sidecar）成功后立即写入还原脚本。
+///
+/// # Errors
+///
+/// `name_template` 不合法（见 `validate_name_template`）时返回 `InvalidInput`，不触碰
+/// 任何文件。还原脚本写入失败时立即中止并返回 `Err`——继续改名将无从还原。单文件失败
+/// （命名耗尽、`exists` / `rename` IO 错误）累计到 `report.failed` / `errors`，不中断
+/// 主流程。
+pub fn rename(
+    sources: &[Source],
+    name_template: &str,
+    dry_run: bool,
+    sidecar: Option<CandidateProvider>,
+    tags: Option<TagProvider>,
+    companions: Option<CompanionProvider>,
+    mut undo: Option<&mut UndoLog<'_>>,
+) -> common::Result<RenameReport> {
+    // FFI 等绕过 dispatch 的调用方同样受保护：非法模板在扫描前拒绝。
+    validate_name_template(name_template).map_err(|msg| {
+        common::Error::Io(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid --name-template: {msg}"),
+        ))
+    })?;
+    let mut index = Index::new();
+    for (loc, backend) in sources {
+        index.visit_location(loc, backend);
+    }
+    index.parse_exif(configured_chrono_offset());
+    if let Some(provider) = sidecar {
+        index.enrich_candidates(provider);
+    }
+    if let Some(provider) = tags.filter(|_| uses_tags(name_template)) {
+        index.enrich_tags(provider);
+    }
+    if uses_event(name_template) {
+        assign_events(&mut index, configured_chrono_offset());
+    }
+    let stats = index.stats();
+
+    let mut entries: Vec<&Info> = index.files().values().collect();
+    entries.sort_by(|a, b| a.full_path.cmp(&b.full_path));
+
+    let skipped = stats.skipped_empty + stats.skipped_unreadable + stats.walker_errors;
+    let mut report = RenameReport {
+        scanned: entries.len() + usize::try_from(skipped).unwrap_or(usize::MAX),
+        dry_run,
+        ..RenameReport::default()
+    };
+    let mut planned: HashSet<Location> = HashSet::new();
+    for src in entries {
+        if !src.is_media() {
+            report.skipped_non_media += 1;
+            continue;
+        }
+        let outcome = rename_one(
+            src,
+            name_template,
+            dry_run,
+            companions,
+            &mut planned,
+            undo.as_deref_mut(),
+        );
+        match outcome {
+            Ok(Some(records)) => {
+                report.renamed += 1;
+                report.renames.extend(records);
+            }
+            Ok(None) => report.unchanged += 1,
+            Err(Failure::File(e)) => record_failure(&mut report, src, &e),
+            Err(Failure::Undo(e)) => return Err(e.into()),
+        }
+    }
+
+    // 提出宏外：tracing 字段表达式仅在事件被订阅时求值（同 copy::run）。
+    let result = if report.failed == 0 { "ok" } else { "partial" };
+    debug!(
+        feature = FEATURE_RENAME,
+        operation = "summary",
+        result,
+        scanned = report.scanned,
+        renamed = report.renamed,
+        unchanged = report.unchanged,
+        skipped_non_media = report.skipped_non_media,
+        failed = report.failed,
+        dry_run,
+        "rename summary"
+    );
+    Ok(report)
+}
+
+// 单文件失败：`File` 计入 `report.failed` 后继续；`Undo` 是还原脚本写入失败，整批中止。
+enum Failure {
+    File(io::Error),
+    Undo(io::Error),
+}
+
+impl From<io::Error> for Failure {
+    fn from(e: io::Error) -> Self {
+        Self::File(e)
+    }
+}
+
+// 单文件：渲染新名 → 选未占用候选 → 改名媒体 → 随行 sidecar。None = 无需改名。
+// 每个 `backend.rename` 成功后立即写还原记录，再动下一个文件。
+fn rename_one(
+    src: &Info,
+    name_template: &str,
+    dry_run: bool,
+    companions: Option<CompanionProvider>,
+    planned: &mut HashSet<Location>,
+    mut undo: Option<&mut UndoLog<'_>>,
+) -> Result<Option<Vec<RenameRecord>>, Failure> {
+    let fields = TemplateFields::from_info(src, &[name_template])?;
+    let ctx = fields.context(src);
+    let (name, stem, ext) = target_file_name(src, Some(name_template), &ctx);
+    let src_loc = src.location();
+    let backend = src.backend();
+
+    let Some(target) = pick_target(src_loc, (&name, &stem, &ext), planned, |loc| {
+        backend.exists(loc)
+    })?
+    else {
+        return Err(io::Error::other(format!("exhausted unique-name attempts for {name}")).into());
+    };
+    if target == *src_loc {
+        return Ok(None);
+    }
+    // 先列 sidecar：探测基于原名，媒体改名后 `<stem>.xmp` 仍在原处。
+    let siblings = companions.map_or_else(Vec::new, |provider| provider(src_loc, &backend));
+    if !dry_run {
+        backend.rename(src_loc, &target, false)?;
+    }
+    log_rename(src_loc, &target, dry_run, "file");
+    planned.insert(target.clone());
+
+    let mut records = Vec::new();
+    push_record(&mut records, undo.as_deref_mut(), src_loc, &target)?;
+    for companion in siblings {
+        let Some(dst) = companion_target(src_loc, &target, &companion) else {
+            continue;
+        };
+        // 伴随文件 best-effort：媒体已改名，失败只 warn；目标已占用时不覆盖。
+        let result = if planned.contains(&dst) {
+            Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "target reserved",
+            ))
+        } else {
+            match backend.exists(&dst) {
+                Ok(true) => Err(io::Error::new(
+                    io::ErrorKind::AlreadyExists,
+                    "target exists",
+                )),
+                Ok(false) if dry_run => Ok(()),
+                Ok(false) => backend.rename(&companion, &dst, false),
+                Err(e) => Err(e),
+            }
+        };
+        match result {
+            Ok(()) => {
+                log_rename(&companion, &dst, dry_run, "sidecar");
+                push_record(&mut records, undo.as_deref_mut(), &companion, &dst)?;
+                planned.insert(dst);
+            }
+            Err(e) => {
+                let source = companion.display();
+                let target = dst.display();
+                let err = e.to_string();
+                warn!(
+                    feature = FEATURE_RENAME,
+                    operation = "rename_sidecar",
+                    result = "error",
+                    source,
+                    target,
+                    err,
+                    "cannot rename sidecar companion"
+                );
+            }
+        }
+    }
+    Ok(Some(records))
+}
+
+// 记一条已执行的改名；给了还原脚本时先追加并 flush，写不进去即 `Failure::Undo`。
+fn push_record(
+    records: &mut Vec<RenameRecord>,
+    undo: Option<&mut UndoLog<'_>>,
+    from: &Location,
+    to: &Location,
+) -> Result<(), Failure> {
+    let record = RenameRecord {
+        from: from.display(),
+        to: to.display(),
+    };
+    if let Some(log) = undo {
+        log.append(&record).map_err(Failure::Undo)?;
+    }
+    records.push(record);
+    Ok(())
+}
+
+// 依次试 `name` / `stem_1.ext` …：命中自身即"无需改名"；本批已预定或磁盘已存在的跳过。
+// `exists` 注入为闭包便于单测；其 IO 错误必须传播，吞成"不存在"会覆盖已有文件。
+fn pick_target(
+    src_loc: &Location,
+    (name, stem, ext): (&str, &str, &str),
+    planned: &HashSet<Location>,
+    exists: impl Fn(&Location) -> io::Result<bool>,
+) -> io::Result<Option<Location>> {
+    let dir = src_loc
+        .path()
+        .parent()
+        .expect("internal: indexed file path must have a parent");
+    for i in 0..=config().copy.unique_name_max_attempts {
+        let candidate = src_loc.with_path(dir.join(candidate_name(name, stem, ext, i)));
+        if candidate == *src_loc {
+            return Ok(Some(candidate));
+        }
+        if planned.contains(&candidate) || exists(&candidate)? {
+            continue;
+        }
+        return Ok(Some(candidate));
+    }
+    Ok(None)
+}
+
+fn log_rename(from: &Location, to: &Location, dry_run: bool, kind: &'static str) {
+    let source = from.display();
+    let target = to.display();
+    let result = if dry_run { "dry_run" } else { "ok" };
+    debug!(
+        feature = FEATURE_RENAME,
+        operation = "rename_file",
+        result,
+        kind,
+        source,
+        target,
+        "file renamed"
+    );
+}
+
+fn record_failure(report: &mut RenameReport, src: &Info, e: &io::Error) {
+    let path = src.full_path.to_string();
+    let msg = e.to_string();
+    error!(
+        feature = FEATURE_RENAME,
+        operation = "rename_file",
+        result = "error",
+        source = %path,
+        error = %msg,
+        "rename item failed"
+    );
+    report.errors.push(ReportError { path, message: msg });
+    report.failed += 1;
+}
+
+#[cfg(test)]
+#[path = "rename_tests.rs"]
+mod tests;
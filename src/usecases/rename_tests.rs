@@ -0,0 +1,236 @@
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use camino::Utf8PathBuf;
+use tempfile::tempdir;
+
+use super::*;
+use crate::adapters::backend::local::LocalBackend;
+use crate::entities::backend::Backend;
+use crate::entities::test_common as tc;
+
+const TMPL: &str = "{media_kind}.{ext}";
+
+fn local_source(p: &Path) -> Source {
+    let backend: Arc<dyn Backend> = LocalBackend::arc();
+    (
+        Location::Local(Utf8PathBuf::from(p.to_str().unwrap())),
+        backend,
+    )
+}
+
+fn run(dir: &Path, dry_run: bool) -> RenameReport {
+    rename(
+        &[local_source(dir)],
+        TMPL,
+        dry_run,
+        None,
+        None,
+        Some(crate::adapters::sidecar::companions_with_backend),
+        None,
+    )
+    .unwrap()
+}
+
+/// 同名渲染按路径序追加 `_1`；sidecar 随媒体改名；非媒体不动；重跑幂等。
+#[test]
+fn rename_in_place_with_suffix_sidecar_and_idempotent_rerun() {
+    let dir = tempdir().unwrap();
+    tc::copy_png_to(dir.path(), "a.png").unwrap();
+    tc::copy_png_to(dir.path(), "b.png").unwrap();
+    fs::write(dir.path().join("a.xmp"), b"<x/>").unwrap();
+    fs::write(dir.path().join("notes.txt"), b"keep me").unwrap();
+
+    let report = run(dir.path(), false);
+    assert_eq!(report.renamed, 2);
+    assert_eq!(report.failed, 0);
+    // a.xmp 作为非媒体计入 skipped，随 a.png 改名。
+    assert_eq!(report.skipped_non_media, 2);
+    for name in ["photo.png", "photo.xmp", "photo_1.png", "notes.txt"] {
+        assert!(dir.path().join(name).is_file(), "missing {name}");
+    }
+    assert!(!dir.path().join("a.png").exists());
+    assert_eq!(report.renames.len(), 3);
+    assert!(report.renames[1].to.ends_with("photo.xmp"));
+
+    let again = run(dir.path(), false);
+    assert_eq!(again.renamed, 0);
+    assert_eq!(again.unchanged, 2);
+}
+
+/// dry-run 不落盘，但本批预定的目标名同样占位，编号与真跑一致。
+#[test]
+fn rename_dry_run_plans_distinct_names_without_touching_files() {
+    let dir = tempdir().unwrap();
+    tc::copy_png_to(dir.path(), "a.png").unwrap();
+    tc::copy_png_to(dir.path(), "b.png").unwrap();
+
+    let report = run(dir.path(), true);
+    assert!(report.dry_run);
+    assert_eq!(report.renamed, 2);
+    assert!(report.renames[0].to.ends_with("photo.png"));
+    assert!(report.renames[1].to.ends_with("photo_1.png"));
+    assert!(dir.path().join("a.png").is_file());
+    assert!(!dir.path().join("photo.png").exists());
+}
+
+#[test]
+fn rename_rejects_invalid_template_before_scanning() {
+    let dir = tempdir().unwrap();
+    let err = rename(
+        &[local_source(dir.path())],
+        "{year}/x",
+        false,
+        None,
+        None,
+        None,
+        None,
+    )
+    .unwrap_err();
+    assert!(
+        err.to_string().contains("invalid --name-template"),
+        "got: {err}"
+    );
+}
+
+#[test]
+fn pick_target_propagates_exists_error() {
+    let src = Location::Local(Utf8PathBuf::from("/in-mem/a.png"));
+    let err = pick_target(&src, ("b.png", "b", "png"), &HashSet::new(), |_| {
+        Err(io::Error::from(io::ErrorKind::TimedOut))
+    })
+    .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+}
+
+#[test]
+fn pick_target_none_after_max_collisions() {
+    let src = Location::Local(Utf8PathBuf::from("/in-mem/a.png"));
+    let got = pick_target(&src, ("b.png", "b", "png"), &HashSet::new(), |_| Ok(true)).unwrap();
+    assert!(got.is_none());
+}
+
+/// 每条改名成功即追加到还原脚本（含随行 sidecar），按执行顺序；路径中的引号被转义。
+#[test]
+fn undo_log_records_each_rename_as_it_happens() {
+    let dir = tempdir().unwrap();
+    tc::copy_png_to(dir.path(), "a.png").unwrap();
+    tc::copy_png_to(dir.path(), "say \"hi\".png").unwrap();
+    fs::write(dir.path().join("a.xmp"), b"<x/>").unwrap();
+
+    let mut out = Vec::new();
+    let mut undo = UndoLog::new(&mut out).unwrap();
+    let report = rename(
+        &[local_source(dir.path())],
+        TMPL,
+        false,
+        None,
+        None,
+        Some(crate::adapters::sidecar::companions_with_backend),
+        Some(&mut undo),
+    )
+    .unwrap();
+    assert_eq!(report.renamed, 2);
+
+    let text = String::from_utf8(out).unwrap();
+    assert!(text.starts_with("#!/usr/bin/env python3\n"));
+    assert!(text.contains("for new, old in reversed(RENAMES):"));
+    let lines: Vec<&str> = text
+        .lines()
+        .filter(|l| l.starts_with("RENAMES.append"))
+        .collect();
+    assert_eq!(lines.len(), report.renames.len());
+    for (line, record) in lines.iter().zip(&report.renames) {
+        assert!(line.contains(&escape_py_string(&record.from)), "{line}");
+    }
+    assert!(text.contains("say \\\"hi\\\".png\"))"), "{text}");
+}
+
+/// 文件名含换行等控制字符时转成 `\xNN`：每条记录仍是完整单行，脚本不被截断。
+#[test]
+fn undo_log_escapes_newline_in_file_name() {
+    let mut out = Vec::new();
+    let mut undo = UndoLog::new(&mut out).unwrap();
+    undo.append(&RenameRecord {
+        from: "/p/two\nlines\r.png".into(),
+        to: "/p/photo\t.png".into(),
+    })
+    .unwrap();
+
+    let text = String::from_utf8(out).unwrap();
+    let last = text.lines().last().unwrap();
+    assert_eq!(
+        last,
+        r#"RENAMES.append(("/p/photo\x09.png", "/p/two\x0alines\x0d.png"))"#
+    );
+}
+
+/// 只写得下头部和一条记录的 sink。
+struct FailingSink {
+    writes_left: usize,
+}
+
+impl Write for FailingSink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.writes_left == 0 {
+            return Err(io::Error::other("disk full"));
+        }
+        self.writes_left -= 1;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// 还原记录写不进去时立即中止：后续文件保持原名，不产生无从还原的改名。
+#[test]
+fn rename_aborts_when_undo_log_cannot_be_written() {
+    let dir = tempdir().unwrap();
+    tc::copy_png_to(dir.path(), "a.png").unwrap();
+    tc::copy_png_to(dir.path(), "b.png").unwrap();
+
+    let mut sink = FailingSink { writes_left: 1 };
+    let mut undo = UndoLog::new(&mut sink).unwrap();
+    let err = rename(
+        &[local_source(dir.path())],
+        TMPL,
+        false,
+        None,
+        None,
+        None,
+        Some(&mut undo),
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("disk full"), "got: {err}");
+    assert!(dir.path().join("photo.png").is_file());
+    assert!(dir.path().join("b.png").is_file());
+}
+
+/// 媒体改名后其还原记录写不进去：立即中止，随行 sidecar 不再改名，免得留下一对都
+/// 不在还原脚本里的改名。
+#[test]
+fn rename_logs_media_record_before_touching_sidecars() {
+    let dir = tempdir().unwrap();
+    tc::copy_png_to(dir.path(), "a.png").unwrap();
+    fs::write(dir.path().join("a.xmp"), b"<x/>").unwrap();
+
+    let mut sink = FailingSink { writes_left: 1 };
+    let mut undo = UndoLog::new(&mut sink).unwrap();
+    let err = rename(
+        &[local_source(dir.path())],
+        TMPL,
+        false,
+        None,
+        None,
+        Some(crate::adapters::sidecar::companions_with_backend),
+        Some(&mut undo),
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("disk full"), "got: {err}");
+    assert!(dir.path().join("photo.png").is_file());
+    assert!(dir.path().join("a.xmp").is_file());
+    assert!(!dir.path().join("photo.xmp").exists());
+}
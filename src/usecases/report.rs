@@ -24,14 +24,67 @@ pub struct CopyReport {
     pub errors: Vec<ReportError>,
 }
 
-/// find 操作报告。`scanned` = Index 中实际入索引的文件总数（不仅是重复组路径数）；
-/// `bytes_read` = 流式哈希过程中累计读取的字节数；`groups` 保留每组完整字段（size + paths）
+/// find 操作报告。`scanned` = 参与比对的文件总数（不仅是重复组路径数），其中
+/// `skipped_unique_size` 份因 size 唯一从未读取内容；
+/// `bytes_read` = 流式哈希过程中累计读取的字节数（分档预过滤的节省即体现在此）；`groups` 保留每组完整字段（size + paths）
 /// 不展平，让下游按 size 过滤或排序时不丢信息（`render_script` 的 `# SIZE N` 注释亦此口径）。
 #[derive(Debug, Default, Serialize)]
 pub struct FindReport {
     pub scanned: usize,
+    pub skipped_unique_size: u64,
     pub groups: Vec<DuplicateGroupReport>,
     pub bytes_read: u64,
+    /// `--apply` 成功处置的副本；未启用 `--apply` 时为空。
+    pub resolutions: Vec<crate::usecases::find_apply::ResolutionRecord>,
+    /// `--apply` 处置失败（含复核哈希不一致）的副本。
+    pub errors: Vec<ReportError>,
+    /// `--similar` 的近似重复组；未启用 `--similar` 时为空（此时 `groups` 为空）。
+    pub similar_groups: Vec<SimilarGroupReport>,
+    /// `--similar-video` 的近似重复视频组；未启用时为空。
+    pub similar_video_groups: Vec<SimilarVideoGroupReport>,
+    /// `--similar-video` 下识别为视频、但抽不出可解码帧（H.264 / HEVC 且无封面）的文件数。
+    pub skipped_unsupported_video: u64,
+}
+
+/// `find --similar` 的一组近似重复图片：`members` 按建议保留顺序排列，首个即 `keep`。
+#[derive(Debug, Default, Serialize)]
+pub struct SimilarGroupReport {
+    pub keep: String,
+    pub members: Vec<SimilarMemberReport>,
+}
+
+/// 近似重复组成员：比较"哪份是最高质量原图"所需的元数据。
+#[derive(Debug, Default, Serialize)]
+pub struct SimilarMemberReport {
+    pub path: String,
+    pub size: u64,
+    pub width: u32,
+    pub height: u32,
+    /// 解析到任一 EXIF / XMP 字段；被社交软件剥离元数据的副本为 false。
+    pub has_exif: bool,
+    /// 与 `keep` 的 pHash 汉明距离（`keep` 自身为 0）。
+    pub distance: u32,
+}
+
+/// `find --similar-video` 的一组近似重复视频：`members` 按建议保留顺序排列，首个即 `keep`。
+#[derive(Debug, Default, Serialize)]
+pub struct SimilarVideoGroupReport {
+    pub keep: String,
+    pub members: Vec<SimilarVideoMemberReport>,
+}
+
+/// 近似重复视频组成员。
+#[derive(Debug, Default, Serialize)]
+pub struct SimilarVideoMemberReport {
+    pub path: String,
+    pub size: u64,
+    /// 首个关键帧的尺寸。
+    pub width: u32,
+    pub height: u32,
+    /// 容器声明的时长；缺失为 0。
+    pub duration_ms: u64,
+    /// 与 `keep` 的逐帧 pHash 汉明距离均值（`keep` 自身为 0）。
+    pub distance: u32,
 }
 
 /// 单个重复组的报告项：组内文件 size（同组共享）+ 路径列表。
@@ -41,6 +94,10 @@ pub struct DuplicateGroupReport {
     pub size: u64,
     /// 组内文件路径列表（保留组边界，不做 CSV 展平）。
     pub paths: Vec<String>,
+    /// 保留的副本（`paths` 之一）。
+    pub survivor: String,
+    /// 保留理由：`output_dir` / `path_order` / 定案的 `--keep` 策略及取值（如 `oldest: mtime …`）。
+    pub survivor_reason: String,
 }
 
 /// 报告中的单条错误记录。
@@ -57,6 +114,10 @@ pub enum Report<'a> {
     Find(&'a FindReport),
     MoveTextShot(&'a crate::usecases::move_text_shot::MoveTextShotReport),
     Cull(&'a crate::usecases::cull::CullReport),
+    Rename(&'a crate::usecases::rename::RenameReport),
+    Stats(&'a crate::usecases::stats::StatsReport),
+    Audit(&'a crate::usecases::audit::AuditReport),
+    Faces(&'a crate::usecases::faces::FacesReport),
 }
 
 /// 报告输出端：序列化格式 + 持久化机制由实现者决定（JSON 写盘 / stdout / 推送…）。
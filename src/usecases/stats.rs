@@ -0,0 +1,227 @@
+//! `stats` 子命令：只读扫描，汇总媒体库按时间 / 相机 / 类型 / 体积的分布。
+//!
+//! - 拍摄时间与 copy 同源（`MediaTimeDecision`，含 P3 sidecar 候选），年 / 月按
+//!   `copy.timezone_offset_hours` 切分，同一文件在 stats 与归档目录里落同一个月
+//! - 时间来源等级（P0..P4）与置信度单列成表：P4 / low 占比高说明大量时间是 mtime 猜测
+//! - 只统计媒体文件；非媒体文件只计数不入表
+//! - `empty_months` 列出首末月之间没有任何文件的月份，用于发现漏导入的时间段
+
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use chrono::{DateTime, Datelike, FixedOffset, Months, NaiveDate, Utc};
+use serde_derive::Serialize;
+use tracing::debug;
+
+use super::copy::Source;
+use super::copy::run::configured_chrono_offset;
+use crate::entities::file_index::{CandidateProvider, Index};
+use crate::entities::file_info::Info;
+use crate::entities::media_time::Confidence;
+use crate::usecases::config::config;
+
+const FEATURE_STATS: &str = "stats";
+
+const UNKNOWN_CAMERA: &str = "unknown";
+
+/// stats 报告。`scanned` = walker 触达文件总数（含空文件 / 读不到的）；
+/// 各分布表只含媒体文件，按键升序排列。
+#[derive(Debug, Default, Serialize)]
+pub struct StatsReport {
+    pub scanned: usize,
+    pub media_files: usize,
+    pub non_media_files: usize,
+    /// 媒体文件总字节数。
+    pub total_bytes: u64,
+    pub by_year: Vec<StatsBucket>,
+    /// 键为 `YYYY-MM`。
+    pub by_month: Vec<StatsBucket>,
+    /// 键为 `Make Model`；两者皆缺记 `unknown`。
+    pub by_camera: Vec<StatsBucket>,
+    pub by_mime: Vec<StatsBucket>,
+    /// 键为采纳时间的来源等级 `P0`..`P4`。
+    pub by_priority: Vec<StatsBucket>,
+    /// 键为 `high` / `low`；fs 兜底计 `low`。
+    pub by_confidence: Vec<StatsBucket>,
+    /// 首末月之间没有任何媒体文件的月份（`YYYY-MM`）。
+    pub empty_months: Vec<String>,
+}
+
+/// 分布表的一行：键 + 文件数 + 字节数。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StatsBucket {
+    pub key: String,
+    pub files: usize,
+    pub bytes: u64,
+}
+
+// 各维度累加器；BTreeMap 让输出按键有序、同输入同输出。
+#[derive(Default)]
+struct Tally(BTreeMap<String, (usize, u64)>);
+
+impl Tally {
+    fn add(&mut self, key: String, bytes: u64) {
+        let slot = self.0.entry(key).or_default();
+        slot.0 += 1;
+        slot.1 += bytes;
+    }
+
+    fn into_buckets(self) -> Vec<StatsBucket> {
+        self.0
+            .into_iter()
+            .map(|(key, (files, bytes))| StatsBucket { key, files, bytes })
+            .collect()
+    }
+}
+
+/// 入口：扫 sources，汇总媒体文件分布并把文本表打印到 stdout。不修改任何文件。
+pub fn stats(sources: &[Source], sidecar: Option<CandidateProvider>) -> StatsReport {
+    let offset = configured_chrono_offset();
+    let mut index = Index::new();
+    for (loc, backend) in sources {
+        index.visit_location(loc, backend);
+    }
+    index.parse_exif(offset);
+    if let Some(provider) = sidecar {
+        index.enrich_candidates(provider);
+    }
+    let visit = index.stats();
+    let skipped = visit.skipped_empty + visit.skipped_unreadable + visit.walker_errors;
+
+    let report = summarize(index.files().values(), offset);
+    let report = StatsReport {
+        scanned: report.scanned + usize::try_from(skipped).unwrap_or(usize::MAX),
+        ..report
+    };
+    debug!(
+        feature = FEATURE_STATS,
+        operation = "summary",
+        result = "ok",
+        scanned = report.scanned,
+        media_files = report.media_files,
+        non_media_files = report.non_media_files,
+        total_bytes = report.total_bytes,
+        empty_months = report.empty_months.len(),
+        "library stats collected"
+    );
+    render_tables(&report, &mut std::io::stdout());
+    report
+}
+
+// 纯聚合：逐个 Info 取判定时间与 EXIF 字段累加到各维度表。
+fn summarize<'a>(files: impl Iterator<Item = &'a Info>, offset: FixedOffset) -> StatsReport {
+    let valid_secs = config().exif.valid_date_time_secs;
+    let mut report = StatsReport::default();
+    let (mut year, mut month, mut camera, mut mime, mut priority, mut confidence) = (
+        Tally::default(),
+        Tally::default(),
+        Tally::default(),
+        Tally::default(),
+        Tally::default(),
+        Tally::default(),
+    );
+    for info in files {
+        report.scanned += 1;
+        let Some(exif) = info.exif_ref().filter(|e| e.is_media()) else {
+            report.non_media_files += 1;
+            continue;
+        };
+        report.media_files += 1;
+        report.total_bytes += info.size;
+
+        let (time, prio, conf) = info.create_time_with_confidence(valid_secs, offset);
+        let local = DateTime::<Utc>::from(time).with_timezone(&offset);
+        year.add(local.format("%Y").to_string(), info.size);
+        month.add(local.format("%Y-%m").to_string(), info.size);
+        camera.add(camera_key(exif.make(), exif.model()), info.size);
+        mime.add(exif.mime_type().to_owned(), info.size);
+        priority.add(prio.as_str().to_owned(), info.size);
+        confidence.add(confidence_key(conf).to_owned(), info.size);
+    }
+    report.by_year = year.into_buckets();
+    report.by_month = month.into_buckets();
+    report.empty_months = empty_months(&report.by_month);
+    report.by_camera = camera.into_buckets();
+    report.by_mime = mime.into_buckets();
+    report.by_priority = priority.into_buckets();
+    report.by_confidence = confidence.into_buckets();
+    report
+}
+
+// 多数厂商的 Model 已含品牌名（"Canon EOS R5"），此时不再重复拼 Make。
+fn camera_key(make: Option<&str>, model: Option<&str>) -> String {
+    let make = make.map(str::trim).filter(|s| !s.is_empty());
+    let model = model.map(str::trim).filter(|s| !s.is_empty());
+    match (make, model) {
+        (Some(make), Some(model)) if model.starts_with(make) => model.to_owned(),
+        (Some(make), Some(model)) => format!("{make} {model}"),
+        (Some(only), None) | (None, Some(only)) => only.to_owned(),
+        (None, None) => UNKNOWN_CAMERA.to_owned(),
+    }
+}
+
+fn confidence_key(confidence: Confidence) -> &'static str {
+    match confidence {
+        Confidence::High => "high",
+        Confidence::Low => "low",
+    }
+}
+
+// by_month 已按 `YYYY-MM` 升序；逐月走到末月，未出现的月份即空档。
+fn empty_months(by_month: &[StatsBucket]) -> Vec<String> {
+    let parse = |key: &str| NaiveDate::parse_from_str(&format!("{key}-01"), "%Y-%m-%d").ok();
+    let (Some(first), Some(last)) = (
+        by_month.first().and_then(|b| parse(&b.key)),
+        by_month.last().and_then(|b| parse(&b.key)),
+    ) else {
+        return Vec::new();
+    };
+    let present: Vec<&str> = by_month.iter().map(|b| b.key.as_str()).collect();
+    let mut gaps = Vec::new();
+    let mut cursor = first;
+    while cursor < last {
+        let key = format!("{:04}-{:02}", cursor.year(), cursor.month());
+        if present.binary_search(&key.as_str()).is_err() {
+            gaps.push(key);
+        }
+        let Some(next) = cursor.checked_add_months(Months::new(1)) else {
+            break;
+        };
+        cursor = next;
+    }
+    gaps
+}
+
+// 每张表一个标题行 + 对齐的 `key  files  bytes` 行；空表整张省略。
+fn render_tables(report: &StatsReport, sink: &mut impl Write) {
+    let _ = writeln!(
+        sink,
+        "scanned {}, media {}, non-media {}, {} bytes",
+        report.scanned, report.media_files, report.non_media_files, report.total_bytes
+    );
+    let tables = [
+        ("year", &report.by_year),
+        ("month", &report.by_month),
+        ("camera", &report.by_camera),
+        ("mime", &report.by_mime),
+        ("time source", &report.by_priority),
+        ("confidence", &report.by_confidence),
+    ];
+    for (title, buckets) in tables {
+        if buckets.is_empty() {
+            continue;
+        }
+        let width = buckets.iter().map(|b| b.key.len()).max().unwrap_or(0);
+        let _ = writeln!(sink, "\n## {title}");
+        for b in buckets {
+            let _ = writeln!(sink, "{:<width$}  {:>8}  {:>14}", b.key, b.files, b.bytes);
+        }
+    }
+    if !report.empty_months.is_empty() {
+        let _ = writeln!(sink, "\n## empty months\n{}", report.empty_months.join(" "));
+    }
+}
+
+#[cfg(test)]
+#[path = "stats_tests.rs"]
+mod tests;
@@ -0,0 +1,106 @@
+use std::fs;
+use std::sync::Arc;
+
+use camino::Utf8PathBuf;
+use tempfile::tempdir;
+
+use super::*;
+use crate::adapters::backend::local::LocalBackend;
+use crate::entities::backend::Backend;
+use crate::entities::test_common as tc;
+use crate::entities::uri::Location;
+
+fn bucket(key: &str, files: usize, bytes: u64) -> StatsBucket {
+    StatsBucket {
+        key: key.to_owned(),
+        files,
+        bytes,
+    }
+}
+
+fn keys(buckets: &[StatsBucket]) -> Vec<&str> {
+    buckets.iter().map(|b| b.key.as_str()).collect()
+}
+
+/// EXIF 时间的 PNG 与只有 mtime 的 PNG 分入不同年月 / 相机 / 来源等级；非媒体只计数。
+#[test]
+fn stats_groups_media_by_time_camera_type_and_source() {
+    let dir = tempdir().unwrap();
+    fs::copy(tc::DATA_PNG_EXIF, dir.path().join("canon.png")).unwrap();
+    tc::copy_png_to(dir.path(), "fallback.png").unwrap();
+    fs::write(dir.path().join("notes.txt"), b"not media").unwrap();
+    let backend: Arc<dyn Backend> = LocalBackend::arc();
+    let root = Location::Local(Utf8PathBuf::from(dir.path().to_str().unwrap()));
+
+    let report = stats(&[(root, backend)], None);
+
+    assert_eq!(
+        (report.scanned, report.media_files, report.non_media_files),
+        (3, 2, 1)
+    );
+    let png_bytes = fs::metadata(tc::DATA_PNG_EXIF).unwrap().len()
+        + fs::metadata(tc::DATA_DNS_BENCHMARK).unwrap().len();
+    assert_eq!(report.total_bytes, png_bytes);
+    assert_eq!(keys(&report.by_year), ["2017", "2024"]);
+    assert_eq!(keys(&report.by_month), ["2017-02", "2024-01"]);
+    assert_eq!(keys(&report.by_camera), ["Canon EOS 7D", "unknown"]);
+    assert_eq!(report.by_mime.len(), 1);
+    assert_eq!(report.by_mime[0].files, 2);
+    assert_eq!(report.by_priority.last().unwrap().key, "P4");
+    assert_eq!(report.by_priority.last().unwrap().files, 1);
+    assert!(keys(&report.by_confidence).contains(&"low"));
+    assert_eq!(
+        report.empty_months.first().map(String::as_str),
+        Some("2017-03")
+    );
+    assert_eq!(
+        report.empty_months.last().map(String::as_str),
+        Some("2023-12")
+    );
+}
+
+#[test]
+fn camera_key_avoids_repeating_make_in_model() {
+    assert_eq!(
+        camera_key(Some("Canon"), Some("Canon EOS R5")),
+        "Canon EOS R5"
+    );
+    assert_eq!(camera_key(Some("SONY "), Some("ILCE-7M3")), "SONY ILCE-7M3");
+    assert_eq!(camera_key(None, Some("Pixel 8")), "Pixel 8");
+    assert_eq!(camera_key(Some(""), None), "unknown");
+}
+
+#[test]
+fn empty_months_spans_year_boundary_and_skips_present_months() {
+    let months = [
+        bucket("2023-11", 1, 1),
+        bucket("2024-02", 1, 1),
+        bucket("2024-03", 1, 1),
+    ];
+    assert_eq!(empty_months(&months), ["2023-12", "2024-01"]);
+    assert!(empty_months(&[bucket("2024-02", 1, 1)]).is_empty());
+    assert!(empty_months(&[]).is_empty());
+}
+
+/// 空表整张省略；键列按最长键左对齐。
+#[test]
+fn render_tables_aligns_keys_and_omits_empty_tables() {
+    let report = StatsReport {
+        scanned: 3,
+        media_files: 2,
+        non_media_files: 1,
+        total_bytes: 300,
+        by_year: vec![bucket("2024", 2, 300)],
+        by_camera: vec![bucket("Canon EOS 7D", 1, 200), bucket("unknown", 1, 100)],
+        empty_months: vec!["2024-02".into()],
+        ..StatsReport::default()
+    };
+    let mut out = Vec::new();
+    render_tables(&report, &mut out);
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.starts_with("scanned 3, media 2, non-media 1, 300 bytes\n"));
+    assert!(out.contains("\n## year\n2024         2             300\n"));
+    assert!(out.contains("\nunknown              1             100\n"));
+    assert!(out.ends_with("\n## empty months\n2024-02\n"));
+    assert!(!out.contains("## mime"));
+}
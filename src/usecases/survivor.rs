@@ -0,0 +1,220 @@
+//! `find` 重复组的保留者（survivor）选择：按 `--keep` 策略依次筛选，全部并列时
+//! 退回路径字典序（即旧行为的"组内首份"）。
+//!
+//! - 策略顺序即优先级：每条策略只在上一条并列的成员里继续筛，剩一份即定案，
+//!   `survivor_reason` 记下定案的那条策略及其取值
+//! - `--output` keep-dir 仍先于全部策略：组内已有副本在 output 下时，策略只在这些
+//!   副本里挑 survivor；只有一份在 output 下时理由记 `output_dir`
+//! - `metadata` 要读容器元数据，只对重复组成员按需解析，不对全量索引 `parse_exif`
+
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use camino::Utf8Path;
+use chrono::{DateTime, SecondsFormat, Utc};
+
+use crate::entities::common::under_prefix;
+
+/// 选不出更好者时的理由：组内路径字典序第一份。
+pub(crate) const REASON_PATH_ORDER: &str = "path_order";
+/// 组内只有一份副本位于 `--output` 下。
+pub(crate) const REASON_OUTPUT_DIR: &str = "output_dir";
+
+const SCHEMES: [&str; 4] = ["local", "smb", "mtp", "adb"];
+
+/// `find --keep` 的单条保留策略。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SurvivorPolicy {
+    /// `prefix:<PATH>`：位于该目录（含子目录）下的副本优先。
+    Prefix(String),
+    /// `oldest`：mtime 最早的优先；后端不提供 mtime 的排最后。
+    Oldest,
+    /// `shortest`：完整路径（按字符计）最短的优先。
+    Shortest,
+    /// `metadata`：解析到的元数据字段最多的优先（保留 EXIF 的胜过被剥离的）。
+    Metadata,
+    /// `scheme:<local|smb|mtp|adb>`：位于该后端上的副本优先。
+    Scheme(String),
+    /// `date-dir`：任一上级目录名形如日期（`2023`、`2023-05`、`2023_05_12 旅行`、
+    /// `20230512`）的优先。
+    DateDir,
+}
+
+impl FromStr for SurvivorPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, arg) = match s.split_once(':') {
+            Some((name, arg)) => (name, Some(arg)),
+            None => (s, None),
+        };
+        match (name.trim().to_ascii_lowercase().as_str(), arg) {
+            ("oldest", None) => Ok(Self::Oldest),
+            ("shortest", None) => Ok(Self::Shortest),
+            ("metadata", None) => Ok(Self::Metadata),
+            ("date-dir", None) => Ok(Self::DateDir),
+            ("prefix", Some(path)) if !path.is_empty() => Ok(Self::Prefix(path.to_string())),
+            ("scheme", Some(scheme)) => {
+                let scheme = scheme.to_ascii_lowercase();
+                if SCHEMES.contains(&scheme.as_str()) {
+                    Ok(Self::Scheme(scheme))
+                } else {
+                    Err(unknown_policy(s))
+                }
+            }
+            _ => Err(unknown_policy(s)),
+        }
+    }
+}
+
+fn unknown_policy(s: &str) -> String {
+    format!(
+        "unknown survivor policy '{s}'; expected prefix:<PATH>, oldest, shortest, metadata, \
+         scheme:<local|smb|mtp|adb> or date-dir"
+    )
+}
+
+impl SurvivorPolicy {
+    /// 是否需要解析成员的容器元数据（调用方据此决定是否为组成员打开 EXIF）。
+    #[must_use]
+    pub fn needs_metadata(&self) -> bool {
+        matches!(self, Self::Metadata)
+    }
+
+    // 越小越优先；同一策略下各成员的 rank 可直接比较。
+    fn rank(&self, m: &Member<'_>) -> u128 {
+        match self {
+            Self::Prefix(prefix) => u128::from(!under_prefix(m.path, prefix)),
+            Self::Oldest => m.modified.map_or(u128::MAX, |t| {
+                t.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_nanos())
+            }),
+            Self::Shortest => m.path.chars().count() as u128,
+            Self::Metadata => u128::MAX - m.metadata_fields as u128,
+            Self::Scheme(scheme) => u128::from(m.scheme != scheme),
+            Self::DateDir => u128::from(date_dir(m.path).is_none()),
+        }
+    }
+
+    // 定案时写进报告的理由：策略名 + 胜出者在该策略下的取值。
+    fn describe(&self, m: &Member<'_>) -> String {
+        match self {
+            Self::Prefix(prefix) => format!("prefix: under {prefix}"),
+            Self::Oldest => match m.modified {
+                Some(t) => format!(
+                    "oldest: mtime {}",
+                    DateTime::<Utc>::from(t).to_rfc3339_opts(SecondsFormat::Secs, true)
+                ),
+                None => "oldest: mtime unknown".to_string(),
+            },
+            Self::Shortest => format!("shortest: {} chars", m.path.chars().count()),
+            Self::Metadata => format!("metadata: {} fields", m.metadata_fields),
+            Self::Scheme(scheme) => format!("scheme: {scheme}"),
+            Self::DateDir => format!("date-dir: {}", date_dir(m.path).unwrap_or_default()),
+        }
+    }
+}
+
+/// 参与比较的重复组成员：由 find 从索引中的 `Info` 填充，单测可直接构造。
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Member<'a> {
+    /// 与报告 / 删除脚本一致的完整路径。
+    pub path: &'a str,
+    pub scheme: &'static str,
+    pub modified: Option<SystemTime>,
+    /// 仅在某条策略 [`SurvivorPolicy::needs_metadata`] 时解析，否则恒 0。
+    pub metadata_fields: usize,
+}
+
+/// 选出的保留者：`index` 为组内下标（组内路径已按字典序排列）。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Survivor {
+    pub index: usize,
+    pub reason: String,
+}
+
+/// 在 `candidates`（组内下标，升序）中按 `policies` 依次筛选保留者。
+/// `candidates` 只剩一份时直接返回 `seed_reason`（如 [`REASON_OUTPUT_DIR`]）。
+pub(crate) fn choose(
+    members: &[Member<'_>],
+    mut candidates: Vec<usize>,
+    policies: &[SurvivorPolicy],
+    seed_reason: &str,
+) -> Survivor {
+    debug_assert!(!candidates.is_empty(), "duplicate group must not be empty");
+    if candidates.len() == 1 {
+        return Survivor {
+            index: candidates[0],
+            reason: seed_reason.to_string(),
+        };
+    }
+    for policy in policies {
+        let best = candidates
+            .iter()
+            .map(|&i| policy.rank(&members[i]))
+            .min()
+            .unwrap_or_default();
+        candidates.retain(|&i| policy.rank(&members[i]) == best);
+        if let [only] = candidates[..] {
+            return Survivor {
+                index: only,
+                reason: policy.describe(&members[only]),
+            };
+        }
+    }
+    Survivor {
+        index: candidates[0],
+        reason: REASON_PATH_ORDER.to_string(),
+    }
+}
+
+// 自内向外找第一个形如日期的上级目录名；文件名本身不算。
+fn date_dir(path: &str) -> Option<&str> {
+    Utf8Path::new(path)
+        .parent()?
+        .iter()
+        .rev()
+        .find(|name| looks_like_date(name))
+}
+
+// `YYYY` 起头（1900..=2100），其后可接：无 / 紧随 `MM` 或 `MMDD` / 分隔符 `-_. ` 后的
+// `MM`（其余字符不再校验，`2023-05-12 旅行` 亦算）/ 分隔符后的非数字（`2023 旅行`）。
+// `1920x1080` 这类以字母紧随的不算。
+fn looks_like_date(name: &str) -> bool {
+    let Some(year) = name
+        .get(..4)
+        .filter(|y| y.bytes().all(|b| b.is_ascii_digit()))
+    else {
+        return false;
+    };
+    if !(1900..=2100).contains(&year.parse::<u16>().unwrap_or(0)) {
+        return false;
+    }
+    let rest = &name[4..];
+    let (digits, separated) = match rest.strip_prefix(['-', '_', '.', ' ']) {
+        Some(after) => (leading_digits(after), true),
+        None => (leading_digits(rest), false),
+    };
+    match digits.len() {
+        0 => separated || rest.is_empty(),
+        2 => valid_month(digits),
+        4 if !separated => valid_month(&digits[..2]) && valid_day(&digits[2..]),
+        _ => false,
+    }
+}
+
+fn leading_digits(s: &str) -> &str {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    &s[..end]
+}
+
+fn valid_month(mm: &str) -> bool {
+    (1..=12).contains(&mm.parse::<u8>().unwrap_or(0))
+}
+
+fn valid_day(dd: &str) -> bool {
+    (1..=31).contains(&dd.parse::<u8>().unwrap_or(0))
+}
+
+#[cfg(test)]
+#[path = "survivor_tests.rs"]
+mod tests;
@@ -0,0 +1,147 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use super::*;
+
+fn member(path: &str) -> Member<'_> {
+    Member {
+        path,
+        scheme: "local",
+        ..Member::default()
+    }
+}
+
+fn all(members: &[Member<'_>]) -> Vec<usize> {
+    (0..members.len()).collect()
+}
+
+#[test]
+fn parse_accepts_every_policy_and_rejects_unknown() {
+    let cases = [
+        ("oldest", SurvivorPolicy::Oldest),
+        ("Shortest", SurvivorPolicy::Shortest),
+        ("metadata", SurvivorPolicy::Metadata),
+        ("date-dir", SurvivorPolicy::DateDir),
+        ("prefix:/a/b", SurvivorPolicy::Prefix("/a/b".into())),
+        (
+            "prefix:smb://nas/share",
+            SurvivorPolicy::Prefix("smb://nas/share".into()),
+        ),
+        ("scheme:SMB", SurvivorPolicy::Scheme("smb".into())),
+    ];
+    for (input, want) in cases {
+        assert_eq!(input.parse::<SurvivorPolicy>().unwrap(), want, "{input}");
+    }
+    for bad in ["newest", "prefix:", "scheme:ftp", "oldest:1", ""] {
+        let err = bad.parse::<SurvivorPolicy>().unwrap_err();
+        assert!(err.contains("unknown survivor policy"), "{bad}: {err}");
+    }
+}
+
+/// 无策略 → 路径序首份；单候选 → seed 理由。
+#[test]
+fn choose_falls_back_to_path_order_and_seed_reason() {
+    let members = [member("/b/x.jpg"), member("/a/x.jpg")];
+    let got = choose(&members, all(&members), &[], REASON_PATH_ORDER);
+    assert_eq!(got.index, 0);
+    assert_eq!(got.reason, REASON_PATH_ORDER);
+
+    let got = choose(
+        &members,
+        vec![1],
+        &[SurvivorPolicy::Shortest],
+        REASON_OUTPUT_DIR,
+    );
+    assert_eq!(got.index, 1);
+    assert_eq!(got.reason, REASON_OUTPUT_DIR);
+}
+
+/// 策略按顺序作 tie-break：prefix 并列时由 oldest 定案，理由记定案策略。
+#[test]
+fn choose_applies_policies_in_order_as_tie_breakers() {
+    let mut members = vec![
+        member("/keep/new.jpg"),
+        member("/keep/old.jpg"),
+        member("/other/oldest.jpg"),
+    ];
+    members[0].modified = Some(UNIX_EPOCH + Duration::from_secs(2_000_000_000));
+    members[1].modified = Some(UNIX_EPOCH + Duration::from_secs(1_600_000_000));
+    members[2].modified = Some(UNIX_EPOCH + Duration::from_secs(1_000));
+    let policies = [
+        SurvivorPolicy::Prefix("/keep".into()),
+        SurvivorPolicy::Oldest,
+    ];
+    let got = choose(&members, all(&members), &policies, REASON_PATH_ORDER);
+    assert_eq!(got.index, 1);
+    assert_eq!(got.reason, "oldest: mtime 2020-09-13T12:26:40Z");
+
+    let got = choose(
+        &members,
+        all(&members),
+        &[SurvivorPolicy::Oldest],
+        REASON_PATH_ORDER,
+    );
+    assert_eq!(got.index, 2);
+}
+
+#[test]
+fn choose_by_shortest_metadata_scheme_and_date_dir() {
+    let mut members = vec![
+        member("/photos/2023-05-12 trip/IMG_0001.jpg"),
+        member("/dl/IMG_0001.jpg"),
+        member("/nas/IMG_0001 (1).jpg"),
+    ];
+    members[2].scheme = "smb";
+    members[2].metadata_fields = 3;
+
+    let cases = [
+        (SurvivorPolicy::Shortest, 1, "shortest: 16 chars"),
+        (SurvivorPolicy::Metadata, 2, "metadata: 3 fields"),
+        (SurvivorPolicy::Scheme("smb".into()), 2, "scheme: smb"),
+        (SurvivorPolicy::DateDir, 0, "date-dir: 2023-05-12 trip"),
+    ];
+    for (policy, index, reason) in cases {
+        let got = choose(&members, all(&members), &[policy], REASON_PATH_ORDER);
+        assert_eq!((got.index, got.reason.as_str()), (index, reason));
+    }
+}
+
+/// mtime 缺失排最后；全部并列时退回路径序。
+#[test]
+fn choose_oldest_ranks_unknown_mtime_last_and_ties_fall_through() {
+    let mut members = vec![member("/a.jpg"), member("/b.jpg")];
+    members[1].modified = Some(UNIX_EPOCH + Duration::from_secs(5));
+    let got = choose(&members, all(&members), &[SurvivorPolicy::Oldest], "");
+    assert_eq!(got.index, 1);
+
+    let tied = [member("/a.jpg"), member("/b.jpg")];
+    let got = choose(&tied, all(&tied), &[SurvivorPolicy::DateDir], "");
+    assert_eq!((got.index, got.reason.as_str()), (0, REASON_PATH_ORDER));
+}
+
+#[test]
+fn looks_like_date_accepts_common_folder_names() {
+    for name in [
+        "2023",
+        "2023-05",
+        "2023_05_12",
+        "2023.05",
+        "20230512",
+        "202305",
+        "2023 旅行",
+    ] {
+        assert!(looks_like_date(name), "{name}");
+    }
+    for name in [
+        "1920x1080",
+        "19201080",
+        "1850",
+        "2023-13",
+        "2023-0512",
+        "IMG_2023",
+        "20231",
+    ] {
+        assert!(!looks_like_date(name), "{name}");
+    }
+    // 文件名本身形如日期不算 date-dir。
+    assert_eq!(date_dir("/dl/20230512.jpg"), None);
+}
@@ -0,0 +1,47 @@
+//! `--min-rating` / `--label` / `--favorite` 过滤：copy / move / cull 共用。
+//!
+//! 设计决策：
+//! - 未评级按 0 星计（与 Lightroom "0 星 = 未评级" 同口径），`--min-rating 0` 即
+//!   "只排除拒绝（-1）的照片"。
+//! - 色标按 ASCII 大小写不敏感比较；Lightroom 本地化版本写入的 `红色` 等原样全等匹配。
+//! - 星级 / 色标来自 XMP（内嵌 packet 或 `.xmp` sidecar），读取在 entities / adapters，
+//!   这里只做判定。
+//! - 收藏只有 Photos 图库记录（XMP 无对应字段）：`--favorite` 下图库外的文件一律不通过。
+
+use crate::entities::tags::Tags;
+
+/// CLI 星级 / 色标 / 收藏过滤条件；全部未设时不过滤。
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TagFilter {
+    /// 最低星级（0..=5）；CLI 层已限定范围。
+    pub min_rating: Option<i8>,
+    /// 要求的色标（如 `Red`）。
+    pub label: Option<String>,
+    /// 只要 Photos 中标为收藏的文件。
+    pub favorite: bool,
+}
+
+impl TagFilter {
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        self.min_rating.is_some() || self.label.is_some() || self.favorite
+    }
+
+    /// `tags` 是否满足全部条件；未启用的条件视为通过。
+    #[must_use]
+    pub fn matches(&self, tags: &Tags) -> bool {
+        let rating_ok = self
+            .min_rating
+            .is_none_or(|min| tags.rating.unwrap_or(0) >= min);
+        let label_ok = self.label.as_deref().is_none_or(|want| {
+            tags.label
+                .as_deref()
+                .is_some_and(|have| have.trim().eq_ignore_ascii_case(want.trim()))
+        });
+        rating_ok && label_ok && (!self.favorite || tags.favorite)
+    }
+}
+
+#[cfg(test)]
+#[path = "tag_filter_tests.rs"]
+mod tests;
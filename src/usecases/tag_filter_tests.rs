@@ -0,0 +1,86 @@
+use super::*;
+
+fn tags(rating: Option<i8>, label: Option<&str>) -> Tags {
+    Tags {
+        rating,
+        label: label.map(str::to_string),
+        ..Tags::default()
+    }
+}
+
+#[test]
+fn inactive_filter_matches_everything() {
+    let f = TagFilter::default();
+    assert!(!f.is_active());
+    assert!(f.matches(&tags(Some(-1), None)));
+    assert!(f.matches(&Tags::default()));
+}
+
+/// 未评级按 0 星；`--min-rating 0` 仅排除拒绝（-1）。
+#[test]
+fn min_rating_treats_unrated_as_zero_and_rejects_minus_one() {
+    let zero = TagFilter {
+        min_rating: Some(0),
+        label: None,
+        ..TagFilter::default()
+    };
+    assert!(zero.matches(&Tags::default()));
+    assert!(!zero.matches(&tags(Some(-1), None)));
+
+    let three = TagFilter {
+        min_rating: Some(3),
+        label: None,
+        ..TagFilter::default()
+    };
+    assert!(three.is_active());
+    assert!(three.matches(&tags(Some(3), None)));
+    assert!(three.matches(&tags(Some(5), None)));
+    assert!(!three.matches(&tags(Some(2), None)));
+    assert!(!three.matches(&Tags::default()));
+}
+
+#[test]
+fn label_matches_case_insensitively_and_requires_presence() {
+    let red = TagFilter {
+        min_rating: None,
+        label: Some("red".into()),
+        ..TagFilter::default()
+    };
+    assert!(red.matches(&tags(None, Some("Red"))));
+    assert!(!red.matches(&tags(None, Some("Green"))));
+    assert!(!red.matches(&Tags::default()));
+    let cn = TagFilter {
+        min_rating: None,
+        label: Some("红色".into()),
+        ..TagFilter::default()
+    };
+    assert!(cn.matches(&tags(None, Some("红色"))));
+}
+
+#[test]
+fn both_conditions_must_hold() {
+    let f = TagFilter {
+        min_rating: Some(4),
+        label: Some("Red".into()),
+        ..TagFilter::default()
+    };
+    assert!(f.matches(&tags(Some(4), Some("Red"))));
+    assert!(!f.matches(&tags(Some(5), Some("Blue"))));
+    assert!(!f.matches(&tags(Some(1), Some("Red"))));
+}
+
+/// 收藏只认 Photos 标记：未标记（含图库外文件）一律不通过。
+#[test]
+fn favorite_requires_photos_flag() {
+    let f = TagFilter {
+        favorite: true,
+        ..TagFilter::default()
+    };
+    assert!(f.is_active());
+    assert!(!f.matches(&tags(Some(5), None)));
+    let fav = Tags {
+        favorite: true,
+        ..Tags::default()
+    };
+    assert!(f.matches(&fav));
+}
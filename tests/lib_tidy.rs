@@ -110,3 +110,27 @@ mod cull;
 
 #[path = "lib_tidy/office_archive.rs"]
 mod office_archive;
+
+#[path = "lib_tidy/rename.rs"]
+mod rename;
+
+#[path = "lib_tidy/find_apply.rs"]
+mod find_apply;
+
+#[path = "lib_tidy/find_similar.rs"]
+mod find_similar;
+
+#[path = "lib_tidy/catalog.rs"]
+mod catalog;
+
+#[path = "lib_tidy/stats.rs"]
+mod stats;
+
+#[path = "lib_tidy/audit.rs"]
+mod audit;
+
+#[path = "lib_tidy/faces.rs"]
+mod faces;
+
+#[path = "lib_tidy/photos_library.rs"]
+mod photos_library;
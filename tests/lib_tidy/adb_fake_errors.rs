@@ -35,6 +35,14 @@ fn adb_copy_reader_error_treats_file_as_skipped() {
             sources: vec![adb_root],
             output: local(out_dir.path().to_str().unwrap()),
             archive_template: None,
+            name_template: None,
+            min_rating: None,
+            label: None,
+            favorite: false,
+            catalog: None,
+            skip_known: false,
+            person: None,
+            faces_db: None,
             report: None,
         },
     )
@@ -83,6 +91,14 @@ fn adb_copy_mixed_files_one_failure_does_not_block_others() {
             sources: vec![adb_root],
             output: local(out_dir.path().to_str().unwrap()),
             archive_template: None,
+            name_template: None,
+            min_rating: None,
+            label: None,
+            favorite: false,
+            catalog: None,
+            skip_known: false,
+            person: None,
+            faces_db: None,
             report: None,
         },
     )
@@ -125,6 +141,12 @@ fn adb_find_walk_error_is_swallowed() {
             secure: false,
             sources: vec![adb_root],
             output: None,
+            keep: Vec::new(),
+            apply: None,
+            quarantine: None,
+            similar: false,
+            similar_video: false,
+            phash_max: None,
             report: None,
         },
     )
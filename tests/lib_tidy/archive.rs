@@ -4,7 +4,7 @@
 use std::sync::Arc;
 
 use tempfile::tempdir;
-use tidymedia::{Backend, Commands, FakeBackend, tidy, tidy_with};
+use tidymedia::{Backend, Commands, FakeBackend, run_cli, tidy, tidy_with};
 
 use super::{DATA_DIR, FakeBackendFactory, adb_loc, local, smb_loc};
 
@@ -17,6 +17,12 @@ fn tidy_dispatches_find_with_report_writes_json() {
         secure: false,
         sources: vec![local(DATA_DIR)],
         output: None,
+        keep: Vec::new(),
+        apply: None,
+        quarantine: None,
+        similar: false,
+        similar_video: false,
+        phash_max: None,
         report: Some(report_path.to_str().unwrap().to_string()),
     })
     .expect("find with report should succeed");
@@ -36,6 +42,14 @@ fn tidy_dispatches_copy_with_archive_template_validates() {
         sources: vec![local(DATA_DIR)],
         output: local(out.path().to_str().unwrap()),
         archive_template: Some("{year}/{month}/{day}".to_string()),
+        name_template: None,
+        min_rating: None,
+        label: None,
+        favorite: false,
+        catalog: None,
+        skip_known: false,
+        person: None,
+        faces_db: None,
         report: None,
     })
     .expect("copy with valid archive_template should succeed");
@@ -52,6 +66,14 @@ fn tidy_dispatches_move_with_archive_template_validates() {
         sources: vec![local(src.path().to_str().unwrap())],
         output: local(out.path().to_str().unwrap()),
         archive_template: Some("{year}/{month}".to_string()),
+        name_template: None,
+        min_rating: None,
+        label: None,
+        favorite: false,
+        catalog: None,
+        skip_known: false,
+        person: None,
+        faces_db: None,
         report: None,
     })
     .expect("move with valid archive_template should succeed");
@@ -67,6 +89,14 @@ fn tidy_rejects_copy_with_invalid_archive_template() {
         sources: vec![local(DATA_DIR)],
         output: local(out.path().to_str().unwrap()),
         archive_template: Some("{year/{month}".to_string()), // unbalanced brace
+        name_template: None,
+        min_rating: None,
+        label: None,
+        favorite: false,
+        catalog: None,
+        skip_known: false,
+        person: None,
+        faces_db: None,
         report: None,
     })
     .unwrap_err();
@@ -86,6 +116,14 @@ fn tidy_rejects_copy_with_template_lacking_safe_placeholder() {
         sources: vec![local(DATA_DIR)],
         output: local(out.path().to_str().unwrap()),
         archive_template: Some("{valuable_name}".to_string()),
+        name_template: None,
+        min_rating: None,
+        label: None,
+        favorite: false,
+        catalog: None,
+        skip_known: false,
+        person: None,
+        faces_db: None,
         report: None,
     })
     .unwrap_err();
@@ -104,6 +142,14 @@ fn tidy_rejects_move_with_invalid_archive_template() {
         sources: vec![local(src.path().to_str().unwrap())],
         output: local(out.path().to_str().unwrap()),
         archive_template: Some("year}".to_string()), // extra closing brace
+        name_template: None,
+        min_rating: None,
+        label: None,
+        favorite: false,
+        catalog: None,
+        skip_known: false,
+        person: None,
+        faces_db: None,
         report: None,
     })
     .unwrap_err();
@@ -131,6 +177,14 @@ fn tidy_dispatches_copy_with_archive_template_writes_year_month_day_layout() {
         sources: vec![local(src_dir.path().to_str().unwrap())],
         output: local(out.path().to_str().unwrap()),
         archive_template: Some("{year}/{month}/{day}".to_string()),
+        name_template: None,
+        min_rating: None,
+        label: None,
+        favorite: false,
+        catalog: None,
+        skip_known: false,
+        person: None,
+        faces_db: None,
         report: None,
     })
     .expect("copy with archive_template should succeed");
@@ -177,6 +231,14 @@ fn tidy_copy_uses_takeout_sidecar_time_for_undated_media() {
         sources: vec![local(src_dir.path().to_str().unwrap())],
         output: local(out.path().to_str().unwrap()),
         archive_template: Some("{year}/{month}".to_string()),
+        name_template: None,
+        min_rating: None,
+        label: None,
+        favorite: false,
+        catalog: None,
+        skip_known: false,
+        person: None,
+        faces_db: None,
         report: None,
     })
     .expect("copy with takeout sidecar should succeed");
@@ -190,6 +252,73 @@ fn tidy_copy_uses_takeout_sidecar_time_for_undated_media() {
     );
 }
 
+/// Lightroom 星级 / 色标端到端：`rated.jpg` 配 `rated.xmp`（`xmp:Rating=4`、
+/// `xmp:Label=Red`），`plain.jpg` 无 sidecar（未评级 = 0 星）。`--min-rating 3`
+/// 只放行前者，并按 `{rating}/{label}` 归档；未评级文件不进 output。
+#[test]
+fn tidy_copy_min_rating_filters_and_renders_rating_label() {
+    let src_dir = tempdir().unwrap();
+    std::fs::copy(
+        format!("{DATA_DIR}/sample-with-offset.jpg"),
+        src_dir.path().join("rated.jpg"),
+    )
+    .expect("copy fixture into tempdir");
+    std::fs::copy(
+        format!("{DATA_DIR}/sample-no-dates.jpg"),
+        src_dir.path().join("plain.jpg"),
+    )
+    .expect("copy fixture into tempdir");
+    std::fs::write(
+        src_dir.path().join("rated.xmp"),
+        r#"<x:xmpmeta xmlns:x="adobe:ns:meta/"><rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"><rdf:Description xmlns:xmp="http://ns.adobe.com/xap/1.0/" xmp:Rating="4" xmp:Label="Red"/></rdf:RDF></x:xmpmeta>"#,
+    )
+    .unwrap();
+
+    let out = tempdir().unwrap();
+    tidy(Commands::Copy {
+        dry_run: false,
+        include_non_media: false,
+        sources: vec![local(src_dir.path().to_str().unwrap())],
+        output: local(out.path().to_str().unwrap()),
+        archive_template: Some("{rating}/{label}".to_string()),
+        name_template: None,
+        min_rating: Some(3),
+        label: None,
+        favorite: false,
+        catalog: None,
+        skip_known: false,
+        person: None,
+        faces_db: None,
+        report: None,
+    })
+    .expect("copy with --min-rating should succeed");
+
+    assert!(out.path().join("4").join("Red").join("rated.jpg").is_file());
+    assert!(
+        !out.path().join("0").exists(),
+        "unrated plain.jpg must be filtered out"
+    );
+    assert!(src_dir.path().join("plain.jpg").is_file());
+}
+
+// `--min-rating` 由 clap 限定 0..=5：越界在解析期即 InvalidInput，不进 dispatch。
+#[test]
+fn run_cli_rejects_min_rating_out_of_range() {
+    let out = tempdir().unwrap();
+    let err = run_cli([
+        "tidymedia",
+        "copy",
+        "--dry-run",
+        "--min-rating",
+        "6",
+        "--output",
+        out.path().to_str().unwrap(),
+        DATA_DIR,
+    ])
+    .unwrap_err();
+    assert!(format!("{err}").contains("min-rating"), "got: {err}");
+}
+
 /// ADB source → SMB output：手机照片直接归档到 NAS，全程走 `FakeBackend` 不需真实设备/服务器。
 #[test]
 fn tidy_with_copy_adb_source_to_smb_output() {
@@ -216,6 +345,14 @@ fn tidy_with_copy_adb_source_to_smb_output() {
             sources: vec![adb_root],
             output: smb_root,
             archive_template: None,
+            name_template: None,
+            min_rating: None,
+            label: None,
+            favorite: false,
+            catalog: None,
+            skip_known: false,
+            person: None,
+            faces_db: None,
             report: None,
         },
     )
@@ -228,3 +365,59 @@ fn tidy_with_copy_adb_source_to_smb_output() {
         "expected smb to hold copied file at Inbox/1970/01/shot.jpg"
     );
 }
+
+/// `--name-template` 端到端（move）：媒体按模板改名，`shot.xmp` / `shot.jpg.json`
+/// 随之改名落在同目录；被认领的 sidecar 不再作为独立文件归到 `other/`。
+#[test]
+fn run_cli_move_with_name_template_renames_media_and_sidecars() {
+    let src_dir = tempdir().unwrap();
+    std::fs::copy(
+        format!("{DATA_DIR}/sample-no-dates.jpg"),
+        src_dir.path().join("shot.jpg"),
+    )
+    .expect("copy fixture into tempdir");
+    std::fs::write(src_dir.path().join("shot.xmp"), "<x:xmpmeta/>").unwrap();
+    std::fs::write(src_dir.path().join("shot.jpg.json"), "{}").unwrap();
+
+    let out = tempdir().unwrap();
+    run_cli([
+        "tidymedia",
+        "move",
+        "--include-non-media",
+        "--archive-template",
+        "{media_kind}",
+        "--name-template",
+        "{media_kind}_{original_name:upper}.{ext}",
+        "--output",
+        out.path().to_str().unwrap(),
+        src_dir.path().to_str().unwrap(),
+    ])
+    .expect("move with --name-template should succeed");
+
+    let dir = out.path().join("photo");
+    for name in ["photo_SHOT.jpg", "photo_SHOT.xmp", "photo_SHOT.jpg.json"] {
+        assert!(dir.join(name).is_file(), "missing {name}");
+    }
+    assert!(!out.path().join("other").exists());
+    assert_eq!(std::fs::read_dir(src_dir.path()).unwrap().count(), 0);
+}
+
+// 文件名模板字面含 `/` → dispatch 早期拒绝，不触碰任何文件。
+#[test]
+fn run_cli_rejects_name_template_with_separator() {
+    let out = tempdir().unwrap();
+    let err = run_cli([
+        "tidymedia",
+        "copy",
+        "--name-template",
+        "{year}/{original_name}",
+        "--output",
+        out.path().to_str().unwrap(),
+        DATA_DIR,
+    ])
+    .unwrap_err();
+    assert!(
+        format!("{err}").contains("invalid --name-template"),
+        "got: {err}"
+    );
+}
@@ -0,0 +1,52 @@
+//! `audit` 子命令的 dispatch 路径测试：只读审计 + JSON 报告。
+
+use tempfile::tempdir;
+use tidymedia::run_cli;
+
+use super::DATA_DIR;
+
+#[test]
+fn run_cli_audit_reports_future_dated_file() {
+    let src_dir = tempdir().unwrap();
+    let photo = src_dir.path().join("future.jpg");
+    std::fs::copy(format!("{DATA_DIR}/sample-future-2099.jpg"), &photo)
+        .expect("copy fixture into tempdir");
+    let aux = tempdir().unwrap();
+    let report = aux.path().join("audit.json");
+
+    run_cli([
+        "tidymedia",
+        "audit",
+        "--report",
+        report.to_str().unwrap(),
+        src_dir.path().to_str().unwrap(),
+    ])
+    .expect("audit should succeed");
+
+    assert!(photo.is_file());
+    let parsed: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&report).unwrap()).unwrap();
+    assert_eq!(parsed["media_files"], 1);
+    let findings = parsed["findings"].as_array().unwrap();
+    assert_eq!(findings.len(), 1, "{findings:?}");
+    assert_eq!(findings[0]["kind"], "future_date");
+    assert!(
+        findings[0]["paths"][0]
+            .as_str()
+            .unwrap()
+            .ends_with("future.jpg")
+    );
+}
+
+#[test]
+fn run_cli_audit_rejects_zero_gap_months() {
+    let src_dir = tempdir().unwrap();
+    let err = run_cli([
+        "tidymedia",
+        "audit",
+        "--gap-months",
+        "0",
+        src_dir.path().to_str().unwrap(),
+    ]);
+    assert!(err.is_err());
+}
@@ -31,6 +31,14 @@ fn tidy_with_copy_fake_smb_to_local_writes_file() {
             sources: vec![smb_root],
             output: out_loc,
             archive_template: None,
+            name_template: None,
+            min_rating: None,
+            label: None,
+            favorite: false,
+            catalog: None,
+            skip_known: false,
+            person: None,
+            faces_db: None,
             report: None,
         },
     )
@@ -73,6 +81,12 @@ fn tidy_with_find_mixed_local_smb_mtp_sources() {
                 local(local_src.path().to_str().unwrap()),
             ],
             output: None,
+            keep: Vec::new(),
+            apply: None,
+            quarantine: None,
+            similar: false,
+            similar_video: false,
+            phash_max: None,
             report: None,
         },
     )
@@ -104,6 +118,14 @@ fn tidy_with_move_local_to_fake_mtp_removes_src() {
             sources: vec![local(src_dir.path().to_str().unwrap())],
             output: mtp_root,
             archive_template: None,
+            name_template: None,
+            min_rating: None,
+            label: None,
+            favorite: false,
+            catalog: None,
+            skip_known: false,
+            person: None,
+            faces_db: None,
             report: None,
         },
     )
@@ -137,6 +159,14 @@ fn tidy_move_with_duplicate_removes_src_when_not_dry_run() {
         sources: vec![local(src_dir.path().to_str().unwrap())],
         output: local(out_dir.path().to_str().unwrap()),
         archive_template: None,
+        name_template: None,
+        min_rating: None,
+        label: None,
+        favorite: false,
+        catalog: None,
+        skip_known: false,
+        person: None,
+        faces_db: None,
         report: None,
     })
     .expect("move with duplicate should succeed");
@@ -169,6 +199,14 @@ fn tidy_move_dry_run_with_duplicate_skips_actual_remove() {
         sources: vec![local(src_dir.path().to_str().unwrap())],
         output: local(out_dir.path().to_str().unwrap()),
         archive_template: None,
+        name_template: None,
+        min_rating: None,
+        label: None,
+        favorite: false,
+        catalog: None,
+        skip_known: false,
+        person: None,
+        faces_db: None,
         report: None,
     })
     .expect("dry-run move with duplicate should succeed");
@@ -207,6 +245,14 @@ fn tidy_with_propagates_smb_open_read_error() {
             sources: vec![smb_root],
             output: local(out_dir.path().to_str().unwrap()),
             archive_template: None,
+            name_template: None,
+            min_rating: None,
+            label: None,
+            favorite: false,
+            catalog: None,
+            skip_known: false,
+            person: None,
+            faces_db: None,
             report: None,
         },
     )
@@ -240,6 +286,12 @@ fn tidy_with_propagates_smb_walk_error() {
             secure: true,
             sources: vec![smb_root],
             output: Some(local(out_dir.path().to_str().unwrap())),
+            keep: Vec::new(),
+            apply: None,
+            quarantine: None,
+            similar: false,
+            similar_video: false,
+            phash_max: None,
             report: None,
         },
     )
@@ -254,6 +306,12 @@ fn default_factory_smb_without_feature_returns_unsupported() {
         secure: false,
         sources: vec![smb_loc("photos")],
         output: None,
+        keep: Vec::new(),
+        apply: None,
+        quarantine: None,
+        similar: false,
+        similar_video: false,
+        phash_max: None,
         report: None,
     });
     let err = res.unwrap_err();
@@ -270,6 +328,12 @@ fn default_factory_adb_without_feature_returns_unsupported() {
         secure: false,
         sources: vec![adb_loc("/sdcard/DCIM")],
         output: None,
+        keep: Vec::new(),
+        apply: None,
+        quarantine: None,
+        similar: false,
+        similar_video: false,
+        phash_max: None,
         report: None,
     });
     let err = res.unwrap_err();
@@ -303,6 +367,14 @@ fn tidy_with_copy_fake_adb_to_local_writes_file() {
             sources: vec![adb_root],
             output: out_loc,
             archive_template: None,
+            name_template: None,
+            min_rating: None,
+            label: None,
+            favorite: false,
+            catalog: None,
+            skip_known: false,
+            person: None,
+            faces_db: None,
             report: None,
         },
     )
@@ -344,6 +416,12 @@ fn tidy_with_find_mixed_local_smb_adb_sources() {
                 local(local_src.path().to_str().unwrap()),
             ],
             output: None,
+            keep: Vec::new(),
+            apply: None,
+            quarantine: None,
+            similar: false,
+            similar_video: false,
+            phash_max: None,
             report: None,
         },
     )
@@ -373,6 +451,14 @@ fn tidy_with_move_local_to_fake_adb_removes_src() {
             sources: vec![local(src_dir.path().to_str().unwrap())],
             output: adb_root,
             archive_template: None,
+            name_template: None,
+            min_rating: None,
+            label: None,
+            favorite: false,
+            catalog: None,
+            skip_known: false,
+            person: None,
+            faces_db: None,
             report: None,
         },
     )
@@ -0,0 +1,72 @@
+//! `copy --catalog` / `--skip-known` 的 dispatch 路径测试：跨次运行识别曾归档过的内容。
+
+use tempfile::tempdir;
+use tidymedia::run_cli;
+
+use super::DATA_DIR;
+
+fn copy_report(args: &[&str]) -> serde_json::Value {
+    let aux = tempdir().unwrap();
+    let report = aux.path().join("copy.json");
+    let mut argv = vec!["tidymedia", "copy", "--report", report.to_str().unwrap()];
+    argv.extend_from_slice(args);
+    run_cli(argv).expect("copy should succeed");
+    serde_json::from_str(&std::fs::read_to_string(&report).unwrap()).unwrap()
+}
+
+#[test]
+fn run_cli_skip_known_skips_content_archived_in_an_earlier_run() {
+    let card = tempdir().unwrap();
+    std::fs::copy(
+        format!("{DATA_DIR}/sample-with-exif.jpg"),
+        card.path().join("DSC0001.jpg"),
+    )
+    .unwrap();
+    let state = tempdir().unwrap();
+    let catalog = state.path().join("catalog.json");
+    let catalog = catalog.to_str().unwrap();
+    let card_path = card.path().to_str().unwrap();
+
+    let archive = tempdir().unwrap();
+    let first = copy_report(&[
+        "--catalog",
+        catalog,
+        "-o",
+        archive.path().to_str().unwrap(),
+        card_path,
+    ]);
+    assert_eq!(first["copied"], 1);
+    let saved = std::fs::read_to_string(catalog).unwrap();
+    assert!(saved.contains(archive.path().to_str().unwrap()), "{saved}");
+
+    // 归档区已重组 / 离线：换一个空 output，catalog 仍认出该内容。
+    drop(archive);
+    let elsewhere = tempdir().unwrap();
+    let second = copy_report(&[
+        "--catalog",
+        catalog,
+        "--skip-known",
+        "-o",
+        elsewhere.path().to_str().unwrap(),
+        card_path,
+    ]);
+    assert_eq!(second["copied"], 0);
+    assert_eq!(second["ignored"], 1);
+    assert_eq!(std::fs::read_dir(elsewhere.path()).unwrap().count(), 0);
+}
+
+#[test]
+fn run_cli_skip_known_requires_a_catalog() {
+    let src = tempdir().unwrap();
+    let out = tempdir().unwrap();
+    let err = run_cli([
+        "tidymedia",
+        "move",
+        "--skip-known",
+        "-o",
+        out.path().to_str().unwrap(),
+        src.path().to_str().unwrap(),
+    ])
+    .unwrap_err();
+    assert!(err.to_string().contains("--skip-known requires"), "{err}");
+}
@@ -1,5 +1,5 @@
 //! `cull` 子命令端到端集成测试。覆盖：CLI clap 注册 / 4 build_* 路径校验 /
-//! source ⊆ output 保护 / dry-run / partial-failure / 真搬迁产物。
+//! source ⊆ output 保护 / dry-run / partial-failure / 真搬迁产物 / review 页 / apply-decisions / restore。
 //!
 //! 4 detector 真实 ONNX 模型 CI 不可触发——所有"流水线行为"测试用 PNG 字节 +
 //! 不存在的 model path 触发 `load_runnable` Err 路径，验 report.failed 计数。
@@ -49,9 +49,16 @@ fn dispatch_returns_invalid_input_when_scrfd_path_empty() {
     let err = tidy(Commands::Cull {
         dry_run: true,
         sources: vec![local(src.path().to_str().unwrap())],
-        output: local(out.path().to_str().unwrap()),
+        output: Some(local(out.path().to_str().unwrap())),
         phash_max: None,
+        group_by: None,
+        min_rating: None,
+        label: None,
         report: None,
+        review: None,
+        cache: None,
+        apply_decisions: None,
+        restore: false,
     })
     .unwrap_err();
     let msg = err.to_string();
@@ -66,9 +73,16 @@ fn dispatch_returns_invalid_input_when_facenet_path_empty() {
     let err = tidy(Commands::Cull {
         dry_run: true,
         sources: vec![local(src.path().to_str().unwrap())],
-        output: local(out.path().to_str().unwrap()),
+        output: Some(local(out.path().to_str().unwrap())),
         phash_max: None,
+        group_by: None,
+        min_rating: None,
+        label: None,
         report: None,
+        review: None,
+        cache: None,
+        apply_decisions: None,
+        restore: false,
     })
     .unwrap_err();
     let msg = err.to_string();
@@ -83,9 +97,16 @@ fn dispatch_returns_invalid_input_when_facemesh_path_empty() {
     let err = tidy(Commands::Cull {
         dry_run: true,
         sources: vec![local(src.path().to_str().unwrap())],
-        output: local(out.path().to_str().unwrap()),
+        output: Some(local(out.path().to_str().unwrap())),
         phash_max: None,
+        group_by: None,
+        min_rating: None,
+        label: None,
         report: None,
+        review: None,
+        cache: None,
+        apply_decisions: None,
+        restore: false,
     })
     .unwrap_err();
     let msg = err.to_string();
@@ -100,9 +121,16 @@ fn dispatch_returns_invalid_input_when_eyestate_path_empty() {
     let err = tidy(Commands::Cull {
         dry_run: true,
         sources: vec![local(src.path().to_str().unwrap())],
-        output: local(out.path().to_str().unwrap()),
+        output: Some(local(out.path().to_str().unwrap())),
         phash_max: None,
+        group_by: None,
+        min_rating: None,
+        label: None,
         report: None,
+        review: None,
+        cache: None,
+        apply_decisions: None,
+        restore: false,
     })
     .unwrap_err();
     let msg = err.to_string();
@@ -123,9 +151,16 @@ fn dispatch_ok_path_writes_report_when_source_has_no_image() {
     tidy(Commands::Cull {
         dry_run: true,
         sources: vec![local(src.path().to_str().unwrap())],
-        output: local(out.path().to_str().unwrap()),
+        output: Some(local(out.path().to_str().unwrap())),
         phash_max: None,
+        group_by: None,
+        min_rating: None,
+        label: None,
         report: Some(report_path.to_str().unwrap().to_string()),
+        review: None,
+        cache: None,
+        apply_decisions: None,
+        restore: false,
     })
     .expect("空 source → 不调 detector，build_* 懒加载不报错");
     let contents = fs::read_to_string(&report_path).expect("report written");
@@ -147,9 +182,16 @@ fn dispatch_non_dry_run_creates_output_dir() {
     tidy(Commands::Cull {
         dry_run: false,
         sources: vec![local(src.path().to_str().unwrap())],
-        output: local(out.path().to_str().unwrap()),
+        output: Some(local(out.path().to_str().unwrap())),
         phash_max: None,
+        group_by: None,
+        min_rating: None,
+        label: None,
         report: None,
+        review: None,
+        cache: None,
+        apply_decisions: None,
+        restore: false,
     })
     .expect("空 source + 非 dry-run → mkdir_p 成功，不调 detector");
 }
@@ -168,30 +210,27 @@ fn dispatch_propagates_usecase_error_when_source_inside_output() {
     let err = tidy(Commands::Cull {
         dry_run: true,
         sources: vec![local(src.to_str().unwrap())],
-        output: local(dir.path().to_str().unwrap()),
+        output: Some(local(dir.path().to_str().unwrap())),
         phash_max: None,
+        group_by: None,
+        min_rating: None,
+        label: None,
         report: None,
+        review: None,
+        cache: None,
+        apply_decisions: None,
+        restore: false,
     })
     .unwrap_err();
     let msg = err.to_string();
     assert!(msg.contains("is inside output"), "got: {msg}");
 }
 
-/// 两张相同 PNG → ahash 相同 → 同组 → 触发 SCRFD `load_runnable` Err → `report.failed`
-/// → `tidy()` 走 partial-failure arm。
-#[test]
-fn tidy_returns_err_when_cull_partial_failure() {
+/// 写两张 64×64 噪声 PNG（同 seed pattern → 同 phash 入同组；高 laplacian variance
+/// 保证 sharpness > sharpness_min=100 通过 filter_blurry，否则 grouped=0 不触发
+/// SCRFD load_runnable Err 无法到达 partial-failure arm）。
+fn write_noise_pngs(dir: &std::path::Path) {
     use image::ImageEncoder;
-    let _cfg = write_temp_config(
-        "/nonexistent/scrfd.onnx",
-        "/nonexistent/facenet.onnx",
-        "/nonexistent/facemesh.onnx",
-        "/nonexistent/eyestate.onnx",
-    );
-    let src = tempdir().unwrap();
-    // 写两张 64×64 噪声 PNG（同 seed pattern → 同 phash 入同组；高 laplacian variance
-    // 保证 sharpness > sharpness_min=100 通过 filter_blurry，否则 grouped=0 不触发
-    // SCRFD load_runnable Err 无法到达 partial-failure arm）。
     for name in &["a.png", "b.png"] {
         let mut buf = Vec::new();
         let mut pixels = Vec::with_capacity(64 * 64 * 3);
@@ -203,21 +242,122 @@ fn tidy_returns_err_when_cull_partial_failure() {
         image::codecs::png::PngEncoder::new(&mut buf)
             .write_image(&pixels, 64, 64, image::ExtendedColorType::Rgb8)
             .unwrap();
-        fs::write(src.path().join(name), buf).unwrap();
+        fs::write(dir.join(name), buf).unwrap();
     }
+}
+
+/// 两张相同 PNG → ahash 相同 → 同组 → 触发 SCRFD `load_runnable` Err → `report.failed`
+/// → `tidy()` 走 partial-failure arm。
+#[test]
+fn tidy_returns_err_when_cull_partial_failure() {
+    let _cfg = write_temp_config(
+        "/nonexistent/scrfd.onnx",
+        "/nonexistent/facenet.onnx",
+        "/nonexistent/facemesh.onnx",
+        "/nonexistent/eyestate.onnx",
+    );
+    let src = tempdir().unwrap();
+    write_noise_pngs(src.path());
     let out = tempdir().unwrap();
     let err = tidy(Commands::Cull {
         dry_run: true,
         sources: vec![local(src.path().to_str().unwrap())],
-        output: local(out.path().to_str().unwrap()),
+        output: Some(local(out.path().to_str().unwrap())),
         phash_max: None,
+        group_by: None,
+        min_rating: None,
+        label: None,
         report: None,
+        review: None,
+        cache: None,
+        apply_decisions: None,
+        restore: false,
     })
     .unwrap_err();
     let msg = err.to_string();
     assert!(msg.contains("cull partial failure"), "got: {msg}");
 }
 
+/// 推理失败也写回缓存：scan 特征已登记，失败图的推理输出不入缓存（下次重跑）。
+#[test]
+fn run_cli_cull_cache_keeps_scan_features_despite_inference_failure() {
+    let _cfg = write_temp_config(
+        "/nonexistent/scrfd.onnx",
+        "/nonexistent/facenet.onnx",
+        "/nonexistent/facemesh.onnx",
+        "/nonexistent/eyestate.onnx",
+    );
+    let src = tempdir().unwrap();
+    write_noise_pngs(src.path());
+    let out = tempdir().unwrap();
+    let state = tempdir().unwrap();
+    let cache = state.path().join("cull-cache.json");
+    let err = run_cli([
+        "tidymedia",
+        "cull",
+        "--dry-run",
+        "--cache",
+        cache.to_str().unwrap(),
+        "--output",
+        out.path().to_str().unwrap(),
+        src.path().to_str().unwrap(),
+    ])
+    .unwrap_err();
+    assert!(err.to_string().contains("cull partial failure"), "{err}");
+    let saved: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&cache).unwrap()).unwrap();
+    // 两张 PNG 字节相同 → 同一内容键。
+    let entries = saved["entries"].as_object().unwrap();
+    assert_eq!(entries.len(), 1);
+    let entry = entries.values().next().unwrap();
+    assert!(entry["phash"].is_u64(), "{entry}");
+    assert!(entry.get("analysis").is_none(), "{entry}");
+}
+
+#[test]
+fn run_cli_cull_rejects_corrupt_cache() {
+    let _cfg = write_temp_config("", "/tmp/m2", "/tmp/m3", "/tmp/m4");
+    let src = tempdir().unwrap();
+    let out = tempdir().unwrap();
+    let state = tempdir().unwrap();
+    let cache = state.path().join("cull-cache.json");
+    fs::write(&cache, b"{").unwrap();
+    // 缓存先于模型加载：即使 scrfd 路径为空，报的也是缓存损坏。
+    let err = run_cli([
+        "tidymedia",
+        "cull",
+        "--cache",
+        cache.to_str().unwrap(),
+        "--output",
+        out.path().to_str().unwrap(),
+        src.path().to_str().unwrap(),
+    ])
+    .unwrap_err();
+    assert!(err.to_string().contains("is corrupt"), "{err}");
+    assert_eq!(fs::read(&cache).unwrap(), b"{");
+}
+
+#[test]
+fn run_cli_cull_string_form_accepts_group_by_flag() {
+    // 同上：--group-by 经 FromStr 解析为 GroupBy 后走到 build_scrfd_detector 报错
+    let _cfg = write_temp_config("", "/tmp/m2", "/tmp/m3", "/tmp/m4");
+    let src = tempdir().unwrap();
+    let out = tempdir().unwrap();
+    let err = run_cli([
+        "tidymedia",
+        "cull",
+        "--dry-run",
+        "--group-by",
+        "time",
+        "--output",
+        out.path().to_str().unwrap(),
+        src.path().to_str().unwrap(),
+    ])
+    .unwrap_err();
+    let msg = err.to_string();
+    assert!(msg.contains("scrfd_model_path is empty"), "got: {msg}");
+}
+
 #[test]
 fn run_cli_cull_string_form_accepts_phash_max_flag() {
     // 字符串形式验 clap 名映射（--phash-max → phash_max: Option<u8>）
@@ -240,3 +380,189 @@ fn run_cli_cull_string_form_accepts_phash_max_flag() {
     let msg = err.to_string();
     assert!(msg.contains("scrfd_model_path is empty"), "got: {msg}");
 }
+
+#[test]
+fn dispatch_writes_review_page_alongside_report() {
+    let _cfg = write_temp_config(
+        "/nonexistent/scrfd.onnx",
+        "/nonexistent/facenet.onnx",
+        "/nonexistent/facemesh.onnx",
+        "/nonexistent/eyestate.onnx",
+    );
+    let src = tempdir().unwrap();
+    let out = tempdir().unwrap();
+    let review_path = out.path().join("review.html");
+    tidy(Commands::Cull {
+        dry_run: true,
+        sources: vec![local(src.path().to_str().unwrap())],
+        output: Some(local(out.path().to_str().unwrap())),
+        phash_max: None,
+        group_by: None,
+        min_rating: None,
+        label: None,
+        report: None,
+        review: Some(review_path.to_str().unwrap().to_string()),
+        cache: None,
+        apply_decisions: None,
+        restore: false,
+    })
+    .expect("空 source → 0 组，review 页照写");
+    let html = fs::read_to_string(&review_path).expect("review written");
+    assert!(html.contains("id=\"cull-decisions\""), "got: {html}");
+    assert!(html.contains("\"groups\":[]"), "got: {html}");
+}
+
+#[test]
+fn run_cli_cull_requires_output_without_apply_decisions() {
+    let src = tempdir().unwrap();
+    let err = run_cli(["tidymedia", "cull", src.path().to_str().unwrap()]).unwrap_err();
+    assert!(err.to_string().contains("--output"), "got: {err}");
+}
+
+/// review 页导出的决定把 best 换成 b：a 进 group 目录，b 回源处，`BEST_` 副本随之更换。
+/// apply-decisions 不加载任何模型，无需 face 配置。
+#[test]
+fn run_cli_cull_apply_decisions_swaps_best() {
+    let dir = tempdir().unwrap();
+    let src = dir.path().join("src");
+    let group = dir.path().join("out").join("group-001");
+    fs::create_dir_all(&src).unwrap();
+    fs::create_dir_all(&group).unwrap();
+    fs::write(src.join("a.jpg"), b"a").unwrap();
+    fs::write(group.join("BEST_a.jpg"), b"a").unwrap();
+    fs::write(group.join("b.jpg"), b"b").unwrap();
+    let path = |p: &std::path::Path| p.to_str().unwrap().to_string();
+    let decisions = serde_json::json!({
+        "version": 1,
+        "dry_run": false,
+        "groups": [{
+            "group_id": 1,
+            "best_dest": path(&group.join("BEST_a.jpg")),
+            "frames": [
+                { "source_path": path(&src.join("a.jpg")), "dest_path": null },
+                { "source_path": path(&src.join("b.jpg")), "dest_path": path(&group.join("b.jpg")) },
+            ],
+            "best": path(&src.join("b.jpg")),
+            "keep_all": false,
+        }],
+    });
+    let decisions_path = dir.path().join("cull-decisions.json");
+    fs::write(&decisions_path, decisions.to_string()).unwrap();
+    let report_path = dir.path().join("apply-report.json");
+    run_cli([
+        "tidymedia",
+        "cull",
+        "--apply-decisions",
+        decisions_path.to_str().unwrap(),
+        "--report",
+        report_path.to_str().unwrap(),
+    ])
+    .expect("apply decisions");
+    assert!(src.join("b.jpg").exists());
+    assert!(!src.join("a.jpg").exists());
+    assert!(group.join("a.jpg").exists());
+    assert!(group.join("BEST_b.jpg").exists());
+    assert!(!group.join("BEST_a.jpg").exists());
+    let contents = fs::read_to_string(&report_path).expect("report written");
+    assert!(contents.contains("\"moved\": 2"), "got: {contents}");
+}
+
+#[test]
+fn run_cli_cull_apply_decisions_rejects_malformed_file() {
+    let dir = tempdir().unwrap();
+    let decisions_path = dir.path().join("cull-decisions.json");
+    fs::write(&decisions_path, "{").unwrap();
+    let err = run_cli([
+        "tidymedia",
+        "cull",
+        "--apply-decisions",
+        decisions_path.to_str().unwrap(),
+    ])
+    .unwrap_err();
+    assert!(
+        err.to_string().contains("is not a valid decisions file"),
+        "got: {err}"
+    );
+}
+
+/// manifest 记录的劣质副本按 hash 校验后搬回源处，`BEST_` 副本、manifest 与空 group 目录删除。
+#[test]
+fn run_cli_cull_restore_moves_culled_back() {
+    let dir = tempdir().unwrap();
+    let src = dir.path().join("src");
+    let out = dir.path().join("out");
+    let group = out.join("group-001");
+    fs::create_dir_all(&src).unwrap();
+    fs::create_dir_all(&group).unwrap();
+    fs::write(src.join("a.jpg"), b"a").unwrap();
+    fs::write(group.join("BEST_a.jpg"), b"a").unwrap();
+    fs::write(group.join("b.jpg"), b"b").unwrap();
+    let path = |p: &std::path::Path| p.to_str().unwrap().to_string();
+    let manifest = serde_json::json!({
+        "group_id": 1,
+        "best": { "src": path(&src.join("a.jpg")), "dst": path(&group.join("BEST_a.jpg")) },
+        "culled": [{
+            "source_path": path(&src.join("b.jpg")),
+            "dest_path": path(&group.join("b.jpg")),
+            "content_hash": format!("{:016x}", xxhash_rust::xxh3::xxh3_64(b"b")),
+        }],
+    });
+    fs::write(group.join("MANIFEST.json"), manifest.to_string()).unwrap();
+    let report_path = dir.path().join("restore-report.json");
+    run_cli([
+        "tidymedia",
+        "cull",
+        "--restore",
+        "--output",
+        out.to_str().unwrap(),
+        "--report",
+        report_path.to_str().unwrap(),
+    ])
+    .expect("restore");
+    assert_eq!(fs::read(src.join("b.jpg")).unwrap(), b"b");
+    assert!(src.join("a.jpg").exists());
+    assert!(!group.exists());
+    let contents = fs::read_to_string(&report_path).expect("report written");
+    assert!(contents.contains("\"moved\": 1"), "got: {contents}");
+}
+
+#[test]
+fn run_cli_cull_restore_requires_output() {
+    let err = run_cli(["tidymedia", "cull", "--restore"]).unwrap_err();
+    assert!(err.to_string().contains("--output"), "got: {err}");
+}
+
+/// 复核页写不出来时 cull 返回 Err；报告已先落盘。
+#[test]
+fn dispatch_fails_when_review_page_cannot_be_written() {
+    let _cfg = write_temp_config(
+        "/nonexistent/scrfd.onnx",
+        "/nonexistent/facenet.onnx",
+        "/nonexistent/facemesh.onnx",
+        "/nonexistent/eyestate.onnx",
+    );
+    let src = tempdir().unwrap();
+    let out = tempdir().unwrap();
+    let report_path = out.path().join("cull-report.json");
+    let review_path = out.path().join("missing").join("review.html");
+    let err = tidy(Commands::Cull {
+        dry_run: true,
+        sources: vec![local(src.path().to_str().unwrap())],
+        output: Some(local(out.path().to_str().unwrap())),
+        phash_max: None,
+        group_by: None,
+        min_rating: None,
+        label: None,
+        report: Some(report_path.to_str().unwrap().to_string()),
+        review: Some(review_path.to_str().unwrap().to_string()),
+        cache: None,
+        apply_decisions: None,
+        restore: false,
+    })
+    .unwrap_err();
+    assert!(err.to_string().contains("review.html"), "got: {err}");
+    assert!(
+        report_path.is_file(),
+        "report is saved before the review page"
+    );
+}
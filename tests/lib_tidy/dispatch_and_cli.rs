@@ -27,6 +27,12 @@ fn tidy_dispatches_find_fast_on_data_dir() {
         secure: false,
         sources: vec![local(DATA_DIR)],
         output: None,
+        keep: Vec::new(),
+        apply: None,
+        quarantine: None,
+        similar: false,
+        similar_video: false,
+        phash_max: None,
         report: None,
     })
     .expect("find fast should succeed");
@@ -38,6 +44,12 @@ fn tidy_dispatches_find_secure_on_data_dir() {
         secure: true,
         sources: vec![local(DATA_DIR)],
         output: None,
+        keep: Vec::new(),
+        apply: None,
+        quarantine: None,
+        similar: false,
+        similar_video: false,
+        phash_max: None,
         report: None,
     })
     .expect("find secure should succeed");
@@ -50,6 +62,12 @@ fn tidy_dispatches_find_with_output_directory() {
         secure: false,
         sources: vec![local(DATA_DIR)],
         output: Some(local(out.path().to_str().unwrap())),
+        keep: Vec::new(),
+        apply: None,
+        quarantine: None,
+        similar: false,
+        similar_video: false,
+        phash_max: None,
         report: None,
     })
     .expect("find with output should succeed");
@@ -64,6 +82,14 @@ fn tidy_dispatches_copy_dry_run_on_data_dir() {
         sources: vec![local(DATA_DIR)],
         output: local(out.path().to_str().unwrap()),
         archive_template: None,
+        name_template: None,
+        min_rating: None,
+        label: None,
+        favorite: false,
+        catalog: None,
+        skip_known: false,
+        person: None,
+        faces_db: None,
         report: None,
     })
     .expect("copy dry run should succeed");
@@ -79,6 +105,14 @@ fn tidy_dispatches_move_dry_run_on_empty_source() {
         sources: vec![local(src.path().to_str().unwrap())],
         output: local(out.path().to_str().unwrap()),
         archive_template: None,
+        name_template: None,
+        min_rating: None,
+        label: None,
+        favorite: false,
+        catalog: None,
+        skip_known: false,
+        person: None,
+        faces_db: None,
         report: None,
     })
     .expect("move dry run should succeed");
@@ -97,6 +131,14 @@ fn tidy_dispatches_copy_with_report_writes_json() {
         sources: vec![local(DATA_DIR)],
         output: local(out.path().to_str().unwrap()),
         archive_template: None,
+        name_template: None,
+        min_rating: None,
+        label: None,
+        favorite: false,
+        catalog: None,
+        skip_known: false,
+        person: None,
+        faces_db: None,
         report: Some(report_path.to_str().unwrap().to_string()),
     })
     .expect("copy with report should succeed");
@@ -116,6 +158,14 @@ fn tidy_dispatches_move_with_report_writes_json() {
         sources: vec![local(src.path().to_str().unwrap())],
         output: local(out.path().to_str().unwrap()),
         archive_template: None,
+        name_template: None,
+        min_rating: None,
+        label: None,
+        favorite: false,
+        catalog: None,
+        skip_known: false,
+        person: None,
+        faces_db: None,
         report: Some(report_path.to_str().unwrap().to_string()),
     })
     .expect("move with report should succeed");
@@ -137,6 +187,12 @@ fn tidy_rejects_smb_uri_with_clear_error() {
             path: Utf8PathBuf::new(),
         }],
         output: None,
+        keep: Vec::new(),
+        apply: None,
+        quarantine: None,
+        similar: false,
+        similar_video: false,
+        phash_max: None,
         report: None,
     });
     let err = res.unwrap_err();
@@ -157,6 +213,14 @@ fn tidy_rejects_mtp_output_with_clear_error() {
             path: Utf8PathBuf::new(),
         },
         archive_template: None,
+        name_template: None,
+        min_rating: None,
+        label: None,
+        favorite: false,
+        catalog: None,
+        skip_known: false,
+        person: None,
+        faces_db: None,
         report: None,
     });
     let err = res.unwrap_err();
@@ -171,6 +235,12 @@ fn tidy_rejects_adb_uri_with_clear_error() {
         secure: false,
         sources: vec![adb_loc("/sdcard/DCIM")],
         output: None,
+        keep: Vec::new(),
+        apply: None,
+        quarantine: None,
+        similar: false,
+        similar_video: false,
+        phash_max: None,
         report: None,
     });
     let err = res.unwrap_err();
@@ -187,6 +257,14 @@ fn tidy_rejects_adb_output_with_clear_error() {
         sources: vec![local(DATA_DIR)],
         output: adb_loc("/sdcard/Out"),
         archive_template: None,
+        name_template: None,
+        min_rating: None,
+        label: None,
+        favorite: false,
+        catalog: None,
+        skip_known: false,
+        person: None,
+        faces_db: None,
         report: None,
     });
     let err = res.unwrap_err();
@@ -211,6 +289,14 @@ fn tidy_rejects_copy_smb_source() {
         }],
         output: local(out.path().to_str().unwrap()),
         archive_template: None,
+        name_template: None,
+        min_rating: None,
+        label: None,
+        favorite: false,
+        catalog: None,
+        skip_known: false,
+        person: None,
+        faces_db: None,
         report: None,
     });
     assert!(format!("{}", res.unwrap_err()).contains("smb-backend not enabled"));
@@ -228,6 +314,12 @@ fn tidy_rejects_find_mtp_output() {
             storage: "s".into(),
             path: Utf8PathBuf::new(),
         }),
+        keep: Vec::new(),
+        apply: None,
+        quarantine: None,
+        similar: false,
+        similar_video: false,
+        phash_max: None,
         report: None,
     });
     assert!(format!("{}", res.unwrap_err()).contains("mtp-backend not enabled"));
@@ -250,6 +342,14 @@ fn tidy_rejects_move_smb_source() {
         }],
         output: local(out.path().to_str().unwrap()),
         archive_template: None,
+        name_template: None,
+        min_rating: None,
+        label: None,
+        favorite: false,
+        catalog: None,
+        skip_known: false,
+        person: None,
+        faces_db: None,
         report: None,
     });
     assert!(format!("{}", res.unwrap_err()).contains("smb-backend not enabled"));
@@ -270,6 +370,14 @@ fn tidy_rejects_move_mtp_output() {
             path: Utf8PathBuf::new(),
         },
         archive_template: None,
+        name_template: None,
+        min_rating: None,
+        label: None,
+        favorite: false,
+        catalog: None,
+        skip_known: false,
+        person: None,
+        faces_db: None,
         report: None,
     });
     assert!(format!("{}", res.unwrap_err()).contains("mtp-backend not enabled"));
@@ -300,6 +408,12 @@ fn tidy_find_propagates_error_when_output_is_file() {
         secure: false,
         sources: vec![local(DATA_DIR)],
         output: Some(local(blocker.to_str().unwrap())),
+        keep: Vec::new(),
+        apply: None,
+        quarantine: None,
+        similar: false,
+        similar_video: false,
+        phash_max: None,
         report: None,
     });
     assert!(res.is_err(), "find output must be an existing directory");
@@ -314,6 +428,12 @@ fn tidy_find_propagates_error_when_output_missing() {
         secure: false,
         sources: vec![local(DATA_DIR)],
         output: Some(local("/no/such/dir/xyz")),
+        keep: Vec::new(),
+        apply: None,
+        quarantine: None,
+        similar: false,
+        similar_video: false,
+        phash_max: None,
         report: None,
     });
     let err = res.unwrap_err();
@@ -350,6 +470,12 @@ fn tidy_find_propagates_permission_denied_metadata_error() {
             secure: false,
             sources: vec![local(DATA_DIR)],
             output: Some(smb_out),
+            keep: Vec::new(),
+            apply: None,
+            quarantine: None,
+            similar: false,
+            similar_video: false,
+            phash_max: None,
             report: None,
         },
     )
@@ -377,6 +503,14 @@ fn tidy_copy_propagates_mkdir_error_when_output_parent_is_file() {
         sources: vec![local(DATA_DIR)],
         output: local(bad_out.to_str().unwrap()),
         archive_template: None,
+        name_template: None,
+        min_rating: None,
+        label: None,
+        favorite: false,
+        catalog: None,
+        skip_known: false,
+        person: None,
+        faces_db: None,
         report: None,
     });
     assert!(res.is_err(), "mkdir_p must fail when parent is a file");
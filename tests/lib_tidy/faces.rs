@@ -0,0 +1,193 @@
+//! `faces list` / `faces name` / `faces split` 与 `copy --person` / `{person}` 的 dispatch 路径测试。
+//! `faces scan` 需真实 ONNX 模型，由 usecase 单测经 Fake detector 覆盖；这里手写人脸库。
+
+use std::path::Path;
+
+use sha2::{Digest, Sha512};
+use tempfile::tempdir;
+use tidymedia::run_cli;
+
+use super::DATA_DIR;
+
+/// 写一个只含 `image` 内容的人脸库：一张脸，簇 1，可选命名。
+fn write_face_db(path: &Path, image: &Path, name: Option<&str>) {
+    let key = hex::encode(&Sha512::digest(std::fs::read(image).unwrap())[..]);
+    let mut embedding = vec![0.0_f32; 128];
+    embedding[0] = 1.0;
+    let names = name.map_or_else(|| serde_json::json!({}), |n| serde_json::json!({ "1": n }));
+    let db = serde_json::json!({
+        "version": 1,
+        "images": {
+            key: {
+                "location": image.to_str().unwrap(),
+                "faces": [{ "bbox": [0.0, 0.0, 10.0, 10.0], "embedding": embedding, "cluster": 1 }],
+            },
+        },
+        "names": names,
+        "next_cluster_id": 2,
+    });
+    std::fs::write(path, db.to_string()).unwrap();
+}
+
+#[test]
+fn run_cli_faces_name_persists_and_list_reports_it() {
+    let state = tempdir().unwrap();
+    let db = state.path().join("faces.json");
+    write_face_db(
+        &db,
+        Path::new(&format!("{DATA_DIR}/sample-with-exif.jpg")),
+        None,
+    );
+    let db = db.to_str().unwrap();
+
+    run_cli(["tidymedia", "faces", "name", "1", "Alice", "--db", db]).unwrap();
+    let saved: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(db).unwrap()).unwrap();
+    assert_eq!(saved["names"]["1"], "Alice");
+
+    let report = state.path().join("list.json");
+    run_cli([
+        "tidymedia",
+        "faces",
+        "list",
+        "--db",
+        db,
+        "--report",
+        report.to_str().unwrap(),
+    ])
+    .unwrap();
+    let listed: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&report).unwrap()).unwrap();
+    assert_eq!(listed["clusters"][0]["id"], 1);
+    assert_eq!(listed["clusters"][0]["name"], "Alice");
+    assert_eq!(listed["clusters"][0]["faces"], 1);
+
+    let err = run_cli(["tidymedia", "faces", "name", "9", "Bob", "--db", db]).unwrap_err();
+    assert!(
+        err.to_string().contains("face cluster 9 does not exist"),
+        "{err}"
+    );
+}
+
+/// 两张正交的脸误并在簇 1：split 留 1 号与名字给先出现的一份，另一份得 2 号并写回库。
+#[test]
+fn run_cli_faces_split_persists_new_cluster() {
+    let state = tempdir().unwrap();
+    let db = state.path().join("faces.json");
+    let (mut alice, mut bob) = (vec![0.0_f32; 128], vec![0.0_f32; 128]);
+    alice[0] = 1.0;
+    bob[1] = 1.0;
+    let face = |embedding: &[f32]| serde_json::json!({ "bbox": [0.0, 0.0, 10.0, 10.0], "embedding": embedding, "cluster": 1 });
+    let json = serde_json::json!({
+        "version": 1,
+        "images": {
+            "00": { "location": "/a.jpg", "faces": [face(&alice)] },
+            "01": { "location": "/b.jpg", "faces": [face(&bob)] },
+        },
+        "names": { "1": "Alice" },
+        "next_cluster_id": 2,
+    });
+    std::fs::write(&db, json.to_string()).unwrap();
+    let db = db.to_str().unwrap();
+
+    let err = run_cli([
+        "tidymedia",
+        "faces",
+        "split",
+        "1",
+        "--cosine-min",
+        "-0.5",
+        "--db",
+        db,
+    ])
+    .unwrap_err();
+    assert!(err.to_string().contains("stays whole"), "{err}");
+
+    run_cli(["tidymedia", "faces", "split", "1", "--db", db]).unwrap();
+    let saved: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(db).unwrap()).unwrap();
+    assert_eq!(saved["images"]["00"]["faces"][0]["cluster"], 1);
+    assert_eq!(saved["images"]["01"]["faces"][0]["cluster"], 2);
+    assert_eq!(saved["names"], serde_json::json!({ "1": "Alice" }));
+    assert_eq!(saved["next_cluster_id"], 3);
+}
+
+#[test]
+fn run_cli_faces_rejects_corrupt_db() {
+    let state = tempdir().unwrap();
+    let db = state.path().join("faces.json");
+    std::fs::write(&db, b"{").unwrap();
+    let err = run_cli(["tidymedia", "faces", "list", "--db", db.to_str().unwrap()]).unwrap_err();
+    assert!(err.to_string().contains("is corrupt"), "{err}");
+}
+
+/// `--person` 只复制库里含该人物的图；`{person}` 把它归到人名目录。
+#[test]
+fn run_cli_copy_person_filters_and_names_folder() {
+    let card = tempdir().unwrap();
+    for name in ["sample-with-exif.jpg", "sample-with-gps.jpg"] {
+        std::fs::copy(format!("{DATA_DIR}/{name}"), card.path().join(name)).unwrap();
+    }
+    let state = tempdir().unwrap();
+    let db = state.path().join("faces.json");
+    write_face_db(
+        &db,
+        &card.path().join("sample-with-exif.jpg"),
+        Some("Alice"),
+    );
+    let out = tempdir().unwrap();
+
+    run_cli([
+        "tidymedia",
+        "copy",
+        "--faces-db",
+        db.to_str().unwrap(),
+        "--person",
+        "alice",
+        "--archive-template",
+        "People/{person}",
+        "-o",
+        out.path().to_str().unwrap(),
+        card.path().to_str().unwrap(),
+    ])
+    .unwrap();
+    assert!(
+        out.path()
+            .join("People/Alice/sample-with-exif.jpg")
+            .is_file()
+    );
+    assert!(!out.path().join("People/unknown").exists());
+}
+
+#[test]
+fn run_cli_copy_person_requires_db_and_known_name() {
+    let card = tempdir().unwrap();
+    let out = tempdir().unwrap();
+    let (out, card) = (out.path().to_str().unwrap(), card.path().to_str().unwrap());
+    let err = run_cli(["tidymedia", "copy", "--person", "Alice", "-o", out, card]).unwrap_err();
+    assert!(err.to_string().contains("requires --faces-db"), "{err}");
+
+    let state = tempdir().unwrap();
+    let db = state.path().join("faces.json");
+    write_face_db(
+        &db,
+        Path::new(&format!("{DATA_DIR}/sample-with-exif.jpg")),
+        Some("Bob"),
+    );
+    let err = run_cli([
+        "tidymedia",
+        "copy",
+        "--person",
+        "Alice",
+        "--faces-db",
+        db.to_str().unwrap(),
+        "-o",
+        out,
+        card,
+    ])
+    .unwrap_err();
+    assert!(
+        err.to_string().contains("matches no named face cluster"),
+        "{err}"
+    );
+}
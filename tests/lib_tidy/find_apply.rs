@@ -0,0 +1,104 @@
+//! `find --apply` 的 dispatch 路径测试：直接处置重复副本 + 报告 + 参数组合校验。
+
+use tempfile::tempdir;
+use tidymedia::run_cli;
+
+#[test]
+fn run_cli_find_apply_quarantine_keeps_one_copy_and_reports() {
+    let src = tempdir().unwrap();
+    for name in ["a.jpg", "b.jpg", "c.jpg"] {
+        std::fs::write(src.path().join(name), b"duplicate payload").unwrap();
+    }
+    let aux = tempdir().unwrap();
+    let quarantine = aux.path().join("dups");
+    let report = aux.path().join("find.json");
+
+    run_cli([
+        "tidymedia",
+        "find",
+        "--apply",
+        "quarantine",
+        "--quarantine",
+        quarantine.to_str().unwrap(),
+        "--report",
+        report.to_str().unwrap(),
+        src.path().to_str().unwrap(),
+    ])
+    .expect("find --apply quarantine should succeed");
+
+    // 路径字典序首份为 survivor，其余两份移入隔离目录。
+    assert!(src.path().join("a.jpg").is_file());
+    assert!(!src.path().join("b.jpg").exists());
+    assert!(!src.path().join("c.jpg").exists());
+
+    let parsed: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&report).unwrap()).unwrap();
+    let resolutions = parsed["resolutions"].as_array().unwrap();
+    assert_eq!(resolutions.len(), 2);
+    for r in resolutions {
+        assert_eq!(r["action"], "quarantine");
+        let target = r["target"].as_str().unwrap();
+        assert!(std::path::Path::new(target).is_file(), "missing {target}");
+    }
+    assert!(parsed["errors"].as_array().unwrap().is_empty());
+}
+
+// `--quarantine` 不配 `--apply quarantine`：扫描前即拒绝，文件不动。
+#[test]
+fn run_cli_find_rejects_quarantine_without_matching_apply() {
+    let src = tempdir().unwrap();
+    for name in ["a.jpg", "b.jpg"] {
+        std::fs::write(src.path().join(name), b"duplicate payload").unwrap();
+    }
+    let aux = tempdir().unwrap();
+    for extra in [&["--apply", "delete"][..], &[][..]] {
+        let mut args = vec!["tidymedia", "find"];
+        args.extend_from_slice(extra);
+        args.extend(["--quarantine", aux.path().to_str().unwrap()]);
+        args.push(src.path().to_str().unwrap());
+        let err = run_cli(args).expect_err("mismatched --quarantine must fail");
+        assert!(err.to_string().contains("--quarantine"), "{err}");
+    }
+    assert!(src.path().join("b.jpg").is_file());
+}
+
+// 再次 `--apply hardlink`：副本已是 survivor 的硬链接，视为已处置，不留临时链接文件。
+#[test]
+fn run_cli_find_apply_hardlink_twice_leaves_no_temp_links() {
+    let src = tempdir().unwrap();
+    for name in ["a.jpg", "b.jpg"] {
+        std::fs::write(src.path().join(name), b"duplicate payload").unwrap();
+    }
+    let aux = tempdir().unwrap();
+    let report = aux.path().join("find.json");
+
+    for _ in 0..2 {
+        run_cli([
+            "tidymedia",
+            "find",
+            "--apply",
+            "hardlink",
+            "--report",
+            report.to_str().unwrap(),
+            src.path().to_str().unwrap(),
+        ])
+        .expect("find --apply hardlink should succeed");
+
+        let parsed: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&report).unwrap()).unwrap();
+        assert!(parsed["errors"].as_array().unwrap().is_empty(), "{parsed}");
+        let mut names: Vec<_> = std::fs::read_dir(src.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        assert_eq!(names, ["a.jpg", "b.jpg"]);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        let a = std::fs::metadata(src.path().join("a.jpg")).unwrap();
+        let b = std::fs::metadata(src.path().join("b.jpg")).unwrap();
+        assert_eq!(a.ino(), b.ino());
+    }
+}
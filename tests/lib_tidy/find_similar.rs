@@ -0,0 +1,67 @@
+//! `find --similar` 的 dispatch 路径测试：近似组落报告 + 与字节判等 flag 的互斥。
+
+use image::RgbImage;
+use tempfile::tempdir;
+use tidymedia::run_cli;
+
+#[test]
+fn run_cli_find_similar_reports_resized_copy() {
+    let src = tempdir().unwrap();
+    let original = RgbImage::from_fn(200, 200, |x, y| {
+        let v = u8::try_from((x * 3 + y) % 256).unwrap();
+        image::Rgb([v, 255 - v, v / 3])
+    });
+    original.save(src.path().join("original.png")).unwrap();
+    image::imageops::resize(&original, 80, 80, image::imageops::FilterType::Triangle)
+        .save(src.path().join("small.png"))
+        .unwrap();
+    let aux = tempdir().unwrap();
+    let report = aux.path().join("similar.json");
+
+    run_cli([
+        "tidymedia",
+        "find",
+        "--similar",
+        "--phash-max",
+        "12",
+        "--report",
+        report.to_str().unwrap(),
+        src.path().to_str().unwrap(),
+    ])
+    .expect("find --similar should succeed");
+
+    let parsed: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&report).unwrap()).unwrap();
+    let groups = parsed["similar_groups"].as_array().unwrap();
+    assert_eq!(groups.len(), 1, "{parsed}");
+    assert!(
+        groups[0]["keep"]
+            .as_str()
+            .unwrap()
+            .ends_with("original.png")
+    );
+    assert_eq!(groups[0]["members"][1]["width"], 80);
+    // 近似判定不改动任何文件。
+    assert!(src.path().join("small.png").is_file());
+}
+
+#[test]
+fn run_cli_find_similar_rejects_byte_level_flags() {
+    let src = tempdir().unwrap();
+    let path = src.path().to_str().unwrap();
+    for extra in [
+        &["--secure"][..],
+        &["--apply", "delete"][..],
+        &["--keep", "oldest"][..],
+    ] {
+        let mut args = vec!["tidymedia", "find", "--similar"];
+        args.extend_from_slice(extra);
+        args.push(path);
+        let err = run_cli(args).expect_err("conflicting flags must fail");
+        assert!(err.to_string().contains("cannot be used with"), "{err}");
+    }
+    let err = run_cli(["tidymedia", "find", "--similar", "--similar-video", path]).unwrap_err();
+    assert!(err.to_string().contains("cannot be used with"), "{err}");
+    let err = run_cli(["tidymedia", "find", "--phash-max", "5", path]).unwrap_err();
+    assert!(err.to_string().contains("--similar"), "{err}");
+}
@@ -45,6 +45,14 @@ fn move_keeps_src_when_target_open_write_fails() {
             sources: vec![local(src_dir.path().to_str().unwrap())],
             output: smb_root,
             archive_template: None,
+            name_template: None,
+            min_rating: None,
+            label: None,
+            favorite: false,
+            catalog: None,
+            skip_known: false,
+            person: None,
+            faces_db: None,
             report: None,
         },
     )
@@ -93,6 +101,14 @@ fn move_keeps_src_and_dst_when_remove_file_fails() {
             sources: vec![smb_src_root],
             output: local(out_dir.path().to_str().unwrap()),
             archive_template: None,
+            name_template: None,
+            min_rating: None,
+            label: None,
+            favorite: false,
+            catalog: None,
+            skip_known: false,
+            person: None,
+            faces_db: None,
             report: None,
         },
     )
@@ -146,6 +162,14 @@ fn move_retry_after_target_open_write_failure_succeeds() {
             sources: vec![local(src_dir.path().to_str().unwrap())],
             output: smb_root.clone(),
             archive_template: None,
+            name_template: None,
+            min_rating: None,
+            label: None,
+            favorite: false,
+            catalog: None,
+            skip_known: false,
+            person: None,
+            faces_db: None,
             report: None,
         },
     )
@@ -171,6 +195,14 @@ fn move_retry_after_target_open_write_failure_succeeds() {
             sources: vec![local(src_dir.path().to_str().unwrap())],
             output: smb_root,
             archive_template: None,
+            name_template: None,
+            min_rating: None,
+            label: None,
+            favorite: false,
+            catalog: None,
+            skip_known: false,
+            person: None,
+            faces_db: None,
             report: None,
         },
     )
@@ -216,6 +248,14 @@ fn move_keeps_src_when_target_write_fails() {
             sources: vec![local(src_dir.path().to_str().unwrap())],
             output: smb_root,
             archive_template: None,
+            name_template: None,
+            min_rating: None,
+            label: None,
+            favorite: false,
+            catalog: None,
+            skip_known: false,
+            person: None,
+            faces_db: None,
             report: None,
         },
     )
@@ -265,6 +305,14 @@ fn copy_succeeds_when_dst_open_read_would_fail_after_transfer() {
             sources: vec![local(src_dir.path().to_str().unwrap())],
             output: smb_root,
             archive_template: None,
+            name_template: None,
+            min_rating: None,
+            label: None,
+            favorite: false,
+            catalog: None,
+            skip_known: false,
+            person: None,
+            faces_db: None,
             report: None,
         },
     )
@@ -308,6 +356,14 @@ fn copy_reports_failure_when_unique_name_exhausted() {
             sources: vec![local(src_dir.path().to_str().unwrap())],
             output: local(out.path().to_str().unwrap()),
             archive_template: None,
+            name_template: None,
+            min_rating: None,
+            label: None,
+            favorite: false,
+            catalog: None,
+            skip_known: false,
+            person: None,
+            faces_db: None,
             report: None,
         },
     )
@@ -346,6 +402,14 @@ fn tidy_returns_err_when_copy_partial_failure() {
         sources: vec![local(src_dir.path().to_str().unwrap())],
         output: local(out.path().to_str().unwrap()),
         archive_template: None,
+        name_template: None,
+        min_rating: None,
+        label: None,
+        favorite: false,
+        catalog: None,
+        skip_known: false,
+        person: None,
+        faces_db: None,
         report: None,
     })
     .expect_err("tidy must surface partial failure as Err for non-zero CLI exit");
@@ -383,6 +447,14 @@ fn tidy_returns_err_when_move_partial_failure() {
         sources: vec![local(src_dir.path().to_str().unwrap())],
         output: local(out.path().to_str().unwrap()),
         archive_template: None,
+        name_template: None,
+        min_rating: None,
+        label: None,
+        favorite: false,
+        catalog: None,
+        skip_known: false,
+        person: None,
+        faces_db: None,
         report: None,
     })
     .expect_err("tidy must surface move partial failure as Err");
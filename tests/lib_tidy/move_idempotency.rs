@@ -18,6 +18,14 @@ fn move_cmd(src: &std::path::Path, out: &std::path::Path, dry_run: bool) -> Comm
         sources: vec![local(src.to_str().unwrap())],
         output: local(out.to_str().unwrap()),
         archive_template: None,
+        name_template: None,
+        min_rating: None,
+        label: None,
+        favorite: false,
+        catalog: None,
+        skip_known: false,
+        person: None,
+        faces_db: None,
         report: None,
     }
 }
@@ -184,6 +192,14 @@ fn copy_counts_duplicate_source_as_ignored_exactly_once() {
             sources: vec![local(src_dir.path().to_str().unwrap())],
             output: local(out_dir.path().to_str().unwrap()),
             archive_template: None,
+            name_template: None,
+            min_rating: None,
+            label: None,
+            favorite: false,
+            catalog: None,
+            skip_known: false,
+            person: None,
+            faces_db: None,
             report: None,
         },
     )
@@ -228,6 +244,14 @@ fn copy_report_scanned_sums_all_skip_categories_exactly() {
             ],
             output: local(out_dir.path().to_str().unwrap()),
             archive_template: None,
+            name_template: None,
+            min_rating: None,
+            label: None,
+            favorite: false,
+            catalog: None,
+            skip_known: false,
+            person: None,
+            faces_db: None,
             report: None,
         },
     );
@@ -264,6 +288,12 @@ fn find_with_valid_output_dir_scans_sources() {
             secure: false,
             sources: vec![local(DATA_DIR)],
             output: Some(local(out_dir.path().to_str().unwrap())),
+            keep: Vec::new(),
+            apply: None,
+            quarantine: None,
+            similar: false,
+            similar_video: false,
+            phash_max: None,
             report: None,
         },
     )
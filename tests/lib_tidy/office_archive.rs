@@ -23,6 +23,14 @@ fn copy_archives_pdf_by_dc_creation_date() {
         sources: vec![local(src.path().to_str().unwrap())],
         output: local(out.path().to_str().unwrap()),
         archive_template: None,
+        name_template: None,
+        min_rating: None,
+        label: None,
+        favorite: false,
+        catalog: None,
+        skip_known: false,
+        person: None,
+        faces_db: None,
         report: None,
     })
     .expect("copy with --include-non-media should succeed");
@@ -50,6 +58,14 @@ fn copy_archives_docx_by_dcterms_created() {
         sources: vec![local(src.path().to_str().unwrap())],
         output: local(out.path().to_str().unwrap()),
         archive_template: None,
+        name_template: None,
+        min_rating: None,
+        label: None,
+        favorite: false,
+        catalog: None,
+        skip_known: false,
+        person: None,
+        faces_db: None,
         report: None,
     })
     .expect("copy with --include-non-media should succeed");
@@ -74,6 +90,14 @@ fn copy_archives_txt_falls_back_to_mtime_or_filename() {
         sources: vec![local(src.path().to_str().unwrap())],
         output: local(out.path().to_str().unwrap()),
         archive_template: None,
+        name_template: None,
+        min_rating: None,
+        label: None,
+        favorite: false,
+        catalog: None,
+        skip_known: false,
+        person: None,
+        faces_db: None,
         report: None,
     })
     .expect("copy with --include-non-media should succeed for txt");
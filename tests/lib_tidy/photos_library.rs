@@ -0,0 +1,153 @@
+//! Apple Photos 图库源（`*.photoslibrary`）的 dispatch 路径测试：只读命令从 originals/
+//! 导出，会删 / 改名 / 搬源文件的命令一律拒绝，不碰包内任何文件。
+
+use std::path::{Path, PathBuf};
+
+use tempfile::{TempDir, tempdir};
+use tidymedia::{Error, run_cli};
+
+use super::DATA_DIR;
+
+/// 最小图库包：`originals/A/AAAA.jpeg` 一张原图 + `resources/derivatives/` 一张缩略图。
+fn library() -> (TempDir, PathBuf) {
+    let dir = tempdir().unwrap();
+    let root = dir.path().join("Family.photoslibrary");
+    for sub in ["originals/A", "resources/derivatives", "database"] {
+        std::fs::create_dir_all(root.join(sub)).unwrap();
+    }
+    std::fs::copy(
+        format!("{DATA_DIR}/sample-no-dates.jpg"),
+        root.join("originals/A/AAAA.jpeg"),
+    )
+    .unwrap();
+    std::fs::copy(
+        format!("{DATA_DIR}/sample-with-exif.jpg"),
+        root.join("resources/derivatives/AAAA_1_105_c.jpeg"),
+    )
+    .unwrap();
+    (dir, root)
+}
+
+fn files_under(root: &Path) -> Vec<PathBuf> {
+    let mut out: Vec<PathBuf> = walk(root);
+    out.sort();
+    out
+}
+
+fn walk(dir: &Path) -> Vec<PathBuf> {
+    std::fs::read_dir(dir)
+        .unwrap()
+        .flat_map(|e| {
+            let path = e.unwrap().path();
+            if path.is_dir() { walk(&path) } else { vec![path] }
+        })
+        .collect()
+}
+
+#[test]
+fn run_cli_rejects_commands_that_modify_a_photos_library() {
+    let (_dir, root) = library();
+    let before = files_under(&root);
+    let out = tempdir().unwrap();
+    let out = out.path().to_str().unwrap();
+    let lib = root.to_str().unwrap();
+    let originals = root.join("originals");
+    let originals = originals.to_str().unwrap();
+
+    let commands: [&[&str]; 7] = [
+        &["move", "-o", out, lib],
+        // 图库内子目录同样受保护。
+        &["move", "-o", out, originals],
+        &["rename", "--name-template", "{hash8}.{ext}", lib],
+        &["find", "--apply", "delete", lib],
+        &["find", "--apply", "hardlink", originals],
+        &["cull", "-o", out, lib],
+        &["move-text-shot", "-o", out, lib],
+    ];
+    for args in commands {
+        let mut argv = vec!["tidymedia"];
+        argv.extend_from_slice(args);
+        let err = run_cli(argv).unwrap_err();
+        let Error::Io(io) = &err else {
+            panic!("{args:?}: unexpected error {err}");
+        };
+        assert_eq!(io.kind(), std::io::ErrorKind::InvalidInput, "{args:?}");
+        assert!(err.to_string().contains("Photos library"), "{args:?}: {err}");
+    }
+    assert_eq!(files_under(&root), before, "library must be left untouched");
+}
+
+/// 只读命令照常接受图库源。
+#[test]
+fn run_cli_find_without_apply_reads_a_photos_library() {
+    let (_dir, root) = library();
+    run_cli(["tidymedia", "find", root.to_str().unwrap()]).expect("find is read-only");
+}
+
+#[cfg(feature = "photos-library")]
+mod with_database {
+    use rusqlite::Connection;
+
+    use super::*;
+
+    /// 2024-05-01T06:30:00Z 的 Core Data 秒。
+    const CREATED_CORE_DATA: f64 = 736_237_800.0;
+
+    /// 给 [`library`] 的原图补一份 Photos 5+ 数据库：拍摄时间 + 时区 + 导入前原名。
+    fn write_database(root: &Path) {
+        let conn = Connection::open(root.join("database/Photos.sqlite")).unwrap();
+        conn.execute_batch(&format!(
+            "CREATE TABLE ZASSET (Z_PK INTEGER PRIMARY KEY, ZUUID TEXT, ZDIRECTORY TEXT, \
+                 ZFILENAME TEXT, ZDATECREATED REAL, ZFAVORITE INTEGER, ZKINDSUBTYPE INTEGER, \
+                 ZTRASHEDSTATE INTEGER);
+             CREATE TABLE ZADDITIONALASSETATTRIBUTES (Z_PK INTEGER PRIMARY KEY, ZASSET INTEGER, \
+                 ZTIMEZONEOFFSET INTEGER, ZORIGINALFILENAME TEXT);
+             INSERT INTO ZASSET VALUES (1, 'AAAA', 'A', 'AAAA.jpeg', {CREATED_CORE_DATA}, 1, 0, 0);
+             INSERT INTO ZADDITIONALASSETATTRIBUTES (ZASSET, ZTIMEZONEOFFSET, ZORIGINALFILENAME) \
+                 VALUES (1, 28800, 'IMG_0001.JPG');"
+        ))
+        .unwrap();
+    }
+
+    /// copy 从图库包根导出：只取 originals/ 下的原图（缩略图不归档），拍摄时间取自
+    /// 数据库（原图本身无日期），`{original_name}` 换回导入前原名；图库不被改动。
+    #[test]
+    fn run_cli_copy_exports_originals_from_a_photos_library() {
+        let (_dir, root) = library();
+        write_database(&root);
+        let before = files_under(&root);
+        let out = tempdir().unwrap();
+        let aux = tempdir().unwrap();
+        let report = aux.path().join("copy.json");
+
+        run_cli([
+            "tidymedia",
+            "copy",
+            "--favorite",
+            "--archive-template",
+            "{year}/{month}",
+            "--name-template",
+            "{original_name}_{hash8}.{ext}",
+            "--report",
+            report.to_str().unwrap(),
+            "-o",
+            out.path().to_str().unwrap(),
+            root.to_str().unwrap(),
+        ])
+        .expect("copy from a photos library should succeed");
+
+        let report: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&report).unwrap()).unwrap();
+        assert_eq!(report["copied"], 1, "{report}");
+        let copied = files_under(out.path());
+        assert_eq!(copied.len(), 1, "{copied:?}");
+        let rel = copied[0].strip_prefix(out.path()).unwrap();
+        assert_eq!(rel.parent().unwrap(), Path::new("2024/05"), "{rel:?}");
+        let name = rel.file_name().unwrap().to_str().unwrap();
+        assert!(
+            name.starts_with("IMG_0001_") && name.ends_with(".jpeg"),
+            "{name}"
+        );
+        assert_eq!(files_under(&root), before, "copy must not touch the library");
+    }
+}
@@ -73,6 +73,14 @@ fn tidy_copy_mtp_source_propagates_factory_error() {
         sources: vec![mtp_loc("DCIM")],
         output: local(out.path().to_str().unwrap()),
         archive_template: None,
+        name_template: None,
+        min_rating: None,
+        label: None,
+        favorite: false,
+        catalog: None,
+        skip_known: false,
+        person: None,
+        faces_db: None,
         report: None,
     })
     .unwrap_err();
@@ -89,6 +97,14 @@ fn tidy_copy_mtp_output_propagates_factory_error() {
         sources: vec![local(DATA_DIR)],
         output: mtp_loc("Out"),
         archive_template: None,
+        name_template: None,
+        min_rating: None,
+        label: None,
+        favorite: false,
+        catalog: None,
+        skip_known: false,
+        person: None,
+        faces_db: None,
         report: None,
     })
     .unwrap_err();
@@ -103,6 +119,12 @@ fn tidy_find_mtp_source_propagates_factory_error() {
         secure: false,
         sources: vec![mtp_loc("DCIM")],
         output: None,
+        keep: Vec::new(),
+        apply: None,
+        quarantine: None,
+        similar: false,
+        similar_video: false,
+        phash_max: None,
         report: None,
     })
     .unwrap_err();
@@ -117,6 +139,12 @@ fn tidy_find_mtp_output_propagates_factory_error() {
         secure: false,
         sources: vec![local(DATA_DIR)],
         output: Some(mtp_loc("Out")),
+        keep: Vec::new(),
+        apply: None,
+        quarantine: None,
+        similar: false,
+        similar_video: false,
+        phash_max: None,
         report: None,
     })
     .unwrap_err();
@@ -0,0 +1,75 @@
+//! `rename` 子命令的 dispatch 路径测试：就地改名 + sidecar 随行 + 还原脚本 + 报告。
+
+use tempfile::tempdir;
+use tidymedia::run_cli;
+
+use super::DATA_DIR;
+
+#[test]
+fn run_cli_rename_in_place_writes_undo_log_and_report() {
+    let src_dir = tempdir().unwrap();
+    std::fs::copy(
+        format!("{DATA_DIR}/sample-no-dates.jpg"),
+        src_dir.path().join("shot.jpg"),
+    )
+    .expect("copy fixture into tempdir");
+    std::fs::write(src_dir.path().join("shot.xmp"), "<x:xmpmeta/>").unwrap();
+    let aux = tempdir().unwrap();
+    let undo = aux.path().join("undo.py");
+    let report = aux.path().join("rename.json");
+
+    run_cli([
+        "tidymedia",
+        "rename",
+        "--name-template",
+        "{media_kind}_{original_name:upper}.{ext}",
+        "--undo-log",
+        undo.to_str().unwrap(),
+        "--report",
+        report.to_str().unwrap(),
+        src_dir.path().to_str().unwrap(),
+    ])
+    .expect("rename should succeed");
+
+    for name in ["photo_SHOT.jpg", "photo_SHOT.xmp"] {
+        assert!(src_dir.path().join(name).is_file(), "missing {name}");
+    }
+    assert!(!src_dir.path().join("shot.jpg").exists());
+
+    let script = std::fs::read_to_string(&undo).unwrap();
+    assert!(script.starts_with("#!/usr/bin/env python3"));
+    assert_eq!(script.matches("RENAMES.append(").count(), 2);
+
+    let parsed: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&report).unwrap()).unwrap();
+    assert_eq!(parsed["renamed"], 1);
+    assert_eq!(parsed["renames"].as_array().unwrap().len(), 2);
+}
+
+// dry-run：不改名，也不创建还原脚本。
+#[test]
+fn run_cli_rename_dry_run_leaves_files_and_skips_undo_log() {
+    let src_dir = tempdir().unwrap();
+    std::fs::copy(
+        format!("{DATA_DIR}/sample-no-dates.jpg"),
+        src_dir.path().join("shot.jpg"),
+    )
+    .expect("copy fixture into tempdir");
+    let aux = tempdir().unwrap();
+    let undo = aux.path().join("undo.py");
+
+    run_cli([
+        "tidymedia",
+        "rename",
+        "--dry-run",
+        "--name-template",
+        "{media_kind}.{ext}",
+        "--undo-log",
+        undo.to_str().unwrap(),
+        src_dir.path().to_str().unwrap(),
+    ])
+    .expect("dry-run rename should succeed");
+
+    assert!(src_dir.path().join("shot.jpg").is_file());
+    assert!(!undo.exists());
+}
@@ -307,7 +307,7 @@ fn binary_cull_dry_run_walks_main_flow() {
         std::fs::write(src.path().join(name), buf).unwrap();
     }
     // 非空 face model path → build_* 返 Ok 懒加载；dry-run 真跑 pick_best 才需 detector
-    // → cull pub fn 走主路径；但调 detect_faces 时 ensure_raw 触发 load_runnable 失败
+    // → cull pub fn 走主路径；但调 detect_faces 时 load_instance 触发 load_runnable 失败
     // → record_failure 计入 report.failed → tidy() 退非 0
     let cfg_dir = tempdir().unwrap();
     let cfg_path = cfg_dir.path().join("config.yaml");
@@ -0,0 +1,35 @@
+//! `stats` 子命令的 dispatch 路径测试：只读汇总 + JSON 报告。
+
+use tempfile::tempdir;
+use tidymedia::run_cli;
+
+use super::DATA_DIR;
+
+#[test]
+fn run_cli_stats_writes_report_without_touching_sources() {
+    let src_dir = tempdir().unwrap();
+    let photo = src_dir.path().join("camera.jpg");
+    std::fs::copy(format!("{DATA_DIR}/sample-with-make-model.jpg"), &photo)
+        .expect("copy fixture into tempdir");
+    std::fs::write(src_dir.path().join("notes.txt"), b"not media").unwrap();
+    let aux = tempdir().unwrap();
+    let report = aux.path().join("stats.json");
+
+    run_cli([
+        "tidymedia",
+        "stats",
+        "--report",
+        report.to_str().unwrap(),
+        src_dir.path().to_str().unwrap(),
+    ])
+    .expect("stats should succeed");
+
+    assert!(photo.is_file());
+    let parsed: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&report).unwrap()).unwrap();
+    assert_eq!(parsed["media_files"], 1);
+    assert_eq!(parsed["non_media_files"], 1);
+    assert_eq!(parsed["by_camera"][0]["key"], "TestCam TestModel");
+    assert_eq!(parsed["by_mime"][0]["key"], "image/jpeg");
+    assert_eq!(parsed["by_month"].as_array().unwrap().len(), 1);
+}
@@ -34,6 +34,14 @@ fn copy_with_chinese_src_directory_preserves_chars() {
             sources: vec![local(src_dir.to_str().unwrap())],
             output: local(out.path().to_str().unwrap()),
             archive_template: None,
+            name_template: None,
+            min_rating: None,
+            label: None,
+            favorite: false,
+            catalog: None,
+            skip_known: false,
+            person: None,
+            faces_db: None,
             report: None,
         },
     )
@@ -67,6 +75,14 @@ fn copy_preserves_chinese_filename() {
             sources: vec![local(src_dir.path().to_str().unwrap())],
             output: local(out.path().to_str().unwrap()),
             archive_template: None,
+            name_template: None,
+            min_rating: None,
+            label: None,
+            favorite: false,
+            catalog: None,
+            skip_known: false,
+            person: None,
+            faces_db: None,
             report: None,
         },
     )
@@ -105,6 +121,14 @@ fn copy_into_long_root_path_reports_failures_if_any() {
             sources: vec![local(src_dir.path().to_str().unwrap())],
             output: local(deep.to_str().unwrap()),
             archive_template: None,
+            name_template: None,
+            min_rating: None,
+            label: None,
+            favorite: false,
+            catalog: None,
+            skip_known: false,
+            person: None,
+            faces_db: None,
             report: None,
         },
     );
@@ -72,6 +72,14 @@ fn move_cmd(src: &Path, out: &Path) -> Commands {
         sources: vec![local(src.to_str().unwrap())],
         output: local(out.to_str().unwrap()),
         archive_template: None,
+        name_template: None,
+        min_rating: None,
+        label: None,
+        favorite: false,
+        catalog: None,
+        skip_known: false,
+        person: None,
+        faces_db: None,
         report: None,
     }
 }
@@ -63,3 +63,18 @@ fn p0_beats_p1_even_if_later() {
     assert_eq!(d.priority, Priority::P0);
     assert_eq!(d.utc.timestamp(), 1_700_000_200);
 }
+
+/// Apple Photos 图库数据库的 `ZDATECREATED`（带原生时区偏移）→ P0。
+#[test]
+fn photos_library_date_yields_p0() {
+    let c = epoch_to_candidate(
+        1_714_545_000,
+        Source::PhotosLibrary,
+        Some(utc_offset()),
+        false,
+    )
+    .unwrap();
+    let d = resolve(vec![c], None, None, fixed_now()).unwrap();
+    assert_eq!(d.priority, Priority::P0);
+    assert_eq!(d.source, Source::PhotosLibrary);
+}